@@ -35,6 +35,24 @@ use rand_xorshift::XorShiftRng;
         });
     }
 
+    #[bench]
+    fn bench_g1_mul_glv(b: &mut ::test::Bencher) {
+        const SAMPLES: usize = 1000;
+
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        let v: Vec<(G1Affine, Fr)> = (0..SAMPLES)
+            .map(|_| (G1::rand(&mut rng).into_affine(), Fr::rand(&mut rng)))
+            .collect();
+
+        let mut count = 0;
+        b.iter(|| {
+            let tmp = v[count].0.mul_glv(v[count].1);
+            count = (count + 1) % SAMPLES;
+            tmp
+        });
+    }
+
     #[bench]
     fn bench_g1_add_assign(b: &mut ::test::Bencher) {
         const SAMPLES: usize = 1000;
@@ -6,4 +6,6 @@ mod bls12_377;
 mod bls12_381;
 mod mnt4753;
 mod mnt6753;
+#[cfg(feature = "parallel")]
+mod msm;
 mod sw6;
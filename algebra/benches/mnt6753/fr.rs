@@ -1,4 +1,4 @@
-use algebra::UniformRand;
+use algebra::{fields::safegcd::safegcd_inverse, UniformRand};
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
 
@@ -219,6 +219,21 @@ fn bench_fr_inverse(b: &mut ::test::Bencher) {
     });
 }
 
+#[bench]
+fn bench_fr_inverse_safegcd(b: &mut ::test::Bencher) {
+    const SAMPLES: usize = 1000;
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let v: Vec<Fr> = (0..SAMPLES).map(|_| Fr::rand(&mut rng)).collect();
+
+    let mut count = 0;
+    b.iter(|| {
+        count = (count + 1) % SAMPLES;
+        safegcd_inverse(&v[count])
+    });
+}
+
 #[bench]
 fn bench_fr_negate(b: &mut ::test::Bencher) {
     const SAMPLES: usize = 1000;
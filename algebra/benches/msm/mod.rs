@@ -0,0 +1,52 @@
+// Benchmarks of the variable-base MSM at increasing input lengths, with the
+// window size chosen by `VariableBaseMSM::get_window_size` as well as with
+// windows two bits smaller and larger. The crossover points of the window
+// sizes show up where the adaptive choice changes.
+use algebra::UniformRand;
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+use algebra::{
+    curves::bls12_381::{G1Affine, G1Projective as G1},
+    fields::bls12_381::Fr,
+    msm::VariableBaseMSM,
+    BigInteger256, FpParameters, PrimeField, ProjectiveCurve,
+};
+
+fn setup(samples: usize) -> (Vec<G1Affine>, Vec<BigInteger256>) {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let g = (0..samples)
+        .map(|_| G1::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let v = (0..samples)
+        .map(|_| Fr::rand(&mut rng).into_repr())
+        .collect::<Vec<_>>();
+    (g, v)
+}
+
+fn window_size(samples: usize) -> usize {
+    let num_bits = <Fr as PrimeField>::Params::MODULUS_BITS as usize;
+    VariableBaseMSM::get_window_size(samples, num_bits)
+}
+
+macro_rules! msm_bench {
+    ($name:ident, $samples:expr, $delta:expr) => {
+        #[bench]
+        fn $name(b: &mut ::test::Bencher) {
+            let (g, v) = setup($samples);
+            let c = (window_size($samples) as isize + $delta) as usize;
+            b.iter(|| VariableBaseMSM::multi_scalar_mul_with_window_size(&g, &v, c));
+        }
+    };
+}
+
+msm_bench!(bench_msm_2_8_smaller_window, 1 << 8, -2);
+msm_bench!(bench_msm_2_8, 1 << 8, 0);
+msm_bench!(bench_msm_2_8_larger_window, 1 << 8, 2);
+msm_bench!(bench_msm_2_12_smaller_window, 1 << 12, -2);
+msm_bench!(bench_msm_2_12, 1 << 12, 0);
+msm_bench!(bench_msm_2_12_larger_window, 1 << 12, 2);
+msm_bench!(bench_msm_2_16_smaller_window, 1 << 16, -2);
+msm_bench!(bench_msm_2_16, 1 << 16, 0);
+msm_bench!(bench_msm_2_16_larger_window, 1 << 16, 2);
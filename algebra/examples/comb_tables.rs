@@ -0,0 +1,55 @@
+//! Generates the comb tables of the generator of a group, to be shipped with a
+//! service and read by `CombTable::read` instead of being computed at startup.
+//!
+//! Usage: comb_tables <group> <output file> [<teeth> <number of combs>]
+//!
+//! where <group> is one of bls12_377_g1, bls12_377_g2, bls12_381_g1,
+//! bls12_381_g2, mnt4753_g1, mnt4753_g2, mnt6753_g1 and mnt6753_g2.
+use algebra::{
+    curves::{bls12_377, bls12_381, mnt4753, mnt6753},
+    msm::{CombTable, DEFAULT_COMB_TEETH, DEFAULT_NUM_COMBS},
+    AffineCurve, ToBytes,
+};
+use std::{env, fs::File, io::{BufWriter, Write}, process, time::Instant};
+
+fn write_table<G: AffineCurve>(path: &str, teeth: usize, num_combs: usize) {
+    let start = Instant::now();
+    let table = CombTable::with_parameters(&G::prime_subgroup_generator(), teeth, num_combs);
+    println!("Computed the tables in {:?}", start.elapsed());
+
+    let mut writer = BufWriter::new(File::create(path).expect("should create the output file"));
+    table.write(&mut writer).expect("should write the tables");
+    writer.flush().expect("should write the tables");
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    if args.len() != 3 && args.len() != 5 {
+        eprintln!("Usage: {} <group> <output file> [<teeth> <number of combs>]", args[0]);
+        process::exit(1);
+    }
+    let (teeth, num_combs) = if args.len() == 5 {
+        (
+            args[3].parse().expect("the number of teeth should be an integer"),
+            args[4].parse().expect("the number of combs should be an integer"),
+        )
+    } else {
+        (DEFAULT_COMB_TEETH, DEFAULT_NUM_COMBS)
+    };
+    let path = &args[2];
+
+    match args[1].as_str() {
+        "bls12_377_g1" => write_table::<bls12_377::G1Affine>(path, teeth, num_combs),
+        "bls12_377_g2" => write_table::<bls12_377::G2Affine>(path, teeth, num_combs),
+        "bls12_381_g1" => write_table::<bls12_381::G1Affine>(path, teeth, num_combs),
+        "bls12_381_g2" => write_table::<bls12_381::G2Affine>(path, teeth, num_combs),
+        "mnt4753_g1" => write_table::<mnt4753::G1Affine>(path, teeth, num_combs),
+        "mnt4753_g2" => write_table::<mnt4753::G2Affine>(path, teeth, num_combs),
+        "mnt6753_g1" => write_table::<mnt6753::G1Affine>(path, teeth, num_combs),
+        "mnt6753_g2" => write_table::<mnt6753::G2Affine>(path, teeth, num_combs),
+        group => {
+            eprintln!("Unknown group: {}", group);
+            process::exit(1);
+        },
+    }
+}
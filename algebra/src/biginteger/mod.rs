@@ -106,8 +106,11 @@ pub trait BigInteger:
 }
 
 pub mod arithmetic {
+    use std::cmp::Ordering;
+
     /// Calculate a + b + carry, returning the sum and modifying the
     /// carry value.
+    #[cfg(not(feature = "no_u128"))]
     #[inline(always)]
     pub fn adc(a: u64, b: u64, carry: &mut u64) -> u64 {
         let tmp = u128::from(a) + u128::from(b) + u128::from(*carry);
@@ -119,6 +122,7 @@ pub mod arithmetic {
 
     /// Calculate a - b - borrow, returning the result and modifying
     /// the borrow value.
+    #[cfg(not(feature = "no_u128"))]
     #[inline(always)]
     pub(crate) fn sbb(a: u64, b: u64, borrow: &mut u64) -> u64 {
         let tmp = (1u128 << 64) + u128::from(a) - u128::from(b) - u128::from(*borrow);
@@ -130,6 +134,7 @@ pub mod arithmetic {
 
     /// Calculate a + (b * c) + carry, returning the least significant digit
     /// and setting carry to the most significant digit.
+    #[cfg(not(feature = "no_u128"))]
     #[inline(always)]
     pub fn mac_with_carry(a: u64, b: u64, c: u64, carry: &mut u64) -> u64 {
         let tmp = (u128::from(a)) + u128::from(b) * u128::from(c) + u128::from(*carry);
@@ -138,4 +143,143 @@ pub mod arithmetic {
 
         tmp as u64
     }
+
+    #[cfg(feature = "no_u128")]
+    pub use self::portable::{adc, mac_with_carry};
+
+    #[cfg(feature = "no_u128")]
+    pub(crate) use self::portable::sbb;
+
+    /// The primitives above on 64 bit integers only, for the targets and
+    /// toolchains on which 128 bit integers are missing or emulated
+    /// inconsistently. They are used instead of the `u128` ones with the
+    /// `no_u128` feature, and give the same results bit for bit.
+    pub mod portable {
+        /// Calculate a + b + carry, returning the sum and modifying the
+        /// carry value.
+        #[inline(always)]
+        pub fn adc(a: u64, b: u64, carry: &mut u64) -> u64 {
+            let (sum, carry_1) = a.overflowing_add(b);
+            let (sum, carry_2) = sum.overflowing_add(*carry);
+            *carry = u64::from(carry_1) + u64::from(carry_2);
+
+            sum
+        }
+
+        /// Calculate a - b - borrow, returning the result and modifying
+        /// the borrow value.
+        #[inline(always)]
+        pub fn sbb(a: u64, b: u64, borrow: &mut u64) -> u64 {
+            let (diff, borrow_1) = a.overflowing_sub(b);
+            let (diff, borrow_2) = diff.overflowing_sub(*borrow);
+            *borrow = if borrow_1 || borrow_2 { 1 } else { 0 };
+
+            diff
+        }
+
+        /// Calculate a + (b * c) + carry, returning the least significant digit
+        /// and setting carry to the most significant digit. The product is
+        /// computed from the 32 bit halves of b and c.
+        #[inline(always)]
+        pub fn mac_with_carry(a: u64, b: u64, c: u64, carry: &mut u64) -> u64 {
+            const LOW: u64 = 0xffff_ffff;
+            let (b_lo, b_hi) = (b & LOW, b >> 32);
+            let (c_lo, c_hi) = (c & LOW, c >> 32);
+
+            let (mid, mid_carry) = (b_lo * c_hi).overflowing_add(b_hi * c_lo);
+            let (lo, lo_carry) = (b_lo * c_lo).overflowing_add(mid << 32);
+            let hi = b_hi * c_hi + (mid >> 32) + (u64::from(mid_carry) << 32) + u64::from(lo_carry);
+
+            let (lo, carry_1) = lo.overflowing_add(a);
+            let (lo, carry_2) = lo.overflowing_add(*carry);
+            *carry = hi + u64::from(carry_1) + u64::from(carry_2);
+
+            lo
+        }
+    }
+
+    /// Returns the product of the little endian limb sequences `a` and `b`,
+    /// of `a.len() + b.len()` limbs.
+    pub(crate) fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut res = vec![0u64; a.len() + b.len()];
+        for (i, &a_i) in a.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b_j) in b.iter().enumerate() {
+                res[i + j] = mac_with_carry(res[i + j], a_i, b_j, &mut carry);
+            }
+            res[i + b.len()] = carry;
+        }
+        res
+    }
+
+    /// Computes the Barrett constant `mu = floor(2^(128 * k) / p)` of the
+    /// `k` limb modulus `p`, given `r2 = 2^(128 * k) mod p` and
+    /// `inv = -p^(-1) mod 2^64`, both of which are part of the Montgomery
+    /// parameters of the field.
+    ///
+    /// As `2^(128 * k) - r2` is a multiple of `p`, `mu` is obtained by exact
+    /// division, i.e. by multiplying with `p^(-1)` limb by limb. Since
+    /// `p >= 2^(64 * (k - 1))`, `mu` fits into `k + 1` limbs, so it suffices
+    /// to compute modulo `2^(64 * (k + 1))`.
+    #[cfg(test)]
+    pub(crate) fn barrett_mu(modulus: &[u64], r2: &[u64], inv: u64) -> Vec<u64> {
+        let k = modulus.len();
+        let limb = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+
+        // The lowest k + 1 limbs of 2^(128 * k) - r2.
+        let mut rem = vec![0u64; k + 1];
+        let mut borrow = 0;
+        for (i, r) in rem.iter_mut().enumerate() {
+            *r = sbb(0, limb(r2, i), &mut borrow);
+        }
+
+        let p_inv = inv.wrapping_neg();
+        let mut mu = vec![0u64; k + 1];
+        for (i, mu_i) in mu.iter_mut().enumerate() {
+            let q = rem[i].wrapping_mul(p_inv);
+            *mu_i = q;
+            // rem -= q * p * 2^(64 * i), modulo 2^(64 * (k + 1))
+            let mut carry = 0;
+            let mut borrow = 0;
+            for j in 0..=(k - i) {
+                let prod = mac_with_carry(0, q, limb(modulus, j), &mut carry);
+                rem[i + j] = sbb(rem[i + j], prod, &mut borrow);
+            }
+        }
+        mu
+    }
+
+    /// Reduces the `2 * k` limb integer `x` modulo the `k` limb modulus `p`,
+    /// using Barrett's algorithm (Handbook of Applied Cryptography, 14.42)
+    /// with `mu` as given by `FpParameters::BARRETT_MU`. The most significant limb of
+    /// `p` must be non-zero. Returns the `k` limbs of the remainder.
+    pub(crate) fn barrett_reduce(x: &[u64], modulus: &[u64], mu: &[u64]) -> Vec<u64> {
+        let k = modulus.len();
+        assert_eq!(x.len(), 2 * k);
+        assert_eq!(mu.len(), k + 1);
+
+        // q = floor(floor(x / 2^(64 * (k - 1))) * mu / 2^(64 * (k + 1))) is
+        // at most 2 below floor(x / p).
+        let q = mul_limbs(&x[k - 1..], mu);
+        let qp = mul_limbs(&q[k + 1..], modulus);
+
+        // r = x - q * p, computed modulo 2^(64 * (k + 1)) as r < 3p.
+        let mut r = x[..=k].to_vec();
+        let mut borrow = 0;
+        for (r_i, &qp_i) in r.iter_mut().zip(qp.iter()) {
+            *r_i = sbb(*r_i, qp_i, &mut borrow);
+        }
+
+        let geq_modulus =
+            |r: &[u64]| r[k] != 0 || r[..k].iter().rev().cmp(modulus.iter().rev()) != Ordering::Less;
+        while geq_modulus(&r) {
+            let mut borrow = 0;
+            for (r_i, &p_i) in r.iter_mut().zip(modulus.iter().chain(Some(&0))) {
+                *r_i = sbb(*r_i, p_i, &mut borrow);
+            }
+        }
+
+        r.truncate(k);
+        r
+    }
 }
@@ -103,3 +103,42 @@ fn test_biginteger832() {
     use crate::biginteger::BigInteger832 as B;
     test_biginteger(B::new([0u64; 13]));
 }
+
+#[cfg(not(feature = "no_u128"))]
+#[test]
+fn test_portable_arithmetic() {
+    use crate::biginteger::arithmetic::{self, portable};
+    use rand::Rng;
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    let edge = [0u64, 1, 2, 0xffff_ffff, 0x1_0000_0000, u64::max_value() - 1, u64::max_value()];
+    let mut inputs = vec![];
+    for &a in edge.iter() {
+        for &b in edge.iter() {
+            for &c in edge.iter() {
+                inputs.push((a, b, c));
+            }
+        }
+    }
+    inputs.extend((0..10000).map(|_| (rng.gen(), rng.gen(), rng.gen())));
+
+    for &(a, b, c) in inputs.iter() {
+        for &carry in [0u64, 1, c].iter() {
+            let (mut expected, mut found) = (carry, carry);
+            assert_eq!(arithmetic::adc(a, b, &mut expected), portable::adc(a, b, &mut found));
+            assert_eq!(expected, found);
+
+            let (mut expected, mut found) = (carry, carry);
+            assert_eq!(
+                arithmetic::mac_with_carry(a, b, c, &mut expected),
+                portable::mac_with_carry(a, b, c, &mut found)
+            );
+            assert_eq!(expected, found);
+        }
+        for &borrow in [0u64, 1].iter() {
+            let (mut expected, mut found) = (borrow, borrow);
+            assert_eq!(arithmetic::sbb(a, b, &mut expected), portable::sbb(a, b, &mut found));
+            assert_eq!(expected, found);
+        }
+    }
+}
@@ -1,13 +1,108 @@
-use crate::Error;
+use crate::{fields::DecodingMode, Error};
 
+/// Bit serialization. `write_bits` writes the most significant bit first, and
+/// the coefficients of an extension field element in increasing degree, each
+/// most significant bit first.
 pub trait ToBits {
-    /// Serialize `self` into a bit vector.
+    /// Serialize `self` into a bit vector, most significant bit first.
     fn write_bits(&self) -> Vec<bool>;
+
+    /// Same as `write_bits`.
+    fn write_bits_be(&self) -> Vec<bool> {
+        self.write_bits()
+    }
+
+    /// Serialize `self` into a bit vector, least significant bit first. The
+    /// coefficients of an extension field element are still written in
+    /// increasing degree.
+    fn write_bits_le(&self) -> Vec<bool> {
+        let mut bits = self.write_bits_be();
+        bits.reverse();
+        bits
+    }
 }
 
 pub trait FromBits: Sized {
-    /// Reads `self` from `bits`
+    /// The number of bits written by `ToBits`, or 0, as by default, if it is
+    /// not fixed, in which case the length of the inputs is not checked.
+    const NUM_BITS: usize = 0;
+
+    /// Reads `self` from `bits`, most significant bit first. Leading zeros
+    /// may be omitted. Fails on the encodings of integers greater than or
+    /// equal to the modulus, for each coefficient of an extension field
+    /// element.
     fn read_bits(bits: Vec<bool>) -> Result<Self, Error>;
+
+    /// Reads `self` from exactly `NUM_BITS` bits, most significant bit first,
+    /// handling the encodings of integers greater than or equal to the modulus
+    /// according to `mode`. In `DecodingMode::Strict`, every element has a
+    /// single accepted encoding. By default, such encodings are rejected in
+    /// both modes, as by `read_bits`.
+    fn read_bits_with_mode(bits: Vec<bool>, _mode: DecodingMode) -> Result<Self, Error> {
+        Self::read_bits_be_checked(bits)
+    }
+
+    /// Reads `self` from `bits` as written by `write_bits`, without any check,
+    /// for trusted inputs only: the result is unspecified for non canonical
+    /// encodings. By default, the checks of `read_bits` are performed anyway.
+    ///
+    /// # Panics
+    ///
+    /// If `bits` is too short or too long, or, by default, not a valid
+    /// encoding.
+    fn read_bits_unchecked(bits: Vec<bool>) -> Self {
+        Self::read_bits(bits).expect("invalid bit encoding")
+    }
+
+    /// Same as `read_bits`.
+    fn read_bits_be(bits: Vec<bool>) -> Result<Self, Error> {
+        Self::read_bits(bits)
+    }
+
+    /// Reads `self` from `bits`, least significant bit first, as written by
+    /// `write_bits_le`.
+    fn read_bits_le(mut bits: Vec<bool>) -> Result<Self, Error> {
+        bits.reverse();
+        Self::read_bits_be(bits)
+    }
+
+    /// Same as `read_bits_be`, failing unless `bits` has exactly `NUM_BITS`
+    /// bits.
+    fn read_bits_be_checked(bits: Vec<bool>) -> Result<Self, Error> {
+        if Self::NUM_BITS != 0 {
+            check_bits_len(&bits, Self::NUM_BITS)?;
+        }
+        Self::read_bits_be(bits)
+    }
+
+    /// Same as `read_bits_le`, failing unless `bits` has exactly `NUM_BITS`
+    /// bits.
+    fn read_bits_le_checked(bits: Vec<bool>) -> Result<Self, Error> {
+        if Self::NUM_BITS != 0 {
+            check_bits_len(&bits, Self::NUM_BITS)?;
+        }
+        Self::read_bits_le(bits)
+    }
+}
+
+/// Returns an error unless `bits` has exactly `expected` bits.
+pub(crate) fn check_bits_len(bits: &[bool], expected: usize) -> Result<(), Error> {
+    if bits.len() != expected {
+        return Err(Box::new(BitSerializationError::InvalidLength(expected, bits.len())));
+    }
+    Ok(())
+}
+
+/// Splits the bits of the `n` coefficients of an extension field element into
+/// `n - 1` chunks of `size` bits and the remaining bits, failing instead of
+/// panicking if there are not enough bits.
+pub(crate) fn split_coefficient_bits(bits: &[bool], size: usize, n: usize) -> Result<Vec<Vec<bool>>, Error> {
+    if bits.len() < (n - 1) * size {
+        return Err(Box::new(BitSerializationError::InvalidLength(n * size, bits.len())));
+    }
+    let mut chunks = bits[..(n - 1) * size].chunks(size).map(|c| c.to_vec()).collect::<Vec<_>>();
+    chunks.push(bits[(n - 1) * size..].to_vec());
+    Ok(chunks)
 }
 
 pub trait ToCompressedBits {
@@ -25,6 +120,7 @@ pub enum BitSerializationError {
     NotPrimeOrder,
     NotOnCurve,
     InvalidFlags,
+    InvalidLength(usize, usize),
 }
 
 impl std::fmt::Display for BitSerializationError {
@@ -35,6 +131,8 @@ impl std::fmt::Display for BitSerializationError {
             BitSerializationError::NotPrimeOrder => "point is not in the prime order subgroup".to_owned(),
             BitSerializationError::NotOnCurve => "point is not on curve".to_owned(),
             BitSerializationError::InvalidFlags => "illegal flags combination".to_owned(),
+            BitSerializationError::InvalidLength(expected, found) =>
+                format!("expected {} bits, found {}", expected, found),
         };
         write!(f, "{}", msg)
     }
@@ -45,4 +143,32 @@ impl std::error::Error for BitSerializationError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         None
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Implements only the required method of `FromBits`.
+    #[derive(Debug, PartialEq)]
+    struct Nibble(u8);
+
+    impl FromBits for Nibble {
+        fn read_bits(bits: Vec<bool>) -> Result<Self, Error> {
+            if bits.len() > 4 {
+                return Err(Box::new(BitSerializationError::InvalidLength(4, bits.len())));
+            }
+            Ok(Nibble(bits.iter().fold(0, |acc, &bit| (acc << 1) | bit as u8)))
+        }
+    }
+
+    #[test]
+    fn test_from_bits_defaults() {
+        let bits = vec![true, false, true, true];
+        assert_eq!(Nibble::read_bits_with_mode(bits.clone(), DecodingMode::Strict).unwrap(), Nibble(11));
+        assert_eq!(Nibble::read_bits_with_mode(bits.clone(), DecodingMode::ReduceModOrder).unwrap(), Nibble(11));
+        assert_eq!(Nibble::read_bits_unchecked(bits.clone()), Nibble(11));
+        assert_eq!(Nibble::read_bits_le_checked(bits).unwrap(), Nibble(13));
+        assert!(Nibble::read_bits_with_mode(vec![false; 5], DecodingMode::Strict).is_err());
+    }
+}
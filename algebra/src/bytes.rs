@@ -1,5 +1,6 @@
+use crate::curves::AffineCurve;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use std::io::{Read, Result as IoResult, Write};
+use std::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
 
 pub trait ToBytes {
     /// Serializes `self` into `writer`.
@@ -272,9 +273,96 @@ impl FromBytes for Vec<u8> {
     }
 }
 
+/// The validation performed when deserializing data, to be chosen according
+/// to where the data comes from: `Strict` for data received from the network,
+/// `Standard` or `Trusted` for data written by the application itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SerializationProfile {
+    /// Field elements are canonical, curve points are on the curve and in the
+    /// prime order subgroup.
+    Strict,
+    /// Field elements are canonical, curve points are on the curve. The
+    /// subgroup check, which costs a scalar multiplication per point, is
+    /// skipped.
+    Standard,
+    /// No check beyond the ones needed to decode the data. Field elements are
+    /// still checked to be canonical, the check being negligible, but curve
+    /// points are not checked at all.
+    Trusted,
+}
+
+impl Default for SerializationProfile {
+    fn default() -> Self {
+        SerializationProfile::Strict
+    }
+}
+
+impl SerializationProfile {
+    /// Checks `point` according to the profile.
+    pub fn check_point<G: AffineCurve>(self, point: &G) -> IoResult<()> {
+        match self {
+            SerializationProfile::Strict if !point.group_membership_test() => Err(IoError::new(
+                ErrorKind::InvalidData,
+                "point not on the curve or not in the prime order subgroup",
+            )),
+            SerializationProfile::Standard if !point.is_on_curve() => Err(IoError::new(
+                ErrorKind::InvalidData,
+                "point not on the curve",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads a point and checks it according to the profile.
+    pub fn read_point<G: AffineCurve, R: Read>(self, reader: R) -> IoResult<G> {
+        let point = G::read(reader)?;
+        self.check_point(&point)?;
+        Ok(point)
+    }
+}
+
+/// Deserialization checked according to a `SerializationProfile`.
+pub trait FromBytesWithProfile: FromBytes {
+    /// Reads `Self` from `reader`, with the checks of `profile`.
+    fn read_with_profile<R: Read>(reader: R, profile: SerializationProfile) -> IoResult<Self>;
+}
+
+/// A reader attached to a `SerializationProfile`, so that the trust boundary
+/// is set once where the reader is created rather than at each read.
+pub struct ProfiledReader<R: Read> {
+    reader:  R,
+    profile: SerializationProfile,
+}
+
+impl<R: Read> ProfiledReader<R> {
+    pub fn new(reader: R, profile: SerializationProfile) -> Self {
+        Self { reader, profile }
+    }
+
+    pub fn profile(&self) -> SerializationProfile {
+        self.profile
+    }
+
+    /// Reads a `T` with the checks of the profile of the reader.
+    pub fn read_item<T: FromBytesWithProfile>(&mut self) -> IoResult<T> {
+        T::read_with_profile(&mut self.reader, self.profile)
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Read for ProfiledReader<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        self.reader.read(buf)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::ToBytes;
+    use super::{FromBytes, FromBytesWithProfile, ProfiledReader, SerializationProfile, ToBytes};
     #[test]
     fn test_macro_empty() {
         let array: Vec<u8> = vec![];
@@ -297,4 +385,52 @@ mod test {
         actual_bytes.extend_from_slice(&array3);
         assert_eq!(bytes, actual_bytes);
     }
+
+    #[test]
+    fn test_serialization_profiles() {
+        use crate::{
+            curves::{bls12_381::G1Affine, AffineCurve},
+            fields::{bls12_381::Fq, Field},
+            UniformRand,
+        };
+        use rand::SeedableRng;
+        use rand_xorshift::XorShiftRng;
+
+        let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+        let valid = G1Affine::prime_subgroup_generator();
+        let mut off_curve = valid;
+        off_curve.y += &Fq::one();
+        let out_of_subgroup = loop {
+            if let Some(point) = G1Affine::get_point_from_x(Fq::rand(&mut rng), false) {
+                break point;
+            }
+        };
+        assert!(!out_of_subgroup.group_membership_test());
+
+        // Strict checks the subgroup, Standard only the curve equation, Trusted
+        // nothing at all
+        let off_curve_bytes = to_bytes![off_curve].unwrap();
+        assert!(G1Affine::read_with_profile(off_curve_bytes.as_slice(), SerializationProfile::Strict).is_err());
+        assert!(G1Affine::read_with_profile(off_curve_bytes.as_slice(), SerializationProfile::Standard).is_err());
+        assert_eq!(
+            G1Affine::read_with_profile(off_curve_bytes.as_slice(), SerializationProfile::Trusted).unwrap(),
+            off_curve
+        );
+        let out_of_subgroup_bytes = to_bytes![out_of_subgroup].unwrap();
+        assert!(G1Affine::read_with_profile(out_of_subgroup_bytes.as_slice(), SerializationProfile::Strict).is_err());
+        for profile in vec![SerializationProfile::Standard, SerializationProfile::Trusted] {
+            assert_eq!(
+                G1Affine::read_with_profile(out_of_subgroup_bytes.as_slice(), profile).unwrap(),
+                out_of_subgroup
+            );
+        }
+
+        // The profile is attached to the reader
+        let bytes = to_bytes![valid, off_curve].unwrap();
+        let mut reader = ProfiledReader::new(bytes.as_slice(), SerializationProfile::default());
+        assert_eq!(reader.profile(), SerializationProfile::Strict);
+        assert_eq!(reader.read_item::<G1Affine>().unwrap(), valid);
+        assert!(reader.read_item::<G1Affine>().is_err());
+        assert_eq!(G1Affine::read(to_bytes![off_curve].unwrap().as_slice()).unwrap(), off_curve);
+    }
 }
@@ -0,0 +1,130 @@
+//! Runtime discovery of what a build of the library supports, so that the
+//! orchestrators of heterogeneous fleets of provers can route jobs to the
+//! binaries able to run them. Each crate of the library exposes a
+//! `capabilities()` function, extending the report of the crates it depends on
+//! with its own features and serialization formats.
+
+/// A versioned serialization format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SerializationFormat {
+    pub name:    &'static str,
+    pub version: u32,
+}
+
+/// What a build of the library supports.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The crates of the library linked in, with their versions.
+    pub crates:                Vec<(&'static str, &'static str)>,
+    /// The curves available, by name of their module in `algebra::curves`.
+    pub curves:                Vec<&'static str>,
+    /// The features compiled in, as `crate/feature`.
+    pub features:              Vec<&'static str>,
+    /// The GPU backends available for multi-scalar multiplications and FFTs.
+    /// No GPU backend is implemented at the moment, so this is always empty.
+    pub gpu_backends:          Vec<&'static str>,
+    /// The versioned serialization formats read and written.
+    pub serialization_formats: Vec<SerializationFormat>,
+}
+
+impl Capabilities {
+    /// Returns the capabilities of a crate on top of those of its dependencies,
+    /// skipping the features already listed.
+    pub fn extend(
+        mut self,
+        krate: (&'static str, &'static str),
+        features: &[(&'static str, bool)],
+        serialization_formats: &[SerializationFormat],
+    ) -> Self {
+        if !self.crates.contains(&krate) {
+            self.crates.push(krate);
+        }
+        for &(feature, enabled) in features.iter() {
+            if enabled && !self.features.contains(&feature) {
+                self.features.push(feature);
+            }
+        }
+        for format in serialization_formats.iter() {
+            if !self.serialization_formats.contains(format) {
+                self.serialization_formats.push(*format);
+            }
+        }
+        self
+    }
+
+    pub fn supports_curve(&self, curve: &str) -> bool {
+        self.curves.iter().any(|&c| c == curve)
+    }
+
+    /// Returns `true` if `feature`, given as `crate/feature`, is compiled in.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|&f| f == feature)
+    }
+
+    /// Returns the version of the serialization format `name`, if supported.
+    pub fn serialization_version(&self, name: &str) -> Option<u32> {
+        self.serialization_formats
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| f.version)
+    }
+}
+
+/// Returns the capabilities of this crate.
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        crates: vec![],
+        curves: vec![
+            "bls12_377",
+            "bls12_381",
+            "ed25519",
+            "edwards_bls12",
+            "edwards_sw6",
+            "jubjub",
+            "mnt4753",
+            "mnt6",
+            "mnt6753",
+            "secp256k1",
+            "sw6",
+        ],
+        features: vec![],
+        gpu_backends: vec![],
+        serialization_formats: vec![],
+    }
+    .extend(
+        ("algebra", env!("CARGO_PKG_VERSION")),
+        &[
+            ("algebra/parallel", cfg!(feature = "parallel")),
+            ("algebra/fft", cfg!(feature = "fft")),
+            ("algebra/hash_to_curve", cfg!(feature = "hash_to_curve")),
+            ("algebra/no_u128", cfg!(feature = "no_u128")),
+            ("algebra/mmap", cfg!(feature = "mmap")),
+        ],
+        &[],
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = capabilities();
+        assert!(capabilities.supports_curve("bls12_381"));
+        assert!(!capabilities.supports_curve("bn254"));
+        assert_eq!(capabilities.has_feature("algebra/parallel"), cfg!(feature = "parallel"));
+        assert!(capabilities.gpu_backends.is_empty());
+
+        let format = SerializationFormat { name: "format", version: 2 };
+        let extended = capabilities
+            .extend(("other", "0.1.0"), &[("other/a", true), ("other/b", false)], &[format])
+            .extend(("other", "0.1.0"), &[("other/a", true)], &[format]);
+        assert_eq!(extended.crates.len(), 2);
+        assert!(extended.has_feature("other/a"));
+        assert!(!extended.has_feature("other/b"));
+        assert_eq!(extended.serialization_formats, vec![format]);
+        assert_eq!(extended.serialization_version("format"), Some(2));
+        assert_eq!(extended.serialization_version("unknown"), None);
+    }
+}
@@ -1,7 +1,11 @@
 use crate::field_new;
 use crate::{
     biginteger::{BigInteger256, BigInteger384},
-    curves::models::{ModelParameters, SWModelParameters},
+    curves::{
+        bls12::g1::is_in_g1,
+        bls12_377::Bls12_377Parameters,
+        models::{GLVParameters, ModelParameters, SWAffinePoint, SWModelParameters},
+    },
     fields::{
         bls12_377::{Fq, Fr},
         Field,
@@ -50,6 +54,73 @@ impl SWModelParameters for Bls12_377G1Parameters {
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
     }
+
+    /// Checks membership in G1 with φ(p) = [λ]p.
+    #[inline]
+    fn is_in_correct_subgroup_assuming_on_curve<G: SWAffinePoint<Self>>(p: &G) -> bool {
+        is_in_g1::<Bls12_377Parameters, G>(p)
+    }
+}
+
+impl GLVParameters for Bls12_377G1Parameters {
+    /// ENDO_COEFF = 80949648264912719408558363140637477264845294720710499478137287262712535938301461879813459410945
+    const ENDO_COEFF: Fq = field_new!(Fq, BigInteger384([
+        0xdacd106da5847973,
+        0xd8fe2454bac2a79a,
+        0x1ada4fd6fd832edc,
+        0xfb9868449d150908,
+        0xd63eb8aeea32285e,
+        0x167d6a36f873fd0,
+    ]));
+
+    /// LAMBDA = 91893752504881257701523279626832445440
+    const LAMBDA: Fr = field_new!(Fr, BigInteger256([
+        0xae8012cd506fe7e2,
+        0x8adb5f3b1ec9d536,
+        0x15b65fe3a66fe319,
+        0x91f331343200452,
+    ]));
+
+    /// SCALAR_DECOMP_BASIS = ((91893752504881257701523279626832445440, -1),
+    ///     (1, 91893752504881257701523279626832445441))
+    const SCALAR_DECOMP_BASIS: [(Fr, Fr); 2] = [
+        (
+            field_new!(Fr, BigInteger256([
+                0xae8012cd506fe7e2,
+                0x8adb5f3b1ec9d536,
+                0x15b65fe3a66fe319,
+                0x91f331343200452,
+            ])),
+            field_new!(Fr, BigInteger256([
+                0x8cf500000000000e,
+                0xe75281ef6000000e,
+                0x49dc37a90b0ba012,
+                0x55f8b2c6e710ab9,
+            ])),
+        ),
+        (
+            field_new!(Fr, BigInteger256([
+                0x7d1c7ffffffffff3,
+                0x7257f50f6ffffff2,
+                0x16d81575512c0fee,
+                0xd4bda322bbb9a9d,
+            ])),
+            field_new!(Fr, BigInteger256([
+                0x218b12cd506fe7d4,
+                0xa388dd4bbec9d528,
+                0xcbda283a9b644306,
+                0x3bfa7e6d4aef998,
+            ])),
+        ),
+    ];
+
+    /// SCALAR_DECOMP_COEFFS = (b2·2^384 / r, -b1·2^384 / r)
+    const SCALAR_DECOMP_COEFFS: [&'static [u64]; 2] = [
+        &[0x5cc5a03b7b820cf6, 0x3366fc876f25c6b5, 0x7f72ed32af90182c, 0xb3f7aa969fd37160, 0x3],
+        &[0x3947927eaa01523f, 0xb65247b102cb27b9, 0xd],
+    ];
+
+    const SCALAR_DECOMP_SHIFT: usize = 6;
 }
 
 /// G1_GENERATOR_X =
@@ -1,8 +1,11 @@
 use crate::field_new;
-use super::g1::Bls12_377G1Parameters;
+use super::{g1::Bls12_377G1Parameters, Bls12_377Parameters};
 use crate::{
     biginteger::{BigInteger256, BigInteger384},
-    curves::models::{ModelParameters, SWModelParameters},
+    curves::{
+        bls12::g2::{is_in_g2, scale_by_cofactor_g2},
+        models::{ModelParameters, SWAffinePoint, SWModelParameters},
+    },
     fields::{
         bls12_377::{Fq, Fq2, Fr},
         Field,
@@ -72,6 +75,19 @@ impl SWModelParameters for Bls12_377G2Parameters {
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
     }
+
+    /// Clears the cofactor with the endomorphism based map of RFC 9380,
+    /// Appendix G.3, which multiplies by a multiple of the cofactor.
+    #[inline]
+    fn scale_by_cofactor_fast<G: SWAffinePoint<Self>>(p: &G) -> G::Projective {
+        scale_by_cofactor_g2::<Bls12_377Parameters, G>(p)
+    }
+
+    /// Checks membership in G2 with ψ(p) = [x]p.
+    #[inline]
+    fn is_in_correct_subgroup_assuming_on_curve<G: SWAffinePoint<Self>>(p: &G) -> bool {
+        is_in_g2::<Bls12_377Parameters, G>(p)
+    }
 }
 
 pub const G2_GENERATOR_X: Fq2 = field_new!(Fq2, G2_GENERATOR_X_C0, G2_GENERATOR_X_C1);
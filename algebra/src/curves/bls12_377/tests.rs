@@ -1,11 +1,14 @@
 #![allow(unused_imports)]
 use crate::{
+    biginteger::BigInteger256,
     curves::{
+        bls12::{g2::psi, Bls12Parameters},
         bls12_377::{
-            g1::Bls12_377G1Parameters, Bls12_377, G1Affine, G1Projective, G2Affine, G2Projective,
+            g1::Bls12_377G1Parameters, Bls12_377, Bls12_377Parameters, G1Affine, G1Projective,
+            G2Affine, G2Projective,
         },
-        models::SWModelParameters,
-        tests::curve_tests,
+        models::{scale_by_cofactor_bits, GLVParameters, SWModelParameters},
+        tests::{curve_tests, multi_pairing_test},
         AffineCurve, PairingEngine, ProjectiveCurve,
     },
     fields::{
@@ -13,6 +16,7 @@ use crate::{
         Field, FpParameters, PrimeField, SquareRootField,
     },
     groups::tests::group_test,
+    kat::{curve_answers, field_answers, pairing_answers, KnownAnswers},
 };
 use std::ops::{AddAssign, MulAssign};
 
@@ -35,6 +39,36 @@ fn test_g1_generator() {
     assert!(generator.is_in_correct_subgroup_assuming_on_curve());
 }
 
+#[test]
+fn test_g1_glv() {
+    let g = G1Affine::prime_subgroup_generator();
+    assert_eq!(g.endomorphism(), g.mul(Bls12_377G1Parameters::LAMBDA).into_affine());
+    assert!(g.mul_glv(Fr::zero()).is_zero());
+
+    for _ in 0..10 {
+        let p = rand::random::<G1Projective>().into_affine();
+        let s: Fr = rand::random();
+        assert_eq!(p.mul_glv(s), p.mul(s));
+    }
+}
+
+#[test]
+fn test_g1_subgroup_check() {
+    // Random points of the curve are almost never in G1
+    let mut num_points = 0;
+    while num_points < 10 {
+        if let Some(p) = G1Affine::get_point_from_x(rand::random::<Fq>(), false) {
+            let in_g1 = scale_by_cofactor_bits(&p, Fr::characteristic()).is_zero();
+            assert_eq!(p.is_in_correct_subgroup_assuming_on_curve(), in_g1);
+            assert!(p
+                .scale_by_cofactor()
+                .into_affine()
+                .is_in_correct_subgroup_assuming_on_curve());
+            num_points += 1;
+        }
+    }
+}
+
 #[test]
 fn test_g2_projective_curve() {
     curve_tests::<G2Projective>();
@@ -54,6 +88,45 @@ fn test_g2_generator() {
     assert!(generator.is_in_correct_subgroup_assuming_on_curve());
 }
 
+#[test]
+fn test_g2_scale_by_cofactor_fast() {
+    // psi acts on G2 as the multiplication by p, which is x modulo r
+    let mut x = Fr::from_repr(BigInteger256::from(Bls12_377Parameters::X[0]));
+    if Bls12_377Parameters::X_IS_NEGATIVE {
+        x = -x;
+    }
+    let p = rand::random::<G2Projective>().into_affine();
+    assert_eq!(psi::<Bls12_377Parameters, _>(&p), p.mul(x).into_affine());
+
+    // Points of the twist are mapped to G2
+    let mut num_points = 0;
+    while num_points < 10 {
+        if let Some(p) = G2Affine::get_point_from_x(rand::random::<Fq2>(), false) {
+            let q = p.scale_by_cofactor_fast().into_affine();
+            assert!(q.is_on_curve());
+            assert!(q.is_in_correct_subgroup_assuming_on_curve());
+            num_points += 1;
+        }
+    }
+}
+
+#[test]
+fn test_g2_subgroup_check() {
+    // Random points of the twist are almost never in G2
+    let mut num_points = 0;
+    while num_points < 10 {
+        if let Some(p) = G2Affine::get_point_from_x(rand::random::<Fq2>(), false) {
+            let in_g2 = scale_by_cofactor_bits(&p, Fr::characteristic()).is_zero();
+            assert_eq!(p.is_in_correct_subgroup_assuming_on_curve(), in_g2);
+            assert!(p
+                .scale_by_cofactor()
+                .into_affine()
+                .is_in_correct_subgroup_assuming_on_curve());
+            num_points += 1;
+        }
+    }
+}
+
 //    #[test]
 //    fn test_bilinearity() {
 //        let a: G1Projective = rand::random();
@@ -105,6 +178,11 @@ fn test_bilinearity() {
     assert_eq!(ans2.pow(Fr::characteristic()), Fq12::one());
 }
 
+#[test]
+fn test_multi_pairing() {
+    multi_pairing_test::<Bls12_377>();
+}
+
 #[test]
 fn test_g1_generator_raw() {
     let mut x = Fq::zero();
@@ -144,3 +222,14 @@ fn bls12_377_unique() {
     use std::str::FromStr;
     println!("{}", Fq::from_str("155198655607781456406391640216936120121836107652948796323930557600032281009004493664981332883744016074664192874906").unwrap());
 }
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<G1Affine>(&mut answers, "g1");
+    curve_answers::<G2Affine>(&mut answers, "g2");
+    pairing_answers::<Bls12_377>(&mut answers, "pairing");
+    answers.check("src/curves/bls12_377/test_vec/bls12_377_kat");
+}
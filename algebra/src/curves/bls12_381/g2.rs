@@ -2,12 +2,15 @@ use crate::field_new;
 use crate::{
     biginteger::{BigInteger256, BigInteger384},
     curves::{
-        bls12::{G2Affine as Bls12G2Affine, G2Prepared, G2Projective as Bls12G2Projective},
+        bls12::{
+            g2::{is_in_g2, scale_by_cofactor_g2}, G2Affine as Bls12G2Affine, G2Prepared,
+            G2Projective as Bls12G2Projective,
+        },
         bls12_381::{
             g1::{Bls12_381G1Parameters, G1Affine},
             Bls12_381, Bls12_381Parameters,
         },
-        models::{ModelParameters, SWModelParameters},
+        models::{ModelParameters, SWAffinePoint, SWModelParameters},
         PairingCurve, PairingEngine,
     },
     fields::{
@@ -86,6 +89,19 @@ impl SWModelParameters for Bls12_381G2Parameters {
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
     }
+
+    /// Clears the cofactor with the endomorphism based map of RFC 9380,
+    /// Appendix G.3, which multiplies by a multiple of the cofactor.
+    #[inline]
+    fn scale_by_cofactor_fast<G: SWAffinePoint<Self>>(p: &G) -> G::Projective {
+        scale_by_cofactor_g2::<Bls12_381Parameters, G>(p)
+    }
+
+    /// Checks membership in G2 with ψ(p) = [x]p.
+    #[inline]
+    fn is_in_correct_subgroup_assuming_on_curve<G: SWAffinePoint<Self>>(p: &G) -> bool {
+        is_in_g2::<Bls12_381Parameters, G>(p)
+    }
 }
 
 pub const G2_GENERATOR_X: Fq2 = field_new!(Fq2, G2_GENERATOR_X_C0, G2_GENERATOR_X_C1);
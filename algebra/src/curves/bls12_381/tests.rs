@@ -1,13 +1,14 @@
 use crate::{
-    biginteger::BigInteger384,
+    biginteger::{BigInteger256, BigInteger384},
     curves::{
         bls12_381::{
             g1::{Bls12_381G1Parameters, G1Affine, G1Projective},
             g2::{Bls12_381G2Parameters, G2Affine, G2Projective},
-            Bls12_381,
+            Bls12_381, Bls12_381Parameters,
         },
-        models::SWModelParameters,
-        tests::curve_tests,
+        bls12::{g2::psi, Bls12Parameters},
+        models::{scale_by_cofactor_bits, GLVParameters, SWModelParameters},
+        tests::{curve_tests, multi_pairing_test},
         AffineCurve, PairingEngine, ProjectiveCurve,
     },
     fields::{
@@ -15,6 +16,7 @@ use crate::{
         Field, PrimeField, SquareRootField,
     },
     groups::tests::group_test,
+    kat::{curve_answers, field_answers, pairing_answers, KnownAnswers},
 };
 use rand;
 use std::ops::{AddAssign, MulAssign};
@@ -38,6 +40,36 @@ fn test_g1_generator() {
     assert!(generator.is_in_correct_subgroup_assuming_on_curve());
 }
 
+#[test]
+fn test_g1_glv() {
+    let g = G1Affine::prime_subgroup_generator();
+    assert_eq!(g.endomorphism(), g.mul(Bls12_381G1Parameters::LAMBDA).into_affine());
+    assert!(g.mul_glv(Fr::zero()).is_zero());
+
+    for _ in 0..10 {
+        let p = rand::random::<G1Projective>().into_affine();
+        let s: Fr = rand::random();
+        assert_eq!(p.mul_glv(s), p.mul(s));
+    }
+}
+
+#[test]
+fn test_g1_subgroup_check() {
+    // Random points of the curve are almost never in G1
+    let mut num_points = 0;
+    while num_points < 10 {
+        if let Some(p) = G1Affine::get_point_from_x(rand::random::<Fq>(), false) {
+            let in_g1 = scale_by_cofactor_bits(&p, Fr::characteristic()).is_zero();
+            assert_eq!(p.is_in_correct_subgroup_assuming_on_curve(), in_g1);
+            assert!(p
+                .scale_by_cofactor()
+                .into_affine()
+                .is_in_correct_subgroup_assuming_on_curve());
+            num_points += 1;
+        }
+    }
+}
+
 #[test]
 fn test_g2_projective_curve() {
     curve_tests::<G2Projective>();
@@ -57,6 +89,45 @@ fn test_g2_generator() {
     assert!(generator.is_in_correct_subgroup_assuming_on_curve());
 }
 
+#[test]
+fn test_g2_scale_by_cofactor_fast() {
+    // psi acts on G2 as the multiplication by p, which is x modulo r
+    let mut x = Fr::from_repr(BigInteger256::from(Bls12_381Parameters::X[0]));
+    if Bls12_381Parameters::X_IS_NEGATIVE {
+        x = -x;
+    }
+    let p = rand::random::<G2Projective>().into_affine();
+    assert_eq!(psi::<Bls12_381Parameters, _>(&p), p.mul(x).into_affine());
+
+    // Points of the twist are mapped to G2
+    let mut num_points = 0;
+    while num_points < 10 {
+        if let Some(p) = G2Affine::get_point_from_x(rand::random::<Fq2>(), false) {
+            let q = p.scale_by_cofactor_fast().into_affine();
+            assert!(q.is_on_curve());
+            assert!(q.is_in_correct_subgroup_assuming_on_curve());
+            num_points += 1;
+        }
+    }
+}
+
+#[test]
+fn test_g2_subgroup_check() {
+    // Random points of the twist are almost never in G2
+    let mut num_points = 0;
+    while num_points < 10 {
+        if let Some(p) = G2Affine::get_point_from_x(rand::random::<Fq2>(), false) {
+            let in_g2 = scale_by_cofactor_bits(&p, Fr::characteristic()).is_zero();
+            assert_eq!(p.is_in_correct_subgroup_assuming_on_curve(), in_g2);
+            assert!(p
+                .scale_by_cofactor()
+                .into_affine()
+                .is_in_correct_subgroup_assuming_on_curve());
+            num_points += 1;
+        }
+    }
+}
+
 #[test]
 fn test_bilinearity() {
     let a: G1Projective = rand::random();
@@ -82,6 +153,11 @@ fn test_bilinearity() {
     assert_eq!(ans3.pow(Fr::characteristic()), Fq12::one());
 }
 
+#[test]
+fn test_multi_pairing() {
+    multi_pairing_test::<Bls12_381>();
+}
+
 #[test]
 fn test_g1_generator_raw() {
     let mut x = Fq::zero();
@@ -470,7 +546,9 @@ fn test_g2_is_valid() {
             false,
         );
         assert!(!p.is_on_curve());
-        assert!(p.is_in_correct_subgroup_assuming_on_curve());
+        // The subgroup check uses the endomorphism ψ of the curve, hence says
+        // nothing about points off the curve: only the on-curve check rejects it
+        assert!(!p.group_membership_test());
     }
 
     // Reject point on a twist (b = 2 * (u + 1))
@@ -782,3 +860,14 @@ fn test_g2_doubling_correctness() {
         )
     );
 }
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<G1Affine>(&mut answers, "g1");
+    curve_answers::<G2Affine>(&mut answers, "g2");
+    pairing_answers::<Bls12_381>(&mut answers, "pairing");
+    answers.check("src/curves/bls12_381/test_vec/bls12_381_kat");
+}
@@ -0,0 +1,161 @@
+use crate::field_new;
+use crate::{
+    biginteger::BigInteger256,
+    curves::{
+        models::{
+            Elligator2Parameters, ModelParameters, MontgomeryModelParameters, TEModelParameters,
+        },
+        twisted_edwards_extended::{GroupAffine, GroupProjective},
+    },
+    fields::ed25519::{fq::Fq, fr::Fr},
+};
+use std::str::FromStr;
+
+#[cfg(test)]
+mod tests;
+
+pub type Ed25519Affine = GroupAffine<Ed25519Parameters>;
+pub type Ed25519Projective = GroupProjective<Ed25519Parameters>;
+
+const GENERATOR_X: Fq = field_new!(Fq, BigInteger256([
+    0xe2cabc553f9da287,
+    0x9ca598562396e489,
+    0x9879936bade4b5b7,
+    0x759e23707e6077d0,
+]));
+const GENERATOR_Y: Fq = field_new!(Fq, BigInteger256([
+    0x333333333333334a,
+    0x3333333333333333,
+    0x3333333333333333,
+    0x3333333333333333,
+]));
+
+/// `Ed25519` is the twisted Edwards curve birationally equivalent to
+/// Curve25519, as specified in RFC 8032. These curves have equations of the
+/// form: ax² + y² = 1 + dx²y².
+/// over some base finite field Fq.
+///
+/// Ed25519's curve equation: -x² + y² = 1 - (121665/121666)x²y²
+///
+/// q = 2^255 - 19
+///   = 57896044618658097711785492504343953926634992332820282019728792003956564819949.
+///
+/// a = -1.
+/// d = -(121665/121666) mod q
+///   = 37095705934669439343138083508754565189542113879843219016388785533085940283555.
+///
+/// The base point B = (x, 4/5) generates the subgroup of prime order
+/// r = 2^252 + 27742317777372353535851937790883648493.
+///
+/// These parameters are obtained from:
+/// <https://tools.ietf.org/html/rfc8032#section-5.1>
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct Ed25519Parameters;
+
+impl ModelParameters for Ed25519Parameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl TEModelParameters for Ed25519Parameters {
+    /// COEFF_A = -1
+    const COEFF_A: Fq = field_new!(Fq, BigInteger256([
+        0xffffffffffffffc7,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ]));
+
+    /// COEFF_D = -(121665/121666) mod q
+    const COEFF_D: Fq = field_new!(Fq, BigInteger256([
+        0x80ed8bfedf47e9fa,
+        0x10a18777afc62973,
+        0xe5939207bc188690,
+        0x2c822b5a729fc526,
+    ]));
+
+    /// COFACTOR = 8
+    const COFACTOR: &'static [u64] = &[8];
+
+    /// COFACTOR^(-1) mod r =
+    /// 2713877091499598330239944961141122840321418634767465352250731601857045344121
+    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger256([
+        0xa7ed9ce5a30a2c13,
+        0xeb2106215d086329,
+        0xffffffffffffffff,
+        0xfffffffffffffff,
+    ]));
+
+    /// AFFINE_GENERATOR_COEFFS = (GENERATOR_X, GENERATOR_Y)
+    const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) = (GENERATOR_X, GENERATOR_Y);
+
+    type MontgomeryModelParameters = Ed25519Parameters;
+
+    /// Multiplication by `a` is simply negation here.
+    #[inline(always)]
+    fn mul_by_a(elem: &Self::BaseField) -> Self::BaseField {
+        -(*elem)
+    }
+}
+
+impl MontgomeryModelParameters for Ed25519Parameters {
+    /// COEFF_A = 486662
+    const COEFF_A: Fq = field_new!(Fq, BigInteger256([
+        0x11a2ee4,
+        0x0,
+        0x0,
+        0x0,
+    ]));
+    /// COEFF_B = 4/(a - d) = -486664
+    const COEFF_B: Fq = field_new!(Fq, BigInteger256([
+        0xfffffffffee5d0bd,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ]));
+
+    type TEModelParameters = Ed25519Parameters;
+}
+
+impl Elligator2Parameters for Ed25519Parameters {
+    /// ELLIGATOR2_Z = 2
+    const ELLIGATOR2_Z: Fq = field_new!(Fq, BigInteger256([
+        0x4c,
+        0x0,
+        0x0,
+        0x0,
+    ]));
+}
+
+impl FromStr for Ed25519Affine {
+    type Err = ();
+
+    fn from_str(mut s: &str) -> Result<Self, Self::Err> {
+        s = s.trim();
+        if s.is_empty() {
+            return Err(());
+        }
+        if s.len() < 3 {
+            return Err(());
+        }
+        if !(s.starts_with('(') && s.ends_with(')')) {
+            return Err(());
+        }
+        let mut point = Vec::new();
+        for substr in s.split(|c| c == '(' || c == ')' || c == ',' || c == ' ') {
+            if !substr.is_empty() {
+                point.push(Fq::from_str(substr)?);
+            }
+        }
+        if point.len() != 2 {
+            return Err(());
+        }
+        let point = Ed25519Affine::new(point[0], point[1]);
+
+        if !point.is_on_curve() {
+            Err(())
+        } else {
+            Ok(point)
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    bytes::{FromBytes, ToBytes},
+    curves::{ed25519::*, tests::curve_tests, AffineCurve, ProjectiveCurve, models::twisted_edwards_extended::tests::montgomery_conversion_test},
+    fields::ed25519::{fq::Fq, fr::Fr},
+    groups::tests::group_test,
+    kat::{curve_answers, field_answers, KnownAnswers},
+};
+use rand;
+use std::str::FromStr;
+
+#[test]
+fn test_projective_curve() {
+    curve_tests::<Ed25519Projective>();
+}
+
+#[test]
+fn test_projective_group() {
+    let a = rand::random();
+    let b = rand::random();
+    for _i in 0..100 {
+        group_test::<Ed25519Projective>(a, b);
+    }
+}
+
+#[test]
+fn test_affine_group() {
+    let a: Ed25519Affine = rand::random();
+    let b: Ed25519Affine = rand::random();
+    for _i in 0..100 {
+        group_test::<Ed25519Affine>(a, b);
+    }
+}
+
+#[test]
+fn test_generator() {
+    let generator = Ed25519Affine::prime_subgroup_generator();
+    assert!(generator.is_on_curve());
+    assert!(generator.is_in_correct_subgroup_assuming_on_curve());
+}
+
+#[test]
+fn test_conversion() {
+    let a: Ed25519Affine = rand::random();
+    let b: Ed25519Affine = rand::random();
+    let a_b = {
+        use crate::groups::Group;
+        (a + &b).double().double()
+    };
+    let a_b2 = (a.into_projective() + &b.into_projective())
+        .double()
+        .double();
+    assert_eq!(a_b, a_b2.into_affine());
+    assert_eq!(a_b.into_projective(), a_b2);
+}
+
+#[test]
+fn test_scalar_multiplication() {
+    let f1 = Fr::from_str(
+        "4689493967052314197864314149577630052494407961708524131501414954084887121376",
+    )
+    .unwrap();
+    let f2 = Fr::from_str(
+        "6409303174114863069752062893003883627972984731370645419590313236855747063656",
+    )
+    .unwrap();
+
+    let g = Ed25519Affine::prime_subgroup_generator();
+    let f1g = Ed25519Affine::from_str(
+        "(32849105345033251953536920815800981788826225620084077625599867211825474782020, \
+         34187291482411641204952124181322277310194937238302140213738703643676530864648)",
+    )
+    .unwrap();
+    let f1f2g = Ed25519Affine::from_str(
+        "(12611222428030949062430971131312942609273670172433145838763367718366388175886, \
+         40383025048892652323057174219942951740144256525567174466712707017559817695173)",
+    )
+    .unwrap();
+
+    assert!(!f1g.is_zero());
+    assert!(!f1f2g.is_zero());
+
+    assert_eq!(g * &f1, f1g);
+    assert_eq!(g * &(f1 * &f2), f1f2g);
+    assert_eq!(f1g * &f2, f1f2g);
+}
+
+#[test]
+fn test_bytes() {
+    let g_from_repr = Ed25519Affine::prime_subgroup_generator();
+
+    let g_bytes = to_bytes![g_from_repr].unwrap();
+    let g = Ed25519Affine::read(g_bytes.as_slice()).unwrap();
+    assert_eq!(g_from_repr, g);
+}
+
+#[test]
+fn test_montgomery_conversion() {
+    montgomery_conversion_test::<Ed25519Parameters>();
+}
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<Ed25519Affine>(&mut answers, "group");
+    answers.check("src/curves/ed25519/test_vec/ed25519_kat");
+}
@@ -1,6 +1,8 @@
 use crate::{
     curves::{edwards_bls12::*, tests::curve_tests, AffineCurve, ProjectiveCurve, models::twisted_edwards_extended::tests::montgomery_conversion_test},
+    fields::edwards_bls12::{fq::Fq, fr::Fr},
     groups::tests::group_test,
+    kat::{curve_answers, field_answers, KnownAnswers},
 };
 use rand;
 
@@ -53,3 +55,12 @@ fn test_conversion() {
 fn test_montgomery_conversion() {
     montgomery_conversion_test::<EdwardsParameters>();
 }
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<EdwardsAffine>(&mut answers, "group");
+    answers.check("src/curves/edwards_bls12/test_vec/edwards_bls12_kat");
+}
@@ -2,7 +2,9 @@ use crate::field_new;
 use crate::{
     biginteger::BigInteger256,
     curves::{
-        models::{ModelParameters, TEModelParameters, MontgomeryModelParameters},
+        models::{
+            Elligator2Parameters, ModelParameters, MontgomeryModelParameters, TEModelParameters,
+        },
         twisted_edwards_extended::{GroupAffine, GroupProjective},
     },
     fields::jubjub::{fq::Fq, fr::Fr},
@@ -117,6 +119,16 @@ impl MontgomeryModelParameters for JubJubParameters {
     type TEModelParameters = JubJubParameters;
 }
 
+impl Elligator2Parameters for JubJubParameters {
+    /// ELLIGATOR2_Z = 5
+    const ELLIGATOR2_Z: Fq = field_new!(Fq, BigInteger256([
+        47244640245u64,
+        7411222800919301131u64,
+        14736824336091166661u64,
+        345034914438815205u64,
+    ]));
+}
+
 impl FromStr for JubJubAffine {
     type Err = ();
 
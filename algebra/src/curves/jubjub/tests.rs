@@ -1,8 +1,9 @@
 use crate::{
     bytes::{FromBytes, ToBytes},
     curves::{jubjub::*, tests::curve_tests, AffineCurve, ProjectiveCurve, models::twisted_edwards_extended::tests::montgomery_conversion_test},
-    fields::jubjub::fr::Fr,
+    fields::{jubjub::{fq::Fq, fr::Fr}, Field},
     groups::tests::group_test,
+    kat::{curve_answers, field_answers, KnownAnswers},
 };
 use rand;
 use std::str::FromStr;
@@ -30,6 +31,14 @@ fn test_affine_group() {
     }
 }
 
+#[test]
+fn test_compressed_point_of_small_order() {
+    // (0, -1) has order two, it is on the curve but not in the prime order subgroup
+    let point = JubJubAffine::new(Fq::zero(), -Fq::one());
+    assert!(point.is_on_curve());
+    assert!(JubJubAffine::from_compressed(&point.to_compressed()).is_err());
+}
+
 #[test]
 fn test_generator() {
     let generator = JubJubAffine::prime_subgroup_generator();
@@ -107,3 +116,12 @@ fn test_bytes() {
 fn test_montgomery_conversion() {
     montgomery_conversion_test::<JubJubParameters>();
 }
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<JubJubAffine>(&mut answers, "group");
+    answers.check("src/curves/jubjub/test_vec/jubjub_kat");
+}
@@ -2,7 +2,7 @@ use crate::field_new;
 use crate::{
     biginteger::BigInteger768,
     curves::{
-        models::{ModelParameters, SWModelParameters},
+        models::{ModelParameters, SWModelParameters, SWUParameters},
     },
     fields::mnt4753::{Fq, Fr},
 };
@@ -73,6 +73,24 @@ impl SWModelParameters for MNT4G1Parameters {
         (G1_GENERATOR_X, G1_GENERATOR_Y);
 }
 
+impl SWUParameters for MNT4G1Parameters {
+    // Z = 13, in Montgomery representation
+    const SWU_Z: Fq = field_new!(Fq, BigInteger768([
+        11881297496860141143,
+        13588356353764843511,
+        9969398190777826186,
+        17325157081734070311,
+        16341533986183788031,
+        8322434028726676858,
+        13631157743146294957,
+        8365783422740577875,
+        3010239015809771096,
+        11776256826687733591,
+        7214251687253691272,
+        268626707558702,
+    ]));
+}
+
 // generator of prime order r
 // x = 7790163481385331313124631546957228376128961350185262705123068027727518350362064426002432450801002268747950550964579198552865939244360469674540925037890082678099826733417900510086646711680891516503232107232083181010099241949569
 // in Montgomery rep.
@@ -3,11 +3,11 @@ use crate::{curves::{
         G1Affine, G1Projective, G2Affine, G2Projective,
         MNT4,
     },
-    tests::curve_tests,
+    tests::{curve_tests, multi_pairing_test},
     AffineCurve, PairingEngine,
 }, biginteger::BigInteger768, fields::mnt4753::{fq::Fq, fq2::Fq2, fq4::Fq4, fr::Fr}, groups::tests::{
     group_test, compression_test, gt_compression_test
-}, ProjectiveCurve, Field, PrimeField, ToBits, FromCompressedBits};
+}, kat::{curve_answers, field_answers, pairing_answers, KnownAnswers}, ProjectiveCurve, Field, PrimeField, ToBits, FromCompressedBits};
 use rand;
 use std::ops::AddAssign;
 
@@ -489,6 +489,11 @@ fn test_bilinearity() {
     assert_eq!(ans3.pow(Fr::characteristic()), Fq4::one());
 }
 
+#[test]
+fn test_multi_pairing() {
+    multi_pairing_test::<MNT4>();
+}
+
 #[test]
 fn test_gt_compression(){
     let even = Fq4::new(
@@ -1594,4 +1599,15 @@ fn test_g2_affine_projective_conversion() {
     ));
 
     assert_eq!(a_a.into_projective(), a);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<G1Affine>(&mut answers, "g1");
+    curve_answers::<G2Affine>(&mut answers, "g2");
+    pairing_answers::<MNT4>(&mut answers, "pairing");
+    answers.check("src/curves/mnt4753/test_vec/mnt4753_kat");
+}
@@ -4,8 +4,9 @@ use crate::{
         tests::curve_tests,
         AffineCurve, PairingEngine,
     },
-    fields::mnt6::fr::Fr,
+    fields::mnt6::{fq::Fq, fr::Fr},
     groups::tests::group_test,
+    kat::{curve_answers, field_answers, pairing_answers, KnownAnswers},
 };
 use rand;
 
@@ -90,3 +91,14 @@ fn test_product_of_pairings() {
     ]);
     assert_eq!(ans1, ans2);
 }
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<G1Affine>(&mut answers, "g1");
+    curve_answers::<G2Affine>(&mut answers, "g2");
+    pairing_answers::<MNT6>(&mut answers, "pairing");
+    answers.check("src/curves/mnt6/test_vec/mnt6_kat");
+}
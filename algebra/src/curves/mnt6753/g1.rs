@@ -2,7 +2,7 @@ use crate::field_new;
 use crate::{
     biginteger::BigInteger768,
     curves::{
-        models::{ModelParameters, SWModelParameters},
+        models::{ModelParameters, SWModelParameters, SWUParameters},
     },
     fields::mnt6753::{Fq, Fr},
 };
@@ -73,6 +73,24 @@ impl SWModelParameters for MNT6G1Parameters {
         (G1_GENERATOR_X, G1_GENERATOR_Y);
 }
 
+impl SWUParameters for MNT6G1Parameters {
+    // Z = 11, in Montgomery representation
+    const SWU_Z: Fq = field_new!(Fq, BigInteger768([
+        5145524327033718740,
+        14149824967095184544,
+        5159730833497260295,
+        3902941467692815387,
+        15830098551216085679,
+        8665641533746801158,
+        17502192300007146323,
+        14483698255198590748,
+        546300946688995976,
+        4331975528992054828,
+        5311428878520309260,
+        495362057711802,
+    ]));
+}
+
 //generator of prime order r
 //x =3458420969484235708806261200128850544017070333833944116801482064540723\
 // 2681492354777628704146649173606059496596309331847515262279936470308751\
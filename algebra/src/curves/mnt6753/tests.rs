@@ -2,9 +2,9 @@ use crate::{curves::{
     mnt6753::{
         G1Affine, G1Projective, G2Affine, G2Projective, MNT6
     },
-    tests::curve_tests,
+    tests::{curve_tests, multi_pairing_test},
     AffineCurve, PairingEngine,
-}, biginteger::BigInteger768, fields::mnt6753::{fq::Fq, fq3::Fq3, fq6::Fq6, fr::Fr}, groups::tests::group_test, ProjectiveCurve, Field, PrimeField, ToBits, FromCompressedBits};
+}, biginteger::BigInteger768, fields::mnt6753::{fq::Fq, fq3::Fq3, fq6::Fq6, fr::Fr}, groups::tests::group_test, kat::{curve_answers, field_answers, pairing_answers, KnownAnswers}, ProjectiveCurve, Field, PrimeField, ToBits, FromCompressedBits};
 use rand;
 use std::ops::AddAssign;
 use crate::groups::tests::{compression_test, gt_compression_test};
@@ -611,6 +611,11 @@ fn test_bilinearity() {
     assert_eq!(ans3.pow(Fr::characteristic()), Fq6::one());
 }
 
+#[test]
+fn test_multi_pairing() {
+    multi_pairing_test::<MNT6>();
+}
+
 
 #[test]
 fn test_gt_compression(){
@@ -2026,4 +2031,15 @@ fn test_g2_affine_projective_conversion() {
         false
     ));
     assert_eq!(a_a.into_projective(), a);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<G1Affine>(&mut answers, "g1");
+    curve_answers::<G2Affine>(&mut answers, "g2");
+    pairing_answers::<MNT6>(&mut answers, "pairing");
+    answers.check("src/curves/mnt6753/test_vec/mnt6753_kat");
+}
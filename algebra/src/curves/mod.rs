@@ -1,6 +1,12 @@
-use crate::{bytes::{FromBytes, ToBytes}, fields::{Field, PrimeField, SquareRootField}, groups::Group};
-use crate::UniformRand;
+use crate::{
+    biginteger::{BigInteger256, BigInteger320, BigInteger384, BigInteger768, BigInteger832},
+    bytes::{FromBytes, ToBytes},
+    fields::{Field, FieldDecodingError, PrimeField, SquareRootField},
+    groups::Group,
+};
+use crate::{BitSerializationError, Error, UniformRand};
 use std::{
+    convert::TryFrom,
     fmt::{Debug, Display},
     hash::Hash,
     ops::{Add, AddAssign, Neg, Sub, SubAssign},
@@ -8,6 +14,7 @@ use std::{
 
 pub mod bls12_377;
 pub mod bls12_381;
+pub mod ed25519;
 pub mod edwards_bls12;
 pub mod edwards_sw6;
 pub mod jubjub;
@@ -15,12 +22,15 @@ pub mod mnt4753;
 pub mod mnt6753;
 pub mod mnt6;
 pub mod models;
+pub mod prepared_cache;
+pub mod secp256k1;
 pub mod sw6;
 
 #[cfg(test)]
 pub mod tests;
 
 pub use self::models::*;
+pub use self::prepared_cache::PreparedCache;
 
 pub trait PairingEngine: Sized + 'static + Copy + Debug + Sync + Send {
     /// This is the scalar field of the G1/G2 groups.
@@ -94,6 +104,33 @@ pub trait PairingEngine: Sized + 'static + Copy + Debug + Sync + Send {
         Self::final_exponentiation(&Self::miller_loop(i)).unwrap()
     }
 
+    /// Performs a single Miller loop over all the pairs of prepared points in
+    /// `pairs`. The product of their pairings is obtained by a single final
+    /// exponentiation of the result, e.g. to check e(A, B)·e(C, D) = 1 at the
+    /// cost of one pairing and an additional Miller loop.
+    #[must_use]
+    fn multi_miller_loop(
+        pairs: &[(
+            <Self::G1Affine as PairingCurve>::Prepared,
+            <Self::G2Affine as PairingCurve>::Prepared,
+        )],
+    ) -> Self::Fqk {
+        let refs = pairs.iter().map(|(p, q)| (p, q)).collect::<Vec<_>>();
+        Self::miller_loop(refs.iter())
+    }
+
+    /// Computes the product of the pairings of `pairs`, with a shared Miller
+    /// loop and a single final exponentiation.
+    #[must_use]
+    fn multi_pairing(
+        pairs: &[(
+            <Self::G1Affine as PairingCurve>::Prepared,
+            <Self::G2Affine as PairingCurve>::Prepared,
+        )],
+    ) -> Self::Fqk {
+        Self::final_exponentiation(&Self::multi_miller_loop(pairs)).unwrap()
+    }
+
     /// Performs multiple pairing operations
     #[must_use]
     fn pairing<G1, G2>(p: G1, q: G2) -> Self::Fqk
@@ -175,7 +212,7 @@ pub trait ProjectiveCurve:
     fn add_assign_mixed(&mut self, other: &Self::Affine);
 
     /// Performs scalar multiplication of this element.
-    fn mul_assign<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(&mut self, other: S);
+    fn mul_assign<S: Into<ScalarRepr<Self::ScalarField>>>(&mut self, other: S);
 
     /// Converts this element into its affine representation.
     #[must_use]
@@ -233,15 +270,28 @@ pub trait AffineCurve:
     #[must_use]
     fn group_membership_test(&self) -> bool;
 
+    /// Checks that the current point is on curve, which is much cheaper than
+    /// checking that it's in the prime order subgroup. By default, performs the
+    /// full `group_membership_test`.
+    #[must_use]
+    fn is_on_curve(&self) -> bool {
+        self.group_membership_test()
+    }
+
     /// Performs scalar multiplication of this element with mixed addition.
     #[must_use]
-    fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(&self, other: S)
-        -> Self::Projective;
+    fn mul<S: Into<ScalarRepr<Self::ScalarField>>>(&self, other: S) -> Self::Projective;
 
     /// Converts this element into its projective representation.
     #[must_use]
     fn into_projective(&self) -> Self::Projective;
 
+    /// Attempts to construct a point of the curve given an x-coordinate, with
+    /// the lexicographically largest y-coordinate iff `greatest` is set. The
+    /// point is not guaranteed to be in the prime order subgroup.
+    #[must_use]
+    fn from_x_coordinate(x: Self::BaseField, greatest: bool) -> Option<Self>;
+
     /// Multiply this element by the cofactor.
     #[must_use]
     fn mul_by_cofactor(&self) -> Self;
@@ -250,8 +300,136 @@ pub trait AffineCurve:
     /// `Self::ScalarField`.
     #[must_use]
     fn mul_by_cofactor_inv(&self) -> Self;
+
+    /// Maps this element to the prime order subgroup, using the fastest map
+    /// available for the curve. The result may differ from `mul_by_cofactor`
+    /// by an invertible scalar factor.
+    #[must_use]
+    fn scale_by_cofactor_fast(&self) -> Self::Projective {
+        self.mul_by_cofactor().into_projective()
+    }
+
+    /// Returns the compressed encoding of this element: the `ToBytes`
+    /// serialization of the x-coordinate, followed by a byte of flags telling
+    /// whether the y-coordinate is odd (`COMPRESSED_PARITY_FLAG`) and whether
+    /// this is the point at infinity (`COMPRESSED_INFINITY_FLAG`).
+    #[must_use]
+    fn to_compressed(&self) -> Vec<u8>;
+
+    /// Decodes the encoding of `to_compressed`, recomputing the y-coordinate
+    /// from the x-coordinate and its parity. Fails if the encoding is not
+    /// canonical, or if the point is not on the curve or not in the prime
+    /// order subgroup.
+    fn from_compressed(bytes: &[u8]) -> Result<Self, Error>;
+}
+
+/// The flag of a compressed point with odd y-coordinate.
+pub const COMPRESSED_PARITY_FLAG: u8 = 1;
+
+/// The flag of the compressed point at infinity.
+pub const COMPRESSED_INFINITY_FLAG: u8 = 1 << 1;
+
+/// Returns the compressed encoding of a point with x-coordinate `x`.
+pub(crate) fn write_compressed<F: Field>(x: &F, flags: u8) -> Vec<u8> {
+    let mut bytes = vec![];
+    x.write(&mut bytes).unwrap();
+    bytes.push(flags);
+    bytes
 }
 
+/// Splits a compressed point into its x-coordinate and its flags.
+pub(crate) fn read_compressed<F: Field>(bytes: &[u8]) -> Result<(F, u8), Error> {
+    let mut reader = bytes;
+    let x = F::read(&mut reader)?;
+    let flags = u8::read(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(Box::new(BitSerializationError::InvalidLength(
+            bytes.len() - reader.len(),
+            bytes.len(),
+        )));
+    }
+    if flags & !(COMPRESSED_PARITY_FLAG | COMPRESSED_INFINITY_FLAG) != 0 {
+        return Err(Box::new(BitSerializationError::InvalidFlags));
+    }
+    Ok((x, flags))
+}
+
+/// Checks that `bytes` is the canonical encoding of the decoded `point`, so
+/// that each point has a single compressed encoding.
+pub(crate) fn check_compressed<G: AffineCurve>(point: G, bytes: &[u8]) -> Result<G, Error> {
+    if point.to_compressed() != bytes {
+        return Err(Box::new(BitSerializationError::InvalidFieldElement(
+            "non-canonical compressed point".to_owned(),
+        )));
+    }
+    Ok(point)
+}
+
+/// The big integer representation of a scalar for the groups having `F` as
+/// scalar field, as consumed by the scalar multiplications of `AffineCurve`
+/// and `ProjectiveCurve`.
+///
+/// Different fields may share the same big integer type (e.g. the base and
+/// the scalar field of a curve), so accepting a plain `BigInt` would make
+/// multiplying by an element of the wrong field go unnoticed. A `ScalarRepr`
+/// is instead tied to its field: it is obtained from (a reference to) an
+/// element of `F`, or from a canonical big integer via `TryFrom`.
+#[derive(Derivative)]
+#[derivative(
+    Copy(bound = "F: PrimeField"),
+    Clone(bound = "F: PrimeField"),
+    PartialEq(bound = "F: PrimeField"),
+    Eq(bound = "F: PrimeField"),
+    Debug(bound = "F: PrimeField")
+)]
+pub struct ScalarRepr<F: PrimeField>(F::BigInt);
+
+impl<F: PrimeField> ScalarRepr<F> {
+    /// Returns the big integer representing the scalar.
+    #[inline]
+    pub fn into_repr(self) -> F::BigInt {
+        self.0
+    }
+}
+
+impl<F: PrimeField> From<F> for ScalarRepr<F> {
+    #[inline]
+    fn from(scalar: F) -> Self {
+        ScalarRepr(scalar.into_repr())
+    }
+}
+
+impl<'a, F: PrimeField> From<&'a F> for ScalarRepr<F> {
+    #[inline]
+    fn from(scalar: &'a F) -> Self {
+        ScalarRepr(scalar.into_repr())
+    }
+}
+
+macro_rules! impl_scalar_repr_try_from_bigint {
+    ($bigint: ident) => {
+        impl<F: PrimeField<BigInt = $bigint>> TryFrom<$bigint> for ScalarRepr<F> {
+            type Error = FieldDecodingError;
+
+            /// Fails if `repr` is not smaller than the modulus of `F`.
+            #[inline]
+            fn try_from(repr: $bigint) -> Result<Self, Self::Error> {
+                if F::is_canonical_repr(&repr) {
+                    Ok(ScalarRepr(repr))
+                } else {
+                    Err(FieldDecodingError::NonCanonical)
+                }
+            }
+        }
+    };
+}
+
+impl_scalar_repr_try_from_bigint!(BigInteger256);
+impl_scalar_repr_try_from_bigint!(BigInteger320);
+impl_scalar_repr_try_from_bigint!(BigInteger384);
+impl_scalar_repr_try_from_bigint!(BigInteger768);
+impl_scalar_repr_try_from_bigint!(BigInteger832);
+
 pub trait PairingCurve: AffineCurve {
     type Engine: PairingEngine<Fr = Self::ScalarField>;
     type Prepared: ToBytes + FromBytes + Default + Clone + Eq + PartialEq + Send + Sync + Debug + 'static;
@@ -1,14 +1,45 @@
 use crate::{bytes::ToBytes, curves::{
-    bls12::Bls12Parameters,
+    bls12::{g2::mul_by_x, Bls12Parameters},
+    models::{GLVParameters, ModelParameters, SWAffinePoint},
     short_weierstrass_jacobian::{GroupAffine, GroupProjective},
-    AffineCurve,
-}, FromBytes};
+    AffineCurve, ProjectiveCurve,
+}, fields::Field, FromBytes};
 use std::io::{Result as IoResult, Write, Read};
 use std::io;
 
 pub type G1Affine<P> = GroupAffine<<P as Bls12Parameters>::G1Parameters>;
 pub type G1Projective<P> = GroupProjective<<P as Bls12Parameters>::G1Parameters>;
 
+/// Returns true if `p`, which must be on the curve, is in G1, with the test
+/// of Scott (https://eprint.iacr.org/2021/1130). On G1 the endomorphism φ acts
+/// as multiplication by λ, which is either -x^2 or x^2 - 1 modulo r, so that
+/// φ(p) = [λ]p is checked with two multiplications by x instead of one by r.
+pub fn is_in_g1<B, G>(p: &G) -> bool
+where
+    B: Bls12Parameters,
+    B::G1Parameters: GLVParameters,
+    G: SWAffinePoint<B::G1Parameters>,
+{
+    type Fr<B> = <<B as Bls12Parameters>::G1Parameters as ModelParameters>::ScalarField;
+
+    let two_to_64 = Fr::<B>::from(1u128 << 64);
+    let x = B::X
+        .iter()
+        .rev()
+        .fold(Fr::<B>::zero(), |acc, limb| acc * &two_to_64 + &Fr::<B>::from(*limb));
+    let x_squared = x.square();
+
+    let p_proj = p.into_projective();
+    let x_squared_p = mul_by_x::<B, _>(&mul_by_x::<B, _>(&p_proj));
+    let phi_p = B::G1Parameters::endomorphism(p).into_projective();
+    if B::G1Parameters::LAMBDA == -x_squared {
+        (x_squared_p + &phi_p).is_zero()
+    } else {
+        debug_assert!(B::G1Parameters::LAMBDA == x_squared - &Fr::<B>::one());
+        (x_squared_p - &p_proj - &phi_p).is_zero()
+    }
+}
+
 #[derive(Derivative)]
 #[derivative(
     Clone(bound = "P: Bls12Parameters"),
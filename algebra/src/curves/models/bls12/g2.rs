@@ -1,9 +1,11 @@
 use crate::{bytes::ToBytes, curves::{
     bls12::{Bls12Parameters, TwistType},
-    models::SWModelParameters,
+    models::{SWAffinePoint, SWModelParameters},
     short_weierstrass_jacobian::{GroupAffine, GroupProjective},
-    AffineCurve,
-}, fields::{BitIterator, Field, Fp2}, FromBytes};
+    AffineCurve, ProjectiveCurve,
+}, fields::{
+    fp12_2over3over2::Fp12Parameters, fp6_3over2::Fp6Parameters, BitIterator, Field, Fp2,
+}, FromBytes};
 use std::io::{Result as IoResult, Write, Read};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use std::io;
@@ -163,3 +165,74 @@ fn addition_step<B: Bls12Parameters>(
         TwistType::D => (lambda, -theta, j),
     }
 }
+
+/// The endomorphism ψ = φ⁻¹ ∘ π ∘ φ of the twist, where φ is the untwisting
+/// isomorphism to the curve over Fp12 and π is the p-power Frobenius map.
+/// On G2, ψ acts as the multiplication by p, i.e. by x modulo the group order.
+pub fn psi<B: Bls12Parameters, G: SWAffinePoint<B::G2Parameters>>(p: &G) -> G {
+    match p.xy() {
+        None => *p,
+        Some((mut x, mut y)) => {
+            x.frobenius_map(1);
+            y.frobenius_map(1);
+
+            // xi^((p - 1)/3) and xi^((p - 1)/2), for the sextic non-residue xi
+            let c_x = <B::Fp6Params as Fp6Parameters>::FROBENIUS_COEFF_FP6_C1[1];
+            let c = <B::Fp12Params as Fp12Parameters>::FROBENIUS_COEFF_FP12_C1[1];
+            let c_y = c.square() * &c;
+
+            match B::TWIST_TYPE {
+                TwistType::M => {
+                    x *= &c_x.inverse().unwrap();
+                    y *= &c_y.inverse().unwrap();
+                },
+                TwistType::D => {
+                    x *= &c_x;
+                    y *= &c_y;
+                },
+            }
+            G::from_xy_unchecked(x, y)
+        },
+    }
+}
+
+// Multiplies `p` by the (signed) parameter x of the curve.
+pub(crate) fn mul_by_x<B: Bls12Parameters, G: ProjectiveCurve>(p: &G) -> G {
+    let mut res = G::zero();
+    for bit in BitIterator::new(B::X).skip_while(|bit| !bit) {
+        res.double_in_place();
+        if bit {
+            res += p;
+        }
+    }
+    if B::X_IS_NEGATIVE {
+        -res
+    } else {
+        res
+    }
+}
+
+/// Returns true if `p`, which must be on the curve, is in G2, with the test
+/// ψ(p) = [x]p of Scott (https://eprint.iacr.org/2021/1130), which costs a
+/// multiplication by x instead of one by the order of G2.
+pub fn is_in_g2<B: Bls12Parameters, G: SWAffinePoint<B::G2Parameters>>(p: &G) -> bool {
+    let psi_p = psi::<B, G>(p).into_projective();
+    (mul_by_x::<B, _>(&p.into_projective()) - &psi_p).is_zero()
+}
+
+/// Maps `p` to G2 with the endomorphism based map of Budroni and Pintore
+/// (https://eprint.iacr.org/2017/419), as in RFC 9380, Appendix G.3:
+/// h_eff * p = [x^2 - x - 1]p + [x - 1]ψ(p) + ψ^2(2p),
+/// where h_eff is a multiple of the cofactor of G2.
+pub fn scale_by_cofactor_g2<B: Bls12Parameters, G: SWAffinePoint<B::G2Parameters>>(p: &G) -> G::Projective {
+    let p_proj = p.into_projective();
+    let t1 = mul_by_x::<B, _>(&p_proj);
+    let psi_p = psi::<B, G>(p).into_projective();
+
+    let mut t3 = psi::<B, G>(&psi::<B, G>(&p_proj.double().into_affine())).into_projective();
+    t3 -= &psi_p;
+    let t2 = mul_by_x::<B, _>(&(t1 + &psi_p));
+    t3 += &t2;
+    t3 -= &t1;
+    t3 - &p_proj
+}
@@ -1,4 +1,8 @@
-use crate::{biginteger::BigInteger, fields::{Field, PrimeField, SquareRootField}};
+use crate::{
+    biginteger::{arithmetic as fa, BigInteger},
+    curves::{AffineCurve, ProjectiveCurve},
+    fields::{BitIterator, Field, FpParameters, PrimeField, SquareRootField},
+};
 
 pub mod bls12;
 pub mod mnt4;
@@ -61,6 +65,252 @@ pub trait SWModelParameters: ModelParameters {
         }
         result
     }
+
+    /// Maps `p` to the prime order subgroup, by multiplication with the cofactor.
+    /// Curves may override it with a faster map, such as the multiplication with
+    /// another multiple of the cofactor or an endomorphism based map, as long as
+    /// it acts on the prime order subgroup as an invertible scalar.
+    #[inline]
+    fn scale_by_cofactor_fast<G: SWAffinePoint<Self>>(p: &G) -> G::Projective
+    where
+        Self: Sized,
+    {
+        scale_by_cofactor_bits(p, Self::COFACTOR)
+    }
+
+    /// Returns true if `p`, which must be on the curve, is in the prime order
+    /// subgroup. All the points are if the cofactor is one, otherwise `p` is
+    /// multiplied by the order of the subgroup. Curves may override it with a
+    /// faster test, such as an endomorphism based one.
+    #[inline]
+    fn is_in_correct_subgroup_assuming_on_curve<G: SWAffinePoint<Self>>(p: &G) -> bool
+    where
+        Self: Sized,
+    {
+        if Self::COFACTOR == [1] {
+            return true;
+        }
+        scale_by_cofactor_bits(p, Self::ScalarField::characteristic()).is_zero()
+    }
+}
+
+/// The affine points of a short Weierstrass curve with parameters `P`, in any of
+/// its representations, giving access to their coordinates.
+pub trait SWAffinePoint<P: SWModelParameters>:
+    AffineCurve<BaseField = P::BaseField, ScalarField = P::ScalarField>
+{
+    /// Returns the coordinates of the point, or None for the point at infinity.
+    fn xy(&self) -> Option<(P::BaseField, P::BaseField)>;
+
+    /// Returns the point with coordinates (x, y), which must be on the curve.
+    fn from_xy_unchecked(x: P::BaseField, y: P::BaseField) -> Self;
+}
+
+/// Multiplies `p` by the integer with little endian limbs `cofactor`, skipping
+/// its leading zeros, so that small cofactors cost a few doublings.
+pub fn scale_by_cofactor_bits<G: AffineCurve>(p: &G, cofactor: &[u64]) -> G::Projective {
+    let mut res = G::Projective::zero();
+    for bit in BitIterator::new(cofactor).skip_while(|bit| !bit) {
+        res.double_in_place();
+        if bit {
+            res.add_assign_mixed(p);
+        }
+    }
+    res
+}
+
+/// Parameters of a short Weierstrass curve with an efficiently computable
+/// endomorphism φ(x, y) = (β·x, y), where β is a primitive cube root of unity
+/// of the base field. On the prime order subgroup φ acts as multiplication by
+/// a cube root of unity λ of the scalar field, which allows to split a scalar
+/// k into two scalars of about half the length with k = k1 + k2·λ mod r
+/// (Gallant-Lambert-Vanstone, https://www.iacr.org/archive/crypto2001/21390189.pdf).
+pub trait GLVParameters: SWModelParameters {
+    /// β, the cube root of unity of the base field defining φ.
+    const ENDO_COEFF: Self::BaseField;
+
+    /// λ, the eigenvalue of φ on the prime order subgroup.
+    const LAMBDA: Self::ScalarField;
+
+    /// A reduced basis ((a1, b1), (a2, b2)) of the lattice of all (a, b) with
+    /// a + b·λ = 0 mod r, oriented such that a1·b2 - a2·b1 = r, b1 <= 0 and
+    /// b2 >= 0. The entries are given as scalar field elements.
+    const SCALAR_DECOMP_BASIS: [(Self::ScalarField, Self::ScalarField); 2];
+
+    /// The coefficients b2·2^m / r and -b1·2^m / r, rounded down, with
+    /// m = 64·SCALAR_DECOMP_SHIFT. They are used to approximate the
+    /// coordinates of a scalar with respect to the lattice basis.
+    const SCALAR_DECOMP_COEFFS: [&'static [u64]; 2];
+
+    /// The shift m of `SCALAR_DECOMP_COEFFS`, in limbs.
+    const SCALAR_DECOMP_SHIFT: usize;
+
+    /// Returns φ(p) = (β·x, y).
+    #[inline]
+    fn endomorphism<G: SWAffinePoint<Self>>(p: &G) -> G
+    where
+        Self: Sized,
+    {
+        match p.xy() {
+            None => *p,
+            Some((x, y)) => G::from_xy_unchecked(x * &Self::ENDO_COEFF, y),
+        }
+    }
+
+    /// Splits `k` into `(k1, k2)` such that k = k1 + k2·λ mod r. Both parts
+    /// are returned as a pair of a sign flag (set if the part is negative)
+    /// and its absolute value.
+    fn scalar_decomposition(
+        k: &Self::ScalarField,
+    ) -> (
+        (bool, <Self::ScalarField as PrimeField>::BigInt),
+        (bool, <Self::ScalarField as PrimeField>::BigInt),
+    ) {
+        let k_repr = k.into_repr();
+        let c1 = Self::ScalarField::from_repr(mul_and_shift(
+            k_repr.as_ref(),
+            Self::SCALAR_DECOMP_COEFFS[0],
+            Self::SCALAR_DECOMP_SHIFT,
+        ));
+        let c2 = Self::ScalarField::from_repr(mul_and_shift(
+            k_repr.as_ref(),
+            Self::SCALAR_DECOMP_COEFFS[1],
+            Self::SCALAR_DECOMP_SHIFT,
+        ));
+
+        let (a1, b1) = Self::SCALAR_DECOMP_BASIS[0];
+        let (a2, b2) = Self::SCALAR_DECOMP_BASIS[1];
+
+        // (k, 0) - c1·(a1, b1) - c2·(a2, b2) is a short lattice translate of (k, 0).
+        let k1 = *k - &(c1 * &a1) - &(c2 * &a2);
+        let k2 = -(c1 * &b1) - &(c2 * &b2);
+
+        (into_signed_repr(k1), into_signed_repr(k2))
+    }
+}
+
+/// Returns (a·b) >> (64·shift), truncated to the width of `B`.
+fn mul_and_shift<B: BigInteger>(a: &[u64], b: &[u64], shift: usize) -> B {
+    let mut prod = vec![0u64; a.len() + b.len()];
+    for (i, a_i) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, b_j) in b.iter().enumerate() {
+            prod[i + j] = fa::mac_with_carry(prod[i + j], *a_i, *b_j, &mut carry);
+        }
+        prod[i + b.len()] = carry;
+    }
+
+    let mut res = B::default();
+    for (limb, p) in res.as_mut().iter_mut().zip(prod.iter().skip(shift)) {
+        *limb = *p;
+    }
+    res
+}
+
+/// Returns the sign and the absolute value of `x`, where elements larger than
+/// (r - 1)/2 are regarded as negative.
+fn into_signed_repr<F: PrimeField>(x: F) -> (bool, F::BigInt) {
+    let mut half = F::Params::MODULUS;
+    half.div2();
+    let repr = x.into_repr();
+    if repr > half {
+        (true, (-x).into_repr())
+    } else {
+        (false, repr)
+    }
+}
+
+/// Parameters of a short Weierstrass curve with A·B != 0 for the simplified
+/// Shallue-van de Woestijne-Ulas map (RFC 9380, Section 6.6.2), which sends
+/// every base field element to a point of the curve.
+pub trait SWUParameters: SWModelParameters {
+    /// Z, a non-square of the base field chosen as in RFC 9380, Appendix H.2.
+    const SWU_Z: Self::BaseField;
+
+    /// Maps `u` to the affine coordinates of a point of the curve.
+    fn map_to_curve(u: &Self::BaseField) -> (Self::BaseField, Self::BaseField) {
+        simplified_swu(u, &Self::COEFF_A, &Self::COEFF_B, &Self::SWU_Z)
+    }
+}
+
+/// Parameters of a short Weierstrass curve E with A·B = 0, such as the G1 of
+/// the BLS12 curves, for which the simplified SWU map is applied on a curve E'
+/// with A'·B' != 0 and composed with an isogeny φ: E' -> E
+/// (Wahby-Boneh, https://eprint.iacr.org/2019/403).
+pub trait WBParameters: SWModelParameters {
+    /// The coefficient A' of E'.
+    const ISOGENOUS_COEFF_A: Self::BaseField;
+
+    /// The coefficient B' of E'.
+    const ISOGENOUS_COEFF_B: Self::BaseField;
+
+    /// Z of the simplified SWU map on E'.
+    const ISOGENOUS_SWU_Z: Self::BaseField;
+
+    /// The polynomials of φ(x, y) = (x_num(x) / x_den(x), y·y_num(x) / y_den(x)),
+    /// given by their coefficients starting from the constant term.
+    const ISOGENY_X_NUM: &'static [Self::BaseField];
+    const ISOGENY_X_DEN: &'static [Self::BaseField];
+    const ISOGENY_Y_NUM: &'static [Self::BaseField];
+    const ISOGENY_Y_DEN: &'static [Self::BaseField];
+
+    /// Maps `u` to the affine coordinates of a point of the curve, or to `None`
+    /// if it is mapped to the point at infinity.
+    fn map_to_curve(u: &Self::BaseField) -> Option<(Self::BaseField, Self::BaseField)> {
+        let (x, y) = simplified_swu(
+            u,
+            &Self::ISOGENOUS_COEFF_A,
+            &Self::ISOGENOUS_COEFF_B,
+            &Self::ISOGENOUS_SWU_Z,
+        );
+
+        // φ maps the points of its kernel, the zeros of the denominators,
+        // to the point at infinity.
+        let x_den_inv = evaluate_poly(Self::ISOGENY_X_DEN, &x).inverse()?;
+        let y_den_inv = evaluate_poly(Self::ISOGENY_Y_DEN, &x).inverse()?;
+
+        let x_mapped = evaluate_poly(Self::ISOGENY_X_NUM, &x) * &x_den_inv;
+        let y_mapped = y * &evaluate_poly(Self::ISOGENY_Y_NUM, &x) * &y_den_inv;
+        Some((x_mapped, y_mapped))
+    }
+}
+
+/// Returns the affine coordinates of the image of `u` under the simplified SWU
+/// map to y^2 = x^3 + a·x + b, following the straight-line description of
+/// RFC 9380, Section 6.6.2. The sign of y is chosen equal to the sign of `u`.
+fn simplified_swu<F: SquareRootField>(u: &F, a: &F, b: &F, z: &F) -> (F, F) {
+    let g = |x: &F| (x.square() + a) * x + b;
+
+    // tv1 = Z^2·u^4 + Z·u^2
+    let z_u2 = *z * &u.square();
+    let tv1 = z_u2.square() + &z_u2;
+
+    let x1 = match tv1.inverse() {
+        // x1 = (-B / A)·(1 + 1 / tv1)
+        Some(tv1_inv) => -(*b) * &a.inverse().unwrap() * &(F::one() + &tv1_inv),
+        // Exceptional case tv1 = 0: x1 = B / (Z·A)
+        None => *b * &(*z * a).inverse().unwrap(),
+    };
+
+    let (x, mut y) = match g(&x1).sqrt() {
+        Some(y1) => (x1, y1),
+        None => {
+            // If g(x1) is not a square, then g(x2) is, for x2 = Z·u^2·x1.
+            let x2 = z_u2 * &x1;
+            (x2, g(&x2).sqrt().unwrap())
+        }
+    };
+
+    if u.is_odd() != y.is_odd() {
+        y = -y;
+    }
+    (x, y)
+}
+
+/// Evaluates the polynomial with coefficients `coeffs`, starting from the
+/// constant term, at `x`.
+fn evaluate_poly<F: Field>(coeffs: &[F], x: &F) -> F {
+    coeffs.iter().rev().fold(F::zero(), |acc, c| acc * x + c)
 }
 
 pub trait TEModelParameters: ModelParameters {
@@ -110,6 +360,17 @@ pub trait TEModelParameters: ModelParameters {
 
         ret
     }
+
+    /// Maps `p` to the prime order subgroup, by multiplication with the cofactor,
+    /// which only costs doublings for the usual cofactors 4 and 8. Curves may
+    /// override it with a faster map, as for `SWModelParameters`.
+    #[inline]
+    fn scale_by_cofactor_fast<G>(p: &G) -> G::Projective
+    where
+        G: AffineCurve<BaseField = Self::BaseField, ScalarField = Self::ScalarField>,
+    {
+        scale_by_cofactor_bits(p, Self::COFACTOR)
+    }
 }
 
 pub trait MontgomeryModelParameters: ModelParameters {
@@ -117,4 +378,55 @@ pub trait MontgomeryModelParameters: ModelParameters {
     const COEFF_B: Self::BaseField;
 
     type TEModelParameters: TEModelParameters<BaseField = Self::BaseField>;
-}
\ No newline at end of file
+}
+
+/// Parameters of a twisted Edwards curve for the Elligator 2 map (RFC 9380,
+/// Section 6.8.2), which sends every base field element to a point of the
+/// birationally equivalent Montgomery curve K·t^2 = s^3 + J·s^2 + s.
+pub trait Elligator2Parameters: TEModelParameters {
+    /// Z, a non-square of the base field.
+    const ELLIGATOR2_Z: Self::BaseField;
+
+    /// Maps `u` to the affine coordinates of a point of the curve.
+    fn map_to_curve(u: &Self::BaseField) -> (Self::BaseField, Self::BaseField) {
+        let j = <Self::MontgomeryModelParameters as MontgomeryModelParameters>::COEFF_A;
+        let k = <Self::MontgomeryModelParameters as MontgomeryModelParameters>::COEFF_B;
+
+        // Map to the curve t^2 = s^3 + (J / K)·s^2 + s / K^2, a scaling of
+        // the Montgomery curve.
+        let k_inv = k.inverse().unwrap();
+        let c1 = j * &k_inv;
+        let c2 = k_inv.square();
+        let g = |x: &Self::BaseField| ((*x + &c1) * x + &c2) * x;
+
+        // x1 = -c1 / (1 + Z·u^2), or x1 = -c1 in the exceptional case
+        // 1 + Z·u^2 = 0.
+        let x1 = match (Self::ELLIGATOR2_Z * &u.square() + &Self::BaseField::one()).inverse() {
+            Some(den_inv) => -c1 * &den_inv,
+            None => -c1,
+        };
+
+        // Exactly one of g(x1) and g(x2) is a square, for x2 = -x1 - c1.
+        let (x, y) = match g(&x1).sqrt() {
+            Some(y1) => (x1, if y1.is_odd() { y1 } else { -y1 }),
+            None => {
+                let x2 = -x1 - &c1;
+                let y2 = g(&x2).sqrt().unwrap();
+                (x2, if y2.is_odd() { -y2 } else { y2 })
+            }
+        };
+
+        // Back to the Montgomery curve, and from there to the twisted Edwards
+        // curve via (v, w) = (s / t, (s - 1) / (s + 1)). The exceptional
+        // points t = 0 and s = -1 are mapped to the identity.
+        let s = x * &k;
+        let t = y * &k;
+        match (t.inverse(), (s + &Self::BaseField::one()).inverse()) {
+            (Some(t_inv), Some(s_plus_one_inv)) => (
+                s * &t_inv,
+                (s - &Self::BaseField::one()) * &s_plus_one_inv,
+            ),
+            _ => (Self::BaseField::zero(), Self::BaseField::one()),
+        }
+    }
+}
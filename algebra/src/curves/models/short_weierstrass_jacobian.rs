@@ -1,15 +1,19 @@
 use rand::{Rng, distributions::{Standard, Distribution}};
-use crate::curves::models::SWModelParameters as Parameters;
-use crate::UniformRand;
+use crate::curves::models::{GLVParameters, SWAffinePoint, SWModelParameters as Parameters};
+use crate::{BitSerializationError, Error, UniformRand};
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
     io::{Read, Result as IoResult, Write},
     marker::PhantomData,
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
-    curves::{AffineCurve, ProjectiveCurve},
+    bytes::{FromBytes, FromBytesWithProfile, SerializationProfile, ToBytes},
+    curves::{
+        check_compressed, read_compressed, write_compressed, AffineCurve, ProjectiveCurve, ScalarRepr,
+        COMPRESSED_INFINITY_FLAG, COMPRESSED_PARITY_FLAG,
+    },
     fields::{BitIterator, Field, PrimeField, SquareRootField},
 };
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
@@ -111,10 +115,53 @@ impl<P: Parameters> GroupAffine<P> {
         }
     }
 
+    /// Checks that the point, which must be on the curve, is in the prime order
+    /// subgroup, with the test of the parameters of the curve.
     #[inline]
     pub fn is_in_correct_subgroup_assuming_on_curve(&self) -> bool {
-        self.mul_bits(BitIterator::new(P::ScalarField::characteristic()))
-            .is_zero()
+        P::is_in_correct_subgroup_assuming_on_curve(self)
+    }
+}
+
+impl<P: GLVParameters> GroupAffine<P> {
+    /// Applies the endomorphism φ(x, y) = (β·x, y), which acts as
+    /// multiplication by `P::LAMBDA` on the prime order subgroup.
+    pub fn endomorphism(&self) -> Self {
+        let mut res = *self;
+        res.x *= &P::ENDO_COEFF;
+        res
+    }
+
+    /// Scalar multiplication using the GLV decomposition k = k1 + k2·λ, which
+    /// replaces the doublings for the upper half of the scalar by a single
+    /// application of the endomorphism. The result is only correct for points
+    /// in the prime order subgroup.
+    pub fn mul_glv<S: Into<ScalarRepr<P::ScalarField>>>(&self, by: S) -> GroupProjective<P> {
+        let k = P::ScalarField::from_repr(by.into().into_repr());
+        let ((k1_neg, k1), (k2_neg, k2)) = P::scalar_decomposition(&k);
+
+        let p1 = if k1_neg { -*self } else { *self };
+        let p2 = if k2_neg { -self.endomorphism() } else { self.endomorphism() };
+        let mut p1_p2 = p1.into_projective();
+        p1_p2.add_assign_mixed(&p2);
+
+        let mut res = GroupProjective::zero();
+        let mut found_one = false;
+        for (b1, b2) in BitIterator::new(k1).zip(BitIterator::new(k2)) {
+            if found_one {
+                res.double_in_place();
+            } else {
+                found_one = b1 || b2;
+            }
+
+            match (b1, b2) {
+                (true, true) => res += &p1_p2,
+                (true, false) => res.add_assign_mixed(&p1),
+                (false, true) => res.add_assign_mixed(&p2),
+                (false, false) => {},
+            }
+        }
+        res
     }
 }
 
@@ -148,8 +195,13 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
     }
 
     #[inline]
-    fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(&self, by: S) -> GroupProjective<P> {
-        let bits = BitIterator::new(by.into());
+    fn is_on_curve(&self) -> bool {
+        GroupAffine::is_on_curve(self)
+    }
+
+    #[inline]
+    fn mul<S: Into<ScalarRepr<Self::ScalarField>>>(&self, by: S) -> GroupProjective<P> {
+        let bits = BitIterator::new(by.into().into_repr());
         self.mul_bits(bits)
     }
 
@@ -158,13 +210,62 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
         (*self).into()
     }
 
+    fn from_x_coordinate(x: P::BaseField, greatest: bool) -> Option<Self> {
+        Self::get_point_from_x(x, greatest)
+    }
+
     fn mul_by_cofactor(&self) -> Self {
         self.scale_by_cofactor().into()
     }
 
+    fn scale_by_cofactor_fast(&self) -> Self::Projective {
+        P::scale_by_cofactor_fast(self)
+    }
+
     fn mul_by_cofactor_inv(&self) -> Self {
         self.mul(P::COFACTOR_INV).into()
     }
+
+    fn to_compressed(&self) -> Vec<u8> {
+        if self.infinity {
+            write_compressed(&P::BaseField::zero(), COMPRESSED_INFINITY_FLAG)
+        } else if self.y.is_odd() {
+            write_compressed(&self.x, COMPRESSED_PARITY_FLAG)
+        } else {
+            write_compressed(&self.x, 0)
+        }
+    }
+
+    fn from_compressed(bytes: &[u8]) -> Result<Self, Error> {
+        let (x, flags) = read_compressed::<P::BaseField>(bytes)?;
+        let point = if flags & COMPRESSED_INFINITY_FLAG != 0 {
+            Self::zero()
+        } else {
+            let point = Self::get_point_from_x_and_parity(x, flags & COMPRESSED_PARITY_FLAG != 0)
+                .ok_or(BitSerializationError::NotOnCurve)?;
+            if !point.is_in_correct_subgroup_assuming_on_curve() {
+                return Err(Box::new(BitSerializationError::NotPrimeOrder));
+            }
+            point
+        };
+        check_compressed(point, bytes)
+    }
+}
+
+impl<P: Parameters> SWAffinePoint<P> for GroupAffine<P> {
+    #[inline]
+    fn xy(&self) -> Option<(P::BaseField, P::BaseField)> {
+        if self.infinity {
+            None
+        } else {
+            Some((self.x, self.y))
+        }
+    }
+
+    #[inline]
+    fn from_xy_unchecked(x: P::BaseField, y: P::BaseField) -> Self {
+        Self::new(x, y, false)
+    }
 }
 
 impl<P: Parameters> Neg for GroupAffine<P> {
@@ -199,6 +300,13 @@ impl<P: Parameters> FromBytes for GroupAffine<P> {
     }
 }
 
+impl<P: Parameters> FromBytesWithProfile for GroupAffine<P> {
+    #[inline]
+    fn read_with_profile<R: Read>(reader: R, profile: SerializationProfile) -> IoResult<Self> {
+        profile.read_point(reader)
+    }
+}
+
 impl<P: Parameters> Default for GroupAffine<P> {
     #[inline]
     fn default() -> Self {
@@ -211,8 +319,7 @@ impl<P: Parameters> Default for GroupAffine<P> {
     Copy(bound = "P: Parameters"),
     Clone(bound = "P: Parameters"),
     Eq(bound = "P: Parameters"),
-    Debug(bound = "P: Parameters"),
-    Hash(bound = "P: Parameters")
+    Debug(bound = "P: Parameters")
 )]
 pub struct GroupProjective<P: Parameters> {
     pub x:   P::BaseField,
@@ -253,6 +360,13 @@ impl<P: Parameters> PartialEq for GroupProjective<P> {
     }
 }
 
+/// Hashes the affine form of the point, so that projective representations
+/// of the same point hash to the same value.
+impl<P: Parameters> Hash for GroupProjective<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.into_affine().hash(state)
+    }
+}
 
 impl<P: Parameters> Distribution<GroupProjective<P>> for Standard {
     #[inline]
@@ -531,12 +645,12 @@ impl<P: Parameters> ProjectiveCurve for GroupProjective<P> {
         }
     }
 
-    fn mul_assign<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(&mut self, other: S) {
+    fn mul_assign<S: Into<ScalarRepr<Self::ScalarField>>>(&mut self, other: S) {
         let mut res = Self::zero();
 
         let mut found_one = false;
 
-        for i in BitIterator::new(other.into()) {
+        for i in BitIterator::new(other.into().into_repr()) {
             if found_one {
                 res.double_in_place();
             } else {
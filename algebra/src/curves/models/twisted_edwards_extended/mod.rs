@@ -1,15 +1,20 @@
 use rand::{Rng, distributions::{Standard, Distribution}};
-use crate::UniformRand;
+use crate::{BitSerializationError, Error, UniformRand};
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
     io::{Read, Result as IoResult, Write},
     marker::PhantomData,
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
-    curves::{models::TEModelParameters as Parameters, models::MontgomeryModelParameters as MontgomeryParameters, AffineCurve, ProjectiveCurve},
+    bytes::{FromBytes, FromBytesWithProfile, SerializationProfile, ToBytes},
+    curves::{
+        check_compressed, read_compressed, write_compressed, models::TEModelParameters as Parameters,
+        models::MontgomeryModelParameters as MontgomeryParameters, AffineCurve, ProjectiveCurve, ScalarRepr,
+        COMPRESSED_INFINITY_FLAG, COMPRESSED_PARITY_FLAG,
+    },
     fields::{BitIterator, Field, PrimeField, SquareRootField},
 };
 
@@ -143,21 +148,51 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
         self.is_on_curve() && self.is_in_correct_subgroup_assuming_on_curve()
     }
 
-    fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(&self, by: S) -> GroupProjective<P> {
-        self.mul_bits(BitIterator::new(by.into()))
+    fn is_on_curve(&self) -> bool {
+        GroupAffine::is_on_curve(self)
+    }
+
+    fn mul<S: Into<ScalarRepr<Self::ScalarField>>>(&self, by: S) -> GroupProjective<P> {
+        self.mul_bits(BitIterator::new(by.into().into_repr()))
     }
 
     fn into_projective(&self) -> GroupProjective<P> {
         (*self).into()
     }
 
+    fn from_x_coordinate(x: P::BaseField, greatest: bool) -> Option<Self> {
+        Self::get_point_from_x(x, greatest)
+    }
+
     fn mul_by_cofactor(&self) -> Self {
         self.scale_by_cofactor().into()
     }
 
+    fn scale_by_cofactor_fast(&self) -> Self::Projective {
+        P::scale_by_cofactor_fast(self)
+    }
+
     fn mul_by_cofactor_inv(&self) -> Self {
         self.mul(P::COFACTOR_INV).into()
     }
+
+    fn to_compressed(&self) -> Vec<u8> {
+        // The point at infinity is (0, 1), which needs no flag of its own
+        write_compressed(&self.x, if self.y.is_odd() { COMPRESSED_PARITY_FLAG } else { 0 })
+    }
+
+    fn from_compressed(bytes: &[u8]) -> Result<Self, Error> {
+        let (x, flags) = read_compressed::<P::BaseField>(bytes)?;
+        if flags & COMPRESSED_INFINITY_FLAG != 0 {
+            return Err(Box::new(BitSerializationError::InvalidFlags));
+        }
+        let point = Self::get_point_from_x_and_parity(x, flags & COMPRESSED_PARITY_FLAG != 0)
+            .ok_or(BitSerializationError::NotOnCurve)?;
+        if !point.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(Box::new(BitSerializationError::NotPrimeOrder));
+        }
+        check_compressed(point, bytes)
+    }
 }
 
 impl<P: Parameters> Neg for GroupAffine<P> {
@@ -220,7 +255,7 @@ impl<'a, P: Parameters> Mul<&'a P::ScalarField> for GroupAffine<P> {
 
 impl<'a, P: Parameters> MulAssign<&'a P::ScalarField> for GroupAffine<P> {
     fn mul_assign(&mut self, other: &'a P::ScalarField) {
-        *self = <Self as AffineCurve>::mul(self, other.into_repr()).into_affine();
+        *self = <Self as AffineCurve>::mul(self, other).into_affine();
     }
 }
 
@@ -241,6 +276,13 @@ impl<P: Parameters> FromBytes for GroupAffine<P> {
     }
 }
 
+impl<P: Parameters> FromBytesWithProfile for GroupAffine<P> {
+    #[inline]
+    fn read_with_profile<R: Read>(reader: R, profile: SerializationProfile) -> IoResult<Self> {
+        profile.read_point(reader)
+    }
+}
+
 impl<P: Parameters> Default for GroupAffine<P> {
     #[inline]
     fn default() -> Self {
@@ -256,7 +298,7 @@ impl<P: Parameters> Distribution<GroupAffine<P>> for Standard {
             let greatest = rng.gen();
 
             if let Some(p) = GroupAffine::get_point_from_x(x, greatest) {
-                return p.scale_by_cofactor().into();
+                return p.scale_by_cofactor_fast().into();
             }
         }
     }
@@ -301,8 +343,7 @@ mod group_impl {
     Copy(bound = "P: Parameters"),
     Clone(bound = "P: Parameters"),
     Eq(bound = "P: Parameters"),
-    Debug(bound = "P: Parameters"),
-    Hash(bound = "P: Parameters")
+    Debug(bound = "P: Parameters")
 )]
 pub struct GroupProjective<P: Parameters> {
     pub x: P::BaseField,
@@ -334,6 +375,14 @@ impl<P: Parameters> PartialEq for GroupProjective<P> {
     }
 }
 
+/// Hashes the affine form of the point, so that projective representations
+/// of the same point hash to the same value.
+impl<P: Parameters> Hash for GroupProjective<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.into_affine().hash(state)
+    }
+}
+
 impl<P: Parameters> Distribution<GroupProjective<P>> for Standard {
     #[inline]
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GroupProjective<P> {
@@ -342,7 +391,7 @@ impl<P: Parameters> Distribution<GroupProjective<P>> for Standard {
             let greatest = rng.gen();
 
             if let Some(p) = GroupAffine::get_point_from_x(x, greatest) {
-                return p.scale_by_cofactor();
+                return p.scale_by_cofactor_fast();
             }
         }
     }
@@ -495,12 +544,12 @@ impl<P: Parameters> ProjectiveCurve for GroupProjective<P> {
         self.z = f * &g;
     }
 
-    fn mul_assign<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(&mut self, other: S) {
+    fn mul_assign<S: Into<ScalarRepr<Self::ScalarField>>>(&mut self, other: S) {
         let mut res = Self::zero();
 
         let mut found_one = false;
 
-        for i in BitIterator::new(other.into()) {
+        for i in BitIterator::new(other.into().into_repr()) {
             if found_one {
                 res.double_in_place();
             } else {
@@ -616,7 +665,7 @@ impl<'a, P: Parameters> Mul<&'a P::ScalarField> for GroupProjective<P> {
 
 impl<'a, P: Parameters> MulAssign<&'a P::ScalarField> for GroupProjective<P> {
     fn mul_assign(&mut self, other: &'a P::ScalarField) {
-        <Self as ProjectiveCurve>::mul_assign(self, other.into_repr());
+        <Self as ProjectiveCurve>::mul_assign(self, other);
     }
 }
 
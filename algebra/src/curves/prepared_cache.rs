@@ -0,0 +1,169 @@
+//! A cache of the prepared forms of points which are paired over and over
+//! again, such as the G2 elements of a verifying key or an aggregated public
+//! key. Preparing a G2 point, i.e. precomputing the line coefficients of its
+//! Miller loop, costs about as much as the Miller loop itself, so verifiers
+//! which check many proofs or signatures against the same few points can
+//! prepare them once and keep the result, also across restarts by means of
+//! the serialization of the cache.
+use crate::{
+    bytes::{FromBytes, ToBytes},
+    curves::PairingCurve,
+};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{self, Read, Result as IoResult, Write},
+};
+
+/// Maps points to their prepared forms, evicting the least recently inserted
+/// point when full.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "G: PairingCurve"),
+    Debug(bound = "G: PairingCurve"),
+    PartialEq(bound = "G: PairingCurve"),
+    Eq(bound = "G: PairingCurve")
+)]
+pub struct PreparedCache<G: PairingCurve> {
+    capacity: usize,
+    prepared: HashMap<G, G::Prepared>,
+    order:    VecDeque<G>,
+}
+
+impl<G: PairingCurve> PreparedCache<G> {
+    /// Creates an empty cache holding the prepared forms of at most `capacity`
+    /// points. Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity of a prepared cache must be positive");
+        Self {
+            capacity,
+            prepared: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.prepared.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prepared.is_empty()
+    }
+
+    pub fn contains(&self, point: &G) -> bool {
+        self.prepared.contains_key(point)
+    }
+
+    /// Returns the prepared form of `point`, if cached.
+    pub fn get(&self, point: &G) -> Option<&G::Prepared> {
+        self.prepared.get(point)
+    }
+
+    /// Returns the prepared form of `point`, preparing and caching it if needed.
+    pub fn get_or_prepare(&mut self, point: &G) -> &G::Prepared {
+        if !self.prepared.contains_key(point) {
+            self.insert(*point, point.prepare());
+        }
+        &self.prepared[point]
+    }
+
+    /// Caches `prepared` as the prepared form of `point`, which it must be.
+    pub fn insert(&mut self, point: G, prepared: G::Prepared) {
+        if self.prepared.insert(point, prepared).is_none() {
+            if self.order.len() == self.capacity {
+                let evicted = self.order.pop_front().unwrap();
+                self.prepared.remove(&evicted);
+            }
+            self.order.push_back(point);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.prepared.clear();
+        self.order.clear();
+    }
+}
+
+/// Writes the capacity and the number of cached points as u32, followed by
+/// the cached points and their prepared forms in order of insertion.
+impl<G: PairingCurve> ToBytes for PreparedCache<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        writer.write_u32::<BigEndian>(self.capacity as u32)?;
+        writer.write_u32::<BigEndian>(self.order.len() as u32)?;
+        for point in &self.order {
+            point.write(&mut writer)?;
+            self.prepared[point].write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// The prepared forms are not checked against the points when reading, which
+/// would cost as much as preparing the points anew: caches must only be read
+/// from trusted storage.
+impl<G: PairingCurve> FromBytes for PreparedCache<G> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let capacity = reader.read_u32::<BigEndian>()? as usize;
+        let len = reader.read_u32::<BigEndian>()? as usize;
+        if capacity == 0 || len > capacity {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid prepared cache of {} points and capacity {}", len, capacity),
+            ));
+        }
+        let mut cache = Self::new(capacity);
+        for _ in 0..len {
+            let point = G::read(&mut reader)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let prepared = G::Prepared::read(&mut reader)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            cache.insert(point, prepared);
+        }
+        Ok(cache)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        curves::{bls12_381::G2Affine, AffineCurve, ProjectiveCurve},
+        UniformRand,
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_prepared_cache() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let points = (0..4)
+            .map(|_| <G2Affine as AffineCurve>::Projective::rand(rng).into_affine())
+            .collect::<Vec<G2Affine>>();
+
+        let mut cache = PreparedCache::<G2Affine>::new(3);
+        for p in &points[..3] {
+            assert_eq!(cache.get_or_prepare(p), &p.prepare());
+        }
+        cache.get_or_prepare(&points[0]);
+        assert_eq!(cache.len(), 3);
+
+        // The first point inserted is the first evicted.
+        assert_eq!(cache.get_or_prepare(&points[3]), &points[3].prepare());
+        assert_eq!(cache.len(), 3);
+        assert!(!cache.contains(&points[0]));
+        assert!(points[1..].iter().all(|p| cache.contains(p)));
+
+        let serialized = to_bytes!(cache).unwrap();
+        let deserialized = PreparedCache::<G2Affine>::read(serialized.as_slice()).unwrap();
+        assert_eq!(cache, deserialized);
+        assert!(PreparedCache::<G2Affine>::read(&serialized[..serialized.len() - 1]).is_err());
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(cache.get(&points[1]).is_none());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::field_new;
+use crate::{
+    biginteger::BigInteger256,
+    curves::{
+        models::{GLVParameters, ModelParameters, SWModelParameters},
+        short_weierstrass_projective::{GroupAffine, GroupProjective},
+    },
+    fields::{
+        secp256k1::{Fq, Fr},
+        Field,
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+pub type Secp256k1Affine = GroupAffine<Secp256k1Parameters>;
+pub type Secp256k1Projective = GroupProjective<Secp256k1Parameters>;
+
+/// `secp256k1` is the prime order short Weierstrass curve y² = x³ + 7 over
+/// the base field Fq, as specified in SEC 2, section 2.4.1:
+/// <https://www.secg.org/sec2-v2.pdf>
+///
+/// q = 2^256 - 2^32 - 977
+///   = 115792089237316195423570985008687907853269984665640564039457584007908834671663.
+///
+/// r = 115792089237316195423570985008687907852837564279074904382605163141518161494337.
+///
+/// The curve is not pairing-friendly; it is meant for signatures and
+/// commitments interoperating with existing ecosystems.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct Secp256k1Parameters;
+
+impl ModelParameters for Secp256k1Parameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl SWModelParameters for Secp256k1Parameters {
+    /// COEFF_A = 0
+    const COEFF_A: Fq = field_new!(Fq, BigInteger256([0x0, 0x0, 0x0, 0x0]));
+
+    /// COEFF_B = 7
+    const COEFF_B: Fq = field_new!(Fq, BigInteger256([
+        0x700001ab7,
+        0x0,
+        0x0,
+        0x0,
+    ]));
+
+    /// COFACTOR = 1
+    const COFACTOR: &'static [u64] = &[1];
+
+    /// COFACTOR_INV = 1
+    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger256([
+        0x402da1732fc9bebf,
+        0x4551231950b75fc4,
+        0x1,
+        0x0,
+    ]));
+
+    /// AFFINE_GENERATOR_COEFFS = (GENERATOR_X, GENERATOR_Y)
+    const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
+        (GENERATOR_X, GENERATOR_Y);
+
+    #[inline(always)]
+    fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
+        Self::BaseField::zero()
+    }
+}
+
+impl GLVParameters for Secp256k1Parameters {
+    /// ENDO_COEFF = 55594575648329892869085402983802832744385952214688224221778511981742606582254
+    const ENDO_COEFF: Fq = field_new!(Fq, BigInteger256([
+        0x58a4361c8e81894e,
+        0x3fde1631c4b80af,
+        0xf8e98978d02e3905,
+        0x7a4a36aebcbb3d53,
+    ]));
+
+    /// LAMBDA = 37718080363155996902926221483475020450927657555482586988616620542887997980018
+    const LAMBDA: Fr = field_new!(Fr, BigInteger256([
+        0xf07deb3dc9926c9e,
+        0x2c93e7ad83c6944c,
+        0x73a9660652697d91,
+        0x532840178558d639,
+    ]));
+
+    /// SCALAR_DECOMP_BASIS = ((64502973549206556628585045361533709077, -303414439467246543595250775667605759171),
+    ///     (367917413016453100223835821029139468248, 64502973549206556628585045361533709077))
+    const SCALAR_DECOMP_BASIS: [(Fr, Fr); 2] = [
+        (
+            field_new!(Fr, BigInteger256([
+                0xb326003c6621faab,
+                0x9c244f20bb8e4702,
+                0xe893209a45dbb030,
+                0x3daa8a1471e8ca7f,
+            ])),
+            field_new!(Fr, BigInteger256([
+                0xfacdf5bcc55d1b05,
+                0x9f925ae0b4a1b2f8,
+                0xea8e4b51753b808d,
+                0xddedf753620af939,
+            ])),
+        ),
+        (
+            field_new!(Fr, BigInteger256([
+                0x782a690c70fb20e7,
+                0xb740d126b6353445,
+                0xfe04d548d0a02fa1,
+                0x5fbc92c10fddd145,
+            ])),
+            field_new!(Fr, BigInteger256([
+                0xb326003c6621faab,
+                0x9c244f20bb8e4702,
+                0xe893209a45dbb030,
+                0x3daa8a1471e8ca7f,
+            ])),
+        ),
+    ];
+
+    /// SCALAR_DECOMP_COEFFS = (b2·2^384 / r, -b1·2^384 / r)
+    const SCALAR_DECOMP_COEFFS: [&'static [u64]; 2] = [
+        &[0xe893209a45dbb030, 0x3daa8a1471e8ca7f, 0xe86c90e49284eb15, 0x3086d221a7d46bcd],
+        &[0x1571b4ae8ac47f71, 0x221208ac9df506c6, 0x6f547fa90abfe4c4, 0xe4437ed6010e8828],
+    ];
+
+    const SCALAR_DECOMP_SHIFT: usize = 6;
+}
+
+/// GENERATOR_X =
+/// 55066263022277343669578718895168534326250603453777594175500187360389116729240
+pub const GENERATOR_X: Fq = field_new!(Fq, BigInteger256([
+    0xd7362e5a487e2097,
+    0x231e295329bc66db,
+    0x979f48c033fd129c,
+    0x9981e643e9089f48,
+]));
+
+/// GENERATOR_Y =
+/// 32670510020758816978083085130507043184471273380659243275938904335757337482424
+pub const GENERATOR_Y: Fq = field_new!(Fq, BigInteger256([
+    0xb15ea6d2d3dbabe2,
+    0x8dfc5d5d1f1dc64d,
+    0x70b6b59aac19c136,
+    0xcf3f851fd4a582d6,
+]));
@@ -0,0 +1,116 @@
+use crate::{
+    curves::{
+        models::GLVParameters, secp256k1::*, tests::curve_tests, AffineCurve, ProjectiveCurve,
+        ScalarRepr,
+    },
+    fields::secp256k1::{Fq, Fr, FrParameters},
+    groups::tests::{compression_test, group_test},
+    kat::{curve_answers, field_answers, KnownAnswers},
+    Field, FpParameters, FromCompressedBits, PrimeField, ToCompressedBits,
+};
+use rand;
+use std::{convert::TryFrom, str::FromStr};
+
+#[test]
+fn test_projective_curve() {
+    curve_tests::<Secp256k1Projective>();
+}
+
+#[test]
+fn test_projective_group() {
+    let a: Secp256k1Projective = rand::random();
+    let b: Secp256k1Projective = rand::random();
+    group_test(a, b);
+}
+
+#[test]
+fn test_generator() {
+    let generator = Secp256k1Affine::prime_subgroup_generator();
+    assert!(generator.is_on_curve());
+    assert!(generator.is_in_correct_subgroup_assuming_on_curve());
+}
+
+#[test]
+fn test_scalar_multiplication() {
+    let f1 = Fr::from_str(
+        "77059549740374936337596179780007572461065571555507600191520924336939429631266",
+    )
+    .unwrap();
+    let f2 = Fr::from_str(
+        "57068342215440698567564368834304843313972799247029998661603969804853926820579",
+    )
+    .unwrap();
+    let f1g = Secp256k1Affine::new(
+        Fq::from_str(
+            "23960696573610029253367988531088137163395307586261939660421638862381187549638",
+        )
+        .unwrap(),
+        Fq::from_str(
+            "5176714262835066281222529495396963740342889891785920566957581938958806065714",
+        )
+        .unwrap(),
+        false,
+    );
+    let f1f2g = Secp256k1Affine::new(
+        Fq::from_str(
+            "108253343866477740770533727388014682299727198630272630253909954081310647081813",
+        )
+        .unwrap(),
+        Fq::from_str(
+            "31761887013193154411311596434325516509524828756226005569953674774443724828965",
+        )
+        .unwrap(),
+        false,
+    );
+    assert!(f1g.is_on_curve());
+    assert!(f1f2g.is_on_curve());
+
+    let g = Secp256k1Affine::prime_subgroup_generator();
+    assert_eq!(g.mul(f1).into_affine(), f1g);
+    assert_eq!(f1g.mul(f2).into_affine(), f1f2g);
+    assert_eq!(g.mul(f1 * &f2).into_affine(), f1f2g);
+}
+
+#[test]
+fn test_scalar_repr() {
+    let g = Secp256k1Affine::prime_subgroup_generator();
+    let s: Fr = rand::random();
+
+    let repr = ScalarRepr::<Fr>::try_from(s.into_repr()).unwrap();
+    assert_eq!(g.mul(repr), g.mul(s));
+    assert_eq!(g.mul(&s), g.mul(s));
+
+    // Big integers not smaller than the group order don't represent a scalar.
+    assert!(ScalarRepr::<Fr>::try_from(FrParameters::MODULUS).is_err());
+}
+
+#[test]
+fn test_glv() {
+    let g = Secp256k1Affine::prime_subgroup_generator();
+    assert_eq!(g.endomorphism(), g.mul(Secp256k1Parameters::LAMBDA).into_affine());
+    assert!(g.mul_glv(Fr::zero()).is_zero());
+
+    for _ in 0..10 {
+        let p = rand::random::<Secp256k1Projective>().into_affine();
+        let s: Fr = rand::random();
+        assert_eq!(p.mul_glv(s), p.mul(s));
+    }
+}
+
+#[test]
+fn test_compression_decompression() {
+    let g = Secp256k1Affine::prime_subgroup_generator();
+    let p: Secp256k1Affine = rand::random::<Secp256k1Projective>().into_affine();
+    let (even, odd) = if p.y.is_odd() { (-p, p) } else { (p, -p) };
+    compression_test::<Secp256k1Affine>(even, odd);
+    assert_eq!(Secp256k1Affine::decompress(g.compress()).unwrap(), g);
+}
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<Secp256k1Affine>(&mut answers, "group");
+    answers.check("src/curves/secp256k1/test_vec/secp256k1_kat");
+}
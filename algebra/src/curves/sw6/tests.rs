@@ -4,7 +4,9 @@ use crate::{
         tests::curve_tests,
         AffineCurve, PairingEngine,
     },
+    fields::sw6::{Fq, Fr},
     groups::tests::group_test,
+    kat::{curve_answers, field_answers, pairing_answers, KnownAnswers},
 };
 
 #[test]
@@ -109,3 +111,14 @@ fn print_g2_generator() {
     println!("pub const G2_GENERATOR_Y_C1: Fq = Fq::new({});", y_c1.0);
     println!("pub const G2_GENERATOR_Y_C2: Fq = Fq::new({});", y_c2.0);
 }
+
+#[test]
+fn test_kat() {
+    let mut answers = KnownAnswers::new();
+    field_answers::<Fq>(&mut answers, "fq");
+    field_answers::<Fr>(&mut answers, "fr");
+    curve_answers::<G1Affine>(&mut answers, "g1");
+    curve_answers::<G2Affine>(&mut answers, "g2");
+    pairing_answers::<SW6>(&mut answers, "pairing");
+    answers.check("src/curves/sw6/test_vec/sw6_kat");
+}
@@ -1,10 +1,11 @@
 use crate::{
-    curves::{AffineCurve, ProjectiveCurve},
-    fields::{Field, PrimeField},
+    curves::{AffineCurve, PairingCurve, PairingEngine, ProjectiveCurve},
+    fields::Field,
 };
-use crate::UniformRand;
+use crate::{ToBytes, UniformRand};
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
+use std::collections::HashSet;
 
 pub const ITERATIONS: usize = 10;
 
@@ -101,18 +102,18 @@ fn random_multiplication_test<G: ProjectiveCurve>() {
         // s ( a + b )
         let mut tmp1 = a;
         tmp1.add_assign(&b);
-        tmp1.mul_assign(s.into_repr());
+        tmp1.mul_assign(s);
 
         // sa + sb
-        a.mul_assign(s.into_repr());
-        b.mul_assign(s.into_repr());
+        a.mul_assign(s);
+        b.mul_assign(s);
 
         let mut tmp2 = a;
         tmp2.add_assign(&b);
 
         // Affine multiplication
-        let mut tmp3 = a_affine.mul(s.into_repr());
-        tmp3.add_assign(&b_affine.mul(s.into_repr()));
+        let mut tmp3 = a_affine.mul(s);
+        tmp3.add_assign(&b_affine.mul(s));
 
         assert_eq!(tmp1, tmp2);
         assert_eq!(tmp1, tmp3);
@@ -221,6 +222,56 @@ fn random_transformation_test<G: ProjectiveCurve>() {
     }
 }
 
+fn random_compression_test<G: ProjectiveCurve>() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    for _ in 0..ITERATIONS {
+        let a = G::rand(&mut rng).into_affine();
+        let compressed = a.to_compressed();
+        assert_eq!(G::Affine::from_compressed(&compressed).unwrap(), a);
+        assert_eq!(G::Affine::from_compressed(&(-a).to_compressed()).unwrap(), -a);
+
+        // The x-coordinate and a byte of flags, instead of both coordinates
+        assert!(compressed.len() < to_bytes!(a).unwrap().len());
+
+        // Truncated, extended, or with unknown flags
+        assert!(G::Affine::from_compressed(&compressed[..compressed.len() - 1]).is_err());
+        let mut extended = compressed.clone();
+        extended.push(0);
+        assert!(G::Affine::from_compressed(&extended).is_err());
+        let mut flagged = compressed.clone();
+        *flagged.last_mut().unwrap() |= 1 << 7;
+        assert!(G::Affine::from_compressed(&flagged).is_err());
+    }
+
+    let zero = G::Affine::zero();
+    assert_eq!(G::Affine::from_compressed(&zero.to_compressed()).unwrap(), zero);
+}
+
+/// Checks that `multi_pairing` computes the product of the pairings.
+pub fn multi_pairing_test<E: PairingEngine>() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let a = E::G1Projective::rand(&mut rng).into_affine();
+    let b = E::G2Projective::rand(&mut rng).into_affine();
+    let c = E::G1Projective::rand(&mut rng).into_affine();
+    let d = E::G2Projective::rand(&mut rng).into_affine();
+
+    let pairs = vec![(a.prepare(), b.prepare()), (c.prepare(), d.prepare())];
+    assert_eq!(E::multi_pairing(&pairs), E::pairing(a, b) * &E::pairing(c, d));
+    let refs = [(&pairs[0].0, &pairs[0].1), (&pairs[1].0, &pairs[1].1)];
+    assert_eq!(E::multi_miller_loop(&pairs), E::miller_loop(refs.iter()));
+    assert!(E::multi_pairing(&[]).is_one());
+
+    // e(s·a, b)·e(-a, s·b) = 1
+    let s = E::Fr::rand(&mut rng);
+    let pairs = vec![
+        (a.mul(s).into_affine().prepare(), b.prepare()),
+        ((-a).prepare(), b.mul(s).into_affine().prepare()),
+    ];
+    assert!(E::multi_pairing(&pairs).is_one());
+}
+
 pub fn curve_tests<G: ProjectiveCurve>() {
     let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
 
@@ -274,9 +325,29 @@ pub fn curve_tests<G: ProjectiveCurve>() {
         assert_eq!(b, c);
     }
 
+    // Hashing agrees with equality, whatever the projective representation.
+    {
+        let a = G::rand(&mut rng);
+        let mut b = a.double();
+        b.add_assign(&-a);
+        assert_eq!(a, b);
+
+        let mut set = HashSet::new();
+        set.insert(a);
+        set.insert(b);
+        set.insert(a.into_affine().into_projective());
+        assert_eq!(set.len(), 1);
+
+        let mut zeros = HashSet::new();
+        zeros.insert(G::zero());
+        zeros.insert(a - &a);
+        assert_eq!(zeros.len(), 1);
+    }
+
     random_addition_test::<G>();
     random_multiplication_test::<G>();
     random_doubling_test::<G>();
     random_negation_test::<G>();
     random_transformation_test::<G>();
+    random_compression_test::<G>();
 }
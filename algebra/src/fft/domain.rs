@@ -16,6 +16,7 @@ use rayon::prelude::*;
 use rand::Rng;
 
 use super::multicore::Worker;
+use crate::tuning::tuning_strategy;
 
 /// Defines a domain over which finite field (I)FFTs can be performed. Works
 /// only for fields that have a large multiplicative subgroup of size that is
@@ -47,7 +48,7 @@ impl<F: PrimeField> fmt::Debug for EvaluationDomain<F> {
 
 impl<F: PrimeField> EvaluationDomain<F> {
     fn calculate_chunk_size(size: usize) -> usize {
-        size / rayon::current_num_threads()
+        std::cmp::max(tuning_strategy().fft_chunk_size(size, rayon::current_num_threads()), 1)
     }
 
     /// Sample an element that is *not* in the domain.
@@ -137,7 +138,7 @@ impl<F: PrimeField> EvaluationDomain<F> {
         evals.par_iter_mut().for_each(|val| *val *= &self.size_inv);
     }
 
-    fn distribute_powers(coeffs: &mut Vec<F>, g: F) {
+    pub(crate) fn distribute_powers(coeffs: &mut Vec<F>, g: F) {
         Worker::new().scope(coeffs.len(), |scope, chunk| {
             for (i, v) in coeffs.chunks_mut(chunk).enumerate() {
                 scope.spawn(move |_| {
@@ -239,6 +240,16 @@ impl<F: PrimeField> EvaluationDomain<F> {
         }
     }
 
+    /// Divide the evaluations of a polynomial over the coset of the domain,
+    /// as given by `coset_fft`, by those of the vanishing polynomial. As the
+    /// latter is constant on the coset, this is a multiplication by a single
+    /// inverse.
+    pub fn divide_by_vanishing_poly_on_coset(&self, evals: &[F]) -> Vec<F> {
+        let mut evals = evals.to_vec();
+        self.divide_by_vanishing_poly_on_coset_in_place(&mut evals);
+        evals
+    }
+
     /// The target polynomial is the zero polynomial in our
     /// evaluation domain, so we must perform division over
     /// a coset.
@@ -307,6 +318,8 @@ fn best_fft<F: PrimeField>(a: &mut [F], worker: &Worker, omega: F, log_n: u32) {
 
     if log_n <= log_cpus {
         serial_fft(a, omega, log_n);
+    } else if log_n >= tuning_strategy().four_step_fft_min_log_size() {
+        four_step_fft(a, omega, log_n);
     } else {
         parallel_fft(a, worker, omega, log_n, log_cpus);
     }
@@ -415,6 +428,71 @@ pub(crate) fn parallel_fft<F: PrimeField>(
     });
 }
 
+/// Computes the FFT of `a` by the four-step algorithm: Viewing `a` as a
+/// `n1 x n2` matrix, stored row-major, with `n1 * n2 = n`, it performs
+/// 1. `n2` FFTs of size `n1` over the columns,
+/// 2. the multiplication of the entry `(k1, j2)` by `omega^(k1 * j2)`,
+/// 3. `n1` FFTs of size `n2` over the rows,
+///
+/// and reads off the result column-wise. The columns are made contiguous by
+/// transposition, so that all the small FFTs run on contiguous memory and
+/// in parallel.
+pub(crate) fn four_step_fft<F: PrimeField>(a: &mut [F], omega: F, log_n: u32) {
+    let n = a.len();
+    assert_eq!(n, 1 << log_n);
+
+    let log_n1 = log_n / 2;
+    let log_n2 = log_n - log_n1;
+    let n1 = 1 << log_n1;
+    let n2 = 1 << log_n2;
+
+    let mut tmp = vec![F::zero(); n];
+    transpose(a, &mut tmp, n1, n2);
+
+    // The FFTs over the columns, now rows of `tmp`, and the twiddle factors.
+    let omega_n1 = omega.pow(&[n2 as u64]);
+    tmp.par_chunks_mut(n1).enumerate().for_each(|(j2, row)| {
+        serial_fft(row, omega_n1, log_n1);
+        let w = omega.pow(&[j2 as u64]);
+        let mut twiddle = F::one();
+        for x in row.iter_mut() {
+            *x *= &twiddle;
+            twiddle *= &w;
+        }
+    });
+
+    // The FFTs over the rows.
+    transpose(&tmp, a, n2, n1);
+    let omega_n2 = omega.pow(&[n1 as u64]);
+    a.par_chunks_mut(n2).for_each(|row| serial_fft(row, omega_n2, log_n2));
+
+    // The output at `k1 + n1 * k2` is the entry `(k1, k2)`.
+    transpose(a, &mut tmp, n1, n2);
+    a.copy_from_slice(&tmp);
+}
+
+/// Writes the transpose of the `rows x cols` matrix `src` into `dst`, both
+/// stored row-major. Proceeds in square blocks to keep the accesses to `src`
+/// cache local, and in parallel over the blocks of rows of `dst`.
+pub(crate) fn transpose<F: Copy + Send + Sync>(src: &[F], dst: &mut [F], rows: usize, cols: usize) {
+    const BLOCK_SIZE: usize = 16;
+
+    assert_eq!(src.len(), rows * cols);
+    assert_eq!(dst.len(), rows * cols);
+
+    dst.par_chunks_mut(BLOCK_SIZE * rows).enumerate().for_each(|(b, dst_rows)| {
+        let first_col = b * BLOCK_SIZE;
+        let num_cols = dst_rows.len() / rows;
+        for first_row in (0..rows).step_by(BLOCK_SIZE) {
+            for c in 0..num_cols {
+                for r in first_row..std::cmp::min(first_row + BLOCK_SIZE, rows) {
+                    dst_rows[c * rows + r] = src[r * cols + first_col + c];
+                }
+            }
+        }
+    });
+}
+
 /// An iterator over the elements of the domain.
 pub struct Elements<F: PrimeField> {
     cur_elem: F,
@@ -0,0 +1,192 @@
+//! This module contains a `GeneralEvaluationDomain`, which is either an
+//! `EvaluationDomain` or a `MixedRadixEvaluationDomain`, whichever of the
+//! two is the smaller one for a given number of coefficients.
+
+use std::fmt;
+use crate::PrimeField;
+use rand::Rng;
+
+use super::{
+    domain::{EvaluationDomain, Elements as Radix2Elements},
+    mixed_radix::{MixedRadixEvaluationDomain, Elements as MixedRadixElements},
+};
+
+/// An evaluation domain over which finite field (I)FFTs can be performed,
+/// of power-of-2 size or, for fields specifying a `SMALL_SUBGROUP_BASE`, of
+/// mixed radix size.
+#[derive(Copy, Clone, Hash, Eq, PartialEq)]
+pub enum GeneralEvaluationDomain<F: PrimeField> {
+    /// A domain of power-of-2 size.
+    Radix2(EvaluationDomain<F>),
+    /// A domain of size `2^i * q^j`.
+    MixedRadix(MixedRadixEvaluationDomain<F>),
+}
+
+impl<F: PrimeField> fmt::Debug for GeneralEvaluationDomain<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneralEvaluationDomain::Radix2(domain) => domain.fmt(f),
+            GeneralEvaluationDomain::MixedRadix(domain) => domain.fmt(f),
+        }
+    }
+}
+
+macro_rules! dispatch {
+    ($self:ident, $domain:ident => $e:expr) => {
+        match $self {
+            GeneralEvaluationDomain::Radix2($domain) => $e,
+            GeneralEvaluationDomain::MixedRadix($domain) => $e,
+        }
+    };
+}
+
+impl<F: PrimeField> GeneralEvaluationDomain<F> {
+    /// Construct the smallest domain that is large enough for evaluations of
+    /// a polynomial having `num_coeffs` coefficients. The power-of-2 domain
+    /// is preferred among domains of the same size.
+    pub fn new(num_coeffs: usize) -> Option<Self> {
+        let radix2_size = EvaluationDomain::<F>::compute_size_of_domain(num_coeffs);
+        let mixed_radix_size = MixedRadixEvaluationDomain::<F>::compute_size_of_domain(num_coeffs);
+
+        match (radix2_size, mixed_radix_size) {
+            (Some(radix2_size), Some(mixed_radix_size)) if mixed_radix_size < radix2_size => {
+                MixedRadixEvaluationDomain::new(num_coeffs).map(GeneralEvaluationDomain::MixedRadix)
+            },
+            (None, Some(_)) => {
+                MixedRadixEvaluationDomain::new(num_coeffs).map(GeneralEvaluationDomain::MixedRadix)
+            },
+            _ => EvaluationDomain::new(num_coeffs).map(GeneralEvaluationDomain::Radix2),
+        }
+    }
+
+    /// Return the size of the smallest domain that is large enough for
+    /// evaluations of a polynomial having `num_coeffs` coefficients.
+    pub fn compute_size_of_domain(num_coeffs: usize) -> Option<usize> {
+        let radix2_size = EvaluationDomain::<F>::compute_size_of_domain(num_coeffs);
+        let mixed_radix_size = MixedRadixEvaluationDomain::<F>::compute_size_of_domain(num_coeffs);
+
+        match (radix2_size, mixed_radix_size) {
+            (Some(radix2_size), Some(mixed_radix_size)) => Some(std::cmp::min(radix2_size, mixed_radix_size)),
+            (radix2_size, mixed_radix_size) => radix2_size.or(mixed_radix_size),
+        }
+    }
+
+    /// Sample an element that is *not* in the domain.
+    pub fn sample_element_outside_domain<R: Rng>(&self, rng: &mut R) -> F {
+        dispatch!(self, domain => domain.sample_element_outside_domain(rng))
+    }
+
+    /// Return the size of `self`.
+    pub fn size(&self) -> usize {
+        dispatch!(self, domain => domain.size())
+    }
+
+    /// Return a generator of the multiplicative subgroup.
+    pub fn group_gen(&self) -> F {
+        dispatch!(self, domain => domain.group_gen)
+    }
+
+    /// Compute a FFT.
+    pub fn fft(&self, coeffs: &[F]) -> Vec<F> {
+        dispatch!(self, domain => domain.fft(coeffs))
+    }
+
+    /// Compute a FFT, modifying the vector in place.
+    pub fn fft_in_place(&self, coeffs: &mut Vec<F>) {
+        dispatch!(self, domain => domain.fft_in_place(coeffs))
+    }
+
+    /// Compute a IFFT.
+    pub fn ifft(&self, evals: &[F]) -> Vec<F> {
+        dispatch!(self, domain => domain.ifft(evals))
+    }
+
+    /// Compute a IFFT, modifying the vector in place.
+    pub fn ifft_in_place(&self, evals: &mut Vec<F>) {
+        dispatch!(self, domain => domain.ifft_in_place(evals))
+    }
+
+    /// Compute a FFT over a coset of the domain.
+    pub fn coset_fft(&self, coeffs: &[F]) -> Vec<F> {
+        dispatch!(self, domain => domain.coset_fft(coeffs))
+    }
+
+    /// Compute a FFT over a coset of the domain, modifying the input vector
+    /// in place.
+    pub fn coset_fft_in_place(&self, coeffs: &mut Vec<F>) {
+        dispatch!(self, domain => domain.coset_fft_in_place(coeffs))
+    }
+
+    /// Compute a IFFT over a coset of the domain.
+    pub fn coset_ifft(&self, evals: &[F]) -> Vec<F> {
+        dispatch!(self, domain => domain.coset_ifft(evals))
+    }
+
+    /// Compute a IFFT over a coset of the domain, modifying the input vector in place.
+    pub fn coset_ifft_in_place(&self, evals: &mut Vec<F>) {
+        dispatch!(self, domain => domain.coset_ifft_in_place(evals))
+    }
+
+    /// Evaluate all the lagrange polynomials defined by this domain at the point
+    /// `tau`.
+    pub fn evaluate_all_lagrange_coefficients(&self, tau: F) -> Vec<F> {
+        dispatch!(self, domain => domain.evaluate_all_lagrange_coefficients(tau))
+    }
+
+    /// Return the sparse vanishing polynomial.
+    pub fn vanishing_polynomial(&self) -> crate::SparsePolynomial<F> {
+        dispatch!(self, domain => domain.vanishing_polynomial())
+    }
+
+    /// This evaluates the vanishing polynomial for this domain at tau.
+    pub fn evaluate_vanishing_polynomial(&self, tau: F) -> F {
+        dispatch!(self, domain => domain.evaluate_vanishing_polynomial(tau))
+    }
+
+    /// Return an iterator over the elements of the domain.
+    pub fn elements(&self) -> Elements<F> {
+        match self {
+            GeneralEvaluationDomain::Radix2(domain) => Elements::Radix2(domain.elements()),
+            GeneralEvaluationDomain::MixedRadix(domain) => Elements::MixedRadix(domain.elements()),
+        }
+    }
+
+    /// Divide the evaluations of a polynomial over the coset of the domain,
+    /// as given by `coset_fft`, by those of the vanishing polynomial.
+    pub fn divide_by_vanishing_poly_on_coset(&self, evals: &[F]) -> Vec<F> {
+        dispatch!(self, domain => domain.divide_by_vanishing_poly_on_coset(evals))
+    }
+
+    /// The target polynomial is the zero polynomial in our
+    /// evaluation domain, so we must perform division over
+    /// a coset.
+    pub fn divide_by_vanishing_poly_on_coset_in_place(&self, evals: &mut [F]) {
+        dispatch!(self, domain => domain.divide_by_vanishing_poly_on_coset_in_place(evals))
+    }
+
+    /// Perform O(n) multiplication of two polynomials that are presented by their
+    /// evaluations in the domain.
+    /// Returns the evaluations of the product over the domain.
+    #[must_use]
+    pub fn mul_polynomials_in_evaluation_domain(&self, self_evals: &[F], other_evals: &[F]) -> Vec<F> {
+        dispatch!(self, domain => domain.mul_polynomials_in_evaluation_domain(self_evals, other_evals))
+    }
+}
+
+/// An iterator over the elements of the domain.
+pub enum Elements<F: PrimeField> {
+    /// The elements of a power-of-2 domain.
+    Radix2(Radix2Elements<F>),
+    /// The elements of a mixed radix domain.
+    MixedRadix(MixedRadixElements<F>),
+}
+
+impl<F: PrimeField> Iterator for Elements<F> {
+    type Item = F;
+    fn next(&mut self) -> Option<F> {
+        match self {
+            Elements::Radix2(elements) => elements.next(),
+            Elements::MixedRadix(elements) => elements.next(),
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! This module contains a `MixedRadixEvaluationDomain` for fields whose
+//! multiplicative group has, apart from a power-of-2 subgroup, a subgroup of
+//! order `q^k` for a small odd prime `q`, as specified by the
+//! `SMALL_SUBGROUP_BASE` of the field parameters. Its sizes are of the form
+//! `2^i * q^j`, which allows to choose domains considerably closer to the
+//! number of constraints than the next power of two, and domains beyond the
+//! two-adicity of the field.
+
+use std::fmt;
+use crate::{FpParameters, PrimeField};
+use rayon::prelude::*;
+use rand::Rng;
+
+use super::domain::{serial_fft, transpose, EvaluationDomain};
+use crate::tuning::tuning_strategy;
+
+/// Defines a domain of size `2^i * q^j` over which finite field (I)FFTs can
+/// be performed, with `q` the `SMALL_SUBGROUP_BASE` of the field.
+#[derive(Copy, Clone, Hash, Eq, PartialEq)]
+pub struct MixedRadixEvaluationDomain<F: PrimeField> {
+    /// The size of the domain.
+    pub size:                  u64,
+    /// `log_2` of the power-of-2 part of `self.size`.
+    pub two_adicity:           u32,
+    /// `log_q` of the power-of-`q` part of `self.size`.
+    pub small_subgroup_adicity: u32,
+    /// Size of the domain as a field element.
+    pub size_as_field_element: F,
+    /// Inverse of the size in the field.
+    pub size_inv:              F,
+    /// A generator of the subgroup.
+    pub group_gen:             F,
+    /// Inverse of the generator of the subgroup.
+    pub group_gen_inv:         F,
+    /// Multiplicative generator of the finite field.
+    pub generator_inv:         F,
+}
+
+impl<F: PrimeField> fmt::Debug for MixedRadixEvaluationDomain<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Mixed radix multiplicative subgroup of size {}", self.size)
+    }
+}
+
+impl<F: PrimeField> MixedRadixEvaluationDomain<F> {
+    fn calculate_chunk_size(size: usize) -> usize {
+        std::cmp::max(tuning_strategy().fft_chunk_size(size, rayon::current_num_threads()), 1)
+    }
+
+    /// Returns the `SMALL_SUBGROUP_BASE` `q` and its adicity `k`, if the
+    /// field specifies them.
+    fn small_subgroup() -> Option<(u64, u32)> {
+        match (F::Params::SMALL_SUBGROUP_BASE, F::Params::SMALL_SUBGROUP_BASE_ADICITY) {
+            (Some(q), Some(k)) => Some((q as u64, k)),
+            _ => None,
+        }
+    }
+
+    /// Returns the exponents `(i, j)` of the smallest size `2^i * q^j` which
+    /// is at least `num_coeffs`, with `i <= TWO_ADICITY` and `j <= k`.
+    fn best_size_exponents(num_coeffs: usize) -> Option<(u32, u32)> {
+        let (q, k) = Self::small_subgroup()?;
+        let num_coeffs = num_coeffs as u64;
+
+        let mut best: Option<(u64, u32, u32)> = None;
+        let mut q_power = 1u64;
+        for j in 0..=k {
+            // The smallest power of 2 lifting q^j to at least `num_coeffs`.
+            let mut i = 0;
+            let mut size = q_power;
+            while size < num_coeffs && i < F::Params::TWO_ADICITY && size >> 63 == 0 {
+                size <<= 1;
+                i += 1;
+            }
+            if size >= num_coeffs && best.map_or(true, |(best_size, _, _)| size < best_size) {
+                best = Some((size, i, j));
+            }
+            q_power = match q_power.checked_mul(q) {
+                Some(q_power) => q_power,
+                None => break,
+            };
+        }
+        best.map(|(_, i, j)| (i, j))
+    }
+
+    /// Sample an element that is *not* in the domain.
+    pub fn sample_element_outside_domain<R: Rng>(&self, rng: &mut R) -> F {
+        let mut t = F::rand(rng);
+        while self.evaluate_vanishing_polynomial(t).is_zero() {
+            t = F::rand(rng);
+        }
+        t
+    }
+
+    /// Construct the smallest domain that is large enough for evaluations of
+    /// a polynomial having `num_coeffs` coefficients. Returns `None` if the
+    /// field does not specify a `SMALL_SUBGROUP_BASE`, or if there is no
+    /// such domain.
+    pub fn new(num_coeffs: usize) -> Option<Self> {
+        let (q, k) = Self::small_subgroup()?;
+        let (two_adicity, small_subgroup_adicity) = Self::best_size_exponents(num_coeffs)?;
+        let size = q.pow(small_subgroup_adicity) << two_adicity;
+
+        // Compute the generator for the multiplicative subgroup, the
+        // (2^(s - i) * q^(k - j))-th power of the 2^s * q^k root of unity.
+        let mut group_gen = F::large_subgroup_root_of_unity()?;
+        for _ in two_adicity..F::Params::TWO_ADICITY {
+            group_gen.square_in_place();
+        }
+        for _ in small_subgroup_adicity..k {
+            group_gen = group_gen.pow(&[q]);
+        }
+
+        let size_as_field_element = F::from_repr(F::BigInt::from(size));
+        let size_inv = size_as_field_element.inverse()?;
+
+        Some(MixedRadixEvaluationDomain {
+            size,
+            two_adicity,
+            small_subgroup_adicity,
+            size_as_field_element,
+            size_inv,
+            group_gen,
+            group_gen_inv: group_gen.inverse()?,
+            generator_inv: F::multiplicative_generator().inverse()?
+        })
+    }
+
+    /// Return the size of the smallest domain that is large enough for
+    /// evaluations of a polynomial having `num_coeffs` coefficients.
+    pub fn compute_size_of_domain(num_coeffs: usize) -> Option<usize> {
+        let (q, _) = Self::small_subgroup()?;
+        let (i, j) = Self::best_size_exponents(num_coeffs)?;
+        Some((q.pow(j) << i) as usize)
+    }
+
+    /// Return the size of `self`.
+    pub fn size(&self) -> usize {
+        self.size as usize
+    }
+
+    fn small_subgroup_base(&self) -> usize {
+        F::Params::SMALL_SUBGROUP_BASE.unwrap() as usize
+    }
+
+    /// Compute a FFT.
+    pub fn fft(&self, coeffs: &[F]) -> Vec<F> {
+        let mut coeffs = coeffs.to_vec();
+        self.fft_in_place(&mut coeffs);
+        coeffs
+    }
+
+    /// Compute a FFT, modifying the vector in place.
+    pub fn fft_in_place(&self, coeffs: &mut Vec<F>) {
+        coeffs.resize(self.size(), F::zero());
+        mixed_radix_fft(
+            coeffs,
+            self.group_gen,
+            self.small_subgroup_base(),
+            self.small_subgroup_adicity,
+            self.two_adicity,
+        );
+    }
+
+    /// Compute a IFFT.
+    pub fn ifft(&self, evals: &[F]) -> Vec<F> {
+        let mut evals = evals.to_vec();
+        self.ifft_in_place(&mut evals);
+        evals
+    }
+
+    /// Compute a IFFT, modifying the vector in place.
+    #[inline]
+    pub fn ifft_in_place(&self, evals: &mut Vec<F>) {
+        evals.resize(self.size(), F::zero());
+        mixed_radix_fft(
+            evals,
+            self.group_gen_inv,
+            self.small_subgroup_base(),
+            self.small_subgroup_adicity,
+            self.two_adicity,
+        );
+        evals.par_iter_mut().for_each(|val| *val *= &self.size_inv);
+    }
+
+    /// Compute a FFT over a coset of the domain.
+    pub fn coset_fft(&self, coeffs: &[F]) -> Vec<F> {
+        let mut coeffs = coeffs.to_vec();
+        self.coset_fft_in_place(&mut coeffs);
+        coeffs
+    }
+
+    /// Compute a FFT over a coset of the domain, modifying the input vector
+    /// in place.
+    pub fn coset_fft_in_place(&self, coeffs: &mut Vec<F>) {
+        EvaluationDomain::<F>::distribute_powers(coeffs, F::multiplicative_generator());
+        self.fft_in_place(coeffs);
+    }
+
+    /// Compute a IFFT over a coset of the domain.
+    pub fn coset_ifft(&self, evals: &[F]) -> Vec<F> {
+        let mut evals = evals.to_vec();
+        self.coset_ifft_in_place(&mut evals);
+        evals
+    }
+
+    /// Compute a IFFT over a coset of the domain, modifying the input vector in place.
+    pub fn coset_ifft_in_place(&self, evals: &mut Vec<F>) {
+        self.ifft_in_place(evals);
+        EvaluationDomain::<F>::distribute_powers(evals, self.generator_inv);
+    }
+
+    /// Evaluate all the lagrange polynomials defined by this domain at the point
+    /// `tau`.
+    pub fn evaluate_all_lagrange_coefficients(&self, tau: F) -> Vec<F> {
+        let size = self.size as usize;
+        let t_size = tau.pow(&[self.size]);
+        let one = F::one();
+        if t_size.is_one() {
+            let mut u = vec![F::zero(); size];
+            let mut omega_i = one;
+            for u_i in u.iter_mut() {
+                if omega_i == tau {
+                    *u_i = one;
+                    break;
+                }
+                omega_i *= &self.group_gen;
+            }
+            u
+        } else {
+            use crate::fields::batch_inversion;
+
+            let mut l = (t_size - &one) * &self.size_inv;
+            let mut r = one;
+            let mut u = vec![F::zero(); size];
+            let mut ls = vec![F::zero(); size];
+            for (u_i, ls_i) in u.iter_mut().zip(ls.iter_mut()) {
+                *u_i = tau - &r;
+                *ls_i = l;
+                l *= &self.group_gen;
+                r *= &self.group_gen;
+            }
+
+            batch_inversion(u.as_mut_slice());
+            u.par_iter_mut().zip(ls).for_each(|(tau_minus_r, l)| {
+                *tau_minus_r = l * tau_minus_r;
+            });
+            u
+        }
+    }
+
+    /// Return the sparse vanishing polynomial.
+    pub fn vanishing_polynomial(&self) -> crate::SparsePolynomial<F> {
+        let coeffs = vec![(0, -F::one()), (self.size(), F::one())];
+        crate::SparsePolynomial::from_coefficients_vec(coeffs)
+    }
+
+    /// This evaluates the vanishing polynomial for this domain at tau.
+    /// For multiplicative subgroups, this polynomial is `z(X) = X^self.size - 1`.
+    pub fn evaluate_vanishing_polynomial(&self, tau: F) -> F {
+        tau.pow(&[self.size]) - &F::one()
+    }
+
+    /// Return an iterator over the elements of the domain.
+    pub fn elements(&self) -> Elements<F> {
+        Elements {
+            cur_elem: F::one(),
+            cur_pow: 0,
+            domain: *self,
+        }
+    }
+
+    /// Divide the evaluations of a polynomial over the coset of the domain,
+    /// as given by `coset_fft`, by those of the vanishing polynomial. As the
+    /// latter is constant on the coset, this is a multiplication by a single
+    /// inverse.
+    pub fn divide_by_vanishing_poly_on_coset(&self, evals: &[F]) -> Vec<F> {
+        let mut evals = evals.to_vec();
+        self.divide_by_vanishing_poly_on_coset_in_place(&mut evals);
+        evals
+    }
+
+    /// The target polynomial is the zero polynomial in our
+    /// evaluation domain, so we must perform division over
+    /// a coset.
+    pub fn divide_by_vanishing_poly_on_coset_in_place(&self, evals: &mut [F]) {
+        let i = self
+            .evaluate_vanishing_polynomial(F::multiplicative_generator())
+            .inverse()
+            .unwrap();
+
+        evals.par_iter_mut().for_each(|eval| *eval *= &i);
+    }
+
+    /// Perform O(n) multiplication of two polynomials that are presented by their
+    /// evaluations in the domain.
+    /// Returns the evaluations of the product over the domain.
+    #[must_use]
+    pub fn mul_polynomials_in_evaluation_domain(&self, self_evals: &[F], other_evals: &[F]) -> Vec<F> {
+        assert_eq!(self_evals.len(), other_evals.len());
+        let mut result = self_evals.to_vec();
+        let chunk_size = Self::calculate_chunk_size(self.size());
+        result
+            .par_chunks_mut(chunk_size)
+            .zip(other_evals.par_chunks(chunk_size))
+            .for_each(|(a, b)| {
+                for (a, b) in a.iter_mut().zip(b) {
+                    *a *= b;
+                }
+            });
+        result
+    }
+}
+
+/// Computes the FFT of `a`, of size `n = q^j * 2^log_two`, for the `n`-th
+/// root of unity `omega` by decimation in time: The `q` subsequences
+/// `a[r], a[r + q], a[r + 2q], ...` are transformed recursively (in parallel),
+/// and recombined by FFTs of size `q`. The remaining power-of-2 FFTs are done
+/// by `serial_fft`.
+pub(crate) fn mixed_radix_fft<F: PrimeField>(a: &mut [F], omega: F, q: usize, j: u32, log_two: u32) {
+    let n = a.len();
+    assert_eq!(n, q.pow(j) << log_two);
+
+    if j == 0 {
+        serial_fft(a, omega, log_two);
+        return;
+    }
+
+    let m = n / q;
+    let omega_q = omega.pow(&[q as u64]);
+    let mut subs = (0..q)
+        .map(|r| a.iter().skip(r).step_by(q).cloned().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    subs.par_iter_mut().for_each(|sub| mixed_radix_fft(sub, omega_q, q, j - 1, log_two));
+
+    // The q-th roots of unity, and the twiddle factors omega^k for k < m.
+    let powers = |g: F, len: usize| {
+        let mut powers = Vec::with_capacity(len);
+        let mut cur = F::one();
+        for _ in 0..len {
+            powers.push(cur);
+            cur *= &g;
+        }
+        powers
+    };
+    let zetas = powers(omega.pow(&[m as u64]), q);
+    let twiddles = powers(omega, m);
+
+    // The output at k + m * t is sum_r zeta^(r * t) * omega^(r * k) * subs[r][k],
+    // which is written to the entry (k, t) of the m x q matrix `tmp`.
+    let mut tmp = vec![F::zero(); n];
+    tmp.par_chunks_mut(q).enumerate().for_each(|(k, out)| {
+        let mut w = F::one();
+        let z = subs
+            .iter()
+            .map(|sub| {
+                let z_r = sub[k] * &w;
+                w *= &twiddles[k];
+                z_r
+            })
+            .collect::<Vec<_>>();
+        for (t, out_t) in out.iter_mut().enumerate() {
+            *out_t = z
+                .iter()
+                .enumerate()
+                .fold(F::zero(), |acc, (r, z_r)| acc + &(zetas[(r * t) % q] * z_r));
+        }
+    });
+    transpose(&tmp, a, m, q);
+}
+
+/// An iterator over the elements of the domain.
+pub struct Elements<F: PrimeField> {
+    cur_elem: F,
+    cur_pow: u64,
+    domain: MixedRadixEvaluationDomain<F>,
+}
+
+impl<F: PrimeField> Iterator for Elements<F> {
+    type Item = F;
+    fn next(&mut self) -> Option<F> {
+        if self.cur_pow == self.domain.size {
+            None
+        } else {
+            let cur_elem = self.cur_elem;
+            self.cur_elem *= &self.domain.group_gen;
+            self.cur_pow += 1;
+            Some(cur_elem)
+        }
+    }
+}
@@ -1,11 +1,15 @@
 pub mod domain;
 pub mod evaluations;
+pub mod general;
+pub mod mixed_radix;
 pub mod polynomial;
 
 pub(crate) mod multicore;
 
 pub use domain::EvaluationDomain;
 pub use evaluations::Evaluations;
+pub use general::GeneralEvaluationDomain;
+pub use mixed_radix::MixedRadixEvaluationDomain;
 pub use polynomial::{DensePolynomial, SparsePolynomial, DenseOrSparsePolynomial};
 
 #[cfg(test)]
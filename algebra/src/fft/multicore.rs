@@ -1,6 +1,7 @@
 //! This is an interface for dealing with the kinds of
 //! parallel computations involved in `snark`. It's
 //! currently just a thin wrapper around `rayon`.
+use crate::tuning::tuning_strategy;
 use rayon::{self, Scope};
 
 #[derive(Copy, Clone)]
@@ -14,8 +15,10 @@ impl Worker {
         Self { cpus }
     }
 
+    /// The log of the number of parallel tasks an FFT is split into, as chosen
+    /// by the current `TuningStrategy`.
     pub(crate) fn log_num_cpus(&self) -> u32 {
-        log2_floor(self.cpus)
+        tuning_strategy().fft_log_num_tasks(self.cpus)
     }
 
     pub(crate) fn scope<'a, F, R>(&self, elements: usize, f: F) -> R
@@ -23,36 +26,8 @@ impl Worker {
         F: 'a + Send + FnOnce(&Scope<'a>, usize) -> R,
         R: Send,
     {
-        let chunk_size = if elements < self.cpus {
-            1
-        } else {
-            elements / self.cpus
-        };
+        let chunk_size = std::cmp::max(tuning_strategy().fft_chunk_size(elements, self.cpus), 1);
 
         rayon::scope(move |scope| f(scope, chunk_size))
     }
 }
-
-pub(crate) fn log2_floor(num: usize) -> u32 {
-    assert!(num > 0);
-
-    let mut pow = 0;
-
-    while (1 << (pow + 1)) <= num {
-        pow += 1;
-    }
-
-    pow
-}
-
-#[test]
-fn test_log2_floor() {
-    assert_eq!(log2_floor(1), 0);
-    assert_eq!(log2_floor(2), 1);
-    assert_eq!(log2_floor(3), 1);
-    assert_eq!(log2_floor(4), 2);
-    assert_eq!(log2_floor(5), 2);
-    assert_eq!(log2_floor(6), 2);
-    assert_eq!(log2_floor(7), 2);
-    assert_eq!(log2_floor(8), 3);
-}
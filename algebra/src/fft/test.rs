@@ -70,3 +70,118 @@ fn parallel_fft_consistency() {
 
     test_consistency::<Bls12_381, _>(rng);
 }
+
+#[test]
+fn four_step_fft_consistency() {
+    fn test_consistency<E: PairingEngine, R: rand::Rng>(rng: &mut R) {
+        for log_d in 0..12 {
+            let d = 1 << log_d;
+
+            let mut v1 = (0..d).map(|_| E::Fr::rand(rng)).collect::<Vec<_>>();
+            let mut v2 = v1.clone();
+
+            let domain = EvaluationDomain::new(v1.len()).unwrap();
+
+            four_step_fft(&mut v1, domain.group_gen, log_d);
+            serial_fft(&mut v2, domain.group_gen, log_d);
+
+            assert_eq!(v1, v2);
+        }
+    }
+
+    let rng = &mut rand::thread_rng();
+
+    test_consistency::<Bls12_381, _>(rng);
+}
+
+#[test]
+fn mixed_radix_fft_consistency() {
+    use crate::{
+        fields::mnt4753::Fr,
+        general::GeneralEvaluationDomain,
+        mixed_radix::MixedRadixEvaluationDomain,
+        DensePolynomial, Field,
+    };
+
+    let rng = &mut rand::thread_rng();
+
+    // The scalar field of MNT4-753 has a multiplicative subgroup of order 5^4.
+    for &size in [1, 5, 10, 20, 25, 50, 125, 250].iter() {
+        let domain = MixedRadixEvaluationDomain::<Fr>::new(size).unwrap();
+        assert_eq!(domain.size(), size);
+
+        // The FFT matches the naive evaluation over the domain elements.
+        let coeffs = (0..size).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_slice(&coeffs);
+        let evals = domain.fft(&coeffs);
+        for (element, eval) in domain.elements().zip(evals.iter()) {
+            assert_eq!(poly.evaluate(element), *eval);
+        }
+        assert_eq!(domain.group_gen.pow(&[size as u64]), Fr::one());
+
+        assert_eq!(domain.ifft(&evals), coeffs, "ifft(fft(.)) != iden");
+        assert_eq!(domain.coset_ifft(&domain.coset_fft(&coeffs)), coeffs, "coset_ifft(coset_fft(.)) != iden");
+        for element in domain.elements() {
+            assert!(domain.evaluate_vanishing_polynomial(element).is_zero());
+        }
+    }
+
+    // The smallest domain is chosen, the power-of-2 one among those of the same size.
+    for &(num_coeffs, size) in [(0, 1), (3, 4), (5, 5), (513, 625), (600, 625), (1000, 1000), (1024, 1024)].iter() {
+        let domain = GeneralEvaluationDomain::<Fr>::new(num_coeffs).unwrap();
+        assert_eq!(domain.size(), size);
+        assert_eq!(GeneralEvaluationDomain::<Fr>::compute_size_of_domain(num_coeffs), Some(size));
+        match domain {
+            GeneralEvaluationDomain::Radix2(_) => assert!(size.is_power_of_two()),
+            GeneralEvaluationDomain::MixedRadix(_) => assert!(!size.is_power_of_two()),
+        }
+    }
+
+    // Fields without a small subgroup base fall back to power-of-2 domains.
+    assert!(MixedRadixEvaluationDomain::<<Bls12_381 as PairingEngine>::Fr>::new(5).is_none());
+    assert_eq!(GeneralEvaluationDomain::<<Bls12_381 as PairingEngine>::Fr>::new(5).unwrap().size(), 8);
+}
+
+// Test computing the quotient of a * b - c by the vanishing polynomial
+// over a coset, as done by the QAP reduction, for evaluations c = a * b
+// over the domain.
+#[test]
+fn coset_division() {
+    use crate::DensePolynomial;
+
+    fn test_coset_division<E: PairingEngine, R: rand::Rng>(rng: &mut R) {
+        for log_d in 1..8 {
+            let d = 1 << log_d;
+            let domain = EvaluationDomain::<E::Fr>::new(d).unwrap();
+
+            let a = (0..d).map(|_| E::Fr::rand(rng)).collect::<Vec<_>>();
+            let b = (0..d).map(|_| E::Fr::rand(rng)).collect::<Vec<_>>();
+            let c = domain.mul_polynomials_in_evaluation_domain(&a, &b);
+
+            let a_poly = DensePolynomial::from_coefficients_vec(domain.ifft(&a));
+            let b_poly = DensePolynomial::from_coefficients_vec(domain.ifft(&b));
+            let c_poly = DensePolynomial::from_coefficients_vec(domain.ifft(&c));
+
+            let a_coset = domain.coset_fft(&a_poly);
+            let b_coset = domain.coset_fft(&b_poly);
+            let c_coset = domain.coset_fft(&c_poly);
+            let num = domain
+                .mul_polynomials_in_evaluation_domain(&a_coset, &b_coset)
+                .into_iter()
+                .zip(c_coset)
+                .map(|(ab, c)| ab - &c)
+                .collect::<Vec<_>>();
+            let h = DensePolynomial::from_coefficients_vec(
+                domain.coset_ifft(&domain.divide_by_vanishing_poly_on_coset(&num)),
+            );
+
+            let expected = &(&a_poly * &b_poly) - &c_poly;
+            assert_eq!(h.mul_by_vanishing_poly(domain), expected);
+            assert_eq!(domain.coset_ifft(&domain.coset_fft(&c_poly)), c_poly.coeffs);
+        }
+    }
+
+    let rng = &mut rand::thread_rng();
+
+    test_coset_division::<Bls12_381, _>(rng);
+}
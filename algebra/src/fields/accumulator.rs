@@ -0,0 +1,176 @@
+//! An accumulator of products of prime field elements with lazy reduction: the
+//! double-width products of the Montgomery representations are added up, and a
+//! single Montgomery reduction is performed for many products instead of one
+//! per product.
+//!
+//! The sum must stay below `p * R` for the final reduction to be correct, where
+//! `R = 2^(64 * N)` for `N` limbs. As `p < R / 2^s` for a modulus with `s` spare
+//! bits in its most significant limb, this allows for `2^s` products; when
+//! the capacity is exhausted, the accumulator reduces its sum and starts anew,
+//! so that it never overflows.
+use crate::{
+    biginteger::{arithmetic as fa, BigInteger},
+    fields::{FpParameters, PrimeField},
+};
+
+/// Accumulates the sum of products of elements of `F` with lazy reduction.
+#[derive(Clone, Debug)]
+pub struct FpAccumulator<F: PrimeField> {
+    /// The unreduced sum, in `2 * N` limbs.
+    limbs:   Vec<u64>,
+    /// The number of products in `limbs`.
+    count:   u64,
+    /// The sum of the products reduced so far.
+    reduced: F,
+}
+
+impl<F: PrimeField> Default for FpAccumulator<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> FpAccumulator<F> {
+    pub fn new() -> Self {
+        Self {
+            limbs:   vec![0u64; 2 * Self::num_limbs()],
+            count:   0,
+            reduced: F::zero(),
+        }
+    }
+
+    fn num_limbs() -> usize {
+        F::Params::MODULUS.as_ref().len()
+    }
+
+    /// Returns the number of products accumulated before a reduction, i.e.
+    /// `2^s` for a modulus with `s` spare bits.
+    pub fn capacity() -> u64 {
+        let spare_bits = 64 * Self::num_limbs() as u32 - F::Params::MODULUS_BITS;
+        1 << spare_bits.min(63)
+    }
+
+    /// Returns the number of products that can be added before the next
+    /// reduction.
+    pub fn remaining(&self) -> u64 {
+        Self::capacity() - self.count
+    }
+
+    /// Adds `a * b` to the sum.
+    pub fn add_product(&mut self, a: &F, b: &F) {
+        if self.count == Self::capacity() {
+            self.reduced += &self.reduce_limbs();
+            self.limbs.iter_mut().for_each(|l| *l = 0);
+            self.count = 0;
+        }
+
+        let n = Self::num_limbs();
+        let a = a.into_repr_raw();
+        let b = b.into_repr_raw();
+        for (i, a_i) in a.as_ref().iter().enumerate() {
+            let mut carry = 0;
+            for (j, b_j) in b.as_ref().iter().enumerate() {
+                self.limbs[i + j] = fa::mac_with_carry(self.limbs[i + j], *a_i, *b_j, &mut carry);
+            }
+            // The sum is smaller than `p * R`, hence the carry never overflows
+            for limb in self.limbs[i + n..].iter_mut() {
+                if carry == 0 {
+                    break;
+                }
+                *limb = fa::adc(*limb, 0, &mut carry);
+            }
+        }
+        self.count += 1;
+    }
+
+    /// Returns the sum of the products added so far.
+    pub fn sum(&self) -> F {
+        self.reduced + &self.reduce_limbs()
+    }
+
+    /// Montgomery reduction of the unreduced sum `T < p * R`, returning the
+    /// element whose Montgomery representation is `T / R`.
+    fn reduce_limbs(&self) -> F {
+        let n = Self::num_limbs();
+        let modulus = F::Params::MODULUS;
+        let mut t = self.limbs.clone();
+        let mut overflow = false;
+        for i in 0..n {
+            let k = t[i].wrapping_mul(F::Params::INV);
+            let mut carry = 0;
+            for (j, m_j) in modulus.as_ref().iter().enumerate() {
+                t[i + j] = fa::mac_with_carry(t[i + j], k, *m_j, &mut carry);
+            }
+            for limb in t[i + n..].iter_mut() {
+                if carry == 0 {
+                    break;
+                }
+                *limb = fa::adc(*limb, 0, &mut carry);
+            }
+            overflow |= carry != 0;
+        }
+
+        // The result is smaller than `2p`, possibly overflowing `R` if the
+        // modulus has no spare bit
+        let mut result = F::BigInt::default();
+        result.as_mut().copy_from_slice(&t[n..]);
+        if overflow || result >= modulus {
+            result.sub_noborrow(&modulus);
+        }
+        F::from_repr_raw(result)
+    }
+}
+
+/// Returns the inner product of `a` and `b`, reduced once per `capacity()`
+/// products.
+pub fn sum_of_products<F: PrimeField>(a: &[F], b: &[F]) -> F {
+    assert_eq!(a.len(), b.len());
+
+    let mut acc = FpAccumulator::new();
+    for (a_i, b_i) in a.iter().zip(b.iter()) {
+        acc.add_product(a_i, b_i);
+    }
+    acc.sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fields::{bls12_381, mnt6753, secp256k1};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn test_accumulator<F: PrimeField>(capacity: u64) {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+        assert_eq!(FpAccumulator::<F>::capacity(), capacity);
+
+        for len in vec![0, 1, 2, 3, 10, 100] {
+            let a = (0..len).map(|_| F::rand(&mut rng)).collect::<Vec<_>>();
+            let b = (0..len).map(|_| F::rand(&mut rng)).collect::<Vec<_>>();
+            let expected = a
+                .iter()
+                .zip(b.iter())
+                .fold(F::zero(), |acc, (a_i, b_i)| acc + &(*a_i * b_i));
+            assert_eq!(sum_of_products(&a, &b), expected);
+        }
+
+        // The worst case: the largest Montgomery representations, across
+        // reductions
+        let mut repr = F::Params::MODULUS;
+        repr.sub_noborrow(&F::BigInt::from(1));
+        let max = F::from_repr_raw(repr);
+        let mut acc = FpAccumulator::new();
+        for i in 0..(2 * capacity.min(1 << 10) + 1) {
+            assert_eq!(acc.sum(), F::from(i) * &max * &max);
+            acc.add_product(&max, &max);
+        }
+    }
+
+    #[test]
+    fn test_fp_accumulator() {
+        test_accumulator::<bls12_381::Fr>(2);
+        test_accumulator::<bls12_381::Fq>(8);
+        test_accumulator::<secp256k1::Fq>(1);
+        test_accumulator::<mnt6753::Fr>(1 << 15);
+    }
+}
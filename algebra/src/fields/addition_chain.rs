@@ -0,0 +1,120 @@
+//! Addition chains for the exponentiation by a fixed exponent, such as the
+//! `(p - 1) / 2` of Euler's criterion. The chain is derived from the sliding
+//! window decomposition of the exponent, with the window size minimizing the
+//! number of multiplications for its length: about `log(e)` squarings and
+//! `log(e) / (w + 1) + 2^(w - 1)` multiplications, instead of the `log(e) / 2`
+//! multiplications of square and multiply.
+use crate::fields::{BitIterator, Field};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdditionChain {
+    /// The exponent is processed as the sequence of pairs `(s, d)`: square `s`
+    /// times, then multiply by the `d`-th power of the base. The digits `d` are
+    /// odd, except for a trailing `(s, 0)` for the trailing zeros.
+    steps:     Vec<(usize, usize)>,
+    max_digit: usize,
+}
+
+impl AdditionChain {
+    /// Returns the chain of the exponent given by `u64` limbs, least
+    /// significant limb first.
+    pub fn new<S: AsRef<[u64]>>(exp: S) -> Self {
+        let bits = BitIterator::new(exp).skip_while(|b| !b).collect::<Vec<_>>();
+        let n = bits.len();
+
+        // The precomputation of the odd powers up to 2^w costs 2^(w - 1)
+        // multiplications, the windows n / (w + 1)
+        let window = (1..=8)
+            .min_by_key(|w| (1usize << (w - 1)) + n / (w + 1))
+            .unwrap();
+
+        let mut steps = vec![];
+        let mut squarings = 0;
+        let mut i = 0;
+        while i < n {
+            if !bits[i] {
+                squarings += 1;
+                i += 1;
+                continue;
+            }
+            // The longest window of at most `window` bits ending with a one
+            let mut end = (i + window).min(n);
+            while !bits[end - 1] {
+                end -= 1;
+            }
+            let digit = bits[i..end].iter().fold(0, |acc, b| 2 * acc + *b as usize);
+            steps.push((squarings + end - i, digit));
+            squarings = 0;
+            i = end;
+        }
+        if squarings > 0 {
+            steps.push((squarings, 0));
+        }
+        let max_digit = steps.iter().map(|(_, digit)| *digit).max().unwrap_or(0);
+
+        Self { steps, max_digit }
+    }
+
+    /// Returns `base` to the power of the exponent of the chain.
+    pub fn pow<F: Field>(&self, base: &F) -> F {
+        if self.steps.is_empty() {
+            return F::one();
+        }
+
+        // The odd powers of the base up to the largest digit
+        let base_square = base.square();
+        let mut odd_powers = vec![*base];
+        for k in 1..(self.max_digit + 1) / 2 {
+            let power = odd_powers[k - 1] * &base_square;
+            odd_powers.push(power);
+        }
+
+        // The first digit is the leading window, nothing to square before
+        let mut res = odd_powers[self.steps[0].1 / 2];
+        for (squarings, digit) in self.steps.iter().skip(1) {
+            for _ in 0..*squarings {
+                res.square_in_place();
+            }
+            if *digit != 0 {
+                res *= &odd_powers[digit / 2];
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fields::{bls12_381::Fq, mnt4753::Fr, FpParameters, PrimeField};
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    fn test_addition_chain<F: PrimeField>() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        let exps = vec![
+            vec![0u64],
+            vec![1u64],
+            vec![2u64],
+            vec![0b1011_0000u64],
+            vec![u64::max_value(), 0],
+            vec![rng.gen(), rng.gen(), rng.gen()],
+            F::Params::MODULUS_MINUS_ONE_DIV_TWO.as_ref().to_vec(),
+            F::Params::T_MINUS_ONE_DIV_TWO.as_ref().to_vec(),
+        ];
+        for exp in exps {
+            let chain = AdditionChain::new(&exp);
+            for _ in 0..10 {
+                let base = F::rand(&mut rng);
+                assert_eq!(chain.pow(&base), base.pow(&exp));
+            }
+        }
+    }
+
+    #[test]
+    fn test_addition_chains() {
+        test_addition_chain::<Fq>();
+        test_addition_chain::<Fr>();
+    }
+}
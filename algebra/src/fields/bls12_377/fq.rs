@@ -47,6 +47,16 @@ impl FpParameters for FqParameters {
 
     const INV: u64 = 9586122913090633727u64;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0xf56317936bff32de,
+        0x5a1de2710a9647e7,
+        0xb52236169b40b43b,
+        0xb43799ddac681abc,
+        0xf28160bbd3dceee,
+        0x542343310183a5db,
+        0x98,
+    ];
+
     // GENERATOR = -5
     const GENERATOR: BigInteger = BigInteger([
         0xfc0b8000000002fa,
@@ -1,6 +1,6 @@
 use crate::{
     biginteger::BigInteger256 as BigInteger,
-    fields::{Fp256, Fp256Parameters, FpParameters},
+    fields::{Fp256, Fp256Parameters, FpParameters, InversionAlgorithm},
 };
 
 pub type Fr = Fp256<FrParameters>;
@@ -42,6 +42,14 @@ impl FpParameters for FrParameters {
 
     const INV: u64 = 725501752471715839u64;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0x48130845479e7a85,
+        0x428602e35a78963d,
+        0x3947927eaa01523f,
+        0xb65247b102cb27b9,
+        0xd,
+    ];
+
     // GENERATOR = 11
     const GENERATOR: BigInteger = BigInteger([
         1855201571499933546u64,
@@ -87,4 +95,6 @@ impl FpParameters for FrParameters {
         0x655e9a2ca55660b4,
         0x12ab,
     ]);
+
+    const INVERSION_ALGORITHM: InversionAlgorithm = InversionAlgorithm::SafeGcd;
 }
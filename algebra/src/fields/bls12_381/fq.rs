@@ -47,6 +47,16 @@ impl FpParameters for FqParameters {
 
     const INV: u64 = 0x89f3fffcfffcfffd;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0x13e207f56591ba2e,
+        0x997167a058f1c07b,
+        0xdf4771e0286779d3,
+        0x1b82741ff6a0a94b,
+        0x28101b0cc7a6ba29,
+        0xd835d2f3cc9e45ce,
+        0x9,
+    ];
+
     // GENERATOR = 2
     const GENERATOR: BigInteger = BigInteger([
         0x321300000006554f,
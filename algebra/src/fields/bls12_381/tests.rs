@@ -6,7 +6,7 @@ use crate::{
         },
         fp12_2over3over2::Fp12Parameters,
         fp6_3over2::Fp6Parameters,
-        tests::{field_test, frobenius_test, primefield_test, sqrt_field_test},
+        tests::{field_test, frobenius_coeff_test, frobenius_test, primefield_test, sqrt_field_test},
         Field, Fp2Parameters, FpParameters, PrimeField, SquareRootField,
 
     },
@@ -62,6 +62,7 @@ fn test_bls12_381_fq2() {
         sqrt_field_test(a);
     }
     frobenius_test::<Fq2, _>(Fq::characteristic(), 13);
+    frobenius_coeff_test(Fq2Parameters::frobenius_coeff_c1, 2);
 }
 
 #[test]
@@ -75,6 +76,8 @@ fn test_bls12_381_fq6() {
         field_test(g, h);
     }
     frobenius_test::<Fq6, _>(Fq::characteristic(), 13);
+    frobenius_coeff_test(Fq6Parameters::frobenius_coeff_c1, 6);
+    frobenius_coeff_test(Fq6Parameters::frobenius_coeff_c2, 6);
 }
 
 #[test]
@@ -88,6 +91,32 @@ fn test_bls12_381_fq12() {
         field_test(g, h);
     }
     frobenius_test::<Fq12, _>(Fq::characteristic(), 13);
+    frobenius_coeff_test(Fq12Parameters::frobenius_coeff_c1, 12);
+}
+
+#[test]
+fn test_bls12_381_tower_structure() {
+    assert_eq!(Fq::tower_shape(), Vec::<usize>::new());
+    assert_eq!(Fq2::tower_shape(), vec![2]);
+    assert_eq!(Fq6::tower_shape(), vec![2, 3]);
+    assert_eq!(Fq12::tower_shape(), vec![2, 3, 2]);
+    assert_eq!(<Fq12 as Field>::DEGREE_OVER_BASE_FIELD, 2);
+    assert_eq!(<Fq12 as Field>::EXTENSION_DEGREE, 12);
+
+    let nonresidues = Fq12::tower_nonresidues();
+    assert_eq!(nonresidues.len(), 3);
+    assert_eq!(nonresidues[0], vec![Fq2Parameters::NONRESIDUE]);
+    assert_eq!(nonresidues[1], Fq6Parameters::NONRESIDUE.to_base_prime_field_elements());
+    assert_eq!(nonresidues[2].len(), 6);
+    assert_eq!(Fq6::tower_nonresidues(), nonresidues[..2].to_vec());
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    let a: Fq12 = UniformRand::rand(&mut rng);
+    let coeffs = a.to_base_prime_field_elements();
+    assert_eq!(coeffs.len(), 12);
+    assert_eq!(coeffs[..6].to_vec(), a.c0.to_base_prime_field_elements());
+    assert_eq!(coeffs[0], a.c0.c0.c0);
+    assert_eq!(coeffs[11], a.c1.c2.c1);
 }
 
 #[test]
@@ -2410,3 +2439,57 @@ fn test_fq12_mul_by_014() {
         assert_eq!(a, b);
     }
 }
+
+// Maps `a` to the cyclotomic subgroup, raising it to (p^6 - 1)(p^2 + 1).
+fn fq12_to_cyclotomic(a: Fq12) -> Fq12 {
+    let mut f = a;
+    f.frobenius_map(6);
+    f *= &a.inverse().unwrap();
+    let mut g = f;
+    g.frobenius_map(2);
+    g * &f
+}
+
+#[test]
+fn test_fq12_cyclotomic_exp_sparse() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    let x: &[u64] = &[0xd201000000010000];
+
+    for _ in 0..10 {
+        let a = fq12_to_cyclotomic(Fq12::rand(&mut rng));
+        assert_eq!(a.cyclotomic_exp_sparse(x), a.cyclotomic_exp(x));
+        let e = [u64::rand(&mut rng), u64::rand(&mut rng)];
+        assert_eq!(a.cyclotomic_exp_sparse(e), a.cyclotomic_exp(e));
+        assert_eq!(a.cyclotomic_exp_sparse([1u64]), a);
+        assert_eq!(a.cyclotomic_exp_sparse([0u64]), Fq12::one());
+    }
+    assert_eq!(Fq12::one().cyclotomic_exp_sparse(x), Fq12::one());
+}
+
+#[test]
+fn test_fq12_torus_compression() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    for _ in 0..10 {
+        let a = fq12_to_cyclotomic(Fq12::rand(&mut rng));
+        assert!(a.norm_over_fp6().is_one());
+        let bytes = a.to_compressed();
+        assert_eq!(bytes.len(), 6 * 48 + 1);
+        assert_eq!(Fq12::from_compressed(&bytes).unwrap(), a);
+
+        // Wrong length and unknown flags
+        assert!(Fq12::from_compressed(&bytes[..bytes.len() - 1]).is_err());
+        let mut bad_flags = bytes.clone();
+        *bad_flags.last_mut().unwrap() = 4;
+        assert!(Fq12::from_compressed(&bad_flags).is_err());
+    }
+
+    for a in &[Fq12::one(), -Fq12::one()] {
+        assert_eq!(Fq12::from_compressed(&a.to_compressed()).unwrap(), *a);
+    }
+
+    // The identity has a single encoding
+    let mut bytes = Fq12::one().to_compressed();
+    bytes[0] = 1;
+    assert!(Fq12::from_compressed(&bytes).is_err());
+}
@@ -0,0 +1,101 @@
+use crate::{
+    biginteger::BigInteger256 as BigInteger,
+    fields::{Fp256, Fp256Parameters, FpParameters, InversionAlgorithm},
+};
+
+/// Modulus 2^255 - 19, reduced with the generic Montgomery arithmetic.
+pub type Fq = Fp256<FqParameters>;
+
+pub struct FqParameters;
+
+impl Fp256Parameters for FqParameters {}
+impl FpParameters for FqParameters {
+    type BigInt = BigInteger;
+
+    // MODULUS = 57896044618658097711785492504343953926634992332820282019728792003956564819949
+    const MODULUS: BigInteger = BigInteger([
+        0xffffffffffffffed,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ]);
+
+    const MODULUS_BITS: u32 = 255;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 1;
+
+    const R: BigInteger = BigInteger([
+        0x26,
+        0x0,
+        0x0,
+        0x0,
+    ]);
+
+    const R2: BigInteger = BigInteger([
+        0x5a4,
+        0x0,
+        0x0,
+        0x0,
+    ]);
+
+    const INV: u64 = 0x86bca1af286bca1b;
+
+    const BARRETT_MU: &'static [u64] = &[
+        0x4c,
+        0x0,
+        0x0,
+        0x0,
+        0x2,
+    ];
+
+    // GENERATOR = 2
+    const GENERATOR: BigInteger = BigInteger([
+        0x4c,
+        0x0,
+        0x0,
+        0x0,
+    ]);
+
+    const TWO_ADICITY: u32 = 2;
+
+    // ROOT_OF_UNITY = GENERATOR^T = 19681161376707505956807079304988542015446066515923890162744021073123829784752
+    const ROOT_OF_UNITY: BigInteger = BigInteger([
+        0x3b5807d4fe2bdb04,
+        0x3f590fdb51be9ed,
+        0x6d6e16bf336202d1,
+        0x75776b0bd6c71ba8,
+    ]);
+
+    // (MODULUS - 1) / 2 =
+    // 28948022309329048855892746252171976963317496166410141009864396001978282409974
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xfffffffffffffff6,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ]);
+
+    // T and T_MINUS_ONE_DIV_TWO, where MODULUS - 1 = 2^S * T
+
+    // T = (MODULUS - 1) / 2^S =
+    // 14474011154664524427946373126085988481658748083205070504932198000989141204987
+    const T: BigInteger = BigInteger([
+        0xfffffffffffffffb,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x1fffffffffffffff,
+    ]);
+
+    // (T - 1) / 2 =
+    // 7237005577332262213973186563042994240829374041602535252466099000494570602493
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xfffffffffffffffd,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xfffffffffffffff,
+    ]);
+
+    const INVERSION_ALGORITHM: InversionAlgorithm = InversionAlgorithm::SafeGcd;
+}
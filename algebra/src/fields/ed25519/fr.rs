@@ -0,0 +1,100 @@
+use crate::{
+    biginteger::BigInteger256 as BigInteger,
+    fields::{Fp256, Fp256Parameters, FpParameters, InversionAlgorithm},
+};
+
+pub type Fr = Fp256<FrParameters>;
+
+pub struct FrParameters;
+
+impl Fp256Parameters for FrParameters {}
+impl FpParameters for FrParameters {
+    type BigInt = BigInteger;
+
+    // MODULUS = 7237005577332262213973186563042994240857116359379907606001950938285454250989
+    const MODULUS: BigInteger = BigInteger([
+        0x5812631a5cf5d3ed,
+        0x14def9dea2f79cd6,
+        0x0,
+        0x1000000000000000,
+    ]);
+
+    const MODULUS_BITS: u32 = 253;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 3;
+
+    const R: BigInteger = BigInteger([
+        0xd6ec31748d98951d,
+        0xc6ef5bf4737dcf70,
+        0xfffffffffffffffe,
+        0xfffffffffffffff,
+    ]);
+
+    const R2: BigInteger = BigInteger([
+        0xa40611e3449c0f01,
+        0xd00e1ba768859347,
+        0xceec73d217f5be65,
+        0x399411b7c309a3d,
+    ]);
+
+    const INV: u64 = 0xd2b51da312547e1b;
+
+    const BARRETT_MU: &'static [u64] = &[
+        0xed9ce5a30a2c131b,
+        0x2106215d086329a7,
+        0xffffffffffffffeb,
+        0xffffffffffffffff,
+        0xf,
+    ];
+
+    // GENERATOR = 2
+    const GENERATOR: BigInteger = BigInteger([
+        0x55c5ffcebe3b564d,
+        0x78ffbe0a4404020b,
+        0xfffffffffffffffd,
+        0xfffffffffffffff,
+    ]);
+
+    const TWO_ADICITY: u32 = 2;
+
+    // ROOT_OF_UNITY = GENERATOR^T = 4202356475871964119699734399548423449193549369991576068503119564443318355924
+    const ROOT_OF_UNITY: BigInteger = BigInteger([
+        0x7c790e32b42f0e7d,
+        0x4c8ce706a7ae2cc8,
+        0xd73823cc921779ad,
+        0x5599959893f562a,
+    ]);
+
+    // (MODULUS - 1) / 2 =
+    // 3618502788666131106986593281521497120428558179689953803000975469142727125494
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0x2c09318d2e7ae9f6,
+        0xa6f7cef517bce6b,
+        0x0,
+        0x800000000000000,
+    ]);
+
+    // T and T_MINUS_ONE_DIV_TWO, where MODULUS - 1 = 2^S * T
+
+    // T = (MODULUS - 1) / 2^S =
+    // 1809251394333065553493296640760748560214279089844976901500487734571363562747
+    const T: BigInteger = BigInteger([
+        0x960498c6973d74fb,
+        0x537be77a8bde735,
+        0x0,
+        0x400000000000000,
+    ]);
+
+    // (T - 1) / 2 =
+    // 904625697166532776746648320380374280107139544922488450750243867285681781373
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xcb024c634b9eba7d,
+        0x29bdf3bd45ef39a,
+        0x0,
+        0x200000000000000,
+    ]);
+
+    const INVERSION_ALGORITHM: InversionAlgorithm = InversionAlgorithm::SafeGcd;
+}
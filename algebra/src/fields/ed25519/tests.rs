@@ -0,0 +1,91 @@
+use crate::{
+    fields::tests::{field_test, primefield_test, sqrt_field_test},
+    fields::ed25519::{Fq, Fr},
+    Field, SquareRootField, UniformRand,
+};
+
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use std::str::FromStr;
+
+pub(crate) const ITERATIONS: usize = 5;
+
+#[test]
+fn test_ed25519_fr() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    for _ in 0..ITERATIONS {
+        let a: Fr = UniformRand::rand(&mut rng);
+        let b: Fr = UniformRand::rand(&mut rng);
+        field_test(a, b);
+        primefield_test::<Fr>();
+        sqrt_field_test(b);
+    }
+}
+
+#[test]
+fn test_ed25519_fq() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    for _ in 0..ITERATIONS {
+        let a: Fq = UniformRand::rand(&mut rng);
+        let b: Fq = UniformRand::rand(&mut rng);
+        field_test(a, b);
+        primefield_test::<Fq>();
+        sqrt_field_test(a);
+    }
+}
+
+#[test]
+fn test_fq_mul() {
+    let f1 = Fq::from_str(
+        "10517897101310404165145044276099564020899992116522284903899755174733130460146",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "22187124534483548190167968242001440635489943980198732271334859863323574521239",
+    )
+    .unwrap();
+    let f3 = Fq::from_str(
+        "49633732543511020952889693758430638839900969283437153676855192708158598710093",
+    )
+    .unwrap();
+    assert_eq!(f1 * &f2, f3);
+}
+
+#[test]
+fn test_fq_inverse() {
+    let f1 = Fq::from_str(
+        "10517897101310404165145044276099564020899992116522284903899755174733130460146",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "39088555621953038316175825905965535345283902007295387363867384497640097127287",
+    )
+    .unwrap();
+    assert_eq!(f1.inverse().unwrap(), f2);
+}
+
+#[test]
+fn test_fq_sqrt() {
+    let f1 = Fq::from_str(
+        "50349370585418201487858514313304769696346038088171963226586618597573081870406",
+    )
+    .unwrap();
+    assert_eq!(f1.sqrt().unwrap().square(), f1);
+}
+
+#[test]
+fn test_fr_mul() {
+    let f1 = Fr::from_str(
+        "4429977470733608753078532634090576113145190026713142126770692741471367141706",
+    )
+    .unwrap();
+    let f2 = Fr::from_str(
+        "2549164076176937162488376881277592400725014580571095260931335957583513132485",
+    )
+    .unwrap();
+    let f3 = Fr::from_str(
+        "2747747204201196309815005669262894945650184036687846750221685441294181366548",
+    )
+    .unwrap();
+    assert_eq!(f1 * &f2, f3);
+}
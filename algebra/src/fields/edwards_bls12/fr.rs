@@ -1,6 +1,6 @@
 use crate::{
     biginteger::BigInteger256 as BigInteger,
-    fields::{Fp256, Fp256Parameters, FpParameters},
+    fields::{Fp256, Fp256Parameters, FpParameters, InversionAlgorithm},
 };
 
 pub type Fr = Fp256<FrParameters>;
@@ -41,6 +41,14 @@ impl FpParameters for FrParameters {
 
     const INV: u64 = 9659935179256617473u64;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0xfa6edc84f087e05e,
+        0x298f76613f4770ba,
+        0xe51e49faa805492a,
+        0xd9491ec40b2c9ee4,
+        0x36,
+    ];
+
     // 5
     const GENERATOR: BigInteger = BigInteger([
         11289572479685143826u64,
@@ -65,7 +73,19 @@ impl FpParameters for FrParameters {
         168160046336021674u64,
     ]);
 
-    const T: BigInteger = BigInteger([0x0, 0x0, 0x0, 0x0]);
+    const T: BigInteger = BigInteger([
+        6678124996694371583u64,
+        2975139753996731775u64,
+        14706092969812227584u64,
+        168160046336021674u64,
+    ]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        12562434535201961599u64,
+        1487569876998365887u64,
+        7353046484906113792u64,
+        84080023168010837u64,
+    ]);
 
-    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0x0, 0x0, 0x0, 0x0]);
+    const INVERSION_ALGORITHM: InversionAlgorithm = InversionAlgorithm::SafeGcd;
 }
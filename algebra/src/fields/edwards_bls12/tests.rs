@@ -1,4 +1,4 @@
-use crate::fields::tests::{field_test, primefield_test};
+use crate::fields::tests::{field_test, primefield_test, sqrt_field_test};
 
 #[test]
 fn test_edwards_bls12_fr() {
@@ -8,6 +8,7 @@ fn test_edwards_bls12_fr() {
     let b: Fr = rand::random();
     field_test(a, b);
     primefield_test::<Fr>();
+    sqrt_field_test(a);
 }
 
 #[test]
@@ -47,6 +47,16 @@ impl FpParameters for FrParameters {
 
     const INV: u64 = 16242011933465909059u64;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0x1f8ed099ee0dcdcd,
+        0xf80d564caf9c8825,
+        0x631fd1f414cfb6fe,
+        0xa1bcceed634103d7,
+        0x7940b05de9ee7775,
+        0xa11a19880c1d2ed8,
+        0x4c2,
+    ];
+
     // 2
     const GENERATOR: BigInteger = BigInteger([
         1999556893213776791u64,
@@ -77,7 +87,21 @@ impl FpParameters for FrParameters {
         7568644544155918u64,
     ]);
 
-    const T: BigInteger = BigInteger([0x0, 0x0, 0x0, 0x0, 0x0, 0x0]);
+    const T: BigInteger = BigInteger([
+        5782852926996632741u64,
+        10160572951715783904u64,
+        8680081325396045328u64,
+        15623293663189641372u64,
+        6210983053257673289u64,
+        3784322272077959u64,
+    ]);
 
-    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0x0, 0x0, 0x0, 0x0, 0x0, 0x0]);
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        2891426463498316370u64,
+        5080286475857891952u64,
+        4340040662698022664u64,
+        17035018868449596494u64,
+        12328863563483612452u64,
+        1892161136038979u64,
+    ]);
 }
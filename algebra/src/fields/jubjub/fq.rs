@@ -1,6 +1,6 @@
 use crate::{
     biginteger::BigInteger256 as BigInteger,
-    fields::{Fp256, Fp256Parameters, FpParameters},
+    fields::{Fp256, Fp256Parameters, FpParameters, InversionAlgorithm},
 };
 
 pub type Fq = Fp256<FqParameters>;
@@ -41,6 +41,14 @@ impl FpParameters for FqParameters {
 
     const INV: u64 = 0xfffffffeffffffff;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0x42737a020c0d6393,
+        0x65043eb4be4bad71,
+        0x38b5dcb707e08ed3,
+        0x355094edfede377c,
+        0x2,
+    ];
+
     //
     const GENERATOR: BigInteger = BigInteger([
         0xefffffff1,
@@ -84,4 +92,6 @@ impl FpParameters for FqParameters {
         0x94cebea4199cec04,
         0x39f6d3a9,
     ]);
+
+    const INVERSION_ALGORITHM: InversionAlgorithm = InversionAlgorithm::SafeGcd;
 }
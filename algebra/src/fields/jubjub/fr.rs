@@ -1,6 +1,6 @@
 use crate::{
     biginteger::BigInteger256 as BigInteger,
-    fields::{Fp256, Fp256Parameters, FpParameters},
+    fields::{Fp256, Fp256Parameters, FpParameters, InversionAlgorithm},
 };
 
 pub type Fr = Fp256<FrParameters>;
@@ -41,6 +41,14 @@ impl FpParameters for FrParameters {
 
     const INV: u64 = 0x1ba3a358ef788ef9;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0x77565ee9b2461ca9,
+        0x1b7c72192b99f029,
+        0xc5aee5b83f0476a0,
+        0xaa84a76ff6f1bbe1,
+        0x11,
+    ];
+
     const GENERATOR: BigInteger = BigInteger([
         0x720b1b19d49ea8f1,
         0xbf4aa36101f13a58,
@@ -57,9 +65,26 @@ impl FpParameters for FrParameters {
         0x4d6b87b1da259e2,
     ]);
 
-    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0x0, 0x0, 0x0, 0x0]);
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0x684b872f6b7b965b,
+        0x53341049e6640841,
+        0x83339d80809a1d80,
+        0x73eda753299d7d4,
+    ]);
 
-    const T: BigInteger = BigInteger([0x0, 0x0, 0x0, 0x0]);
+    const T: BigInteger = BigInteger([
+        0x684b872f6b7b965b,
+        0x53341049e6640841,
+        0x83339d80809a1d80,
+        0x73eda753299d7d4,
+    ]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xb425c397b5bdcb2d,
+        0x299a0824f3320420,
+        0x4199cec0404d0ec0,
+        0x39f6d3a994cebea,
+    ]);
 
-    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0x0, 0x0, 0x0, 0x0]);
+    const INVERSION_ALGORITHM: InversionAlgorithm = InversionAlgorithm::SafeGcd;
 }
@@ -3,7 +3,7 @@ use crate::{
     bytes::{FromBytes, ToBytes},
     fields::{
         jubjub::{fq::Fq, fr::Fr},
-        tests::{field_test, primefield_test},
+        tests::{field_test, primefield_test, sqrt_field_test},
         Field,
         LegendreSymbol::*,
         PrimeField, SquareRootField,
@@ -17,6 +17,7 @@ fn test_jubjub_fr() {
     let b: Fr = rand::random();
     field_test(a, b);
     primefield_test::<Fr>();
+    sqrt_field_test(a);
 }
 
 #[test]
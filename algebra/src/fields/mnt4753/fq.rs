@@ -74,6 +74,22 @@ impl FpParameters for FqParameters {
 
     const INV: u64 = 0xF2044CFBE45E7FFF;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0xabba3f5d42026f38,
+        0x576a460d27d25968,
+        0xc7d5ea5a84517732,
+        0xdcadd560309f1347,
+        0x278f1c5c76148a08,
+        0x60c9b8f015490661,
+        0xdcbc2aa6fc0d1e75,
+        0xe8c0383b7865972f,
+        0xa69745f07fdcd4e2,
+        0x5d19fee4cb2b8aae,
+        0xce015aaa82abb6c1,
+        0x45b3ea09c9f1babb,
+        0x90be,
+    ];
+
     // primitive root = 17
     // in Montgomery rep.
     const GENERATOR: BigInteger = BigInteger([
@@ -113,6 +129,30 @@ impl FpParameters for FqParameters {
         0x1080B8906B7C4,
     ]);
 
+    const SMALL_SUBGROUP_BASE: Option<u32> = Some(5);
+
+    const SMALL_SUBGROUP_BASE_ADICITY: Option<u32> = Some(2);
+
+    //2^15*5^2-th root of unity=
+    //1224945890276221774762683291971092661851001145536496372639375285464991\
+    // 4979954138109976331601455448780251166045203053508523342111624583986869\
+    // 3016583666253568268887856918237105984707754537421335936345246194296298\
+    // 03955083254436531
+    const LARGE_SUBGROUP_ROOT_OF_UNITY: Option<BigInteger> = Some(BigInteger([
+        0x7BE1F1D123F7B888,
+        0x96E4F023C4994337,
+        0x5A7B50CD5499351B,
+        0x6D9FDEC8DE6EC83A,
+        0x74CDAD01951AA963,
+        0xB3BD7998BCD80684,
+        0x627226CDD7F87F17,
+        0xC6D3C3097B11A675,
+        0xFCF853509BB56358,
+        0x272FA61396A4D851,
+        0x17B5335959BBBD2B,
+        0x63ABADDE7527,
+    ]));
+
     const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
         0xAF4831EF122F4000,
         0x71CEAA29166E88CF,
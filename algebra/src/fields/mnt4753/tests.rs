@@ -1,6 +1,6 @@
 use crate::{
     biginteger::{BigInteger, BigInteger768},
-    fields::tests::{field_test, frobenius_test, primefield_test, sqrt_field_test},
+    fields::tests::{field_test, frobenius_coeff_test, frobenius_test, primefield_test, sqrt_field_test},
     fields::mnt4753::{Fq, FqParameters, Fq2, Fq2Parameters, Fq4, Fq4Parameters, Fr},
     fields::FpParameters,
     fields::models::{fp2::Fp2Parameters, fp4::Fp4Parameters},
@@ -52,6 +52,7 @@ fn test_mnt4753_fq2() {
         sqrt_field_test(a);
     }
     frobenius_test::<Fq2, _>(Fq::characteristic(), 13);
+    frobenius_coeff_test(Fq2Parameters::frobenius_coeff_c1, 2);
 }
 
 #[test]
@@ -63,6 +64,20 @@ fn test_mnt4753_fq4() {
         field_test(g, h);
     }
     frobenius_test::<Fq4, _>(Fq::characteristic(), 13);
+    frobenius_coeff_test(Fq4Parameters::frobenius_coeff_c1, 4);
+}
+
+#[test]
+fn test_mnt4753_fq4_tower_nonresidues() {
+    let nonresidues = Fq4::tower_nonresidues();
+    assert_eq!(nonresidues.len(), 2);
+    assert_eq!(nonresidues[0], vec![Fq2Parameters::NONRESIDUE]);
+
+    // The generator Y of Fq4 over Fq2 squares to the reported nonresidue.
+    let y = Fq4::new(Fq2::zero(), Fq2::one());
+    let mut expected = nonresidues[1].clone();
+    expected.extend_from_slice(&[Fq::zero(), Fq::zero()]);
+    assert_eq!(y.square().to_base_prime_field_elements(), expected);
 }
 
 
@@ -44,6 +44,15 @@ impl FpParameters for FqParameters {
 
     const INV: u64 = 0xbb4334a3ffffffff;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0xb89904f971fa695,
+        0x98d6d36eec6f3419,
+        0x7aef136ec67999ff,
+        0xdd2083b624550b0b,
+        0x78260441e1aa15ad,
+        0x447ba4,
+    ];
+
     const GENERATOR: BigInteger = BigInteger([
         0xb1ddfacffd532b94,
         0x25e295ff76674008,
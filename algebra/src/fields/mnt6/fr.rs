@@ -44,6 +44,15 @@ impl FpParameters for FrParameters {
 
     const INV: u64 = 12714121028002250751u64;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0xa120e17982ac2de0,
+        0xfbce55c5c1adf01,
+        0x4434f1fd12fa02de,
+        0xdd2083b624550b09,
+        0x78260441e1aa15ad,
+        0x447ba4,
+    ];
+
     const GENERATOR: BigInteger = BigInteger([
         2709730703260633621u64,
         13556085429182073539u64,
@@ -73,6 +73,22 @@ impl FpParameters for FqParameters {
 
     const INV: u64 = 0xC90776E23FFFFFFF;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0x7fc166ee880b372f,
+        0x4745619dc63b1c3d,
+        0x983c988ffe68b6b8,
+        0x597f9f802e8e4768,
+        0xad1daf7b72cd49db,
+        0x502821bb6a983163,
+        0xdcbc2aa6fba04820,
+        0xe8c0383b7865972f,
+        0xa69745f07fdcd4e2,
+        0x5d19fee4cb2b8aae,
+        0xce015aaa82abb6c1,
+        0x45b3ea09c9f1babb,
+        0x90be,
+    ];
+
     //primitive root 17
     //Montgomery rep.
     const GENERATOR: BigInteger = BigInteger([
@@ -112,6 +128,30 @@ impl FpParameters for FqParameters {
         0x1B9F32A8BD6AB,
     ]);
 
+    const SMALL_SUBGROUP_BASE: Option<u32> = Some(5);
+
+    const SMALL_SUBGROUP_BASE_ADICITY: Option<u32> = Some(4);
+
+    //2^30*5^4-th root of unity=
+    //3514922034367589816771280967337190118142043453199601659291327877814106\
+    // 2969297452804389148078475009659471494231942053332464075649895466569644\
+    // 5032091551962201144603180327939625938976225380192014060794767443094881\
+    // 80746590777838793
+    const LARGE_SUBGROUP_ROOT_OF_UNITY: Option<BigInteger> = Some(BigInteger([
+        0x8E113801AD41A73A,
+        0x319A0063CA12B3AD,
+        0x556EF9F74B6AA86F,
+        0xFA914093CD8FC691,
+        0xE1B9799286976F7B,
+        0x6275D2F5B96CE4BC,
+        0x5F97B754BA8C82A9,
+        0x62CE3B091DDEBC5F,
+        0x6338071CD91FB4A1,
+        0x8866E24461D3779E,
+        0xF5E1ACA26B7BD11B,
+        0x137B406979CE5,
+    ]));
+
     const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
         0xEC83BB7120000000,
         0xA7504C8B87D09D27,
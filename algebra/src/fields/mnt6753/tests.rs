@@ -1,4 +1,4 @@
-use crate::{BigInteger, BigInteger768, fields::tests::{field_test, frobenius_test, primefield_test, sqrt_field_test}, fields::mnt6753::{Fq, Fq3, Fq6, FqParameters, Fq3Parameters, Fq6Parameters}, fields::FpParameters, fields::models::{Fp3Parameters, Fp6Parameters}, Field, PrimeField, SquareRootField, UniformRand, bytes::ToBytes, to_bytes, ToBits};
+use crate::{BigInteger, BigInteger768, fields::tests::{field_test, frobenius_coeff_test, frobenius_test, primefield_test, sqrt_field_test}, fields::mnt6753::{Fq, Fq3, Fq6, FqParameters, Fq3Parameters, Fq6Parameters}, fields::FpParameters, fields::models::{Fp3Parameters, Fp6Parameters}, Field, PrimeField, SquareRootField, UniformRand, bytes::ToBytes, to_bytes, ToBits};
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
 use std::{
@@ -48,6 +48,8 @@ fn test_mnt6753_fq3() {
         sqrt_field_test(a);
     }
     frobenius_test::<Fq3, _>(Fq::characteristic(), 13);
+    frobenius_coeff_test(Fq3Parameters::frobenius_coeff_c1, 3);
+    frobenius_coeff_test(Fq3Parameters::frobenius_coeff_c2, 3);
 }
 
 #[test]
@@ -61,6 +63,7 @@ fn test_mnt6753_fq6() {
         field_test(g, h);
     }
     frobenius_test::<Fq6, _>(Fq::characteristic(), 13);
+    frobenius_coeff_test(Fq6Parameters::frobenius_coeff_c1, 6);
 }
 
 
@@ -2397,4 +2400,46 @@ fn test_fq6_mul_by_2345() {
 
         assert_eq!(a.mul_by_2345(&to_mul), b);
     }
-}
\ No newline at end of file
+}
+// Maps `a` to the cyclotomic subgroup, raising it to (p^3 - 1)(p + 1).
+fn fq6_to_cyclotomic(a: Fq6) -> Fq6 {
+    let mut f = a;
+    f.frobenius_map(3);
+    f *= &a.inverse().unwrap();
+    let mut g = f;
+    g.frobenius_map(1);
+    g * &f
+}
+
+#[test]
+fn test_fq6_cyclotomic_square() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    for _ in 0..100 {
+        let a = fq6_to_cyclotomic(Fq6::rand(&mut rng));
+        assert_eq!(a.cyclotomic_square(), a.square());
+    }
+}
+
+#[test]
+fn test_fq6_torus_compression() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    for _ in 0..10 {
+        let a = fq6_to_cyclotomic(Fq6::rand(&mut rng));
+        assert!(a.norm_over_fp3().is_one());
+        let bytes = a.to_compressed();
+        assert_eq!(bytes.len(), to_bytes!(a).unwrap().len() / 2 + 1);
+        assert_eq!(Fq6::from_compressed(&bytes).unwrap(), a);
+
+        // Wrong length and unknown flags
+        assert!(Fq6::from_compressed(&bytes[..bytes.len() - 1]).is_err());
+        let mut bad_flags = bytes.clone();
+        *bad_flags.last_mut().unwrap() = 4;
+        assert!(Fq6::from_compressed(&bad_flags).is_err());
+    }
+
+    for a in &[Fq6::one(), -Fq6::one()] {
+        assert_eq!(Fq6::from_compressed(&a.to_compressed()).unwrap(), *a);
+    }
+}
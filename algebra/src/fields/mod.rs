@@ -1,4 +1,4 @@
-use crate::{biginteger::BigInteger, bytes::{FromBytes, ToBytes}, UniformRand, bits::{ToBits, FromBits}, Error, BitSerializationError};
+use crate::{biginteger::{arithmetic as fa, BigInteger}, bytes::{FromBytes, ToBytes}, UniformRand, bits::{check_bits_len, ToBits, FromBits}, Error, BitSerializationError};
 use std::{
     fmt::{Debug, Display},
     hash::Hash,
@@ -9,8 +9,14 @@ use std::{
 #[macro_use]
 mod macros;
 
+pub mod accumulator;
+pub use self::accumulator::*;
+
+pub mod addition_chain;
+
 pub mod bls12_377;
 pub mod bls12_381;
+pub mod ed25519;
 pub mod edwards_bls12;
 pub mod edwards_sw6;
 pub mod jubjub;
@@ -18,6 +24,8 @@ pub mod mnt4753;
 pub mod mnt6753;
 pub mod mnt6;
 pub mod models;
+pub mod safegcd;
+pub mod secp256k1;
 pub mod sw6;
 #[cfg(test)]
 pub mod tests;
@@ -88,6 +96,49 @@ pub trait Field:
     + for<'a> MulAssign<&'a Self>
     + for<'a> DivAssign<&'a Self>
 {
+    /// The prime field at the bottom of the tower of extensions `Self` is
+    /// built as, i.e. `Self` itself for prime fields.
+    type BasePrimeField: PrimeField;
+
+    /// The degree of `Self` over the field it is an extension of, 1 for
+    /// prime fields.
+    const DEGREE_OVER_BASE_FIELD: usize;
+
+    /// The degree of `Self` over `Self::BasePrimeField`.
+    const EXTENSION_DEGREE: usize;
+
+    /// Returns the degrees of the extensions of the tower, from the prime
+    /// field upwards, e.g. `[2, 3, 2]` for Fp12 as quadratic extension of a
+    /// cubic extension of Fp2. Empty for prime fields.
+    fn tower_shape() -> Vec<usize>;
+
+    /// Returns the nonresidues defining the extensions of the tower, from the
+    /// prime field upwards, each given by its coefficients over
+    /// `Self::BasePrimeField` (as by `to_base_prime_field_elements`). Empty
+    /// for prime fields.
+    fn tower_nonresidues() -> Vec<Vec<Self::BasePrimeField>>;
+
+    /// Returns the `Self::EXTENSION_DEGREE` coefficients of `self` over
+    /// `Self::BasePrimeField`, in the order they are serialized.
+    fn to_base_prime_field_elements(&self) -> Vec<Self::BasePrimeField>;
+
+    /// Returns `self` as an element of `Self::BasePrimeField`, or `None` if
+    /// any of its coefficients but the constant one is non-zero.
+    fn as_base_field(&self) -> Option<Self::BasePrimeField>;
+
+    /// Embeds an element of `Self::BasePrimeField` into `Self`.
+    fn from_base_field(elem: Self::BasePrimeField) -> Self;
+
+    /// Returns the norm of `self` over `Self::BasePrimeField`, i.e. the
+    /// product of its conjugates. Computed through the tower, as the norm of
+    /// the norm over the field below.
+    fn norm(&self) -> Self::BasePrimeField;
+
+    /// Returns the trace of `self` over `Self::BasePrimeField`, i.e. the sum
+    /// of its conjugates. Computed through the tower, as the trace of the
+    /// trace over the field below.
+    fn trace(&self) -> Self::BasePrimeField;
+
     /// Returns the zero element of the field, the additive identity.
     fn zero() -> Self;
 
@@ -180,6 +231,11 @@ pub trait FpParameters: 'static + Send + Sync + Sized {
     /// INV = -(MODULUS^{-1} mod MODULUS) mod MODULUS
     const INV: u64;
 
+    /// BARRETT_MU = floor(2^(128 * N) / MODULUS), with N the number of limbs of
+    /// `Self::BigInt`, as N + 1 little endian limbs. If empty, as by default,
+    /// `from_le_bytes_mod_order` reduces all inputs limb by limb.
+    const BARRETT_MU: &'static [u64] = &[];
+
     /// A multiplicative generator that is also a quadratic nonresidue.
     /// `Self::GENERATOR` is an element having multiplicative order
     /// `Self::MODULUS - 1`.
@@ -197,6 +253,18 @@ pub trait FpParameters: 'static + Send + Sync + Sized {
     /// 2^s root of unity computed by GENERATOR^t
     const ROOT_OF_UNITY: Self::BigInt;
 
+    /// An odd prime `q` such that `q^k` divides `(MODULUS - 1) / 2^s` for a
+    /// not too small `k`, if any is specified. Allows evaluation domains of
+    /// mixed radix `2^i * q^j`.
+    const SMALL_SUBGROUP_BASE: Option<u32> = None;
+
+    /// The `k` for `Self::SMALL_SUBGROUP_BASE`.
+    const SMALL_SUBGROUP_BASE_ADICITY: Option<u32> = None;
+
+    /// A `2^s * q^k` root of unity, with `q` and `k` as above. Its `q^k`-th
+    /// power is `Self::ROOT_OF_UNITY`.
+    const LARGE_SUBGROUP_ROOT_OF_UNITY: Option<Self::BigInt> = None;
+
     /// t for 2^s * t = MODULUS - 1
     const T: Self::BigInt;
 
@@ -205,10 +273,24 @@ pub trait FpParameters: 'static + Send + Sync + Sized {
 
     /// (Self::MODULUS - 1) / 2
     const MODULUS_MINUS_ONE_DIV_TWO: Self::BigInt;
+
+    /// The algorithm `Field::inverse()` uses, to be chosen by benchmarking
+    /// both on the field.
+    const INVERSION_ALGORITHM: InversionAlgorithm = InversionAlgorithm::BinaryEuclid;
+}
+
+/// The algorithms for inversion in prime fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InversionAlgorithm {
+    /// The binary extended Euclidean algorithm, of variable time.
+    BinaryEuclid,
+    /// The safegcd algorithm of Bernstein and Yang, of constant time. See
+    /// `safegcd::safegcd_inverse`.
+    SafeGcd,
 }
 
 /// The interface for a prime field.
-pub trait PrimeField: Field + FromStr {
+pub trait PrimeField: Field<BasePrimeField = Self> + FromStr {
     type Params: FpParameters<BigInt = Self::BigInt>;
     type BigInt: BigInteger;
 
@@ -234,6 +316,10 @@ pub trait PrimeField: Field + FromStr {
     /// Returns the 2^s root of unity.
     fn root_of_unity() -> Self;
 
+    /// Returns the `2^s * q^k` root of unity of the mixed radix domains, if
+    /// the field specifies a `SMALL_SUBGROUP_BASE`.
+    fn large_subgroup_root_of_unity() -> Option<Self>;
+
     /// Return the a QNR^T
     fn qnr_to_t() -> Self {
         Self::root_of_unity()
@@ -259,6 +345,132 @@ pub trait PrimeField: Field + FromStr {
         Self::Params::MODULUS_MINUS_ONE_DIV_TWO
     }
 
+    /// Returns true iff `repr` is the canonical representative of a field
+    /// element, i.e. it is strictly smaller than the modulus.
+    fn is_canonical_repr(repr: &Self::BigInt) -> bool {
+        repr < &Self::Params::MODULUS
+    }
+
+    /// Reads a field element from the little endian byte encoding of an
+    /// integer, handling non canonical encodings according to `mode`.
+    fn from_le_bytes_with_mode(bytes: &[u8], mode: DecodingMode) -> Result<Self, FieldDecodingError> {
+        match mode {
+            DecodingMode::Strict => {
+                let repr_bytes = Self::BigInt::default().as_ref().len() * 8;
+                if bytes.len() > repr_bytes {
+                    return Err(FieldDecodingError::InvalidLength(bytes.len(), repr_bytes));
+                }
+                let mut padded = bytes.to_vec();
+                padded.resize(repr_bytes, 0u8);
+                let repr = Self::BigInt::read(padded.as_slice())
+                    .map_err(|_| FieldDecodingError::InvalidLength(bytes.len(), repr_bytes))?;
+                if Self::is_canonical_repr(&repr) {
+                    Ok(Self::from_repr(repr))
+                } else {
+                    Err(FieldDecodingError::NonCanonical)
+                }
+            },
+            DecodingMode::ReduceModOrder => Ok(Self::from_le_bytes_mod_order(bytes)),
+        }
+    }
+
+    /// Reads a field element from the little endian byte encoding of an
+    /// integer, reducing it modulo the field characteristic.
+    ///
+    /// Inputs of up to twice the size of `Self::BigInt`, such as the outputs
+    /// of `expand_message` when hashing to the field, are reduced by a single
+    /// Barrett reduction, if `Self::Params::BARRETT_MU` is given. Other inputs
+    /// are reduced limb by limb.
+    fn from_le_bytes_mod_order(bytes: &[u8]) -> Self {
+        let modulus = Self::Params::MODULUS;
+        let num_limbs = modulus.as_ref().len();
+
+        if !Self::Params::BARRETT_MU.is_empty() && bytes.len() <= 16 * num_limbs {
+            let mut limbs = vec![0u64; 2 * num_limbs];
+            for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+                let mut buf = [0u8; 8];
+                buf[..chunk.len()].copy_from_slice(chunk);
+                *limb = u64::from_le_bytes(buf);
+            }
+            let mu = Self::Params::BARRETT_MU;
+            let mut repr = Self::BigInt::default();
+            repr.as_mut().copy_from_slice(&fa::barrett_reduce(&limbs, modulus.as_ref(), mu));
+            return Self::from_repr(repr);
+        }
+        from_le_bytes_mod_order_by_limbs(bytes)
+    }
+
+    /// Reads a field element from the big endian bit encoding of an integer,
+    /// handling non canonical encodings according to `mode`.
+    fn from_bits_with_mode(bits: &[bool], mode: DecodingMode) -> Result<Self, FieldDecodingError> {
+        match mode {
+            DecodingMode::Strict => {
+                let modulus_bits = Self::Params::MODULUS_BITS as usize;
+                let leading_zeros = bits.iter().take_while(|b| !**b).count();
+                let bits = &bits[leading_zeros..];
+                if bits.len() > modulus_bits {
+                    return Err(FieldDecodingError::InvalidLength(bits.len(), modulus_bits));
+                }
+                let repr = Self::BigInt::from_bits(bits);
+                if Self::is_canonical_repr(&repr) {
+                    Ok(Self::from_repr(repr))
+                } else {
+                    Err(FieldDecodingError::NonCanonical)
+                }
+            },
+            DecodingMode::ReduceModOrder => {
+                let two = Self::one().double();
+                let mut res = Self::zero();
+                for &b in bits.iter() {
+                    res *= &two;
+                    if b {
+                        res += &Self::one();
+                    }
+                }
+                Ok(res)
+            },
+        }
+    }
+}
+
+/// Policy applied when decoding a prime field element from an encoding of an
+/// integer which might not be its canonical representative.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodingMode {
+    /// Reject encodings of integers greater than or equal to the modulus.
+    /// Required wherever malleability matters, e.g. signature verification
+    /// or transcript determinism.
+    Strict,
+    /// Reduce the encoded integer modulo the field characteristic.
+    /// Suitable when mapping hash outputs to field elements.
+    ReduceModOrder,
+}
+
+/// Errors returned when decoding a prime field element.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FieldDecodingError {
+    /// The encoded integer is greater than or equal to the modulus.
+    NonCanonical,
+    /// The encoding has length `.0`, while at most `.1` is allowed.
+    InvalidLength(usize, usize),
+}
+
+impl Display for FieldDecodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldDecodingError::NonCanonical => write!(f, "encoding is over the field modulus"),
+            FieldDecodingError::InvalidLength(len, max) => {
+                write!(f, "encoding length {} is greater than the maximum allowed ({})", len, max)
+            },
+        }
+    }
+}
+
+impl std::error::Error for FieldDecodingError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
 }
 
 impl<F: PrimeField> ToBits for F {
@@ -286,6 +498,8 @@ impl<F: PrimeField> ToBits for F {
 }
 
 impl<F: PrimeField> FromBits for F {
+    const NUM_BITS: usize = <F as PrimeField>::Params::MODULUS_BITS as usize;
+
     #[inline]
     fn read_bits(bits: Vec<bool>) -> Result<Self, Error> {
         let modulus_bits = <Self as PrimeField>::Params::MODULUS_BITS as usize;
@@ -314,6 +528,16 @@ impl<F: PrimeField> FromBits for F {
             }
         }
     }
+
+    fn read_bits_with_mode(bits: Vec<bool>, mode: DecodingMode) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        Ok(Self::from_bits_with_mode(&bits, mode)?)
+    }
+
+    #[inline]
+    fn read_bits_unchecked(bits: Vec<bool>) -> Self {
+        Self::from_repr(<Self as PrimeField>::BigInt::from_bits(&bits))
+    }
 }
 
 /// Attempts to convert a boolean vec into a valid field element for field `ToF`.
@@ -340,6 +564,11 @@ pub trait SquareRootField: Field {
     /// Returns the Legendre symbol.
     fn legendre(&self) -> LegendreSymbol;
 
+    /// Returns whether `self` is a square, zero included.
+    fn is_square(&self) -> bool {
+        !self.legendre().is_qnr()
+    }
+
     /// Returns the square root of self, if it exists.
     #[must_use]
     fn sqrt(&self) -> Option<Self>;
@@ -409,6 +638,26 @@ impl_field_into_bigint!(Fp384, BigInteger384, Fp384Parameters);
 impl_field_into_bigint!(Fp768, BigInteger768, Fp768Parameters);
 impl_field_into_bigint!(Fp832, BigInteger832, Fp832Parameters);
 
+/// Reduces the little endian byte encoding of an integer modulo the field
+/// characteristic by Horner's rule over 64 bit limbs, most significant limb
+/// first.
+pub(crate) fn from_le_bytes_mod_order_by_limbs<F: PrimeField>(bytes: &[u8]) -> F {
+    let two_to_64 = F::from(std::u64::MAX) + &F::one();
+    let mut res = F::zero();
+    let rem = bytes.len() % 8;
+    let (head, tail) = bytes.split_at(bytes.len() - rem);
+    let mut top = [0u8; 8];
+    top[..rem].copy_from_slice(tail);
+    res += &F::from(u64::from_le_bytes(top));
+    for chunk in head.chunks(8).rev() {
+        let mut limb = [0u8; 8];
+        limb.copy_from_slice(chunk);
+        res *= &two_to_64;
+        res += &F::from(u64::from_le_bytes(limb));
+    }
+    res
+}
+
 pub fn batch_inversion<F: Field>(v: &mut [F]) {
     // Montgomery’s Trick and Fast Implementation of Masked AES
     // Genelle, Prouff and Quisquater
@@ -440,3 +689,48 @@ pub fn batch_inversion<F: Field>(v: &mut [F]) {
         tmp = newtmp;
     }
 }
+
+/// The flag of the compressed identity of an algebraic torus, see
+/// `write_torus_compressed`.
+pub const TORUS_IDENTITY_FLAG: u8 = 1;
+
+/// Returns the compressed encoding of an element c0 + c1·w of norm one of a
+/// quadratic extension F[w], represented by g = (1 + c0) / c1 in F, or by None
+/// for the identity: the encoding of g (zero for the identity) followed by a
+/// flags byte, `TORUS_IDENTITY_FLAG` for the identity.
+pub(crate) fn write_torus_compressed<F: Field>(g: Option<F>) -> Vec<u8> {
+    let mut bytes = vec![];
+    g.unwrap_or_else(F::zero).write(&mut bytes).unwrap();
+    bytes.push(if g.is_none() { TORUS_IDENTITY_FLAG } else { 0 });
+    bytes
+}
+
+/// Decodes the encoding of `write_torus_compressed`, returning None for the
+/// identity.
+pub(crate) fn read_torus_compressed<F: Field>(bytes: &[u8]) -> Result<Option<F>, Error> {
+    let mut reader = bytes;
+    let g = F::read(&mut reader)?;
+    let flags = u8::read(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(Box::new(BitSerializationError::InvalidLength(
+            bytes.len() - reader.len(),
+            bytes.len(),
+        )));
+    }
+    match flags {
+        0 => Ok(Some(g)),
+        TORUS_IDENTITY_FLAG => Ok(None),
+        _ => Err(Box::new(BitSerializationError::InvalidFlags)),
+    }
+}
+
+/// Checks that `bytes` is the canonical compressed encoding `encoding` of the
+/// decoded element, so that each element has a single compressed encoding.
+pub(crate) fn check_torus_compressed(encoding: Vec<u8>, bytes: &[u8]) -> Result<(), Error> {
+    if encoding != bytes {
+        return Err(Box::new(BitSerializationError::InvalidFieldElement(
+            "non-canonical compressed element".to_owned(),
+        )));
+    }
+    Ok(())
+}
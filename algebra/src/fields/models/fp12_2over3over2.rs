@@ -1,5 +1,6 @@
 use rand::{Rng, distributions::{Standard, Distribution}};
-use crate::{UniformRand, ToBits, FromBits, PrimeField, Error};
+use crate::{bits::{check_bits_len, split_coefficient_bits}, fields::DecodingMode};
+use crate::{UniformRand, ToBits, FromBits, Error};
 use std::{
     cmp::Ordering,
     io::{Read, Result as IoResult, Write},
@@ -9,7 +10,10 @@ use std::{
 
 use crate::{
     bytes::{FromBytes, ToBytes},
-    fields::{fp6_3over2::*, Field, Fp2, Fp2Parameters, FpParameters},
+    fields::{
+        batch_inversion, check_torus_compressed, fp6_3over2::*, read_torus_compressed,
+        write_torus_compressed, Field, Fp2, Fp2Parameters,
+    },
     BitIterator,
 };
 
@@ -18,6 +22,13 @@ pub trait Fp12Parameters: 'static + Send + Sync + Copy {
 
     /// Coefficients for the Frobenius automorphism.
     const FROBENIUS_COEFF_FP12_C1: [Fp2<Fp2Params<Self>>; 12];
+
+    /// Returns the coefficient of `c1` in the `power`-th Frobenius map. As
+    /// the Frobenius map is of order 12 on `Fp12`, any `power` is valid.
+    #[inline(always)]
+    fn frobenius_coeff_c1(power: usize) -> Fp2<Fp2Params<Self>> {
+        Self::FROBENIUS_COEFF_FP12_C1[power % 12]
+    }
 }
 
 /// An element of Fp12, represented by c0 + c1 * v
@@ -201,6 +212,164 @@ impl<P: Fp12Parameters> Fp12<P> {
         }
         res
     }
+
+    /// Squares an element of the cyclotomic subgroup in the compressed form
+    /// of Karabina (https://eprint.iacr.org/2010/542.pdf), which consists of
+    /// c0.c1, c0.c2, c1.c0 and c1.c2 only, ignoring c0.c0 and c1.c1.
+    fn cyclotomic_square_compressed(&mut self) {
+        let fp2_nr = <P::Fp6Params as Fp6Parameters>::mul_fp2_by_nonresidue;
+
+        let g1 = self.c0.c1;
+        let g2 = self.c0.c2;
+        let g3 = self.c1.c0;
+        let g5 = self.c1.c2;
+
+        let g1_sq = g1.square();
+        let g2_sq = g2.square();
+        let g3_sq = g3.square();
+        let g5_sq = g5.square();
+
+        // 2·g1·g5 and 2·g2·g3
+        let g1_g5 = (g1 + &g5).square() - &g1_sq - &g5_sq;
+        let g2_g3 = (g2 + &g3).square() - &g2_sq - &g3_sq;
+
+        // c1.c0 = 3·xi·(2·g1·g5) + 2·g3
+        let tmp = fp2_nr(&g1_g5);
+        self.c1.c0 = (tmp + &g3).double() + &tmp;
+
+        // c0.c2 = 3·(xi·g5^2 + g1^2) - 2·g2
+        let tmp = fp2_nr(&g5_sq) + &g1_sq;
+        self.c0.c2 = (tmp - &g2).double() + &tmp;
+
+        // c0.c1 = 3·(g3^2 + xi·g2^2) - 2·g1
+        let tmp = g3_sq + &fp2_nr(&g2_sq);
+        self.c0.c1 = (tmp - &g1).double() + &tmp;
+
+        // c1.c2 = 3·(2·g2·g3) + 2·g5
+        self.c1.c2 = (g2_g3 + &g5).double() + &g2_g3;
+    }
+
+    /// Returns the numerator and the denominator of c1.c1 of the element of
+    /// the cyclotomic subgroup with Karabina's compressed form `self`. The
+    /// denominator is zero only for the identity.
+    fn cyclotomic_decompression_fraction(&self) -> (Fp2<Fp2Params<P>>, Fp2<Fp2Params<P>>) {
+        let fp2_nr = <P::Fp6Params as Fp6Parameters>::mul_fp2_by_nonresidue;
+
+        let g1 = self.c0.c1;
+        let g2 = self.c0.c2;
+        let g3 = self.c1.c0;
+        let g5 = self.c1.c2;
+
+        if g3.is_zero() {
+            // c1.c1 = 2·g1·g5 / g2
+            ((g1 * &g5).double(), g2)
+        } else {
+            // c1.c1 = (xi·g5^2 + 3·g1^2 - 2·g2) / 4·g3
+            let g1_sq = g1.square();
+            let num = fp2_nr(&g5.square()) + &(g1_sq - &g2).double() + &g1_sq;
+            (num, g3.double().double())
+        }
+    }
+
+    /// Completes the compressed form `self` of an element of the cyclotomic
+    /// subgroup, given its coefficient c1.c1.
+    fn cyclotomic_decompress(mut self, c1_c1: Fp2<Fp2Params<P>>) -> Self {
+        let fp2_nr = <P::Fp6Params as Fp6Parameters>::mul_fp2_by_nonresidue;
+
+        // c0.c0 = xi·(2·c1.c1^2 + c1.c0·c1.c2 - 3·c0.c1·c0.c2) + 1
+        let g1_g2 = self.c0.c1 * &self.c0.c2;
+        let tmp = (c1_c1.square() - &g1_g2).double() - &g1_g2 + &(self.c1.c0 * &self.c1.c2);
+        self.c0.c0 = fp2_nr(&tmp) + &Fp2::one();
+        self.c1.c1 = c1_c1;
+        self
+    }
+
+    /// Raises `self`, which must be in the cyclotomic subgroup, to the power
+    /// `exp`, like `cyclotomic_exp`, but with Karabina's compressed squarings.
+    /// The squarings self^(2^i) at the set bits i of `exp` are decompressed at
+    /// the cost of a single inversion, so this is faster for exponents of low
+    /// Hamming weight, such as the parameter x of the BLS12 curves.
+    pub fn cyclotomic_exp_sparse<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        // The bits of `exp` from the least significant one, up to the most
+        // significant set bit
+        let mut bits = BitIterator::new(exp).skip_while(|bit| !bit).collect::<Vec<_>>();
+        bits.reverse();
+
+        let mut res = Self::one();
+        let mut squarings = Vec::new();
+        let mut compressed = *self;
+        for (i, bit) in bits.into_iter().enumerate() {
+            if i > 0 {
+                compressed.cyclotomic_square_compressed();
+            }
+            if bit {
+                if i == 0 {
+                    res = *self;
+                } else {
+                    squarings.push(compressed);
+                }
+            }
+        }
+
+        let (numerators, mut denominators): (Vec<_>, Vec<_>) = squarings
+            .iter()
+            .map(|squaring| squaring.cyclotomic_decompression_fraction())
+            .unzip();
+        batch_inversion(&mut denominators);
+        for ((squaring, num), den_inv) in squarings.into_iter().zip(numerators).zip(denominators) {
+            // A zero denominator, left as is by the batch inversion, is the
+            // one of the identity
+            if !den_inv.is_zero() {
+                res *= &squaring.cyclotomic_decompress(num * &den_inv);
+            }
+        }
+        res
+    }
+
+    /// Returns the norm c0^2 - v·c1^2 of `self` over Fp6, which is one for the
+    /// elements of the cyclotomic subgroup, e.g. the outputs of pairings.
+    pub fn norm_over_fp6(&self) -> Fp6<P::Fp6Params> {
+        self.c0.square() - &Self::mul_fp6_by_nonresidue(&self.c1.square())
+    }
+
+    /// Compresses `self`, which must be of norm one over Fp6, e.g. the output
+    /// of a pairing, to half its size with the algebraic torus T2 (Rubin and
+    /// Silverberg, https://eprint.iacr.org/2003/039.pdf): c0 + c1·w is
+    /// represented by g = (1 + c0) / c1, with g = 0 for -1, and the identity is
+    /// flagged.
+    pub fn to_compressed(&self) -> Vec<u8> {
+        debug_assert!(self.norm_over_fp6().is_one());
+        let g = if self.c1.is_zero() {
+            if self.c0.is_one() {
+                None
+            } else {
+                Some(Fp6::zero())
+            }
+        } else {
+            Some((Fp6::one() + &self.c0) * &self.c1.inverse().unwrap())
+        };
+        write_torus_compressed(g)
+    }
+
+    /// Decodes the encoding of `to_compressed` as (g + w) / (g - w). Fails if
+    /// the encoding is not canonical. The decoded element is of norm one, but
+    /// it is up to the caller to check that it is in the subgroup of pairing
+    /// outputs, if needed.
+    pub fn from_compressed(bytes: &[u8]) -> Result<Self, Error> {
+        let res = match read_torus_compressed::<Fp6<P::Fp6Params>>(bytes)? {
+            None => Self::one(),
+            Some(g) => {
+                // (g + w) / (g - w) = (g^2 + v + 2·g·w) / (g^2 - v), where
+                // g^2 - v is not zero as v is not a square in Fp6
+                let g_sq = g.square();
+                let v = Self::mul_fp6_by_nonresidue(&Fp6::one());
+                let den_inv = (g_sq - &v).inverse().unwrap();
+                Self::new((g_sq + &v) * &den_inv, g.double() * &den_inv)
+            },
+        };
+        check_torus_compressed(res.to_compressed(), bytes)?;
+        Ok(res)
+    }
 }
 
 impl<P: Fp12Parameters> std::fmt::Display for Fp12<P> {
@@ -217,6 +386,53 @@ impl<P: Fp12Parameters> Distribution<Fp12<P>> for Standard {
 }
 
 impl<P: Fp12Parameters> Field for Fp12<P> {
+    type BasePrimeField = <Fp2Params<P> as Fp2Parameters>::Fp;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 2;
+    const EXTENSION_DEGREE: usize = 2 * <Fp6<P::Fp6Params> as Field>::EXTENSION_DEGREE;
+
+    fn tower_shape() -> Vec<usize> {
+        let mut shape = <Fp6<P::Fp6Params> as Field>::tower_shape();
+        shape.push(2);
+        shape
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self::BasePrimeField>> {
+        let mut nonresidues = <Fp6<P::Fp6Params> as Field>::tower_nonresidues();
+        nonresidues.push(Fp6::<P::Fp6Params>::new(Fp2::zero(), Fp2::one(), Fp2::zero()).to_base_prime_field_elements());
+        nonresidues
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self::BasePrimeField> {
+        let mut elements = Vec::with_capacity(Self::EXTENSION_DEGREE);
+        elements.extend(self.c0.to_base_prime_field_elements());
+        elements.extend(self.c1.to_base_prime_field_elements());
+        elements
+    }
+
+    fn as_base_field(&self) -> Option<Self::BasePrimeField> {
+        if self.c1.is_zero() {
+            self.c0.as_base_field()
+        } else {
+            None
+        }
+    }
+
+    fn from_base_field(elem: Self::BasePrimeField) -> Self {
+        Self::new(Fp6::from_base_field(elem), Fp6::zero())
+    }
+
+    /// Norm over Fp, as the norm of Norm(a) = a.c0^2 - nonresidue * a.c1^2
+    /// over Fp6.
+    fn norm(&self) -> Self::BasePrimeField {
+        (self.c0.square() - &Self::mul_fp6_by_nonresidue(&self.c1.square())).norm()
+    }
+
+    /// Trace over Fp, as the trace of Tr(a) = 2 * a.c0 over Fp6.
+    fn trace(&self) -> Self::BasePrimeField {
+        self.c0.double().trace()
+    }
+
     fn zero() -> Self {
         Self::new(Fp6::zero(), Fp6::zero())
     }
@@ -258,15 +474,10 @@ impl<P: Fp12Parameters> Field for Fp12<P> {
         self.c0.frobenius_map(power);
         self.c1.frobenius_map(power);
 
-        self.c1
-            .c0
-            .mul_assign(&P::FROBENIUS_COEFF_FP12_C1[power % 12]);
-        self.c1
-            .c1
-            .mul_assign(&P::FROBENIUS_COEFF_FP12_C1[power % 12]);
-        self.c1
-            .c2
-            .mul_assign(&P::FROBENIUS_COEFF_FP12_C1[power % 12]);
+        let coeff = P::frobenius_coeff_c1(power);
+        self.c1.c0.mul_assign(&coeff);
+        self.c1.c1.mul_assign(&coeff);
+        self.c1.c2.mul_assign(&coeff);
     }
 
     fn square(&self) -> Self {
@@ -487,16 +698,48 @@ impl<P: Fp12Parameters> ToBits for Fp12<P> {
         let mut bits = self.c0.write_bits();
         bits.extend_from_slice(self.c1.write_bits().as_slice());
         bits
+    }
 
+    /// Writes the coefficients in increasing degree, each least significant
+    /// bit first.
+    fn write_bits_le(&self) -> Vec<bool> {
+        let mut bits = self.c0.write_bits_le();
+        bits.extend_from_slice(self.c1.write_bits_le().as_slice());
+        bits
     }
 }
 
 impl<P: Fp12Parameters> FromBits for Fp12<P> {
+    const NUM_BITS: usize = 2 * <Fp6<P::Fp6Params> as FromBits>::NUM_BITS;
+
     fn read_bits(bits: Vec<bool>) -> Result<Self, Error> {
-        let size = (6 * <<<P::Fp6Params as Fp6Parameters>::Fp2Params as Fp2Parameters>
-        ::Fp as PrimeField>::Params::MODULUS_BITS) as usize;
-        let c0 = Fp6::read_bits(bits[..size].to_vec())?;
-        let c1 = Fp6::read_bits(bits[size..].to_vec())?;
+        let size = <Fp6<P::Fp6Params> as FromBits>::NUM_BITS;
+        let mut coeffs = split_coefficient_bits(&bits, size, 2)?.into_iter();
+        let c0 = Fp6::read_bits(coeffs.next().unwrap())?;
+        let c1 = Fp6::read_bits(coeffs.next().unwrap())?;
         Ok(Fp12::new(c0, c1))
     }
+
+    fn read_bits_le(bits: Vec<bool>) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <Fp6<P::Fp6Params> as FromBits>::NUM_BITS;
+        let c0 = Fp6::read_bits_le(bits[..size].to_vec())?;
+        let c1 = Fp6::read_bits_le(bits[size..2 * size].to_vec())?;
+        Ok(Fp12::new(c0, c1))
+    }
+
+    fn read_bits_with_mode(bits: Vec<bool>, mode: DecodingMode) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <Fp6<P::Fp6Params> as FromBits>::NUM_BITS;
+        let c0 = Fp6::read_bits_with_mode(bits[..size].to_vec(), mode)?;
+        let c1 = Fp6::read_bits_with_mode(bits[size..2 * size].to_vec(), mode)?;
+        Ok(Fp12::new(c0, c1))
+    }
+
+    fn read_bits_unchecked(bits: Vec<bool>) -> Self {
+        let size = <Fp6<P::Fp6Params> as FromBits>::NUM_BITS;
+        let c0 = Fp6::read_bits_unchecked(bits[..size].to_vec());
+        let c1 = Fp6::read_bits_unchecked(bits[size..].to_vec());
+        Fp12::new(c0, c1)
+    }
 }
@@ -1,3 +1,4 @@
+use crate::{bits::{check_bits_len, split_coefficient_bits}, fields::DecodingMode};
 use crate::{UniformRand, ToBits, FromBits, Error};
 use rand::{Rng, distributions::{Standard, Distribution}};
 use std::{
@@ -8,7 +9,7 @@ use std::{
 };
 use crate::{
     bytes::{FromBytes, ToBytes},
-    fields::{Field, LegendreSymbol, PrimeField, SquareRootField, FpParameters},
+    fields::{Field, LegendreSymbol, PrimeField, SquareRootField},
 };
 
 /// Model for quadratic extension field of prime field F=Fp
@@ -32,6 +33,13 @@ pub trait Fp2Parameters: 'static + Send + Sync {
     // (pi^0(X), pi^1(X)) = (C1_0*X, C1_1*X),
     const FROBENIUS_COEFF_FP2_C1: [Self::Fp; 2];
 
+    /// Returns the coefficient of `c1` in the `power`-th Frobenius map. As
+    /// the Frobenius map is of order 2 on `Fp2`, any `power` is valid.
+    #[inline(always)]
+    fn frobenius_coeff_c1(power: usize) -> Self::Fp {
+        Self::FROBENIUS_COEFF_FP2_C1[power % 2]
+    }
+
     #[inline(always)]
     fn mul_fp_by_nonresidue(fe: &Self::Fp) -> Self::Fp {
         Self::NONRESIDUE * fe
@@ -65,8 +73,51 @@ impl<P: Fp2Parameters> Fp2<P> {
         }
     }
 
+    pub fn mul_by_fp(&mut self, element: &P::Fp) {
+        self.c0.mul_assign(&element);
+        self.c1.mul_assign(&element);
+    }
+}
+
+impl<P: Fp2Parameters> Field for Fp2<P> {
+    type BasePrimeField = P::Fp;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 2;
+    const EXTENSION_DEGREE: usize = 2 * <P::Fp as Field>::EXTENSION_DEGREE;
+
+    fn tower_shape() -> Vec<usize> {
+        let mut shape = <P::Fp as Field>::tower_shape();
+        shape.push(2);
+        shape
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self::BasePrimeField>> {
+        let mut nonresidues = <P::Fp as Field>::tower_nonresidues();
+        nonresidues.push(vec![P::NONRESIDUE]);
+        nonresidues
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self::BasePrimeField> {
+        let mut elements = Vec::with_capacity(Self::EXTENSION_DEGREE);
+        elements.extend(self.c0.to_base_prime_field_elements());
+        elements.extend(self.c1.to_base_prime_field_elements());
+        elements
+    }
+
+    fn as_base_field(&self) -> Option<Self::BasePrimeField> {
+        if self.c1.is_zero() {
+            Some(self.c0)
+        } else {
+            None
+        }
+    }
+
+    fn from_base_field(elem: Self::BasePrimeField) -> Self {
+        Self::new(elem, P::Fp::zero())
+    }
+
     /// Norm of Fp2 over Fp: Norm(a) = a.x^2 - beta * a.y^2
-    pub fn norm(&self) -> P::Fp {
+    fn norm(&self) -> Self::BasePrimeField {
         let t0 = self.c0.square();
         let mut t1 = self.c1.square();
         t1 = -P::mul_fp_by_nonresidue(&t1);
@@ -74,13 +125,11 @@ impl<P: Fp2Parameters> Fp2<P> {
         t1
     }
 
-    pub fn mul_by_fp(&mut self, element: &P::Fp) {
-        self.c0.mul_assign(&element);
-        self.c1.mul_assign(&element);
+    /// Trace of Fp2 over Fp: Tr(a) = 2 * a.x
+    fn trace(&self) -> Self::BasePrimeField {
+        self.c0.double()
     }
-}
 
-impl<P: Fp2Parameters> Field for Fp2<P> {
     fn zero() -> Self {
         Fp2::new(P::Fp::zero(), P::Fp::zero())
     }
@@ -172,7 +221,7 @@ impl<P: Fp2Parameters> Field for Fp2<P> {
     }
 
     fn frobenius_map(&mut self, power: usize) {
-        self.c1.mul_assign(&P::FROBENIUS_COEFF_FP2_C1[power % 2]);
+        self.c1.mul_assign(&P::frobenius_coeff_c1(power));
     }
 }
 
@@ -292,17 +341,50 @@ impl<P: Fp2Parameters> ToBits for Fp2<P> {
         let mut bits = self.c0.write_bits();
         bits.extend_from_slice(self.c1.write_bits().as_slice());
         bits
+    }
 
+    /// Writes the coefficients in increasing degree, each least significant
+    /// bit first.
+    fn write_bits_le(&self) -> Vec<bool> {
+        let mut bits = self.c0.write_bits_le();
+        bits.extend_from_slice(self.c1.write_bits_le().as_slice());
+        bits
     }
 }
 
 impl<P: Fp2Parameters> FromBits for Fp2<P> {
+    const NUM_BITS: usize = 2 * <P::Fp as FromBits>::NUM_BITS;
+
     fn read_bits(bits: Vec<bool>) -> Result<Self, Error> {
-        let size = <P::Fp as PrimeField>::Params::MODULUS_BITS as usize;
-        let c0 = P::Fp::read_bits(bits[..size].to_vec())?;
-        let c1 = P::Fp::read_bits(bits[size..].to_vec())?;
+        let size = <P::Fp as FromBits>::NUM_BITS;
+        let mut coeffs = split_coefficient_bits(&bits, size, 2)?.into_iter();
+        let c0 = P::Fp::read_bits(coeffs.next().unwrap())?;
+        let c1 = P::Fp::read_bits(coeffs.next().unwrap())?;
+        Ok(Fp2::new(c0, c1))
+    }
+
+    fn read_bits_le(bits: Vec<bool>) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <P::Fp as FromBits>::NUM_BITS;
+        let c0 = P::Fp::read_bits_le(bits[..size].to_vec())?;
+        let c1 = P::Fp::read_bits_le(bits[size..2 * size].to_vec())?;
         Ok(Fp2::new(c0, c1))
     }
+
+    fn read_bits_with_mode(bits: Vec<bool>, mode: DecodingMode) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <P::Fp as FromBits>::NUM_BITS;
+        let c0 = P::Fp::read_bits_with_mode(bits[..size].to_vec(), mode)?;
+        let c1 = P::Fp::read_bits_with_mode(bits[size..2 * size].to_vec(), mode)?;
+        Ok(Fp2::new(c0, c1))
+    }
+
+    fn read_bits_unchecked(bits: Vec<bool>) -> Self {
+        let size = <P::Fp as FromBits>::NUM_BITS;
+        let c0 = P::Fp::read_bits_unchecked(bits[..size].to_vec());
+        let c1 = P::Fp::read_bits_unchecked(bits[size..].to_vec());
+        Fp2::new(c0, c1)
+    }
 }
 
 impl<P: Fp2Parameters> Neg for Fp2<P> {
@@ -1,4 +1,5 @@
 use rand::{Rng, distributions::{Standard, Distribution}};
+use crate::{bits::{check_bits_len, split_coefficient_bits}, fields::DecodingMode};
 use crate::{UniformRand, ToBits, FromBits, Error};
 use std::{
     cmp::{Ord, Ordering, PartialOrd},
@@ -10,7 +11,7 @@ use std::{
 
 use crate::{
     bytes::{FromBytes, ToBytes},
-    fields::{Field, LegendreSymbol, PrimeField, SquareRootField, FpParameters},
+    fields::{Field, LegendreSymbol, PrimeField, SquareRootField},
 };
 
 /// Model for cubic extension field of a prime field F=Fp
@@ -41,6 +42,19 @@ pub trait Fp3Parameters: 'static + Send + Sync {
     /// this is needed for the square root algorithm
     const QUADRATIC_NONRESIDUE_TO_T: (Self::Fp, Self::Fp, Self::Fp);
 
+    /// Returns the coefficient of `c1` in the `power`-th Frobenius map. As
+    /// the Frobenius map is of order 3 on `Fp3`, any `power` is valid.
+    #[inline(always)]
+    fn frobenius_coeff_c1(power: usize) -> Self::Fp {
+        Self::FROBENIUS_COEFF_FP3_C1[power % 3]
+    }
+
+    /// Returns the coefficient of `c2` in the `power`-th Frobenius map.
+    #[inline(always)]
+    fn frobenius_coeff_c2(power: usize) -> Self::Fp {
+        Self::FROBENIUS_COEFF_FP3_C2[power % 3]
+    }
+
     #[inline(always)]
     fn mul_fp_by_nonresidue(fe: &Self::Fp) -> Self::Fp {
         Self::NONRESIDUE * fe
@@ -82,17 +96,6 @@ impl<P: Fp3Parameters> Fp3<P> {
         self.c2.mul_assign(value);
     }
 
-    // Calculate the norm of an element with respect to the base field Fp.
-    pub fn norm(&self) -> P::Fp {
-        let mut self_to_p = *self;
-        self_to_p.frobenius_map(1);
-        let mut self_to_p2 = *self;
-        self_to_p2.frobenius_map(2);
-        self_to_p *= &(self_to_p2 * self);
-        assert!(self_to_p.c1.is_zero() && self_to_p.c2.is_zero());
-        self_to_p.c0
-    }
-
     // Returns the value of QNR^T.
     #[inline]
     pub fn qnr_to_t() -> Self {
@@ -105,6 +108,59 @@ impl<P: Fp3Parameters> Fp3<P> {
 }
 
 impl<P: Fp3Parameters> Field for Fp3<P> {
+    type BasePrimeField = P::Fp;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 3;
+    const EXTENSION_DEGREE: usize = 3 * <P::Fp as Field>::EXTENSION_DEGREE;
+
+    fn tower_shape() -> Vec<usize> {
+        let mut shape = <P::Fp as Field>::tower_shape();
+        shape.push(3);
+        shape
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self::BasePrimeField>> {
+        let mut nonresidues = <P::Fp as Field>::tower_nonresidues();
+        nonresidues.push(vec![P::NONRESIDUE]);
+        nonresidues
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self::BasePrimeField> {
+        let mut elements = Vec::with_capacity(Self::EXTENSION_DEGREE);
+        elements.extend(self.c0.to_base_prime_field_elements());
+        elements.extend(self.c1.to_base_prime_field_elements());
+        elements.extend(self.c2.to_base_prime_field_elements());
+        elements
+    }
+
+    fn as_base_field(&self) -> Option<Self::BasePrimeField> {
+        if self.c1.is_zero() && self.c2.is_zero() {
+            Some(self.c0)
+        } else {
+            None
+        }
+    }
+
+    fn from_base_field(elem: Self::BasePrimeField) -> Self {
+        Self::new(elem, P::Fp::zero(), P::Fp::zero())
+    }
+
+    /// Norm of Fp3 over Fp: Norm(a) = a.c0^3 + alpha * a.c1^3 + alpha^2 * a.c2^3
+    /// - 3 * alpha * a.c0 * a.c1 * a.c2
+    fn norm(&self) -> Self::BasePrimeField {
+        let c0_cube = self.c0.square() * &self.c0;
+        let c1_cube = P::mul_fp_by_nonresidue(&(self.c1.square() * &self.c1));
+        let c2_cube =
+            P::mul_fp_by_nonresidue(&P::mul_fp_by_nonresidue(&(self.c2.square() * &self.c2)));
+        let c0c1c2 = P::mul_fp_by_nonresidue(&(self.c0 * &self.c1 * &self.c2));
+        c0_cube + &c1_cube + &c2_cube - &(c0c1c2.double() + &c0c1c2)
+    }
+
+    /// Trace of Fp3 over Fp: Tr(a) = 3 * a.c0
+    fn trace(&self) -> Self::BasePrimeField {
+        self.c0.double() + &self.c0
+    }
+
     fn zero() -> Self {
         Fp3 {
             c0:          P::Fp::zero(),
@@ -242,8 +298,8 @@ impl<P: Fp3Parameters> Field for Fp3<P> {
     }
 
     fn frobenius_map(&mut self, power: usize) {
-        self.c1.mul_assign(&P::FROBENIUS_COEFF_FP3_C1[power % 3]);
-        self.c2.mul_assign(&P::FROBENIUS_COEFF_FP3_C2[power % 3]);
+        self.c1.mul_assign(&P::frobenius_coeff_c1(power));
+        self.c2.mul_assign(&P::frobenius_coeff_c2(power));
     }
 }
 
@@ -353,18 +409,55 @@ impl<P: Fp3Parameters> ToBits for Fp3<P> {
         bits.extend_from_slice(self.c1.write_bits().as_slice());
         bits.extend_from_slice(self.c2.write_bits().as_slice());
         bits
+    }
 
+    /// Writes the coefficients in increasing degree, each least significant
+    /// bit first.
+    fn write_bits_le(&self) -> Vec<bool> {
+        let mut bits = self.c0.write_bits_le();
+        bits.extend_from_slice(self.c1.write_bits_le().as_slice());
+        bits.extend_from_slice(self.c2.write_bits_le().as_slice());
+        bits
     }
 }
 
 impl<P: Fp3Parameters> FromBits for Fp3<P> {
+    const NUM_BITS: usize = 3 * <P::Fp as FromBits>::NUM_BITS;
+
     fn read_bits(bits: Vec<bool>) -> Result<Self, Error> {
-        let size = <P::Fp as PrimeField>::Params::MODULUS_BITS as usize;
-        let c0 = P::Fp::read_bits(bits[..size].to_vec())?;
-        let c1 = P::Fp::read_bits(bits[size..(2*size)].to_vec())?;
-        let c2 = P::Fp::read_bits(bits[(2*size)..].to_vec())?;
+        let size = <P::Fp as FromBits>::NUM_BITS;
+        let mut coeffs = split_coefficient_bits(&bits, size, 3)?.into_iter();
+        let c0 = P::Fp::read_bits(coeffs.next().unwrap())?;
+        let c1 = P::Fp::read_bits(coeffs.next().unwrap())?;
+        let c2 = P::Fp::read_bits(coeffs.next().unwrap())?;
         Ok(Fp3::new(c0, c1, c2))
     }
+
+    fn read_bits_le(bits: Vec<bool>) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <P::Fp as FromBits>::NUM_BITS;
+        let c0 = P::Fp::read_bits_le(bits[..size].to_vec())?;
+        let c1 = P::Fp::read_bits_le(bits[size..2 * size].to_vec())?;
+        let c2 = P::Fp::read_bits_le(bits[2 * size..3 * size].to_vec())?;
+        Ok(Fp3::new(c0, c1, c2))
+    }
+
+    fn read_bits_with_mode(bits: Vec<bool>, mode: DecodingMode) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <P::Fp as FromBits>::NUM_BITS;
+        let c0 = P::Fp::read_bits_with_mode(bits[..size].to_vec(), mode)?;
+        let c1 = P::Fp::read_bits_with_mode(bits[size..2 * size].to_vec(), mode)?;
+        let c2 = P::Fp::read_bits_with_mode(bits[2 * size..3 * size].to_vec(), mode)?;
+        Ok(Fp3::new(c0, c1, c2))
+    }
+
+    fn read_bits_unchecked(bits: Vec<bool>) -> Self {
+        let size = <P::Fp as FromBits>::NUM_BITS;
+        let c0 = P::Fp::read_bits_unchecked(bits[..size].to_vec());
+        let c1 = P::Fp::read_bits_unchecked(bits[size..2 * size].to_vec());
+        let c2 = P::Fp::read_bits_unchecked(bits[2 * size..].to_vec());
+        Fp3::new(c0, c1, c2)
+    }
 }
 
 impl<P: Fp3Parameters> Neg for Fp3<P> {
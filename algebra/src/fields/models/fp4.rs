@@ -1,5 +1,6 @@
 use rand::{Rng, distributions::{Standard, Distribution}};
-use crate::{UniformRand, ToBits, FromBits, PrimeField, Error, BitSerializationError};
+use crate::{bits::{check_bits_len, split_coefficient_bits}, fields::DecodingMode};
+use crate::{UniformRand, ToBits, FromBits, Error, BitSerializationError};
 
 use std::{
     cmp::Ordering,
@@ -8,7 +9,7 @@ use std::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use crate::{bytes::{FromBytes, ToBytes}, fields::{Field, Fp2, Fp2Parameters, FpParameters},
+use crate::{bytes::{FromBytes, ToBytes}, fields::{Field, Fp2, Fp2Parameters},
             biginteger::BigInteger, ToCompressedBits, FromCompressedBits};
 use crate::fields::SquareRootField;
 
@@ -29,6 +30,13 @@ pub trait Fp4Parameters: 'static + Send + Sync {
     /// Coefficients for the Frobenius map.
     const FROBENIUS_COEFF_FP4_C1: [<Self::Fp2Params as Fp2Parameters>::Fp; 4];
 
+    /// Returns the coefficient of `c1` in the `power`-th Frobenius map. As
+    /// the Frobenius map is of order 4 on `Fp4`, any `power` is valid.
+    #[inline(always)]
+    fn frobenius_coeff_c1(power: usize) -> <Self::Fp2Params as Fp2Parameters>::Fp {
+        Self::FROBENIUS_COEFF_FP4_C1[power % 4]
+    }
+
     #[inline(always)]
     fn mul_fp2_by_nonresidue(fe: &Fp2<Self::Fp2Params>) -> Fp2<Self::Fp2Params> {
         Self::NONRESIDUE * fe
@@ -128,6 +136,55 @@ impl<P: Fp4Parameters> Fp4<P> {
 }
 
 impl<P: Fp4Parameters> Field for Fp4<P> {
+    type BasePrimeField = <P::Fp2Params as Fp2Parameters>::Fp;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 2;
+    const EXTENSION_DEGREE: usize = 2 * <Fp2<P::Fp2Params> as Field>::EXTENSION_DEGREE;
+
+    fn tower_shape() -> Vec<usize> {
+        let mut shape = <Fp2<P::Fp2Params> as Field>::tower_shape();
+        shape.push(2);
+        shape
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self::BasePrimeField>> {
+        let mut nonresidues = <Fp2<P::Fp2Params> as Field>::tower_nonresidues();
+        // The arithmetic uses U = (0, 1), see `mul_by_nonresidue`, not P::NONRESIDUE.
+        let u = Fp2::<P::Fp2Params>::new(Self::BasePrimeField::zero(), Self::BasePrimeField::one());
+        nonresidues.push(u.to_base_prime_field_elements());
+        nonresidues
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self::BasePrimeField> {
+        let mut elements = Vec::with_capacity(Self::EXTENSION_DEGREE);
+        elements.extend(self.c0.to_base_prime_field_elements());
+        elements.extend(self.c1.to_base_prime_field_elements());
+        elements
+    }
+
+    fn as_base_field(&self) -> Option<Self::BasePrimeField> {
+        if self.c1.is_zero() {
+            self.c0.as_base_field()
+        } else {
+            None
+        }
+    }
+
+    fn from_base_field(elem: Self::BasePrimeField) -> Self {
+        Self::new(Fp2::from_base_field(elem), Fp2::zero())
+    }
+
+    /// Norm over Fp, as the norm of Norm(a) = a.c0^2 - nonresidue * a.c1^2
+    /// over Fp2.
+    fn norm(&self) -> Self::BasePrimeField {
+        (self.c0.square() - &Self::mul_by_nonresidue(&self.c1.square())).norm()
+    }
+
+    /// Trace over Fp, as the trace of Tr(a) = 2 * a.c0 over Fp2.
+    fn trace(&self) -> Self::BasePrimeField {
+        self.c0.double().trace()
+    }
+
     fn zero() -> Self {
         Fp4 {
             c0:          Fp2::zero(),
@@ -228,8 +285,9 @@ impl<P: Fp4Parameters> Field for Fp4<P> {
     fn frobenius_map(&mut self, power: usize) {
         self.c0.frobenius_map(power);
         self.c1.frobenius_map(power);
-        self.c1.c0.mul_assign(&P::FROBENIUS_COEFF_FP4_C1[power % 4]);
-        self.c1.c1.mul_assign(&P::FROBENIUS_COEFF_FP4_C1[power % 4]);
+        let coeff = P::frobenius_coeff_c1(power);
+        self.c1.c0.mul_assign(&coeff);
+        self.c1.c1.mul_assign(&coeff);
     }
 }
 
@@ -305,17 +363,50 @@ impl<P: Fp4Parameters> ToBits for Fp4<P> {
         let mut bits = self.c0.write_bits();
         bits.extend_from_slice(self.c1.write_bits().as_slice());
         bits
+    }
 
+    /// Writes the coefficients in increasing degree, each least significant
+    /// bit first.
+    fn write_bits_le(&self) -> Vec<bool> {
+        let mut bits = self.c0.write_bits_le();
+        bits.extend_from_slice(self.c1.write_bits_le().as_slice());
+        bits
     }
 }
 
 impl<P: Fp4Parameters> FromBits for Fp4<P> {
+    const NUM_BITS: usize = 2 * <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+
     fn read_bits(bits: Vec<bool>) -> Result<Self, Error> {
-        let size = 2 * <<P::Fp2Params as Fp2Parameters>::Fp as PrimeField>::Params::MODULUS_BITS as usize;
-        let c0 = Fp2::read_bits(bits[..size].to_vec())?;
-        let c1 = Fp2::read_bits(bits[size..].to_vec())?;
+        let size = <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+        let mut coeffs = split_coefficient_bits(&bits, size, 2)?.into_iter();
+        let c0 = Fp2::read_bits(coeffs.next().unwrap())?;
+        let c1 = Fp2::read_bits(coeffs.next().unwrap())?;
         Ok(Fp4::new(c0, c1))
     }
+
+    fn read_bits_le(bits: Vec<bool>) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+        let c0 = Fp2::read_bits_le(bits[..size].to_vec())?;
+        let c1 = Fp2::read_bits_le(bits[size..2 * size].to_vec())?;
+        Ok(Fp4::new(c0, c1))
+    }
+
+    fn read_bits_with_mode(bits: Vec<bool>, mode: DecodingMode) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+        let c0 = Fp2::read_bits_with_mode(bits[..size].to_vec(), mode)?;
+        let c1 = Fp2::read_bits_with_mode(bits[size..2 * size].to_vec(), mode)?;
+        Ok(Fp4::new(c0, c1))
+    }
+
+    fn read_bits_unchecked(bits: Vec<bool>) -> Self {
+        let size = <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+        let c0 = Fp2::read_bits_unchecked(bits[..size].to_vec());
+        let c1 = Fp2::read_bits_unchecked(bits[size..].to_vec());
+        Fp4::new(c0, c1)
+    }
 }
 
 
@@ -1,5 +1,6 @@
 use rand::{Rng, distributions::{Standard, Distribution}};
-use crate::{UniformRand, ToCompressedBits, FromCompressedBits, ToBits, FromBits, PrimeField, Error, BitSerializationError};
+use crate::{bits::{check_bits_len, split_coefficient_bits}, fields::DecodingMode};
+use crate::{UniformRand, ToCompressedBits, FromCompressedBits, ToBits, FromBits, Error, BitSerializationError};
 use std::{
     cmp::Ordering,
     io::{Read, Result as IoResult, Write},
@@ -10,7 +11,10 @@ use std::{
 use crate::{
     biginteger::BigInteger,
     bytes::{FromBytes, ToBytes},
-    fields::{Field, SquareRootField, Fp3, Fp3Parameters, FpParameters},
+    fields::{
+        check_torus_compressed, read_torus_compressed, write_torus_compressed, Field,
+        SquareRootField, Fp3, Fp3Parameters,
+    },
 };
 
 
@@ -32,6 +36,13 @@ pub trait Fp6Parameters: 'static + Send + Sync {
     /// Coefficients for the Frobenius automorphism.
     const FROBENIUS_COEFF_FP6_C1: [<Self::Fp3Params as Fp3Parameters>::Fp; 6];
 
+    /// Returns the coefficient of `c1` in the `power`-th Frobenius map. As
+    /// the Frobenius map is of order 6 on `Fp6`, any `power` is valid.
+    #[inline(always)]
+    fn frobenius_coeff_c1(power: usize) -> <Self::Fp3Params as Fp3Parameters>::Fp {
+        Self::FROBENIUS_COEFF_FP6_C1[power % 6]
+    }
+
     #[inline(always)]
     fn mul_fp3_by_nonresidue(fe: &Fp3<Self::Fp3Params>) -> Fp3<Self::Fp3Params> {
         Self::NONRESIDUE * fe
@@ -80,6 +91,56 @@ impl<P: Fp6Parameters> Fp6<P> {
         Self::new(self.c0, -self.c1)
     }
 
+    /// Squares an element of the cyclotomic subgroup, i.e. of norm one over
+    /// Fp3, with the formulas of Granger and Scott
+    /// (https://eprint.iacr.org/2009/565.pdf, Section 3.2), as in libff. The
+    /// element is seen as a + b·Y + c·Y^2 over Fp2' = Fp[Y^3]/(Y^6 - alpha),
+    /// with a = c0.c0 + c1.c1·Y^3, b = c1.c0 + c0.c2·Y^3, c = c0.c1 + c1.c2·Y^3,
+    /// whose squares cost three squarings in Fp2'.
+    pub fn cyclotomic_square(&self) -> Self {
+        let alpha = <P::Fp3Params as Fp3Parameters>::NONRESIDUE;
+
+        // (x0 + x1·Y^3)^2 = x0^2 + alpha·x1^2 + 2·x0·x1·Y^3
+        let square_fp2 = |x0: &<P::Fp3Params as Fp3Parameters>::Fp,
+                          x1: &<P::Fp3Params as Fp3Parameters>::Fp| {
+            let x0_x1 = *x0 * x1;
+            (x0.square() + &(alpha * &x1.square()), x0_x1.double())
+        };
+
+        let (a0, a1) = (self.c0.c0, self.c1.c1);
+        let (b0, b1) = (self.c1.c0, self.c0.c2);
+        let (c0, c1) = (self.c0.c1, self.c1.c2);
+        let (a_sq0, a_sq1) = square_fp2(&a0, &a1);
+        let (b_sq0, b_sq1) = square_fp2(&b0, &b1);
+        let (c_sq0, c_sq1) = square_fp2(&c0, &c1);
+
+        // A = 3·a^2 - 2·conj(a)
+        let mut r_a0 = a_sq0 - &a0;
+        r_a0 = r_a0.double() + &a_sq0;
+        let mut r_a1 = a_sq1 + &a1;
+        r_a1 = r_a1.double() + &a_sq1;
+
+        // B = 3·Y^3·c^2 + 2·conj(b)
+        let tmp = alpha * &c_sq1;
+        let mut r_b0 = tmp + &b0;
+        r_b0 = r_b0.double() + &tmp;
+        let mut r_b1 = c_sq0 - &b1;
+        r_b1 = r_b1.double() + &c_sq0;
+
+        // C = 3·b^2 - 2·conj(c)
+        let mut r_c0 = b_sq0 - &c0;
+        r_c0 = r_c0.double() + &b_sq0;
+        let mut r_c1 = b_sq1 + &c1;
+        r_c1 = r_c1.double() + &b_sq1;
+
+        Self::new(
+            Fp3::new(r_a0, r_c0, r_b1),
+            Fp3::new(r_b0, r_a1, r_c1),
+        )
+    }
+
+    /// Raises `self`, which must be in the cyclotomic subgroup, to the power
+    /// `exponent`, with the non-adjacent form of the exponent.
     pub fn cyclotomic_exp<B: BigInteger>(&self, exponent: &B) -> Self {
         let mut res = Self::one();
         let self_inverse = self.unitary_inverse();
@@ -89,7 +150,7 @@ impl<P: Fp6Parameters> Fp6<P> {
 
         for &value in naf.iter().rev() {
             if found_nonzero {
-                res = res.square();
+                res = res.cyclotomic_square();
             }
 
             if value != 0 {
@@ -106,6 +167,51 @@ impl<P: Fp6Parameters> Fp6<P> {
         res
     }
 
+    /// Returns the norm c0^2 - Y^2·c1^2 of `self` over Fp3, which is one for
+    /// the elements of the cyclotomic subgroup, e.g. the outputs of pairings.
+    pub fn norm_over_fp3(&self) -> Fp3<P::Fp3Params> {
+        self.c0.square() - &Self::mul_by_nonresidue(&self.c1.square())
+    }
+
+    /// Compresses `self`, which must be of norm one over Fp3, e.g. the output
+    /// of a pairing, to half its size with the algebraic torus T2 (Rubin and
+    /// Silverberg, https://eprint.iacr.org/2003/039.pdf): c0 + c1·Y is
+    /// represented by g = (1 + c0) / c1, with g = 0 for -1, and the identity is
+    /// flagged.
+    pub fn to_compressed(&self) -> Vec<u8> {
+        debug_assert!(self.norm_over_fp3().is_one());
+        let g = if self.c1.is_zero() {
+            if self.c0.is_one() {
+                None
+            } else {
+                Some(Fp3::zero())
+            }
+        } else {
+            Some((Fp3::one() + &self.c0) * &self.c1.inverse().unwrap())
+        };
+        write_torus_compressed(g)
+    }
+
+    /// Decodes the encoding of `to_compressed` as (g + Y) / (g - Y). Fails if
+    /// the encoding is not canonical. The decoded element is of norm one, but
+    /// it is up to the caller to check that it is in the subgroup of pairing
+    /// outputs, if needed.
+    pub fn from_compressed(bytes: &[u8]) -> Result<Self, Error> {
+        let res = match read_torus_compressed::<Fp3<P::Fp3Params>>(bytes)? {
+            None => Self::one(),
+            Some(g) => {
+                // (g + Y) / (g - Y) = (g^2 + Y^2 + 2·g·Y) / (g^2 - Y^2), where
+                // g^2 - Y^2 is not zero as Y^2 is not a square in Fp3
+                let g_sq = g.square();
+                let y_sq = Self::mul_by_nonresidue(&Fp3::one());
+                let den_inv = (g_sq - &y_sq).inverse().unwrap();
+                Self::new((g_sq + &y_sq) * &den_inv, g.double() * &den_inv)
+            },
+        };
+        check_torus_compressed(res.to_compressed(), bytes)?;
+        Ok(res)
+    }
+
     //Mul by an element of the form [c0: (0, 0, a), c1: (b, c, d)]
     pub fn mul_by_2345(self, other: &Self) -> Self
     /* Devegili OhEig Scott Dahab --- Multiplication and Squaring on Pairing-Friendly Fields.pdf; Section 3 (Karatsuba) */
@@ -123,6 +229,59 @@ impl<P: Fp6Parameters> Fp6<P> {
 }
 
 impl<P: Fp6Parameters> Field for Fp6<P> {
+    type BasePrimeField = <P::Fp3Params as Fp3Parameters>::Fp;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 2;
+    const EXTENSION_DEGREE: usize = 2 * <Fp3<P::Fp3Params> as Field>::EXTENSION_DEGREE;
+
+    fn tower_shape() -> Vec<usize> {
+        let mut shape = <Fp3<P::Fp3Params> as Field>::tower_shape();
+        shape.push(2);
+        shape
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self::BasePrimeField>> {
+        let mut nonresidues = <Fp3<P::Fp3Params> as Field>::tower_nonresidues();
+        // The arithmetic uses U = (0, 1, 0), see `mul_by_nonresidue`, not P::NONRESIDUE.
+        let u = Fp3::<P::Fp3Params>::new(
+            Self::BasePrimeField::zero(),
+            Self::BasePrimeField::one(),
+            Self::BasePrimeField::zero(),
+        );
+        nonresidues.push(u.to_base_prime_field_elements());
+        nonresidues
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self::BasePrimeField> {
+        let mut elements = Vec::with_capacity(Self::EXTENSION_DEGREE);
+        elements.extend(self.c0.to_base_prime_field_elements());
+        elements.extend(self.c1.to_base_prime_field_elements());
+        elements
+    }
+
+    fn as_base_field(&self) -> Option<Self::BasePrimeField> {
+        if self.c1.is_zero() {
+            self.c0.as_base_field()
+        } else {
+            None
+        }
+    }
+
+    fn from_base_field(elem: Self::BasePrimeField) -> Self {
+        Self::new(Fp3::from_base_field(elem), Fp3::zero())
+    }
+
+    /// Norm over Fp, as the norm of Norm(a) = a.c0^2 - nonresidue * a.c1^2
+    /// over Fp3.
+    fn norm(&self) -> Self::BasePrimeField {
+        (self.c0.square() - &Self::mul_by_nonresidue(&self.c1.square())).norm()
+    }
+
+    /// Trace over Fp, as the trace of Tr(a) = 2 * a.c0 over Fp3.
+    fn trace(&self) -> Self::BasePrimeField {
+        self.c0.double().trace()
+    }
+
     fn zero() -> Self {
         Fp6 {
             c0:          Fp3::zero(),
@@ -228,7 +387,7 @@ impl<P: Fp6Parameters> Field for Fp6<P> {
         self.c0.frobenius_map(power);
         self.c1.frobenius_map(power);
         self.c1
-            .mul_assign_by_fp(&P::FROBENIUS_COEFF_FP6_C1[power % 6]);
+            .mul_assign_by_fp(&P::frobenius_coeff_c1(power));
     }
 }
 
@@ -304,17 +463,50 @@ impl<P: Fp6Parameters> ToBits for Fp6<P> {
         let mut bits = self.c0.write_bits();
         bits.extend_from_slice(self.c1.write_bits().as_slice());
         bits
+    }
 
+    /// Writes the coefficients in increasing degree, each least significant
+    /// bit first.
+    fn write_bits_le(&self) -> Vec<bool> {
+        let mut bits = self.c0.write_bits_le();
+        bits.extend_from_slice(self.c1.write_bits_le().as_slice());
+        bits
     }
 }
 
 impl<P: Fp6Parameters> FromBits for Fp6<P> {
+    const NUM_BITS: usize = 2 * <Fp3<P::Fp3Params> as FromBits>::NUM_BITS;
+
     fn read_bits(bits: Vec<bool>) -> Result<Self, Error> {
-        let size = 3 * <<P::Fp3Params as Fp3Parameters>::Fp as PrimeField>::Params::MODULUS_BITS as usize;
-        let c0 = Fp3::read_bits(bits[..size].to_vec())?;
-        let c1 = Fp3::read_bits(bits[size..].to_vec())?;
+        let size = <Fp3<P::Fp3Params> as FromBits>::NUM_BITS;
+        let mut coeffs = split_coefficient_bits(&bits, size, 2)?.into_iter();
+        let c0 = Fp3::read_bits(coeffs.next().unwrap())?;
+        let c1 = Fp3::read_bits(coeffs.next().unwrap())?;
+        Ok(Fp6::new(c0, c1))
+    }
+
+    fn read_bits_le(bits: Vec<bool>) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <Fp3<P::Fp3Params> as FromBits>::NUM_BITS;
+        let c0 = Fp3::read_bits_le(bits[..size].to_vec())?;
+        let c1 = Fp3::read_bits_le(bits[size..2 * size].to_vec())?;
         Ok(Fp6::new(c0, c1))
     }
+
+    fn read_bits_with_mode(bits: Vec<bool>, mode: DecodingMode) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <Fp3<P::Fp3Params> as FromBits>::NUM_BITS;
+        let c0 = Fp3::read_bits_with_mode(bits[..size].to_vec(), mode)?;
+        let c1 = Fp3::read_bits_with_mode(bits[size..2 * size].to_vec(), mode)?;
+        Ok(Fp6::new(c0, c1))
+    }
+
+    fn read_bits_unchecked(bits: Vec<bool>) -> Self {
+        let size = <Fp3<P::Fp3Params> as FromBits>::NUM_BITS;
+        let c0 = Fp3::read_bits_unchecked(bits[..size].to_vec());
+        let c1 = Fp3::read_bits_unchecked(bits[size..].to_vec());
+        Fp6::new(c0, c1)
+    }
 }
 
 impl<P: Fp6Parameters> Neg for Fp6<P> {
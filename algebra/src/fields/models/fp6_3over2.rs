@@ -1,5 +1,6 @@
 use rand::{Rng, distributions::{Standard, Distribution}};
-use crate::{UniformRand, ToBits, FromBits, PrimeField, Error};
+use crate::{bits::{check_bits_len, split_coefficient_bits}, fields::DecodingMode};
+use crate::{UniformRand, ToBits, FromBits, Error};
 use std::{
     cmp::Ordering,
     io::{Read, Result as IoResult, Write},
@@ -9,7 +10,7 @@ use std::{
 
 use crate::{
     bytes::{FromBytes, ToBytes},
-    fields::{Field, Fp2, Fp2Parameters, FpParameters},
+    fields::{Field, Fp2, Fp2Parameters},
 };
 
 pub trait Fp6Parameters: 'static + Send + Sync + Copy {
@@ -21,6 +22,19 @@ pub trait Fp6Parameters: 'static + Send + Sync + Copy {
     const FROBENIUS_COEFF_FP6_C1: [Fp2<Self::Fp2Params>; 6];
     const FROBENIUS_COEFF_FP6_C2: [Fp2<Self::Fp2Params>; 6];
 
+    /// Returns the coefficient of `c1` in the `power`-th Frobenius map. As
+    /// the Frobenius map is of order 6 on `Fp6`, any `power` is valid.
+    #[inline(always)]
+    fn frobenius_coeff_c1(power: usize) -> Fp2<Self::Fp2Params> {
+        Self::FROBENIUS_COEFF_FP6_C1[power % 6]
+    }
+
+    /// Returns the coefficient of `c2` in the `power`-th Frobenius map.
+    #[inline(always)]
+    fn frobenius_coeff_c2(power: usize) -> Fp2<Self::Fp2Params> {
+        Self::FROBENIUS_COEFF_FP6_C2[power % 6]
+    }
+
     #[inline(always)]
     fn mul_fp2_by_nonresidue(fe: &Fp2<Self::Fp2Params>) -> Fp2<Self::Fp2Params> {
         Self::NONRESIDUE * fe
@@ -142,6 +156,59 @@ impl<P: Fp6Parameters> Fp6<P> {
 }
 
 impl<P: Fp6Parameters> Field for Fp6<P> {
+    type BasePrimeField = <P::Fp2Params as Fp2Parameters>::Fp;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 3;
+    const EXTENSION_DEGREE: usize = 3 * <Fp2<P::Fp2Params> as Field>::EXTENSION_DEGREE;
+
+    fn tower_shape() -> Vec<usize> {
+        let mut shape = <Fp2<P::Fp2Params> as Field>::tower_shape();
+        shape.push(3);
+        shape
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self::BasePrimeField>> {
+        let mut nonresidues = <Fp2<P::Fp2Params> as Field>::tower_nonresidues();
+        nonresidues.push(P::NONRESIDUE.to_base_prime_field_elements());
+        nonresidues
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self::BasePrimeField> {
+        let mut elements = Vec::with_capacity(Self::EXTENSION_DEGREE);
+        elements.extend(self.c0.to_base_prime_field_elements());
+        elements.extend(self.c1.to_base_prime_field_elements());
+        elements.extend(self.c2.to_base_prime_field_elements());
+        elements
+    }
+
+    fn as_base_field(&self) -> Option<Self::BasePrimeField> {
+        if self.c1.is_zero() && self.c2.is_zero() {
+            self.c0.as_base_field()
+        } else {
+            None
+        }
+    }
+
+    fn from_base_field(elem: Self::BasePrimeField) -> Self {
+        Self::new(Fp2::from_base_field(elem), Fp2::zero(), Fp2::zero())
+    }
+
+    /// Norm over Fp, as the norm of Norm(a) = a.c0^3 + nonresidue * a.c1^3
+    /// + nonresidue^2 * a.c2^3 - 3 * nonresidue * a.c0 * a.c1 * a.c2 over Fp2.
+    fn norm(&self) -> Self::BasePrimeField {
+        let c0_cube = self.c0.square() * &self.c0;
+        let c1_cube = P::mul_fp2_by_nonresidue(&(self.c1.square() * &self.c1));
+        let c2_cube =
+            P::mul_fp2_by_nonresidue(&P::mul_fp2_by_nonresidue(&(self.c2.square() * &self.c2)));
+        let c0c1c2 = P::mul_fp2_by_nonresidue(&(self.c0 * &self.c1 * &self.c2));
+        (c0_cube + &c1_cube + &c2_cube - &(c0c1c2.double() + &c0c1c2)).norm()
+    }
+
+    /// Trace over Fp, as the trace of Tr(a) = 3 * a.c0 over Fp2.
+    fn trace(&self) -> Self::BasePrimeField {
+        (self.c0.double() + &self.c0).trace()
+    }
+
     fn zero() -> Self {
         Self::new(Fp2::zero(), Fp2::zero(), Fp2::zero())
     }
@@ -263,8 +330,8 @@ impl<P: Fp6Parameters> Field for Fp6<P> {
         self.c1.frobenius_map(power);
         self.c2.frobenius_map(power);
 
-        self.c1.mul_assign(&P::FROBENIUS_COEFF_FP6_C1[power % 6]);
-        self.c2.mul_assign(&P::FROBENIUS_COEFF_FP6_C2[power % 6]);
+        self.c1.mul_assign(&P::frobenius_coeff_c1(power));
+        self.c2.mul_assign(&P::frobenius_coeff_c2(power));
     }
 }
 
@@ -475,16 +542,53 @@ impl<P: Fp6Parameters> ToBits for Fp6<P> {
         bits.extend_from_slice(self.c1.write_bits().as_slice());
         bits.extend_from_slice(self.c2.write_bits().as_slice());
         bits
+    }
 
+    /// Writes the coefficients in increasing degree, each least significant
+    /// bit first.
+    fn write_bits_le(&self) -> Vec<bool> {
+        let mut bits = self.c0.write_bits_le();
+        bits.extend_from_slice(self.c1.write_bits_le().as_slice());
+        bits.extend_from_slice(self.c2.write_bits_le().as_slice());
+        bits
     }
 }
 
 impl<P: Fp6Parameters> FromBits for Fp6<P> {
+    const NUM_BITS: usize = 3 * <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+
     fn read_bits(bits: Vec<bool>) -> Result<Self, Error> {
-        let size = 2 * <<P::Fp2Params as Fp2Parameters>::Fp as PrimeField>::Params::MODULUS_BITS as usize;
-        let c0 = Fp2::read_bits(bits[..size].to_vec())?;
-        let c1 = Fp2::read_bits(bits[size..(2*size)].to_vec())?;
-        let c2 = Fp2::read_bits(bits[(2*size)..].to_vec())?;
+        let size = <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+        let mut coeffs = split_coefficient_bits(&bits, size, 3)?.into_iter();
+        let c0 = Fp2::read_bits(coeffs.next().unwrap())?;
+        let c1 = Fp2::read_bits(coeffs.next().unwrap())?;
+        let c2 = Fp2::read_bits(coeffs.next().unwrap())?;
         Ok(Fp6::new(c0, c1, c2))
     }
+
+    fn read_bits_le(bits: Vec<bool>) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+        let c0 = Fp2::read_bits_le(bits[..size].to_vec())?;
+        let c1 = Fp2::read_bits_le(bits[size..2 * size].to_vec())?;
+        let c2 = Fp2::read_bits_le(bits[2 * size..3 * size].to_vec())?;
+        Ok(Fp6::new(c0, c1, c2))
+    }
+
+    fn read_bits_with_mode(bits: Vec<bool>, mode: DecodingMode) -> Result<Self, Error> {
+        check_bits_len(&bits, Self::NUM_BITS)?;
+        let size = <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+        let c0 = Fp2::read_bits_with_mode(bits[..size].to_vec(), mode)?;
+        let c1 = Fp2::read_bits_with_mode(bits[size..2 * size].to_vec(), mode)?;
+        let c2 = Fp2::read_bits_with_mode(bits[2 * size..3 * size].to_vec(), mode)?;
+        Ok(Fp6::new(c0, c1, c2))
+    }
+
+    fn read_bits_unchecked(bits: Vec<bool>) -> Self {
+        let size = <Fp2<P::Fp2Params> as FromBits>::NUM_BITS;
+        let c0 = Fp2::read_bits_unchecked(bits[..size].to_vec());
+        let c1 = Fp2::read_bits_unchecked(bits[size..2 * size].to_vec());
+        let c2 = Fp2::read_bits_unchecked(bits[2 * size..].to_vec());
+        Fp6::new(c0, c1, c2)
+    }
 }
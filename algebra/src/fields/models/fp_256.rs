@@ -1,6 +1,7 @@
 use std::{
     cmp::{Ord, Ordering, PartialOrd},
     fmt::{Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
     io::{Read, Result as IoResult, Write, Error as IoError, ErrorKind},
     marker::PhantomData,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
@@ -10,7 +11,10 @@ use std::{
 use crate::{
     biginteger::{arithmetic as fa, BigInteger as _BigInteger, BigInteger256 as BigInteger},
     bytes::{FromBytes, ToBytes},
-    fields::{Field, FpParameters, LegendreSymbol, PrimeField, SquareRootField},
+    fields::{
+        addition_chain::AdditionChain, safegcd::safegcd_inverse, Field, FpParameters,
+        InversionAlgorithm, LegendreSymbol, PrimeField, SquareRootField,
+    },
 };
 
 pub trait Fp256Parameters: FpParameters<BigInt = BigInteger> {}
@@ -18,7 +22,6 @@ pub trait Fp256Parameters: FpParameters<BigInt = BigInteger> {}
 #[derive(Derivative)]
 #[derivative(
     Default(bound = ""),
-    Hash(bound = ""),
     Clone(bound = ""),
     Copy(bound = ""),
     Debug(bound = ""),
@@ -34,6 +37,11 @@ pub struct Fp256<P>(
 );
 
 impl<P: Fp256Parameters> Fp256<P> {
+    /// Whether the most significant bit of the modulus is unset, as it is for
+    /// all moduli but the ones of secp256k1. Then sums and Montgomery products
+    /// of reduced elements fit in 256 bits, and no carry needs to be handled.
+    const MODULUS_HAS_SPARE_BIT: bool = (P::MODULUS.0)[3] >> 63 == 0;
+
     #[inline]
     pub fn new(element: BigInteger) -> Self {
         Fp256::<P>(element, PhantomData)
@@ -51,6 +59,19 @@ impl<P: Fp256Parameters> Fp256<P> {
         }
     }
 
+    /// Reduces `self`, where `carry` is the bit overflowing the 256 bits of the
+    /// representation. A carry can only occur for moduli without a spare bit
+    /// in the most significant limb, like the ones of secp256k1: for the other
+    /// moduli, `carry` is ignored and this is the same as `reduce`.
+    #[inline]
+    fn reduce_with_carry(&mut self, carry: bool) {
+        if !Self::MODULUS_HAS_SPARE_BIT && carry {
+            self.0.sub_noborrow(&P::MODULUS);
+        } else {
+            self.reduce();
+        }
+    }
+
     #[inline]
     fn mont_reduce(
         &mut self,
@@ -102,11 +123,44 @@ impl<P: Fp256Parameters> Fp256<P> {
         (self.0).0[1] = r5;
         (self.0).0[2] = r6;
         (self.0).0[3] = r7;
-        self.reduce();
+        self.reduce_with_carry(carry != 0);
     }
 }
 
 impl<P: Fp256Parameters> Field for Fp256<P> {
+    type BasePrimeField = Self;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 1;
+    const EXTENSION_DEGREE: usize = 1;
+
+    fn tower_shape() -> Vec<usize> {
+        vec![]
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self>> {
+        vec![]
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self> {
+        vec![*self]
+    }
+
+    fn as_base_field(&self) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn from_base_field(elem: Self) -> Self {
+        elem
+    }
+
+    fn norm(&self) -> Self {
+        *self
+    }
+
+    fn trace(&self) -> Self {
+        *self
+    }
+
     #[inline]
     fn zero() -> Self {
         Fp256::<P>(BigInteger::from(0), PhantomData)
@@ -126,10 +180,11 @@ impl<P: Fp256Parameters> Field for Fp256<P> {
 
     #[inline]
     fn double_in_place(&mut self) -> &mut Self {
-        // This cannot exceed the backing capacity.
+        // This can exceed the backing capacity only if the modulus has no spare bit.
+        let carry = (self.0).0[3] >> 63 == 1;
         self.0.mul2();
         // However, it may need to be reduced.
-        self.reduce();
+        self.reduce_with_carry(carry);
         self
     }
 
@@ -201,6 +256,8 @@ impl<P: Fp256Parameters> Field for Fp256<P> {
     fn inverse(&self) -> Option<Self> {
         if self.is_zero() {
             None
+        } else if P::INVERSION_ALGORITHM == InversionAlgorithm::SafeGcd {
+            safegcd_inverse(self)
         } else {
             // Guajardo Kumar Paar Pelzl
             // Efficient Software-Implementation of Finite Fields with Applications to
@@ -221,8 +278,11 @@ impl<P: Fp256Parameters> Field for Fp256<P> {
                     if b.0.is_even() {
                         b.0.div2();
                     } else {
-                        b.0.add_nocarry(&P::MODULUS);
+                        let carry = b.0.add_nocarry(&P::MODULUS);
                         b.0.div2();
+                        if !Self::MODULUS_HAS_SPARE_BIT && carry {
+                            (b.0).0[3] |= 1 << 63;
+                        }
                     }
                 }
 
@@ -232,8 +292,11 @@ impl<P: Fp256Parameters> Field for Fp256<P> {
                     if c.0.is_even() {
                         c.0.div2();
                     } else {
-                        c.0.add_nocarry(&P::MODULUS);
+                        let carry = c.0.add_nocarry(&P::MODULUS);
                         c.0.div2();
+                        if !Self::MODULUS_HAS_SPARE_BIT && carry {
+                            (c.0).0[3] |= 1 << 63;
+                        }
                     }
                 }
 
@@ -340,6 +403,11 @@ impl<P: Fp256Parameters> PrimeField for Fp256<P> {
     fn root_of_unity() -> Self {
         Fp256::<P>(P::ROOT_OF_UNITY, PhantomData)
     }
+
+    #[inline]
+    fn large_subgroup_root_of_unity() -> Option<Self> {
+        P::LARGE_SUBGROUP_ROOT_OF_UNITY.map(|root| Fp256::<P>(root, PhantomData))
+    }
 }
 
 impl<P: Fp256Parameters> SquareRootField for Fp256<P> {
@@ -347,8 +415,8 @@ impl<P: Fp256Parameters> SquareRootField for Fp256<P> {
     fn legendre(&self) -> LegendreSymbol {
         use crate::fields::LegendreSymbol::*;
 
-        // s = self^((MODULUS - 1) // 2)
-        let s = self.pow(P::MODULUS_MINUS_ONE_DIV_TWO);
+        // s = self^((MODULUS - 1) // 2), by Euler's criterion
+        let s = AdditionChain::new(P::MODULUS_MINUS_ONE_DIV_TWO).pow(self);
         if s.is_zero() {
             Zero
         } else if s.is_one() {
@@ -418,6 +486,15 @@ impl<P: Fp256Parameters> Ord for Fp256<P> {
     }
 }
 
+/// Hashes the canonical (i.e. non-Montgomery) representation, the same
+/// integer written by `ToBytes`.
+impl<P: Fp256Parameters> Hash for Fp256<P> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.into_repr().hash(state)
+    }
+}
+
 impl<P: Fp256Parameters> PartialOrd for Fp256<P> {
     #[inline(always)]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -543,11 +620,11 @@ impl<'a, P: Fp256Parameters> Div<&'a Fp256<P>> for Fp256<P> {
 impl<'a, P: Fp256Parameters> AddAssign<&'a Self> for Fp256<P> {
     #[inline]
     fn add_assign(&mut self, other: &Self) {
-        // This cannot exceed the backing capacity.
-        self.0.add_nocarry(&other.0);
+        // This can exceed the backing capacity only if the modulus has no spare bit.
+        let carry = self.0.add_nocarry(&other.0);
         // However, it may need to be reduced
 
-        self.reduce();
+        self.reduce_with_carry(carry);
     }
 }
 
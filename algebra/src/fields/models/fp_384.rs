@@ -1,6 +1,7 @@
 use std::{
     cmp::{Ord, Ordering, PartialOrd},
     fmt::{Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
     io::{Read, Result as IoResult, Write, Error as IoError, ErrorKind},
     marker::PhantomData,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
@@ -10,7 +11,10 @@ use std::{
 use crate::{
     biginteger::{arithmetic as fa, BigInteger as _BigInteger, BigInteger384 as BigInteger},
     bytes::{FromBytes, ToBytes},
-    fields::{Field, FpParameters, LegendreSymbol, PrimeField, SquareRootField},
+    fields::{
+        addition_chain::AdditionChain, safegcd::safegcd_inverse, Field, FpParameters,
+        InversionAlgorithm, LegendreSymbol, PrimeField, SquareRootField,
+    },
 };
 
 pub trait Fp384Parameters: FpParameters<BigInt = BigInteger> {}
@@ -18,7 +22,6 @@ pub trait Fp384Parameters: FpParameters<BigInt = BigInteger> {}
 #[derive(Derivative)]
 #[derivative(
     Default(bound = "P: Fp384Parameters"),
-    Hash(bound = "P: Fp384Parameters"),
     Clone(bound = "P: Fp384Parameters"),
     Copy(bound = "P: Fp384Parameters"),
     Debug(bound = "P: Fp384Parameters"),
@@ -141,6 +144,39 @@ impl<P: Fp384Parameters> Fp384<P> {
 }
 
 impl<P: Fp384Parameters> Field for Fp384<P> {
+    type BasePrimeField = Self;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 1;
+    const EXTENSION_DEGREE: usize = 1;
+
+    fn tower_shape() -> Vec<usize> {
+        vec![]
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self>> {
+        vec![]
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self> {
+        vec![*self]
+    }
+
+    fn as_base_field(&self) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn from_base_field(elem: Self) -> Self {
+        elem
+    }
+
+    fn norm(&self) -> Self {
+        *self
+    }
+
+    fn trace(&self) -> Self {
+        *self
+    }
+
     #[inline]
     fn zero() -> Self {
         Fp384::<P>(BigInteger::from(0), PhantomData)
@@ -255,6 +291,8 @@ impl<P: Fp384Parameters> Field for Fp384<P> {
     fn inverse(&self) -> Option<Self> {
         if self.is_zero() {
             None
+        } else if P::INVERSION_ALGORITHM == InversionAlgorithm::SafeGcd {
+            safegcd_inverse(self)
         } else {
             // Guajardo Kumar Paar Pelzl
             // Efficient Software-Implementation of Finite Fields with Applications to
@@ -401,6 +439,11 @@ impl<P: Fp384Parameters> PrimeField for Fp384<P> {
     fn root_of_unity() -> Self {
         Fp384::<P>(P::ROOT_OF_UNITY, PhantomData)
     }
+
+    #[inline]
+    fn large_subgroup_root_of_unity() -> Option<Self> {
+        P::LARGE_SUBGROUP_ROOT_OF_UNITY.map(|root| Fp384::<P>(root, PhantomData))
+    }
 }
 
 impl<P: Fp384Parameters> SquareRootField for Fp384<P> {
@@ -408,8 +451,8 @@ impl<P: Fp384Parameters> SquareRootField for Fp384<P> {
     fn legendre(&self) -> LegendreSymbol {
         use crate::fields::LegendreSymbol::*;
 
-        // s = self^((MODULUS - 1) // 2)
-        let s = self.pow(P::MODULUS_MINUS_ONE_DIV_TWO);
+        // s = self^((MODULUS - 1) // 2), by Euler's criterion
+        let s = AdditionChain::new(P::MODULUS_MINUS_ONE_DIV_TWO).pow(self);
         if s.is_zero() {
             Zero
         } else if s.is_one() {
@@ -439,6 +482,15 @@ impl<P: Fp384Parameters> Ord for Fp384<P> {
     }
 }
 
+/// Hashes the canonical (i.e. non-Montgomery) representation, the same
+/// integer written by `ToBytes`.
+impl<P: Fp384Parameters> Hash for Fp384<P> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.into_repr().hash(state)
+    }
+}
+
 impl<P: Fp384Parameters> PartialOrd for Fp384<P> {
     #[inline(always)]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -1,20 +1,20 @@
 use std::{
     cmp::{Ord, Ordering, PartialOrd},
     fmt::{Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
     io::{Read, Result as IoResult, Write, Error as IoError, ErrorKind},
     marker::PhantomData,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     str::FromStr,
 };
 
-use crate::{biginteger::{arithmetic as fa, BigInteger as _BigInteger, BigInteger768 as BigInteger}, bytes::{FromBytes, ToBytes}, fields::{Field, FpParameters, LegendreSymbol, PrimeField, SquareRootField}, MulShort};
+use crate::{biginteger::{arithmetic as fa, BigInteger as _BigInteger, BigInteger768 as BigInteger}, bytes::{FromBytes, ToBytes}, fields::{addition_chain::AdditionChain, safegcd::safegcd_inverse, Field, FpParameters, InversionAlgorithm, LegendreSymbol, PrimeField, SquareRootField}, MulShort};
 
 pub trait Fp768Parameters: FpParameters<BigInt = BigInteger> {}
 
 #[derive(Derivative)]
 #[derivative(
     Default(bound = "P: Fp768Parameters"),
-    Hash(bound = "P: Fp768Parameters"),
     Clone(bound = "P: Fp768Parameters"),
     Copy(bound = "P: Fp768Parameters"),
     Debug(bound = "P: Fp768Parameters"),
@@ -282,6 +282,39 @@ impl<P: Fp768Parameters> Fp768<P> {
 }
 
 impl<P: Fp768Parameters> Field for Fp768<P> {
+    type BasePrimeField = Self;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 1;
+    const EXTENSION_DEGREE: usize = 1;
+
+    fn tower_shape() -> Vec<usize> {
+        vec![]
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self>> {
+        vec![]
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self> {
+        vec![*self]
+    }
+
+    fn as_base_field(&self) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn from_base_field(elem: Self) -> Self {
+        elem
+    }
+
+    fn norm(&self) -> Self {
+        *self
+    }
+
+    fn trace(&self) -> Self {
+        *self
+    }
+
     #[inline]
     fn zero() -> Self {
         Fp768::<P>(BigInteger::from(0), PhantomData)
@@ -551,6 +584,8 @@ impl<P: Fp768Parameters> Field for Fp768<P> {
     fn inverse(&self) -> Option<Self> {
         if self.is_zero() {
             None
+        } else if P::INVERSION_ALGORITHM == InversionAlgorithm::SafeGcd {
+            safegcd_inverse(self)
         } else {
             // Guajardo Kumar Paar Pelzl
             // Efficient Software-Implementation of Finite Fields with Applications to
@@ -707,6 +742,11 @@ impl<P: Fp768Parameters> PrimeField for Fp768<P> {
         Fp768::<P>(P::ROOT_OF_UNITY, PhantomData)
     }
 
+    #[inline]
+    fn large_subgroup_root_of_unity() -> Option<Self> {
+        P::LARGE_SUBGROUP_ROOT_OF_UNITY.map(|root| Fp768::<P>(root, PhantomData))
+    }
+
     #[inline]
     fn size_in_bits() -> usize {
         P::MODULUS_BITS as usize
@@ -733,8 +773,8 @@ impl<P: Fp768Parameters> SquareRootField for Fp768<P> {
     fn legendre(&self) -> LegendreSymbol {
         use crate::fields::LegendreSymbol::*;
 
-        // s = self^((MODULUS - 1) // 2)
-        let s = self.pow(P::MODULUS_MINUS_ONE_DIV_TWO);
+        // s = self^((MODULUS - 1) // 2), by Euler's criterion
+        let s = AdditionChain::new(P::MODULUS_MINUS_ONE_DIV_TWO).pow(self);
         if s.is_zero() {
             Zero
         } else if s.is_one() {
@@ -766,6 +806,15 @@ impl<P: Fp768Parameters> Ord for Fp768<P> {
     }
 }
 
+/// Hashes the canonical (i.e. non-Montgomery) representation, the same
+/// integer written by `ToBytes`.
+impl<P: Fp768Parameters> Hash for Fp768<P> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.into_repr().hash(state)
+    }
+}
+
 impl<P: Fp768Parameters> PartialOrd for Fp768<P> {
     #[inline(always)]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
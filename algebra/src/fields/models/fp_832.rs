@@ -1,11 +1,15 @@
 use crate::{
     biginteger::{arithmetic as fa, BigInteger as _BigInteger, BigInteger832 as BigInteger},
     bytes::{FromBytes, ToBytes},
-    fields::{Field, FpParameters, LegendreSymbol, PrimeField, SquareRootField},
+    fields::{
+        addition_chain::AdditionChain, safegcd::safegcd_inverse, Field, FpParameters,
+        InversionAlgorithm, LegendreSymbol, PrimeField, SquareRootField,
+    },
 };
 use std::{
     cmp::{Ord, Ordering, PartialOrd},
     fmt::{Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
     io::{Read, Result as IoResult, Write, Error as IoError, ErrorKind},
     marker::PhantomData,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
@@ -17,7 +21,6 @@ pub trait Fp832Parameters: FpParameters<BigInt = BigInteger> {}
 #[derive(Derivative)]
 #[derivative(
     Default(bound = "P: Fp832Parameters"),
-    Hash(bound = "P: Fp832Parameters"),
     Clone(bound = "P: Fp832Parameters"),
     Copy(bound = "P: Fp832Parameters"),
     Debug(bound = "P: Fp832Parameters"),
@@ -317,6 +320,39 @@ impl<P: Fp832Parameters> Fp832<P> {
 }
 
 impl<P: Fp832Parameters> Field for Fp832<P> {
+    type BasePrimeField = Self;
+
+    const DEGREE_OVER_BASE_FIELD: usize = 1;
+    const EXTENSION_DEGREE: usize = 1;
+
+    fn tower_shape() -> Vec<usize> {
+        vec![]
+    }
+
+    fn tower_nonresidues() -> Vec<Vec<Self>> {
+        vec![]
+    }
+
+    fn to_base_prime_field_elements(&self) -> Vec<Self> {
+        vec![*self]
+    }
+
+    fn as_base_field(&self) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn from_base_field(elem: Self) -> Self {
+        elem
+    }
+
+    fn norm(&self) -> Self {
+        *self
+    }
+
+    fn trace(&self) -> Self {
+        *self
+    }
+
     #[inline]
     fn zero() -> Self {
         Fp832::<P>(BigInteger::from(0), PhantomData)
@@ -521,6 +557,8 @@ impl<P: Fp832Parameters> Field for Fp832<P> {
     fn inverse(&self) -> Option<Self> {
         if self.is_zero() {
             None
+        } else if P::INVERSION_ALGORITHM == InversionAlgorithm::SafeGcd {
+            safegcd_inverse(self)
         } else {
             // Guajardo Kumar Paar Pelzl
             // Efficient Software-Implementation of Finite Fields with Applications to
@@ -679,6 +717,11 @@ impl<P: Fp832Parameters> PrimeField for Fp832<P> {
         Fp832::<P>(P::ROOT_OF_UNITY, PhantomData)
     }
 
+    #[inline]
+    fn large_subgroup_root_of_unity() -> Option<Self> {
+        P::LARGE_SUBGROUP_ROOT_OF_UNITY.map(|root| Fp832::<P>(root, PhantomData))
+    }
+
     #[inline]
     fn size_in_bits() -> usize {
         P::MODULUS_BITS as usize
@@ -705,8 +748,8 @@ impl<P: Fp832Parameters> SquareRootField for Fp832<P> {
     fn legendre(&self) -> LegendreSymbol {
         use crate::fields::LegendreSymbol::*;
 
-        // s = self^((MODULUS - 1) // 2)
-        let s = self.pow(P::MODULUS_MINUS_ONE_DIV_TWO);
+        // s = self^((MODULUS - 1) // 2), by Euler's criterion
+        let s = AdditionChain::new(P::MODULUS_MINUS_ONE_DIV_TWO).pow(self);
         if s.is_zero() {
             Zero
         } else if s.is_one() {
@@ -738,6 +781,15 @@ impl<P: Fp832Parameters> Ord for Fp832<P> {
     }
 }
 
+/// Hashes the canonical (i.e. non-Montgomery) representation, the same
+/// integer written by `ToBytes`.
+impl<P: Fp832Parameters> Hash for Fp832<P> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.into_repr().hash(state)
+    }
+}
+
 impl<P: Fp832Parameters> PartialOrd for Fp832<P> {
     #[inline(always)]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
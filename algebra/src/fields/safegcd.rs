@@ -0,0 +1,442 @@
+//! Constant-time inversion in prime fields by the safegcd algorithm of
+//! Bernstein and Yang ("Fast constant-time gcd computation and modular
+//! inversion", TCHES 2019), following the implementation of libsecp256k1.
+//!
+//! The inverse of `x` is computed by a fixed number of divsteps on `f = p` and
+//! `g = x`, performed in batches of 62: each batch is run on the low 64 bits of
+//! `f` and `g` only, yielding a transition matrix which is then applied to the
+//! full `f` and `g`, and to the coefficients `d` and `e` of Bezout's identities
+//! `d * x = f` and `e * x = g` modulo `p`. Once `g = 0`, `f = ±1` and `±d` is
+//! the inverse. The integers are represented by limbs of 62 bits, of which the
+//! most significant one is signed.
+use crate::{fields::FpParameters, PrimeField};
+
+const M62: u64 = u64::max_value() >> 2;
+
+/// The largest number of 62 bit limbs, enough for moduli of up to 928 bits.
+const MAX_LIMBS: usize = 15;
+
+type Signed62 = [i64; MAX_LIMBS];
+
+/// A signed 128 bit accumulator of products of 64 bit integers.
+#[cfg(not(feature = "no_u128"))]
+#[derive(Clone, Copy)]
+struct Acc(i128);
+
+#[cfg(not(feature = "no_u128"))]
+impl Acc {
+    #[inline(always)]
+    fn mul(a: i64, b: i64) -> Self {
+        Acc(i128::from(a) * i128::from(b))
+    }
+
+    #[inline(always)]
+    fn add_mul(&mut self, a: i64, b: i64) {
+        self.0 += i128::from(a) * i128::from(b);
+    }
+
+    /// The low 64 bits.
+    #[inline(always)]
+    fn low(&self) -> u64 {
+        self.0 as u64
+    }
+
+    /// Arithmetic shift right by 62 bits.
+    #[inline(always)]
+    fn shr62(&mut self) {
+        self.0 >>= 62;
+    }
+}
+
+#[cfg(feature = "no_u128")]
+use self::portable::Acc;
+
+/// The accumulator above on 64 bit integers only, used instead of the `i128`
+/// one with the `no_u128` feature. It gives the same results bit for bit, and
+/// does not branch on the values either.
+#[cfg_attr(not(feature = "no_u128"), allow(dead_code))]
+mod portable {
+    use crate::biginteger::arithmetic::portable::mac_with_carry;
+
+    /// A signed 128 bit integer, in two's complement.
+    #[derive(Clone, Copy)]
+    pub(super) struct Acc {
+        hi: u64,
+        lo: u64,
+    }
+
+    impl Acc {
+        #[inline(always)]
+        pub(super) fn mul(a: i64, b: i64) -> Self {
+            // The product of the absolute values, negated if the signs differ
+            let (sign_a, sign_b) = (a >> 63, b >> 63);
+            let abs_a = ((a ^ sign_a).wrapping_sub(sign_a)) as u64;
+            let abs_b = ((b ^ sign_b).wrapping_sub(sign_b)) as u64;
+            let mut hi = 0;
+            let lo = mac_with_carry(0, abs_a, abs_b, &mut hi);
+
+            let negate = (sign_a ^ sign_b) as u64;
+            let (lo, carry) = (lo ^ negate).overflowing_add(negate & 1);
+            let hi = (hi ^ negate).wrapping_add(u64::from(carry));
+            Acc { hi, lo }
+        }
+
+        #[inline(always)]
+        pub(super) fn add_mul(&mut self, a: i64, b: i64) {
+            let product = Self::mul(a, b);
+            let (lo, carry) = self.lo.overflowing_add(product.lo);
+            self.hi = self.hi.wrapping_add(product.hi).wrapping_add(u64::from(carry));
+            self.lo = lo;
+        }
+
+        /// The low 64 bits.
+        #[inline(always)]
+        pub(super) fn low(&self) -> u64 {
+            self.lo
+        }
+
+        /// Arithmetic shift right by 62 bits.
+        #[inline(always)]
+        pub(super) fn shr62(&mut self) {
+            self.lo = (self.lo >> 62) | (self.hi << 2);
+            self.hi = ((self.hi as i64) >> 62) as u64;
+        }
+    }
+}
+
+/// Returns the inverse of `x`, or `None` if `x` is zero. Apart from the check
+/// for zero, the running time does not depend on `x`.
+pub fn safegcd_inverse<F: PrimeField>(x: &F) -> Option<F> {
+    if x.is_zero() {
+        return None;
+    }
+    let mut inverse = F::BigInt::default();
+    inverse_limbs(
+        x.into_repr().as_ref(),
+        F::Params::MODULUS.as_ref(),
+        F::Params::MODULUS_BITS,
+        inverse.as_mut(),
+    );
+    Some(F::from_repr(inverse))
+}
+
+/// Writes the inverse modulo the odd `modulus` of `modulus_bits` bits of the
+/// non-zero `value < modulus` to `inverse`.
+fn inverse_limbs(value: &[u64], modulus: &[u64], modulus_bits: u32, inverse: &mut [u64]) {
+    // The values are in (-2p, 2p), i.e. of modulus_bits + 2 bits with the sign
+    let num_limbs = (modulus_bits as usize + 2 + 61) / 62;
+    assert!(num_limbs <= MAX_LIMBS);
+
+    // modulus^-1 mod 2^62, by Newton iteration
+    let mut modulus_inv = 1u64;
+    for _ in 0..6 {
+        modulus_inv =
+            modulus_inv.wrapping_mul(2u64.wrapping_sub(modulus[0].wrapping_mul(modulus_inv)));
+    }
+    let modulus_inv = modulus_inv & M62;
+
+    let modulus = to_signed62(modulus, num_limbs);
+    let mut f = modulus;
+    let mut g = to_signed62(value, num_limbs);
+    let mut d = [0i64; MAX_LIMBS];
+    let mut e = [0i64; MAX_LIMBS];
+    e[0] = 1;
+
+    // The number of divsteps after which g = 0 for inputs of d bits, as bounded
+    // by Theorem 11.2 of the paper
+    let d_bits = modulus_bits as usize;
+    let num_divsteps = if d_bits < 46 {
+        (49 * d_bits + 80) / 17
+    } else {
+        (49 * d_bits + 57) / 17
+    };
+
+    let mut delta = 1i64;
+    for _ in 0..(num_divsteps + 61) / 62 {
+        let (new_delta, t) = divsteps_62(delta, f[0] as u64, g[0] as u64);
+        delta = new_delta;
+        update_de(&mut d, &mut e, &t, &modulus, modulus_inv, num_limbs);
+        update_fg(&mut f, &mut g, &t, num_limbs);
+    }
+
+    // f = ±1, and d * x = f
+    let f_sign = f[num_limbs - 1] >> 63;
+    normalize(&mut d, f_sign, &modulus, num_limbs);
+    from_signed62(&d, num_limbs, inverse);
+}
+
+/// Splits the little endian 64 bit limbs of a non-negative integer into
+/// `num_limbs` limbs of 62 bits.
+fn to_signed62(limbs: &[u64], num_limbs: usize) -> Signed62 {
+    let mut result = [0i64; MAX_LIMBS];
+    for (i, limb) in result.iter_mut().take(num_limbs).enumerate() {
+        let (word, shift) = (62 * i / 64, 62 * i % 64);
+        let mut bits = limbs.get(word).map_or(0, |w| w >> shift);
+        if shift > 2 {
+            bits |= limbs.get(word + 1).map_or(0, |w| w << (64 - shift));
+        }
+        *limb = (bits & M62) as i64;
+    }
+    result
+}
+
+/// Writes the non-negative integer with normalized limbs `a` to the little
+/// endian 64 bit limbs `limbs`.
+fn from_signed62(a: &Signed62, num_limbs: usize, limbs: &mut [u64]) {
+    limbs.iter_mut().for_each(|l| *l = 0);
+    for (i, limb) in a.iter().take(num_limbs).enumerate() {
+        let (word, shift) = (62 * i / 64, 62 * i % 64);
+        let bits = *limb as u64;
+        if word < limbs.len() {
+            limbs[word] |= bits << shift;
+        }
+        if shift > 2 && word + 1 < limbs.len() {
+            limbs[word + 1] |= bits >> (64 - shift);
+        }
+    }
+}
+
+/// Performs 62 divsteps on the low 64 bits `f` and `g` of the odd `f` and of
+/// `g`, returning the new `delta` and the transition matrix `[u, v, q, r]` such
+/// that `2^62 * f' = u * f + v * g` and `2^62 * g' = q * f + r * g`. The
+/// entries satisfy `|u| + |v| <= 2^62` and `|q| + |r| <= 2^62`.
+fn divsteps_62(mut delta: i64, mut f: u64, mut g: u64) -> (i64, [i64; 4]) {
+    let (mut u, mut v, mut q, mut r) = (1i64, 0i64, 0i64, 1i64);
+    for _ in 0..62 {
+        // All ones if delta > 0, resp. if g is odd
+        let c1 = delta.wrapping_neg() >> 63;
+        let c2 = -((g & 1) as i64);
+        let swap = c1 & c2;
+
+        // (delta, f, g, u, v, q, r) = (-delta, g, -f, q, r, -u, -v) if swap
+        let x = (f ^ g) & swap as u64;
+        f ^= x;
+        g ^= x;
+        g = (g ^ swap as u64).wrapping_sub(swap as u64);
+        let x = (u ^ q) & swap;
+        u ^= x;
+        q ^= x;
+        q = (q ^ swap) - swap;
+        let x = (v ^ r) & swap;
+        v ^= x;
+        r ^= x;
+        r = (r ^ swap) - swap;
+        delta = (delta ^ swap) - swap;
+
+        // g = (g + f) / 2 if g is odd, g / 2 otherwise. Instead of halving the
+        // row of g, the row of f is doubled.
+        g = g.wrapping_add(f & c2 as u64);
+        q += u & c2;
+        r += v & c2;
+        g >>= 1;
+        u <<= 1;
+        v <<= 1;
+        delta += 1;
+    }
+    (delta, [u, v, q, r])
+}
+
+/// Computes `(d, e) = t * (d, e) / 2^62` modulo the modulus, keeping `d` and
+/// `e` in `(-2p, p)`.
+fn update_de(
+    d: &mut Signed62,
+    e: &mut Signed62,
+    t: &[i64; 4],
+    modulus: &Signed62,
+    modulus_inv: u64,
+    num_limbs: usize,
+) {
+    let [u, v, q, r] = *t;
+
+    // Add the modulus times md, resp. me, chosen to make the bottom 62 bits of
+    // the sums zero, and such that the results stay in range
+    let sd = d[num_limbs - 1] >> 63;
+    let se = e[num_limbs - 1] >> 63;
+    let mut md = (t[0] & sd) + (t[1] & se);
+    let mut me = (t[2] & sd) + (t[3] & se);
+    let mut cd = Acc::mul(u, d[0]);
+    cd.add_mul(v, e[0]);
+    let mut ce = Acc::mul(q, d[0]);
+    ce.add_mul(r, e[0]);
+    md -= (modulus_inv.wrapping_mul(cd.low()).wrapping_add(md as u64) & M62) as i64;
+    me -= (modulus_inv.wrapping_mul(ce.low()).wrapping_add(me as u64) & M62) as i64;
+    cd.add_mul(modulus[0], md);
+    ce.add_mul(modulus[0], me);
+    debug_assert_eq!(cd.low() & M62, 0);
+    debug_assert_eq!(ce.low() & M62, 0);
+    cd.shr62();
+    ce.shr62();
+
+    for i in 1..num_limbs {
+        cd.add_mul(u, d[i]);
+        cd.add_mul(v, e[i]);
+        cd.add_mul(modulus[i], md);
+        ce.add_mul(q, d[i]);
+        ce.add_mul(r, e[i]);
+        ce.add_mul(modulus[i], me);
+        d[i - 1] = (cd.low() & M62) as i64;
+        e[i - 1] = (ce.low() & M62) as i64;
+        cd.shr62();
+        ce.shr62();
+    }
+    d[num_limbs - 1] = cd.low() as i64;
+    e[num_limbs - 1] = ce.low() as i64;
+}
+
+/// Computes `(f, g) = t * (f, g) / 2^62`, the division being exact.
+fn update_fg(f: &mut Signed62, g: &mut Signed62, t: &[i64; 4], num_limbs: usize) {
+    let [u, v, q, r] = *t;
+
+    let mut cf = Acc::mul(u, f[0]);
+    cf.add_mul(v, g[0]);
+    let mut cg = Acc::mul(q, f[0]);
+    cg.add_mul(r, g[0]);
+    debug_assert_eq!(cf.low() & M62, 0);
+    debug_assert_eq!(cg.low() & M62, 0);
+    cf.shr62();
+    cg.shr62();
+
+    for i in 1..num_limbs {
+        cf.add_mul(u, f[i]);
+        cf.add_mul(v, g[i]);
+        cg.add_mul(q, f[i]);
+        cg.add_mul(r, g[i]);
+        f[i - 1] = (cf.low() & M62) as i64;
+        g[i - 1] = (cg.low() & M62) as i64;
+        cf.shr62();
+        cg.shr62();
+    }
+    f[num_limbs - 1] = cf.low() as i64;
+    g[num_limbs - 1] = cg.low() as i64;
+}
+
+/// Brings `a` in `(-2p, p)` to `[0, p)`, negating it if `negate` is all ones.
+fn normalize(a: &mut Signed62, negate: i64, modulus: &Signed62, num_limbs: usize) {
+    // Add the modulus if negative, to (-p, p), then negate if requested
+    let cond_add = a[num_limbs - 1] >> 63;
+    for i in 0..num_limbs {
+        a[i] += modulus[i] & cond_add;
+        a[i] = (a[i] ^ negate) - negate;
+    }
+    propagate_carries(a, num_limbs);
+
+    // Add the modulus again if still negative, to [0, p)
+    let cond_add = a[num_limbs - 1] >> 63;
+    for i in 0..num_limbs {
+        a[i] += modulus[i] & cond_add;
+    }
+    propagate_carries(a, num_limbs);
+}
+
+fn propagate_carries(a: &mut Signed62, num_limbs: usize) {
+    for i in 0..num_limbs - 1 {
+        a[i + 1] += a[i] >> 62;
+        a[i] &= M62 as i64;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        biginteger::BigInteger,
+        fields::{
+            bls12_377, bls12_381, ed25519, edwards_bls12, jubjub, mnt4753, mnt6, secp256k1, sw6,
+            InversionAlgorithm,
+        },
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    // Returns x^(p - 2), the inverse of a non-zero x by Fermat's little theorem.
+    fn fermat_inverse<F: PrimeField>(x: &F) -> F {
+        let mut exp = F::Params::MODULUS;
+        exp.sub_noborrow(&F::BigInt::from(2));
+        x.pow(exp)
+    }
+
+    fn test_safegcd<F: PrimeField>() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        assert!(safegcd_inverse(&F::zero()).is_none());
+        assert_eq!(safegcd_inverse(&F::one()), Some(F::one()));
+        let minus_one = -F::one();
+        assert_eq!(safegcd_inverse(&minus_one), Some(minus_one));
+        for _ in 0..1000 {
+            let x = F::rand(&mut rng);
+            assert_eq!(safegcd_inverse(&x), x.inverse());
+            assert_eq!(safegcd_inverse(&x), Some(fermat_inverse(&x)));
+        }
+    }
+
+    // Checks `Field::inverse` on a field whose parameters select safegcd.
+    fn test_field_inverse<F: PrimeField>() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+        assert_eq!(F::Params::INVERSION_ALGORITHM, InversionAlgorithm::SafeGcd);
+
+        assert!(F::zero().inverse().is_none());
+        assert_eq!(F::one().inverse(), Some(F::one()));
+        for _ in 0..100 {
+            let x = F::rand(&mut rng);
+            let inverse = x.inverse().unwrap();
+            assert_eq!(x * &inverse, F::one());
+            assert_eq!(inverse, fermat_inverse(&x));
+        }
+    }
+
+    #[test]
+    fn test_safegcd_inverse() {
+        test_safegcd::<bls12_381::Fr>();
+        test_safegcd::<bls12_381::Fq>();
+        test_safegcd::<secp256k1::Fq>();
+        test_safegcd::<mnt4753::Fr>();
+        test_safegcd::<mnt6::Fq>();
+        test_safegcd::<sw6::Fq>();
+    }
+
+    #[test]
+    fn test_safegcd_field_inverse() {
+        test_field_inverse::<bls12_377::Fr>();
+        test_field_inverse::<ed25519::Fq>();
+        test_field_inverse::<ed25519::Fr>();
+        test_field_inverse::<edwards_bls12::Fr>();
+        test_field_inverse::<jubjub::Fq>();
+        test_field_inverse::<jubjub::Fr>();
+        test_field_inverse::<secp256k1::Fq>();
+        test_field_inverse::<secp256k1::Fr>();
+    }
+
+    #[cfg(not(feature = "no_u128"))]
+    #[test]
+    fn test_portable_acc() {
+        use rand::Rng;
+
+        // The factors of the updates are at most 2^62 in absolute value, and
+        // at most three products are accumulated between two shifts
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+        let bound = 1i64 << 62;
+        let edge = [0i64, 1, -1, 2, -2, M62 as i64, -(M62 as i64), bound, -bound];
+        let mut inputs = vec![];
+        for &a in edge.iter() {
+            for &b in edge.iter() {
+                inputs.push((a, b));
+            }
+        }
+        inputs.extend(
+            (0..10000).map(|_| (rng.gen_range(-bound, bound + 1), rng.gen_range(-bound, bound + 1))),
+        );
+
+        let (mut expected, mut found) = (Acc::mul(0, 0), portable::Acc::mul(0, 0));
+        for (i, &(a, b)) in inputs.iter().enumerate() {
+            assert_eq!(Acc::mul(a, b).low(), portable::Acc::mul(a, b).low());
+            expected.add_mul(a, b);
+            found.add_mul(a, b);
+            assert_eq!(expected.low(), found.low());
+            if i % 3 == 2 {
+                expected.shr62();
+                found.shr62();
+                assert_eq!(expected.low(), found.low());
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    biginteger::BigInteger256 as BigInteger,
+    fields::{Fp256, Fp256Parameters, FpParameters, InversionAlgorithm},
+};
+
+/// Modulus 2^256 - 2^32 - 977, reduced with the generic Montgomery arithmetic.
+pub type Fq = Fp256<FqParameters>;
+
+pub struct FqParameters;
+
+impl Fp256Parameters for FqParameters {}
+impl FpParameters for FqParameters {
+    type BigInt = BigInteger;
+
+    // MODULUS = 115792089237316195423570985008687907853269984665640564039457584007908834671663
+    const MODULUS: BigInteger = BigInteger([
+        0xfffffffefffffc2f,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ]);
+
+    const MODULUS_BITS: u32 = 256;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 0;
+
+    const R: BigInteger = BigInteger([
+        0x1000003d1,
+        0x0,
+        0x0,
+        0x0,
+    ]);
+
+    const R2: BigInteger = BigInteger([
+        0x7a2000e90a1,
+        0x1,
+        0x0,
+        0x0,
+    ]);
+
+    const INV: u64 = 0xd838091dd2253531;
+
+    const BARRETT_MU: &'static [u64] = &[
+        0x1000003d1,
+        0x0,
+        0x0,
+        0x0,
+        0x1,
+    ];
+
+    // GENERATOR = 3
+    const GENERATOR: BigInteger = BigInteger([
+        0x300000b73,
+        0x0,
+        0x0,
+        0x0,
+    ]);
+
+    const TWO_ADICITY: u32 = 1;
+
+    // ROOT_OF_UNITY = GENERATOR^T = 115792089237316195423570985008687907853269984665640564039457584007908834671662
+    const ROOT_OF_UNITY: BigInteger = BigInteger([
+        0xfffffffdfffff85e,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ]);
+
+    // (MODULUS - 1) / 2 =
+    // 57896044618658097711785492504343953926634992332820282019728792003954417335831
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xffffffff7ffffe17,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ]);
+
+    // T and T_MINUS_ONE_DIV_TWO, where MODULUS - 1 = 2^S * T
+
+    // T = (MODULUS - 1) / 2^S =
+    // 57896044618658097711785492504343953926634992332820282019728792003954417335831
+    const T: BigInteger = BigInteger([
+        0xffffffff7ffffe17,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ]);
+
+    // (T - 1) / 2 =
+    // 28948022309329048855892746252171976963317496166410141009864396001977208667915
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xffffffffbfffff0b,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ]);
+
+    const INVERSION_ALGORITHM: InversionAlgorithm = InversionAlgorithm::SafeGcd;
+}
@@ -0,0 +1,159 @@
+use crate::{
+    fields::tests::{field_test, primefield_test, sqrt_field_test},
+    fields::secp256k1::{Fq, Fr},
+    Field, SquareRootField, UniformRand,
+};
+
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use std::str::FromStr;
+
+pub(crate) const ITERATIONS: usize = 5;
+
+#[test]
+fn test_secp256k1_fr() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    for _ in 0..ITERATIONS {
+        let a: Fr = UniformRand::rand(&mut rng);
+        let b: Fr = UniformRand::rand(&mut rng);
+        field_test(a, b);
+        primefield_test::<Fr>();
+        sqrt_field_test(b);
+    }
+}
+
+#[test]
+fn test_secp256k1_fq() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    for _ in 0..ITERATIONS {
+        let a: Fq = UniformRand::rand(&mut rng);
+        let b: Fq = UniformRand::rand(&mut rng);
+        field_test(a, b);
+        primefield_test::<Fq>();
+        sqrt_field_test(a);
+    }
+}
+
+// The moduli of secp256k1 leave no spare bit in the most significant limb,
+// hence the following tests exercise the carry paths of the arithmetic.
+
+#[test]
+fn test_fq_add_with_carry() {
+    let f1 = Fq::from_str(
+        "115792089237316193906455461685865271915348212377597935812802573065342398595049",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "115792089237316195423525467821155518704089322011430945998491704855326985730302",
+    )
+    .unwrap();
+    let f3 = Fq::from_str(
+        "115792089237316193906409944498332882766167549723388317771836693912760549653688",
+    )
+    .unwrap();
+    assert_eq!(f1 + &f2, f3);
+}
+
+#[test]
+fn test_fq_double_in_place_thrice() {
+    let mut f1 = Fq::from_str(
+        "115792089237316195423570985008687907853269984664390663830382481399159612960303",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "115792089237316195423570985008687907853269984663140763621307378790410391248943",
+    )
+    .unwrap();
+    let f3 = Fq::from_str(
+        "115792089237316195423570985008687907853269984655641362366856763137915060980783",
+    )
+    .unwrap();
+    f1.double_in_place();
+    assert_eq!(f1, f2);
+    f1.double_in_place();
+    f1.double_in_place();
+    assert_eq!(f1, f3);
+}
+
+#[test]
+fn test_fq_mul() {
+    let f1 = Fq::from_str(
+        "54314484353974878918833823043703112788444063164769804218895646712080277563958",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "1518519461068296049748913552942124756771235564828927580385821535582133842176",
+    )
+    .unwrap();
+    let f3 = Fq::from_str(
+        "60872419633257526631498083147574604538088274392795285553556010376392838071367",
+    )
+    .unwrap();
+    assert_eq!(f1 * &f2, f3);
+}
+
+#[test]
+fn test_fq_inverse() {
+    let f1 = Fq::from_str(
+        "54314484353974878918833823043703112788444063164769804218895646712080277563958",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "85541766473186240156228351883908732470603790092517482547542947505436855754770",
+    )
+    .unwrap();
+    assert_eq!(f1.inverse().unwrap(), f2);
+    assert_eq!(f2.inverse().unwrap(), f1);
+}
+
+#[test]
+fn test_fq_sqrt() {
+    let f1 = Fq::from_str(
+        "28420572053430486983127735240034924225704761017821282287711018446192435040197",
+    )
+    .unwrap();
+    let r1 = Fq::from_str(
+        "27127139588778670217617171268665951532404802849742593496862118645699873677583",
+    )
+    .unwrap();
+    let r2 = Fq::from_str(
+        "88664949648537525205953813740021956320865181815897970542595465362208960994080",
+    )
+    .unwrap();
+    let root = f1.sqrt().unwrap();
+    assert!(root == r1 || root == r2);
+}
+
+#[test]
+fn test_fr_add_with_carry() {
+    let f1 = Fr::from_str(
+        "115792089237316195423570985008687907852836504688937291773641352104682681800527",
+    )
+    .unwrap();
+    let f2 = Fr::from_str(
+        "115792089237316195423570985008687907852287173707235782030967793464120263320312",
+    )
+    .unwrap();
+    let f3 = Fr::from_str(
+        "115792089237316195423570985008687907852286114117098169422003982427284783626502",
+    )
+    .unwrap();
+    assert_eq!(f1 + &f2, f3);
+}
+
+#[test]
+fn test_fr_mul() {
+    let f1 = Fr::from_str(
+        "89576031210713149173088806542273087861719248915189561960391773705410702171238",
+    )
+    .unwrap();
+    let f2 = Fr::from_str(
+        "36318347041445649703069935930137432963822619289588782059124818413615611233817",
+    )
+    .unwrap();
+    let f3 = Fr::from_str(
+        "74945996127209392688610409465931494668156901801881574543569636847925018802308",
+    )
+    .unwrap();
+    assert_eq!(f1 * &f2, f3);
+}
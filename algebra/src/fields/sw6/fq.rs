@@ -68,6 +68,23 @@ impl FpParameters for FqParameters {
 
     const INV: u64 = 14469047335842394791u64;
 
+    const BARRETT_MU: &'static [u64] = &[
+        0x304154fd5b436ee,
+        0x648514392b813361,
+        0xac3036e0e0cfdc98,
+        0x59f7183e82377359,
+        0xb1416009b9abdece,
+        0x9307d5a410c5e7f9,
+        0x11ea179d75aff439,
+        0x52119f30332a8e48,
+        0xea4ee07b8904fd1,
+        0x9341cb5eca9793b3,
+        0x63af479efd332c8d,
+        0x6f5f9f09a96c94a1,
+        0x99dbf2631451267d,
+        0x48c601a57afb7,
+    ];
+
     /// GENERATOR = 13
     const GENERATOR: BigInteger = BigInteger([
         16669393626057438558u64,
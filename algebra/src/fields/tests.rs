@@ -1,6 +1,10 @@
 use crate::{
-    fields::{Field, LegendreSymbol, PrimeField, SquareRootField},
-    ToBytes, to_bytes,
+    biginteger::{arithmetic::barrett_mu, BigInteger},
+    fields::{
+        from_le_bytes_mod_order_by_limbs, DecodingMode, Field, FieldDecodingError, FpParameters, LegendreSymbol,
+        PrimeField, SquareRootField,
+    },
+    ToBytes, to_bytes, UniformRand,
 };
 use rand::{Rng, SeedableRng};
 use rand_xorshift::XorShiftRng;
@@ -201,6 +205,72 @@ fn random_serialization_tests<F: Field, R: Rng>(rng: &mut R) {
     }
 }
 
+fn random_decoding_mode_tests<F: PrimeField, R: Rng>(rng: &mut R) {
+    for _ in 0..ITERATIONS {
+        let a = F::rand(rng);
+
+        // Canonical encodings are accepted in both modes
+        let a_bytes = to_bytes!(a).unwrap();
+        assert_eq!(F::from_le_bytes_with_mode(&a_bytes, DecodingMode::Strict).unwrap(), a);
+        assert_eq!(F::from_le_bytes_with_mode(&a_bytes, DecodingMode::ReduceModOrder).unwrap(), a);
+
+        let a_bits = a.write_bits();
+        assert_eq!(F::from_bits_with_mode(&a_bits, DecodingMode::Strict).unwrap(), a);
+        assert_eq!(F::from_bits_with_mode(&a_bits, DecodingMode::ReduceModOrder).unwrap(), a);
+
+        // a + p is rejected in strict mode and reduced to a otherwise
+        let mut a_plus_p = a.into_repr();
+        if !a_plus_p.add_nocarry(&F::Params::MODULUS) {
+            let bytes = to_bytes!(a_plus_p).unwrap();
+            assert_eq!(
+                F::from_le_bytes_with_mode(&bytes, DecodingMode::Strict),
+                Err(FieldDecodingError::NonCanonical)
+            );
+            assert_eq!(F::from_le_bytes_with_mode(&bytes, DecodingMode::ReduceModOrder).unwrap(), a);
+        }
+    }
+
+    // The modulus itself is the smallest non canonical encoding
+    let modulus_bytes = to_bytes!(F::Params::MODULUS).unwrap();
+    assert_eq!(
+        F::from_le_bytes_with_mode(&modulus_bytes, DecodingMode::Strict),
+        Err(FieldDecodingError::NonCanonical)
+    );
+    assert!(F::from_le_bytes_with_mode(&modulus_bytes, DecodingMode::ReduceModOrder).unwrap().is_zero());
+    let modulus_bits = F::Params::MODULUS.to_bits();
+    assert_eq!(
+        F::from_bits_with_mode(&modulus_bits, DecodingMode::Strict),
+        Err(FieldDecodingError::NonCanonical)
+    );
+    assert!(F::from_bits_with_mode(&modulus_bits, DecodingMode::ReduceModOrder).unwrap().is_zero());
+
+    // Oversized encodings are rejected in strict mode only
+    let mut long_bytes = modulus_bytes.clone();
+    long_bytes.extend_from_slice(&[1u8; 64]);
+    assert!(F::from_le_bytes_with_mode(&long_bytes, DecodingMode::Strict).is_err());
+    assert!(F::from_le_bytes_with_mode(&long_bytes, DecodingMode::ReduceModOrder).is_ok());
+
+    // Reduction of wide inputs agrees with the bitwise reduction, and with the
+    // limb by limb reduction used without `BARRETT_MU`
+    let le_bytes_to_bits = |bytes: &[u8]| {
+        bytes.iter().rev().flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1 == 1)).collect::<Vec<_>>()
+    };
+    let repr_bytes = modulus_bytes.len();
+    for len in &[repr_bytes + 1, 2 * repr_bytes - 1, 2 * repr_bytes, 2 * repr_bytes + 1, 3 * repr_bytes] {
+        let bytes = (0..*len).map(|_| rng.gen()).collect::<Vec<u8>>();
+        assert_eq!(
+            F::from_le_bytes_mod_order(&bytes),
+            F::from_bits_with_mode(&le_bytes_to_bits(&bytes), DecodingMode::ReduceModOrder).unwrap()
+        );
+        assert_eq!(F::from_le_bytes_mod_order(&bytes), from_le_bytes_mod_order_by_limbs::<F>(&bytes));
+    }
+    let max_bytes = vec![0xffu8; 2 * repr_bytes];
+    assert_eq!(
+        F::from_le_bytes_mod_order(&max_bytes),
+        F::from_bits_with_mode(&le_bytes_to_bits(&max_bytes), DecodingMode::ReduceModOrder).unwrap()
+    );
+}
+
 fn random_field_tests<F: Field>() {
     let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
 
@@ -235,6 +305,47 @@ fn random_field_tests<F: Field>() {
         assert_eq!(a, copy);
     }
 
+    // Downcasting to the base prime field
+    {
+        let b = F::BasePrimeField::rand(&mut rng);
+        let a = F::from_base_field(b);
+        assert_eq!(a.as_base_field(), Some(b));
+        assert_eq!(a.to_base_prime_field_elements()[0], b);
+        assert_eq!(F::one().as_base_field(), Some(F::BasePrimeField::one()));
+
+        let c = F::rand(&mut rng);
+        let coeffs = c.to_base_prime_field_elements();
+        if coeffs[1..].iter().all(|coeff| coeff.is_zero()) {
+            assert_eq!(c.as_base_field(), Some(coeffs[0]));
+        } else {
+            assert!(c.as_base_field().is_none());
+        }
+    }
+
+    // Norm and trace over the base prime field
+    {
+        let a = F::rand(&mut rng);
+        let b = F::rand(&mut rng);
+        assert_eq!((a * &b).norm(), a.norm() * &b.norm());
+        assert_eq!((a + &b).trace(), a.trace() + &b.trace());
+
+        // The norm is the product and the trace the sum of the conjugates.
+        let mut product = F::one();
+        let mut sum = F::zero();
+        for power in 0..F::EXTENSION_DEGREE {
+            let mut conjugate = a;
+            conjugate.frobenius_map(power);
+            product *= &conjugate;
+            sum += &conjugate;
+        }
+        assert_eq!(product.as_base_field(), Some(a.norm()));
+        assert_eq!(sum.as_base_field(), Some(a.trace()));
+
+        let c = F::BasePrimeField::rand(&mut rng);
+        let degree = F::BasePrimeField::from(F::EXTENSION_DEGREE as u64);
+        assert_eq!(F::from_base_field(c).trace(), c * &degree);
+    }
+
     for _ in 0..ITERATIONS {
         //Serialization tests
         let a = F::rand(&mut rng);
@@ -243,7 +354,42 @@ fn random_field_tests<F: Field>() {
         let a_serialized = a.write_bits();
         let a_deserialized = F::read_bits(a_serialized.clone()).unwrap();
         assert_eq!(a, a_deserialized);
+
+        // Explicit bit orders
+        assert_eq!(a.write_bits_be(), a_serialized);
+        let a_serialized_le = a.write_bits_le();
+        assert_eq!(a_serialized_le.len(), F::NUM_BITS);
+        assert_eq!(F::read_bits_be(a_serialized.clone()).unwrap(), a);
+        assert_eq!(F::read_bits_le(a_serialized_le.clone()).unwrap(), a);
+
+        // Length checked reads
+        assert_eq!(a_serialized.len(), F::NUM_BITS);
+        assert_eq!(F::read_bits_be_checked(a_serialized.clone()).unwrap(), a);
+        assert_eq!(F::read_bits_le_checked(a_serialized_le.clone()).unwrap(), a);
+        assert!(F::read_bits_be_checked(a_serialized[1..].to_vec()).is_err());
+        assert!(F::read_bits_le_checked(a_serialized_le[1..].to_vec()).is_err());
+        let mut too_long = a_serialized_le;
+        too_long.push(false);
+        assert!(F::read_bits_le_checked(too_long).is_err());
+
+        // Strict and unchecked reads
+        assert_eq!(F::read_bits_with_mode(a_serialized.clone(), DecodingMode::Strict).unwrap(), a);
+        assert_eq!(F::read_bits_with_mode(a_serialized.clone(), DecodingMode::ReduceModOrder).unwrap(), a);
+        assert!(F::read_bits_with_mode(a_serialized[1..].to_vec(), DecodingMode::Strict).is_err());
+        assert_eq!(F::read_bits_unchecked(a_serialized), a);
     }
+
+    // Coefficients of all ones are over the modulus
+    let all_ones = vec![true; F::NUM_BITS];
+    assert!(F::read_bits(all_ones.clone()).is_err());
+    assert!(F::read_bits_with_mode(all_ones.clone(), DecodingMode::Strict).is_err());
+    assert!(F::read_bits_with_mode(all_ones, DecodingMode::ReduceModOrder).is_ok());
+
+    // Too short inputs are rejected rather than panicking
+    if F::EXTENSION_DEGREE > 1 {
+        assert!(F::read_bits(vec![]).is_err());
+    }
+    assert!(F::read_bits_le_checked(vec![]).is_err());
 }
 
 fn random_sqrt_tests<F: SquareRootField>() {
@@ -253,11 +399,15 @@ fn random_sqrt_tests<F: SquareRootField>() {
         let a = F::rand(&mut rng);
         let b = a.square();
         assert_eq!(b.legendre(), LegendreSymbol::QuadraticResidue);
+        assert!(b.is_square());
+        assert_eq!(a.is_square(), a.sqrt().is_some());
 
         let b = b.sqrt().unwrap();
         assert!(a == b || a == -b);
     }
 
+    assert!(F::zero().is_square());
+
     let mut c = F::one();
     for _ in 0..ITERATIONS {
         let mut b = c.square();
@@ -380,9 +530,16 @@ pub fn primefield_test<F: PrimeField>() {
     let one = F::one();
     assert_eq!(F::from_repr(one.into_repr()), one);
     assert_eq!(F::from_str("1").ok().unwrap(), one);
+    if !F::Params::BARRETT_MU.is_empty() {
+        assert_eq!(
+            F::Params::BARRETT_MU,
+            barrett_mu(F::Params::MODULUS.as_ref(), F::Params::R2.as_ref(), F::Params::INV).as_slice()
+        );
+    }
 
     let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
     random_serialization_tests::<F, _>(&mut rng);
+    random_decoding_mode_tests::<F, _>(&mut rng);
 }
 
 pub fn sqrt_field_test<F: SquareRootField>(elem: F) {
@@ -415,3 +572,19 @@ pub fn frobenius_test<F: Field, C: AsRef<[u64]>>(characteristic: C, maxpower: us
         }
     }
 }
+
+/// Checks the Frobenius coefficients `coeff(power)` of an extension with
+/// coefficients in `F` against their definition as `N^((p^power - 1) / k)`
+/// for a nonresidue `N` in `F`, i.e. that `coeff(0) = 1` and
+/// `coeff(power + 1) = coeff(power)^p * coeff(1)`, up to and beyond the
+/// extension `degree`.
+pub fn frobenius_coeff_test<F: Field>(coeff: impl Fn(usize) -> F, degree: usize) {
+    assert_eq!(coeff(0), F::one());
+    for power in 0..2 * degree {
+        let mut expected = coeff(power);
+        expected.frobenius_map(1);
+        expected *= &coeff(1);
+        assert_eq!(coeff(power + 1), expected);
+    }
+    assert_eq!(coeff(degree), F::one());
+}
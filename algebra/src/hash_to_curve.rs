@@ -0,0 +1,284 @@
+//! Hashing of byte strings to points of elliptic curves, following RFC 9380
+//! (https://www.rfc-editor.org/rfc/rfc9380): a message is hashed to two base
+//! field elements with `expand_message_xmd`, each of which is mapped to the
+//! curve by the map given by the curve parameters, and the sum of both points
+//! is mapped to the prime order subgroup.
+use crate::{
+    curves::{
+        models::{
+            short_weierstrass_jacobian, short_weierstrass_projective, twisted_edwards_extended,
+            Elligator2Parameters, SWUParameters,
+        },
+        AffineCurve, ProjectiveCurve,
+    },
+    fields::{FpParameters, PrimeField},
+    Error,
+};
+use digest::{generic_array::typenum::Unsigned, Digest};
+use std::fmt;
+
+/// The security level in bits targeted by `hash_to_field`.
+const SECURITY_PARAMETER: usize = 128;
+
+#[derive(Debug)]
+pub enum HashToCurveError {
+    DomainSeparatorTooLong(usize),
+    OutputTooLong(usize),
+}
+
+impl fmt::Display for HashToCurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            HashToCurveError::DomainSeparatorTooLong(len) => {
+                format!("domain separator of {} bytes is longer than 255 bytes", len)
+            }
+            HashToCurveError::OutputTooLong(len) => {
+                format!("cannot expand a message to {} bytes", len)
+            }
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::error::Error for HashToCurveError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+/// Expands `msg` to `len_in_bytes` uniformly random bytes, using the Merkle-Damgard
+/// hash function `D` and the domain separator `dst` (RFC 9380, Section 5.3.1).
+pub fn expand_message_xmd<D: Digest>(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<u8>, Error> {
+    let b_in_bytes = <D::OutputSize as Unsigned>::to_usize();
+    let r_in_bytes = <D::BlockSize as Unsigned>::to_usize();
+
+    let ell = (len_in_bytes + b_in_bytes - 1) / b_in_bytes;
+    if ell > 255 || len_in_bytes > 0xffff {
+        return Err(Box::new(HashToCurveError::OutputTooLong(len_in_bytes)));
+    }
+    if dst.len() > 255 {
+        return Err(Box::new(HashToCurveError::DomainSeparatorTooLong(dst.len())));
+    }
+
+    let mut dst_prime = dst.to_vec();
+    dst_prime.push(dst.len() as u8);
+
+    // b_0 = H(Z_pad || msg || l_i_b_str || 0 || DST_prime)
+    let mut b_0_input = vec![0u8; r_in_bytes];
+    b_0_input.extend_from_slice(msg);
+    b_0_input.extend_from_slice(&(len_in_bytes as u16).to_be_bytes());
+    b_0_input.push(0);
+    b_0_input.extend_from_slice(&dst_prime);
+    let b_0 = D::digest(&b_0_input);
+
+    // b_1 = H(b_0 || 1 || DST_prime), b_i = H((b_0 XOR b_(i-1)) || i || DST_prime)
+    let mut uniform_bytes = Vec::with_capacity(ell * b_in_bytes);
+    let mut b_i = vec![0u8; b_in_bytes];
+    for i in 1..=ell {
+        let mut b_i_input = b_0
+            .iter()
+            .zip(b_i.iter())
+            .map(|(b_0_j, b_i_j)| b_0_j ^ b_i_j)
+            .collect::<Vec<_>>();
+        b_i_input.push(i as u8);
+        b_i_input.extend_from_slice(&dst_prime);
+        b_i = D::digest(&b_i_input).to_vec();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+/// Hashes `msg` to `count` elements of the prime field `F`, using the domain
+/// separator `dst` (RFC 9380, Section 5.2).
+pub fn hash_to_field<F: PrimeField, D: Digest>(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+) -> Result<Vec<F>, Error> {
+    // Each element is reduced from L bytes, so that its statistical distance
+    // from the uniform distribution is negligible.
+    let len_per_elem = (F::Params::MODULUS_BITS as usize + SECURITY_PARAMETER + 7) / 8;
+    let uniform_bytes = expand_message_xmd::<D>(msg, dst, count * len_per_elem)?;
+
+    let base = F::from(256u64);
+    let elems = uniform_bytes
+        .chunks(len_per_elem)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(F::zero(), |acc, byte| acc * &base + &F::from(*byte))
+        })
+        .collect();
+    Ok(elems)
+}
+
+/// A curve which supports hashing to its prime order subgroup.
+pub trait HashToCurve: ProjectiveCurve {
+    /// Maps `u` to a point of the curve, not necessarily in the prime order
+    /// subgroup.
+    fn map_to_curve(u: &Self::BaseField) -> Self;
+
+    /// Maps a point of the curve to the prime order subgroup.
+    fn clear_cofactor(&self) -> Self {
+        self.into_affine().scale_by_cofactor_fast()
+    }
+
+    /// Hashes `msg` to a point of the prime order subgroup, using the domain
+    /// separator `domain_sep`. The result is indistinguishable from a random
+    /// point, with unknown discrete logarithm with respect to any other point.
+    fn hash_to_curve<D: Digest>(domain_sep: &[u8], msg: &[u8]) -> Result<Self, Error>
+    where
+        Self::BaseField: PrimeField,
+    {
+        let u = hash_to_field::<Self::BaseField, D>(msg, domain_sep, 2)?;
+        let q = Self::map_to_curve(&u[0]) + &Self::map_to_curve(&u[1]);
+        Ok(q.clear_cofactor())
+    }
+}
+
+impl<P: SWUParameters> HashToCurve for short_weierstrass_jacobian::GroupProjective<P> {
+    fn map_to_curve(u: &P::BaseField) -> Self {
+        let (x, y) = P::map_to_curve(u);
+        short_weierstrass_jacobian::GroupAffine::new(x, y, false).into_projective()
+    }
+}
+
+impl<P: SWUParameters> HashToCurve for short_weierstrass_projective::GroupProjective<P> {
+    fn map_to_curve(u: &P::BaseField) -> Self {
+        let (x, y) = P::map_to_curve(u);
+        short_weierstrass_projective::GroupAffine::new(x, y, false).into_projective()
+    }
+}
+
+impl<P: Elligator2Parameters> HashToCurve for twisted_edwards_extended::GroupProjective<P> {
+    fn map_to_curve(u: &P::BaseField) -> Self {
+        let (x, y) = P::map_to_curve(u);
+        twisted_edwards_extended::GroupAffine::new(x, y).into_projective()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::curves::{
+        bls12_381::{G1Affine as Bls12_381G1Affine, G1Projective as Bls12_381G1Projective},
+        ed25519::Ed25519Projective,
+        jubjub::JubJubProjective,
+        mnt4753::G1Projective as MNT4G1Projective,
+        mnt6753::G1Projective as MNT6G1Projective,
+    };
+    use crate::fields::bls12_381::Fq;
+    use blake2::Blake2s;
+    use sha2::Sha256;
+    use std::str::FromStr;
+
+    #[test]
+    fn expand_message_xmd_test_vectors() {
+        // RFC 9380, Appendix K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let test_vectors: [(&[u8], [u8; 32]); 2] = [
+            (
+                b"",
+                [
+                    0x68, 0xa9, 0x85, 0xb8, 0x7e, 0xb6, 0xb4, 0x69, 0x52, 0x12, 0x89, 0x11, 0xf2,
+                    0xa4, 0x41, 0x2b, 0xbc, 0x30, 0x2a, 0x9d, 0x75, 0x96, 0x67, 0xf8, 0x7f, 0x7a,
+                    0x21, 0xd8, 0x03, 0xf0, 0x72, 0x35,
+                ],
+            ),
+            (
+                b"abc",
+                [
+                    0xd8, 0xcc, 0xab, 0x23, 0xb5, 0x98, 0x5c, 0xce, 0xa8, 0x65, 0xc6, 0xc9, 0x7b,
+                    0x6e, 0x5b, 0x83, 0x50, 0xe7, 0x94, 0xe6, 0x03, 0xb4, 0xb9, 0x79, 0x02, 0xf5,
+                    0x3a, 0x8a, 0x0d, 0x60, 0x56, 0x15,
+                ],
+            ),
+        ];
+        for (msg, expected) in test_vectors.iter() {
+            let uniform_bytes = expand_message_xmd::<Sha256>(msg, dst, 32).unwrap();
+            assert_eq!(uniform_bytes.as_slice(), &expected[..]);
+        }
+
+        assert!(expand_message_xmd::<Sha256>(b"", dst, 255 * 32 + 1).is_err());
+        assert!(expand_message_xmd::<Sha256>(b"", &[0u8; 256], 32).is_err());
+    }
+
+    #[test]
+    fn bls12_381_g1_test_vectors() {
+        // RFC 9380, Appendix J.9.1
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let test_vectors: [(&[u8], &str, &str); 2] = [
+            (
+                b"",
+                "794311575721400831362957049303781044852006323422624111893352859557450008308620925451441746926395141598720928151969",
+                "1343412193624222137939591894701031123123641958980729764240763391191550653712890272928110356903136085217047453540965",
+            ),
+            (
+                b"abc",
+                "513738460217615943921285247703448567647875874745567372796164155472383127756567780059136521508428662765965997467907",
+                "1786897908129645780825838873875416513994655004408749907941296449131605892957529391590865627492442562626458913769565",
+            ),
+        ];
+        for (msg, x, y) in test_vectors.iter() {
+            let p = Bls12_381G1Projective::hash_to_curve::<Sha256>(dst, msg).unwrap();
+            let expected =
+                Bls12_381G1Affine::new(Fq::from_str(x).unwrap(), Fq::from_str(y).unwrap(), false);
+            assert_eq!(p.into_affine(), expected);
+        }
+    }
+
+    fn hash_to_curve_test<G: HashToCurve>()
+    where
+        G::BaseField: PrimeField,
+    {
+        let domain_sep = b"ginger-lib hash to curve test";
+        for i in 0..10u8 {
+            let msg = [i; 32];
+            let p = G::hash_to_curve::<Blake2s>(domain_sep, &msg).unwrap();
+            assert!(p.group_membership_test());
+            assert!(!ProjectiveCurve::is_zero(&p));
+
+            // The hash is deterministic, and depends on the domain separator.
+            assert_eq!(p, G::hash_to_curve::<Blake2s>(domain_sep, &msg).unwrap());
+            assert_ne!(p, G::hash_to_curve::<Blake2s>(b"another domain", &msg).unwrap());
+        }
+
+        // Small field elements, among them the exceptional case u = 0, are
+        // mapped to the curve as well.
+        for i in 0..10u64 {
+            let p = G::map_to_curve(&G::BaseField::from(i)).clear_cofactor();
+            assert!(p.group_membership_test());
+        }
+    }
+
+    #[test]
+    fn mnt4753_g1_hash_to_curve() {
+        hash_to_curve_test::<MNT4G1Projective>();
+    }
+
+    #[test]
+    fn mnt6753_g1_hash_to_curve() {
+        hash_to_curve_test::<MNT6G1Projective>();
+    }
+
+    #[test]
+    fn bls12_381_g1_hash_to_curve() {
+        hash_to_curve_test::<Bls12_381G1Projective>();
+    }
+
+    #[test]
+    fn jubjub_hash_to_curve() {
+        hash_to_curve_test::<JubJubProjective>();
+    }
+
+    #[test]
+    fn ed25519_hash_to_curve() {
+        hash_to_curve_test::<Ed25519Projective>();
+    }
+}
@@ -0,0 +1,178 @@
+//! Known-answer tests (KATs).
+//!
+//! A KAT file pins the serialized results of a fixed set of computations,
+//! e.g. field operations at fixed inputs, scalar multiplications of a
+//! generator, pairings of fixed points or hashes of fixed inputs. Each line
+//! of a KAT file has the form `<label> <hex>`, where `<hex>` is the `ToBytes`
+//! serialization of the value computed under `<label>`. Empty lines and lines
+//! starting with `#` are ignored.
+//!
+//! The files are checked at test time by the `test_kat` tests of the
+//! respective modules. After an intended change of an encoding or of a
+//! parameter set they are regenerated by running these tests with the
+//! `REGENERATE_KAT` environment variable set:
+//!
+//! ```text
+//! REGENERATE_KAT=1 cargo test kat
+//! ```
+use crate::{
+    bytes::ToBytes,
+    curves::{AffineCurve, PairingEngine, ProjectiveCurve},
+    fields::{Field, PrimeField, SquareRootField},
+};
+use std::fmt::Write as FmtWrite;
+
+/// The environment variable which, if set, makes `KnownAnswers::check`
+/// (re)write the KAT file instead of comparing against it.
+pub const REGENERATE_KAT: &str = "REGENERATE_KAT";
+
+/// The exponent used for the `pow` known answer of a field.
+const POW_EXP: [u64; 2] = [0x9e3779b97f4a7c15, 0xf39cc0605cedc834];
+
+/// An ordered list of labelled, serialized known answers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    answers: Vec<(String, String)>,
+}
+
+impl KnownAnswers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the serialization of `value` under `label`.
+    pub fn push<T: ToBytes>(&mut self, label: &str, value: &T) {
+        let mut bytes = vec![];
+        value.write(&mut bytes).unwrap();
+        let mut hex = String::with_capacity(2 * bytes.len());
+        for b in bytes.iter() {
+            write!(hex, "{:02x}", b).unwrap();
+        }
+        self.answers.push((label.to_owned(), hex));
+    }
+
+    /// Parses the contents of a KAT file.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut answers = vec![];
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(label), Some(hex), None) => {
+                    answers.push((label.to_owned(), hex.to_lowercase()))
+                },
+                _ => return Err(format!("malformed KAT line {}: {}", i + 1, line)),
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    /// Renders the answers in the KAT file format.
+    pub fn render(&self) -> String {
+        let mut out = String::from(
+            "# Known-answer tests, regenerate with `REGENERATE_KAT=1 cargo test kat`.\n",
+        );
+        for (label, hex) in self.answers.iter() {
+            writeln!(out, "{} {}", label, hex).unwrap();
+        }
+        out
+    }
+
+    /// Returns a description of every answer differing from `expected`,
+    /// including answers present in only one of the two lists.
+    pub fn mismatches(&self, expected: &Self) -> Vec<String> {
+        let mut mismatches = vec![];
+        for (label, hex) in self.answers.iter() {
+            match expected.answers.iter().find(|(l, _)| l == label) {
+                Some((_, e)) if e == hex => {},
+                Some((_, e)) => {
+                    mismatches.push(format!("{}: expected {}, computed {}", label, e, hex))
+                },
+                None => mismatches.push(format!("{}: no known answer", label)),
+            }
+        }
+        for (label, _) in expected.answers.iter() {
+            if !self.answers.iter().any(|(l, _)| l == label) {
+                mismatches.push(format!("{}: not computed", label));
+            }
+        }
+        mismatches
+    }
+
+    /// Compares the answers against the KAT file at `path`, or (re)writes the
+    /// file if `REGENERATE_KAT` is set.
+    ///
+    /// # Panics
+    ///
+    /// If the file can't be read or written, or if any answer differs.
+    pub fn check(&self, path: &str) {
+        if std::env::var_os(REGENERATE_KAT).is_some() {
+            std::fs::write(path, self.render())
+                .unwrap_or_else(|e| panic!("cannot write KAT file {}: {}", path, e));
+            return;
+        }
+        let contents = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("cannot read KAT file {}: {}", path, e));
+        let expected = Self::parse(&contents).unwrap_or_else(|e| panic!("{}: {}", path, e));
+        let mismatches = self.mismatches(&expected);
+        assert!(
+            mismatches.is_empty(),
+            "known answers differ from {}:\n{}",
+            path,
+            mismatches.join("\n")
+        );
+    }
+}
+
+/// Pushes the results of the basic field operations at fixed inputs, under
+/// labels starting with `prefix`.
+pub fn field_answers<F: PrimeField + SquareRootField>(answers: &mut KnownAnswers, prefix: &str) {
+    let a = F::from(7u64).inverse().unwrap();
+    let b = -F::from(11u64).square();
+    let c = a + &b;
+
+    answers.push(&format!("{}.a", prefix), &a);
+    answers.push(&format!("{}.b", prefix), &b);
+    answers.push(&format!("{}.add", prefix), &c);
+    answers.push(&format!("{}.sub", prefix), &(a - &b));
+    answers.push(&format!("{}.mul", prefix), &(a * &b));
+    answers.push(&format!("{}.square", prefix), &a.square());
+    answers.push(&format!("{}.double", prefix), &b.double());
+    answers.push(&format!("{}.inverse", prefix), &b.inverse().unwrap());
+    answers.push(&format!("{}.pow", prefix), &a.pow(POW_EXP));
+
+    // Of the two square roots, the smaller one is pinned.
+    let root = c.square().sqrt().unwrap();
+    answers.push(&format!("{}.sqrt", prefix), &std::cmp::min(root, -root));
+}
+
+/// Pushes the results of the basic group operations on the prime subgroup
+/// generator, under labels starting with `prefix`.
+pub fn curve_answers<G: AffineCurve>(answers: &mut KnownAnswers, prefix: &str) {
+    let g = G::prime_subgroup_generator();
+    let k = G::ScalarField::from(7u64).inverse().unwrap();
+    let kg = g.mul(k);
+
+    let mut kg_plus_g = kg;
+    kg_plus_g.add_assign_mixed(&g);
+
+    answers.push(&format!("{}.generator", prefix), &g);
+    answers.push(&format!("{}.neg", prefix), &-g);
+    answers.push(&format!("{}.double", prefix), &g.into_projective().double().into_affine());
+    answers.push(&format!("{}.mul", prefix), &kg.into_affine());
+    answers.push(&format!("{}.add", prefix), &kg_plus_g.into_affine());
+}
+
+/// Pushes the pairings of fixed multiples of the generators, under labels
+/// starting with `prefix`.
+pub fn pairing_answers<E: PairingEngine>(answers: &mut KnownAnswers, prefix: &str) {
+    let g1 = E::G1Affine::prime_subgroup_generator();
+    let g2 = E::G2Affine::prime_subgroup_generator();
+    let k = E::Fr::from(7u64).inverse().unwrap();
+
+    answers.push(&format!("{}.generators", prefix), &E::pairing(g1, g2));
+    answers.push(&format!("{}.mul", prefix), &E::pairing(g1.mul(k), g2));
+}
@@ -31,7 +31,7 @@
     private_in_public,
     unsafe_code
 )]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
 
 #[macro_use]
 extern crate derivative;
@@ -56,12 +56,21 @@ pub use self::fields::*;
 pub mod groups;
 pub use self::groups::*;
 
+pub mod kat;
+
+pub mod capabilities;
+
 mod rand;
 pub use self::rand::*;
 
 mod to_field_vec;
 pub use to_field_vec::ToConstraintField;
 
+#[cfg(any(feature = "parallel", feature = "fft"))]
+pub mod tuning;
+#[cfg(any(feature = "parallel", feature = "fft"))]
+pub use self::tuning::*;
+
 #[cfg(feature = "parallel")]
 pub mod msm;
 #[cfg(feature = "parallel")]
@@ -72,4 +81,9 @@ pub mod fft;
 #[cfg(feature = "fft")]
 pub use self::fft::*;
 
+#[cfg(feature = "hash_to_curve")]
+pub mod hash_to_curve;
+#[cfg(feature = "hash_to_curve")]
+pub use self::hash_to_curve::*;
+
 pub type Error = Box<dyn std::error::Error>;
\ No newline at end of file
@@ -0,0 +1,229 @@
+use crate::{
+    bytes::{FromBytes, ToBytes},
+    AffineCurve, BigInteger, FpParameters, PrimeField, ProjectiveCurve,
+};
+use rayon::prelude::*;
+use std::io::{self, Read, Result as IoResult, Write};
+
+/// The default number of teeth of the combs of a `CombTable`.
+pub const DEFAULT_COMB_TEETH: usize = 8;
+
+/// The default number of combs of a `CombTable`.
+pub const DEFAULT_NUM_COMBS: usize = 4;
+
+/// The largest number of teeth accepted when reading a `CombTable`, bounding the
+/// memory taken by the tables of untrusted inputs.
+pub const MAX_COMB_TEETH: usize = 20;
+
+/// The precomputed tables of the Lim-Lee comb method for the multiplication of a
+/// fixed base, e.g. a generator or a point of a commitment key, by scalars.
+///
+/// A scalar of `n` bits is split into `h` blocks of `a = ceil(n / h)` bits, the
+/// teeth of the comb, and each block into `v` sub-blocks of `b = ceil(a / v)`
+/// bits, one for each comb. Comb `j` holds, for each `u` in `0..2^h`, the point
+/// `2^(j * b) * sum_i u_i * 2^(i * a) * base`. A multiplication then takes `b - 1`
+/// doublings and `v * b` mixed additions, against `v * 2^h` points of storage.
+///
+/// The tables can be written to disk, so that services ship them instead of
+/// computing them at startup. As for the points themselves, reading the tables
+/// checks neither that the points are on the curve nor that they are multiples
+/// of the expected base: tables must come from a trusted source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CombTable<G: AffineCurve> {
+    teeth:     usize,
+    num_combs: usize,
+    num_bits:  usize,
+    /// `table[j * 2^teeth + u]` is the entry `u` of comb `j`.
+    table:     Vec<G>,
+}
+
+impl<G: AffineCurve> CombTable<G> {
+    /// Precomputes the tables of `base` with the default parameters.
+    pub fn new(base: &G) -> Self {
+        Self::with_parameters(base, DEFAULT_COMB_TEETH, DEFAULT_NUM_COMBS)
+    }
+
+    /// Precomputes the tables of `base` for `num_combs` combs of `teeth` teeth.
+    pub fn with_parameters(base: &G, teeth: usize, num_combs: usize) -> Self {
+        assert!(teeth > 0 && teeth <= MAX_COMB_TEETH);
+        let num_bits = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        let block_len = (num_bits + teeth - 1) / teeth;
+        assert!(num_combs > 0 && num_combs <= block_len);
+        let sub_block_len = (block_len + num_combs - 1) / num_combs;
+
+        // The teeth 2^(i * a) * base
+        let mut teeth_points = Vec::with_capacity(teeth);
+        let mut g = base.into_projective();
+        for _ in 0..teeth {
+            teeth_points.push(g);
+            for _ in 0..block_len {
+                g.double_in_place();
+            }
+        }
+
+        // Each entry of the first comb adds the highest tooth to a previous one
+        let mut first_comb = vec![G::Projective::zero(); 1 << teeth];
+        for u in 1..(1 << teeth) {
+            let top = (0..teeth).rev().find(|i| u & (1 << i) != 0).unwrap();
+            first_comb[u] = first_comb[u ^ (1 << top)] + &teeth_points[top];
+        }
+
+        // The other combs are shifted by multiples of b bits
+        let mut table = first_comb;
+        let table_len = table.len();
+        for j in 1..num_combs {
+            let previous_comb = table[(j - 1) * table_len..].to_vec();
+            table.par_extend(previous_comb.into_par_iter().map(|mut g| {
+                for _ in 0..sub_block_len {
+                    g.double_in_place();
+                }
+                g
+            }));
+        }
+        G::Projective::batch_normalization(&mut table);
+
+        Self {
+            teeth,
+            num_combs,
+            num_bits,
+            table: table.into_iter().map(|g| g.into_affine()).collect(),
+        }
+    }
+
+    pub fn teeth(&self) -> usize {
+        self.teeth
+    }
+
+    pub fn num_combs(&self) -> usize {
+        self.num_combs
+    }
+
+    /// Returns the base of the tables.
+    pub fn base(&self) -> G {
+        self.table[1]
+    }
+
+    /// Computes `scalar * base`.
+    pub fn mul(&self, scalar: &G::ScalarField) -> G::Projective {
+        let scalar = scalar.into_repr();
+        let block_len = (self.num_bits + self.teeth - 1) / self.teeth;
+        let sub_block_len = (block_len + self.num_combs - 1) / self.num_combs;
+        let comb_len = 1 << self.teeth;
+
+        let mut res = G::Projective::zero();
+        for k in (0..sub_block_len).rev() {
+            res.double_in_place();
+            for j in 0..self.num_combs {
+                let offset = j * sub_block_len + k;
+                if offset >= block_len {
+                    continue;
+                }
+                let mut u = 0usize;
+                for i in 0..self.teeth {
+                    let bit = i * block_len + offset;
+                    if bit < self.num_bits && scalar.get_bit(bit) {
+                        u |= 1 << i;
+                    }
+                }
+                if u != 0 {
+                    res.add_assign_mixed(&self.table[j * comb_len + u]);
+                }
+            }
+        }
+        res
+    }
+
+    /// Computes `scalar * base` for each of `scalars`, in parallel.
+    pub fn batch_mul(&self, scalars: &[G::ScalarField]) -> Vec<G::Projective> {
+        scalars.par_iter().map(|s| self.mul(s)).collect()
+    }
+}
+
+impl<G: AffineCurve> ToBytes for CombTable<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.teeth as u32).write(&mut writer)?;
+        (self.num_combs as u32).write(&mut writer)?;
+        (self.num_bits as u32).write(&mut writer)?;
+        for g in self.table.iter() {
+            g.write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<G: AffineCurve> FromBytes for CombTable<G> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let teeth = u32::read(&mut reader)? as usize;
+        let num_combs = u32::read(&mut reader)? as usize;
+        let num_bits = u32::read(&mut reader)? as usize;
+
+        if num_bits != <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "comb tables for a different scalar field",
+            ));
+        }
+        if teeth == 0
+            || teeth > MAX_COMB_TEETH
+            || num_combs == 0
+            || num_combs > (num_bits + teeth - 1) / teeth
+        {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid comb parameters"));
+        }
+
+        let table = (0..num_combs << teeth)
+            .map(|_| G::read(&mut reader))
+            .collect::<IoResult<Vec<_>>>()?;
+
+        Ok(Self { teeth, num_combs, num_bits, table })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::curves::bls12_381::{G1Affine, G2Affine};
+    use crate::fields::bls12_381::Fr;
+    use crate::UniformRand;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn test_comb<G: AffineCurve<ScalarField = Fr>>(teeth: usize, num_combs: usize) {
+        let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+        let base = G::prime_subgroup_generator().mul(Fr::rand(&mut rng)).into_affine();
+        let comb = CombTable::with_parameters(&base, teeth, num_combs);
+        assert_eq!(comb.base(), base);
+
+        let scalars = (0..20)
+            .map(|_| Fr::rand(&mut rng))
+            .chain(vec![Fr::from(0u64), Fr::from(1u64), -Fr::from(1u64)])
+            .collect::<Vec<_>>();
+        for (s, res) in scalars.iter().zip(comb.batch_mul(&scalars)) {
+            assert_eq!(res.into_affine(), base.mul(*s).into_affine());
+        }
+    }
+
+    #[test]
+    fn test_comb_bls12_381() {
+        test_comb::<G1Affine>(DEFAULT_COMB_TEETH, DEFAULT_NUM_COMBS);
+        test_comb::<G1Affine>(1, 1);
+        test_comb::<G1Affine>(5, 3);
+        test_comb::<G2Affine>(6, 7);
+    }
+
+    #[test]
+    fn test_comb_serialization() {
+        let comb = CombTable::with_parameters(&G1Affine::prime_subgroup_generator(), 4, 2);
+
+        let bytes = to_bytes!(comb).unwrap();
+        assert_eq!(CombTable::<G1Affine>::read(bytes.as_slice()).unwrap(), comb);
+
+        assert!(CombTable::<G1Affine>::read(&bytes[..bytes.len() - 1]).is_err());
+        assert!(CombTable::<G2Affine>::read(bytes.as_slice()).is_err());
+
+        let mut bad_teeth = bytes.clone();
+        bad_teeth[0] = 0;
+        assert!(CombTable::<G1Affine>::read(bad_teeth.as_slice()).is_err());
+    }
+}
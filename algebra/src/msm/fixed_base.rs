@@ -1,15 +1,13 @@
-use crate::{BigInteger, FpParameters, PrimeField, ProjectiveCurve};
+use crate::{tuning::tuning_strategy, AffineCurve, BigInteger, FpParameters, PrimeField, ProjectiveCurve};
 use rayon::prelude::*;
 
 pub struct FixedBaseMSM;
 
 impl FixedBaseMSM {
+    /// Returns the window size of the tables for `num_scalars` scalars, as
+    /// chosen by the current `TuningStrategy`.
     pub fn get_mul_window_size(num_scalars: usize) -> usize {
-        if num_scalars < 32 {
-            3
-        } else {
-            (f64::from(num_scalars as u32)).ln().ceil() as usize
-        }
+        tuning_strategy().fixed_base_msm_window_size(num_scalars)
     }
 
     pub fn get_window_table<T: ProjectiveCurve>(
@@ -78,3 +76,119 @@ impl FixedBaseMSM {
         v.par_iter().map(|e| Self::windowed_mul::<T>(outerc, window, table, e)).collect::<Vec<_>>()
     }
 }
+
+/// The window tables for the multiplication of a fixed base, e.g. a generator,
+/// by scalars. For windows of `w` bits, the table of window `j` holds the
+/// multiples `u * 2^(j * w) * base` for `u` in `0..2^w`, so that a
+/// multiplication takes one mixed addition per window and no doubling.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixedBaseMul<G: AffineCurve> {
+    window:      usize,
+    num_windows: usize,
+    /// `table[j * 2^window + u] = u * 2^(j * window) * base`
+    table:       Vec<G>,
+}
+
+impl<G: AffineCurve> FixedBaseMul<G> {
+    /// Precomputes the tables of `base` for windows of `window` bits.
+    pub fn new(base: &G, window: usize) -> Self {
+        assert!(window > 0 && window < 32);
+        let num_bits = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        let num_windows = (num_bits + window - 1) / window;
+
+        // The bases 2^(j * w) * base of the windows
+        let mut window_bases = Vec::with_capacity(num_windows);
+        let mut g = base.into_projective();
+        for _ in 0..num_windows {
+            window_bases.push(g);
+            for _ in 0..window {
+                g.double_in_place();
+            }
+        }
+
+        let table = window_bases
+            .par_iter()
+            .flat_map(|g| {
+                let mut multiples = Vec::with_capacity(1 << window);
+                let mut multiple = G::Projective::zero();
+                for _ in 0..1 << window {
+                    multiples.push(multiple);
+                    multiple += g;
+                }
+                G::Projective::batch_normalization(&mut multiples);
+                multiples.into_iter().map(|g| g.into_affine()).collect::<Vec<_>>()
+            })
+            .collect();
+
+        Self { window, num_windows, table }
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the base of the tables.
+    pub fn base(&self) -> G {
+        self.table[1]
+    }
+
+    /// Computes `scalar * base`.
+    pub fn mul(&self, scalar: &G::ScalarField) -> G::Projective {
+        let scalar = scalar.into_repr();
+        let num_bits = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+
+        let mut res = G::Projective::zero();
+        for j in 0..self.num_windows {
+            let mut u = 0usize;
+            for i in 0..self.window {
+                let bit = j * self.window + i;
+                if bit < num_bits && scalar.get_bit(bit) {
+                    u |= 1 << i;
+                }
+            }
+            if u != 0 {
+                res.add_assign_mixed(&self.table[(j << self.window) + u]);
+            }
+        }
+        res
+    }
+
+    /// Computes `scalar * base` for each of `scalars`, in parallel.
+    pub fn batch_mul(&self, scalars: &[G::ScalarField]) -> Vec<G::Projective> {
+        scalars.par_iter().map(|s| self.mul(s)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::curves::bls12_381::{G1Affine, G2Affine};
+    use crate::fields::bls12_381::Fr;
+    use crate::UniformRand;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn test_fixed_base_mul<G: AffineCurve<ScalarField = Fr>>(window: usize) {
+        let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+        let base = G::prime_subgroup_generator().mul(Fr::rand(&mut rng)).into_affine();
+        let table = FixedBaseMul::new(&base, window);
+        assert_eq!(table.base(), base);
+
+        let scalars = (0..20)
+            .map(|_| Fr::rand(&mut rng))
+            .chain(vec![Fr::from(0u64), Fr::from(1u64), -Fr::from(1u64)])
+            .collect::<Vec<_>>();
+        for (s, res) in scalars.iter().zip(table.batch_mul(&scalars)) {
+            assert_eq!(res.into_affine(), base.mul(*s).into_affine());
+        }
+    }
+
+    #[test]
+    fn test_fixed_base_mul_bls12_381() {
+        test_fixed_base_mul::<G1Affine>(1);
+        test_fixed_base_mul::<G1Affine>(4);
+        test_fixed_base_mul::<G1Affine>(7);
+        test_fixed_base_mul::<G2Affine>(5);
+    }
+}
@@ -1,4 +1,8 @@
+mod comb;
 mod fixed_base;
+mod precomp;
 mod variable_base;
+pub use comb::*;
 pub use fixed_base::*;
+pub use precomp::*;
 pub use variable_base::*;
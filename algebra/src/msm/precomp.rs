@@ -0,0 +1,169 @@
+use crate::{
+    tuning::tuning_strategy, AffineCurve, BigInteger, FpParameters, PrimeField,
+    ProjectiveCurve,
+};
+use rayon::prelude::*;
+
+/// Precomputed window tables for variable-base multi-scalar multiplication
+/// over a fixed set of bases, as obtained by `precompute`.
+///
+/// For every base `P` the table holds the multiples `2^(j * c) * P` for each
+/// window `j` of the scalar bit length, where `c` is the window size. A
+/// multi-scalar multiplication then accumulates the windows of all scalars
+/// into a single set of buckets, and needs neither doublings nor the
+/// recombination of per-window sums. This pays off whenever the same bases
+/// (e.g. those of a CRS) are used for many multi-scalar multiplications.
+pub struct MsmPrecomp<G: AffineCurve> {
+    window: usize,
+    num_windows: usize,
+    /// `table[i * num_windows + j] = 2^(j * window) * bases[i]`
+    table: Vec<G>,
+}
+
+/// Precomputes the window tables of `bases`, using a window size suited
+/// to multi-scalar multiplications of length `bases.len()`.
+pub fn precompute<G: AffineCurve>(bases: &[G]) -> MsmPrecomp<G> {
+    MsmPrecomp::with_window_size(bases, MsmPrecomp::<G>::get_window_size(bases.len()))
+}
+
+impl<G: AffineCurve> MsmPrecomp<G> {
+    /// Returns the window size of the tables for `num_scalars` bases, as
+    /// chosen by the current `TuningStrategy`.
+    pub fn get_window_size(num_scalars: usize) -> usize {
+        tuning_strategy().precomputed_msm_window_size(num_scalars)
+    }
+
+    /// Precomputes the window tables of `bases` for windows of `window` bits.
+    pub fn with_window_size(bases: &[G], window: usize) -> Self {
+        assert!(window > 0 && window < 64);
+
+        let num_bits =
+            <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        let num_windows = (num_bits + window - 1) / window;
+
+        let table = bases
+            .par_iter()
+            .flat_map(|base| {
+                let mut multiples = Vec::with_capacity(num_windows);
+                let mut g = base.into_projective();
+                for _ in 0..num_windows {
+                    multiples.push(g);
+                    for _ in 0..window {
+                        g.double_in_place();
+                    }
+                }
+                G::Projective::batch_normalization(&mut multiples);
+                multiples.into_iter().map(|g| g.into_affine()).collect::<Vec<_>>()
+            })
+            .collect();
+
+        Self {
+            window,
+            num_windows,
+            table,
+        }
+    }
+
+    /// Returns the number of bases covered by the tables.
+    pub fn len(&self) -> usize {
+        self.table.len() / self.num_windows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    pub fn window_size(&self) -> usize {
+        self.window
+    }
+
+    /// Computes `sum_i scalars[i] * bases[i]`. As for `VariableBaseMSM`,
+    /// surplus bases or scalars are ignored.
+    pub fn mul(
+        &self,
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+    ) -> G::Projective {
+        let c = self.window;
+        let zero = G::Projective::zero();
+
+        let num_scalars = std::cmp::min(scalars.len(), self.len());
+        if num_scalars == 0 {
+            return zero;
+        }
+        let chunk_size = (num_scalars + rayon::current_num_threads() - 1) / rayon::current_num_threads();
+
+        scalars[..num_scalars]
+            .par_chunks(chunk_size)
+            .zip(self.table.par_chunks(chunk_size * self.num_windows))
+            .map(|(scalars, table)| {
+                // We don't need the "zero" bucket, so we only have 2^c - 1 buckets
+                let mut buckets = vec![zero; (1 << c) - 1];
+                scalars
+                    .iter()
+                    .zip(table.chunks(self.num_windows))
+                    .filter(|(s, _)| !s.is_zero())
+                    .for_each(|(&scalar, multiples)| {
+                        let mut scalar = scalar;
+                        for base in multiples {
+                            // The lowest `c` bits of what is left of the scalar
+                            // select the bucket of `2^(j * c) * base`.
+                            let digit = scalar.as_ref()[0] % (1 << c);
+                            if digit != 0 {
+                                buckets[(digit - 1) as usize].add_assign_mixed(base);
+                            }
+                            scalar.divn(c as u32);
+                        }
+                    });
+                G::Projective::batch_normalization(&mut buckets);
+
+                let mut res = zero;
+                let mut running_sum = zero;
+                for b in buckets.into_iter().map(|g| g.into_affine()).rev() {
+                    running_sum.add_assign_mixed(&b);
+                    res += &running_sum;
+                }
+                res
+            })
+            .reduce(|| zero, |a, b| a + &b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::curves::bls12_381::G1Projective;
+    use crate::fields::bls12_381::Fr;
+    use crate::msm::VariableBaseMSM;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use crate::UniformRand;
+
+    #[test]
+    fn test_with_bls12() {
+        const SAMPLES: usize = 1 << 10;
+
+        let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+        let v = (0..SAMPLES)
+            .map(|_| Fr::rand(&mut rng).into_repr())
+            .collect::<Vec<_>>();
+        let g = (0..SAMPLES)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+
+        let precomp = precompute(g.as_slice());
+        assert_eq!(precomp.len(), SAMPLES);
+
+        let expected = VariableBaseMSM::multi_scalar_mul(g.as_slice(), v.as_slice());
+
+        // The tables are reusable across multiplications.
+        assert_eq!(precomp.mul(v.as_slice()).into_affine(), expected.into_affine());
+        assert_eq!(precomp.mul(v.as_slice()).into_affine(), expected.into_affine());
+
+        // Fewer scalars than bases, and a window size not dividing the
+        // scalar bit length.
+        let precomp = MsmPrecomp::with_window_size(g.as_slice(), 7);
+        let expected = VariableBaseMSM::multi_scalar_mul(g.as_slice(), &v[..SAMPLES - 1]);
+        assert_eq!(precomp.mul(&v[..SAMPLES - 1]).into_affine(), expected.into_affine());
+    }
+}
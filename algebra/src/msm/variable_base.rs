@@ -1,22 +1,50 @@
 use crate::{
+    tuning::{tuning_strategy, DEFAULT_MAX_MSM_WINDOW_SIZE},
     AffineCurve, BigInteger, Field, FpParameters, PrimeField,
     ProjectiveCurve,
 };
 use rayon::prelude::*;
+use std::io::{Read, Result as IoResult};
+#[cfg(feature = "mmap")]
+use std::{
+    fs::File,
+    io::{Error as IoError, ErrorKind},
+    marker::PhantomData,
+};
 
 pub struct VariableBaseMSM;
 
 impl VariableBaseMSM {
+    /// The largest window size chosen by the default `get_window_size`,
+    /// bounding the memory taken by the buckets.
+    pub const MAX_WINDOW_SIZE: usize = DEFAULT_MAX_MSM_WINDOW_SIZE;
+
+    /// Returns the window size of a multi-scalar multiplication of
+    /// `num_scalars` scalars of `num_bits` bits, as chosen by the current
+    /// `TuningStrategy`.
+    pub fn get_window_size(num_scalars: usize, num_bits: usize) -> usize {
+        let c = tuning_strategy().msm_window_size(num_scalars, num_bits, rayon::current_num_threads());
+        assert!(c > 0 && c < 64);
+        c
+    }
+
+    /// Returns `by * g` for a small integer `by`.
+    fn mul_small<G: ProjectiveCurve>(g: &G, by: usize) -> G {
+        let mut res = G::zero();
+        for i in (0..64 - (by as u64).leading_zeros()).rev() {
+            res.double_in_place();
+            if (by >> i) & 1 == 1 {
+                res += g;
+            }
+        }
+        res
+    }
+
     fn msm_inner<G: AffineCurve>(
         bases: &[G],
         scalars: &[<G::ScalarField as PrimeField>::BigInt],
+        c: usize,
     ) -> G::Projective {
-        let c = if scalars.len() < 32 {
-            3
-        } else {
-            (2.0 / 3.0 * (f64::from(scalars.len() as u32)).log2() + 2.0).ceil() as usize
-        };
-
         let num_bits =
             <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
         let fr_one = G::ScalarField::one().into_repr();
@@ -24,19 +52,37 @@ impl VariableBaseMSM {
         let zero = G::zero().into_projective();
         let window_starts: Vec<_> = (0..num_bits).step_by(c).collect();
 
+        // We don't need the "zero" bucket, so we only have 2^c - 1 buckets.
+        // If there are fewer windows than threads, the buckets of each
+        // window are split into ranges processed in parallel as well.
+        let num_buckets = (1 << c) - 1;
+        let num_parts = std::cmp::min(
+            std::cmp::max(1, rayon::current_num_threads() / window_starts.len()),
+            num_buckets,
+        );
+        let part_size = (num_buckets + num_parts - 1) / num_parts;
+        let tasks: Vec<_> = window_starts
+            .iter()
+            .flat_map(|&w_start| {
+                (0..num_buckets)
+                    .step_by(part_size)
+                    .map(move |lo| (w_start, lo, std::cmp::min(lo + part_size, num_buckets)))
+            })
+            .collect();
+
         // Each window is of size `c`.
         // We divide up the bits 0..num_bits into windows of size `c`, and
-        // in parallel process each such window.
-        let window_sums: Vec<_> = window_starts
+        // in parallel process each range of buckets of each such window.
+        let part_sums: Vec<_> = tasks
             .into_par_iter()
-            .map(|w_start| {
+            .map(|(w_start, lo, hi)| {
                 let mut res = zero;
-                // We don't need the "zero" bucket, so we only have 2^c - 1 buckets
-                let mut buckets = vec![zero; (1 << c) - 1];
+                // The bucket of digit `d` is buckets[d - 1 - lo].
+                let mut buckets = vec![zero; hi - lo];
                 scalars.iter().zip(bases).filter(|(s, _)| !s.is_zero()).for_each(|(&scalar, base)|  {
                     if scalar == fr_one {
                         // We only process unit scalars once in the first window.
-                        if w_start == 0 {
+                        if w_start == 0 && lo == 0 {
                             res.add_assign_mixed(base);
                         }
                     } else {
@@ -47,28 +93,35 @@ impl VariableBaseMSM {
                         scalar.divn(w_start as u32);
 
                         // We mod the remaining bits by the window size.
-                        let scalar = scalar.as_ref()[0] % (1 << c);
+                        let scalar = (scalar.as_ref()[0] % (1 << c)) as usize;
 
-                        // If the scalar is non-zero, we update the corresponding
-                        // bucket.
-                        // (Recall that `buckets` doesn't have a zero bucket.)
-                        if scalar != 0 {
-                            buckets[(scalar - 1) as usize].add_assign_mixed(&base);
+                        // If the scalar falls into our range of buckets, we
+                        // update the corresponding bucket.
+                        if scalar > lo && scalar <= hi {
+                            buckets[scalar - 1 - lo].add_assign_mixed(&base);
                         }
                     }
                 });
                 G::Projective::batch_normalization(&mut buckets);
 
+                // The running sums yield sum_d (d - lo) * bucket_d, the
+                // remaining lo * sum_d bucket_d is added afterwards.
                 let mut running_sum = G::Projective::zero();
                 for b in buckets.into_iter().map(|g| g.into_affine()).rev() {
                     running_sum.add_assign_mixed(&b);
                     res += &running_sum;
                 }
+                res += &Self::mul_small(&running_sum, lo);
 
-                res
+                (w_start, res)
             })
             .collect();
 
+        let mut window_sums = vec![zero; window_starts.len()];
+        for (w_start, sum) in part_sums {
+            window_sums[w_start / c] += &sum;
+        }
+
         // We store the sum for the lowest window.
         let lowest = window_sums.first().unwrap();
 
@@ -86,14 +139,151 @@ impl VariableBaseMSM {
         bases: &[G],
         scalars: &[<G::ScalarField as PrimeField>::BigInt],
     ) -> G::Projective {
-        Self::msm_inner(bases, scalars)
+        let num_bits =
+            <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        let c = Self::get_window_size(std::cmp::min(bases.len(), scalars.len()), num_bits);
+        Self::msm_inner(bases, scalars, c)
+    }
+
+    /// As `multi_scalar_mul`, but with windows of `c` bits instead of the
+    /// window size chosen from the input length.
+    pub fn multi_scalar_mul_with_window_size<G: AffineCurve>(
+        bases: &[G],
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+        c: usize,
+    ) -> G::Projective {
+        assert!(c > 0 && c < 64);
+        Self::msm_inner(bases, scalars, c)
+    }
+
+    /// As `multi_scalar_mul`, but consuming the bases from an iterator in
+    /// chunks of `chunk_size`, so that at no time more than `chunk_size`
+    /// bases are held in memory. Suited for bases which don't fit into memory,
+    /// e.g. those read from a large CRS file. Surplus bases or scalars are
+    /// ignored.
+    pub fn multi_scalar_mul_streamed<G, I>(
+        bases: I,
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+        chunk_size: usize,
+    ) -> G::Projective
+        where
+            G: AffineCurve,
+            I: IntoIterator<Item = G>,
+    {
+        assert!(chunk_size > 0);
+
+        let mut bases = bases.into_iter();
+        let mut res = G::Projective::zero();
+        let mut chunk = Vec::with_capacity(chunk_size);
+        for scalars_chunk in scalars.chunks(chunk_size) {
+            chunk.clear();
+            chunk.extend(bases.by_ref().take(scalars_chunk.len()));
+            if chunk.is_empty() {
+                break;
+            }
+            res += &Self::multi_scalar_mul(&chunk, scalars_chunk);
+        }
+        res
+    }
+
+    /// As `multi_scalar_mul_streamed`, reading the bases in their `ToBytes`
+    /// serialization from `reader`. Fails if fewer than `scalars.len()` bases
+    /// can be read.
+    pub fn multi_scalar_mul_from_reader<G: AffineCurve, R: Read>(
+        mut reader: R,
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+        chunk_size: usize,
+    ) -> IoResult<G::Projective> {
+        assert!(chunk_size > 0);
+
+        let mut res = G::Projective::zero();
+        let mut chunk = Vec::with_capacity(chunk_size);
+        for scalars_chunk in scalars.chunks(chunk_size) {
+            chunk.clear();
+            for _ in 0..scalars_chunk.len() {
+                chunk.push(G::read(&mut reader)?);
+            }
+            res += &Self::multi_scalar_mul(&chunk, scalars_chunk);
+        }
+        Ok(res)
+    }
+
+    /// As `multi_scalar_mul_from_reader`, reading the bases from their mapping
+    /// in memory: only the pages of the current chunk need to be resident, and
+    /// the operating system evicts them once they are consumed. Fails if fewer
+    /// than `scalars.len()` bases are mapped.
+    #[cfg(feature = "mmap")]
+    pub fn multi_scalar_mul_mapped<G: AffineCurve>(
+        bases: &MappedBases<G>,
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+        chunk_size: usize,
+    ) -> IoResult<G::Projective> {
+        Self::multi_scalar_mul_from_reader::<G, _>(bases.as_bytes(), scalars, chunk_size)
+    }
+}
+
+/// Affine bases in their `ToBytes` serialization, mapped read-only in memory
+/// from a file, e.g. the bases of a CRS larger than the memory.
+#[cfg(feature = "mmap")]
+pub struct MappedBases<G: AffineCurve> {
+    mmap:      memmap::Mmap,
+    offset:    usize,
+    len:       usize,
+    base_size: usize,
+    _base:     PhantomData<G>,
+}
+
+#[cfg(feature = "mmap")]
+impl<G: AffineCurve> MappedBases<G> {
+    /// Maps `file` in memory, holding `len` bases starting at byte `offset`.
+    /// Fails if the file is too short. The file must not be modified, nor
+    /// truncated, as long as it is mapped.
+    #[allow(unsafe_code)]
+    pub fn open(file: &File, offset: usize, len: usize) -> IoResult<Self> {
+        let mut base = vec![];
+        G::zero().write(&mut base)?;
+        let base_size = base.len();
+
+        let end = len
+            .checked_mul(base_size)
+            .and_then(|size| size.checked_add(offset))
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "too many bases"))?;
+        if (file.metadata()?.len() as usize) < end {
+            return Err(IoError::new(ErrorKind::UnexpectedEof, "truncated bases"));
+        }
+
+        // Safe as long as the file is not modified while mapped, as required above
+        let mmap = unsafe { memmap::Mmap::map(file)? };
+        Ok(Self { mmap, offset, len, base_size, _base: PhantomData })
+    }
+
+    /// Returns the number of mapped bases.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reads the base at `index`.
+    pub fn get(&self, index: usize) -> IoResult<G> {
+        if index >= self.len {
+            return Err(IoError::new(ErrorKind::InvalidInput, "base index out of range"));
+        }
+        G::read(&self.as_bytes()[index * self.base_size..])
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.mmap[self.offset..self.offset + self.len * self.base_size]
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::curves::bls12_381::G1Projective;
+    use crate::curves::bls12_381::{G1Affine, G1Projective};
+    use crate::ToBytes;
     use crate::fields::bls12_381::Fr;
     use rand::SeedableRng;
     use rand_xorshift::XorShiftRng;
@@ -106,7 +296,7 @@ mod test {
         let mut acc = G::Projective::zero();
 
         for (base, scalar) in bases.iter().zip(scalars.iter()) {
-            acc += &base.mul(*scalar);
+            acc += &base.mul(G::ScalarField::from_repr(*scalar));
         }
         acc
     }
@@ -149,4 +339,115 @@ mod test {
 
         assert_eq!(naive.into_affine(), fast.into_affine());
     }
+
+    #[test]
+    fn test_with_bls12_window_sizes() {
+        const SAMPLES: usize = 1 << 6;
+
+        let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+        let mut v = (0..SAMPLES)
+            .map(|_| Fr::rand(&mut rng).into_repr())
+            .collect::<Vec<_>>();
+        // Unit and zero scalars are special cased.
+        v[0] = Fr::one().into_repr();
+        v[1] = Fr::zero().into_repr();
+        let g = (0..SAMPLES)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+
+        let naive = naive_var_base_msm(g.as_slice(), v.as_slice()).into_affine();
+        for &c in &[1, 2, 5, 8, 13, VariableBaseMSM::MAX_WINDOW_SIZE] {
+            let fast = VariableBaseMSM::multi_scalar_mul_with_window_size(g.as_slice(), v.as_slice(), c);
+            assert_eq!(naive, fast.into_affine());
+        }
+    }
+
+    #[test]
+    fn test_with_bls12_streamed() {
+        const SAMPLES: usize = 1 << 8;
+
+        let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+        let v = (0..SAMPLES)
+            .map(|_| Fr::rand(&mut rng).into_repr())
+            .collect::<Vec<_>>();
+        let g = (0..SAMPLES)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+
+        let expected = VariableBaseMSM::multi_scalar_mul(g.as_slice(), v.as_slice()).into_affine();
+
+        let mut g_bytes = vec![];
+        for base in g.iter() {
+            base.write(&mut g_bytes).unwrap();
+        }
+
+        for &chunk_size in &[1, 37, SAMPLES, 2 * SAMPLES] {
+            let streamed = VariableBaseMSM::multi_scalar_mul_streamed(g.iter().cloned(), v.as_slice(), chunk_size);
+            assert_eq!(expected, streamed.into_affine());
+
+            let read = VariableBaseMSM::multi_scalar_mul_from_reader::<G1Affine, _>(
+                g_bytes.as_slice(),
+                v.as_slice(),
+                chunk_size,
+            ).unwrap();
+            assert_eq!(expected, read.into_affine());
+        }
+
+        // Surplus scalars are ignored when streaming, but not when reading.
+        let streamed = VariableBaseMSM::multi_scalar_mul_streamed(g[..SAMPLES - 1].iter().cloned(), v.as_slice(), 16);
+        let expected = VariableBaseMSM::multi_scalar_mul(&g[..SAMPLES - 1], v.as_slice());
+        assert_eq!(expected.into_affine(), streamed.into_affine());
+        assert!(VariableBaseMSM::multi_scalar_mul_from_reader::<G1Affine, _>(
+            &g_bytes[..g_bytes.len() - 1],
+            v.as_slice(),
+            16,
+        ).is_err());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_with_bls12_mapped() {
+        use std::fs::{self, File};
+
+        const SAMPLES: usize = 1 << 8;
+        const OFFSET: usize = 13;
+
+        let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+        let v = (0..SAMPLES)
+            .map(|_| Fr::rand(&mut rng).into_repr())
+            .collect::<Vec<_>>();
+        let g = (0..SAMPLES)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+
+        let expected = VariableBaseMSM::multi_scalar_mul(g.as_slice(), v.as_slice()).into_affine();
+
+        // The bases follow some header in the file
+        let mut g_bytes = vec![0u8; OFFSET];
+        for base in g.iter() {
+            base.write(&mut g_bytes).unwrap();
+        }
+        let path = std::env::temp_dir().join(format!("ginger-mapped-bases-{}", std::process::id()));
+        fs::write(&path, &g_bytes).unwrap();
+        let file = File::open(&path).unwrap();
+
+        let bases = MappedBases::<G1Affine>::open(&file, OFFSET, SAMPLES).unwrap();
+        assert_eq!(bases.len(), SAMPLES);
+        assert_eq!(bases.get(SAMPLES - 1).unwrap(), g[SAMPLES - 1]);
+        assert!(bases.get(SAMPLES).is_err());
+        for &chunk_size in &[1, 37, SAMPLES, 2 * SAMPLES] {
+            let mapped = VariableBaseMSM::multi_scalar_mul_mapped(&bases, v.as_slice(), chunk_size).unwrap();
+            assert_eq!(expected, mapped.into_affine());
+        }
+
+        // Surplus scalars are rejected, as are files too short for the bases.
+        let bases = MappedBases::<G1Affine>::open(&file, OFFSET, SAMPLES - 1).unwrap();
+        assert!(VariableBaseMSM::multi_scalar_mul_mapped(&bases, v.as_slice(), 16).is_err());
+        assert!(MappedBases::<G1Affine>::open(&file, OFFSET + 1, SAMPLES).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
 }
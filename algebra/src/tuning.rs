@@ -0,0 +1,259 @@
+//! The heuristics choosing the window sizes of multi-scalar multiplications and
+//! the splitting of FFTs into parallel tasks. The defaults are tuned on x86_64
+//! machines with a moderate number of cores; other architectures may be better
+//! served by a `TuningStrategy` of their own, installed by `set_tuning_strategy`.
+
+use lazy_static::lazy_static;
+use std::sync::{Arc, RwLock};
+
+/// The log size from which on FFTs use the four-step algorithm by default.
+pub const DEFAULT_FOUR_STEP_FFT_MIN_LOG_SIZE: u32 = 14;
+
+/// The largest window size chosen by default for variable base multi-scalar
+/// multiplications, bounding the memory taken by the buckets.
+pub const DEFAULT_MAX_MSM_WINDOW_SIZE: usize = 16;
+
+/// A set of heuristics for the parameters of the parallel algorithms of the
+/// crate. Each method defaults to the built-in heuristic, so that implementors
+/// only override the ones they want to change. The returned window sizes must be
+/// in `1..64`.
+pub trait TuningStrategy: Send + Sync {
+    /// Returns the window size of a variable base multi-scalar multiplication of
+    /// `num_scalars` scalars of `num_bits` bits, run on `num_threads` threads.
+    /// By default, the one minimizing the number of group additions, i.e.
+    /// `ceil(num_bits / c) * (num_scalars + 2^(c + 1))` for window size `c`.
+    fn msm_window_size(&self, num_scalars: usize, num_bits: usize, _num_threads: usize) -> usize {
+        let cost = |c: usize| ((num_bits + c - 1) / c) * (num_scalars + (1 << (c + 1)));
+        (2..=DEFAULT_MAX_MSM_WINDOW_SIZE)
+            .min_by_key(|&c| cost(c))
+            .unwrap()
+    }
+
+    /// Returns the window size of the tables of a fixed base multi-scalar
+    /// multiplication of `num_scalars` scalars.
+    fn fixed_base_msm_window_size(&self, num_scalars: usize) -> usize {
+        if num_scalars < 32 {
+            3
+        } else {
+            ln_ceil(num_scalars)
+        }
+    }
+
+    /// Returns the window size of the tables precomputed for a variable base
+    /// multi-scalar multiplication with `num_scalars` fixed bases.
+    fn precomputed_msm_window_size(&self, num_scalars: usize) -> usize {
+        if num_scalars < 32 {
+            3
+        } else {
+            // ceil(2 / 3 * log2(n) + 2) = 2 + ceil(log2(n^2) / 3)
+            2 + (log2_ceil_square(num_scalars as u64) as usize + 2) / 3
+        }
+    }
+
+    /// Returns the number of elements processed by each parallel task of an
+    /// element-wise pass over `num_elements` elements, run on `num_threads` threads.
+    fn fft_chunk_size(&self, num_elements: usize, num_threads: usize) -> usize {
+        if num_elements < num_threads {
+            1
+        } else {
+            num_elements / num_threads
+        }
+    }
+
+    /// Returns the log of the number of sub-FFTs computed in parallel by an FFT
+    /// run on `num_threads` threads. FFTs of smaller log size are run serially.
+    fn fft_log_num_tasks(&self, num_threads: usize) -> u32 {
+        log2_floor(num_threads)
+    }
+
+    /// Returns the log size from which on FFTs use the four-step algorithm.
+    fn four_step_fft_min_log_size(&self) -> u32 {
+        DEFAULT_FOUR_STEP_FFT_MIN_LOG_SIZE
+    }
+}
+
+/// The built-in heuristics.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultTuningStrategy;
+
+impl TuningStrategy for DefaultTuningStrategy {}
+
+/// `floor(e^k)` for `k` in `0..45`, `e^45` being the first power above `u64::MAX`.
+const EXP_FLOOR: [u64; 45] = [
+    1, 2, 7, 20, 54, 148, 403, 1096, 2980, 8103, 22026, 59874, 162754, 442413, 1202604,
+    3269017, 8886110, 24154952, 65659969, 178482300, 485165195, 1318815734, 3584912846,
+    9744803446, 26489122129, 72004899337, 195729609428, 532048240601, 1446257064291,
+    3931334297144, 10686474581524, 29048849665247, 78962960182680, 214643579785916,
+    583461742527454, 1586013452313430, 4311231547115195, 11719142372802611,
+    31855931757113756, 86593400423993746, 235385266837019985, 639843493530054949,
+    1739274941520501047, 4727839468229346561, 12851600114359308275,
+];
+
+/// Returns `ceil(ln(num))`, computed without floating point so that the window
+/// sizes, and hence the results of the computations, are the same on all
+/// platforms. As `e^k` is irrational for `k > 0`, `ln(num) <= k` iff
+/// `num <= floor(e^k)`.
+fn ln_ceil(num: usize) -> usize {
+    EXP_FLOOR
+        .iter()
+        .position(|&e| num as u64 <= e)
+        .unwrap_or(EXP_FLOOR.len())
+}
+
+/// Returns `ceil(log2(num^2))`. The square is computed on two `u64` words, so
+/// that no `u128` arithmetic is needed.
+fn log2_ceil_square(num: u64) -> u32 {
+    assert!(num > 0);
+
+    // num^2 = hi * 2^64 + lo
+    let (num_hi, num_lo) = (num >> 32, num & 0xffff_ffff);
+    let cross = num_hi * num_lo;
+    let (lo, carry) = (num_lo * num_lo).overflowing_add(cross << 33);
+    let hi = num_hi * num_hi + (cross >> 31) + u64::from(carry);
+
+    // The bit length of num^2 - 1
+    let (hi, lo) = if lo == 0 { (hi - 1, u64::max_value()) } else { (hi, lo - 1) };
+    if hi != 0 {
+        128 - hi.leading_zeros()
+    } else {
+        64 - lo.leading_zeros()
+    }
+}
+
+fn log2_floor(num: usize) -> u32 {
+    assert!(num > 0);
+
+    let mut pow = 0;
+
+    while (1 << (pow + 1)) <= num {
+        pow += 1;
+    }
+
+    pow
+}
+
+lazy_static! {
+    static ref TUNING_STRATEGY: RwLock<Arc<dyn TuningStrategy>> =
+        RwLock::new(Arc::new(DefaultTuningStrategy));
+}
+
+/// Installs `strategy` for all the subsequent computations of the process.
+pub fn set_tuning_strategy(strategy: Arc<dyn TuningStrategy>) {
+    *TUNING_STRATEGY.write().unwrap() = strategy;
+}
+
+/// Returns the strategy currently in use.
+pub fn tuning_strategy() -> Arc<dyn TuningStrategy> {
+    TUNING_STRATEGY.read().unwrap().clone()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_log2_floor() {
+        assert_eq!(log2_floor(1), 0);
+        assert_eq!(log2_floor(2), 1);
+        assert_eq!(log2_floor(3), 1);
+        assert_eq!(log2_floor(4), 2);
+        assert_eq!(log2_floor(5), 2);
+        assert_eq!(log2_floor(6), 2);
+        assert_eq!(log2_floor(7), 2);
+        assert_eq!(log2_floor(8), 3);
+    }
+
+    #[test]
+    fn test_integer_window_sizes() {
+        assert_eq!(ln_ceil(1), 0);
+        assert_eq!(ln_ceil(2), 1);
+        assert_eq!(ln_ceil(3), 2);
+        assert_eq!(ln_ceil(7), 2);
+        assert_eq!(ln_ceil(8), 3);
+        assert_eq!(ln_ceil(u32::MAX as usize), 23);
+        assert_eq!(log2_ceil_square(1), 0);
+        assert_eq!(log2_ceil_square(2), 2);
+        assert_eq!(log2_ceil_square(3), 4);
+        assert_eq!(log2_ceil_square(1 << 32), 64);
+        assert_eq!(log2_ceil_square((1 << 32) + 1), 65);
+        assert_eq!(log2_ceil_square(u64::max_value()), 128);
+        for n in 1..1u64 << 16 {
+            assert_eq!(log2_ceil_square(n), 64 - (n * n - 1).leading_zeros());
+        }
+
+        // The same as the floating point formulas
+        let strategy = DefaultTuningStrategy;
+        for n in (32..1 << 16).chain((16..32).map(|i| (1 << i) + 1)) {
+            let x = n as f64;
+            assert_eq!(strategy.fixed_base_msm_window_size(n), x.ln().ceil() as usize);
+            assert_eq!(
+                strategy.precomputed_msm_window_size(n),
+                (2.0 / 3.0 * x.log2() + 2.0).ceil() as usize
+            );
+        }
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_window_sizes_above_u32() {
+        assert_eq!(ln_ceil(1 << 32), 23);
+        assert_eq!(ln_ceil(12851600114359308275), 44);
+        assert_eq!(ln_ceil(12851600114359308276), 45);
+        assert_eq!(ln_ceil(usize::MAX), 45);
+
+        let strategy = DefaultTuningStrategy;
+        for n in (32..48).map(|i| (1 << i) + 1) {
+            let x = n as f64;
+            assert_eq!(strategy.fixed_base_msm_window_size(n), x.ln().ceil() as usize);
+            assert_eq!(
+                strategy.precomputed_msm_window_size(n),
+                (2.0 / 3.0 * x.log2() + 2.0).ceil() as usize
+            );
+        }
+        assert_eq!(strategy.fixed_base_msm_window_size(usize::MAX), 45);
+        assert_eq!(strategy.precomputed_msm_window_size(usize::MAX), 45);
+    }
+}
+
+#[cfg(all(test, feature = "parallel"))]
+mod msm_test {
+    use super::*;
+    use crate::{
+        curves::bls12_381::G1Projective, fields::bls12_381::Fr, msm::VariableBaseMSM,
+        PrimeField, ProjectiveCurve, UniformRand,
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct SmallWindows {
+        calls: AtomicUsize,
+    }
+
+    impl TuningStrategy for SmallWindows {
+        fn msm_window_size(&self, _: usize, _: usize, _: usize) -> usize {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            3
+        }
+    }
+
+    #[test]
+    fn custom_tuning_strategy() {
+        let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+        let v = (0..100)
+            .map(|_| Fr::rand(&mut rng).into_repr())
+            .collect::<Vec<_>>();
+        let g = (0..100)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let expected = VariableBaseMSM::multi_scalar_mul_with_window_size(&g, &v, 3).into_affine();
+
+        let strategy = Arc::new(SmallWindows { calls: AtomicUsize::new(0) });
+        set_tuning_strategy(strategy.clone());
+        let result = VariableBaseMSM::multi_scalar_mul(&g, &v).into_affine();
+        set_tuning_strategy(Arc::new(DefaultTuningStrategy));
+
+        assert_eq!(expected, result);
+        assert!(strategy.calls.load(Ordering::SeqCst) > 0);
+    }
+}
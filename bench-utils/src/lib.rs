@@ -177,6 +177,8 @@ mod inner {
     }
 }
 
+pub mod regression;
+
 mod tests {
     use super::*;
 
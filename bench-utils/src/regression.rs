@@ -0,0 +1,315 @@
+//! A minimal harness to run a fixed suite of micro-benchmarks and compare the
+//! timings against a stored baseline, so that downstream projects can fail their
+//! CI on performance regressions.
+//!
+//! A baseline is stored as text, one benchmark per line, as the name of the
+//! benchmark and its time per iteration in nanoseconds, separated by a tab.
+//! Empty lines and lines starting with `#` are ignored.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, BufRead, Write},
+    time::{Duration, Instant},
+};
+
+/// Hides `dummy` from the optimizer, so that computations whose result is unused
+/// are not optimized away.
+pub fn black_box<T>(dummy: T) -> T {
+    unsafe {
+        let ret = std::ptr::read_volatile(&dummy);
+        std::mem::forget(dummy);
+        ret
+    }
+}
+
+/// A named routine to be timed.
+pub struct Benchmark {
+    pub name: String,
+    routine:  Box<dyn FnMut()>,
+}
+
+impl Benchmark {
+    pub fn new<S: Into<String>, F: FnMut() + 'static>(name: S, routine: F) -> Self {
+        Self { name: name.into(), routine: Box::new(routine) }
+    }
+}
+
+/// How the benchmarks of a suite are timed: each benchmark is run `samples`
+/// times, each sample repeating the routine until `min_sample_time` has
+/// elapsed, and the median time per iteration over the samples is kept.
+#[derive(Clone, Debug)]
+pub struct SuiteConfig {
+    pub samples:         usize,
+    pub min_sample_time: Duration,
+}
+
+impl Default for SuiteConfig {
+    fn default() -> Self {
+        Self { samples: 11, min_sample_time: Duration::from_millis(50) }
+    }
+}
+
+/// The time per iteration of a benchmark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub name:           String,
+    pub nanos_per_iter: f64,
+}
+
+/// Runs `benchmarks` in order, returning their measurements.
+pub fn run_suite(benchmarks: Vec<Benchmark>, config: &SuiteConfig) -> Vec<Measurement> {
+    assert!(config.samples > 0);
+
+    benchmarks
+        .into_iter()
+        .map(|mut benchmark| {
+            // Warm up caches and lazily initialized data
+            (benchmark.routine)();
+
+            let mut samples = (0..config.samples)
+                .map(|_| {
+                    let start = Instant::now();
+                    let mut iterations = 0u32;
+                    while iterations == 0 || start.elapsed() < config.min_sample_time {
+                        (benchmark.routine)();
+                        iterations += 1;
+                    }
+                    start.elapsed().as_nanos() as f64 / f64::from(iterations)
+                })
+                .collect::<Vec<_>>();
+            samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            Measurement { name: benchmark.name, nanos_per_iter: samples[samples.len() / 2] }
+        })
+        .collect()
+}
+
+/// The reference timings of a suite, by benchmark name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline {
+    pub nanos_per_iter: BTreeMap<String, f64>,
+}
+
+impl Baseline {
+    pub fn from_measurements(measurements: &[Measurement]) -> Self {
+        Self {
+            nanos_per_iter: measurements
+                .iter()
+                .map(|m| (m.name.clone(), m.nanos_per_iter))
+                .collect(),
+        }
+    }
+
+    pub fn read<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut nanos_per_iter = BTreeMap::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.rsplitn(2, '\t');
+            let nanos = fields.next().and_then(|nanos| nanos.trim().parse::<f64>().ok());
+            match (fields.next(), nanos) {
+                (Some(name), Some(nanos)) => {
+                    nanos_per_iter.insert(name.to_owned(), nanos);
+                },
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid baseline line: {}", line),
+                    ))
+                },
+            }
+        }
+        Ok(Self { nanos_per_iter })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for (name, nanos) in self.nanos_per_iter.iter() {
+            writeln!(writer, "{}\t{}", name, nanos)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Within the tolerance of the baseline.
+    Unchanged,
+    /// Faster than the baseline by more than the tolerance.
+    Improved,
+    /// Slower than the baseline by more than the tolerance.
+    Regressed,
+    /// Not in the baseline.
+    New,
+    /// In the baseline, but not measured.
+    Missing,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub name:     String,
+    pub baseline: Option<f64>,
+    pub measured: Option<f64>,
+    pub status:   Status,
+}
+
+impl Comparison {
+    /// The measured time relative to the baseline, if both are known.
+    pub fn ratio(&self) -> Option<f64> {
+        match (self.baseline, self.measured) {
+            (Some(baseline), Some(measured)) => Some(measured / baseline),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of the comparison of a suite against a baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionReport {
+    pub tolerance:   f64,
+    pub comparisons: Vec<Comparison>,
+}
+
+impl RegressionReport {
+    /// Compares `measurements` against `baseline`: a benchmark regressed if it is
+    /// slower than its baseline by more than the relative `tolerance`, e.g. 0.1
+    /// for 10%, and improved if it is faster by more than `tolerance`.
+    pub fn compare(measurements: &[Measurement], baseline: &Baseline, tolerance: f64) -> Self {
+        assert!(tolerance >= 0.0);
+
+        let mut comparisons = measurements
+            .iter()
+            .map(|m| {
+                let reference = baseline.nanos_per_iter.get(&m.name).cloned();
+                let status = match reference {
+                    None => Status::New,
+                    Some(reference) if m.nanos_per_iter > reference * (1.0 + tolerance) => {
+                        Status::Regressed
+                    },
+                    Some(reference) if m.nanos_per_iter < reference * (1.0 - tolerance) => {
+                        Status::Improved
+                    },
+                    Some(_) => Status::Unchanged,
+                };
+                Comparison {
+                    name: m.name.clone(),
+                    baseline: reference,
+                    measured: Some(m.nanos_per_iter),
+                    status,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for (name, &reference) in baseline.nanos_per_iter.iter() {
+            if measurements.iter().all(|m| &m.name != name) {
+                comparisons.push(Comparison {
+                    name: name.clone(),
+                    baseline: Some(reference),
+                    measured: None,
+                    status: Status::Missing,
+                });
+            }
+        }
+
+        Self { tolerance, comparisons }
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &Comparison> {
+        self.comparisons.iter().filter(|c| c.status == Status::Regressed)
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+}
+
+impl fmt::Display for RegressionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = |nanos: Option<f64>| nanos.map_or("-".to_owned(), |nanos| format!("{:.0}ns", nanos));
+        writeln!(f, "Tolerance: {:.1}%", self.tolerance * 100.0)?;
+        for c in self.comparisons.iter() {
+            let ratio = c.ratio().map_or("-".to_owned(), |ratio| format!("{:+.1}%", (ratio - 1.0) * 100.0));
+            writeln!(
+                f,
+                "{:<40} {:>14} {:>14} {:>8} {:?}",
+                c.name,
+                nanos(c.baseline),
+                nanos(c.measured),
+                ratio,
+                c.status
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn measurement(name: &str, nanos_per_iter: f64) -> Measurement {
+        Measurement { name: name.to_owned(), nanos_per_iter }
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let baseline = Baseline::from_measurements(&[
+            measurement("Fq mul", 42.5),
+            measurement("G1 MSM: 1024 bases", 1.25e6),
+        ]);
+        let mut bytes = b"# A comment\n\n".to_vec();
+        baseline.write(&mut bytes).unwrap();
+        assert_eq!(Baseline::read(bytes.as_slice()).unwrap(), baseline);
+
+        assert!(Baseline::read("Fq mul 42".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn compare_against_baseline() {
+        let baseline = Baseline::from_measurements(&[
+            measurement("a", 100.0),
+            measurement("b", 100.0),
+            measurement("c", 100.0),
+            measurement("d", 100.0),
+        ]);
+        let measurements = [
+            measurement("a", 105.0),
+            measurement("b", 120.0),
+            measurement("c", 80.0),
+            measurement("e", 1.0),
+        ];
+        let report = RegressionReport::compare(&measurements, &baseline, 0.1);
+        let statuses = report.comparisons.iter().map(|c| (c.name.as_str(), c.status)).collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                ("a", Status::Unchanged),
+                ("b", Status::Regressed),
+                ("c", Status::Improved),
+                ("e", Status::New),
+                ("d", Status::Missing),
+            ]
+        );
+        assert!(report.has_regressions());
+        assert_eq!(report.regressions().count(), 1);
+
+        let report = RegressionReport::compare(&measurements[..1], &baseline, 0.1);
+        assert!(!report.has_regressions());
+    }
+
+    #[test]
+    fn run_benchmarks() {
+        let config = SuiteConfig { samples: 3, min_sample_time: Duration::from_millis(1) };
+        let measurements = run_suite(
+            vec![Benchmark::new("sum", || {
+                black_box((0..black_box(1000u64)).sum::<u64>());
+            })],
+            &config,
+        );
+        assert_eq!(measurements.len(), 1);
+        assert_eq!(measurements[0].name, "sum");
+        assert!(measurements[0].nanos_per_iter > 0.0);
+    }
+}
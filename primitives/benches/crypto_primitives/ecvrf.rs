@@ -64,10 +64,27 @@ fn ecvrf_verify(c: &mut Criterion) {
     });
 }
 
+fn ecvrf_batch_verify(c: &mut Criterion) {
+    let mut rng = &mut rand::thread_rng();
+    let pp = <BHMNT6 as FixedLengthCRH>::setup(rng).unwrap();
+    let (pk, sk) = EcVrfMNT4::keygen(&mut rng);
+    let messages = (0..100).map(|_| vec![MNT4Fr::rand(rng)]).collect::<Vec<_>>();
+    let message_refs = messages.iter().map(|m| m.as_slice()).collect::<Vec<_>>();
+    let proofs = EcVrfMNT4::batch_prove(&mut rng, &pp, &pk, &sk, &message_refs).unwrap();
+    let pks = vec![pk; 100];
+
+    c.bench_function("EcVrfMNT4: Batch Verify 100 proofs", move |b| {
+        b.iter(|| {
+            let message_refs = messages.iter().map(|m| m.as_slice()).collect::<Vec<_>>();
+            EcVrfMNT4::batch_verify(&pp, &pks, &message_refs, &proofs).unwrap()
+        })
+    });
+}
+
 criterion_group! {
     name = ecvrf;
     config = Criterion::default().sample_size(20);
-    targets = ecvrf_keygen, ecvrf_prove, ecvrf_verify
+    targets = ecvrf_keygen, ecvrf_prove, ecvrf_verify, ecvrf_batch_verify
 }
 
 criterion_main!(ecvrf);
\ No newline at end of file
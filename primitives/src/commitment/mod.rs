@@ -7,8 +7,9 @@ use algebra::bytes::ToBytes;
 pub mod blake2s;
 pub mod injective_map;
 pub mod pedersen;
+pub mod vector;
 
-use crate::Error;
+use crate::{Error, KeyProvider};
 
 pub trait CommitmentScheme {
     type Output: ToBytes + Clone + Default + Eq + Hash + Debug;
@@ -22,4 +23,15 @@ pub trait CommitmentScheme {
         input: &[u8],
         r: &Self::Randomness,
     ) -> Result<Self::Output, Error>;
+
+    /// Commits to `input` with the randomness `key_id` of `provider`, e.g. a
+    /// long-term blinding factor kept out of the application.
+    fn commit_with_key_provider<K: KeyProvider<Self::Randomness>>(
+        parameters: &Self::Parameters,
+        input: &[u8],
+        provider: &K,
+        key_id: &str,
+    ) -> Result<Self::Output, Error> {
+        provider.with_secret_key(key_id, |r| Self::commit(parameters, input, r))?
+    }
 }
@@ -0,0 +1,136 @@
+use crate::{
+    commitment::vector::VectorCommitment,
+    crh::FieldBasedHash,
+    merkle_tree::{field_based_mht::*, MerkleTreeError},
+    Error,
+};
+
+use rand::Rng;
+use std::marker::PhantomData;
+
+/// Vector commitment given by the root of a `FieldBasedMerkleHashTree` having the
+/// messages as leaves, e.g. with the Poseidon hash. The openings are the Merkle paths,
+/// whose directions must match the position being opened. Updates recompute the
+/// path from the updated leaf to the root.
+pub struct FieldBasedMerkleVectorCommitment<P: FieldBasedMerkleTreeConfig> {
+    _config: PhantomData<P>,
+}
+
+impl<P: FieldBasedMerkleTreeConfig> FieldBasedMerkleVectorCommitment<P> {
+    /// Maximum number of messages which can be committed.
+    pub const MAX_LEN: usize = 1 << (P::HEIGHT - 1);
+}
+
+impl<P: FieldBasedMerkleTreeConfig> VectorCommitment for FieldBasedMerkleVectorCommitment<P> {
+    type Parameters = ();
+    type Message = <P::H as FieldBasedHash>::Data;
+    type Commitment = <P::H as FieldBasedHash>::Data;
+    type Opening = FieldBasedMerkleTreePath<P>;
+    type Committed = FieldBasedMerkleHashTree<P>;
+
+    fn setup<R: Rng>(max_len: usize, _rng: &mut R) -> Result<Self::Parameters, Error> {
+        if max_len > Self::MAX_LEN {
+            Err(MerkleTreeError::TooManyLeaves(max_len))?
+        }
+        Ok(())
+    }
+
+    fn commit(
+        _parameters: &Self::Parameters,
+        messages: &[Self::Message],
+    ) -> Result<(Self::Commitment, Self::Committed), Error> {
+        if messages.len() > Self::MAX_LEN {
+            Err(MerkleTreeError::TooManyLeaves(messages.len()))?
+        }
+        let tree = FieldBasedMerkleHashTree::new(messages)?;
+        Ok((tree.root(), tree))
+    }
+
+    fn open(
+        _parameters: &Self::Parameters,
+        committed: &Self::Committed,
+        index: usize,
+    ) -> Result<Self::Opening, Error> {
+        match committed.leaves().get(index) {
+            Some(leaf) => committed.generate_proof(index, leaf),
+            None => Err(MerkleTreeError::IncorrectLeafIndex(index))?,
+        }
+    }
+
+    fn verify(
+        _parameters: &Self::Parameters,
+        commitment: &Self::Commitment,
+        index: usize,
+        message: &Self::Message,
+        opening: &Self::Opening,
+    ) -> Result<bool, Error> {
+        if index >= Self::MAX_LEN {
+            Err(MerkleTreeError::IncorrectLeafIndex(index))?
+        }
+        // The leaf is a right child at level i if the i-th bit of its index is set
+        let is_at_index = opening.path
+            .iter()
+            .enumerate()
+            .all(|(i, &(_, direction))| direction == ((index >> i) & 1 == 1));
+        Ok(is_at_index && opening.verify(commitment, message)?)
+    }
+
+    fn update(
+        _parameters: &Self::Parameters,
+        committed: &mut Self::Committed,
+        index: usize,
+        new_message: &Self::Message,
+    ) -> Result<Self::Commitment, Error> {
+        committed.update_leaf(index, *new_message)?;
+        Ok(committed.root())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        commitment::vector::{FieldBasedMerkleVectorCommitment, VectorCommitment},
+        crh::MNT4PoseidonHash,
+        merkle_tree::field_based_mht::FieldBasedMerkleTreeConfig,
+    };
+    use algebra::{fields::mnt4753::Fr, UniformRand};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    struct MNT4753FieldBasedMerkleTreeParams;
+
+    impl FieldBasedMerkleTreeConfig for MNT4753FieldBasedMerkleTreeParams {
+        const HEIGHT: usize = 5;
+        type H = MNT4PoseidonHash;
+    }
+
+    type VC = FieldBasedMerkleVectorCommitment<MNT4753FieldBasedMerkleTreeParams>;
+
+    #[test]
+    fn field_based_merkle_vector_commitment_test() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let parameters = VC::setup(16, rng).unwrap();
+        assert!(VC::setup(17, rng).is_err());
+
+        let messages = (0..11).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let (commitment, mut committed) = VC::commit(&parameters, &messages).unwrap();
+
+        for i in 0..messages.len() {
+            let opening = VC::open(&parameters, &committed, i).unwrap();
+            assert!(VC::verify(&parameters, &commitment, i, &messages[i], &opening).unwrap());
+            assert!(!VC::verify(&parameters, &commitment, i, &Fr::rand(rng), &opening).unwrap());
+
+            // The opening is bound to the position
+            assert!(!VC::verify(&parameters, &commitment, i ^ 1, &messages[i], &opening).unwrap());
+        }
+
+        let new_message = Fr::rand(rng);
+        let updated_commitment = VC::update(&parameters, &mut committed, 7, &new_message).unwrap();
+        let opening = VC::open(&parameters, &committed, 7).unwrap();
+        assert!(VC::verify(&parameters, &updated_commitment, 7, &new_message, &opening).unwrap());
+        assert!(!VC::verify(&parameters, &commitment, 7, &new_message, &opening).unwrap());
+
+        // Too many messages
+        assert!(VC::commit(&parameters, &vec![Fr::rand(rng); 17]).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use rand::Rng;
+use std::fmt::Debug;
+
+use algebra::bytes::ToBytes;
+
+pub mod pedersen;
+pub use self::pedersen::*;
+
+pub mod pointproofs;
+pub use self::pointproofs::*;
+
+#[cfg(feature = "merkle_tree")]
+pub mod field_based_mht;
+#[cfg(feature = "merkle_tree")]
+pub use self::field_based_mht::*;
+
+use crate::Error;
+
+/// Commitment to a vector of messages, which can be opened at single positions,
+/// e.g. as a building block for accumulators.
+pub trait VectorCommitment {
+    type Parameters: Clone;
+    type Message: Clone + Eq + Debug;
+    type Commitment: ToBytes + Clone + Default + Eq + Debug;
+    type Opening: Clone + Debug;
+    /// Data kept by the committer to open positions and update the commitment.
+    type Committed;
+
+    /// Generates parameters for vectors of at most `max_len` messages.
+    fn setup<R: Rng>(max_len: usize, rng: &mut R) -> Result<Self::Parameters, Error>;
+
+    fn commit(
+        parameters: &Self::Parameters,
+        messages: &[Self::Message],
+    ) -> Result<(Self::Commitment, Self::Committed), Error>;
+
+    /// Opens the position `index` of the committed vector.
+    fn open(
+        parameters: &Self::Parameters,
+        committed: &Self::Committed,
+        index: usize,
+    ) -> Result<Self::Opening, Error>;
+
+    /// Checks that `opening` opens `commitment` to `message` at the position `index`.
+    fn verify(
+        parameters: &Self::Parameters,
+        commitment: &Self::Commitment,
+        index: usize,
+        message: &Self::Message,
+        opening: &Self::Opening,
+    ) -> Result<bool, Error>;
+
+    /// Replaces the message at the position `index` by `new_message`, returning
+    /// the commitment to the updated vector.
+    fn update(
+        parameters: &Self::Parameters,
+        committed: &mut Self::Committed,
+        index: usize,
+        new_message: &Self::Message,
+    ) -> Result<Self::Commitment, Error>;
+}
+
+/// Vector commitment whose commitment can be updated by anyone, without knowing
+/// the committed vector.
+pub trait HomomorphicVectorCommitment: VectorCommitment {
+    /// Returns the commitment obtained from `commitment` by replacing `old_message`
+    /// by `new_message` at the position `index`.
+    fn update_commitment(
+        parameters: &Self::Parameters,
+        commitment: &Self::Commitment,
+        index: usize,
+        old_message: &Self::Message,
+        new_message: &Self::Message,
+    ) -> Result<Self::Commitment, Error>;
+}
@@ -0,0 +1,187 @@
+use crate::{
+    commitment::vector::{HomomorphicVectorCommitment, VectorCommitment},
+    CryptoError, Error,
+};
+use algebra::{groups::Group, FpParameters, PrimeField};
+
+use rand::Rng;
+use std::marker::PhantomData;
+
+/// Parameters of the `PedersenVectorCommitment`: for each position, the powers of
+/// two of a random generator, which allows fixed base scalar multiplications in circuit.
+#[derive(Clone, Default, Debug)]
+pub struct PedersenVectorCommitmentParameters<G: Group> {
+    pub generators: Vec<Vec<G>>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "G: Group"),
+    Debug(bound = "G: Group"),
+)]
+pub struct PedersenVectorCommitted<G: Group> {
+    pub messages:   Vec<G::ScalarField>,
+    pub commitment: G,
+}
+
+/// The Pedersen vector commitment m_0 * G_0 + ... + m_{n-1} * G_{n-1} to the scalars
+/// m_0, ..., m_{n-1}, for random generators G_i. It is binding but not hiding, and
+/// homomorphic. An opening consists of the whole vector, so that it is not succinct.
+pub struct PedersenVectorCommitment<G: Group> {
+    _group: PhantomData<G>,
+}
+
+impl<G: Group> PedersenVectorCommitment<G> {
+    fn generator_powers<R: Rng>(num_powers: usize, rng: &mut R) -> Vec<G> {
+        let mut powers = Vec::with_capacity(num_powers);
+        let mut base = G::rand(rng);
+        for _ in 0..num_powers {
+            powers.push(base);
+            base.double_in_place();
+        }
+        powers
+    }
+
+    fn commit_to_vector(
+        parameters: &PedersenVectorCommitmentParameters<G>,
+        messages: &[G::ScalarField],
+    ) -> Result<G, Error> {
+        if messages.len() > parameters.generators.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("messages".to_owned(), messages.len())));
+        }
+        let mut result = G::zero();
+        for (message, powers) in messages.iter().zip(&parameters.generators) {
+            result += &powers[0].mul(message);
+        }
+        Ok(result)
+    }
+}
+
+impl<G: Group> VectorCommitment for PedersenVectorCommitment<G> {
+    type Parameters = PedersenVectorCommitmentParameters<G>;
+    type Message = G::ScalarField;
+    type Commitment = G;
+    type Opening = Vec<G::ScalarField>;
+    type Committed = PedersenVectorCommitted<G>;
+
+    fn setup<R: Rng>(max_len: usize, rng: &mut R) -> Result<Self::Parameters, Error> {
+        let time = start_timer!(|| format!("PedersenVectorCommitment::Setup: {} positions", max_len));
+        let num_powers = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        let generators = (0..max_len)
+            .map(|_| Self::generator_powers(num_powers, rng))
+            .collect();
+        end_timer!(time);
+        Ok(PedersenVectorCommitmentParameters { generators })
+    }
+
+    fn commit(
+        parameters: &Self::Parameters,
+        messages: &[Self::Message],
+    ) -> Result<(Self::Commitment, Self::Committed), Error> {
+        let commit_time = start_timer!(|| "PedersenVectorCommitment::Commit");
+        let commitment = Self::commit_to_vector(parameters, messages)?;
+        end_timer!(commit_time);
+        Ok((commitment, PedersenVectorCommitted { messages: messages.to_vec(), commitment }))
+    }
+
+    fn open(
+        _parameters: &Self::Parameters,
+        committed: &Self::Committed,
+        index: usize,
+    ) -> Result<Self::Opening, Error> {
+        if index >= committed.messages.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("index".to_owned(), index)));
+        }
+        Ok(committed.messages.clone())
+    }
+
+    fn verify(
+        parameters: &Self::Parameters,
+        commitment: &Self::Commitment,
+        index: usize,
+        message: &Self::Message,
+        opening: &Self::Opening,
+    ) -> Result<bool, Error> {
+        if index >= opening.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("index".to_owned(), index)));
+        }
+        Ok(&opening[index] == message && &Self::commit_to_vector(parameters, opening)? == commitment)
+    }
+
+    fn update(
+        parameters: &Self::Parameters,
+        committed: &mut Self::Committed,
+        index: usize,
+        new_message: &Self::Message,
+    ) -> Result<Self::Commitment, Error> {
+        if index >= committed.messages.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("index".to_owned(), index)));
+        }
+        committed.commitment = Self::update_commitment(
+            parameters,
+            &committed.commitment,
+            index,
+            &committed.messages[index],
+            new_message,
+        )?;
+        committed.messages[index] = *new_message;
+        Ok(committed.commitment)
+    }
+}
+
+impl<G: Group> HomomorphicVectorCommitment for PedersenVectorCommitment<G> {
+    /// Adds (new_message - old_message) * G_index to `commitment`.
+    fn update_commitment(
+        parameters: &Self::Parameters,
+        commitment: &Self::Commitment,
+        index: usize,
+        old_message: &Self::Message,
+        new_message: &Self::Message,
+    ) -> Result<Self::Commitment, Error> {
+        if index >= parameters.generators.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("index".to_owned(), index)));
+        }
+        let delta = *new_message - old_message;
+        Ok(*commitment + &parameters.generators[index][0].mul(&delta))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::commitment::vector::{
+        HomomorphicVectorCommitment, PedersenVectorCommitment, VectorCommitment,
+    };
+    use algebra::{curves::edwards_bls12::EdwardsProjective, fields::edwards_bls12::fr::Fr, UniformRand};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type VC = PedersenVectorCommitment<EdwardsProjective>;
+
+    #[test]
+    fn pedersen_vector_commitment_test() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let parameters = VC::setup(8, rng).unwrap();
+        let mut messages = (0..6).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let (commitment, mut committed) = VC::commit(&parameters, &messages).unwrap();
+
+        for i in 0..messages.len() {
+            let opening = VC::open(&parameters, &committed, i).unwrap();
+            assert!(VC::verify(&parameters, &commitment, i, &messages[i], &opening).unwrap());
+            assert!(!VC::verify(&parameters, &commitment, i, &Fr::rand(rng), &opening).unwrap());
+        }
+
+        // Homomorphic update
+        let new_message = Fr::rand(rng);
+        let updated_commitment = VC::update_commitment(&parameters, &commitment, 3, &messages[3], &new_message).unwrap();
+        assert_eq!(VC::update(&parameters, &mut committed, 3, &new_message).unwrap(), updated_commitment);
+        messages[3] = new_message;
+        assert_eq!(VC::commit(&parameters, &messages).unwrap().0, updated_commitment);
+
+        let opening = VC::open(&parameters, &committed, 3).unwrap();
+        assert!(VC::verify(&parameters, &updated_commitment, 3, &new_message, &opening).unwrap());
+        assert!(!VC::verify(&parameters, &commitment, 3, &new_message, &opening).unwrap());
+
+        // Too many messages
+        assert!(VC::commit(&parameters, &vec![Fr::rand(rng); 9]).is_err());
+    }
+}
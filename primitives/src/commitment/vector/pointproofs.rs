@@ -0,0 +1,384 @@
+use crate::{
+    commitment::vector::{HomomorphicVectorCommitment, VectorCommitment},
+    CryptoError, Error,
+};
+use algebra::{
+    bytes::ToBytes, groups::Group, msm::{FixedBaseMSM, FixedBaseMul, VariableBaseMSM}, to_bytes, AffineCurve, Field,
+    FpParameters, PairingCurve, PairingEngine, PrimeField, ProjectiveCurve, UniformRand,
+};
+use blake2::Blake2s;
+use digest::Digest;
+
+use rand::Rng;
+use std::{marker::PhantomData, ops::Neg};
+
+/// Parameters of the `PointproofsVectorCommitment` for vectors of at most N messages:
+/// the powers of a secret alpha in the exponent of the generators of the pairing groups.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "E: PairingEngine"),
+    Debug(bound = "E: PairingEngine"),
+)]
+pub struct PointproofsParameters<E: PairingEngine> {
+    /// g1^{alpha^i}, for i = 1, ..., 2N except N + 1.
+    pub g1_powers: Vec<E::G1Affine>,
+    /// g2^{alpha^i}, for i = 0, ..., N.
+    pub g2_powers: Vec<E::G2Affine>,
+    /// e(g1, g2)^{alpha^{N + 1}}.
+    pub gt_power:  E::Fqk,
+}
+
+impl<E: PairingEngine> PointproofsParameters<E> {
+    /// The maximum number of messages of a committed vector.
+    pub fn max_len(&self) -> usize {
+        self.g2_powers.len() - 1
+    }
+
+    // Returns g1^{alpha^i}, for i in [1, 2N] different from N + 1.
+    fn g1_power(&self, i: usize) -> E::G1Affine {
+        let n = self.max_len();
+        debug_assert!(i >= 1 && i <= 2 * n && i != n + 1);
+        if i <= n {
+            self.g1_powers[i - 1]
+        } else {
+            self.g1_powers[i - 2]
+        }
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "E: PairingEngine"),
+    Debug(bound = "E: PairingEngine"),
+)]
+pub struct PointproofsCommitted<E: PairingEngine> {
+    pub messages:   Vec<E::Fr>,
+    pub commitment: E::G1Projective,
+}
+
+/// The vector commitment of [Pointproofs](https://eprint.iacr.org/2019/1255), in its
+/// non hiding variant: the commitment to m_1, ..., m_n is
+/// C = g1^{m_1 * alpha + ... + m_n * alpha^n}, and the opening of the position i is
+/// the single point of G1 g1^{sum_{j != i} m_j * alpha^{N + 1 - i + j}}, checked by
+/// e(C, g2^{alpha^{N + 1 - i}}) = e(opening, g2) * e(g1, g2)^{alpha^{N + 1} * m_i}.
+/// The openings of several positions of a commitment can be aggregated into a single
+/// one, see `aggregate`. The commitment is homomorphic.
+///
+/// Positions are numbered from 0, so that the position `index` holds m_{index + 1}.
+/// Since the parameters are generated from a secret alpha, `setup` is only meant for
+/// testing: in production they must come from a trusted setup ceremony.
+pub struct PointproofsVectorCommitment<E: PairingEngine> {
+    _engine: PhantomData<E>,
+}
+
+impl<E: PairingEngine> PointproofsVectorCommitment<E> {
+    fn check_index(parameters: &PointproofsParameters<E>, index: usize) -> Result<(), Error> {
+        if index >= parameters.max_len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("index".to_owned(), index)));
+        }
+        Ok(())
+    }
+
+    fn msm(bases: &[E::G1Affine], scalars: &[E::Fr]) -> E::G1Projective {
+        let scalars = scalars.iter().map(|scalar| scalar.into_repr()).collect::<Vec<_>>();
+        VariableBaseMSM::multi_scalar_mul(bases, &scalars)
+    }
+
+    // Checks that e(commitment, g2_point) = e(opening, g2) * e(g1, g2)^{alpha^{N + 1} * message}.
+    fn check_pairing(
+        parameters: &PointproofsParameters<E>,
+        commitment: &E::G1Projective,
+        g2_point: &E::G2Affine,
+        opening: &E::G1Projective,
+        message: &E::Fr,
+    ) -> bool {
+        let result = E::product_of_pairings(
+            [
+                (&commitment.into_affine().prepare(), &g2_point.prepare()),
+                (&opening.into_affine().neg().prepare(), &parameters.g2_powers[0].prepare()),
+            ]
+                .iter(),
+        );
+        result == parameters.gt_power.pow(message.into_repr())
+    }
+
+    /// Returns the scalars t_i by which the openings of the positions `indices` are
+    /// combined in an aggregated opening, derived from the commitment and the
+    /// opened messages by hashing.
+    fn aggregation_scalars(
+        commitment: &E::G1Projective,
+        indices: &[usize],
+        messages: &[E::Fr],
+    ) -> Result<Vec<E::Fr>, Error> {
+        let mut state = b"Pointproofs aggregation".to_vec();
+        state.extend_from_slice(&to_bytes![commitment]?);
+        for (index, message) in indices.iter().zip(messages) {
+            state.extend_from_slice(&to_bytes![*index as u64, message]?);
+        }
+
+        let num_bits = <E::Fr as PrimeField>::Params::MODULUS_BITS
+            + <E::Fr as PrimeField>::Params::REPR_SHAVE_BITS;
+        let num_bytes = (num_bits / 8) as usize;
+        let mut scalars = Vec::with_capacity(indices.len());
+        for i in 0..indices.len() {
+            let mut counter = 0u32;
+            let scalar = loop {
+                let mut bytes = Vec::with_capacity(num_bytes);
+                let mut block = 0u32;
+                while bytes.len() < num_bytes {
+                    let mut block_input = to_bytes![i as u64, counter, block]?;
+                    block_input.extend_from_slice(&state);
+                    bytes.extend_from_slice(&Blake2s::digest(&block_input));
+                    block += 1;
+                }
+                match E::Fr::from_random_bytes(&bytes[..num_bytes]) {
+                    Some(scalar) => break scalar,
+                    None => counter += 1,
+                }
+            };
+            scalars.push(scalar);
+        }
+        Ok(scalars)
+    }
+
+    /// Aggregates the `openings` of the positions `indices` of `commitment`, which hold
+    /// `messages`, into the single opening sum_i t_i * opening_i.
+    pub fn aggregate(
+        commitment: &E::G1Projective,
+        indices: &[usize],
+        messages: &[E::Fr],
+        openings: &[E::G1Projective],
+    ) -> Result<E::G1Projective, Error> {
+        if messages.len() != indices.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("messages".to_owned(), messages.len())));
+        }
+        if openings.len() != indices.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("openings".to_owned(), openings.len())));
+        }
+        let scalars = Self::aggregation_scalars(commitment, indices, messages)?;
+        let mut result = <E::G1Projective as ProjectiveCurve>::zero();
+        for (opening, scalar) in openings.iter().zip(scalars.iter()) {
+            result += &opening.mul(scalar);
+        }
+        Ok(result)
+    }
+
+    /// Checks that the aggregated `opening` opens `commitment` to `messages` at the
+    /// positions `indices`, i.e. that
+    /// e(C, prod_i g2^{alpha^{N + 1 - i} * t_i}) = e(opening, g2) * e(g1, g2)^{alpha^{N + 1} * sum_i m_i * t_i}.
+    pub fn verify_aggregated(
+        parameters: &PointproofsParameters<E>,
+        commitment: &E::G1Projective,
+        indices: &[usize],
+        messages: &[E::Fr],
+        opening: &E::G1Projective,
+    ) -> Result<bool, Error> {
+        if messages.len() != indices.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("messages".to_owned(), messages.len())));
+        }
+        for &index in indices.iter() {
+            Self::check_index(parameters, index)?;
+        }
+        let scalars = Self::aggregation_scalars(commitment, indices, messages)?;
+
+        let n = parameters.max_len();
+        let mut g2_point = <E::G2Projective as ProjectiveCurve>::zero();
+        let mut message = E::Fr::zero();
+        for ((&index, m), t) in indices.iter().zip(messages).zip(scalars.iter()) {
+            g2_point += &parameters.g2_powers[n - index].mul(*t);
+            message += &(*m * t);
+        }
+        Ok(Self::check_pairing(parameters, commitment, &g2_point.into_affine(), opening, &message))
+    }
+}
+
+impl<E: PairingEngine> VectorCommitment for PointproofsVectorCommitment<E> {
+    type Parameters = PointproofsParameters<E>;
+    type Message = E::Fr;
+    type Commitment = E::G1Projective;
+    type Opening = E::G1Projective;
+    type Committed = PointproofsCommitted<E>;
+
+    fn setup<R: Rng>(max_len: usize, rng: &mut R) -> Result<Self::Parameters, Error> {
+        let time = start_timer!(|| format!("PointproofsVectorCommitment::Setup: {} positions", max_len));
+        if max_len == 0 {
+            return Err(Box::new(CryptoError::IncorrectInputLength("max_len".to_owned(), max_len)));
+        }
+        let alpha = loop {
+            let alpha = E::Fr::rand(rng);
+            if !alpha.is_zero() {
+                break alpha;
+            }
+        };
+        let mut alpha_powers = Vec::with_capacity(2 * max_len + 1);
+        let mut power = E::Fr::one();
+        for _ in 0..=2 * max_len {
+            alpha_powers.push(power);
+            power *= &alpha;
+        }
+
+        // The generators are multiplied by many powers, precompute their tables
+        let window = FixedBaseMSM::get_mul_window_size(2 * max_len);
+        let g1 = E::G1Projective::prime_subgroup_generator();
+        let g1_powers = alpha_powers[1..=2 * max_len]
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != max_len)
+            .map(|(_, power)| *power)
+            .collect::<Vec<_>>();
+        let mut g1_powers = FixedBaseMul::new(&g1.into_affine(), window).batch_mul(&g1_powers);
+        E::G1Projective::batch_normalization(&mut g1_powers);
+
+        let g2 = E::G2Projective::prime_subgroup_generator();
+        let mut g2_powers =
+            FixedBaseMul::new(&g2.into_affine(), window).batch_mul(&alpha_powers[..=max_len]);
+        E::G2Projective::batch_normalization(&mut g2_powers);
+
+        let gt_power = E::pairing(g1.mul(&alpha_powers[max_len + 1]), g2);
+        end_timer!(time);
+
+        Ok(PointproofsParameters {
+            g1_powers: g1_powers.iter().map(|power| power.into_affine()).collect(),
+            g2_powers: g2_powers.iter().map(|power| power.into_affine()).collect(),
+            gt_power,
+        })
+    }
+
+    fn commit(
+        parameters: &Self::Parameters,
+        messages: &[Self::Message],
+    ) -> Result<(Self::Commitment, Self::Committed), Error> {
+        if messages.len() > parameters.max_len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("messages".to_owned(), messages.len())));
+        }
+        let commit_time = start_timer!(|| "PointproofsVectorCommitment::Commit");
+        let commitment = Self::msm(&parameters.g1_powers[..messages.len()], messages);
+        end_timer!(commit_time);
+        Ok((commitment, PointproofsCommitted { messages: messages.to_vec(), commitment }))
+    }
+
+    /// Computes the opening sum_{j != i} m_j * g1^{alpha^{N + 1 - i + j}}, where
+    /// i = index + 1.
+    fn open(
+        parameters: &Self::Parameters,
+        committed: &Self::Committed,
+        index: usize,
+    ) -> Result<Self::Opening, Error> {
+        if index >= committed.messages.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("index".to_owned(), index)));
+        }
+        let open_time = start_timer!(|| "PointproofsVectorCommitment::Open");
+        let n = parameters.max_len();
+        let (bases, scalars): (Vec<_>, Vec<_>) = committed
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != index)
+            .map(|(j, message)| (parameters.g1_power(n + 1 + j - index), *message))
+            .unzip();
+        let opening = Self::msm(&bases, &scalars);
+        end_timer!(open_time);
+        Ok(opening)
+    }
+
+    fn verify(
+        parameters: &Self::Parameters,
+        commitment: &Self::Commitment,
+        index: usize,
+        message: &Self::Message,
+        opening: &Self::Opening,
+    ) -> Result<bool, Error> {
+        Self::check_index(parameters, index)?;
+        let g2_point = parameters.g2_powers[parameters.max_len() - index];
+        Ok(Self::check_pairing(parameters, commitment, &g2_point, opening, message))
+    }
+
+    fn update(
+        parameters: &Self::Parameters,
+        committed: &mut Self::Committed,
+        index: usize,
+        new_message: &Self::Message,
+    ) -> Result<Self::Commitment, Error> {
+        if index >= committed.messages.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("index".to_owned(), index)));
+        }
+        committed.commitment = Self::update_commitment(
+            parameters,
+            &committed.commitment,
+            index,
+            &committed.messages[index],
+            new_message,
+        )?;
+        committed.messages[index] = *new_message;
+        Ok(committed.commitment)
+    }
+}
+
+impl<E: PairingEngine> HomomorphicVectorCommitment for PointproofsVectorCommitment<E> {
+    /// Adds (new_message - old_message) * g1^{alpha^{index + 1}} to `commitment`.
+    fn update_commitment(
+        parameters: &Self::Parameters,
+        commitment: &Self::Commitment,
+        index: usize,
+        old_message: &Self::Message,
+        new_message: &Self::Message,
+    ) -> Result<Self::Commitment, Error> {
+        Self::check_index(parameters, index)?;
+        let delta = *new_message - old_message;
+        Ok(*commitment + &parameters.g1_powers[index].mul(delta))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::commitment::vector::{
+        HomomorphicVectorCommitment, PointproofsVectorCommitment, VectorCommitment,
+    };
+    use algebra::{curves::bls12_381::Bls12_381, fields::bls12_381::Fr, UniformRand};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type VC = PointproofsVectorCommitment<Bls12_381>;
+
+    #[test]
+    fn pointproofs_vector_commitment_test() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let parameters = VC::setup(8, rng).unwrap();
+        let mut messages = (0..6).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let (commitment, mut committed) = VC::commit(&parameters, &messages).unwrap();
+
+        let openings = (0..messages.len())
+            .map(|i| VC::open(&parameters, &committed, i).unwrap())
+            .collect::<Vec<_>>();
+        for i in 0..messages.len() {
+            assert!(VC::verify(&parameters, &commitment, i, &messages[i], &openings[i]).unwrap());
+            assert!(!VC::verify(&parameters, &commitment, i, &Fr::rand(rng), &openings[i]).unwrap());
+            assert!(!VC::verify(&parameters, &commitment, (i + 1) % 6, &messages[i], &openings[i]).unwrap());
+        }
+
+        // Aggregated openings
+        let indices = [1, 4, 5];
+        let opened_messages = indices.iter().map(|&i| messages[i]).collect::<Vec<_>>();
+        let opened = indices.iter().map(|&i| openings[i]).collect::<Vec<_>>();
+        let aggregated = VC::aggregate(&commitment, &indices, &opened_messages, &opened).unwrap();
+        assert!(VC::verify_aggregated(&parameters, &commitment, &indices, &opened_messages, &aggregated).unwrap());
+        let mut bad_messages = opened_messages.clone();
+        bad_messages[2] = Fr::rand(rng);
+        assert!(!VC::verify_aggregated(&parameters, &commitment, &indices, &bad_messages, &aggregated).unwrap());
+        assert!(!VC::verify_aggregated(&parameters, &commitment, &[1, 4, 3], &opened_messages, &aggregated).unwrap());
+
+        // Homomorphic update
+        let new_message = Fr::rand(rng);
+        let updated_commitment = VC::update_commitment(&parameters, &commitment, 3, &messages[3], &new_message).unwrap();
+        assert_eq!(VC::update(&parameters, &mut committed, 3, &new_message).unwrap(), updated_commitment);
+        messages[3] = new_message;
+        assert_eq!(VC::commit(&parameters, &messages).unwrap().0, updated_commitment);
+
+        let opening = VC::open(&parameters, &committed, 3).unwrap();
+        assert!(VC::verify(&parameters, &updated_commitment, 3, &new_message, &opening).unwrap());
+        assert!(!VC::verify(&parameters, &commitment, 3, &new_message, &opening).unwrap());
+
+        // Too many messages
+        assert!(VC::commit(&parameters, &vec![Fr::rand(rng); 9]).is_err());
+    }
+}
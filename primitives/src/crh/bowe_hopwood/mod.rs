@@ -167,8 +167,13 @@ mod test {
         crh::{bowe_hopwood::BoweHopwoodPedersenCRH, pedersen::PedersenWindow},
         FixedLengthCRH,
     };
-    use algebra::curves::edwards_sw6::EdwardsProjective;
-    use rand::thread_rng;
+    use algebra::{
+        curves::{edwards_sw6::EdwardsProjective, jubjub::JubJubProjective},
+        kat::KnownAnswers,
+        ProjectiveCurve,
+    };
+    use rand::{thread_rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
 
     #[test]
     fn test_simple_bh() {
@@ -189,4 +194,25 @@ mod test {
         )
         .unwrap();
     }
+
+    #[test]
+    fn test_bh_kat() {
+        #[derive(Clone)]
+        struct TestWindow {}
+        impl PedersenWindow for TestWindow {
+            const WINDOW_SIZE: usize = 63;
+            const NUM_WINDOWS: usize = 8;
+        }
+        type H = BoweHopwoodPedersenCRH<JubJubProjective, TestWindow>;
+
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let params = <H as FixedLengthCRH>::setup(rng).unwrap();
+
+        let mut answers = KnownAnswers::new();
+        let input = (0..64u8).collect::<Vec<_>>();
+        answers.push("jubjub.hash_1_2_3", &H::evaluate(&params, &[1, 2, 3]).unwrap().into_affine());
+        answers.push("jubjub.hash_0_to_31", &H::evaluate(&params, &input[..32]).unwrap().into_affine());
+        answers.push("jubjub.hash_0_to_63", &H::evaluate(&params, &input).unwrap().into_affine());
+        answers.check("src/crh/bowe_hopwood/test_vec/bowe_hopwood_kat");
+    }
 }
@@ -1,5 +1,5 @@
 use algebra::{
-    Field, bytes::ToBytes
+    Field, PrimeField, FpParameters, bytes::ToBytes, to_bytes
 };
 use rand::Rng;
 use std::hash::Hash;
@@ -11,7 +11,9 @@ pub mod pedersen;
 pub mod poseidon;
 pub use self::poseidon::*;
 
-use crate::Error;
+pub mod pow;
+
+use crate::{Error, CryptoError};
 
 
 pub trait FixedLengthCRH {
@@ -39,4 +41,23 @@ pub trait BatchFieldBasedHash {
     type Parameters: FieldBasedHashParameters<Fr = Self::Data>;
 
     fn batch_evaluate_2_1(input_array: &mut[Self::Data]);
-}
\ No newline at end of file
+}
+
+/// A personalization string (domain tag), separating the different uses of the same
+/// hash function (e.g. leaves vs inner nodes of a Merkle tree, nullifiers, transcripts).
+pub trait FieldBasedHashPersonalization: 'static {
+    const PERSONALIZATION: &'static [u8];
+}
+
+/// Encodes `personalization` as a single field element, by packing its bytes followed by
+/// a terminating 0x01 byte, so that different strings give different tags.
+pub fn personalization_tag<F: PrimeField>(personalization: &[u8]) -> Result<F, Error> {
+    let max_len = (<F::Params as FpParameters>::CAPACITY / 8) as usize;
+    if personalization.len() >= max_len {
+        return Err(Box::new(CryptoError::IncorrectInputLength("personalization".to_owned(), personalization.len())));
+    }
+    let mut bytes = personalization.to_vec();
+    bytes.push(1u8);
+    bytes.resize(to_bytes!(F::zero())?.len(), 0u8);
+    Ok(F::read(bytes.as_slice())?)
+}
@@ -1,5 +1,6 @@
-use crate::{Error, bytes_to_bits};
-use rand::Rng;
+use crate::{Error, CryptoError, bytes_to_bits};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
 use rayon::prelude::*;
 use std::{
     fmt::{Debug, Formatter, Result as FmtResult},
@@ -34,6 +35,21 @@ impl<G: Group, W: PedersenWindow> PedersenCRH<G, W> {
         generators_powers
     }
 
+    /// Deterministically generates the parameters personalized with `personalization`,
+    /// of at most 31 bytes: the generators are sampled from a ChaCha20 RNG seeded with
+    /// the personalization followed by a 0x01 byte and padded with zeros, so that
+    /// different personalizations give independent parameters.
+    pub fn setup_personalized(personalization: &[u8]) -> Result<PedersenParameters<G>, Error> {
+        let mut seed = [0u8; 32];
+        if personalization.len() >= seed.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("personalization".to_owned(), personalization.len())));
+        }
+        seed[..personalization.len()].copy_from_slice(personalization);
+        seed[personalization.len()] = 1u8;
+        let mut rng = ChaChaRng::from_seed(seed);
+        Ok(PedersenParameters { generators: Self::create_generators(&mut rng) })
+    }
+
     pub fn generator_powers<R: Rng>(num_powers: usize, rng: &mut R) -> Vec<G> {
         let mut cur_gen_powers = Vec::with_capacity(num_powers);
         let mut base = G::rand(rng);
@@ -134,3 +150,61 @@ impl<ConstraintF: Field, G: Group + ToConstraintField<ConstraintF>> ToConstraint
         Ok(Vec::new())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{
+        curves::{edwards_bls12::EdwardsProjective, jubjub::JubJubProjective},
+        kat::KnownAnswers,
+        ProjectiveCurve,
+    };
+    use rand_xorshift::XorShiftRng;
+
+    #[derive(Clone)]
+    struct TestWindow {}
+    impl PedersenWindow for TestWindow {
+        const WINDOW_SIZE: usize = 128;
+        const NUM_WINDOWS: usize = 4;
+    }
+
+    #[test]
+    fn test_pedersen_kat() {
+        type H = PedersenCRH<JubJubProjective, TestWindow>;
+
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let params = <H as FixedLengthCRH>::setup(rng).unwrap();
+
+        let mut answers = KnownAnswers::new();
+        let input = (0..64u8).collect::<Vec<_>>();
+        answers.push("jubjub.hash_1_2_3", &H::evaluate(&params, &[1, 2, 3]).unwrap().into_affine());
+        answers.push("jubjub.hash_0_to_31", &H::evaluate(&params, &input[..32]).unwrap().into_affine());
+        answers.push("jubjub.hash_0_to_63", &H::evaluate(&params, &input).unwrap().into_affine());
+        answers.check("src/crh/pedersen/test_vec/pedersen_kat");
+    }
+
+    #[derive(Clone)]
+    struct Window;
+
+    impl PedersenWindow for Window {
+        const WINDOW_SIZE: usize = 4;
+        const NUM_WINDOWS: usize = 8;
+    }
+
+    #[test]
+    fn personalized_setup_test() {
+        type H = PedersenCRH<EdwardsProjective, Window>;
+
+        let input = [1u8, 2, 3, 4];
+
+        let leaf_params = H::setup_personalized(b"leaf").unwrap();
+        let node_params = H::setup_personalized(b"node").unwrap();
+
+        // The parameters are deterministic, and independent for different personalizations
+        assert_eq!(H::evaluate(&leaf_params, &input).unwrap(), H::evaluate(&H::setup_personalized(b"leaf").unwrap(), &input).unwrap());
+        assert_ne!(H::evaluate(&leaf_params, &input).unwrap(), H::evaluate(&node_params, &input).unwrap());
+        assert_ne!(leaf_params.generators, H::setup_personalized(b"leaf\0").unwrap().generators);
+
+        assert!(H::setup_personalized(&[0u8; 32]).is_err());
+    }
+}
@@ -0,0 +1,378 @@
+//! Generation of the parameters of the Poseidon permutation for a prime field, following
+//! the reference implementation of the Poseidon paper (https://eprint.iacr.org/2019/458):
+//! the number of rounds is the cheapest one satisfying the security inequalities of the
+//! paper (Section 5.5 and Appendix C of the paper, as in the script `calc_round_numbers.py`),
+//! with the security margin of two full rounds and 7.5% more partial rounds, and the
+//! round constants and the MDS matrix are sampled by the Grain LFSR as in the script
+//! `generate_parameters_grain.sage`.
+use algebra::{BigInteger, FpParameters, PrimeField};
+
+use crate::{CryptoError, Error};
+
+/// The parameters of a Poseidon permutation with capacity of one field element, named
+/// after the constants of `PoseidonParameters`. The matrix `mds_cst` is a Cauchy matrix
+/// with full size entries, hence it does not come with the `MDS_CST_SHORT` constants
+/// which the fast matrix mix requires.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoseidonGeneratedParameters<F: PrimeField> {
+    pub t: usize,              // Number of S-Boxes
+    pub r_f: i32,              // Number of full rounds (for each of the two full rounds)
+    pub r_p: i32,              // Number of partial rounds
+    pub r: usize,              // The rate of the hash function
+    pub alpha: i64,            // The exponent of the S-Box, -1 for the inversion S-Box
+    pub round_cst: Vec<F>,     // Array of round constants
+    pub mds_cst: Vec<F>,       // The MDS matrix, row by row
+    pub after_zero_perm: Vec<F>, // State vector after a zero permutation
+}
+
+/// Generates the parameters of a Poseidon permutation over `F` for the given `rate`, the
+/// S-Box x^`alpha` (where `alpha` = -1 stands for the inversion S-Box), and `security`
+/// bits of security against the attacks considered by the Poseidon paper.
+pub fn generate_parameters<F: PrimeField>(
+    rate: usize,
+    alpha: i64,
+    security: usize,
+) -> Result<PoseidonGeneratedParameters<F>, Error> {
+    if rate == 0 {
+        return Err(Box::new(CryptoError::IncorrectInputLength("rate".to_owned(), rate)));
+    }
+    if !is_valid_alpha::<F>(alpha) {
+        return Err(Box::new(CryptoError::InvalidElement("alpha".to_owned())));
+    }
+    let t = rate + 1;
+    let (r_f, r_p) = match find_round_numbers::<F>(t, alpha, security) {
+        Some(round_numbers) => round_numbers,
+        None => return Err(Box::new(CryptoError::InvalidElement("security".to_owned()))),
+    };
+
+    let mut grain = GrainLfsr::new(F::size_in_bits(), t, r_f, r_p, alpha == -1);
+    let round_cst = (0..(r_f + r_p) * t)
+        .map(|_| grain.next_field_element_by_rejection::<F>())
+        .collect::<Vec<_>>();
+    let mds_cst = grain.next_cauchy_matrix::<F>(t);
+
+    let mut params = PoseidonGeneratedParameters {
+        t,
+        r_f: (r_f / 2) as i32,
+        r_p: r_p as i32,
+        r: rate,
+        alpha,
+        round_cst,
+        mds_cst,
+        after_zero_perm: vec![],
+    };
+    let mut state = vec![F::zero(); t];
+    params.permutation(&mut state);
+    params.after_zero_perm = state;
+
+    Ok(params)
+}
+
+impl<F: PrimeField> PoseidonGeneratedParameters<F> {
+    fn sbox(&self, x: F) -> F {
+        if self.alpha == -1 {
+            // The inversion S-Box maps zero to zero
+            x.inverse().unwrap_or(F::zero())
+        } else {
+            x.pow(&[self.alpha as u64])
+        }
+    }
+
+    fn matrix_mix(&self, state: &mut [F]) {
+        let new_state = self.mds_cst
+            .chunks(self.t)
+            .map(|row| row.iter().zip(state.iter()).fold(F::zero(), |acc, (m, s)| acc + &(*m * s)))
+            .collect::<Vec<_>>();
+        state.copy_from_slice(&new_state);
+    }
+
+    /// Applies the permutation to `state` as `PoseidonHash` does: `r_f` full rounds,
+    /// `r_p` partial rounds applying the S-Box to the first element only, and `r_f`
+    /// full rounds, the last of which does not perform the matrix mix.
+    pub fn permutation(&self, state: &mut [F]) {
+        assert_eq!(state.len(), self.t);
+        let num_rounds = (2 * self.r_f + self.r_p) as usize;
+        for (round, round_cst) in self.round_cst.chunks(self.t).take(num_rounds).enumerate() {
+            // Add the round constants to the state vector
+            for (d, rc) in state.iter_mut().zip(round_cst.iter()) {
+                *d += rc;
+            }
+
+            // Apply the S-Box to each element in the full rounds, and only to the
+            // first element in the partial rounds
+            let is_full_round = round < self.r_f as usize || round >= (self.r_f + self.r_p) as usize;
+            if is_full_round {
+                for d in state.iter_mut() {
+                    *d = self.sbox(*d);
+                }
+            } else {
+                state[0] = self.sbox(state[0]);
+            }
+
+            if round != num_rounds - 1 {
+                self.matrix_mix(state);
+            }
+        }
+    }
+}
+
+/// The S-Box x^alpha is a permutation iff gcd(alpha, p - 1) = 1.
+fn is_valid_alpha<F: PrimeField>(alpha: i64) -> bool {
+    if alpha == -1 {
+        return true;
+    }
+    if alpha < 3 {
+        return false;
+    }
+    let alpha = alpha as u64;
+    let p_mod_alpha = F::Params::MODULUS
+        .to_bits()
+        .iter()
+        .fold(0u64, |acc, bit| (2 * acc + *bit as u64) % alpha);
+    let (mut a, mut b) = (alpha, (p_mod_alpha + alpha - 1) % alpha);
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a == 1
+}
+
+/// Returns log2 of the modulus, using its 53 most significant bits.
+fn log2_modulus<F: PrimeField>() -> f64 {
+    let bits = F::Params::MODULUS.to_bits();
+    let num_top_bits = std::cmp::min(53, bits.len());
+    let top = bits[..num_top_bits]
+        .iter()
+        .fold(0u64, |acc, bit| 2 * acc + *bit as u64);
+    (top as f64).log2() + (bits.len() - num_top_bits) as f64
+}
+
+/// Checks the security inequalities of the Poseidon paper for `r_f` full rounds
+/// and `r_p` partial rounds.
+fn is_secure(
+    log2_p: f64,
+    field_size: usize,
+    t: usize,
+    r_f: usize,
+    r_p: usize,
+    alpha: i64,
+    security: usize,
+) -> bool {
+    let (t_f, r_f_f, r_p_f, m) = (t as f64, r_f as f64, r_p as f64, security as f64);
+    let min_m_n = std::cmp::min(security, field_size) as f64;
+    if alpha > 0 {
+        let log_alpha_2 = 1.0 / (alpha as f64).log2();
+        // Statistical attacks
+        let r_f_1 = if m <= (log2_p - ((alpha - 1) as f64) / 2.0).floor() * (t_f + 1.0) { 6.0 } else { 10.0 };
+        // Interpolation attack
+        let r_f_2 = 1.0 + (log_alpha_2 * min_m_n).ceil() + (t_f.log2() * log_alpha_2).ceil() - r_p_f;
+        // Groebner basis attacks
+        let r_f_3 = log_alpha_2 * m.min(log2_p) - r_p_f;
+        let r_f_4 = t_f - 1.0 + log_alpha_2 * (m / (t_f + 1.0)).min(log2_p / 2.0) - r_p_f;
+        let r_f_5 = (t_f - 2.0 + m / (2.0 * (alpha as f64).log2()) - r_p_f) / (t_f - 1.0);
+        let r_f_max = [r_f_1, r_f_2, r_f_3, r_f_4, r_f_5]
+            .iter()
+            .fold(0f64, |acc, r| acc.max(r.ceil()));
+        r_f_f >= r_f_max
+    } else {
+        // Statistical attacks
+        let r_f_1 = if m <= (log2_p - 2.0).floor() * (t_f + 1.0) { 6.0 } else { 10.0 };
+        // Interpolation attack
+        let r_p_1 = 1.0 + (0.5 * min_m_n).ceil() + t_f.log2().ceil() - (r_f_f * t_f.log2()).floor();
+        // Groebner basis attack
+        let r_p_2 = t_f - 1.0 + t_f.log2().ceil() + (m / (t_f + 1.0)).ceil().min((0.5 * log2_p).ceil())
+            - (r_f_f * t_f.log2()).floor();
+        r_f_f >= r_f_1 && r_p_f >= r_p_1.max(r_p_2)
+    }
+}
+
+/// Returns the total number of full rounds and the number of partial rounds, with security
+/// margin, which minimize the number of S-Boxes.
+fn find_round_numbers<F: PrimeField>(t: usize, alpha: i64, security: usize) -> Option<(usize, usize)> {
+    let log2_p = log2_modulus::<F>();
+    let field_size = F::size_in_bits();
+    let mut best: Option<(usize, usize, usize)> = None;
+    for r_p in 1..500 {
+        for r_f in (4..100).step_by(2) {
+            if !is_secure(log2_p, field_size, t, r_f, r_p, alpha, security) {
+                continue;
+            }
+            // Add the security margin
+            let r_f = r_f + 2;
+            let r_p = (r_p as f64 * 1.075).ceil() as usize;
+            let cost = r_f * t + r_p;
+            if best.map_or(true, |(best_cost, best_r_f, _)| cost < best_cost || (cost == best_cost && r_f < best_r_f)) {
+                best = Some((cost, r_f, r_p));
+            }
+        }
+    }
+    best.map(|(_, r_f, r_p)| (r_f, r_p))
+}
+
+/// The Grain LFSR used as pseudo-random generator by the reference implementation.
+struct GrainLfsr {
+    state: [bool; 80],
+    head:  usize,
+}
+
+impl GrainLfsr {
+    fn new(field_size: usize, t: usize, r_f: usize, r_p: usize, is_inverse_sbox: bool) -> Self {
+        // The initial state encodes the field type (1 for prime fields), the S-Box,
+        // the field size, the number of S-Boxes and of rounds, padded with ones.
+        let mut init = vec![];
+        for &(value, len) in [
+            (1, 2),
+            (is_inverse_sbox as usize, 4),
+            (field_size, 12),
+            (t, 12),
+            (r_f, 10),
+            (r_p, 10),
+        ].iter() {
+            init.extend((0..len).rev().map(|i| (value >> i) & 1 == 1));
+        }
+        let mut state = [true; 80];
+        state[..init.len()].copy_from_slice(&init);
+
+        let mut grain = Self { state, head: 0 };
+        // Discard the first 160 bits
+        for _ in 0..160 {
+            grain.clock();
+        }
+        grain
+    }
+
+    fn clock(&mut self) -> bool {
+        let bit = |i: usize| self.state[(self.head + i) % 80];
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.state[self.head] = new_bit;
+        self.head = (self.head + 1) % 80;
+        new_bit
+    }
+
+    /// Output bits are filtered in pairs: the second bit is output if the first one is set,
+    /// and discarded otherwise.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let sample = self.clock();
+            let bit = self.clock();
+            if sample {
+                return bit;
+            }
+        }
+    }
+
+    fn next_bits(&mut self, num_bits: usize) -> Vec<bool> {
+        (0..num_bits).map(|_| self.next_bit()).collect()
+    }
+
+    /// Samples big endian integers of the bit length of the modulus until one is smaller
+    /// than the modulus.
+    fn next_field_element_by_rejection<F: PrimeField>(&mut self) -> F {
+        loop {
+            let repr = F::BigInt::from_bits(&self.next_bits(F::size_in_bits()));
+            if repr < F::Params::MODULUS {
+                return F::from_repr(repr);
+            }
+        }
+    }
+
+    /// Samples a big endian integer of the bit length of the modulus, reduced modulo it.
+    fn next_field_element<F: PrimeField>(&mut self) -> F {
+        self.next_bits(F::size_in_bits())
+            .into_iter()
+            .fold(F::zero(), |acc, bit| if bit { acc.double() + &F::one() } else { acc.double() })
+    }
+
+    /// Samples the Cauchy matrix 1/(x_i + y_j) from pairwise distinct x_1, .., x_t,
+    /// y_1, .., y_t, such that no x_i + y_j is zero.
+    fn next_cauchy_matrix<F: PrimeField>(&mut self, t: usize) -> Vec<F> {
+        loop {
+            let mut elems = (0..2 * t).map(|_| self.next_field_element::<F>()).collect::<Vec<_>>();
+            while (0..2 * t).any(|i| elems[i + 1..].contains(&elems[i])) {
+                elems = (0..2 * t).map(|_| self.next_field_element::<F>()).collect();
+            }
+            let (xs, ys) = elems.split_at(t);
+            let matrix = xs
+                .iter()
+                .flat_map(|x| ys.iter().map(move |y| (*x + y).inverse()))
+                .collect::<Option<Vec<_>>>();
+            if let Some(matrix) = matrix {
+                return matrix;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::crh::poseidon::parameters::MNT4753PoseidonParameters;
+    use crate::crh::PoseidonParameters;
+    use algebra::Field;
+    use algebra::fields::{bls12_381::Fr as BLS12Fr, mnt4753::Fr as MNT4753Fr};
+
+    fn check_parameters<F: PrimeField>(params: &PoseidonGeneratedParameters<F>, security: usize) {
+        let (t, r_f, r_p) = (params.t, params.r_f as usize, params.r_p as usize);
+        assert_eq!(params.round_cst.len(), (2 * r_f + r_p) * t);
+        assert_eq!(params.mds_cst.len(), t * t);
+        assert_eq!(params.after_zero_perm.len(), t);
+
+        // The round numbers, without the security margin, are secure
+        let r_p_no_margin = (r_p as f64 / 1.075).floor() as usize;
+        assert!(is_secure(log2_modulus::<F>(), F::size_in_bits(), t, 2 * r_f - 2, r_p_no_margin, params.alpha, security));
+
+        // Cauchy matrices have all their minors of size 2 non zero
+        let m = |i: usize, j: usize| params.mds_cst[i * t + j];
+        for i1 in 0..t {
+            for i2 in (i1 + 1)..t {
+                for j1 in 0..t {
+                    for j2 in (j1 + 1)..t {
+                        assert!(!(m(i1, j1) * &m(i2, j2) - &(m(i1, j2) * &m(i2, j1))).is_zero());
+                    }
+                }
+            }
+        }
+
+        // The generation is deterministic
+        assert_eq!(params, &generate_parameters::<F>(params.r, params.alpha, security).unwrap());
+    }
+
+    #[test]
+    fn test_generate_parameters() {
+        let params = generate_parameters::<MNT4753Fr>(2, -1, 128).unwrap();
+        assert_eq!(params.t, 3);
+        check_parameters(&params, 128);
+
+        let params = generate_parameters::<BLS12Fr>(2, 5, 128).unwrap();
+        assert_eq!(params.t, 3);
+        check_parameters(&params, 128);
+
+        let params = generate_parameters::<BLS12Fr>(4, 5, 128).unwrap();
+        assert_eq!(params.t, 5);
+        check_parameters(&params, 128);
+
+        // 3 divides r - 1, hence x^3 is not a permutation
+        assert!(generate_parameters::<BLS12Fr>(2, 3, 128).is_err());
+        assert!(generate_parameters::<BLS12Fr>(2, 1, 128).is_err());
+        assert!(generate_parameters::<BLS12Fr>(0, 5, 128).is_err());
+    }
+
+    #[test]
+    fn test_permutation_consistency() {
+        // The permutation agrees with the one of PoseidonHash for the hardcoded parameters
+        let params = PoseidonGeneratedParameters::<MNT4753Fr> {
+            t: MNT4753PoseidonParameters::T,
+            r_f: MNT4753PoseidonParameters::R_F,
+            r_p: MNT4753PoseidonParameters::R_P,
+            r: MNT4753PoseidonParameters::R,
+            alpha: -1,
+            round_cst: MNT4753PoseidonParameters::ROUND_CST.to_vec(),
+            mds_cst: MNT4753PoseidonParameters::MDS_CST.to_vec(),
+            after_zero_perm: MNT4753PoseidonParameters::AFTER_ZERO_PERM.to_vec(),
+        };
+        let mut state = vec![MNT4753Fr::zero(); params.t];
+        params.permutation(&mut state);
+        assert_eq!(state, params.after_zero_perm);
+    }
+}
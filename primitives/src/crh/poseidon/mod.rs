@@ -14,11 +14,17 @@ use crate::crh::{
     }
 };
 
-use crate::crh::{FieldBasedHash, BatchFieldBasedHash};
+use crate::crh::{FieldBasedHash, BatchFieldBasedHash, FieldBasedHashPersonalization, personalization_tag};
+use crate::snapshot::{configuration_hash, Snapshot};
 use crate::Error;
+use algebra::bytes::{FromBytes, ToBytes};
+use std::io::{self, Read, Result as IoResult, Write};
 
 pub mod parameters;
 
+pub mod generator;
+pub use self::generator::*;
+
 pub struct PoseidonHash<F: PrimeField, P: PoseidonParameters<Fr = F>>{
     _field:      PhantomData<F>,
     _parameters: PhantomData<P>,
@@ -424,7 +430,7 @@ impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr=F>> PoseidonHash<F, P> {
         state[2] = new_state[2];
     }
 
-    fn poseidon_perm (state: &mut Vec<F>) {
+    pub(crate) fn poseidon_perm (state: &mut Vec<F>) {
 
         let use_fast = true;
 
@@ -573,15 +579,19 @@ impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr=F>> PoseidonHash<F, P> {
 }
 
 
-impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> FieldBasedHash for PoseidonHash<F, P> {
-    type Data = F;
-    type Parameters = P;
-
-    fn evaluate(input: &[F]) -> Result<F, Error> {
-
+impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> PoseidonHash<F, P> {
+    /// Returns the initial state of the hash: the state after a zero permutation, with
+    /// `personalization_tag` added to the capacity element.
+    pub fn initial_state(personalization_tag: Option<F>) -> Vec<F> {
         // state is a vector of 3 elements. They are initialized to constants that are obtained after applying a permutation to a zero elements vector
         let mut state = vec![P::AFTER_ZERO_PERM[0], P::AFTER_ZERO_PERM[1], P::AFTER_ZERO_PERM[2]];
+        if let Some(tag) = personalization_tag {
+            state[P::R] += &tag;
+        }
+        state
+    }
 
+    fn evaluate_from_state(mut state: Vec<F>, input: &[F]) -> F {
         // calculate the number of cycles to process the input dividing in portions of rate elements
         let num_cycles = input.len() / P::R;
         // check if the input is a multiple of the rate by calculating the remainder of the division
@@ -612,7 +622,133 @@ impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> FieldBasedHash for
         }
 
         // return the first element of the state vector as the hash digest
-        Ok(state[0])
+        state[0]
+    }
+}
+
+impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> FieldBasedHash for PoseidonHash<F, P> {
+    type Data = F;
+    type Parameters = P;
+
+    fn evaluate(input: &[F]) -> Result<F, Error> {
+        Ok(Self::evaluate_from_state(Self::initial_state(None), input))
+    }
+}
+
+/// Poseidon hash personalized with `T::PERSONALIZATION`, which is mixed into the capacity
+/// element of the initial state: hashes with different personalizations, or without
+/// personalization, are independent sponges at no additional cost.
+pub struct PersonalizedPoseidonHash<F: PrimeField, P: PoseidonParameters<Fr = F>, T: FieldBasedHashPersonalization>{
+    _field:           PhantomData<F>,
+    _parameters:      PhantomData<P>,
+    _personalization: PhantomData<T>,
+}
+
+impl<F, P, T> FieldBasedHash for PersonalizedPoseidonHash<F, P, T>
+    where
+        F: PrimeField + MulShort,
+        P: PoseidonParameters<Fr = F>,
+        T: FieldBasedHashPersonalization,
+{
+    type Data = F;
+    type Parameters = P;
+
+    fn evaluate(input: &[F]) -> Result<F, Error> {
+        let tag = personalization_tag::<F>(T::PERSONALIZATION)?;
+        Ok(PoseidonHash::<F, P>::evaluate_from_state(PoseidonHash::<F, P>::initial_state(Some(tag)), input))
+    }
+}
+
+/// Incremental computation of `PoseidonHash` (or of `PersonalizedPoseidonHash`), absorbing
+/// the input in pieces: the digest of the pieces absorbed so far is the hash of their
+/// concatenation. The state can be snapshotted and restored, to resume hashing a stream
+/// after a restart without rehashing it from the beginning.
+pub struct PoseidonSponge<F: PrimeField, P: PoseidonParameters<Fr = F>> {
+    state:       Vec<F>,
+    // The elements absorbed since the last permutation, less than the rate
+    pending:     Vec<F>,
+    _parameters: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: PoseidonParameters<Fr = F>> Clone for PoseidonSponge<F, P> {
+    fn clone(&self) -> Self {
+        Self {
+            state:       self.state.clone(),
+            pending:     self.pending.clone(),
+            _parameters: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> PoseidonSponge<F, P> {
+    /// Starts hashing, with the initial state of `PoseidonHash::initial_state`.
+    pub fn new(personalization_tag: Option<F>) -> Self {
+        Self {
+            state:       PoseidonHash::<F, P>::initial_state(personalization_tag),
+            pending:     Vec::with_capacity(P::R),
+            _parameters: PhantomData,
+        }
+    }
+
+    /// Absorbs `input`, permuting the state for each full chunk of rate elements.
+    pub fn update(&mut self, input: &[F]) {
+        for elem in input {
+            self.pending.push(*elem);
+            if self.pending.len() == P::R {
+                for (s, elem) in self.state.iter_mut().zip(self.pending.drain(..)) {
+                    *s += &elem;
+                }
+                self.state[P::R] += &P::C2;
+                PoseidonHash::<F, P>::poseidon_perm(&mut self.state);
+            }
+        }
+    }
+
+    /// Returns the hash of the input absorbed so far. Absorbing can go on afterwards.
+    pub fn digest(&self) -> F {
+        if self.pending.is_empty() {
+            return self.state[0];
+        }
+        let mut state = self.state.clone();
+        for (s, elem) in state.iter_mut().zip(self.pending.iter()) {
+            *s += elem;
+        }
+        state[P::R] += &P::C2;
+        PoseidonHash::<F, P>::poseidon_perm(&mut state);
+        state[0]
+    }
+}
+
+impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> Snapshot for PoseidonSponge<F, P> {
+    const MAGIC: [u8; 4] = *b"PSPG";
+    const NAME: &'static str = "Poseidon sponge";
+
+    /// Identifies the parameters by the sizes of the state and of the rate, and by
+    /// the state after a zero permutation.
+    fn configuration_id(&self) -> u64 {
+        let mut bytes = vec![];
+        (P::T as u64).write(&mut bytes).unwrap();
+        (P::R as u64).write(&mut bytes).unwrap();
+        for elem in P::AFTER_ZERO_PERM.iter() {
+            elem.write(&mut bytes).unwrap();
+        }
+        configuration_hash(&bytes)
+    }
+
+    fn write_state<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.state.write(&mut writer)?;
+        (self.pending.len() as u8).write(&mut writer)?;
+        self.pending.write(&mut writer)
+    }
+
+    fn read_state<R: Read>(mut reader: R) -> IoResult<Self> {
+        let state = (0..P::T).map(|_| F::read(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        let pending_len = u8::read(&mut reader)? as usize;
+        if pending_len >= P::R {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "too many pending elements"));
+        }
+        let pending = (0..pending_len).map(|_| F::read(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        Ok(Self { state, pending, _parameters: PhantomData })
     }
 }
 
@@ -704,6 +840,102 @@ mod test {
         println!("{:?}", output);
     }
 
+    #[test]
+    fn test_poseidon_personalization() {
+        struct LeafTag;
+        impl FieldBasedHashPersonalization for LeafTag {
+            const PERSONALIZATION: &'static [u8] = b"leaf";
+        }
+
+        struct NodeTag;
+        impl FieldBasedHashPersonalization for NodeTag {
+            const PERSONALIZATION: &'static [u8] = b"node";
+        }
+
+        struct EmptyTag;
+        impl FieldBasedHashPersonalization for EmptyTag {
+            const PERSONALIZATION: &'static [u8] = b"";
+        }
+
+        struct LongTag;
+        impl FieldBasedHashPersonalization for LongTag {
+            const PERSONALIZATION: &'static [u8] = &[0u8; 94];
+        }
+
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+        let input = [MNT4753Fr::rand(&mut rng), MNT4753Fr::rand(&mut rng)];
+
+        let plain = MNT4PoseidonHash::evaluate(&input).unwrap();
+        let leaf = PersonalizedPoseidonHash::<_, MNT4753PoseidonParameters, LeafTag>::evaluate(&input).unwrap();
+        let node = PersonalizedPoseidonHash::<_, MNT4753PoseidonParameters, NodeTag>::evaluate(&input).unwrap();
+        let empty = PersonalizedPoseidonHash::<_, MNT4753PoseidonParameters, EmptyTag>::evaluate(&input).unwrap();
+        assert_ne!(plain, leaf);
+        assert_ne!(plain, empty);
+        assert_ne!(leaf, node);
+        assert_ne!(leaf, empty);
+
+        // Tags are injective
+        assert_ne!(personalization_tag::<MNT4753Fr>(b"a").unwrap(), personalization_tag::<MNT4753Fr>(b"a\0").unwrap());
+
+        // The personalization must fit in a field element
+        assert!(PersonalizedPoseidonHash::<_, MNT4753PoseidonParameters, LongTag>::evaluate(&input).is_err());
+    }
+
+    #[test]
+    fn test_poseidon_sponge() {
+        struct LeafTag;
+        impl FieldBasedHashPersonalization for LeafTag {
+            const PERSONALIZATION: &'static [u8] = b"leaf";
+        }
+
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+        let input = (0..7).map(|_| MNT4753Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+        let mut sponge = PoseidonSponge::<_, MNT4753PoseidonParameters>::new(None);
+        assert_eq!(sponge.digest(), MNT4PoseidonHash::evaluate(&[]).unwrap());
+        sponge.update(&input[..3]);
+        assert_eq!(sponge.digest(), MNT4PoseidonHash::evaluate(&input[..3]).unwrap());
+
+        // Suspend in the middle of a chunk and resume from the snapshot
+        let mut snapshot = vec![];
+        sponge.write_snapshot(&mut snapshot).unwrap();
+        let mut resumed = PoseidonSponge::<_, MNT4753PoseidonParameters>::read_snapshot(&snapshot[..]).unwrap();
+        resumed.update(&input[3..]);
+        assert_eq!(resumed.digest(), MNT4PoseidonHash::evaluate(&input).unwrap());
+
+        // Snapshots of other parameters are rejected
+        assert!(PoseidonSponge::<MNT6753Fr, MNT6753PoseidonParameters>::read_snapshot(&snapshot[..]).is_err());
+        let mut corrupted = snapshot.clone();
+        corrupted[0] ^= 1;
+        assert!(PoseidonSponge::<_, MNT4753PoseidonParameters>::read_snapshot(&corrupted[..]).is_err());
+
+        let tag = personalization_tag::<MNT4753Fr>(LeafTag::PERSONALIZATION).unwrap();
+        let mut sponge = PoseidonSponge::<_, MNT4753PoseidonParameters>::new(Some(tag));
+        sponge.update(&input[..2]);
+        sponge.update(&input[2..]);
+        assert_eq!(
+            sponge.digest(),
+            PersonalizedPoseidonHash::<_, MNT4753PoseidonParameters, LeafTag>::evaluate(&input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_poseidon_kat() {
+        use algebra::kat::KnownAnswers;
+
+        let mut answers = KnownAnswers::new();
+
+        let input = [MNT4753Fr::from(1u64), MNT4753Fr::from(2u64), MNT4753Fr::from(3u64)];
+        answers.push("mnt4.hash_1_2", &MNT4PoseidonHash::evaluate(&input[..2]).unwrap());
+        answers.push("mnt4.hash_1_2_3", &MNT4PoseidonHash::evaluate(&input).unwrap());
+
+        let input = [MNT6753Fr::from(1u64), MNT6753Fr::from(2u64), MNT6753Fr::from(3u64)];
+        answers.push("mnt6.hash_1_2", &MNT6PoseidonHash::evaluate(&input[..2]).unwrap());
+        answers.push("mnt6.hash_1_2_3", &MNT6PoseidonHash::evaluate(&input).unwrap());
+
+        answers.check("src/crh/poseidon/test_vec/poseidon_kat");
+    }
+
     #[test]
     fn test_hash_speed() {
         // =============================================================================
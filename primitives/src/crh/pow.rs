@@ -0,0 +1,81 @@
+//! Proof-of-work style difficulty checks over field based hashes.
+//!
+//! A hash output meets a target iff the canonical integer representative of
+//! the output is strictly smaller than the target. The corresponding gadgets
+//! in `r1cs_crypto` enforce exactly the same relation.
+use algebra::{BigInteger, FpParameters, PrimeField};
+
+use crate::{crh::FieldBasedHash, Error};
+
+/// Returns the target met by the outputs with at least `leading_zeros`
+/// leading zero bits, out of the `MODULUS_BITS` bits of `F`, i.e.
+/// `2^(MODULUS_BITS - leading_zeros)`. A `leading_zeros` of 0 yields the
+/// modulus, which is met by any output, and a `leading_zeros` of
+/// `MODULUS_BITS` yields 1, which is met by the zero output only.
+///
+/// # Panics
+///
+/// If `leading_zeros` exceeds `MODULUS_BITS`.
+pub fn target_from_leading_zeros<F: PrimeField>(leading_zeros: u32) -> F::BigInt {
+    let modulus_bits = F::Params::MODULUS_BITS;
+    assert!(leading_zeros <= modulus_bits);
+    if leading_zeros == 0 {
+        return F::Params::MODULUS;
+    }
+    let mut target = F::BigInt::from(1);
+    target.muln(modulus_bits - leading_zeros);
+    target
+}
+
+/// Returns true iff `hash`, as an integer, is strictly smaller than `target`.
+pub fn meets_target<F: PrimeField>(hash: &F, target: &F::BigInt) -> bool {
+    hash.into_repr() < *target
+}
+
+/// Evaluates `H` on `input` and checks the output against `target`.
+pub fn check_pow<H>(input: &[H::Data], target: &<H::Data as PrimeField>::BigInt) -> Result<bool, Error>
+    where
+        H: FieldBasedHash,
+        H::Data: PrimeField,
+{
+    let hash = H::evaluate(input)?;
+    Ok(meets_target(&hash, target))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{fields::mnt4753::Fr as MNT4753Fr, Field};
+    use crate::crh::poseidon::MNT4PoseidonHash;
+
+    #[test]
+    fn test_target() {
+        let modulus_bits = <MNT4753Fr as PrimeField>::Params::MODULUS_BITS;
+
+        // Any output meets the trivial target, only zero meets the target 1.
+        let trivial = target_from_leading_zeros::<MNT4753Fr>(0);
+        let one = target_from_leading_zeros::<MNT4753Fr>(modulus_bits);
+        assert_eq!(one, <MNT4753Fr as PrimeField>::BigInt::from(1));
+        assert!(meets_target(&-MNT4753Fr::one(), &trivial));
+        assert!(meets_target(&MNT4753Fr::zero(), &one));
+        assert!(!meets_target(&MNT4753Fr::one(), &one));
+
+        // 2^(n - 1) - 1 has exactly one leading zero.
+        let mut x = MNT4753Fr::from(2u64).pow(&[(modulus_bits - 1) as u64]);
+        assert!(!meets_target(&x, &target_from_leading_zeros::<MNT4753Fr>(1)));
+        x -= &MNT4753Fr::one();
+        assert!(meets_target(&x, &target_from_leading_zeros::<MNT4753Fr>(1)));
+        assert!(!meets_target(&x, &target_from_leading_zeros::<MNT4753Fr>(2)));
+
+        // The check agrees with the hash output.
+        let input = [MNT4753Fr::from(1u64), MNT4753Fr::from(2u64)];
+        let hash = MNT4PoseidonHash::evaluate(&input).unwrap();
+        let leading_zeros = modulus_bits - hash.into_repr().num_bits();
+        for zeros in 0..=leading_zeros {
+            let target = target_from_leading_zeros::<MNT4753Fr>(zeros);
+            assert!(check_pow::<MNT4PoseidonHash>(&input, &target).unwrap());
+        }
+        let target = target_from_leading_zeros::<MNT4753Fr>(leading_zeros + 1);
+        assert!(!check_pow::<MNT4PoseidonHash>(&input, &target).unwrap());
+    }
+}
@@ -0,0 +1,222 @@
+//! Hashed ElGamal encryption of field elements, with the key stream derived
+//! by a field based hash from the Diffie-Hellman point. For `G` defined over
+//! the field `F` of the messages, the encryption is cheap to verify in a
+//! circuit over `F`.
+//!
+//! For a public key `pk = sk * G` and randomness `r`, the ciphertext of
+//! `(m_0, ..., m_{n-1})` is `(R, c_0, ..., c_{n-1})` with `R = r * G` and
+//! `c_i = m_i + H(S.x, S.y, i)`, where `S = r * pk = sk * R`.
+use crate::{crh::FieldBasedHash, encryption::FieldBasedEncryptionScheme, CryptoError, Error};
+use algebra::{Field, PrimeField, Group, UniformRand, ProjectiveCurve,
+              ToConstraintField, ToBytes, FromBytes};
+use std::marker::PhantomData;
+use rand::Rng;
+use std::io::{Write, Read, Result as IoResult};
+
+#[allow(dead_code)]
+pub struct FieldBasedElGamalEncryptionScheme<
+    F: PrimeField,
+    G: Group,
+    H: FieldBasedHash,
+>
+{
+    _field:    PhantomData<F>,
+    _group:    PhantomData<G>,
+    _hash:     PhantomData<H>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+Clone(bound = "F: PrimeField, G: ProjectiveCurve"),
+Default(bound = "F: PrimeField, G: ProjectiveCurve"),
+Eq(bound = "F: PrimeField, G: ProjectiveCurve"),
+PartialEq(bound = "F: PrimeField, G: ProjectiveCurve"),
+Debug(bound = "F: PrimeField, G: ProjectiveCurve")
+)]
+pub struct FieldBasedElGamalCiphertext<F: PrimeField, G: ProjectiveCurve> {
+    /// The commitment `r * G` to the randomness.
+    pub r:    G,
+    /// The encrypted message elements.
+    pub c:    Vec<F>,
+}
+
+impl<F: PrimeField, G: ProjectiveCurve> ToBytes for FieldBasedElGamalCiphertext<F, G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.r.write(&mut writer)?;
+        (self.c.len() as u32).write(&mut writer)?;
+        self.c.write(&mut writer)
+    }
+}
+
+impl<F: PrimeField, G: ProjectiveCurve> FromBytes for FieldBasedElGamalCiphertext<F, G> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let r = G::read(&mut reader)?;
+        let len = u32::read(&mut reader)?;
+        let c = (0..len).map(|_| F::read(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        Ok(Self{ r, c })
+    }
+}
+
+impl<F: PrimeField, G: ProjectiveCurve + ToConstraintField<F>, H: FieldBasedHash<Data = F>>
+FieldBasedElGamalEncryptionScheme<F, G, H>
+{
+    /// Returns the `len` elements of the key stream derived from the
+    /// Diffie-Hellman point `shared`.
+    pub fn key_stream(shared: &G, len: usize) -> Result<Vec<F>, Error> {
+        let shared_coords = shared.to_field_elements()?;
+        (0..len).map(|i| {
+            let mut hash_input = shared_coords.clone();
+            hash_input.push(F::from(i as u64));
+            H::evaluate(hash_input.as_slice())
+        }).collect()
+    }
+}
+
+impl<F: PrimeField, G: ProjectiveCurve + ToConstraintField<F>, H: FieldBasedHash<Data = F>> FieldBasedEncryptionScheme for
+FieldBasedElGamalEncryptionScheme<F, G, H>
+{
+    type Data = H::Data;
+    type PublicKey = G;
+    type SecretKey = G::ScalarField;
+    type Randomness = G::ScalarField;
+    type Ciphertext = FieldBasedElGamalCiphertext<F, G>;
+
+    fn keygen<R: Rng>(rng: &mut R) -> (Self::PublicKey, Self::SecretKey)
+    {
+        let secret_key = G::ScalarField::rand(rng);
+        let public_key = G::prime_subgroup_generator()
+            .mul(&secret_key);
+        (public_key, secret_key)
+    }
+
+    fn get_public_key(sk: &Self::SecretKey) -> Self::PublicKey {
+        G::prime_subgroup_generator().mul(sk)
+    }
+
+    fn encrypt<R: Rng>(
+        rng: &mut R,
+        pk: &Self::PublicKey,
+        message: &[Self::Data],
+    ) -> Result<(Self::Ciphertext, Self::Randomness), Error>
+    {
+        let randomness = loop {
+            let r = G::ScalarField::rand(rng);
+            if !r.is_zero() { break r };
+        };
+        let ciphertext = Self::encrypt_with_randomness(pk, message, &randomness)?;
+        Ok((ciphertext, randomness))
+    }
+
+    fn encrypt_with_randomness(
+        pk: &Self::PublicKey,
+        message: &[Self::Data],
+        randomness: &Self::Randomness,
+    ) -> Result<Self::Ciphertext, Error>
+    {
+        if randomness.is_zero() {
+            return Err(Box::new(CryptoError::InvalidElement("randomness".to_owned())));
+        }
+
+        //R = r * G, S = r * pk
+        let r = G::prime_subgroup_generator().mul(randomness);
+        let shared = pk.mul(randomness);
+
+        let c = Self::key_stream(&shared, message.len())?
+            .into_iter()
+            .zip(message)
+            .map(|(k, m)| k + m)
+            .collect();
+
+        Ok(FieldBasedElGamalCiphertext { r, c })
+    }
+
+    fn decrypt(
+        sk: &Self::SecretKey,
+        ciphertext: &Self::Ciphertext,
+    ) -> Result<Vec<Self::Data>, Error>
+    {
+        if !ciphertext.r.group_membership_test() {
+            return Err(Box::new(CryptoError::NotPrimeOrder("ciphertext.r".to_owned())));
+        }
+
+        //S = sk * R
+        let shared = ciphertext.r.mul(sk);
+
+        let message = Self::key_stream(&shared, ciphertext.c.len())?
+            .into_iter()
+            .zip(ciphertext.c.iter())
+            .map(|(k, c)| *c - &k)
+            .collect();
+
+        Ok(message)
+    }
+
+    fn keyverify(pk: &Self::PublicKey) -> bool
+    {
+        pk.group_membership_test()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use algebra::curves::{
+        mnt4753::G1Projective as MNT4G1Projective,
+        mnt6753::G1Projective as MNT6G1Projective,
+    };
+    use algebra::fields::{
+        mnt4753::Fr as MNT4Fr,
+        mnt6753::Fr as MNT6Fr,
+    };
+    use algebra::{to_bytes, ToBytes, FromBytes, UniformRand};
+    use crate::crh::{MNT4PoseidonHash, MNT6PoseidonHash};
+    use crate::encryption::{
+        FieldBasedEncryptionScheme, field_based_elgamal::FieldBasedElGamalEncryptionScheme,
+    };
+    use rand::{Rng, thread_rng};
+
+    type ElGamalMNT4 = FieldBasedElGamalEncryptionScheme<MNT4Fr, MNT6G1Projective, MNT4PoseidonHash>;
+    type ElGamalMNT6 = FieldBasedElGamalEncryptionScheme<MNT6Fr, MNT4G1Projective, MNT6PoseidonHash>;
+
+    fn encrypt_and_decrypt<S: FieldBasedEncryptionScheme, R: Rng>(rng: &mut R, message: &[S::Data]) {
+        let (pk, sk) = S::keygen(rng);
+        assert!(S::keyverify(&pk));
+        assert_eq!(pk, S::get_public_key(&sk));
+
+        let (ciphertext, randomness) = S::encrypt(rng, &pk, message).unwrap();
+        assert_eq!(S::decrypt(&sk, &ciphertext).unwrap(), message.to_vec());
+        assert_eq!(S::encrypt_with_randomness(&pk, message, &randomness).unwrap(), ciphertext);
+
+        //Serialization/deserialization test
+        let ciphertext_serialized = to_bytes!(ciphertext).unwrap();
+        let ciphertext_deserialized = <S as FieldBasedEncryptionScheme>::Ciphertext::read(ciphertext_serialized.as_slice()).unwrap();
+        assert_eq!(ciphertext, ciphertext_deserialized);
+
+        //Decryption with a different secret key fails
+        let (_, wrong_sk) = S::keygen(rng);
+        assert_ne!(S::decrypt(&wrong_sk, &ciphertext).unwrap(), message.to_vec());
+
+        //Encryptions of the same message are randomized
+        let (other_ciphertext, _) = S::encrypt(rng, &pk, message).unwrap();
+        assert_ne!(ciphertext, other_ciphertext);
+    }
+
+    #[test]
+    fn mnt4_elgamal_test() {
+        let rng = &mut thread_rng();
+        let samples = 10;
+        for _ in 0..samples {
+            let message = (0..3).map(|_| MNT4Fr::rand(rng)).collect::<Vec<_>>();
+            encrypt_and_decrypt::<ElGamalMNT4, _>(rng, &message);
+        }
+    }
+
+    #[test]
+    fn mnt6_elgamal_test() {
+        let rng = &mut thread_rng();
+        let samples = 10;
+        for _ in 0..samples {
+            let message = (0..3).map(|_| MNT6Fr::rand(rng)).collect::<Vec<_>>();
+            encrypt_and_decrypt::<ElGamalMNT6, _>(rng, &message);
+        }
+    }
+}
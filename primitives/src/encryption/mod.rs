@@ -0,0 +1,57 @@
+use crate::Error;
+use algebra::{
+    bytes::{
+        ToBytes, FromBytes
+    },
+    Field,
+};
+use rand::Rng;
+use std::hash::Hash;
+use std::fmt::Debug;
+
+pub mod field_based_elgamal;
+
+/// An encryption scheme for vectors of field elements, whose encryption can
+/// be verified in-circuit against the encrypted values, e.g. to encrypt
+/// selected witness values of a SNARK under the public key of an auditor.
+pub trait FieldBasedEncryptionScheme {
+
+    type Data: Field;
+    type PublicKey: ToBytes + Hash + Eq + Clone + Default + Debug + Send + Sync;
+    type SecretKey: ToBytes + Clone + Default;
+    type Randomness: Copy + Clone + Default + Debug + Send + Sync;
+    type Ciphertext: Clone + Default + Send + Sync + Debug + Eq + PartialEq + ToBytes + FromBytes;
+
+    fn keygen<R: Rng>(
+        rng: &mut R,
+    ) -> (Self::PublicKey, Self::SecretKey);
+
+    fn get_public_key(
+        sk: &Self::SecretKey
+    ) -> Self::PublicKey;
+
+    /// Encrypts `message` under `pk`, using fresh randomness sampled from
+    /// `rng`. The randomness is returned as well, as it is needed to prove
+    /// the correctness of the encryption.
+    fn encrypt<R: Rng>(
+        rng: &mut R,
+        pk: &Self::PublicKey,
+        message: &[Self::Data],
+    ) -> Result<(Self::Ciphertext, Self::Randomness), Error>;
+
+    /// Encrypts `message` under `pk` using the given `randomness`.
+    fn encrypt_with_randomness(
+        pk: &Self::PublicKey,
+        message: &[Self::Data],
+        randomness: &Self::Randomness,
+    ) -> Result<Self::Ciphertext, Error>;
+
+    fn decrypt(
+        sk: &Self::SecretKey,
+        ciphertext: &Self::Ciphertext,
+    ) -> Result<Vec<Self::Data>, Error>;
+
+    fn keyverify(
+        pk: &Self::PublicKey,
+    ) -> bool;
+}
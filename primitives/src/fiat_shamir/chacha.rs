@@ -0,0 +1,94 @@
+use crate::{fiat_shamir::FiatShamirRng, Error};
+use algebra::{bytes::ToBytes, to_bytes, PrimeField};
+use digest::Digest;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use std::marker::PhantomData;
+
+/// A random oracle for native use only: the absorbed messages are chained into
+/// a seed by the hash `D`, and the challenges are drawn from a ChaCha20 RNG
+/// seeded with it. Outputs of `D` shorter than the 32 bytes of a ChaCha20 seed
+/// are expanded by hashing them with a counter.
+pub struct ChaChaFSRng<F: PrimeField, D: Digest> {
+    seed:   Vec<u8>,
+    rng:    ChaChaRng,
+    _field: PhantomData<F>,
+    _hash:  PhantomData<D>,
+}
+
+impl<F: PrimeField, D: Digest> ChaChaFSRng<F, D> {
+    fn from_seed_bytes(seed: Vec<u8>) -> Self {
+        let mut chacha_seed = [0u8; 32];
+        if seed.len() >= 32 {
+            chacha_seed.copy_from_slice(&seed[..32]);
+        } else {
+            let mut expanded = Vec::with_capacity(64);
+            let mut counter = 0u8;
+            while expanded.len() < 32 {
+                let mut hash_input = vec![counter];
+                hash_input.extend_from_slice(&seed);
+                expanded.extend_from_slice(&D::digest(&hash_input));
+                counter += 1;
+            }
+            chacha_seed.copy_from_slice(&expanded[..32]);
+        }
+        Self {
+            seed,
+            rng: ChaChaRng::from_seed(chacha_seed),
+            _field: PhantomData,
+            _hash: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField, D: Digest> FiatShamirRng for ChaChaFSRng<F, D> {
+    type Field = F;
+
+    fn new(domain: &[u8]) -> Result<Self, Error> {
+        let mut hash_input = to_bytes![domain.len() as u64]?;
+        hash_input.extend_from_slice(domain);
+        Ok(Self::from_seed_bytes(D::digest(&hash_input).to_vec()))
+    }
+
+    fn absorb_bytes(&mut self, bytes: &[u8]) {
+        // The length prefix makes the chaining injective
+        let mut hash_input = self.seed.clone();
+        hash_input.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+        hash_input.extend_from_slice(bytes);
+        *self = Self::from_seed_bytes(D::digest(&hash_input).to_vec());
+    }
+
+    fn absorb_field_elements(&mut self, elems: &[F]) {
+        let mut bytes = Vec::new();
+        for elem in elems {
+            elem.write(&mut bytes).expect("writing to a vector cannot fail");
+        }
+        self.absorb_bytes(&bytes);
+    }
+
+    fn squeeze_field_elements(&mut self, num: usize) -> Vec<F> {
+        (0..num).map(|_| F::rand(&mut self.rng)).collect()
+    }
+}
+
+impl<F: PrimeField, D: Digest> RngCore for ChaChaFSRng<F, D> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
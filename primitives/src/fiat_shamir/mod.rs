@@ -0,0 +1,103 @@
+//! Random oracles for the Fiat-Shamir transform. A protocol absorbs the
+//! messages of the prover into a `FiatShamirRng` and squeezes its challenges
+//! from it, and chooses the backend per instance: `PoseidonFSRng`, an algebraic
+//! sponge whose challenges can be recomputed in a circuit at a low cost, or
+//! `ChaChaFSRng`, a hash and a stream cipher, much faster natively.
+use crate::Error;
+use algebra::{bytes::ToBytes, to_bytes, PrimeField};
+use rand::RngCore;
+
+pub mod chacha;
+pub use self::chacha::*;
+
+pub mod poseidon;
+pub use self::poseidon::*;
+
+pub trait FiatShamirRng: RngCore + Sized {
+    /// The field of the challenges.
+    type Field: PrimeField;
+
+    /// Returns a random oracle domain separated by `domain`, e.g. the name of
+    /// the protocol and an identifier of its instance.
+    fn new(domain: &[u8]) -> Result<Self, Error>;
+
+    fn absorb_bytes(&mut self, bytes: &[u8]);
+
+    fn absorb_field_elements(&mut self, elems: &[Self::Field]);
+
+    /// Absorbs the serialization of `item`.
+    fn absorb<T: ToBytes>(&mut self, item: &T) -> Result<(), Error> {
+        self.absorb_bytes(&to_bytes!(item)?);
+        Ok(())
+    }
+
+    fn squeeze_field_elements(&mut self, num: usize) -> Vec<Self::Field>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::crh::parameters::MNT4753PoseidonParameters;
+    use algebra::{fields::mnt4753::Fr as MNT4753Fr, Field};
+    use blake2::Blake2s;
+    use sha2::Sha224;
+
+    fn test_fiat_shamir_rng<FS: FiatShamirRng>() {
+        let elems = [FS::Field::from(1u64), FS::Field::from(2u64), FS::Field::from(3u64)];
+
+        // Deterministic
+        let mut fs = FS::new(b"protocol").unwrap();
+        fs.absorb_bytes(b"statement");
+        fs.absorb_field_elements(&elems);
+        let challenges = fs.squeeze_field_elements(3);
+        let mut fs_2 = FS::new(b"protocol").unwrap();
+        fs_2.absorb_bytes(b"statement");
+        fs_2.absorb_field_elements(&elems);
+        assert_eq!(fs_2.squeeze_field_elements(3), challenges);
+        assert_ne!(challenges[0], challenges[1]);
+        assert_ne!(challenges[1], challenges[2]);
+
+        // Squeezing again gives fresh challenges
+        let next = fs.squeeze_field_elements(1);
+        assert_ne!(next[0], challenges[0]);
+        let mut bytes = [0u8; 40];
+        fs.fill_bytes(&mut bytes);
+        assert_ne!(bytes, [0u8; 40]);
+
+        // Domain separated
+        let mut fs_2 = FS::new(b"other protocol").unwrap();
+        fs_2.absorb_bytes(b"statement");
+        fs_2.absorb_field_elements(&elems);
+        assert_ne!(fs_2.squeeze_field_elements(3), challenges);
+
+        // Bound to the absorbed messages, including their length
+        let mut fs_2 = FS::new(b"protocol").unwrap();
+        fs_2.absorb_bytes(b"statement");
+        fs_2.absorb_field_elements(&elems[..2]);
+        assert_ne!(fs_2.squeeze_field_elements(3), challenges);
+        let mut fs_2 = FS::new(b"protocol").unwrap();
+        fs_2.absorb_bytes(b"statement");
+        fs_2.absorb_field_elements(&[elems[0], elems[1], elems[2], FS::Field::zero()]);
+        assert_ne!(fs_2.squeeze_field_elements(3), challenges);
+        let mut fs_2 = FS::new(b"protocol").unwrap();
+        fs_2.absorb_bytes(b"statement\0");
+        fs_2.absorb_field_elements(&elems);
+        assert_ne!(fs_2.squeeze_field_elements(3), challenges);
+    }
+
+    #[test]
+    fn test_poseidon_fs_rng() {
+        test_fiat_shamir_rng::<PoseidonFSRng<MNT4753Fr, MNT4753PoseidonParameters>>();
+
+        // The domain must fit in a field element
+        assert!(PoseidonFSRng::<MNT4753Fr, MNT4753PoseidonParameters>::new(&[0u8; 94]).is_err());
+    }
+
+    #[test]
+    fn test_chacha_fs_rng() {
+        test_fiat_shamir_rng::<ChaChaFSRng<MNT4753Fr, Blake2s>>();
+
+        // Digests shorter than a ChaCha20 seed are expanded
+        test_fiat_shamir_rng::<ChaChaFSRng<MNT4753Fr, Sha224>>();
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    crh::{personalization_tag, poseidon::{PoseidonHash, PoseidonParameters}},
+    fiat_shamir::FiatShamirRng,
+    Error,
+};
+use algebra::{bytes::ToBytes, FpParameters, MulShort, PrimeField};
+use rand::RngCore;
+use std::marker::PhantomData;
+
+/// A random oracle given by the duplex sponge of the Poseidon permutation, whose
+/// state is initialized as the one of `PersonalizedPoseidonHash` with the domain
+/// as personalization. The absorbed field elements are added to the rate, each
+/// chunk together with its length to the capacity, and the challenges are
+/// the first elements of the state after each permutation.
+/// `PoseidonFSRngGadget` computes the same challenges in a circuit.
+pub struct PoseidonFSRng<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> {
+    state:       Vec<F>,
+    pending:     Vec<F>,
+    _parameters: PhantomData<P>,
+}
+
+impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> PoseidonFSRng<F, P> {
+    fn squeeze_field_element(&mut self) -> F {
+        if self.pending.is_empty() {
+            PoseidonHash::<F, P>::poseidon_perm(&mut self.state);
+        } else {
+            for chunk in self.pending.chunks(P::R) {
+                for (s, elem) in self.state.iter_mut().zip(chunk) {
+                    *s += elem;
+                }
+                self.state[P::R] += &F::from(chunk.len() as u64);
+                PoseidonHash::<F, P>::poseidon_perm(&mut self.state);
+            }
+            self.pending.clear();
+        }
+        self.state[0]
+    }
+}
+
+impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> FiatShamirRng for PoseidonFSRng<F, P> {
+    type Field = F;
+
+    fn new(domain: &[u8]) -> Result<Self, Error> {
+        let tag = personalization_tag::<F>(domain)?;
+        Ok(Self {
+            state:       PoseidonHash::<F, P>::initial_state(Some(tag)),
+            pending:     vec![],
+            _parameters: PhantomData,
+        })
+    }
+
+    /// Absorbs the length of `bytes`, and `bytes` packed into field elements of
+    /// `CAPACITY / 8` bytes each.
+    fn absorb_bytes(&mut self, bytes: &[u8]) {
+        let chunk_size = (F::Params::CAPACITY / 8) as usize;
+        self.pending.push(F::from(bytes.len() as u64));
+        for chunk in bytes.chunks(chunk_size) {
+            let mut padded = chunk.to_vec();
+            padded.resize(F::zero().into_repr().as_ref().len() * 8, 0u8);
+            self.pending.push(F::read(padded.as_slice()).expect("the chunk is smaller than the modulus"));
+        }
+    }
+
+    fn absorb_field_elements(&mut self, elems: &[F]) {
+        self.pending.extend_from_slice(elems);
+    }
+
+    fn squeeze_field_elements(&mut self, num: usize) -> Vec<F> {
+        (0..num).map(|_| self.squeeze_field_element()).collect()
+    }
+}
+
+impl<F: PrimeField + MulShort, P: PoseidonParameters<Fr = F>> RngCore for PoseidonFSRng<F, P> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Fills `dest` with the low `CAPACITY / 8` bytes of the challenges, which
+    /// are close to uniform.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let chunk_size = (F::Params::CAPACITY / 8) as usize;
+        for chunk in dest.chunks_mut(chunk_size) {
+            let mut bytes = Vec::new();
+            self.squeeze_field_element()
+                .into_repr()
+                .write(&mut bytes)
+                .expect("writing to a vector cannot fail");
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
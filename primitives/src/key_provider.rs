@@ -0,0 +1,215 @@
+//! Access to long-term secret keys kept outside of the application, e.g. in a
+//! key store on disk or in a hardware security module.
+//!
+//! The algorithms of this crate need the secret scalars themselves, so a
+//! provider lends a key for the duration of a single operation only, through
+//! `with_secret_key`, instead of handing it out. Signatures, VRF proofs and
+//! commitments can be computed with keys of a provider, see
+//! `sign_with_key_provider`, `prove_with_key_provider` and
+//! `commit_with_key_provider`.
+//!
+//! No provider backed by a HSM is included: as the scalars must leave the HSM
+//! to be used by these algorithms, unwrapping them is specific to the HSM and
+//! its policy, e.g. through a PKCS#11 unwrap mechanism. Such a provider is
+//! implemented by the application, unwrapping the key inside `with_secret_key`
+//! and wiping it when the operation returns.
+use crate::{CryptoError, Error};
+use algebra::bytes::FromBytes;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    ptr,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+/// A custodian of secret keys of type `SK`, identified by their key ids.
+pub trait KeyProvider<SK> {
+    /// Calls `f` with the secret key `key_id`, which is not retained by the
+    /// caller after `f` returns. Fails if there is no such key or it cannot
+    /// be accessed.
+    fn with_secret_key<T, F: FnOnce(&SK) -> T>(&self, key_id: &str, f: F) -> Result<T, Error>;
+
+    /// Returns true if the provider holds the secret key `key_id`.
+    fn contains_key(&self, key_id: &str) -> bool;
+}
+
+/// Secret keys held in memory, e.g. for tests or for ephemeral keys.
+pub struct InMemoryKeyProvider<SK> {
+    keys: HashMap<String, SK>,
+}
+
+impl<SK> InMemoryKeyProvider<SK> {
+    pub fn new() -> Self {
+        Self { keys: HashMap::new() }
+    }
+
+    /// Stores `sk` under `key_id`, replacing the previous key, if any.
+    pub fn insert(&mut self, key_id: &str, sk: SK) {
+        self.keys.insert(key_id.to_owned(), sk);
+    }
+
+    /// Removes the key `key_id`, returning true if there was one.
+    pub fn remove(&mut self, key_id: &str) -> bool {
+        self.keys.remove(key_id).is_some()
+    }
+}
+
+impl<SK> Default for InMemoryKeyProvider<SK> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<SK> KeyProvider<SK> for InMemoryKeyProvider<SK> {
+    fn with_secret_key<T, F: FnOnce(&SK) -> T>(&self, key_id: &str, f: F) -> Result<T, Error> {
+        let sk = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| CryptoError::KeyNotFound(key_id.to_owned()))?;
+        Ok(f(sk))
+    }
+
+    fn contains_key(&self, key_id: &str) -> bool {
+        self.keys.contains_key(key_id)
+    }
+}
+
+/// Secret keys stored in a directory, one file per key named after its key
+/// id, holding the `ToBytes` serialization of the key. The files are read on
+/// each access, so that keys can be rotated without restarting, and the key
+/// and the bytes it was read from are wiped once the operation returns. The
+/// keys are stored in plaintext: the directory must be protected by the file
+/// system permissions, or by encryption at rest.
+pub struct FileKeyProvider {
+    dir: PathBuf,
+}
+
+impl FileKeyProvider {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self { dir: dir.as_ref().to_path_buf() }
+    }
+
+    /// Returns the path of the key `key_id`. Key ids are restricted to ASCII
+    /// alphanumerics, '-' and '_', so that they cannot escape the directory.
+    pub fn key_path(&self, key_id: &str) -> Result<PathBuf, Error> {
+        if key_id.is_empty()
+            || !key_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(Box::new(CryptoError::InvalidKeyId(key_id.to_owned())));
+        }
+        Ok(self.dir.join(key_id))
+    }
+}
+
+impl<SK: FromBytes + Default> KeyProvider<SK> for FileKeyProvider {
+    fn with_secret_key<T, F: FnOnce(&SK) -> T>(&self, key_id: &str, f: F) -> Result<T, Error> {
+        let path = self.key_path(key_id)?;
+        if !path.is_file() {
+            return Err(Box::new(CryptoError::KeyNotFound(key_id.to_owned())));
+        }
+        let mut bytes = fs::read(path)?;
+        let sk = SK::read(bytes.as_slice());
+        wipe_bytes(&mut bytes);
+        let mut sk = sk?;
+        let result = f(&sk);
+        wipe(&mut sk);
+        Ok(result)
+    }
+
+    fn contains_key(&self, key_id: &str) -> bool {
+        self.key_path(key_id).map(|path| path.is_file()).unwrap_or(false)
+    }
+}
+
+/// Overwrites `bytes` with zeros, without the writes being optimized out.
+#[allow(unsafe_code)]
+fn wipe_bytes(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // Safe, as `byte` is valid for writes
+        unsafe { ptr::write_volatile(byte, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Replaces `value` by its default, without the write being optimized out.
+#[allow(unsafe_code)]
+fn wipe<T: Default>(value: &mut T) {
+    let default = T::default();
+    // Safe, as `value` is valid for writes, and is dropped before it is
+    // overwritten without being read
+    unsafe {
+        ptr::drop_in_place(value);
+        ptr::write_volatile(value, default);
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{fields::mnt4753::Fr, to_bytes, ToBytes, UniformRand};
+    use rand::thread_rng;
+    use std::fs;
+
+    #[test]
+    fn in_memory_key_provider() {
+        let sk = Fr::rand(&mut thread_rng());
+        let mut provider = InMemoryKeyProvider::new();
+        provider.insert("validator-1", sk);
+
+        assert!(provider.contains_key("validator-1"));
+        assert_eq!(provider.with_secret_key("validator-1", |key| *key).unwrap(), sk);
+        assert!(provider.with_secret_key("validator-2", |key| *key).is_err());
+
+        assert!(provider.remove("validator-1"));
+        assert!(!provider.contains_key("validator-1"));
+    }
+
+    #[test]
+    fn file_key_provider() {
+        let dir = std::env::temp_dir().join(format!("ginger-key-provider-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let sk = Fr::rand(&mut thread_rng());
+        let provider = FileKeyProvider::new(&dir);
+        fs::write(provider.key_path("validator_1").unwrap(), to_bytes!(sk).unwrap()).unwrap();
+
+        assert!(KeyProvider::<Fr>::contains_key(&provider, "validator_1"));
+        assert_eq!(provider.with_secret_key("validator_1", |key: &Fr| *key).unwrap(), sk);
+        assert!(provider.with_secret_key("validator_2", |key: &Fr| *key).is_err());
+
+        // Key ids cannot escape the directory
+        match provider.key_path("../validator_1") {
+            Err(e) => match e.downcast_ref::<CryptoError>() {
+                Some(CryptoError::InvalidKeyId(key_id)) => assert_eq!(key_id, "../validator_1"),
+                _ => panic!("unexpected error {}", e),
+            },
+            Ok(_) => panic!("key id escaping the directory accepted"),
+        }
+        assert!(provider.key_path("").is_err());
+        assert!(!KeyProvider::<Fr>::contains_key(&provider, "../validator_1"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "commitment")]
+    #[test]
+    fn commit_with_key_provider() {
+        use crate::commitment::{blake2s::Blake2sCommitment, CommitmentScheme};
+        use rand::Rng;
+
+        let randomness: [u8; 32] = thread_rng().gen();
+        let mut provider = InMemoryKeyProvider::new();
+        provider.insert("blinding-1", randomness);
+
+        let input = b"committed value";
+        let expected = Blake2sCommitment::commit(&(), input, &randomness).unwrap();
+        let commitment =
+            Blake2sCommitment::commit_with_key_provider(&(), input, &provider, "blinding-1").unwrap();
+        assert_eq!(commitment, expected);
+        assert!(Blake2sCommitment::commit_with_key_provider(&(), input, &provider, "blinding-2").is_err());
+    }
+}
@@ -3,8 +3,11 @@ extern crate bench_utils;
 
 #[cfg(any(
     feature = "commitment",
+    feature = "encryption",
     feature = "merkle_tree",
     feature = "prf",
+    feature = "range_proof",
+    feature = "sigma",
     feature = "signature",
     feature = "vrf"
 ))]
@@ -14,11 +17,30 @@ extern crate derivative;
 pub mod crh;
 pub use self::crh::*;
 
+pub mod transcript;
+pub use self::transcript::*;
+
+pub mod key_provider;
+pub use self::key_provider::*;
+
+pub mod snapshot;
+pub use self::snapshot::*;
+
 #[cfg(feature = "commitment")]
 pub mod commitment;
 #[cfg(feature = "commitment")]
 pub use self::commitment::*;
 
+#[cfg(feature = "encryption")]
+pub mod encryption;
+#[cfg(feature = "encryption")]
+pub use self::encryption::*;
+
+#[cfg(feature = "fiat_shamir")]
+pub mod fiat_shamir;
+#[cfg(feature = "fiat_shamir")]
+pub use self::fiat_shamir::*;
+
 #[cfg(feature = "merkle_tree")]
 pub mod merkle_tree;
 #[cfg(feature = "merkle_tree")]
@@ -29,6 +51,16 @@ pub mod prf;
 #[cfg(feature = "prf")]
 pub use self::prf::*;
 
+#[cfg(feature = "range_proof")]
+pub mod range_proof;
+#[cfg(feature = "range_proof")]
+pub use self::range_proof::*;
+
+#[cfg(feature = "sigma")]
+pub mod sigma;
+#[cfg(feature = "sigma")]
+pub use self::sigma::*;
+
 #[cfg(feature = "signature")]
 pub mod signature;
 #[cfg(feature = "signature")]
@@ -48,6 +80,8 @@ pub enum CryptoError {
     InvalidElement(String),
     NotPrimeOrder(String),
     FailedVerification,
+    KeyNotFound(String),
+    InvalidKeyId(String),
 }
 
 impl std::fmt::Display for CryptoError {
@@ -57,6 +91,8 @@ impl std::fmt::Display for CryptoError {
             CryptoError::InvalidElement(elem) => format!("{} is invalid", elem),
             CryptoError::NotPrimeOrder(elem) => format!("element {} is not prime order", elem),
             CryptoError::FailedVerification => "verification failed".to_owned(),
+            CryptoError::KeyNotFound(key_id) => format!("no secret key {}", key_id),
+            CryptoError::InvalidKeyId(key_id) => format!("invalid key id {:?}", key_id),
         };
         write!(f, "{}", msg)
     }
@@ -82,9 +118,30 @@ pub fn compute_truncation_size(modulus_from: i32, modulus_to: i32) -> usize {
 }
 
 use algebra::{
+    capabilities::{self as algebra_capabilities, Capabilities},
     PrimeField, FpParameters,
 };
 
+/// Returns the capabilities of this crate and of `algebra`.
+pub fn capabilities() -> Capabilities {
+    algebra_capabilities::capabilities().extend(
+        ("primitives", env!("CARGO_PKG_VERSION")),
+        &[
+            ("primitives/commitment", cfg!(feature = "commitment")),
+            ("primitives/encryption", cfg!(feature = "encryption")),
+            ("primitives/fiat_shamir", cfg!(feature = "fiat_shamir")),
+            ("primitives/merkle_tree", cfg!(feature = "merkle_tree")),
+            ("primitives/mmap", cfg!(feature = "mmap")),
+            ("primitives/prf", cfg!(feature = "prf")),
+            ("primitives/range_proof", cfg!(feature = "range_proof")),
+            ("primitives/sigma", cfg!(feature = "sigma")),
+            ("primitives/signature", cfg!(feature = "signature")),
+            ("primitives/vrf", cfg!(feature = "vrf")),
+        ],
+        &[],
+    )
+}
+
 /// Return the number of bytes to skip in a little-endian byte order representation
 /// of a field element belonging to field `F`.
 #[allow(dead_code)]
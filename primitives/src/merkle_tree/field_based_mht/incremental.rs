@@ -0,0 +1,260 @@
+use crate::{
+    crh::FieldBasedHash,
+    snapshot::{configuration_hash, Snapshot},
+    Error,
+};
+use super::*;
+use algebra::bytes::{FromBytes, ToBytes};
+use std::io::{self, Read, Write, Result as IoResult};
+
+/// Append-only Merkle Tree, with the same roots as the `FieldBasedMerkleHashTree` built
+/// from the leaves appended so far, which stores only the frontier of the tree: for each
+/// level, the root of the last full subtree still waiting for its right sibling.
+/// Memory is therefore O(HEIGHT) regardless of the number of leaves, and the frontier
+/// can be serialized and restored to resume appending later on.
+#[derive(Derivative)]
+#[derivative(
+Clone(bound = "P: FieldBasedMerkleTreeConfig"),
+Debug(bound = "P: FieldBasedMerkleTreeConfig, <P::H as FieldBasedHash>::Data: fmt::Debug")
+)]
+pub struct IncrementalMerkleTree<P: FieldBasedMerkleTreeConfig> {
+    num_leaves:   usize,
+    frontier:     Vec<Option<<P::H as FieldBasedHash>::Data>>,
+    // The roots of the empty subtrees of each height
+    empty_hashes: Vec<<P::H as FieldBasedHash>::Data>,
+}
+
+impl<P: FieldBasedMerkleTreeConfig> IncrementalMerkleTree<P> {
+    pub const HEIGHT: u8 = P::HEIGHT as u8;
+
+    /// Maximum number of leaves the tree can hold.
+    pub const MAX_LEAVES: usize = 1 << (P::HEIGHT - 1);
+
+    pub fn new() -> Result<Self, Error> {
+        let mut empty_hashes = Vec::with_capacity(P::HEIGHT);
+        empty_hashes.push(hash_empty::<P::H>()?);
+        for i in 1..P::HEIGHT {
+            let empty_hash = empty_hashes[i - 1];
+            empty_hashes.push(hash_inner_node::<P::H>(empty_hash, empty_hash)?);
+        }
+        Ok(Self {
+            num_leaves: 0,
+            frontier: vec![None; P::HEIGHT],
+            empty_hashes,
+        })
+    }
+
+    #[inline]
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// Appends `leaf` to the tree, hashing only the nodes of the frontier which it completes.
+    pub fn append(&mut self, leaf: <P::H as FieldBasedHash>::Data) -> Result<(), Error> {
+        if self.num_leaves >= Self::MAX_LEAVES {
+            return Err(MerkleTreeError::TooManyLeaves(self.num_leaves))?
+        }
+
+        let mut node = leaf;
+        let mut height = 0;
+        while (self.num_leaves >> height) & 1 == 1 {
+            let left = self.frontier[height].take().unwrap();
+            node = hash_inner_node::<P::H>(left, node)?;
+            height += 1;
+        }
+        self.frontier[height] = Some(node);
+        self.num_leaves += 1;
+
+        Ok(())
+    }
+
+    /// Returns the root of the `FieldBasedMerkleHashTree` built from the leaves appended
+    /// so far, i.e. the root of the smallest full tree containing them, padded with empty
+    /// leaves, and then padded up to HEIGHT with empty leaves as siblings.
+    pub fn root(&self) -> Result<<P::H as FieldBasedHash>::Data, Error> {
+        // The smallest full tree holds at least one (empty) leaf
+        let num_leaves = std::cmp::max(self.num_leaves, 1);
+        let subtree_height = num_leaves.next_power_of_two().trailing_zeros() as usize;
+
+        let mut root = if num_leaves == self.num_leaves && num_leaves.is_power_of_two() {
+            self.frontier[subtree_height].unwrap()
+        } else {
+            let mut node = self.empty_hashes[0];
+            for height in 0..subtree_height {
+                node = match self.frontier[height] {
+                    Some(left) => hash_inner_node::<P::H>(left, node)?,
+                    None => hash_inner_node::<P::H>(node, self.empty_hashes[height])?,
+                };
+            }
+            node
+        };
+
+        for _ in subtree_height..(P::HEIGHT - 1) {
+            root = hash_inner_node::<P::H>(root, self.empty_hashes[0])?;
+        }
+
+        Ok(root)
+    }
+}
+
+impl<P: FieldBasedMerkleTreeConfig> ToBytes for IncrementalMerkleTree<P> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.num_leaves as u64).write(&mut writer)?;
+        for node in self.frontier.iter().filter_map(|node| node.as_ref()) {
+            node.write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: FieldBasedMerkleTreeConfig> FromBytes for IncrementalMerkleTree<P> {
+    /// Restores the tree from its frontier, whose levels are given by the binary
+    /// representation of the number of leaves.
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let mut tree = Self::new()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        let num_leaves = u64::read(&mut reader)? as usize;
+        if num_leaves > Self::MAX_LEAVES {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "too many leaves"));
+        }
+        for height in 0..P::HEIGHT {
+            if (num_leaves >> height) & 1 == 1 {
+                tree.frontier[height] = Some(<P::H as FieldBasedHash>::Data::read(&mut reader)?);
+            }
+        }
+        tree.num_leaves = num_leaves;
+        Ok(tree)
+    }
+}
+
+impl<P: FieldBasedMerkleTreeConfig> Snapshot for IncrementalMerkleTree<P> {
+    const MAGIC: [u8; 4] = *b"IMTF";
+    const NAME: &'static str = "incremental Merkle tree";
+
+    /// Identifies the configuration by the height and by the hash of the empty leaf,
+    /// which depends on the hash function and on its parameters.
+    fn configuration_id(&self) -> u64 {
+        let mut bytes = vec![Self::HEIGHT];
+        self.empty_hashes[0].write(&mut bytes).unwrap();
+        configuration_hash(&bytes)
+    }
+
+    fn write_state<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.write(writer)
+    }
+
+    fn read_state<R: Read>(reader: R) -> IoResult<Self> {
+        Self::read(reader)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        crh::MNT4PoseidonHash,
+        merkle_tree::field_based_mht::*,
+        snapshot::Snapshot,
+    };
+    use algebra::{
+        fields::mnt4753::Fr,
+        to_bytes, FromBytes, ToBytes, UniformRand,
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    struct MNT4753FieldBasedMerkleTreeParams;
+
+    impl FieldBasedMerkleTreeConfig for MNT4753FieldBasedMerkleTreeParams {
+        const HEIGHT: usize = 6;
+        type H = MNT4PoseidonHash;
+    }
+
+    struct MNT4753ShortFieldBasedMerkleTreeParams;
+
+    impl FieldBasedMerkleTreeConfig for MNT4753ShortFieldBasedMerkleTreeParams {
+        const HEIGHT: usize = 5;
+        type H = MNT4PoseidonHash;
+    }
+
+    type MNT4753FieldBasedMerkleTree = FieldBasedMerkleHashTree<MNT4753FieldBasedMerkleTreeParams>;
+    type MNT4753IncrementalMerkleTree = IncrementalMerkleTree<MNT4753FieldBasedMerkleTreeParams>;
+
+    #[test]
+    fn incremental_root_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+
+        let mut tree = MNT4753IncrementalMerkleTree::new().unwrap();
+        assert_eq!(tree.root().unwrap(), MNT4753FieldBasedMerkleTree::new(&[]).unwrap().root());
+
+        let mut leaves = Vec::new();
+        for _ in 0..32 {
+            let leaf = Fr::rand(&mut rng);
+            leaves.push(leaf);
+            tree.append(leaf).unwrap();
+            assert_eq!(tree.num_leaves(), leaves.len());
+
+            // The roots match the ones of the full tree
+            let expected_root = MNT4753FieldBasedMerkleTree::new(&leaves).unwrap().root();
+            assert_eq!(tree.root().unwrap(), expected_root);
+
+            // Restoring the frontier preserves the root
+            let serialized = to_bytes!(tree).unwrap();
+            let restored = MNT4753IncrementalMerkleTree::read(serialized.as_slice()).unwrap();
+            assert_eq!(restored.root().unwrap(), expected_root);
+        }
+
+        // The tree is full
+        assert!(tree.append(Fr::rand(&mut rng)).is_err());
+    }
+
+    #[test]
+    fn incremental_restore_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+        let leaves = (0..13).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+        let mut tree = MNT4753IncrementalMerkleTree::new().unwrap();
+        for &leaf in &leaves[..7] {
+            tree.append(leaf).unwrap();
+        }
+
+        // Resume appending to a restored tree
+        let serialized = to_bytes!(tree).unwrap();
+        let mut restored = MNT4753IncrementalMerkleTree::read(serialized.as_slice()).unwrap();
+        for &leaf in &leaves[7..] {
+            restored.append(leaf).unwrap();
+        }
+        assert_eq!(restored.root().unwrap(), MNT4753FieldBasedMerkleTree::new(&leaves).unwrap().root());
+
+        // Too many leaves
+        let mut serialized = vec![];
+        (MNT4753IncrementalMerkleTree::MAX_LEAVES as u64 + 1).write(&mut serialized).unwrap();
+        assert!(MNT4753IncrementalMerkleTree::read(serialized.as_slice()).is_err());
+    }
+
+    #[test]
+    fn incremental_snapshot_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+        let leaves = (0..13).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+        let mut tree = MNT4753IncrementalMerkleTree::new().unwrap();
+        for &leaf in &leaves[..5] {
+            tree.append(leaf).unwrap();
+        }
+
+        let mut snapshot = vec![];
+        tree.write_snapshot(&mut snapshot).unwrap();
+        let mut restored = MNT4753IncrementalMerkleTree::read_snapshot(snapshot.as_slice()).unwrap();
+        for &leaf in &leaves[5..] {
+            restored.append(leaf).unwrap();
+        }
+        assert_eq!(restored.root().unwrap(), MNT4753FieldBasedMerkleTree::new(&leaves).unwrap().root());
+
+        // The snapshot of a tree of another height is rejected
+        assert!(IncrementalMerkleTree::<MNT4753ShortFieldBasedMerkleTreeParams>::read_snapshot(snapshot.as_slice()).is_err());
+
+        // So is a bare frontier, or a snapshot of another version
+        assert!(MNT4753IncrementalMerkleTree::read_snapshot(to_bytes!(tree).unwrap().as_slice()).is_err());
+        snapshot[4] += 1;
+        assert!(MNT4753IncrementalMerkleTree::read_snapshot(snapshot.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,386 @@
+//! An on-disk format for fully built `FieldBasedMerkleHashTree`s, which can be
+//! mapped in memory to read leaves and paths without loading the tree. The
+//! file consists of:
+//! - the magic bytes `MAPPED_MERKLE_TREE_MAGIC` (4 bytes);
+//! - the version `MAPPED_MERKLE_TREE_VERSION` of the format (u8);
+//! - the height of the tree (u8);
+//! - the size in bytes of a node (u32);
+//! - the number of leaves (u64);
+//! - the identifier of the configuration, as for the snapshots of the
+//!   `IncrementalMerkleTree` (u64);
+//!
+//! followed by the nodes of the tree in the order of `FieldBasedMerkleHashTree`,
+//! i.e. level by level from the root of the subtree holding the leaves, then by
+//! the hash of the empty leaf and by the root of the tree. All integers are
+//! little endian, the nodes are serialized with `ToBytes`.
+use crate::{
+    crh::FieldBasedHash,
+    snapshot::configuration_hash,
+    Error,
+};
+use super::*;
+use algebra::{bytes::{FromBytes, ToBytes}, Field};
+use std::{
+    fs::File,
+    io::{self, Seek, SeekFrom, Write},
+    marker::PhantomData,
+};
+
+/// The magic bytes of a mapped Merkle tree.
+pub const MAPPED_MERKLE_TREE_MAGIC: [u8; 4] = *b"GMTM";
+
+/// The version of the format of the mapped Merkle trees.
+pub const MAPPED_MERKLE_TREE_VERSION: u8 = 1;
+
+/// The length of the header preceding the nodes.
+pub const MAPPED_MERKLE_TREE_HEADER_LEN: usize = 26;
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Returns the size of the serialization of a node, the same for all nodes.
+fn element_size<P: FieldBasedMerkleTreeConfig>() -> usize {
+    let mut bytes = vec![];
+    <P::H as FieldBasedHash>::Data::zero().write(&mut bytes).unwrap();
+    bytes.len()
+}
+
+fn configuration_id<P: FieldBasedMerkleTreeConfig>(empty_hash: &<P::H as FieldBasedHash>::Data) -> u64 {
+    let mut bytes = vec![P::HEIGHT as u8];
+    empty_hash.write(&mut bytes).unwrap();
+    configuration_hash(&bytes)
+}
+
+/// Returns the number of nodes of the tree holding `num_leaves` leaves, padded with
+/// empty leaves up to a power of two.
+fn tree_size(num_leaves: usize) -> usize {
+    2 * num_leaves.max(1).next_power_of_two() - 1
+}
+
+/// Returns the maximum number of leaves of a tree of height `P::HEIGHT`.
+fn max_leaves<P: FieldBasedMerkleTreeConfig>() -> usize {
+    1 << (P::HEIGHT - 1)
+}
+
+/// Writes a mapped Merkle tree while appending its leaves, keeping in memory only,
+/// for each level, the node waiting for its right sibling: each node is written as
+/// soon as it is computed, at its place in the file.
+pub struct MappedMerkleTreeBuilder<P: FieldBasedMerkleTreeConfig, W: Write + Seek> {
+    writer:       W,
+    num_leaves:   usize,
+    next_leaf:    usize,
+    tree_height:  usize,
+    element_size: usize,
+    pending:      Vec<Option<<P::H as FieldBasedHash>::Data>>,
+    tree_root:    Option<<P::H as FieldBasedHash>::Data>,
+    empty_hash:   <P::H as FieldBasedHash>::Data,
+}
+
+impl<P: FieldBasedMerkleTreeConfig, W: Write + Seek> MappedMerkleTreeBuilder<P, W> {
+    /// Starts writing to `writer` the tree of exactly `num_leaves` leaves.
+    pub fn new(writer: W, num_leaves: usize) -> Result<Self, Error> {
+        if num_leaves > max_leaves::<P>() {
+            return Err(Box::new(MerkleTreeError::TooManyLeaves(num_leaves)));
+        }
+        let tree_height = tree_height(tree_size(num_leaves));
+        Ok(Self {
+            writer,
+            num_leaves,
+            next_leaf: 0,
+            tree_height,
+            element_size: element_size::<P>(),
+            pending: vec![None; tree_height],
+            tree_root: None,
+            empty_hash: hash_empty::<P::H>()?,
+        })
+    }
+
+    /// Appends `leaf`, writing the nodes it completes.
+    pub fn push(&mut self, leaf: <P::H as FieldBasedHash>::Data) -> Result<(), Error> {
+        if self.next_leaf == self.num_leaves {
+            return Err(Box::new(MerkleTreeError::TooManyLeaves(self.num_leaves)));
+        }
+        self.add_node(0, self.next_leaf, leaf)?;
+        self.next_leaf += 1;
+        Ok(())
+    }
+
+    /// Pads the leaves with empty leaves, then writes the root and the header.
+    /// Fails if less leaves than announced have been appended.
+    pub fn finish(mut self) -> Result<W, Error> {
+        if self.next_leaf != self.num_leaves {
+            return Err(Box::new(MerkleTreeError::IncorrectNumberOfLeaves(self.next_leaf)));
+        }
+        for position in self.num_leaves..1 << (self.tree_height - 1) {
+            self.add_node(0, position, self.empty_hash)?;
+        }
+
+        let mut root = self.tree_root.unwrap();
+        for _ in self.tree_height..P::HEIGHT {
+            root = hash_inner_node::<P::H>(root, self.empty_hash)?;
+        }
+        let tail = MAPPED_MERKLE_TREE_HEADER_LEN + tree_size(self.num_leaves) * self.element_size;
+        self.writer.seek(SeekFrom::Start(tail as u64))?;
+        self.empty_hash.write(&mut self.writer)?;
+        root.write(&mut self.writer)?;
+
+        self.writer.seek(SeekFrom::Start(0))?;
+        MAPPED_MERKLE_TREE_MAGIC.write(&mut self.writer)?;
+        MAPPED_MERKLE_TREE_VERSION.write(&mut self.writer)?;
+        (P::HEIGHT as u8).write(&mut self.writer)?;
+        (self.element_size as u32).write(&mut self.writer)?;
+        (self.num_leaves as u64).write(&mut self.writer)?;
+        configuration_id::<P>(&self.empty_hash).write(&mut self.writer)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Writes the node at `position` in the level `level` above the leaves, then
+    /// its ancestors completed by it.
+    fn add_node(
+        &mut self,
+        mut level: usize,
+        mut position: usize,
+        mut node: <P::H as FieldBasedHash>::Data,
+    ) -> Result<(), Error> {
+        loop {
+            let index = (1 << (self.tree_height - 1 - level)) - 1 + position;
+            let offset = MAPPED_MERKLE_TREE_HEADER_LEN + index * self.element_size;
+            self.writer.seek(SeekFrom::Start(offset as u64))?;
+            node.write(&mut self.writer)?;
+
+            if level == self.tree_height - 1 {
+                self.tree_root = Some(node);
+                return Ok(());
+            }
+            if position % 2 == 0 {
+                self.pending[level] = Some(node);
+                return Ok(());
+            }
+            let left = self.pending[level].take().unwrap();
+            node = hash_inner_node::<P::H>(left, node)?;
+            level += 1;
+            position /= 2;
+        }
+    }
+}
+
+/// A mapped Merkle tree, mapped read-only in memory from its file: nodes are read
+/// from the file on access, and the operating system caches the most used ones.
+pub struct MappedMerkleTree<P: FieldBasedMerkleTreeConfig> {
+    mmap:         memmap::Mmap,
+    num_leaves:   usize,
+    tree_height:  usize,
+    element_size: usize,
+    _config:      PhantomData<P>,
+}
+
+impl<P: FieldBasedMerkleTreeConfig> MappedMerkleTree<P> {
+    /// Maps `file` in memory, checking its header and its length. The file must not
+    /// be modified, nor truncated, as long as it is mapped.
+    #[allow(unsafe_code)]
+    pub fn open(file: &File) -> Result<Self, Error> {
+        // Safe as long as the file is not modified while mapped, as required above
+        let mmap = unsafe { memmap::Mmap::map(file)? };
+        if mmap.len() < MAPPED_MERKLE_TREE_HEADER_LEN {
+            return Err(Box::new(invalid_data("truncated mapped Merkle tree")));
+        }
+
+        let mut header = &mmap[..MAPPED_MERKLE_TREE_HEADER_LEN];
+        if <[u8; 4]>::read(&mut header)? != MAPPED_MERKLE_TREE_MAGIC {
+            return Err(Box::new(invalid_data("not a mapped Merkle tree")));
+        }
+        if u8::read(&mut header)? != MAPPED_MERKLE_TREE_VERSION {
+            return Err(Box::new(invalid_data("unsupported mapped Merkle tree version")));
+        }
+        if u8::read(&mut header)? as usize != P::HEIGHT {
+            return Err(Box::new(invalid_data("mapped Merkle tree of another height")));
+        }
+        let element_size = u32::read(&mut header)? as usize;
+        if element_size != self::element_size::<P>() {
+            return Err(Box::new(invalid_data("mapped Merkle tree of another hash")));
+        }
+        // Checked before computing the size of the tree, as the header is untrusted
+        let num_leaves = u64::read(&mut header)?;
+        if num_leaves > max_leaves::<P>() as u64 {
+            return Err(Box::new(MerkleTreeError::TooManyLeaves(num_leaves as usize)));
+        }
+        let num_leaves = num_leaves as usize;
+        let tree_height = tree_height(tree_size(num_leaves));
+        let config_id = u64::read(&mut header)?;
+
+        let expected_len = (tree_size(num_leaves) + 2)
+            .checked_mul(element_size)
+            .and_then(|len| len.checked_add(MAPPED_MERKLE_TREE_HEADER_LEN));
+        if expected_len != Some(mmap.len()) {
+            return Err(Box::new(invalid_data("truncated mapped Merkle tree")));
+        }
+        let tree = Self { mmap, num_leaves, tree_height, element_size, _config: PhantomData };
+        if config_id != configuration_id::<P>(&tree.empty_hash()?) {
+            return Err(Box::new(invalid_data("mapped Merkle tree of another hash")));
+        }
+        Ok(tree)
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    pub fn root(&self) -> Result<<P::H as FieldBasedHash>::Data, Error> {
+        self.read_element(tree_size(self.num_leaves) + 1)
+    }
+
+    /// Returns the leaf at `index`.
+    pub fn leaf(&self, index: usize) -> Result<<P::H as FieldBasedHash>::Data, Error> {
+        if index >= self.num_leaves {
+            return Err(Box::new(MerkleTreeError::IncorrectLeafIndex(index)));
+        }
+        self.read_element(convert_index_to_last_level(index, self.tree_height))
+    }
+
+    /// Returns the path of the leaf at `index`, the same as `generate_proof` of the
+    /// `FieldBasedMerkleHashTree` with the same leaves.
+    pub fn generate_proof(&self, index: usize) -> Result<FieldBasedMerkleTreePath<P>, Error> {
+        if index >= self.num_leaves {
+            return Err(Box::new(MerkleTreeError::IncorrectLeafIndex(index)));
+        }
+        let mut path = Vec::with_capacity(P::HEIGHT - 1);
+
+        let mut current_node = convert_index_to_last_level(index, self.tree_height);
+        while !is_root(current_node) {
+            let sibling_hash = self.read_element(sibling(current_node).unwrap())?;
+            path.push((sibling_hash, !is_left_child(current_node)));
+            current_node = parent(current_node).unwrap();
+        }
+
+        // The siblings of the root of the subtree holding the leaves are empty leaves
+        let empty_hash = self.empty_hash()?;
+        for _ in self.tree_height..P::HEIGHT {
+            path.push((empty_hash, false));
+        }
+        Ok(FieldBasedMerkleTreePath { path })
+    }
+
+    fn empty_hash(&self) -> Result<<P::H as FieldBasedHash>::Data, Error> {
+        self.read_element(tree_size(self.num_leaves))
+    }
+
+    /// Reads the `index`-th element after the header.
+    fn read_element(&self, index: usize) -> Result<<P::H as FieldBasedHash>::Data, Error> {
+        let start = MAPPED_MERKLE_TREE_HEADER_LEN + index * self.element_size;
+        Ok(<P::H as FieldBasedHash>::Data::read(&self.mmap[start..start + self.element_size])?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        crh::MNT4PoseidonHash,
+        merkle_tree::field_based_mht::*,
+    };
+    use algebra::{fields::mnt4753::Fr, UniformRand};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::{
+        fs::{self, File, OpenOptions},
+        io::Cursor,
+    };
+
+    struct MNT4753FieldBasedMerkleTreeParams;
+
+    impl FieldBasedMerkleTreeConfig for MNT4753FieldBasedMerkleTreeParams {
+        const HEIGHT: usize = 6;
+        type H = MNT4PoseidonHash;
+    }
+
+    struct MNT4753ShortFieldBasedMerkleTreeParams;
+
+    impl FieldBasedMerkleTreeConfig for MNT4753ShortFieldBasedMerkleTreeParams {
+        const HEIGHT: usize = 5;
+        type H = MNT4PoseidonHash;
+    }
+
+    type MNT4753FieldBasedMerkleTree = FieldBasedMerkleHashTree<MNT4753FieldBasedMerkleTreeParams>;
+    type MNT4753MappedMerkleTree = MappedMerkleTree<MNT4753FieldBasedMerkleTreeParams>;
+    type MNT4753MappedMerkleTreeBuilder<W> = MappedMerkleTreeBuilder<MNT4753FieldBasedMerkleTreeParams, W>;
+
+    fn write_tree(path: &std::path::Path, leaves: &[Fr]) -> File {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .unwrap();
+        let mut builder = MNT4753MappedMerkleTreeBuilder::new(file, leaves.len()).unwrap();
+        for &leaf in leaves {
+            builder.push(leaf).unwrap();
+        }
+        builder.finish().unwrap()
+    }
+
+    #[test]
+    fn mapped_tree_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+        let path = std::env::temp_dir().join(format!("ginger-mapped-tree-{}", std::process::id()));
+
+        for &num_leaves in &[0, 1, 11, 32] {
+            let leaves = (0..num_leaves).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let file = write_tree(&path, &leaves);
+
+            let tree = MNT4753FieldBasedMerkleTree::new(&leaves).unwrap();
+            let mapped = MNT4753MappedMerkleTree::open(&file).unwrap();
+            assert_eq!(mapped.num_leaves(), num_leaves);
+            assert_eq!(mapped.root().unwrap(), tree.root());
+            for (i, leaf) in leaves.iter().enumerate() {
+                assert_eq!(mapped.leaf(i).unwrap(), *leaf);
+                let proof = mapped.generate_proof(i).unwrap();
+                assert_eq!(proof.path, tree.generate_proof(i, leaf).unwrap().path);
+                assert!(proof.verify(&tree.root(), leaf).unwrap());
+            }
+            assert!(mapped.leaf(num_leaves).is_err());
+            assert!(mapped.generate_proof(num_leaves).is_err());
+        }
+
+        // Trees of another height are rejected
+        let file = File::open(&path).unwrap();
+        assert!(MappedMerkleTree::<MNT4753ShortFieldBasedMerkleTreeParams>::open(&file).is_err());
+
+        // So are headers announcing more leaves than the height allows
+        let corrupted = path.with_extension("corrupted");
+        let mut bytes = fs::read(&path).unwrap();
+        for &num_leaves in &[33u64, u64::MAX] {
+            bytes[10..18].copy_from_slice(&num_leaves.to_le_bytes());
+            fs::write(&corrupted, &bytes).unwrap();
+            assert!(MNT4753MappedMerkleTree::open(&File::open(&corrupted).unwrap()).is_err());
+        }
+        fs::remove_file(&corrupted).unwrap();
+
+        // So are truncated files
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(fs::metadata(&path).unwrap().len() - 1).unwrap();
+        assert!(MNT4753MappedMerkleTree::open(&File::open(&path).unwrap()).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn mapped_tree_builder_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+        let leaves = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+        // Exactly the announced number of leaves must be appended
+        let mut builder = MNT4753MappedMerkleTreeBuilder::new(Cursor::new(vec![]), 2).unwrap();
+        builder.push(leaves[0]).unwrap();
+        builder.push(leaves[1]).unwrap();
+        assert!(builder.push(leaves[2]).is_err());
+
+        let mut builder = MNT4753MappedMerkleTreeBuilder::new(Cursor::new(vec![]), 2).unwrap();
+        builder.push(leaves[0]).unwrap();
+        assert!(builder.finish().is_err());
+
+        // The tree must fit in the height
+        assert!(MNT4753MappedMerkleTreeBuilder::new(Cursor::new(vec![]), 33).is_err());
+    }
+}
@@ -3,6 +3,14 @@ use crate::{
 };
 use super::*;
 
+pub mod incremental;
+pub use self::incremental::*;
+
+#[cfg(feature = "mmap")]
+pub mod mapped;
+#[cfg(feature = "mmap")]
+pub use self::mapped::*;
+
 pub trait FieldBasedMerkleTreeConfig {
     const HEIGHT: usize;
     type H: FieldBasedHash;
@@ -98,6 +106,111 @@ impl<P: FieldBasedMerkleTreeConfig> FieldBasedMerkleTreePath<P> {
     }
 }
 
+/// Merkle multiproof: the Merkle paths of several leaves, in which the nodes shared
+/// by the paths, or computable from the opened leaves, are not stored.
+/// Leaf `index` is a right child at level `i` (starting from the leaves) if the i-th
+/// bit of `index` is set.
+#[derive(Derivative)]
+#[derivative(
+Clone(bound = "P: FieldBasedMerkleTreeConfig"),
+Debug(bound = "P: FieldBasedMerkleTreeConfig, <P::H as FieldBasedHash>::Data: fmt::Debug")
+)]
+pub struct FieldBasedMerkleTreeMultiPath<P: FieldBasedMerkleTreeConfig> {
+    /// The indices of the opened leaves, in strictly increasing order.
+    pub indices:   Vec<usize>,
+    /// The siblings which are not computable from the opened leaves, level by level
+    /// from the leaves to the root, and from left to right within a level.
+    pub auxiliary: Vec<<P::H as FieldBasedHash>::Data>,
+}
+
+/// Checks that `indices` are strictly increasing leaf indices of a tree of height `height`.
+fn check_multi_path_indices(indices: &[usize], height: usize) -> Result<(), Error> {
+    if indices.is_empty() {
+        return Err(MerkleTreeError::IncorrectNumberOfLeaves(0))?
+    }
+    for (i, &index) in indices.iter().enumerate() {
+        if index >= 1 << (height - 1) || (i > 0 && index <= indices[i - 1]) {
+            return Err(MerkleTreeError::IncorrectLeafIndex(index))?
+        }
+    }
+    Ok(())
+}
+
+impl<P: FieldBasedMerkleTreeConfig> FieldBasedMerkleTreeMultiPath<P> {
+    pub fn verify(
+        &self,
+        root_hash: &<P::H as FieldBasedHash>::Data,
+        leaves: &[<P::H as FieldBasedHash>::Data],
+    ) -> Result<bool, Error>
+    {
+        if leaves.len() != self.indices.len() {
+            return Err(MerkleTreeError::IncorrectNumberOfLeaves(leaves.len()))?
+        }
+        check_multi_path_indices(&self.indices, P::HEIGHT)?;
+
+        let mut auxiliary = self.auxiliary.iter();
+        let mut nodes = self.indices.iter().cloned().zip(leaves.iter().cloned()).collect::<Vec<_>>();
+
+        // Compute the nodes of each level from the ones of the level below, taking the
+        // siblings from the auxiliary nodes when they are not among them.
+        for _ in 0..P::HEIGHT - 1 {
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut i = 0;
+            while i < nodes.len() {
+                let (index, node) = nodes[i];
+                let parent = if index % 2 == 0 && i + 1 < nodes.len() && nodes[i + 1].0 == index + 1 {
+                    i += 1;
+                    hash_inner_node::<P::H>(node, nodes[i].1)?
+                } else {
+                    let sibling = *auxiliary
+                        .next()
+                        .ok_or(MerkleTreeError::IncorrectPathLength(self.auxiliary.len()))?;
+                    if index % 2 == 0 {
+                        hash_inner_node::<P::H>(node, sibling)?
+                    } else {
+                        hash_inner_node::<P::H>(sibling, node)?
+                    }
+                };
+                parents.push((index / 2, parent));
+                i += 1;
+            }
+            nodes = parents;
+        }
+
+        if auxiliary.next().is_some() {
+            return Err(MerkleTreeError::IncorrectPathLength(self.auxiliary.len()))?
+        }
+
+        Ok(root_hash == &nodes[0].1)
+    }
+}
+
+/// Proof of the replacement of `old_leaf` by `new_leaf` in a Merkle Tree: as the
+/// siblings along the path of the leaf are not affected by the update, the same
+/// path links `old_leaf` to the old root and `new_leaf` to the new one.
+#[derive(Derivative)]
+#[derivative(
+Clone(bound = "P: FieldBasedMerkleTreeConfig"),
+Debug(bound = "P: FieldBasedMerkleTreeConfig, <P::H as FieldBasedHash>::Data: fmt::Debug")
+)]
+pub struct FieldBasedMerkleTreeUpdateProof<P: FieldBasedMerkleTreeConfig> {
+    pub old_leaf: <P::H as FieldBasedHash>::Data,
+    pub new_leaf: <P::H as FieldBasedHash>::Data,
+    pub path:     FieldBasedMerkleTreePath<P>,
+}
+
+impl<P: FieldBasedMerkleTreeConfig> FieldBasedMerkleTreeUpdateProof<P> {
+    /// Verifies the transition from `old_root` to `new_root`.
+    pub fn verify(
+        &self,
+        old_root: &<P::H as FieldBasedHash>::Data,
+        new_root: &<P::H as FieldBasedHash>::Data,
+    ) -> Result<bool, Error>
+    {
+        Ok(self.path.verify(old_root, &self.old_leaf)? && self.path.verify(new_root, &self.new_leaf)?)
+    }
+}
+
 /// Merkle Tree whose leaves are field elements, best with hash functions
 /// that works with field elements, such as Poseidon. This implementation
 /// works with leaves of size 1 field element.
@@ -249,6 +362,89 @@ impl<P: FieldBasedMerkleTreeConfig> FieldBasedMerkleHashTree<P> {
             Ok(FieldBasedMerkleTreePath { path })
         }
     }
+
+    /// Replaces the leaf at `index` by `new_leaf`, updating the nodes along its path and
+    /// the root, and returns the proof of the update.
+    pub fn update_leaf(
+        &mut self,
+        index: usize,
+        new_leaf: <P::H as FieldBasedHash>::Data,
+    ) -> Result<FieldBasedMerkleTreeUpdateProof<P>, Error>
+    {
+        let update_time = start_timer!(|| "MerkleTree::UpdateLeaf");
+
+        let tree_height = tree_height(self.tree.len());
+        if index >= 1 << (tree_height - 1) {
+            Err(MerkleTreeError::IncorrectLeafIndex(index))?
+        }
+        let tree_index = convert_index_to_last_level(index, tree_height);
+        let old_leaf = self.tree[tree_index];
+        let path = self.generate_proof(index, &old_leaf)?;
+
+        // Update the nodes from the leaf up to the root of the tree.
+        self.tree[tree_index] = new_leaf;
+        let mut current_node = tree_index;
+        while let Some(parent_node) = parent(current_node) {
+            self.tree[parent_node] = hash_inner_node::<P::H>(
+                self.tree[left_child(parent_node)],
+                self.tree[right_child(parent_node)],
+            )?;
+            current_node = parent_node;
+        }
+
+        // Update the padding tree, whose siblings are empty hashes.
+        let mut cur_hash = self.tree[0];
+        for node in self.padding_tree.iter_mut() {
+            cur_hash = hash_inner_node::<P::H>(cur_hash, node.1)?;
+            node.0 = cur_hash;
+        }
+        self.root = Some(cur_hash);
+
+        end_timer!(update_time);
+        Ok(FieldBasedMerkleTreeUpdateProof { old_leaf, new_leaf, path })
+    }
+
+    /// Generates the multiproof of the leaves at `indices`, which must be strictly
+    /// increasing.
+    pub fn generate_multi_proof(
+        &self,
+        indices: &[usize],
+    ) -> Result<FieldBasedMerkleTreeMultiPath<P>, Error>
+    {
+        let prove_time = start_timer!(|| "MerkleTree::GenMultiProof");
+
+        let tree_height = tree_height(self.tree.len());
+        check_multi_path_indices(indices, tree_height)?;
+
+        let mut auxiliary = Vec::new();
+        let mut nodes = indices.to_vec();
+        for level in 0..(Self::HEIGHT - 1) as usize {
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut i = 0;
+            while i < nodes.len() {
+                let index = nodes[i];
+                if index % 2 == 0 && i + 1 < nodes.len() && nodes[i + 1] == index + 1 {
+                    i += 1;
+                } else if level + 1 < tree_height {
+                    // The sibling is in the tree
+                    let depth = tree_height - 1 - level;
+                    auxiliary.push(self.tree[convert_index_to_last_level(index ^ 1, depth + 1)]);
+                } else {
+                    // The sibling is in the padding tree
+                    auxiliary.push(self.padding_tree[level + 1 - tree_height].1);
+                }
+                parents.push(index / 2);
+                i += 1;
+            }
+            nodes = parents;
+        }
+
+        end_timer!(prove_time);
+        Ok(FieldBasedMerkleTreeMultiPath {
+            indices: indices.to_vec(),
+            auxiliary,
+        })
+    }
 }
 
 /// Returns the output hash, given a left and right hash value.
@@ -381,4 +577,79 @@ mod test {
         }
         bad_merkle_tree_verify(&leaves);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn multi_proof_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+
+        for &num_leaves in &[1, 5, 16, 32] {
+            let leaves = (0..num_leaves).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let tree = MNT4753FieldBasedMerkleTree::new(&leaves).unwrap();
+            let root = tree.root();
+            let last_level_size = leaves.len().next_power_of_two();
+
+            let index_sets = vec![
+                vec![0],
+                vec![last_level_size - 1],
+                (0..last_level_size).collect::<Vec<_>>(),
+                (0..last_level_size).filter(|i| i % 3 == 1).collect::<Vec<_>>(),
+                (0..last_level_size).step_by(2).collect::<Vec<_>>(),
+            ];
+            for indices in index_sets.into_iter().filter(|indices| !indices.is_empty()) {
+                let opened = indices.iter().map(|&i| tree.leaves()[i]).collect::<Vec<_>>();
+                let proof = tree.generate_multi_proof(&indices).unwrap();
+                assert!(proof.verify(&root, &opened).unwrap());
+                assert!(!proof.verify(&Fr::zero(), &opened).unwrap());
+
+                // The shared nodes are stored only once
+                assert!(proof.auxiliary.len() <= indices.len() * (MNT4753FieldBasedMerkleTree::HEIGHT as usize - 1));
+
+                // A wrong leaf
+                let mut bad_opened = opened.clone();
+                bad_opened[0] = Fr::rand(&mut rng);
+                assert!(!proof.verify(&root, &bad_opened).unwrap());
+
+                // Wrong number of leaves or auxiliary nodes
+                assert!(proof.verify(&root, &opened[1..]).is_err());
+                let mut bad_proof = proof.clone();
+                bad_proof.auxiliary.push(Fr::zero());
+                assert!(bad_proof.verify(&root, &opened).is_err());
+            }
+
+            // Indices must be strictly increasing and in range
+            assert!(tree.generate_multi_proof(&[]).is_err());
+            assert!(tree.generate_multi_proof(&[last_level_size]).is_err());
+            if last_level_size > 1 {
+                assert!(tree.generate_multi_proof(&[1, 0]).is_err());
+                assert!(tree.generate_multi_proof(&[1, 1]).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn update_leaf_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+
+        for &num_leaves in &[1, 5, 32] {
+            let mut leaves = (0..num_leaves).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let mut tree = MNT4753FieldBasedMerkleTree::new(&leaves).unwrap();
+
+            for &index in &[0, num_leaves - 1, num_leaves / 2] {
+                let old_root = tree.root();
+                let new_leaf = Fr::rand(&mut rng);
+                let proof = tree.update_leaf(index, new_leaf).unwrap();
+                leaves[index] = new_leaf;
+
+                // The new root is the one of the tree built from the updated leaves
+                let new_root = tree.root();
+                assert_eq!(new_root, MNT4753FieldBasedMerkleTree::new(&leaves).unwrap().root());
+
+                assert!(proof.verify(&old_root, &new_root).unwrap());
+                assert!(!proof.verify(&new_root, &old_root).unwrap());
+                assert!(!proof.verify(&old_root, &Fr::zero()).unwrap());
+            }
+
+            assert!(tree.update_leaf(num_leaves.next_power_of_two(), Fr::zero()).is_err());
+        }
+    }
+}
@@ -233,6 +233,8 @@ impl<P: MerkleTreeConfig> MerkleHashTree<P> {
 pub enum MerkleTreeError {
     IncorrectLeafIndex(usize),
     IncorrectPathLength(usize),
+    TooManyLeaves(usize),
+    IncorrectNumberOfLeaves(usize),
 }
 
 impl std::fmt::Display for MerkleTreeError {
@@ -242,6 +244,10 @@ impl std::fmt::Display for MerkleTreeError {
                 format!("incorrect leaf index: {}", index)
             },
             MerkleTreeError::IncorrectPathLength(len) => format!("incorrect path length: {}", len),
+            MerkleTreeError::TooManyLeaves(num_leaves) => format!("tree is full: {} leaves", num_leaves),
+            MerkleTreeError::IncorrectNumberOfLeaves(num_leaves) => {
+                format!("incorrect number of leaves: {}", num_leaves)
+            },
         };
         write!(f, "{}", msg)
     }
@@ -257,7 +263,8 @@ impl std::error::Error for MerkleTreeError {
 /// Returns the log2 value of the given number.
 #[inline]
 fn log2(number: usize) -> usize {
-    (number as f64).log2() as usize
+    assert!(number > 0);
+    (0usize.leading_zeros() - 1 - number.leading_zeros()) as usize
 }
 
 /// Returns the height of the tree, given the size of the tree.
@@ -0,0 +1,658 @@
+//! Bulletproofs range proofs (Bünz et al., S&P 2018) over Pedersen commitments
+//! `V = v * g + gamma * h`. A proof consists of `2 * log(n) + 4` group elements
+//! and `5` scalars, where `n` is the bit length of the range.
+use crate::{
+    fiat_shamir::ChaChaFSRng,
+    range_proof::RangeProof,
+    transcript::Transcript,
+    CryptoError, Error,
+};
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    Field, Group, HashToCurve, PrimeField, ProjectiveCurve, UniformRand, VariableBaseMSM,
+};
+use digest::Digest;
+use rand::Rng;
+use std::{
+    io::{self, Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+/// The largest bit length of the ranges, i.e. of the values committed to.
+const MAX_NUM_BITS: usize = 64;
+
+/// The domain separator of the hashing of the generators to the curve.
+const GENERATORS_DOMAIN: &[u8] = b"Bulletproofs generators";
+
+pub struct Bulletproofs<G: ProjectiveCurve, D: Digest> {
+    _group: PhantomData<G>,
+    _hash:  PhantomData<D>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+Clone(bound = "G: ProjectiveCurve"),
+Debug(bound = "G: ProjectiveCurve")
+)]
+pub struct BulletproofsParameters<G: ProjectiveCurve> {
+    pub num_bits: usize,
+    /// The generators the bits of the value are committed to.
+    pub g_vec:    Vec<G>,
+    pub h_vec:    Vec<G>,
+    /// The value and blinding generators of the Pedersen commitments.
+    pub g:        G,
+    pub h:        G,
+    /// The generator the inner product is committed to.
+    pub u:        G,
+}
+
+#[derive(Derivative)]
+#[derivative(
+Clone(bound = "G: ProjectiveCurve"),
+Debug(bound = "G: ProjectiveCurve"),
+Eq(bound = "G: ProjectiveCurve"),
+PartialEq(bound = "G: ProjectiveCurve")
+)]
+pub struct BulletproofsRangeProof<G: ProjectiveCurve> {
+    pub a:      G,
+    pub s:      G,
+    pub t_1:    G,
+    pub t_2:    G,
+    pub tau_x:  G::ScalarField,
+    pub mu:     G::ScalarField,
+    pub t_hat:  G::ScalarField,
+    /// The messages of the inner product argument.
+    pub l_vec:  Vec<G>,
+    pub r_vec:  Vec<G>,
+    pub a_ipa:  G::ScalarField,
+    pub b_ipa:  G::ScalarField,
+}
+
+impl<G: ProjectiveCurve> ToBytes for BulletproofsRangeProof<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.a.write(&mut writer)?;
+        self.s.write(&mut writer)?;
+        self.t_1.write(&mut writer)?;
+        self.t_2.write(&mut writer)?;
+        self.tau_x.write(&mut writer)?;
+        self.mu.write(&mut writer)?;
+        self.t_hat.write(&mut writer)?;
+        (self.l_vec.len() as u32).write(&mut writer)?;
+        self.l_vec.write(&mut writer)?;
+        self.r_vec.write(&mut writer)?;
+        self.a_ipa.write(&mut writer)?;
+        self.b_ipa.write(&mut writer)
+    }
+}
+
+impl<G: ProjectiveCurve> FromBytes for BulletproofsRangeProof<G> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let a = G::read(&mut reader)?;
+        let s = G::read(&mut reader)?;
+        let t_1 = G::read(&mut reader)?;
+        let t_2 = G::read(&mut reader)?;
+        let tau_x = G::ScalarField::read(&mut reader)?;
+        let mu = G::ScalarField::read(&mut reader)?;
+        let t_hat = G::ScalarField::read(&mut reader)?;
+        let rounds = u32::read(&mut reader)?;
+        if rounds > MAX_NUM_BITS.trailing_zeros() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "too many inner product rounds"));
+        }
+        let l_vec = (0..rounds).map(|_| G::read(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        let r_vec = (0..rounds).map(|_| G::read(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        let a_ipa = G::ScalarField::read(&mut reader)?;
+        let b_ipa = G::ScalarField::read(&mut reader)?;
+        Ok(Self { a, s, t_1, t_2, tau_x, mu, t_hat, l_vec, r_vec, a_ipa, b_ipa })
+    }
+}
+
+/// Returns the Fiat-Shamir transcript of a range proof.
+fn new_transcript<F: PrimeField, D: Digest>(
+    num_bits:   usize,
+    commitment: &impl ToBytes,
+) -> Result<Transcript<'static, ChaChaFSRng<F, D>>, Error>
+{
+    let mut transcript = Transcript::new(b"Bulletproofs range proof", None)?;
+    transcript.absorb("num_bits", &(num_bits as u64))?;
+    transcript.absorb("commitment", commitment)?;
+    Ok(transcript)
+}
+
+/// Squeezes a non-zero challenge.
+fn nonzero_challenge<F: PrimeField, D: Digest>(
+    transcript: &mut Transcript<ChaChaFSRng<F, D>>,
+    label:      &str,
+) -> Result<F, Error>
+{
+    loop {
+        let challenge = transcript.challenge(label)?;
+        if !challenge.is_zero() {
+            return Ok(challenge);
+        }
+    }
+}
+
+/// The verifier challenges of a proof.
+struct Challenges<F: PrimeField> {
+    y:     F,
+    z:     F,
+    x:     F,
+    w:     F,
+    x_ipa: Vec<F>,
+}
+
+/// The terms of a sum of multiples of points, which must vanish for the
+/// proofs to verify. The multiples of the generators of the parameters are
+/// accumulated separately, as they are shared among proofs.
+struct MsmAccumulator<G: ProjectiveCurve> {
+    g_vec_scalars: Vec<G::ScalarField>,
+    h_vec_scalars: Vec<G::ScalarField>,
+    g_scalar:      G::ScalarField,
+    h_scalar:      G::ScalarField,
+    u_scalar:      G::ScalarField,
+    bases:         Vec<G>,
+    scalars:       Vec<G::ScalarField>,
+}
+
+impl<G: ProjectiveCurve> MsmAccumulator<G> {
+    fn new(num_bits: usize) -> Self {
+        Self {
+            g_vec_scalars: vec![G::ScalarField::zero(); num_bits],
+            h_vec_scalars: vec![G::ScalarField::zero(); num_bits],
+            g_scalar:      G::ScalarField::zero(),
+            h_scalar:      G::ScalarField::zero(),
+            u_scalar:      G::ScalarField::zero(),
+            bases:         Vec::new(),
+            scalars:       Vec::new(),
+        }
+    }
+
+    fn push(&mut self, base: G, scalar: G::ScalarField) {
+        self.bases.push(base);
+        self.scalars.push(scalar);
+    }
+
+    fn is_zero(self, pp: &BulletproofsParameters<G>) -> bool {
+        let mut bases = self.bases;
+        bases.extend_from_slice(&pp.g_vec);
+        bases.extend_from_slice(&pp.h_vec);
+        bases.extend_from_slice(&[pp.g, pp.h, pp.u]);
+
+        let mut scalars = self.scalars;
+        scalars.extend_from_slice(&self.g_vec_scalars);
+        scalars.extend_from_slice(&self.h_vec_scalars);
+        scalars.extend_from_slice(&[self.g_scalar, self.h_scalar, self.u_scalar]);
+
+        ProjectiveCurve::is_zero(&msm(&bases, &scalars))
+    }
+}
+
+fn msm<G: ProjectiveCurve>(bases: &[G], scalars: &[G::ScalarField]) -> G {
+    let mut bases = bases.to_vec();
+    G::batch_normalization(&mut bases);
+    let bases = bases.iter().map(|base| base.into_affine()).collect::<Vec<_>>();
+    let scalars = scalars.iter().map(|scalar| scalar.into_repr()).collect::<Vec<_>>();
+    VariableBaseMSM::multi_scalar_mul(&bases, &scalars)
+}
+
+fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b).fold(F::zero(), |acc, (a, b)| acc + &(*a * b))
+}
+
+fn powers<F: Field>(base: F, num: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(num);
+    let mut power = F::one();
+    for _ in 0..num {
+        powers.push(power);
+        power *= &base;
+    }
+    powers
+}
+
+impl<G: ProjectiveCurve, D: Digest> Bulletproofs<G, D> {
+
+    /// Derives the parameters for ranges of `num_bits` bits from the public
+    /// `seed`: each generator is the hash to the curve of the seed, its name and
+    /// its index, so that no discrete logarithm relation between the generators
+    /// is known to anyone, including whoever picked the seed.
+    pub fn setup_from_seed(seed: &[u8], num_bits: usize) -> Result<BulletproofsParameters<G>, Error>
+        where
+            G: HashToCurve,
+            G::BaseField: PrimeField,
+    {
+        if !num_bits.is_power_of_two() || num_bits > MAX_NUM_BITS {
+            return Err(Box::new(CryptoError::IncorrectInputLength("num_bits".to_owned(), num_bits)));
+        }
+        let generator = |name: &[u8], index: usize| {
+            let mut msg = seed.to_vec();
+            msg.extend_from_slice(name);
+            msg.extend_from_slice(&(index as u32).to_le_bytes());
+            G::hash_to_curve::<D>(GENERATORS_DOMAIN, &msg)
+        };
+        Ok(BulletproofsParameters {
+            num_bits,
+            g_vec: (0..num_bits).map(|i| generator(b"g_vec", i)).collect::<Result<_, _>>()?,
+            h_vec: (0..num_bits).map(|i| generator(b"h_vec", i)).collect::<Result<_, _>>()?,
+            g: generator(b"g", 0)?,
+            h: generator(b"h", 0)?,
+            u: generator(b"u", 0)?,
+        })
+    }
+
+    /// Recompute the verifier challenges, or return `None` if the proof is
+    /// malformed.
+    fn challenges(
+        pp:         &BulletproofsParameters<G>,
+        commitment: &G,
+        proof:      &BulletproofsRangeProof<G>,
+    ) -> Result<Option<Challenges<G::ScalarField>>, Error>
+    {
+        let rounds = pp.num_bits.trailing_zeros() as usize;
+        if proof.l_vec.len() != rounds || proof.r_vec.len() != rounds {
+            return Ok(None);
+        }
+
+        let mut transcript = new_transcript::<G::ScalarField, D>(pp.num_bits, commitment)?;
+        transcript.absorb("A", &proof.a)?;
+        transcript.absorb("S", &proof.s)?;
+        let y = nonzero_challenge(&mut transcript, "y")?;
+        let z = nonzero_challenge(&mut transcript, "z")?;
+        transcript.absorb("T_1", &proof.t_1)?;
+        transcript.absorb("T_2", &proof.t_2)?;
+        let x = nonzero_challenge(&mut transcript, "x")?;
+        transcript.absorb("tau_x", &proof.tau_x)?;
+        transcript.absorb("mu", &proof.mu)?;
+        transcript.absorb("t_hat", &proof.t_hat)?;
+        let w = nonzero_challenge(&mut transcript, "w")?;
+        let mut x_ipa = Vec::with_capacity(rounds);
+        for (l, r) in proof.l_vec.iter().zip(proof.r_vec.iter()) {
+            transcript.absorb("L", l)?;
+            transcript.absorb("R", r)?;
+            x_ipa.push(nonzero_challenge(&mut transcript, "x_ipa")?);
+        }
+        Ok(Some(Challenges { y, z, x, w, x_ipa }))
+    }
+
+    /// Accumulate `weight` times the check of `t_hat` against the commitment:
+    /// `t_hat * g + tau_x * h == z^2 * V + delta(y, z) * g + x * T_1 + x^2 * T_2`.
+    fn accumulate_polynomial_check(
+        pp:         &BulletproofsParameters<G>,
+        commitment: &G,
+        proof:      &BulletproofsRangeProof<G>,
+        challenges: &Challenges<G::ScalarField>,
+        weight:     G::ScalarField,
+        acc:        &mut MsmAccumulator<G>,
+    )
+    {
+        let n = pp.num_bits;
+        let Challenges { y, z, x, .. } = *challenges;
+        let z_squared = z.square();
+
+        // delta(y, z) = (z - z^2) * <1, y^n> - z^3 * <1, 2^n>
+        let sum_y_pows = powers(y, n).into_iter().fold(G::ScalarField::zero(), |acc, p| acc + &p);
+        let sum_two_pows = powers(G::ScalarField::from(2u64), n).into_iter().fold(G::ScalarField::zero(), |acc, p| acc + &p);
+        let delta = (z - &z_squared) * &sum_y_pows - &(z_squared * &z * &sum_two_pows);
+
+        acc.g_scalar += &(weight * &(proof.t_hat - &delta));
+        acc.h_scalar += &(weight * &proof.tau_x);
+        acc.push(*commitment, -(weight * &z_squared));
+        acc.push(proof.t_1, -(weight * &x));
+        acc.push(proof.t_2, -(weight * &x.square()));
+    }
+
+    /// Accumulate `weight` times the check of the inner product argument for
+    /// `<l, r> == t_hat`, with `l` and `r` committed by `A + x * S`.
+    fn accumulate_inner_product_check(
+        pp:         &BulletproofsParameters<G>,
+        proof:      &BulletproofsRangeProof<G>,
+        challenges: &Challenges<G::ScalarField>,
+        weight:     G::ScalarField,
+        acc:        &mut MsmAccumulator<G>,
+    ) -> Result<(), Error>
+    {
+        let n = pp.num_bits;
+        let rounds = challenges.x_ipa.len();
+        let Challenges { y, z, x, w, .. } = *challenges;
+        let z_squared = z.square();
+
+        let x_ipa_inv = challenges.x_ipa.iter()
+            .map(|x_k| x_k.inverse())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| CryptoError::InvalidElement("inner product challenge".to_owned()))?;
+        let y_inv = y.inverse()
+            .ok_or_else(|| CryptoError::InvalidElement("challenge y".to_owned()))?;
+        let y_inv_pows = powers(y_inv, n);
+        let two_pows = powers(G::ScalarField::from(2u64), n);
+
+        for i in 0..n {
+            // s_i is the coefficient of g_i in the folded generator; the one
+            // of h_i is its inverse.
+            let (mut s_i, mut s_i_inv) = (G::ScalarField::one(), G::ScalarField::one());
+            for k in 0..rounds {
+                if (i >> (rounds - 1 - k)) & 1 == 1 {
+                    s_i *= &challenges.x_ipa[k];
+                    s_i_inv *= &x_ipa_inv[k];
+                } else {
+                    s_i *= &x_ipa_inv[k];
+                    s_i_inv *= &challenges.x_ipa[k];
+                }
+            }
+            acc.g_vec_scalars[i] += &(weight * &(-z - &(proof.a_ipa * &s_i)));
+            acc.h_vec_scalars[i] += &(weight * &(
+                z + &(y_inv_pows[i] * &(z_squared * &two_pows[i] - &(proof.b_ipa * &s_i_inv)))
+            ));
+        }
+
+        acc.h_scalar -= &(weight * &proof.mu);
+        acc.u_scalar += &(weight * &w * &(proof.t_hat - &(proof.a_ipa * &proof.b_ipa)));
+        acc.push(proof.a, weight);
+        acc.push(proof.s, weight * &x);
+        for k in 0..rounds {
+            acc.push(proof.l_vec[k], weight * &challenges.x_ipa[k].square());
+            acc.push(proof.r_vec[k], weight * &x_ipa_inv[k].square());
+        }
+        Ok(())
+    }
+}
+
+impl<G, D> RangeProof for Bulletproofs<G, D>
+    where
+        G: ProjectiveCurve + HashToCurve,
+        G::BaseField: PrimeField,
+        D: Digest,
+{
+    type Parameters = BulletproofsParameters<G>;
+    type Commitment = G;
+    type Blinding = G::ScalarField;
+    type Proof = BulletproofsRangeProof<G>;
+
+    /// Derives the parameters from a seed sampled from `rng`, see `setup_from_seed`.
+    fn setup<R: Rng>(rng: &mut R, num_bits: usize) -> Result<Self::Parameters, Error> {
+        let seed: [u8; 32] = rng.gen();
+        Self::setup_from_seed(&seed, num_bits)
+    }
+
+    fn commit(
+        pp:       &Self::Parameters,
+        value:    u64,
+        blinding: &Self::Blinding,
+    ) -> Result<Self::Commitment, Error>
+    {
+        Ok(pp.g.mul(&G::ScalarField::from(value)) + &pp.h.mul(blinding))
+    }
+
+    fn prove<R: Rng>(
+        rng:      &mut R,
+        pp:       &Self::Parameters,
+        value:    u64,
+        blinding: &Self::Blinding,
+    ) -> Result<(Self::Commitment, Self::Proof), Error>
+    {
+        let n = pp.num_bits;
+        if n < 64 && value >> n != 0 {
+            return Err(Box::new(CryptoError::InvalidElement("value".to_owned())));
+        }
+
+        let commitment = Self::commit(pp, value, blinding)?;
+        let mut transcript = new_transcript::<G::ScalarField, D>(n, &commitment)?;
+
+        // Commit to the bits a_L of the value, and to a_R = a_L - 1^n
+        let one = G::ScalarField::one();
+        let a_l = (0..n)
+            .map(|i| if (value >> i) & 1 == 1 { one } else { G::ScalarField::zero() })
+            .collect::<Vec<_>>();
+        let a_r = a_l.iter().map(|bit| *bit - &one).collect::<Vec<_>>();
+        let alpha = G::ScalarField::rand(rng);
+        let a = pp.h.mul(&alpha) + &msm(&pp.g_vec, &a_l) + &msm(&pp.h_vec, &a_r);
+
+        // Commit to the blinding vectors s_L and s_R
+        let s_l = (0..n).map(|_| G::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let s_r = (0..n).map(|_| G::ScalarField::rand(rng)).collect::<Vec<_>>();
+        let rho = G::ScalarField::rand(rng);
+        let s = pp.h.mul(&rho) + &msm(&pp.g_vec, &s_l) + &msm(&pp.h_vec, &s_r);
+
+        transcript.absorb("A", &a)?;
+        transcript.absorb("S", &s)?;
+        let y: G::ScalarField = nonzero_challenge(&mut transcript, "y")?;
+        let z: G::ScalarField = nonzero_challenge(&mut transcript, "z")?;
+        let z_squared = z.square();
+        let y_pows = powers(y, n);
+        let two_pows = powers(G::ScalarField::from(2u64), n);
+
+        // l(X) = l_0 + l_1 * X, r(X) = r_0 + r_1 * X and t(X) = <l(X), r(X)>
+        let l_0 = a_l.iter().map(|a_l_i| *a_l_i - &z).collect::<Vec<_>>();
+        let l_1 = s_l;
+        let r_0 = (0..n)
+            .map(|i| y_pows[i] * &(a_r[i] + &z) + &(z_squared * &two_pows[i]))
+            .collect::<Vec<_>>();
+        let r_1 = (0..n).map(|i| y_pows[i] * &s_r[i]).collect::<Vec<_>>();
+        let t_1 = inner_product(&l_0, &r_1) + &inner_product(&l_1, &r_0);
+        let t_2 = inner_product(&l_1, &r_1);
+
+        // Commit to the coefficients t_1 and t_2
+        let tau_1 = G::ScalarField::rand(rng);
+        let tau_2 = G::ScalarField::rand(rng);
+        let big_t_1 = pp.g.mul(&t_1) + &pp.h.mul(&tau_1);
+        let big_t_2 = pp.g.mul(&t_2) + &pp.h.mul(&tau_2);
+
+        transcript.absorb("T_1", &big_t_1)?;
+        transcript.absorb("T_2", &big_t_2)?;
+        let x: G::ScalarField = nonzero_challenge(&mut transcript, "x")?;
+
+        let l = (0..n).map(|i| l_0[i] + &(l_1[i] * &x)).collect::<Vec<_>>();
+        let r = (0..n).map(|i| r_0[i] + &(r_1[i] * &x)).collect::<Vec<_>>();
+        let t_hat = inner_product(&l, &r);
+        let tau_x = tau_2 * &x.square() + &(tau_1 * &x) + &(z_squared * blinding);
+        let mu = alpha + &(rho * &x);
+
+        transcript.absorb("tau_x", &tau_x)?;
+        transcript.absorb("mu", &mu)?;
+        transcript.absorb("t_hat", &t_hat)?;
+        let w: G::ScalarField = nonzero_challenge(&mut transcript, "w")?;
+        let u = pp.u.mul(&w);
+
+        // Inner product argument for <l, r> = t_hat, w.r.t. the generators
+        // g_vec and h'_vec, where h'_i = y^-i * h_i.
+        let y_inv = y.inverse().ok_or_else(|| CryptoError::InvalidElement("challenge y".to_owned()))?;
+        let mut g_vec = pp.g_vec.clone();
+        let mut h_vec = pp.h_vec.iter()
+            .zip(powers(y_inv, n))
+            .map(|(h_i, y_inv_pow)| h_i.mul(&y_inv_pow))
+            .collect::<Vec<_>>();
+        let (mut a_vec, mut b_vec) = (l, r);
+        let mut l_vec = Vec::new();
+        let mut r_vec = Vec::new();
+
+        while a_vec.len() > 1 {
+            let half = a_vec.len() / 2;
+            let (a_lo, a_hi) = a_vec.split_at(half);
+            let (b_lo, b_hi) = b_vec.split_at(half);
+            let (g_lo, g_hi) = g_vec.split_at(half);
+            let (h_lo, h_hi) = h_vec.split_at(half);
+
+            let l_k = msm(g_hi, a_lo) + &msm(h_lo, b_hi) + &u.mul(&inner_product(a_lo, b_hi));
+            let r_k = msm(g_lo, a_hi) + &msm(h_hi, b_lo) + &u.mul(&inner_product(a_hi, b_lo));
+            transcript.absorb("L", &l_k)?;
+            transcript.absorb("R", &r_k)?;
+            let x_k: G::ScalarField = nonzero_challenge(&mut transcript, "x_ipa")?;
+            let x_k_inv = x_k.inverse().ok_or_else(|| CryptoError::InvalidElement("inner product challenge".to_owned()))?;
+
+            // a' = x * a_lo + x^-1 * a_hi,  b' = x^-1 * b_lo + x * b_hi,
+            // g' = x^-1 * g_lo + x * g_hi, h' = x * h_lo + x^-1 * h_hi
+            let a_next = (0..half).map(|i| a_lo[i] * &x_k + &(a_hi[i] * &x_k_inv)).collect();
+            let b_next = (0..half).map(|i| b_lo[i] * &x_k_inv + &(b_hi[i] * &x_k)).collect();
+            let g_next = (0..half).map(|i| g_lo[i].mul(&x_k_inv) + &g_hi[i].mul(&x_k)).collect();
+            let h_next = (0..half).map(|i| h_lo[i].mul(&x_k) + &h_hi[i].mul(&x_k_inv)).collect();
+            a_vec = a_next;
+            b_vec = b_next;
+            g_vec = g_next;
+            h_vec = h_next;
+
+            l_vec.push(l_k);
+            r_vec.push(r_k);
+        }
+
+        let proof = BulletproofsRangeProof {
+            a,
+            s,
+            t_1: big_t_1,
+            t_2: big_t_2,
+            tau_x,
+            mu,
+            t_hat,
+            l_vec,
+            r_vec,
+            a_ipa: a_vec[0],
+            b_ipa: b_vec[0],
+        };
+        Ok((commitment, proof))
+    }
+
+    fn verify(
+        pp:         &Self::Parameters,
+        commitment: &Self::Commitment,
+        proof:      &Self::Proof,
+    ) -> Result<bool, Error>
+    {
+        let challenges = match Self::challenges(pp, commitment, proof)? {
+            Some(challenges) => challenges,
+            None => return Ok(false),
+        };
+
+        let mut acc = MsmAccumulator::new(pp.num_bits);
+        Self::accumulate_polynomial_check(pp, commitment, proof, &challenges, G::ScalarField::one(), &mut acc);
+        if !acc.is_zero(pp) {
+            return Ok(false);
+        }
+
+        let mut acc = MsmAccumulator::new(pp.num_bits);
+        Self::accumulate_inner_product_check(pp, proof, &challenges, G::ScalarField::one(), &mut acc)?;
+        Ok(acc.is_zero(pp))
+    }
+
+    fn batch_verify<R: Rng>(
+        rng:       &mut R,
+        pp:        &Self::Parameters,
+        instances: &[(Self::Commitment, Self::Proof)],
+    ) -> Result<bool, Error>
+    {
+        // Check a random linear combination of all the verification equations
+        let mut acc = MsmAccumulator::new(pp.num_bits);
+        for (commitment, proof) in instances.iter() {
+            let challenges = match Self::challenges(pp, commitment, proof)? {
+                Some(challenges) => challenges,
+                None => return Ok(false),
+            };
+            Self::accumulate_polynomial_check(pp, commitment, proof, &challenges, G::ScalarField::rand(rng), &mut acc);
+            Self::accumulate_inner_product_check(pp, proof, &challenges, G::ScalarField::rand(rng), &mut acc)?;
+        }
+        Ok(acc.is_zero(pp))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::range_proof::{RangeProof, bulletproofs::*};
+    use algebra::{
+        curves::jubjub::JubJubProjective as JubJub,
+        to_bytes, AffineCurve, Field, ProjectiveCurve, ToBytes, FromBytes, UniformRand,
+    };
+    use blake2::Blake2s;
+    use rand::{Rng, thread_rng};
+
+    type RangeProofJubJub = Bulletproofs<JubJub, Blake2s>;
+    type Fr = <JubJub as ProjectiveCurve>::ScalarField;
+
+    #[test]
+    fn bulletproofs_range_proof_test() {
+        let rng = &mut thread_rng();
+        let pp = RangeProofJubJub::setup(rng, 32).unwrap();
+
+        for &value in [0u64, 1, 42, (1 << 32) - 1].iter() {
+            let blinding = Fr::rand(rng);
+            let (commitment, proof) = RangeProofJubJub::prove(rng, &pp, value, &blinding).unwrap();
+            assert_eq!(commitment, RangeProofJubJub::commit(&pp, value, &blinding).unwrap());
+            assert!(RangeProofJubJub::verify(&pp, &commitment, &proof).unwrap());
+
+            //Serialization/deserialization test
+            let proof_serialized = to_bytes!(proof).unwrap();
+            let proof_deserialized = BulletproofsRangeProof::<JubJub>::read(proof_serialized.as_slice()).unwrap();
+            assert_eq!(proof, proof_deserialized);
+
+            //Proof for another commitment
+            let other_commitment = RangeProofJubJub::commit(&pp, value + 1, &blinding).unwrap();
+            assert!(!RangeProofJubJub::verify(&pp, &other_commitment, &proof).unwrap());
+        }
+
+        //Out of range value
+        let blinding = Fr::rand(rng);
+        assert!(RangeProofJubJub::prove(rng, &pp, 1 << 32, &blinding).is_err());
+
+        //Tampered proof
+        let value = rng.gen::<u32>() as u64;
+        let (commitment, mut proof) = RangeProofJubJub::prove(rng, &pp, value, &blinding).unwrap();
+        proof.a_ipa += &Fr::one();
+        assert!(!RangeProofJubJub::verify(&pp, &commitment, &proof).unwrap());
+        proof.l_vec.pop();
+        assert!(!RangeProofJubJub::verify(&pp, &commitment, &proof).unwrap());
+    }
+
+    #[test]
+    fn bulletproofs_setup_from_seed_test() {
+        let pp = RangeProofJubJub::setup_from_seed(b"seed", 8).unwrap();
+        let same_pp = RangeProofJubJub::setup_from_seed(b"seed", 8).unwrap();
+        assert_eq!(pp.g_vec, same_pp.g_vec);
+        assert_eq!(pp.h_vec, same_pp.h_vec);
+        assert_eq!((pp.g, pp.h, pp.u), (same_pp.g, same_pp.h, same_pp.u));
+
+        let other_pp = RangeProofJubJub::setup_from_seed(b"other seed", 8).unwrap();
+        assert_ne!(pp.g_vec, other_pp.g_vec);
+        assert_ne!(pp.g, other_pp.g);
+
+        //All the generators are distinct points of the prime order subgroup
+        let mut generators = pp.g_vec.iter().chain(pp.h_vec.iter()).cloned().collect::<Vec<_>>();
+        generators.extend_from_slice(&[pp.g, pp.h, pp.u]);
+        for (i, generator) in generators.iter().enumerate() {
+            assert!(!generator.is_zero());
+            assert!(generator.into_affine().group_membership_test());
+            assert!(!generators[..i].contains(generator));
+        }
+
+        assert!(RangeProofJubJub::setup_from_seed(b"seed", 128).is_err());
+    }
+
+    #[test]
+    fn bulletproofs_deserialization_bounds_test() {
+        let rng = &mut thread_rng();
+        let pp = RangeProofJubJub::setup(rng, 8).unwrap();
+        let blinding = Fr::rand(rng);
+        let (_, proof) = RangeProofJubJub::prove(rng, &pp, 42, &blinding).unwrap();
+
+        //Overwrite the number of rounds, read after 4 points and 3 scalars
+        let mut proof_serialized = to_bytes!(proof).unwrap();
+        let offset = to_bytes!(proof.a, proof.s, proof.t_1, proof.t_2, proof.tau_x, proof.mu, proof.t_hat).unwrap().len();
+        proof_serialized[offset..offset + 4].copy_from_slice(&u32::max_value().to_le_bytes());
+        assert!(BulletproofsRangeProof::<JubJub>::read(proof_serialized.as_slice()).is_err());
+    }
+
+    #[test]
+    fn bulletproofs_batch_verification_test() {
+        let rng = &mut thread_rng();
+        let pp = RangeProofJubJub::setup(rng, 64).unwrap();
+
+        let mut instances = (0..5)
+            .map(|_| {
+                let value = rng.gen();
+                let blinding = Fr::rand(rng);
+                RangeProofJubJub::prove(rng, &pp, value, &blinding).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert!(RangeProofJubJub::batch_verify(rng, &pp, &instances).unwrap());
+
+        //Swap two commitments
+        let commitment = instances[0].0;
+        instances[0].0 = instances[1].0;
+        instances[1].0 = commitment;
+        assert!(!RangeProofJubJub::batch_verify(rng, &pp, &instances).unwrap());
+    }
+}
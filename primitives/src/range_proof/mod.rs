@@ -0,0 +1,51 @@
+use crate::Error;
+use algebra::bytes::{FromBytes, ToBytes};
+use rand::Rng;
+use std::{fmt::Debug, hash::Hash};
+
+pub mod bulletproofs;
+
+/// A proof that a committed value lies in the range `[0, 2^n)`, for a bit
+/// length `n` fixed by the parameters.
+pub trait RangeProof {
+    type Parameters: Clone;
+    type Commitment: ToBytes + Hash + Eq + Clone + Debug + Send + Sync;
+    type Blinding: Clone + Default;
+    type Proof: Clone + Debug + Eq + PartialEq + ToBytes + FromBytes;
+
+    /// Sample parameters for ranges of `num_bits` bits.
+    fn setup<R: Rng>(
+        rng:      &mut R,
+        num_bits: usize,
+    ) -> Result<Self::Parameters, Error>;
+
+    fn commit(
+        pp:       &Self::Parameters,
+        value:    u64,
+        blinding: &Self::Blinding,
+    ) -> Result<Self::Commitment, Error>;
+
+    /// Prove that `value` is in range, returning the proof together with
+    /// the commitment of `value` under `blinding`.
+    fn prove<R: Rng>(
+        rng:      &mut R,
+        pp:       &Self::Parameters,
+        value:    u64,
+        blinding: &Self::Blinding,
+    ) -> Result<(Self::Commitment, Self::Proof), Error>;
+
+    fn verify(
+        pp:         &Self::Parameters,
+        commitment: &Self::Commitment,
+        proof:      &Self::Proof,
+    ) -> Result<bool, Error>;
+
+    /// Verify all the `(commitment, proof)` pairs at once. This is cheaper
+    /// than verifying them one by one, but does not tell which of the proofs
+    /// is invalid.
+    fn batch_verify<R: Rng>(
+        rng:       &mut R,
+        pp:        &Self::Parameters,
+        instances: &[(Self::Commitment, Self::Proof)],
+    ) -> Result<bool, Error>;
+}
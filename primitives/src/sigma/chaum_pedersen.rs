@@ -0,0 +1,121 @@
+use crate::{sigma::SigmaProtocol, Error};
+use algebra::{bytes::ToBytes, Group, UniformRand};
+use rand::Rng;
+use std::{
+    io::{Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+/// Proof of equality of the discrete logarithms of `public_key_1` to base
+/// `generator_1` and of `public_key_2` to base `generator_2`, e.g. of the
+/// correctness of a Diffie-Hellman tuple.
+pub struct ChaumPedersenProtocol<G: Group> {
+    _group: PhantomData<G>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+Copy(bound = "G: Group"),
+Clone(bound = "G: Group"),
+Debug(bound = "G: Group"),
+Eq(bound = "G: Group"),
+PartialEq(bound = "G: Group")
+)]
+pub struct ChaumPedersenStatement<G: Group> {
+    pub generator_1:  G,
+    pub generator_2:  G,
+    pub public_key_1: G,
+    pub public_key_2: G,
+}
+
+impl<G: Group> ToBytes for ChaumPedersenStatement<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.generator_1.write(&mut writer)?;
+        self.generator_2.write(&mut writer)?;
+        self.public_key_1.write(&mut writer)?;
+        self.public_key_2.write(&mut writer)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+Copy(bound = "G: Group"),
+Clone(bound = "G: Group"),
+Debug(bound = "G: Group"),
+Eq(bound = "G: Group"),
+PartialEq(bound = "G: Group")
+)]
+pub struct ChaumPedersenCommitment<G: Group> {
+    pub a_1: G,
+    pub a_2: G,
+}
+
+impl<G: Group> ToBytes for ChaumPedersenCommitment<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.a_1.write(&mut writer)?;
+        self.a_2.write(&mut writer)
+    }
+}
+
+impl<G: Group> SigmaProtocol for ChaumPedersenProtocol<G> {
+    type Challenge = G::ScalarField;
+    type Statement = ChaumPedersenStatement<G>;
+    type Witness = G::ScalarField;
+    type Commitment = ChaumPedersenCommitment<G>;
+    type ProverState = G::ScalarField;
+    type Response = G::ScalarField;
+
+    fn commit<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        _witness:  &Self::Witness,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error>
+    {
+        let r = G::ScalarField::rand(rng);
+        let commitment = ChaumPedersenCommitment {
+            a_1: statement.generator_1.mul(&r),
+            a_2: statement.generator_2.mul(&r),
+        };
+        Ok((commitment, r))
+    }
+
+    fn respond(
+        _statement: &Self::Statement,
+        witness:    &Self::Witness,
+        state:      Self::ProverState,
+        challenge:  &Self::Challenge,
+    ) -> Result<Self::Response, Error>
+    {
+        // z = r + c * x
+        Ok(state + &(*challenge * witness))
+    }
+
+    fn verify(
+        statement:  &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge:  &Self::Challenge,
+        response:   &Self::Response,
+    ) -> Result<bool, Error>
+    {
+        // z * g_i == a_i + c * h_i, for i = 1, 2
+        let check_1 = statement.generator_1.mul(response)
+            == commitment.a_1 + &statement.public_key_1.mul(challenge);
+        let check_2 = statement.generator_2.mul(response)
+            == commitment.a_2 + &statement.public_key_2.mul(challenge);
+        Ok(check_1 && check_2)
+    }
+
+    fn simulate<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+    ) -> Result<(Self::Commitment, Self::Response), Error>
+    {
+        let z = G::ScalarField::rand(rng);
+        let commitment = ChaumPedersenCommitment {
+            a_1: statement.generator_1.mul(&z) - &statement.public_key_1.mul(challenge),
+            a_2: statement.generator_2.mul(&z) - &statement.public_key_2.mul(challenge),
+        };
+        Ok((commitment, z))
+    }
+}
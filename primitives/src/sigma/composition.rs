@@ -0,0 +1,241 @@
+use crate::{sigma::SigmaProtocol, CryptoError, Error};
+use algebra::{bytes::ToBytes, UniformRand};
+use rand::Rng;
+use std::{
+    io::{Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+/// A pair of statements, witnesses, commitments or responses of the two
+/// protocols of a composition.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SigmaPair<A, B> {
+    pub left:  A,
+    pub right: B,
+}
+
+impl<A: ToBytes, B: ToBytes> ToBytes for SigmaPair<A, B> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.left.write(&mut writer)?;
+        self.right.write(&mut writer)
+    }
+}
+
+/// Proof of knowledge of a witness for both of the statements of `P1` and `P2`,
+/// answering the same challenge.
+pub struct AndProtocol<P1: SigmaProtocol, P2: SigmaProtocol<Challenge = P1::Challenge>> {
+    _left:  PhantomData<P1>,
+    _right: PhantomData<P2>,
+}
+
+impl<P1, P2> SigmaProtocol for AndProtocol<P1, P2>
+    where
+        P1: SigmaProtocol,
+        P2: SigmaProtocol<Challenge = P1::Challenge>,
+{
+    type Challenge = P1::Challenge;
+    type Statement = SigmaPair<P1::Statement, P2::Statement>;
+    type Witness = SigmaPair<P1::Witness, P2::Witness>;
+    type Commitment = SigmaPair<P1::Commitment, P2::Commitment>;
+    type ProverState = SigmaPair<P1::ProverState, P2::ProverState>;
+    type Response = SigmaPair<P1::Response, P2::Response>;
+
+    fn commit<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        witness:   &Self::Witness,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error>
+    {
+        let (left_commitment, left_state) = P1::commit(rng, &statement.left, &witness.left)?;
+        let (right_commitment, right_state) = P2::commit(rng, &statement.right, &witness.right)?;
+        Ok((
+            SigmaPair { left: left_commitment, right: right_commitment },
+            SigmaPair { left: left_state, right: right_state },
+        ))
+    }
+
+    fn respond(
+        statement: &Self::Statement,
+        witness:   &Self::Witness,
+        state:     Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Error>
+    {
+        Ok(SigmaPair {
+            left:  P1::respond(&statement.left, &witness.left, state.left, challenge)?,
+            right: P2::respond(&statement.right, &witness.right, state.right, challenge)?,
+        })
+    }
+
+    fn verify(
+        statement:  &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge:  &Self::Challenge,
+        response:   &Self::Response,
+    ) -> Result<bool, Error>
+    {
+        Ok(
+            P1::verify(&statement.left, &commitment.left, challenge, &response.left)? &&
+            P2::verify(&statement.right, &commitment.right, challenge, &response.right)?
+        )
+    }
+
+    fn simulate<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+    ) -> Result<(Self::Commitment, Self::Response), Error>
+    {
+        let (left_commitment, left_response) = P1::simulate(rng, &statement.left, challenge)?;
+        let (right_commitment, right_response) = P2::simulate(rng, &statement.right, challenge)?;
+        Ok((
+            SigmaPair { left: left_commitment, right: right_commitment },
+            SigmaPair { left: left_response, right: right_response },
+        ))
+    }
+}
+
+/// Proof of knowledge of a witness for one of the statements of `P1` and `P2`,
+/// without revealing which one. The branch the prover has no witness for is
+/// simulated, and the two branch challenges are bound to sum up to the
+/// verifier challenge.
+pub struct OrProtocol<P1: SigmaProtocol, P2: SigmaProtocol<Challenge = P1::Challenge>> {
+    _left:  PhantomData<P1>,
+    _right: PhantomData<P2>,
+}
+
+#[derive(Clone, Debug)]
+pub enum OrWitness<W1, W2> {
+    Left(W1),
+    Right(W2),
+}
+
+#[derive(Derivative)]
+#[derivative(
+Clone(bound = "P1: SigmaProtocol, P2: SigmaProtocol<Challenge = P1::Challenge>")
+)]
+pub enum OrProverState<P1: SigmaProtocol, P2: SigmaProtocol<Challenge = P1::Challenge>> {
+    Left {
+        state:               P1::ProverState,
+        simulated_challenge: P1::Challenge,
+        simulated_response:  P2::Response,
+    },
+    Right {
+        state:               P2::ProverState,
+        simulated_challenge: P1::Challenge,
+        simulated_response:  P1::Response,
+    },
+}
+
+#[derive(Derivative)]
+#[derivative(
+Clone(bound = "P1: SigmaProtocol, P2: SigmaProtocol<Challenge = P1::Challenge>"),
+Debug(bound = "P1: SigmaProtocol, P2: SigmaProtocol<Challenge = P1::Challenge>"),
+Eq(bound = "P1: SigmaProtocol, P2: SigmaProtocol<Challenge = P1::Challenge>"),
+PartialEq(bound = "P1: SigmaProtocol, P2: SigmaProtocol<Challenge = P1::Challenge>")
+)]
+pub struct OrResponse<P1: SigmaProtocol, P2: SigmaProtocol<Challenge = P1::Challenge>> {
+    /// The challenge of the left branch; the one of the right branch is the
+    /// verifier challenge minus this one.
+    pub left_challenge: P1::Challenge,
+    pub left:           P1::Response,
+    pub right:          P2::Response,
+}
+
+impl<P1, P2> ToBytes for OrResponse<P1, P2>
+    where
+        P1: SigmaProtocol,
+        P2: SigmaProtocol<Challenge = P1::Challenge>,
+{
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.left_challenge.write(&mut writer)?;
+        self.left.write(&mut writer)?;
+        self.right.write(&mut writer)
+    }
+}
+
+impl<P1, P2> SigmaProtocol for OrProtocol<P1, P2>
+    where
+        P1: SigmaProtocol,
+        P2: SigmaProtocol<Challenge = P1::Challenge>,
+{
+    type Challenge = P1::Challenge;
+    type Statement = SigmaPair<P1::Statement, P2::Statement>;
+    type Witness = OrWitness<P1::Witness, P2::Witness>;
+    type Commitment = SigmaPair<P1::Commitment, P2::Commitment>;
+    type ProverState = OrProverState<P1, P2>;
+    type Response = OrResponse<P1, P2>;
+
+    fn commit<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        witness:   &Self::Witness,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error>
+    {
+        let simulated_challenge = P1::Challenge::rand(rng);
+        match witness {
+            OrWitness::Left(witness) => {
+                let (left, state) = P1::commit(rng, &statement.left, witness)?;
+                let (right, simulated_response) = P2::simulate(rng, &statement.right, &simulated_challenge)?;
+                Ok((SigmaPair { left, right }, OrProverState::Left { state, simulated_challenge, simulated_response }))
+            },
+            OrWitness::Right(witness) => {
+                let (left, simulated_response) = P1::simulate(rng, &statement.left, &simulated_challenge)?;
+                let (right, state) = P2::commit(rng, &statement.right, witness)?;
+                Ok((SigmaPair { left, right }, OrProverState::Right { state, simulated_challenge, simulated_response }))
+            },
+        }
+    }
+
+    fn respond(
+        statement: &Self::Statement,
+        witness:   &Self::Witness,
+        state:     Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Error>
+    {
+        match (witness, state) {
+            (OrWitness::Left(witness), OrProverState::Left { state, simulated_challenge, simulated_response }) => {
+                let left_challenge = *challenge - &simulated_challenge;
+                let left = P1::respond(&statement.left, witness, state, &left_challenge)?;
+                Ok(OrResponse { left_challenge, left, right: simulated_response })
+            },
+            (OrWitness::Right(witness), OrProverState::Right { state, simulated_challenge, simulated_response }) => {
+                let right_challenge = *challenge - &simulated_challenge;
+                let right = P2::respond(&statement.right, witness, state, &right_challenge)?;
+                Ok(OrResponse { left_challenge: simulated_challenge, left: simulated_response, right })
+            },
+            _ => Err(Box::new(CryptoError::InvalidElement("prover state".to_owned()))),
+        }
+    }
+
+    fn verify(
+        statement:  &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge:  &Self::Challenge,
+        response:   &Self::Response,
+    ) -> Result<bool, Error>
+    {
+        let right_challenge = *challenge - &response.left_challenge;
+        Ok(
+            P1::verify(&statement.left, &commitment.left, &response.left_challenge, &response.left)? &&
+            P2::verify(&statement.right, &commitment.right, &right_challenge, &response.right)?
+        )
+    }
+
+    fn simulate<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+    ) -> Result<(Self::Commitment, Self::Response), Error>
+    {
+        let left_challenge = P1::Challenge::rand(rng);
+        let right_challenge = *challenge - &left_challenge;
+        let (left_commitment, left) = P1::simulate(rng, &statement.left, &left_challenge)?;
+        let (right_commitment, right) = P2::simulate(rng, &statement.right, &right_challenge)?;
+        Ok((
+            SigmaPair { left: left_commitment, right: right_commitment },
+            OrResponse { left_challenge, left, right },
+        ))
+    }
+}
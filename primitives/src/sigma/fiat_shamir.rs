@@ -0,0 +1,204 @@
+use crate::{fiat_shamir::ChaChaFSRng, sigma::SigmaProtocol, transcript::Transcript, Error};
+use algebra::bytes::ToBytes;
+use digest::Digest;
+use rand::Rng;
+use std::{
+    io::{Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+/// The non-interactive version of the sigma protocol `P`, obtained by
+/// deriving the challenge from a `Transcript` of the statement and of the
+/// commitment of the prover, whose messages are chained by the hash `D`.
+pub struct FiatShamir<P: SigmaProtocol, D: Digest> {
+    _protocol: PhantomData<P>,
+    _hash:     PhantomData<D>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+Clone(bound = "P: SigmaProtocol"),
+Debug(bound = "P: SigmaProtocol"),
+Eq(bound = "P: SigmaProtocol"),
+PartialEq(bound = "P: SigmaProtocol")
+)]
+pub struct FiatShamirProof<P: SigmaProtocol> {
+    pub commitment: P::Commitment,
+    pub response:   P::Response,
+}
+
+impl<P: SigmaProtocol> ToBytes for FiatShamirProof<P> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.commitment.write(&mut writer)?;
+        self.response.write(&mut writer)
+    }
+}
+
+impl<P: SigmaProtocol, D: Digest> FiatShamir<P, D> {
+
+    /// Derive the challenge from the domain separation `label`, the `statement`
+    /// and the `commitment`.
+    pub fn challenge(
+        label:      &[u8],
+        statement:  &P::Statement,
+        commitment: &P::Commitment,
+    ) -> Result<P::Challenge, Error>
+    {
+        let mut transcript = Transcript::<ChaChaFSRng<P::Challenge, D>>::new(label, None)?;
+        transcript.absorb("statement", statement)?;
+        transcript.absorb("commitment", commitment)?;
+        transcript.challenge("challenge")
+    }
+
+    pub fn prove<R: Rng>(
+        rng:       &mut R,
+        label:     &[u8],
+        statement: &P::Statement,
+        witness:   &P::Witness,
+    ) -> Result<FiatShamirProof<P>, Error>
+    {
+        let (commitment, state) = P::commit(rng, statement, witness)?;
+        let challenge = Self::challenge(label, statement, &commitment)?;
+        let response = P::respond(statement, witness, state, &challenge)?;
+        Ok(FiatShamirProof { commitment, response })
+    }
+
+    pub fn verify(
+        label:     &[u8],
+        statement: &P::Statement,
+        proof:     &FiatShamirProof<P>,
+    ) -> Result<bool, Error>
+    {
+        let challenge = Self::challenge(label, statement, &proof.commitment)?;
+        P::verify(statement, &proof.commitment, &challenge, &proof.response)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sigma::*;
+    use algebra::{curves::jubjub::JubJubProjective as JubJub, Group, UniformRand};
+    use blake2::Blake2s;
+    use rand::thread_rng;
+
+    type Fr = <JubJub as Group>::ScalarField;
+
+    const LABEL: &[u8] = b"sigma test";
+
+    fn schnorr_instance() -> (SchnorrStatement<JubJub>, Fr) {
+        let rng = &mut thread_rng();
+        let generator = JubJub::rand(rng);
+        let x = Fr::rand(rng);
+        (SchnorrStatement { generator, public_key: generator.mul(&x) }, x)
+    }
+
+    #[test]
+    fn schnorr_test() {
+        type FS = FiatShamir<SchnorrProtocol<JubJub>, Blake2s>;
+        let rng = &mut thread_rng();
+        let (statement, witness) = schnorr_instance();
+
+        let proof = FS::prove(rng, LABEL, &statement, &witness).unwrap();
+        assert!(FS::verify(LABEL, &statement, &proof).unwrap());
+
+        //Different label
+        assert!(!FS::verify(b"other label", &statement, &proof).unwrap());
+
+        //Different statement
+        let (wrong_statement, _) = schnorr_instance();
+        assert!(!FS::verify(LABEL, &wrong_statement, &proof).unwrap());
+
+        //Wrong witness
+        let wrong_witness = Fr::rand(rng);
+        let proof = FS::prove(rng, LABEL, &statement, &wrong_witness).unwrap();
+        assert!(!FS::verify(LABEL, &statement, &proof).unwrap());
+    }
+
+    #[test]
+    fn okamoto_test() {
+        type FS = FiatShamir<OkamotoProtocol<JubJub>, Blake2s>;
+        let rng = &mut thread_rng();
+        let (generator_1, generator_2) = (JubJub::rand(rng), JubJub::rand(rng));
+        let witness = (Fr::rand(rng), Fr::rand(rng));
+        let statement = OkamotoStatement {
+            generator_1,
+            generator_2,
+            commitment: generator_1.mul(&witness.0) + &generator_2.mul(&witness.1),
+        };
+
+        let proof = FS::prove(rng, LABEL, &statement, &witness).unwrap();
+        assert!(FS::verify(LABEL, &statement, &proof).unwrap());
+
+        let proof = FS::prove(rng, LABEL, &statement, &(witness.1, witness.0)).unwrap();
+        assert!(!FS::verify(LABEL, &statement, &proof).unwrap());
+    }
+
+    #[test]
+    fn chaum_pedersen_test() {
+        type FS = FiatShamir<ChaumPedersenProtocol<JubJub>, Blake2s>;
+        let rng = &mut thread_rng();
+        let (generator_1, generator_2) = (JubJub::rand(rng), JubJub::rand(rng));
+        let x = Fr::rand(rng);
+        let statement = ChaumPedersenStatement {
+            generator_1,
+            generator_2,
+            public_key_1: generator_1.mul(&x),
+            public_key_2: generator_2.mul(&x),
+        };
+
+        let proof = FS::prove(rng, LABEL, &statement, &x).unwrap();
+        assert!(FS::verify(LABEL, &statement, &proof).unwrap());
+
+        //Not a DH tuple
+        let wrong_statement = ChaumPedersenStatement {
+            public_key_2: generator_2.mul(&Fr::rand(rng)),
+            ..statement
+        };
+        let proof = FS::prove(rng, LABEL, &wrong_statement, &x).unwrap();
+        assert!(!FS::verify(LABEL, &wrong_statement, &proof).unwrap());
+    }
+
+    #[test]
+    fn and_test() {
+        type FS = FiatShamir<AndProtocol<SchnorrProtocol<JubJub>, SchnorrProtocol<JubJub>>, Blake2s>;
+        let rng = &mut thread_rng();
+        let (statement_1, witness_1) = schnorr_instance();
+        let (statement_2, witness_2) = schnorr_instance();
+        let statement = SigmaPair { left: statement_1, right: statement_2 };
+
+        let proof = FS::prove(rng, LABEL, &statement, &SigmaPair { left: witness_1, right: witness_2 }).unwrap();
+        assert!(FS::verify(LABEL, &statement, &proof).unwrap());
+
+        let proof = FS::prove(rng, LABEL, &statement, &SigmaPair { left: witness_1, right: witness_1 }).unwrap();
+        assert!(!FS::verify(LABEL, &statement, &proof).unwrap());
+    }
+
+    #[test]
+    fn or_test() {
+        type FS = FiatShamir<OrProtocol<SchnorrProtocol<JubJub>, SchnorrProtocol<JubJub>>, Blake2s>;
+        let rng = &mut thread_rng();
+        let (statement_1, witness_1) = schnorr_instance();
+        let (statement_2, witness_2) = schnorr_instance();
+        let statement = SigmaPair { left: statement_1, right: statement_2 };
+
+        //Knowledge of either witness is enough
+        let proof = FS::prove(rng, LABEL, &statement, &OrWitness::Left(witness_1)).unwrap();
+        assert!(FS::verify(LABEL, &statement, &proof).unwrap());
+
+        let proof = FS::prove(rng, LABEL, &statement, &OrWitness::Right(witness_2)).unwrap();
+        assert!(FS::verify(LABEL, &statement, &proof).unwrap());
+
+        //But one of them is needed
+        let proof = FS::prove(rng, LABEL, &statement, &OrWitness::Left(witness_2)).unwrap();
+        assert!(!FS::verify(LABEL, &statement, &proof).unwrap());
+
+        //Simulated transcripts are accepting
+        let challenge = Fr::rand(rng);
+        let (commitment, response) = OrProtocol::<SchnorrProtocol<JubJub>, SchnorrProtocol<JubJub>>::simulate(
+            rng, &statement, &challenge
+        ).unwrap();
+        assert!(OrProtocol::<SchnorrProtocol<JubJub>, SchnorrProtocol<JubJub>>::verify(
+            &statement, &commitment, &challenge, &response
+        ).unwrap());
+    }
+}
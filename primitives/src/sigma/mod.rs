@@ -0,0 +1,63 @@
+use crate::Error;
+use algebra::{bytes::ToBytes, PrimeField};
+use rand::Rng;
+use std::fmt::Debug;
+
+pub mod schnorr;
+pub use self::schnorr::*;
+
+pub mod okamoto;
+pub use self::okamoto::*;
+
+pub mod chaum_pedersen;
+pub use self::chaum_pedersen::*;
+
+pub mod composition;
+pub use self::composition::*;
+
+pub mod fiat_shamir;
+pub use self::fiat_shamir::*;
+
+/// A three-move public coin proof of knowledge (commitment, challenge, response),
+/// which is special sound and honest-verifier zero-knowledge.
+pub trait SigmaProtocol {
+    type Challenge: PrimeField;
+    type Statement: ToBytes + Clone + Debug;
+    type Witness: Clone;
+    type Commitment: ToBytes + Clone + Debug + Eq + PartialEq;
+    type ProverState: Clone;
+    type Response: ToBytes + Clone + Debug + Eq + PartialEq;
+
+    /// Compute the first message of the prover, and the state needed to
+    /// answer the challenge.
+    fn commit<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        witness:   &Self::Witness,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error>;
+
+    /// Compute the answer of the prover to `challenge`.
+    fn respond(
+        statement: &Self::Statement,
+        witness:   &Self::Witness,
+        state:     Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Error>;
+
+    /// Check the transcript (`commitment`, `challenge`, `response`) against `statement`.
+    fn verify(
+        statement:  &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge:  &Self::Challenge,
+        response:   &Self::Response,
+    ) -> Result<bool, Error>;
+
+    /// Sample an accepting transcript for the given `challenge` without
+    /// knowing a witness. This is the honest-verifier zero-knowledge
+    /// simulator, as used by the OR composition.
+    fn simulate<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+    ) -> Result<(Self::Commitment, Self::Response), Error>;
+}
@@ -0,0 +1,119 @@
+use crate::{sigma::SigmaProtocol, Error};
+use algebra::{bytes::ToBytes, Group, UniformRand};
+use rand::Rng;
+use std::{
+    io::{Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+/// Proof of knowledge of a representation `(x_1, x_2)` of
+/// `commitment = x_1 * generator_1 + x_2 * generator_2`, e.g. of the opening
+/// of a Pedersen commitment.
+pub struct OkamotoProtocol<G: Group> {
+    _group: PhantomData<G>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+Copy(bound = "G: Group"),
+Clone(bound = "G: Group"),
+Debug(bound = "G: Group"),
+Eq(bound = "G: Group"),
+PartialEq(bound = "G: Group")
+)]
+pub struct OkamotoStatement<G: Group> {
+    pub generator_1: G,
+    pub generator_2: G,
+    pub commitment:  G,
+}
+
+impl<G: Group> ToBytes for OkamotoStatement<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.generator_1.write(&mut writer)?;
+        self.generator_2.write(&mut writer)?;
+        self.commitment.write(&mut writer)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+Copy(bound = "G: Group"),
+Clone(bound = "G: Group"),
+Debug(bound = "G: Group"),
+Eq(bound = "G: Group"),
+PartialEq(bound = "G: Group")
+)]
+pub struct OkamotoResponse<G: Group> {
+    pub z_1: G::ScalarField,
+    pub z_2: G::ScalarField,
+}
+
+impl<G: Group> ToBytes for OkamotoResponse<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.z_1.write(&mut writer)?;
+        self.z_2.write(&mut writer)
+    }
+}
+
+impl<G: Group> SigmaProtocol for OkamotoProtocol<G> {
+    type Challenge = G::ScalarField;
+    type Statement = OkamotoStatement<G>;
+    type Witness = (G::ScalarField, G::ScalarField);
+    type Commitment = G;
+    type ProverState = (G::ScalarField, G::ScalarField);
+    type Response = OkamotoResponse<G>;
+
+    fn commit<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        _witness:  &Self::Witness,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error>
+    {
+        let r_1 = G::ScalarField::rand(rng);
+        let r_2 = G::ScalarField::rand(rng);
+        let a = statement.generator_1.mul(&r_1) + &statement.generator_2.mul(&r_2);
+        Ok((a, (r_1, r_2)))
+    }
+
+    fn respond(
+        _statement: &Self::Statement,
+        witness:    &Self::Witness,
+        state:      Self::ProverState,
+        challenge:  &Self::Challenge,
+    ) -> Result<Self::Response, Error>
+    {
+        // z_i = r_i + c * x_i
+        Ok(OkamotoResponse {
+            z_1: state.0 + &(*challenge * &witness.0),
+            z_2: state.1 + &(*challenge * &witness.1),
+        })
+    }
+
+    fn verify(
+        statement:  &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge:  &Self::Challenge,
+        response:   &Self::Response,
+    ) -> Result<bool, Error>
+    {
+        // z_1 * g_1 + z_2 * g_2 == a + c * h
+        let lhs = statement.generator_1.mul(&response.z_1) + &statement.generator_2.mul(&response.z_2);
+        let rhs = *commitment + &statement.commitment.mul(challenge);
+        Ok(lhs == rhs)
+    }
+
+    fn simulate<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+    ) -> Result<(Self::Commitment, Self::Response), Error>
+    {
+        let response = OkamotoResponse {
+            z_1: G::ScalarField::rand(rng),
+            z_2: G::ScalarField::rand(rng),
+        };
+        let a = statement.generator_1.mul(&response.z_1) + &statement.generator_2.mul(&response.z_2)
+            - &statement.commitment.mul(challenge);
+        Ok((a, response))
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{sigma::SigmaProtocol, Error};
+use algebra::{bytes::ToBytes, Group, UniformRand};
+use rand::Rng;
+use std::{
+    io::{Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+/// Proof of knowledge of the discrete logarithm `x` of `public_key = x * generator`.
+pub struct SchnorrProtocol<G: Group> {
+    _group: PhantomData<G>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+Copy(bound = "G: Group"),
+Clone(bound = "G: Group"),
+Debug(bound = "G: Group"),
+Eq(bound = "G: Group"),
+PartialEq(bound = "G: Group")
+)]
+pub struct SchnorrStatement<G: Group> {
+    pub generator:  G,
+    pub public_key: G,
+}
+
+impl<G: Group> ToBytes for SchnorrStatement<G> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.generator.write(&mut writer)?;
+        self.public_key.write(&mut writer)
+    }
+}
+
+impl<G: Group> SigmaProtocol for SchnorrProtocol<G> {
+    type Challenge = G::ScalarField;
+    type Statement = SchnorrStatement<G>;
+    type Witness = G::ScalarField;
+    type Commitment = G;
+    type ProverState = G::ScalarField;
+    type Response = G::ScalarField;
+
+    fn commit<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        _witness:  &Self::Witness,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error>
+    {
+        let r = G::ScalarField::rand(rng);
+        Ok((statement.generator.mul(&r), r))
+    }
+
+    fn respond(
+        _statement: &Self::Statement,
+        witness:    &Self::Witness,
+        state:      Self::ProverState,
+        challenge:  &Self::Challenge,
+    ) -> Result<Self::Response, Error>
+    {
+        // z = r + c * x
+        Ok(state + &(*challenge * witness))
+    }
+
+    fn verify(
+        statement:  &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge:  &Self::Challenge,
+        response:   &Self::Response,
+    ) -> Result<bool, Error>
+    {
+        // z * g == a + c * h
+        let lhs = statement.generator.mul(response);
+        let rhs = *commitment + &statement.public_key.mul(challenge);
+        Ok(lhs == rhs)
+    }
+
+    fn simulate<R: Rng>(
+        rng:       &mut R,
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+    ) -> Result<(Self::Commitment, Self::Response), Error>
+    {
+        // a = z * g - c * h
+        let z = G::ScalarField::rand(rng);
+        let a = statement.generator.mul(&z) - &statement.public_key.mul(challenge);
+        Ok((a, z))
+    }
+}
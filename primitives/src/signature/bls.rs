@@ -0,0 +1,446 @@
+//! BLS signatures (Boneh-Lynn-Shacham, https://www.iacr.org/archive/asiacrypt2001/22480516.pdf)
+//! over a pairing engine, with signatures in G1 and public keys in G2.
+//!
+//! Signatures of any number of signers can be aggregated into a single
+//! signature by adding them up. To protect the aggregate against rogue key
+//! attacks, each public key comes with a proof of possession of its secret
+//! key (Ristenpart-Yilek, https://eprint.iacr.org/2007/264), which must be
+//! checked before the key is used in an aggregate. The construction follows
+//! the proof of possession scheme of draft-irtf-cfrg-bls-signature.
+use crate::{CryptoError, Error};
+use algebra::{
+    curves::{AffineCurve, PairingCurve, PairingEngine, ProjectiveCurve},
+    fields::Field,
+    hash_to_curve::HashToCurve,
+    to_bytes, FromBytes, ToBytes, UniformRand,
+};
+use digest::Digest;
+use rand::Rng;
+use std::{
+    io::{Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+/// Domain separator for hashing messages to G1.
+const SIGNATURE_DOMAIN_SEP: &[u8] = b"BLS_SIG_G1_XMD_RO_POP_";
+
+/// Domain separator for hashing public keys to G1 in proofs of possession.
+const POP_DOMAIN_SEP: &[u8] = b"BLS_POP_G1_XMD_RO_POP_";
+
+pub struct BlsSignatureScheme<E: PairingEngine, D: Digest> {
+    _engine: PhantomData<E>,
+    _hash:   PhantomData<D>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "E: PairingEngine"),
+    Default(bound = "E: PairingEngine"),
+    Debug(bound = "E: PairingEngine")
+)]
+pub struct BlsSecretKey<E: PairingEngine>(pub E::Fr);
+
+#[derive(Derivative)]
+#[derivative(
+    Copy(bound = "E: PairingEngine"),
+    Clone(bound = "E: PairingEngine"),
+    Default(bound = "E: PairingEngine"),
+    Debug(bound = "E: PairingEngine"),
+    Hash(bound = "E: PairingEngine"),
+    PartialEq(bound = "E: PairingEngine"),
+    Eq(bound = "E: PairingEngine")
+)]
+pub struct BlsPublicKey<E: PairingEngine>(pub E::G2Projective);
+
+#[derive(Derivative)]
+#[derivative(
+    Copy(bound = "E: PairingEngine"),
+    Clone(bound = "E: PairingEngine"),
+    Default(bound = "E: PairingEngine"),
+    Debug(bound = "E: PairingEngine"),
+    PartialEq(bound = "E: PairingEngine"),
+    Eq(bound = "E: PairingEngine")
+)]
+pub struct BlsSignature<E: PairingEngine>(pub E::G1Projective);
+
+/// A proof of possession of the secret key of a public key, i.e. a signature
+/// of the public key itself, under a domain separator distinct from the one
+/// of ordinary signatures.
+pub type BlsProofOfPossession<E> = BlsSignature<E>;
+
+/// A public key with the line coefficients of its Miller loop precomputed, to
+/// verify many signatures against the same key, e.g. the aggregated public key
+/// of a fixed set of signers, without preparing it for each of them.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "E: PairingEngine"),
+    Default(bound = "E: PairingEngine"),
+    Debug(bound = "E: PairingEngine"),
+    PartialEq(bound = "E: PairingEngine"),
+    Eq(bound = "E: PairingEngine")
+)]
+pub struct BlsPreparedPublicKey<E: PairingEngine>(pub <E::G2Affine as PairingCurve>::Prepared);
+
+impl<E: PairingEngine> ToBytes for BlsSecretKey<E> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for BlsSecretKey<E> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(Self(E::Fr::read(reader)?))
+    }
+}
+
+impl<E: PairingEngine> ToBytes for BlsPublicKey<E> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for BlsPublicKey<E> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(Self(E::G2Projective::read(reader)?))
+    }
+}
+
+impl<E: PairingEngine> ToBytes for BlsPreparedPublicKey<E> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+/// The line coefficients are not checked against any public key when reading:
+/// prepared public keys must only be read from trusted storage.
+impl<E: PairingEngine> FromBytes for BlsPreparedPublicKey<E> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(Self(<E::G2Affine as PairingCurve>::Prepared::read(reader)?))
+    }
+}
+
+impl<E: PairingEngine> ToBytes for BlsSignature<E> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for BlsSignature<E> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(Self(E::G1Projective::read(reader)?))
+    }
+}
+
+impl<E: PairingEngine, D: Digest> BlsSignatureScheme<E, D>
+where
+    E::G1Projective: HashToCurve,
+{
+    pub fn keygen<R: Rng>(rng: &mut R) -> (BlsPublicKey<E>, BlsSecretKey<E>) {
+        let secret_key = loop {
+            let sk = E::Fr::rand(rng);
+            if !sk.is_zero() {
+                break sk;
+            }
+        };
+        let secret_key = BlsSecretKey(secret_key);
+        (Self::get_public_key(&secret_key), secret_key)
+    }
+
+    pub fn get_public_key(sk: &BlsSecretKey<E>) -> BlsPublicKey<E> {
+        BlsPublicKey(E::G2Affine::prime_subgroup_generator().mul(sk.0))
+    }
+
+    /// Checks that `pk` is a non-trivial element of G2.
+    pub fn keyverify(pk: &BlsPublicKey<E>) -> bool {
+        !pk.0.is_zero() && pk.0.group_membership_test()
+    }
+
+    pub fn sign(sk: &BlsSecretKey<E>, message: &[u8]) -> Result<BlsSignature<E>, Error> {
+        let h = E::G1Projective::hash_to_curve::<D>(SIGNATURE_DOMAIN_SEP, message)?;
+        Ok(BlsSignature(h.into_affine().mul(sk.0)))
+    }
+
+    pub fn verify(
+        pk: &BlsPublicKey<E>,
+        message: &[u8],
+        signature: &BlsSignature<E>,
+    ) -> Result<bool, Error> {
+        Self::aggregate_verify(&[*pk], &[message], signature)
+    }
+
+    /// Checks that `pk` is a valid public key, and prepares it for `verify_prepared`.
+    pub fn prepare_public_key(pk: &BlsPublicKey<E>) -> Result<BlsPreparedPublicKey<E>, Error> {
+        if !Self::keyverify(pk) {
+            return Err(Box::new(CryptoError::InvalidElement("public key".to_owned())));
+        }
+        Ok(BlsPreparedPublicKey(pk.0.into_affine().prepare()))
+    }
+
+    /// As `verify`, but against a public key prepared by `prepare_public_key`.
+    pub fn verify_prepared(
+        pk: &BlsPreparedPublicKey<E>,
+        message: &[u8],
+        signature: &BlsSignature<E>,
+    ) -> Result<bool, Error> {
+        if !signature.0.group_membership_test() {
+            return Ok(false);
+        }
+        let h = E::G1Projective::hash_to_curve::<D>(SIGNATURE_DOMAIN_SEP, message)?;
+        let h = h.into_affine().prepare();
+        Ok(Self::check_prepared_pairings(&[(&h, &pk.0)], &signature.0))
+    }
+
+    /// Signs the public key corresponding to `sk`.
+    pub fn prove_possession(sk: &BlsSecretKey<E>) -> Result<BlsProofOfPossession<E>, Error> {
+        let pk = Self::get_public_key(sk);
+        let h = E::G1Projective::hash_to_curve::<D>(POP_DOMAIN_SEP, &to_bytes!(pk)?)?;
+        Ok(BlsSignature(h.into_affine().mul(sk.0)))
+    }
+
+    pub fn verify_possession(
+        pk: &BlsPublicKey<E>,
+        proof: &BlsProofOfPossession<E>,
+    ) -> Result<bool, Error> {
+        if !Self::keyverify(pk) || !proof.0.group_membership_test() {
+            return Ok(false);
+        }
+        let h = E::G1Projective::hash_to_curve::<D>(POP_DOMAIN_SEP, &to_bytes!(pk)?)?;
+        Ok(Self::check_pairings(&[(h, pk.0)], &proof.0))
+    }
+
+    /// Adds up signatures, possibly of different messages, to a single one.
+    pub fn aggregate(signatures: &[BlsSignature<E>]) -> Result<BlsSignature<E>, Error> {
+        if signatures.is_empty() {
+            return Err(Box::new(CryptoError::IncorrectInputLength(
+                "signatures".to_owned(),
+                0,
+            )));
+        }
+        let aggregate = signatures
+            .iter()
+            .fold(E::G1Projective::zero(), |acc, sig| acc + &sig.0);
+        Ok(BlsSignature(aggregate))
+    }
+
+    /// Adds up public keys, to verify an aggregate signature of a single message
+    /// against. The proofs of possession of all keys must have been verified.
+    pub fn aggregate_public_keys(pks: &[BlsPublicKey<E>]) -> Result<BlsPublicKey<E>, Error> {
+        if pks.is_empty() {
+            return Err(Box::new(CryptoError::IncorrectInputLength(
+                "public keys".to_owned(),
+                0,
+            )));
+        }
+        let aggregate = pks
+            .iter()
+            .fold(E::G2Projective::zero(), |acc, pk| acc + &pk.0);
+        Ok(BlsPublicKey(aggregate))
+    }
+
+    /// Verifies an aggregate signature of the same `message` by all of `pks`,
+    /// whose proofs of possession must have been verified before. Needs two
+    /// pairings regardless of the number of signers.
+    pub fn fast_aggregate_verify(
+        pks: &[BlsPublicKey<E>],
+        message: &[u8],
+        signature: &BlsSignature<E>,
+    ) -> Result<bool, Error> {
+        if pks.iter().any(|pk| !Self::keyverify(pk)) {
+            return Ok(false);
+        }
+        let aggregate_pk = Self::aggregate_public_keys(pks)?;
+        Self::verify(&aggregate_pk, message, signature)
+    }
+
+    /// Verifies an aggregate signature of `messages[i]` by `pks[i]`, for all i,
+    /// whose proofs of possession must have been verified before.
+    pub fn aggregate_verify(
+        pks: &[BlsPublicKey<E>],
+        messages: &[&[u8]],
+        signature: &BlsSignature<E>,
+    ) -> Result<bool, Error> {
+        if pks.is_empty() || pks.len() != messages.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength(
+                "messages".to_owned(),
+                messages.len(),
+            )));
+        }
+        if pks.iter().any(|pk| !Self::keyverify(pk)) || !signature.0.group_membership_test() {
+            return Ok(false);
+        }
+
+        let mut pairs = Vec::with_capacity(pks.len());
+        for (pk, message) in pks.iter().zip(messages.iter()) {
+            let h = E::G1Projective::hash_to_curve::<D>(SIGNATURE_DOMAIN_SEP, message)?;
+            pairs.push((h, pk.0));
+        }
+        Ok(Self::check_pairings(&pairs, &signature.0))
+    }
+
+    /// Checks e(signature, g2) = prod_i e(h_i, pk_i), as the product of pairings
+    /// e(-signature, g2)·prod_i e(h_i, pk_i) with a single final exponentiation.
+    fn check_pairings(
+        pairs: &[(E::G1Projective, E::G2Projective)],
+        signature: &E::G1Projective,
+    ) -> bool {
+        let prepared = pairs
+            .iter()
+            .map(|(h, pk)| (h.into_affine().prepare(), pk.into_affine().prepare()))
+            .collect::<Vec<_>>();
+        let refs = prepared.iter().map(|(p, q)| (p, q)).collect::<Vec<_>>();
+        Self::check_prepared_pairings(&refs, signature)
+    }
+
+    /// As `check_pairings`, for already prepared pairs.
+    fn check_prepared_pairings(
+        pairs: &[(
+            &<E::G1Affine as PairingCurve>::Prepared,
+            &<E::G2Affine as PairingCurve>::Prepared,
+        )],
+        signature: &E::G1Projective,
+    ) -> bool {
+        let signature = (-*signature).into_affine().prepare();
+        let generator = E::G2Affine::prime_subgroup_generator().prepare();
+
+        let mut refs = pairs.to_vec();
+        refs.push((&signature, &generator));
+        E::product_of_pairings(refs.iter()).is_one()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::curves::bls12_381::Bls12_381;
+    use blake2::Blake2s;
+    use rand::thread_rng;
+
+    type Bls = BlsSignatureScheme<Bls12_381, Blake2s>;
+
+    #[test]
+    fn bls_sign_and_verify() {
+        let rng = &mut thread_rng();
+        let message = b"Hi, I am a BLS signature!";
+
+        let (pk, sk) = Bls::keygen(rng);
+        assert!(Bls::keyverify(&pk));
+        assert_eq!(pk, Bls::get_public_key(&sk));
+
+        let sig = Bls::sign(&sk, message).unwrap();
+        assert!(Bls::verify(&pk, message, &sig).unwrap());
+
+        // BLS signatures are deterministic.
+        assert_eq!(sig, Bls::sign(&sk, message).unwrap());
+
+        //Serialization/deserialization test
+        let sig_serialized = to_bytes!(sig).unwrap();
+        let sig_deserialized = BlsSignature::<Bls12_381>::read(sig_serialized.as_slice()).unwrap();
+        assert_eq!(sig, sig_deserialized);
+
+        //Wrong message, wrong key and wrong signature
+        assert!(!Bls::verify(&pk, b"Bad message", &sig).unwrap());
+        let (other_pk, other_sk) = Bls::keygen(rng);
+        assert!(!Bls::verify(&other_pk, message, &sig).unwrap());
+        let other_sig = Bls::sign(&other_sk, message).unwrap();
+        assert!(!Bls::verify(&pk, message, &other_sig).unwrap());
+    }
+
+    #[test]
+    fn bls_verify_prepared() {
+        let rng = &mut thread_rng();
+        let message = b"Hi, I am a BLS signature!";
+
+        let (pk, sk) = Bls::keygen(rng);
+        let sig = Bls::sign(&sk, message).unwrap();
+        let prepared_pk = Bls::prepare_public_key(&pk).unwrap();
+        assert!(Bls::verify_prepared(&prepared_pk, message, &sig).unwrap());
+        assert!(!Bls::verify_prepared(&prepared_pk, b"Bad message", &sig).unwrap());
+
+        //Serialization/deserialization test
+        let prepared_pk_serialized = to_bytes!(prepared_pk).unwrap();
+        let prepared_pk_deserialized =
+            BlsPreparedPublicKey::<Bls12_381>::read(prepared_pk_serialized.as_slice()).unwrap();
+        assert_eq!(prepared_pk, prepared_pk_deserialized);
+        assert!(Bls::verify_prepared(&prepared_pk_deserialized, message, &sig).unwrap());
+
+        // Aggregated public keys are prepared the same way.
+        let (other_pk, other_sk) = Bls::keygen(rng);
+        let aggregate_pk = Bls::aggregate_public_keys(&[pk, other_pk]).unwrap();
+        let aggregate_sig = Bls::aggregate(&[sig, Bls::sign(&other_sk, message).unwrap()]).unwrap();
+        let prepared_aggregate_pk = Bls::prepare_public_key(&aggregate_pk).unwrap();
+        assert!(Bls::verify_prepared(&prepared_aggregate_pk, message, &aggregate_sig).unwrap());
+        assert!(!Bls::verify_prepared(&prepared_pk, message, &aggregate_sig).unwrap());
+
+        assert!(Bls::prepare_public_key(&BlsPublicKey(Default::default())).is_err());
+    }
+
+    #[test]
+    fn bls_proof_of_possession() {
+        let rng = &mut thread_rng();
+        let (pk, sk) = Bls::keygen(rng);
+        let (other_pk, other_sk) = Bls::keygen(rng);
+
+        let pop = Bls::prove_possession(&sk).unwrap();
+        assert!(Bls::verify_possession(&pk, &pop).unwrap());
+        assert!(!Bls::verify_possession(&other_pk, &pop).unwrap());
+
+        // A signature of the public key is not a proof of possession.
+        let sig = Bls::sign(&sk, &to_bytes!(pk).unwrap()).unwrap();
+        assert!(!Bls::verify_possession(&pk, &sig).unwrap());
+
+        // Neither is a rogue key pk' = other_pk - pk with a made-up proof.
+        let rogue_pk = BlsPublicKey(other_pk.0 - &pk.0);
+        let rogue_pop = Bls::prove_possession(&other_sk).unwrap();
+        assert!(!Bls::verify_possession(&rogue_pk, &rogue_pop).unwrap());
+    }
+
+    #[test]
+    fn bls_aggregate() {
+        let rng = &mut thread_rng();
+        let num_signers = 5;
+        let keys = (0..num_signers).map(|_| Bls::keygen(rng)).collect::<Vec<_>>();
+        let pks = keys.iter().map(|(pk, _)| *pk).collect::<Vec<_>>();
+        for (pk, sk) in keys.iter() {
+            let pop = Bls::prove_possession(sk).unwrap();
+            assert!(Bls::verify_possession(pk, &pop).unwrap());
+        }
+
+        // All signers sign the same message
+        let message = b"Hi, we are an aggregate BLS signature!";
+        let sigs = keys
+            .iter()
+            .map(|(_, sk)| Bls::sign(sk, message).unwrap())
+            .collect::<Vec<_>>();
+        let aggregate_sig = Bls::aggregate(&sigs).unwrap();
+        assert!(Bls::fast_aggregate_verify(&pks, message, &aggregate_sig).unwrap());
+        assert!(!Bls::fast_aggregate_verify(&pks[1..], message, &aggregate_sig).unwrap());
+        assert!(!Bls::fast_aggregate_verify(&pks, b"Bad message", &aggregate_sig).unwrap());
+
+        // Each signer signs its own message
+        let messages = (0..num_signers)
+            .map(|i| format!("Hi, I am signer {}", i).into_bytes())
+            .collect::<Vec<_>>();
+        let message_refs = messages.iter().map(|m| m.as_slice()).collect::<Vec<_>>();
+        let sigs = keys
+            .iter()
+            .zip(message_refs.iter())
+            .map(|((_, sk), m)| Bls::sign(sk, m).unwrap())
+            .collect::<Vec<_>>();
+        let aggregate_sig = Bls::aggregate(&sigs).unwrap();
+        assert!(Bls::aggregate_verify(&pks, &message_refs, &aggregate_sig).unwrap());
+
+        let mut swapped_refs = message_refs.clone();
+        swapped_refs.swap(0, 1);
+        assert!(!Bls::aggregate_verify(&pks, &swapped_refs, &aggregate_sig).unwrap());
+        assert!(Bls::aggregate_verify(&pks, &message_refs[1..], &aggregate_sig).is_err());
+        assert!(Bls::aggregate(&[]).is_err());
+    }
+}
@@ -0,0 +1,348 @@
+//! ECDSA signatures (SEC 1, https://www.secg.org/sec1-v2.pdf, Section 4.1) over
+//! short Weierstrass curves, e.g. over secp256k1 for verifying signatures of
+//! Bitcoin and Ethereum transactions.
+//!
+//! Signatures are produced in the low-s form required by Bitcoin (BIP 146)
+//! and Ethereum (EIP-2), while verification accepts both forms, as specified
+//! by SEC 1.
+use crate::{CryptoError, Error};
+use algebra::{
+    curves::{
+        models::SWModelParameters,
+        short_weierstrass_projective::{GroupAffine, GroupProjective},
+        AffineCurve, ProjectiveCurve,
+    },
+    fields::{Field, FpParameters, PrimeField},
+    msm::FixedBaseMul,
+    BigInteger, FromBytes, ToBits, ToBytes, UniformRand,
+};
+use digest::Digest;
+use rand::Rng;
+use std::{
+    io::{Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+pub struct EcdsaSignatureScheme<P: SWModelParameters, D: Digest> {
+    _parameters: PhantomData<P>,
+    _hash:       PhantomData<D>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: SWModelParameters"),
+    Default(bound = "P: SWModelParameters"),
+    Debug(bound = "P: SWModelParameters")
+)]
+pub struct EcdsaSecretKey<P: SWModelParameters>(pub P::ScalarField);
+
+#[derive(Derivative)]
+#[derivative(
+    Copy(bound = "P: SWModelParameters"),
+    Clone(bound = "P: SWModelParameters"),
+    Default(bound = "P: SWModelParameters"),
+    Debug(bound = "P: SWModelParameters"),
+    Hash(bound = "P: SWModelParameters"),
+    PartialEq(bound = "P: SWModelParameters"),
+    Eq(bound = "P: SWModelParameters")
+)]
+pub struct EcdsaPublicKey<P: SWModelParameters>(pub GroupProjective<P>);
+
+#[derive(Derivative)]
+#[derivative(
+    Copy(bound = "P: SWModelParameters"),
+    Clone(bound = "P: SWModelParameters"),
+    Default(bound = "P: SWModelParameters"),
+    Debug(bound = "P: SWModelParameters"),
+    PartialEq(bound = "P: SWModelParameters"),
+    Eq(bound = "P: SWModelParameters")
+)]
+pub struct EcdsaSignature<P: SWModelParameters> {
+    pub r: P::ScalarField,
+    pub s: P::ScalarField,
+}
+
+impl<P: SWModelParameters> ToBytes for EcdsaSecretKey<P> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+impl<P: SWModelParameters> FromBytes for EcdsaSecretKey<P> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(Self(P::ScalarField::read(reader)?))
+    }
+}
+
+impl<P: SWModelParameters> ToBytes for EcdsaPublicKey<P> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+impl<P: SWModelParameters> FromBytes for EcdsaPublicKey<P> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        Ok(Self(GroupProjective::<P>::read(reader)?))
+    }
+}
+
+impl<P: SWModelParameters> ToBytes for EcdsaSignature<P> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.r.write(&mut writer)?;
+        self.s.write(&mut writer)
+    }
+}
+
+impl<P: SWModelParameters> FromBytes for EcdsaSignature<P> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let r = P::ScalarField::read(&mut reader)?;
+        let s = P::ScalarField::read(&mut reader)?;
+        Ok(Self { r, s })
+    }
+}
+
+impl<P: SWModelParameters, D: Digest> EcdsaSignatureScheme<P, D>
+where
+    P::BaseField: PrimeField,
+{
+    pub fn keygen<R: Rng>(rng: &mut R) -> (EcdsaPublicKey<P>, EcdsaSecretKey<P>) {
+        let secret_key = loop {
+            let sk = P::ScalarField::rand(rng);
+            if !sk.is_zero() {
+                break sk;
+            }
+        };
+        let secret_key = EcdsaSecretKey(secret_key);
+        (Self::get_public_key(&secret_key), secret_key)
+    }
+
+    pub fn get_public_key(sk: &EcdsaSecretKey<P>) -> EcdsaPublicKey<P> {
+        EcdsaPublicKey(GroupAffine::<P>::prime_subgroup_generator().mul(sk.0))
+    }
+
+    /// Returns the window tables of the generator, for signing many messages
+    /// with `sign_with_table`.
+    pub fn generator_table(window: usize) -> FixedBaseMul<GroupAffine<P>> {
+        FixedBaseMul::new(&GroupAffine::<P>::prime_subgroup_generator(), window)
+    }
+
+    /// Checks that `pk` is a non-trivial element of the prime order subgroup.
+    pub fn keyverify(pk: &EcdsaPublicKey<P>) -> bool {
+        !pk.0.is_zero() && pk.0.group_membership_test()
+    }
+
+    pub fn sign<R: Rng>(
+        rng: &mut R,
+        sk: &EcdsaSecretKey<P>,
+        message: &[u8],
+    ) -> Result<EcdsaSignature<P>, Error> {
+        let generator = GroupAffine::<P>::prime_subgroup_generator();
+        Self::sign_with_generator_mul(rng, sk, message, |k| generator.mul(*k))
+    }
+
+    /// Same as `sign`, computing the nonce commitments with the precomputed
+    /// tables of the generator returned by `generator_table`.
+    pub fn sign_with_table<R: Rng>(
+        rng: &mut R,
+        table: &FixedBaseMul<GroupAffine<P>>,
+        sk: &EcdsaSecretKey<P>,
+        message: &[u8],
+    ) -> Result<EcdsaSignature<P>, Error> {
+        if table.base() != GroupAffine::<P>::prime_subgroup_generator() {
+            return Err(Box::new(CryptoError::InvalidElement("generator table".to_owned())));
+        }
+        Self::sign_with_generator_mul(rng, sk, message, |k| table.mul(k))
+    }
+
+    fn sign_with_generator_mul<R: Rng, M: Fn(&P::ScalarField) -> GroupProjective<P>>(
+        rng: &mut R,
+        sk: &EcdsaSecretKey<P>,
+        message: &[u8],
+        mul_generator: M,
+    ) -> Result<EcdsaSignature<P>, Error> {
+        let z = Self::hash_to_scalar(message);
+
+        let (r, s) = loop {
+            let k = P::ScalarField::rand(rng);
+            if k.is_zero() {
+                continue;
+            }
+
+            // r = x(k * G) mod n
+            let r_point = mul_generator(&k).into_affine();
+            let r = Self::base_to_scalar(&r_point.x);
+            if r.is_zero() {
+                continue;
+            }
+
+            // s = k^-1 * (z + r * sk)
+            let s = (z + &(r * &sk.0)) * &k.inverse().unwrap();
+            if s.is_zero() {
+                continue;
+            }
+            break (r, s);
+        };
+
+        // Normalize to the low-s form, (r, -s) being a valid signature as well.
+        let s = if Self::is_high(&s) { -s } else { s };
+        Ok(EcdsaSignature { r, s })
+    }
+
+    pub fn verify(
+        pk: &EcdsaPublicKey<P>,
+        message: &[u8],
+        signature: &EcdsaSignature<P>,
+    ) -> Result<bool, Error> {
+        if !Self::keyverify(pk) || signature.r.is_zero() || signature.s.is_zero() {
+            return Ok(false);
+        }
+        let z = Self::hash_to_scalar(message);
+
+        // R' = (z / s) * G + (r / s) * pk
+        let s_inv = signature.s.inverse().unwrap();
+        let u1 = z * &s_inv;
+        let u2 = signature.r * &s_inv;
+        let r_prime = GroupAffine::<P>::prime_subgroup_generator().mul(u1)
+            + &pk.0.into_affine().mul(u2);
+        if r_prime.is_zero() {
+            return Ok(false);
+        }
+
+        Ok(Self::base_to_scalar(&r_prime.into_affine().x) == signature.r)
+    }
+
+    /// Returns the leftmost `n` bits of the hash of `message` as a scalar, where `n`
+    /// is the bit length of the group order.
+    pub fn hash_to_scalar(message: &[u8]) -> P::ScalarField {
+        let hash = D::digest(message);
+        let num_bits = <P::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        let hash_bits = hash
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1));
+        Self::bits_to_scalar(hash_bits.take(num_bits))
+    }
+
+    /// Returns `x` modulo the group order.
+    pub fn base_to_scalar(x: &P::BaseField) -> P::ScalarField {
+        Self::bits_to_scalar(x.write_bits().into_iter())
+    }
+
+    /// Returns the integer with big-endian bits `bits` modulo the group order.
+    fn bits_to_scalar<I: Iterator<Item = bool>>(bits: I) -> P::ScalarField {
+        bits.fold(P::ScalarField::zero(), |mut acc, bit| {
+            acc.double_in_place();
+            if bit {
+                acc += &P::ScalarField::one();
+            }
+            acc
+        })
+    }
+
+    /// Returns true if `s` is larger than (n - 1) / 2.
+    fn is_high(s: &P::ScalarField) -> bool {
+        let mut half_order = <P::ScalarField as PrimeField>::Params::MODULUS;
+        half_order.div2();
+        s.into_repr() > half_order
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{
+        curves::secp256k1::Secp256k1Parameters,
+        fields::secp256k1::{Fq, Fr},
+        to_bytes,
+    };
+    use rand::thread_rng;
+    use sha2::Sha256;
+    use std::str::FromStr;
+
+    type Ecdsa = EcdsaSignatureScheme<Secp256k1Parameters, Sha256>;
+
+    #[test]
+    fn ecdsa_sign_and_verify() {
+        let rng = &mut thread_rng();
+        let message = b"Hi, I am an ECDSA signature!";
+
+        let (pk, sk) = Ecdsa::keygen(rng);
+        assert!(Ecdsa::keyverify(&pk));
+        assert_eq!(pk, Ecdsa::get_public_key(&sk));
+
+        let sig = Ecdsa::sign(rng, &sk, message).unwrap();
+        assert!(!Ecdsa::is_high(&sig.s));
+        assert!(Ecdsa::verify(&pk, message, &sig).unwrap());
+
+        // The high-s form is valid as well.
+        let high_sig = EcdsaSignature { r: sig.r, s: -sig.s };
+        assert!(Ecdsa::verify(&pk, message, &high_sig).unwrap());
+
+        // Signing with the tables of the generator
+        let table = Ecdsa::generator_table(6);
+        let table_sig = Ecdsa::sign_with_table(rng, &table, &sk, message).unwrap();
+        assert!(Ecdsa::verify(&pk, message, &table_sig).unwrap());
+        let bad_table = FixedBaseMul::new(&pk.0.into_affine(), 6);
+        assert!(Ecdsa::sign_with_table(rng, &bad_table, &sk, message).is_err());
+
+        //Serialization/deserialization test
+        let sig_serialized = to_bytes!(sig).unwrap();
+        let sig_deserialized =
+            EcdsaSignature::<Secp256k1Parameters>::read(sig_serialized.as_slice()).unwrap();
+        assert_eq!(sig, sig_deserialized);
+
+        //Wrong message, wrong key and wrong signature
+        assert!(!Ecdsa::verify(&pk, b"Bad message", &sig).unwrap());
+        let (other_pk, other_sk) = Ecdsa::keygen(rng);
+        assert!(!Ecdsa::verify(&other_pk, message, &sig).unwrap());
+        let other_sig = Ecdsa::sign(rng, &other_sk, message).unwrap();
+        assert!(!Ecdsa::verify(&pk, message, &other_sig).unwrap());
+        let zero_sig = EcdsaSignature { r: Fr::zero(), s: sig.s };
+        assert!(!Ecdsa::verify(&pk, message, &zero_sig).unwrap());
+    }
+
+    #[test]
+    fn ecdsa_secp256k1_test_vector() {
+        // Produced by an independent implementation, with SHA-256.
+        let sk = EcdsaSecretKey::<Secp256k1Parameters>(
+            Fr::from_str(
+                "13840170145645816737842251482747434280357113762558403558088249138233286766301",
+            )
+            .unwrap(),
+        );
+        let pk = EcdsaPublicKey(
+            GroupAffine::<Secp256k1Parameters>::new(
+                Fq::from_str(
+                    "108626704259373488493324494832963472198167093861790438979212875284973843395610",
+                )
+                .unwrap(),
+                Fq::from_str(
+                    "3532285151429480400098290360892322426461524297929807392099748929454186978267",
+                )
+                .unwrap(),
+                false,
+            )
+            .into_projective(),
+        );
+        assert_eq!(pk, Ecdsa::get_public_key(&sk));
+
+        let message = b"ginger-lib ecdsa test vector";
+        let sig = EcdsaSignature {
+            r: Fr::from_str(
+                "58308392157296755950761534242836219408665775082590373654467548414609173430248",
+            )
+            .unwrap(),
+            s: Fr::from_str(
+                "75619370676456454716038841245005055950071528333372040960496763359005633188688",
+            )
+            .unwrap(),
+        };
+        assert!(Ecdsa::verify(&pk, message, &sig).unwrap());
+        assert!(!Ecdsa::verify(&pk, b"Bad message", &sig).unwrap());
+    }
+}
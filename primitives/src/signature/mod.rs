@@ -1,4 +1,4 @@
-use crate::Error;
+use crate::{CryptoError, Error, KeyProvider};
 use algebra::{
     bytes::{
         ToBytes, FromBytes
@@ -9,6 +9,8 @@ use rand::Rng;
 use std::hash::Hash;
 use std::fmt::Debug;
 
+pub mod bls;
+pub mod ecdsa;
 pub mod schnorr;
 
 pub trait SignatureScheme {
@@ -82,4 +84,37 @@ pub trait FieldBasedSignatureScheme {
     fn keyverify(
         pk: &Self::PublicKey,
     ) -> bool;
+
+    /// Signs `message` with the secret key `key_id` of `provider`, whose
+    /// public key is `pk`.
+    fn sign_with_key_provider<R: Rng, K: KeyProvider<Self::SecretKey>>(
+        rng: &mut R,
+        pk: &Self::PublicKey,
+        provider: &K,
+        key_id: &str,
+        message: &[Self::Data],
+    ) -> Result<Self::Signature, Error>
+    {
+        provider.with_secret_key(key_id, |sk| Self::sign(rng, pk, sk, message))?
+    }
+
+    /// Verifies a batch of signatures, the i-th of which is a signature of
+    /// `messages[i]` under `pks[i]`. Returns true if and only if all of them
+    /// are valid.
+    fn batch_verify(
+        pks: &[Self::PublicKey],
+        messages: &[&[Self::Data]],
+        signatures: &[Self::Signature],
+    ) -> Result<bool, Error>
+    {
+        if messages.len() != pks.len() || signatures.len() != pks.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("batch".to_owned(), pks.len())))
+        }
+        for ((pk, message), signature) in pks.iter().zip(messages).zip(signatures) {
+            if !Self::verify(pk, message, signature)? {
+                return Ok(false)
+            }
+        }
+        Ok(true)
+    }
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 use crate::{crh::FieldBasedHash, signature::FieldBasedSignatureScheme, CryptoError, Error, compute_truncation_size};
-use algebra::{Field, PrimeField, Group, UniformRand, ProjectiveCurve,
+use algebra::{Field, PrimeField, Group, UniformRand, ProjectiveCurve, FixedBaseMSM,
               convert, leading_zeros, ToBits, ToConstraintField, ToBytes, FromBytes};
 use std::marker::PhantomData;
 use rand::Rng;
+use rayon::prelude::*;
 use std::io::{Write, Read, Result as IoResult};
 
 #[allow(dead_code)]
@@ -134,9 +135,86 @@ FieldBasedSchnorrSignatureScheme<F, G, H>
         -> Result<bool, Error>
     {
 
-        let pk_coords = pk.to_field_elements()?;
+        //Compute R' = s*G - e * pk
+        let r_prime = {
+            let (e_conv, s_conv) = Self::signature_to_scalars(signature)?;
+            let s_times_g = G::prime_subgroup_generator().mul(&s_conv);
+            let neg_e_times_pk = pk.neg().mul(&e_conv);
+            s_times_g + &neg_e_times_pk
+        };
+
+        Self::check_challenge(pk, message, signature, &r_prime)
+    }
+
+    fn keyverify(pk: &Self::PublicKey) -> bool
+    {
+        pk.group_membership_test()
+    }
+
+    /// This is not a random linear combination batch verification: the signatures
+    /// carry the challenge e instead of the commitment R, so every R' has to be
+    /// recomputed and hashed, and each signature is still checked on its own. Only
+    /// the scalar multiplications are shared: the s * G use a single window table of
+    /// the generator, the e * pk are computed in parallel, and all the R' are
+    /// normalized with a single inversion. Unlike `verify`, the public keys are
+    /// checked with `keyverify`.
+    fn batch_verify(
+        pks: &[Self::PublicKey],
+        messages: &[&[Self::Data]],
+        signatures: &[Self::Signature],
+    ) -> Result<bool, Error>
+    {
+        if messages.len() != pks.len() || signatures.len() != pks.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("batch".to_owned(), pks.len())))
+        }
+        if !pks.par_iter().all(|pk| Self::keyverify(pk)) {
+            return Ok(false)
+        }
+
+        let (e_scalars, s_scalars): (Vec<_>, Vec<_>) = signatures
+            .iter()
+            .map(|signature| Self::signature_to_scalars(signature))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+
+        //Compute s_i * G, sharing the window table of G
+        let scalar_size = G::ScalarField::size_in_bits();
+        let window = FixedBaseMSM::get_mul_window_size(signatures.len());
+        let g_table = FixedBaseMSM::get_window_table(
+            scalar_size,
+            window,
+            G::prime_subgroup_generator()
+        );
+        let s_times_g = FixedBaseMSM::multi_scalar_mul(scalar_size, window, &g_table, &s_scalars);
+
+        //Compute R'_i = s_i * G - e_i * pk_i
+        let mut r_primes = pks
+            .par_iter()
+            .zip(e_scalars.par_iter())
+            .zip(s_times_g.par_iter())
+            .map(|((pk, e), s_times_g)| *s_times_g + &pk.neg().mul(e))
+            .collect::<Vec<_>>();
+        G::batch_normalization(r_primes.as_mut_slice());
+
+        let batch = pks.iter().zip(messages).zip(signatures).zip(r_primes.iter());
+        for (((pk, message), signature), r_prime) in batch {
+            if !Self::check_challenge(pk, message, signature, r_prime)? {
+                return Ok(false)
+            }
+        }
+        Ok(true)
+    }
+}
 
-        //Checks
+impl<F: PrimeField, G: ProjectiveCurve + ToConstraintField<F>, H: FieldBasedHash<Data = F>>
+FieldBasedSchnorrSignatureScheme<F, G, H>
+{
+    /// Checks the lengths of e and s, and converts them to the scalar field.
+    fn signature_to_scalars(
+        signature: &FieldBasedSchnorrSignature<F>,
+    ) -> Result<(G::ScalarField, G::ScalarField), Error>
+    {
         let e_bits = signature.e.write_bits();
         let e_leading_zeros = leading_zeros(e_bits.clone()) as usize;
         if (F::size_in_bits() - e_leading_zeros) >= G::ScalarField::size_in_bits(){
@@ -149,15 +227,20 @@ FieldBasedSchnorrSignatureScheme<F, G, H>
             return Err(Box::new(CryptoError::IncorrectInputLength("signature.s".to_owned(), s_bits.len() - s_leading_zeros)))
         }
 
-        //Compute R' = s*G - e * pk
-        let r_prime = {
-            let s_conv = convert::<G::ScalarField>(s_bits)?;
-            let e_conv =  convert::<G::ScalarField>(e_bits)?;
-            let s_times_g = G::prime_subgroup_generator().mul(&s_conv);
-            let neg_e_times_pk = pk.neg().mul(&e_conv);
-            s_times_g + &neg_e_times_pk
-        };
+        let e_conv = convert::<G::ScalarField>(e_bits)?;
+        let s_conv = convert::<G::ScalarField>(s_bits)?;
+        Ok((e_conv, s_conv))
+    }
 
+    /// Checks that e = H(m || R' || pk.x).
+    fn check_challenge(
+        pk: &G,
+        message: &[F],
+        signature: &FieldBasedSchnorrSignature<F>,
+        r_prime: &G,
+    ) -> Result<bool, Error>
+    {
+        let pk_coords = pk.to_field_elements()?;
         let r_prime_coords = r_prime.to_field_elements()?;
 
         // Compute e' = H(m || R' || pk.x)
@@ -169,11 +252,6 @@ FieldBasedSchnorrSignatureScheme<F, G, H>
 
         Ok(signature.e == e_prime)
     }
-
-    fn keyverify(pk: &Self::PublicKey) -> bool
-    {
-        pk.group_membership_test()
-    }
 }
 
 #[cfg(test)]
@@ -186,9 +264,10 @@ mod test {
         mnt4753::Fr as MNT4Fr,
         mnt6753::Fr as MNT6Fr,
     };
-    use algebra::{ToBytes, to_bytes, FromBytes};
+    use algebra::{Field, ToBytes, to_bytes, FromBytes, UniformRand};
     use crate::crh::{MNT4PoseidonHash, MNT6PoseidonHash};
     use crate::signature::FieldBasedSignatureScheme;
+    use crate::key_provider::InMemoryKeyProvider;
     use crate::signature::schnorr::field_based_schnorr::FieldBasedSchnorrSignatureScheme;
     use rand::{Rng, thread_rng};
 
@@ -202,6 +281,13 @@ mod test {
         let sig = S::sign(rng, &pk, &sk, &message).unwrap();
         assert!(S::verify(&pk, &message, &sig).unwrap());
 
+        //Signing with a key held by a key provider
+        let mut provider = InMemoryKeyProvider::new();
+        provider.insert("key", sk);
+        let provider_sig = S::sign_with_key_provider(rng, &pk, &provider, "key", &message).unwrap();
+        assert!(S::verify(&pk, &message, &provider_sig).unwrap());
+        assert!(S::sign_with_key_provider(rng, &pk, &provider, "other key", &message).is_err());
+
         //Serialization/deserialization test
         let sig_serialized = to_bytes!(sig).unwrap();
         let sig_deserialized = <S as FieldBasedSignatureScheme>::Signature::read(sig_serialized.as_slice()).unwrap();
@@ -227,6 +313,40 @@ mod test {
         assert!(!S::verify(&new_pk, message, &sig).unwrap());
     }
 
+    fn batch_verification<S: FieldBasedSignatureScheme, R: Rng>(rng: &mut R, batch_size: usize, invalid_pk: S::PublicKey) {
+        let mut pks = Vec::with_capacity(batch_size);
+        let mut messages = Vec::with_capacity(batch_size);
+        let mut sigs = Vec::with_capacity(batch_size);
+        for _ in 0..batch_size {
+            let (pk, sk) = S::keygen(rng);
+            let message = vec![S::Data::rand(rng), S::Data::rand(rng)];
+            sigs.push(S::sign(rng, &pk, &sk, &message).unwrap());
+            pks.push(pk);
+            messages.push(message);
+        }
+        let message_refs = messages.iter().map(|m| m.as_slice()).collect::<Vec<_>>();
+        assert!(S::batch_verify(&pks, &message_refs, &sigs).unwrap());
+
+        //A single invalid signature makes the batch fail
+        let bad_message = vec![S::Data::rand(rng)];
+        let mut bad_message_refs = message_refs.clone();
+        bad_message_refs[batch_size / 2] = &bad_message;
+        assert!(!S::batch_verify(&pks, &bad_message_refs, &sigs).unwrap());
+
+        let mut bad_pks = pks.clone();
+        bad_pks.swap(0, batch_size - 1);
+        assert!(!S::batch_verify(&bad_pks, &message_refs, &sigs).unwrap());
+
+        //So does an invalid public key
+        assert!(!S::keyverify(&invalid_pk));
+        let mut bad_pks = pks.clone();
+        bad_pks[0] = invalid_pk;
+        assert!(!S::batch_verify(&bad_pks, &message_refs, &sigs).unwrap());
+
+        //Inconsistent lengths are rejected
+        assert!(S::batch_verify(&pks[1..], &message_refs, &sigs).is_err());
+    }
+
     #[test]
     fn mnt4_schnorr_test() {
         let rng = &mut thread_rng();
@@ -237,6 +357,8 @@ mod test {
             sign_and_verify::<SchnorrMNT4, _>(rng, &[f, g]);
             failed_verification::<SchnorrMNT4, _>(rng, &[f], &[g]);
         }
+        let point_off_curve = MNT6G1Projective::new(MNT4Fr::one(), MNT4Fr::one(), MNT4Fr::one());
+        batch_verification::<SchnorrMNT4, _>(rng, 50, point_off_curve);
     }
 
     #[test]
@@ -249,5 +371,7 @@ mod test {
             sign_and_verify::<SchnorrMNT6, _>(rng,&[f, g]);
             failed_verification::<SchnorrMNT6, _>(rng, &[f], &[g]);
         }
+        let point_off_curve = MNT4G1Projective::new(MNT6Fr::one(), MNT6Fr::one(), MNT6Fr::one());
+        batch_verification::<SchnorrMNT6, _>(rng, 50, point_off_curve);
     }
 }
\ No newline at end of file
@@ -1,9 +1,9 @@
-use crate::{Error, SignatureScheme, bytes_to_bits};
+use crate::{fiat_shamir::ChaChaFSRng, transcript::Transcript, Error, SignatureScheme, bytes_to_bits};
 use algebra::{
     bytes::ToBytes,
     fields::{Field, PrimeField},
     groups::Group,
-    to_bytes, ToConstraintField, UniformRand,
+    ToConstraintField, UniformRand,
 };
 use digest::Digest;
 use rand::Rng;
@@ -49,6 +49,23 @@ pub struct SchnorrSig<G: Group> {
     pub verifier_challenge: G::ScalarField,
 }
 
+impl<G: Group, D: Digest> SchnorrSignature<G, D> {
+    /// Derives the verifier challenge from a transcript of the salt, the
+    /// commitment of the prover and the message.
+    fn challenge(
+        parameters:        &SchnorrSigParameters<G, D>,
+        prover_commitment: &G,
+        message:           &[u8],
+    ) -> Result<G::ScalarField, Error>
+    {
+        let mut transcript = Transcript::<ChaChaFSRng<G::ScalarField, D>>::new(b"Schnorr signature", None)?;
+        transcript.absorb("salt", &parameters.salt)?;
+        transcript.absorb("commitment", prover_commitment)?;
+        transcript.absorb("message", &message)?;
+        transcript.challenge("challenge")
+    }
+}
+
 impl<G: Group + Hash, D: Digest + Send + Sync> SignatureScheme for SchnorrSignature<G, D>
     where
         G::ScalarField: PrimeField,
@@ -93,27 +110,14 @@ impl<G: Group + Hash, D: Digest + Send + Sync> SignatureScheme for SchnorrSignat
         rng: &mut R,
     ) -> Result<Self::Signature, Error> {
         let sign_time = start_timer!(|| "SchnorrSig::Sign");
-        // (k, e);
-        let (random_scalar, verifier_challenge) = loop {
-            // Sample a random scalar `k` from the prime scalar field.
-            let random_scalar: G::ScalarField = G::ScalarField::rand(rng);
-            // Commit to the random scalar via r := k · g.
-            // This is the prover's first msg in the Sigma protocol.
-            let prover_commitment: G = parameters.generator.mul(&random_scalar);
-
-            // Hash everything to get verifier challenge.
-            let mut hash_input = Vec::new();
-            hash_input.extend_from_slice(&parameters.salt);
-            hash_input.extend_from_slice(&to_bytes![prover_commitment]?);
-            hash_input.extend_from_slice(message);
-
-            // Compute the supposed verifier response: e := H(salt || r || msg);
-            if let Some(verifier_challenge) =
-            G::ScalarField::from_random_bytes(&D::digest(&hash_input))
-            {
-                break (random_scalar, verifier_challenge);
-            };
-        };
+        // Sample a random scalar `k` from the prime scalar field.
+        let random_scalar: G::ScalarField = G::ScalarField::rand(rng);
+        // Commit to the random scalar via r := k · g.
+        // This is the prover's first msg in the Sigma protocol.
+        let prover_commitment: G = parameters.generator.mul(&random_scalar);
+
+        // Compute the supposed verifier response: e := H(salt, r, msg);
+        let verifier_challenge = Self::challenge(parameters, &prover_commitment, message)?;
 
         // k - xe;
         let prover_response = random_scalar - &(verifier_challenge * &sk.0);
@@ -142,18 +146,7 @@ impl<G: Group + Hash, D: Digest + Send + Sync> SignatureScheme for SchnorrSignat
         let public_key_times_verifier_challenge = pk.mul(verifier_challenge);
         claimed_prover_commitment += &public_key_times_verifier_challenge;
 
-        let mut hash_input = Vec::new();
-        hash_input.extend_from_slice(&parameters.salt);
-        hash_input.extend_from_slice(&to_bytes![claimed_prover_commitment]?);
-        hash_input.extend_from_slice(&message);
-
-        let obtained_verifier_challenge = if let Some(obtained_verifier_challenge) =
-        G::ScalarField::from_random_bytes(&D::digest(&hash_input))
-        {
-            obtained_verifier_challenge
-        } else {
-            return Ok(false);
-        };
+        let obtained_verifier_challenge = Self::challenge(parameters, &claimed_prover_commitment, message)?;
         end_timer!(verify_time);
         Ok(verifier_challenge == &obtained_verifier_challenge)
     }
@@ -227,6 +220,7 @@ mod test {
     };
     use blake2::Blake2s;
     use rand::thread_rng;
+    use sha2::Sha224;
 
     fn sign_and_verify<S: SignatureScheme>(message: &[u8]) {
         let rng = &mut thread_rng();
@@ -270,4 +264,15 @@ mod test {
             &random_scalar.as_slice(),
         );
     }
+
+    #[test]
+    fn schnorr_signature_short_digest_test() {
+        // The 28 bytes of a SHA-224 digest are shorter than the seed of the challenge RNG
+        let message = "Hi, I am a Schnorr signature!";
+        sign_and_verify::<SchnorrSignature<Edwards, Sha224>>(message.as_bytes());
+        failed_verification::<SchnorrSignature<Edwards, Sha224>>(
+            message.as_bytes(),
+            "Bad message".as_bytes(),
+        );
+    }
 }
\ No newline at end of file
@@ -0,0 +1,86 @@
+//! Snapshots of the state of long running computations, such as hashing a
+//! stream of inputs or appending leaves to a Merkle tree, for checkpointing
+//! them and resuming after a restart. A snapshot consists of:
+//! - the magic bytes of the kind of state (4 bytes);
+//! - the version `SNAPSHOT_VERSION` of the format (u8);
+//! - the identifier of the configuration of the computation, e.g. of the hash
+//!   parameters and of the tree height (u64);
+//!
+//! followed by the state. All integers are little endian. Restoring the
+//! snapshot of another kind of state, or of another configuration, fails
+//! instead of silently resuming a different computation.
+use algebra::bytes::{FromBytes, ToBytes};
+use std::io::{self, Read, Write};
+
+/// The version of the format of the snapshots.
+pub const SNAPSHOT_VERSION: u8 = 1;
+
+/// Returns the 64 bit FNV-1a hash of `bytes`, used to identify configurations.
+pub fn configuration_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// A state which can be snapshotted and restored.
+pub trait Snapshot: Sized {
+    /// The magic bytes identifying the kind of state.
+    const MAGIC: [u8; 4];
+
+    /// The name of the kind of state, for error messages.
+    const NAME: &'static str;
+
+    /// Returns the identifier of the configuration the state belongs to.
+    fn configuration_id(&self) -> u64;
+
+    /// Writes the state, without header.
+    fn write_state<W: Write>(&self, writer: W) -> io::Result<()>;
+
+    /// Reads the state written by `write_state`.
+    fn read_state<R: Read>(reader: R) -> io::Result<Self>;
+
+    /// Writes the header, followed by the state.
+    fn write_snapshot<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        Self::MAGIC.write(&mut writer)?;
+        SNAPSHOT_VERSION.write(&mut writer)?;
+        self.configuration_id().write(&mut writer)?;
+        self.write_state(&mut writer)
+    }
+
+    /// Reads a snapshot, checking that it is a snapshot of this kind of state,
+    /// in a supported version, and of the same configuration.
+    fn read_snapshot<R: Read>(mut reader: R) -> io::Result<Self> {
+        let magic = <[u8; 4]>::read(&mut reader)?;
+        if magic != Self::MAGIC {
+            return Err(invalid_data(format!(
+                "expected a {} snapshot, found magic bytes {:?}",
+                Self::NAME,
+                magic
+            )));
+        }
+        let version = u8::read(&mut reader)?;
+        if version != SNAPSHOT_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported {} snapshot version {}, expected {}",
+                Self::NAME,
+                version,
+                SNAPSHOT_VERSION
+            )));
+        }
+        let configuration_id = u64::read(&mut reader)?;
+        let state = Self::read_state(&mut reader)?;
+        if state.configuration_id() != configuration_id {
+            return Err(invalid_data(format!(
+                "{} snapshot of another configuration: found {:#018x}, expected {:#018x}",
+                Self::NAME,
+                configuration_id,
+                state.configuration_id()
+            )));
+        }
+        Ok(state)
+    }
+}
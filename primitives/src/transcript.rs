@@ -0,0 +1,383 @@
+//! Recording and replay of Fiat-Shamir transcripts, to localize the mismatches
+//! between the transcripts of a prover and a verifier, or of a native
+//! implementation and its gadget. A `TranscriptRecorder` logs every absorb and
+//! squeeze with a label; in replay mode, it fails at the first operation that
+//! diverges from a previous record, instead of at the final check of the proof.
+//! A `Transcript` derives the challenges of a protocol from its messages, with
+//! mandatory domain separation, and logs them into a recorder, if any.
+#[cfg(feature = "fiat_shamir")]
+use crate::{fiat_shamir::FiatShamirRng, CryptoError};
+use crate::Error;
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+use std::{
+    fmt,
+    io::{self, Read, Result as IoResult, Write},
+};
+
+/// An operation on a Fiat-Shamir transcript.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TranscriptEvent {
+    /// The serialization of an item absorbed into the transcript.
+    Absorb { label: String, bytes: Vec<u8> },
+    /// The serialization of a challenge squeezed from the transcript.
+    Squeeze { label: String, bytes: Vec<u8> },
+}
+
+impl TranscriptEvent {
+    pub fn label(&self) -> &str {
+        match self {
+            TranscriptEvent::Absorb { label, .. } | TranscriptEvent::Squeeze { label, .. } => label,
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            TranscriptEvent::Absorb { bytes, .. } | TranscriptEvent::Squeeze { bytes, .. } => bytes,
+        }
+    }
+}
+
+impl fmt::Display for TranscriptEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operation = match self {
+            TranscriptEvent::Absorb { .. } => "absorb",
+            TranscriptEvent::Squeeze { .. } => "squeeze",
+        };
+        write!(f, "{} {}: {}", operation, self.label(), hex::encode(self.bytes()))
+    }
+}
+
+fn write_bytes<W: Write>(bytes: &[u8], mut writer: W) -> IoResult<()> {
+    (bytes.len() as u32).write(&mut writer)?;
+    writer.write_all(bytes)
+}
+
+fn read_bytes<R: Read>(mut reader: R) -> IoResult<Vec<u8>> {
+    // The length is untrusted, so the bytes are read before being allocated
+    let len = u64::from(u32::read(&mut reader)?);
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "truncated transcript event"));
+    }
+    Ok(bytes)
+}
+
+impl ToBytes for TranscriptEvent {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        let tag = match self {
+            TranscriptEvent::Absorb { .. } => 0u8,
+            TranscriptEvent::Squeeze { .. } => 1u8,
+        };
+        tag.write(&mut writer)?;
+        write_bytes(self.label().as_bytes(), &mut writer)?;
+        write_bytes(self.bytes(), &mut writer)
+    }
+}
+
+impl FromBytes for TranscriptEvent {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let tag = u8::read(&mut reader)?;
+        let label = String::from_utf8(read_bytes(&mut reader)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let bytes = read_bytes(&mut reader)?;
+        match tag {
+            0 => Ok(TranscriptEvent::Absorb { label, bytes }),
+            1 => Ok(TranscriptEvent::Squeeze { label, bytes }),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "invalid transcript event")),
+        }
+    }
+}
+
+/// The sequence of operations on a Fiat-Shamir transcript.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TranscriptRecord {
+    events: Vec<TranscriptEvent>,
+}
+
+impl TranscriptRecord {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> &[TranscriptEvent] {
+        &self.events
+    }
+
+    pub fn push(&mut self, event: TranscriptEvent) {
+        self.events.push(event);
+    }
+
+    /// Returns the first operation on which `self` and `other` diverge, if any.
+    pub fn diff(&self, other: &Self) -> Option<TranscriptMismatch> {
+        let len = self.events.len().max(other.events.len());
+        (0..len)
+            .find(|&i| self.events.get(i) != other.events.get(i))
+            .map(|index| TranscriptMismatch {
+                index,
+                expected: self.events.get(index).cloned(),
+                found:    other.events.get(index).cloned(),
+            })
+    }
+}
+
+impl fmt::Display for TranscriptRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, event) in self.events.iter().enumerate() {
+            writeln!(f, "{}: {}", i, event)?;
+        }
+        Ok(())
+    }
+}
+
+impl ToBytes for TranscriptRecord {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.events.len() as u32).write(&mut writer)?;
+        for event in self.events.iter() {
+            event.write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromBytes for TranscriptRecord {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let len = u32::read(&mut reader)?;
+        let events = (0..len)
+            .map(|_| TranscriptEvent::read(&mut reader))
+            .collect::<IoResult<Vec<_>>>()?;
+        Ok(Self { events })
+    }
+}
+
+/// The first divergence between an expected transcript and the one found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranscriptMismatch {
+    /// The index of the operation.
+    pub index:    usize,
+    /// The expected operation, `None` if the transcript found is longer.
+    pub expected: Option<TranscriptEvent>,
+    /// The operation found, `None` if the transcript found is shorter.
+    pub found:    Option<TranscriptEvent>,
+}
+
+impl fmt::Display for TranscriptMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transcripts diverge at operation {}: expected ", self.index)?;
+        match &self.expected {
+            Some(event) => write!(f, "{}", event)?,
+            None => write!(f, "the end of the transcript")?,
+        }
+        write!(f, ", found ")?;
+        match &self.found {
+            Some(event) => write!(f, "{}", event),
+            None => write!(f, "the end of the transcript"),
+        }
+    }
+}
+
+impl std::error::Error for TranscriptMismatch {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+/// Records the operations on a Fiat-Shamir transcript. In replay mode, each
+/// operation is also checked against an expected record.
+#[derive(Clone, Debug, Default)]
+pub struct TranscriptRecorder {
+    record:   TranscriptRecord,
+    expected: Option<TranscriptRecord>,
+}
+
+impl TranscriptRecorder {
+    /// Returns a recorder in record mode.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a recorder in replay mode, failing at the first operation that
+    /// diverges from `expected`.
+    pub fn replay(expected: TranscriptRecord) -> Self {
+        Self { record: TranscriptRecord::new(), expected: Some(expected) }
+    }
+
+    pub fn absorb(&mut self, label: &str, item: &impl ToBytes) -> Result<(), Error> {
+        let bytes = to_bytes![item]?;
+        self.push(TranscriptEvent::Absorb { label: label.to_owned(), bytes })
+    }
+
+    pub fn squeeze(&mut self, label: &str, challenge: &impl ToBytes) -> Result<(), Error> {
+        let bytes = to_bytes![challenge]?;
+        self.push(TranscriptEvent::Squeeze { label: label.to_owned(), bytes })
+    }
+
+    fn push(&mut self, event: TranscriptEvent) -> Result<(), Error> {
+        if let Some(expected) = &self.expected {
+            let index = self.record.events.len();
+            if expected.events.get(index) != Some(&event) {
+                return Err(Box::new(TranscriptMismatch {
+                    index,
+                    expected: expected.events.get(index).cloned(),
+                    found:    Some(event),
+                }));
+            }
+        }
+        self.record.push(event);
+        Ok(())
+    }
+
+    /// Returns the operations recorded so far.
+    pub fn record(&self) -> &TranscriptRecord {
+        &self.record
+    }
+
+    /// Returns the record, failing in replay mode if some expected operations
+    /// were not replayed.
+    pub fn finish(self) -> Result<TranscriptRecord, Error> {
+        if let Some(expected) = &self.expected {
+            if let Some(mismatch) = expected.diff(&self.record) {
+                return Err(Box::new(mismatch));
+            }
+        }
+        Ok(self.record)
+    }
+}
+
+/// A Fiat-Shamir transcript on top of the random oracle `FS`. The transcript
+/// is domain separated by the name of its protocol, and each message and each
+/// challenge by its label, which is absorbed before it: the challenges of a
+/// protocol cannot be reused by another one, nor a message passed for another.
+/// The operations are logged into `recorder`, if any.
+#[cfg(feature = "fiat_shamir")]
+pub struct Transcript<'a, FS: FiatShamirRng> {
+    rng:      FS,
+    recorder: Option<&'a mut TranscriptRecorder>,
+}
+
+#[cfg(feature = "fiat_shamir")]
+impl<'a, FS: FiatShamirRng> Transcript<'a, FS> {
+    /// Returns a transcript domain separated by `protocol`, which must not be
+    /// empty, e.g. the name and the version of the protocol.
+    pub fn new(protocol: &[u8], recorder: Option<&'a mut TranscriptRecorder>) -> Result<Self, Error> {
+        if protocol.is_empty() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("protocol".to_owned(), 0)));
+        }
+        Ok(Self { rng: FS::new(protocol)?, recorder })
+    }
+
+    pub fn absorb(&mut self, label: &str, item: &impl ToBytes) -> Result<(), Error> {
+        self.rng.absorb_bytes(label.as_bytes());
+        self.rng.absorb(item)?;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.absorb(label, item)?;
+        }
+        Ok(())
+    }
+
+    pub fn challenge(&mut self, label: &str) -> Result<FS::Field, Error> {
+        self.rng.absorb_bytes(label.as_bytes());
+        let challenge = self.rng.squeeze_field_elements(1)[0];
+        if let Some(recorder) = &mut self.recorder {
+            recorder.squeeze(label, &challenge)?;
+        }
+        Ok(challenge)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn transcript(recorder: &mut TranscriptRecorder, last: u64) -> Result<(), Error> {
+        recorder.absorb("statement", &[1u8; 4])?;
+        recorder.absorb("commitment", &2u64)?;
+        recorder.squeeze("challenge", &3u64)?;
+        recorder.absorb("response", &last)
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut recorder = TranscriptRecorder::new();
+        transcript(&mut recorder, 4).unwrap();
+        let record = recorder.finish().unwrap();
+        assert_eq!(record.events().len(), 4);
+        assert_eq!(record.events()[2].label(), "challenge");
+
+        let mut replay = TranscriptRecorder::replay(record.clone());
+        transcript(&mut replay, 4).unwrap();
+        assert_eq!(replay.finish().unwrap(), record);
+
+        // The replay fails at the diverging operation
+        let mut replay = TranscriptRecorder::replay(record.clone());
+        let err = transcript(&mut replay, 5).unwrap_err();
+        let mismatch = err.downcast_ref::<TranscriptMismatch>().unwrap();
+        assert_eq!(mismatch.index, 3);
+        assert_eq!(mismatch.expected.as_ref().unwrap(), &record.events()[3]);
+        assert_eq!(replay.record().events().len(), 3);
+
+        // A replay stopping early fails when finished
+        let mut replay = TranscriptRecorder::replay(record.clone());
+        replay.absorb("statement", &[1u8; 4]).unwrap();
+        let err = replay.finish().unwrap_err();
+        assert_eq!(err.downcast_ref::<TranscriptMismatch>().unwrap().found, None);
+    }
+
+    #[test]
+    fn diff_and_serialization() {
+        let mut recorder = TranscriptRecorder::new();
+        transcript(&mut recorder, 4).unwrap();
+        let record = recorder.finish().unwrap();
+        let mut recorder = TranscriptRecorder::new();
+        transcript(&mut recorder, 5).unwrap();
+        let other = recorder.finish().unwrap();
+
+        assert_eq!(record.diff(&record), None);
+        assert_eq!(record.diff(&other).unwrap().index, 3);
+        let mut longer = record.clone();
+        longer.push(TranscriptEvent::Squeeze { label: "extra".to_owned(), bytes: vec![] });
+        let mismatch = record.diff(&longer).unwrap();
+        assert_eq!((mismatch.index, mismatch.expected), (4, None));
+
+        let bytes = to_bytes![record].unwrap();
+        assert_eq!(TranscriptRecord::read(bytes.as_slice()).unwrap(), record);
+        assert!(TranscriptRecord::read(&bytes[..bytes.len() - 1]).is_err());
+
+        // A huge length is not allocated before the bytes are read
+        let mut huge = to_bytes![1u32, 0u8, u32::max_value()].unwrap();
+        huge.push(0);
+        assert!(TranscriptRecord::read(huge.as_slice()).is_err());
+        assert_eq!(format!("{}", record).lines().count(), 4);
+    }
+
+    #[cfg(feature = "fiat_shamir")]
+    #[test]
+    fn transcript_domain_separation() {
+        use crate::fiat_shamir::ChaChaFSRng;
+        use algebra::fields::mnt4753::Fr;
+        use blake2::Blake2s;
+
+        type FS = ChaChaFSRng<Fr, Blake2s>;
+
+        fn challenge(protocol: &[u8], label: &str, recorder: Option<&mut TranscriptRecorder>) -> Fr {
+            let mut transcript = Transcript::<FS>::new(protocol, recorder).unwrap();
+            transcript.absorb(label, &2u64).unwrap();
+            transcript.challenge("challenge").unwrap()
+        }
+
+        let mut recorder = TranscriptRecorder::new();
+        let c = challenge(b"protocol", "commitment", Some(&mut recorder));
+        assert_eq!(c, challenge(b"protocol", "commitment", None));
+        assert_ne!(c, challenge(b"other protocol", "commitment", None));
+        assert_ne!(c, challenge(b"protocol", "response", None));
+        assert!(Transcript::<FS>::new(b"", None).is_err());
+
+        let record = recorder.finish().unwrap();
+        assert_eq!(record.events()[0], TranscriptEvent::Absorb { label: "commitment".to_owned(), bytes: to_bytes![2u64].unwrap() });
+        assert_eq!(record.events()[1], TranscriptEvent::Squeeze { label: "challenge".to_owned(), bytes: to_bytes![c].unwrap() });
+    }
+}
@@ -1,12 +1,15 @@
 use algebra::{Field, PrimeField, FpParameters, convert, leading_zeros, Group, AffineCurve, ProjectiveCurve,
-              ToBytes, to_bytes, ToBits, UniformRand, ToConstraintField, FromBytes};
+              FixedBaseMSM, ToBytes, to_bytes, ToBits, UniformRand, ToConstraintField, FromBytes};
 use crate::{crh::{
     FieldBasedHash, FixedLengthCRH,
 }, vrf::FieldBasedVrf, Error, CryptoError, compute_truncation_size};
 use std::marker::PhantomData;
 use rand::Rng;
+use rayon::prelude::*;
 use std::io::{Write, Read, Result as IoResult};
 
+pub mod rfc9381;
+
 
 pub struct FieldBasedEcVrf<
     F: PrimeField,
@@ -86,7 +89,119 @@ impl<F, G, FH, GH> FieldBasedVrf for FieldBasedEcVrf<F, G, FH, GH>
         message:           &[Self::Data],
     )-> Result<Self::Proof, Error>
     {
-        //Compute mh = hash_to_curve(message)
+        Self::prove_with_generator_mul(
+            rng,
+            group_hash_params,
+            pk,
+            sk,
+            message,
+            |r| G::prime_subgroup_generator().mul(r),
+        )
+    }
+
+    fn proof_to_hash(
+        group_hash_params: &Self::GHParams,
+        pk:                &Self::PublicKey,
+        message:           &[Self::Data],
+        proof:             &Self::Proof
+    )
+        -> Result<Self::Data, Error>
+    {
+        let (c_conv, s_conv) = Self::proof_to_scalars(proof)?;
+
+        //Compute u = g^s - pk^c
+        let u = G::prime_subgroup_generator().mul(&s_conv) - &(pk.mul(&c_conv));
+
+        Self::check_proof(group_hash_params, pk, message, proof, &c_conv, &s_conv, &u)
+    }
+
+    fn keyverify(
+        pk: &Self::PublicKey,
+    ) -> bool {
+        pk.group_membership_test()
+    }
+
+    /// The random r of all the proofs are multiplied by a single window table
+    /// of the generator.
+    fn batch_prove<R: Rng>(
+        rng:               &mut R,
+        group_hash_params: &Self::GHParams,
+        pk:                &Self::PublicKey,
+        sk:                &Self::SecretKey,
+        messages:          &[&[Self::Data]],
+    ) -> Result<Vec<Self::Proof>, Error>
+    {
+        let prover = FieldBasedEcVrfProver::<F, G, FH, GH>::new(
+            group_hash_params,
+            pk,
+            sk,
+            messages.len()
+        );
+        messages
+            .iter()
+            .map(|message| prover.prove(rng, message))
+            .collect()
+    }
+
+    /// The s * g are computed with a single window table of the generator, and,
+    /// as the pk^c, in parallel. The hashes are then checked one by one.
+    fn batch_verify(
+        group_hash_params: &Self::GHParams,
+        pks:               &[Self::PublicKey],
+        messages:          &[&[Self::Data]],
+        proofs:            &[Self::Proof],
+    ) -> Result<Vec<Self::Data>, Error>
+    {
+        if messages.len() != pks.len() || proofs.len() != pks.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("batch".to_owned(), pks.len())))
+        }
+
+        let scalars = proofs
+            .iter()
+            .map(Self::proof_to_scalars)
+            .collect::<Result<Vec<_>, _>>()?;
+        let s_scalars = scalars.iter().map(|(_, s)| *s).collect::<Vec<_>>();
+
+        //Compute s_i * g, sharing the window table of g
+        let scalar_size = G::ScalarField::size_in_bits();
+        let window = FixedBaseMSM::get_mul_window_size(proofs.len());
+        let g_table = FixedBaseMSM::get_window_table(
+            scalar_size,
+            window,
+            G::prime_subgroup_generator()
+        );
+        let s_times_g = FixedBaseMSM::multi_scalar_mul(scalar_size, window, &g_table, &s_scalars);
+
+        //Compute u_i = g^s_i - pk_i^c_i
+        let us = pks
+            .par_iter()
+            .zip(scalars.par_iter())
+            .zip(s_times_g.par_iter())
+            .map(|((pk, (c, _)), s_times_g)| *s_times_g - &pk.mul(c))
+            .collect::<Vec<_>>();
+
+        let batch = pks.iter().zip(messages).zip(proofs).zip(scalars.iter()).zip(us.iter());
+        batch
+            .map(|((((pk, message), proof), (c, s)), u)| {
+                Self::check_proof(group_hash_params, pk, message, proof, c, s, u)
+            })
+            .collect()
+    }
+}
+
+impl<F, G, FH, GH> FieldBasedEcVrf<F, G, FH, GH>
+    where
+        F: PrimeField,
+        G: ProjectiveCurve + ToConstraintField<F>,
+        FH: FieldBasedHash<Data = F>,
+        GH: FixedLengthCRH<Output = G>,
+{
+    /// Computes mh = hash_to_curve(message).
+    fn message_to_curve(
+        group_hash_params: &GH::Parameters,
+        message:           &[F],
+    ) -> Result<G, Error>
+    {
         let mut message_bytes = Vec::new();
         for (i, field_element) in message.iter().enumerate() {
             // The reason for a secure de-packing is not collision resistance (the non-restricted variant
@@ -101,7 +216,20 @@ impl<F, G, FH, GH> FieldBasedVrf for FieldBasedEcVrf<F, G, FH, GH>
             message_bytes.extend_from_slice(to_bytes!(field_element).unwrap().as_slice())
         }
 
-        let message_on_curve = GH::evaluate(group_hash_params, message_bytes.as_slice())?;
+        GH::evaluate(group_hash_params, message_bytes.as_slice())
+    }
+
+    /// Proves `message`, computing the multiples of the generator with `mul_generator`.
+    fn prove_with_generator_mul<R: Rng, M: Fn(&G::ScalarField) -> G>(
+        rng:               &mut R,
+        group_hash_params: &GH::Parameters,
+        pk:                &G,
+        sk:                &G::ScalarField,
+        message:           &[F],
+        mul_generator:     M,
+    ) -> Result<FieldBasedEcVrfProof<F, G>, Error>
+    {
+        let message_on_curve = Self::message_to_curve(group_hash_params, message)?;
 
         //Compute gamma = message_on_curve^sk
         let gamma = message_on_curve.mul(sk);
@@ -114,7 +242,7 @@ impl<F, G, FH, GH> FieldBasedVrf for FieldBasedEcVrf<F, G, FH, GH>
             if r.is_zero() {continue};
 
             //Compute a = g^r
-            let a = G::prime_subgroup_generator().mul(&r);
+            let a = mul_generator(&r);
 
             //Compute b = message_on_curve^r
             let b = message_on_curve.mul(&r);
@@ -157,16 +285,12 @@ impl<F, G, FH, GH> FieldBasedVrf for FieldBasedEcVrf<F, G, FH, GH>
         Ok(FieldBasedEcVrfProof {gamma, c, s})
     }
 
-    fn proof_to_hash(
-        group_hash_params: &Self::GHParams,
-        pk:                &Self::PublicKey,
-        message:           &[Self::Data],
-        proof:             &Self::Proof
-    )
-        -> Result<Self::Data, Error>
+    /// Checks the lengths of c and s and that gamma is in the prime order subgroup,
+    /// and converts c and s to the scalar field.
+    fn proof_to_scalars(
+        proof: &FieldBasedEcVrfProof<F, G>,
+    ) -> Result<(G::ScalarField, G::ScalarField), Error>
     {
-
-        //Checks
         let c_bits = proof.c.write_bits();
         let c_leading_zeros = leading_zeros(c_bits.clone()) as usize;
         if (F::size_in_bits() - c_leading_zeros) >= G::ScalarField::size_in_bits(){
@@ -183,31 +307,27 @@ impl<F, G, FH, GH> FieldBasedVrf for FieldBasedEcVrf<F, G, FH, GH>
             return Err(Box::new(CryptoError::NotPrimeOrder("proof.gamma".to_owned())))
         }
 
-        //Compute mh = hash_to_curve(message)
-        let mut message_bytes = Vec::new();
-        for (i, field_element) in message.iter().enumerate() {
-            // The reason for a secure de-packing is not collision resistance (the non-restricted variant
-            // would be still), but that inside the circuit a field element might be proven to hash to
-            // one of two possible fingerprints (as there might be two different byte sequences satisfying
-            // the depacking constraint mod q). Hence via SNARKs the output of the VRF is not unique and
-            // can be chosen between two possible outputs, which is what we definitely do not want in the
-            // application of the VRF (the VRF is now rather a verifiable random relation, not function).
-            if field_element.into_repr_raw() >= F::Params::MODULUS {
-                return Err(Box::new(CryptoError::InvalidElement(format!("message_element_{}", i).to_owned())));
-            }
-            message_bytes.extend_from_slice(to_bytes!(field_element).unwrap().as_slice())
-        }
-
-        let message_on_curve = GH::evaluate(group_hash_params, message_bytes.as_slice())?;
-
         let c_conv = convert::<G::ScalarField>(c_bits)?;
         let s_conv = convert::<G::ScalarField>(s_bits)?;
+        Ok((c_conv, s_conv))
+    }
 
-        //Compute u = g^s - pk^c
-        let u = G::prime_subgroup_generator().mul(&s_conv) - &(pk.mul(&c_conv));
+    /// Given u = g^s - pk^c, checks that c = H(m||pk.x||u.x||v.x) and returns the
+    /// VRF output.
+    fn check_proof(
+        group_hash_params: &GH::Parameters,
+        pk:                &G,
+        message:           &[F],
+        proof:             &FieldBasedEcVrfProof<F, G>,
+        c_conv:            &G::ScalarField,
+        s_conv:            &G::ScalarField,
+        u:                 &G,
+    ) -> Result<F, Error>
+    {
+        let message_on_curve = Self::message_to_curve(group_hash_params, message)?;
 
         //Compute v = mh^s - gamma^c
-        let v = message_on_curve.mul(&s_conv) - &proof.gamma.mul(&c_conv);
+        let v = message_on_curve.mul(s_conv) - &proof.gamma.mul(c_conv);
 
         //Compute c' = H(m||pk.x||u.x||v.x)
         let mut hash_input = Vec::new();
@@ -235,11 +355,75 @@ impl<F, G, FH, GH> FieldBasedVrf for FieldBasedEcVrf<F, G, FH, GH>
             }
         }
     }
+}
 
-    fn keyverify(
-        pk: &Self::PublicKey,
-    ) -> bool {
-        pk.group_membership_test()
+/// Prover of the `FieldBasedEcVrf` under a fixed key pair, for streams of messages
+/// (e.g. a block producer proving its slots). The window table of the generator is
+/// computed once, so that each proof costs a single variable base multiplication
+/// besides the hash to curve.
+pub struct FieldBasedEcVrfProver<'a, F, G, FH, GH>
+    where
+        F: PrimeField,
+        G: ProjectiveCurve + ToConstraintField<F>,
+        FH: FieldBasedHash<Data = F>,
+        GH: FixedLengthCRH<Output = G>,
+{
+    group_hash_params: &'a GH::Parameters,
+    pk:                G,
+    sk:                G::ScalarField,
+    window:            usize,
+    g_table:           Vec<Vec<G>>,
+    _field:            PhantomData<F>,
+    _field_hash:       PhantomData<FH>,
+}
+
+impl<'a, F, G, FH, GH> FieldBasedEcVrfProver<'a, F, G, FH, GH>
+    where
+        F: PrimeField,
+        G: ProjectiveCurve + ToConstraintField<F>,
+        FH: FieldBasedHash<Data = F>,
+        GH: FixedLengthCRH<Output = G>,
+{
+    /// Creates a prover whose window table is sized for `expected_messages` proofs.
+    pub fn new(
+        group_hash_params: &'a GH::Parameters,
+        pk:                &G,
+        sk:                &G::ScalarField,
+        expected_messages: usize,
+    ) -> Self
+    {
+        let window = FixedBaseMSM::get_mul_window_size(expected_messages);
+        let g_table = FixedBaseMSM::get_window_table(
+            G::ScalarField::size_in_bits(),
+            window,
+            G::prime_subgroup_generator()
+        );
+        Self {
+            group_hash_params,
+            pk: *pk,
+            sk: *sk,
+            window,
+            g_table,
+            _field: PhantomData,
+            _field_hash: PhantomData,
+        }
+    }
+
+    pub fn prove<R: Rng>(
+        &self,
+        rng:     &mut R,
+        message: &[F],
+    ) -> Result<FieldBasedEcVrfProof<F, G>, Error>
+    {
+        let outerc = (G::ScalarField::size_in_bits() + self.window - 1) / self.window;
+        FieldBasedEcVrf::<F, G, FH, GH>::prove_with_generator_mul(
+            rng,
+            self.group_hash_params,
+            &self.pk,
+            &self.sk,
+            message,
+            |r| FixedBaseMSM::windowed_mul(outerc, self.window, &self.g_table, r),
+        )
     }
 }
 
@@ -253,7 +437,7 @@ mod test {
         mnt4753::Fr as MNT4Fr,
         mnt6753::Fr as MNT6Fr,
     };
-    use algebra::{ToBytes, FromBytes, to_bytes};
+    use algebra::{ToBytes, FromBytes, UniformRand, to_bytes};
     use crate::{
         crh::{
             MNT4PoseidonHash, MNT6PoseidonHash,
@@ -314,6 +498,29 @@ mod test {
         assert!(S::proof_to_hash(pp, &new_pk, message, &proof).is_err());
     }
 
+    fn batch_prove_and_verify<S: FieldBasedVrf, R: Rng>(rng: &mut R, pp: &S::GHParams, batch_size: usize) {
+        let (pk, sk) = S::keygen(rng);
+        let messages = (0..batch_size).map(|_| vec![S::Data::rand(rng)]).collect::<Vec<_>>();
+        let message_refs = messages.iter().map(|m| m.as_slice()).collect::<Vec<_>>();
+        let proofs = S::batch_prove(rng, pp, &pk, &sk, &message_refs).unwrap();
+
+        //The outputs are the same as the ones of the single proofs
+        let pks = vec![pk.clone(); batch_size];
+        let outputs = S::batch_verify(pp, &pks, &message_refs, &proofs).unwrap();
+        for ((message, proof), output) in message_refs.iter().zip(&proofs).zip(&outputs) {
+            assert_eq!(S::proof_to_hash(pp, &pk, message, proof).unwrap(), *output);
+        }
+
+        //A single invalid proof makes the batch fail
+        let bad_message = vec![S::Data::rand(rng)];
+        let mut bad_message_refs = message_refs.clone();
+        bad_message_refs[batch_size / 2] = &bad_message;
+        assert!(S::batch_verify(pp, &pks, &bad_message_refs, &proofs).is_err());
+
+        //Inconsistent lengths are rejected
+        assert!(S::batch_verify(pp, &pks[1..], &message_refs, &proofs).is_err());
+    }
+
     #[test]
     fn mnt4_ecvrf_test() {
         let rng = &mut thread_rng();
@@ -325,6 +532,7 @@ mod test {
             prove_and_verify::<EcVrfMNT4, _>(rng, &[f], &pp);
             failed_verification::<EcVrfMNT4, _>(rng, &[f], &[g], &pp);
         }
+        batch_prove_and_verify::<EcVrfMNT4, _>(rng, &pp, 50);
     }
 
     #[test]
@@ -338,5 +546,6 @@ mod test {
             prove_and_verify::<EcVrfMNT6, _>(rng, &[f], &pp);
             failed_verification::<EcVrfMNT6, _>(rng, &[f], &[g], &pp);
         }
+        batch_prove_and_verify::<EcVrfMNT6, _>(rng, &pp, 50);
     }
 }
\ No newline at end of file
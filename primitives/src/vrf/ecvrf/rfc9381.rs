@@ -0,0 +1,304 @@
+use algebra::{
+    biginteger::BigInteger256,
+    curves::ed25519::{Ed25519Affine, Ed25519Parameters, Ed25519Projective},
+    curves::models::TEModelParameters,
+    fields::ed25519::{fq::Fq, fr::Fr},
+    AffineCurve, BigInteger, Field, FpParameters, FromBytes, Group, PrimeField, ProjectiveCurve,
+    SquareRootField, ToBytes, to_bytes,
+};
+use crate::{CryptoError, Error};
+use digest::Digest;
+use rand::Rng;
+use sha2::Sha512;
+
+/// The ECVRF-EDWARDS25519-SHA512-TAI suite of RFC 9381, whose secret keys, public
+/// keys, proofs and outputs are the byte strings specified by the RFC, so that they
+/// can be exchanged with any other conforming implementation. Differently from
+/// `FieldBasedEcVrf`, it is not meant to be verified inside a SNARK.
+///
+/// See <https://www.rfc-editor.org/rfc/rfc9381.html>.
+pub struct EcVrfEdwards25519Sha512Tai;
+
+impl EcVrfEdwards25519Sha512Tai {
+    pub const SUITE_STRING: u8 = 0x03;
+    /// Length of the encoding of a point, and of a secret or public key.
+    pub const PT_LEN: usize = 32;
+    /// Length of the challenge c in a proof.
+    pub const C_LEN: usize = 16;
+    /// Length of the encoding of a scalar.
+    pub const Q_LEN: usize = 32;
+    pub const PROOF_LEN: usize = Self::PT_LEN + Self::C_LEN + Self::Q_LEN;
+    pub const OUTPUT_LEN: usize = 64;
+
+    /// Samples a secret key, returning it together with its public key.
+    pub fn keygen<R: Rng>(rng: &mut R) -> ([u8; 32], [u8; 32]) {
+        let mut sk = [0u8; 32];
+        rng.fill(&mut sk);
+        (sk, Self::get_public_key(&sk))
+    }
+
+    pub fn get_public_key(sk: &[u8; 32]) -> [u8; 32] {
+        let (x, _) = Self::expand_secret_key(sk);
+        let y = Ed25519Projective::prime_subgroup_generator().mul(&x);
+        Self::point_to_string(&y)
+    }
+
+    /// Returns the proof pi_string of `alpha` (Section 5.1).
+    pub fn prove(sk: &[u8; 32], alpha: &[u8]) -> Result<Vec<u8>, Error> {
+        let (x, nonce_key) = Self::expand_secret_key(sk);
+        let y = Ed25519Projective::prime_subgroup_generator().mul(&x);
+        let pk = Self::point_to_string(&y);
+
+        let h = Self::encode_to_curve(&pk, alpha);
+        let h_string = Self::point_to_string(&h);
+        let gamma = h.mul(&x);
+
+        // Nonce generation as in RFC 8032 (Section 5.4.2.2)
+        let k = Self::string_to_scalar(&Self::hash(&[&nonce_key, &h_string]));
+
+        let c = Self::challenge_generation(&[
+            y,
+            h,
+            gamma,
+            Ed25519Projective::prime_subgroup_generator().mul(&k),
+            h.mul(&k),
+        ]);
+        let s = k + &(c * &x);
+
+        let mut pi = Vec::with_capacity(Self::PROOF_LEN);
+        pi.extend_from_slice(&Self::point_to_string(&gamma));
+        pi.extend_from_slice(&to_bytes![c.into_repr()]?[..Self::C_LEN]);
+        pi.extend_from_slice(&to_bytes![s.into_repr()]?);
+        Ok(pi)
+    }
+
+    /// Returns the output beta_string of a proof, without verifying it (Section 5.2).
+    pub fn proof_to_hash(pi: &[u8]) -> Result<Vec<u8>, Error> {
+        let (gamma, _, _) = Self::decode_proof(pi)?;
+        let cofactor_gamma = gamma.into_affine().mul_by_cofactor().into_projective();
+        Ok(Self::hash(&[
+            &[Self::SUITE_STRING, 0x03],
+            &Self::point_to_string(&cofactor_gamma),
+            &[0x00],
+        ]))
+    }
+
+    /// Verifies the proof `pi` of `alpha` under the public key `pk`, returning the VRF
+    /// output beta_string (Section 5.3). If `validate_key` is set, public keys of small
+    /// order are rejected.
+    pub fn verify(pk: &[u8], alpha: &[u8], pi: &[u8], validate_key: bool) -> Result<Vec<u8>, Error> {
+        let y = Self::string_to_point(pk)
+            .ok_or_else(|| CryptoError::InvalidElement("public key".to_owned()))?;
+        if validate_key && AffineCurve::is_zero(&y.into_affine().mul_by_cofactor()) {
+            return Err(Box::new(CryptoError::NotPrimeOrder("public key".to_owned())))
+        }
+
+        let (gamma, c, s) = Self::decode_proof(pi)?;
+        let h = Self::encode_to_curve(pk, alpha);
+
+        // Compute U = s*B - c*Y and V = s*H - c*Gamma
+        let u = Ed25519Projective::prime_subgroup_generator().mul(&s) - &y.mul(&c);
+        let v = h.mul(&s) - &gamma.mul(&c);
+
+        if Self::challenge_generation(&[y, h, gamma, u, v]) != c {
+            return Err(Box::new(CryptoError::FailedVerification))
+        }
+        Self::proof_to_hash(pi)
+    }
+
+    /// Returns the secret scalar x and the nonce key of `sk`, as in RFC 8032
+    /// (Section 5.1.5).
+    fn expand_secret_key(sk: &[u8; 32]) -> (Fr, Vec<u8>) {
+        let hashed_sk = Self::hash(&[sk]);
+        let mut scalar_bytes = hashed_sk[..32].to_vec();
+        scalar_bytes[0] &= 248;
+        scalar_bytes[31] &= 127;
+        scalar_bytes[31] |= 64;
+        (Self::string_to_scalar(&scalar_bytes), hashed_sk[32..].to_vec())
+    }
+
+    /// Try-and-increment encoding of `alpha` to a point of the prime order subgroup
+    /// (Section 5.4.1.1), salted with the public key.
+    fn encode_to_curve(pk: &[u8], alpha: &[u8]) -> Ed25519Projective {
+        // A valid point is found after two attempts on average.
+        let mut ctr = 0u8;
+        loop {
+            let hash = Self::hash(&[&[Self::SUITE_STRING, 0x01], pk, alpha, &[ctr, 0x00]]);
+            if let Some(h) = Self::string_to_point(&hash[..Self::PT_LEN]) {
+                return h.into_affine().mul_by_cofactor().into_projective()
+            }
+            ctr = ctr.checked_add(1).expect("no valid point found in 256 attempts");
+        }
+    }
+
+    /// Computes the challenge c as the first C_LEN bytes of the hash of the points
+    /// (Section 5.4.3).
+    fn challenge_generation(points: &[Ed25519Projective]) -> Fr {
+        let mut input = vec![Self::SUITE_STRING, 0x02];
+        for point in points.iter() {
+            input.extend_from_slice(&Self::point_to_string(point));
+        }
+        input.push(0x00);
+        Self::string_to_scalar(&Self::hash(&[&input])[..Self::C_LEN])
+    }
+
+    /// Splits a proof into Gamma, c and s, rejecting a non canonical s.
+    fn decode_proof(pi: &[u8]) -> Result<(Ed25519Projective, Fr, Fr), Error> {
+        if pi.len() != Self::PROOF_LEN {
+            return Err(Box::new(CryptoError::IncorrectInputLength("proof".to_owned(), pi.len())))
+        }
+        let gamma = Self::string_to_point(&pi[..Self::PT_LEN])
+            .ok_or_else(|| CryptoError::InvalidElement("proof.gamma".to_owned()))?;
+        let c = Self::string_to_scalar(&pi[Self::PT_LEN..Self::PT_LEN + Self::C_LEN]);
+        let s = BigInteger256::read(&pi[Self::PT_LEN + Self::C_LEN..])?;
+        if s >= <Fr as PrimeField>::Params::MODULUS {
+            return Err(Box::new(CryptoError::InvalidElement("proof.s".to_owned())))
+        }
+        Ok((gamma, c, Fr::from_repr(s)))
+    }
+
+    /// Encodes a point as the little endian y coordinate, with the most significant
+    /// bit set to the parity of x (RFC 8032, Section 5.1.2).
+    fn point_to_string(point: &Ed25519Projective) -> [u8; 32] {
+        let point = point.into_affine();
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&to_bytes![point.y.into_repr()].unwrap());
+        if point.x.into_repr().is_odd() {
+            bytes[31] |= 0x80;
+        }
+        bytes
+    }
+
+    /// Decodes a point encoded by `point_to_string`, returning `None` if `bytes` is not
+    /// the canonical encoding of a point of the curve (RFC 8032, Section 5.1.3).
+    fn string_to_point(bytes: &[u8]) -> Option<Ed25519Projective> {
+        if bytes.len() != Self::PT_LEN {
+            return None
+        }
+        let mut y_bytes = bytes.to_vec();
+        let x_is_odd = y_bytes[31] >> 7 == 1;
+        y_bytes[31] &= 0x7f;
+        let y = BigInteger256::read(y_bytes.as_slice()).ok()?;
+        if y >= <Fq as PrimeField>::Params::MODULUS {
+            return None
+        }
+        let y = Fq::from_repr(y);
+
+        // x^2 = (1 - y^2)/(a - d * y^2)
+        let y2 = y.square();
+        let numerator = Fq::one() - &y2;
+        let denominator = Ed25519Parameters::COEFF_A - &(Ed25519Parameters::COEFF_D * &y2);
+        let x = (numerator * &denominator.inverse()?).sqrt()?;
+        if x.is_zero() && x_is_odd {
+            return None
+        }
+        let x = if x.into_repr().is_odd() == x_is_odd { x } else { -x };
+        Some(Ed25519Affine::new(x, y).into_projective())
+    }
+
+    /// Interprets `bytes` as a little endian integer, reduced modulo the group order.
+    fn string_to_scalar(bytes: &[u8]) -> Fr {
+        let base = Fr::from_repr(BigInteger256::from(256));
+        bytes.iter().rev().fold(Fr::zero(), |acc, byte| {
+            acc * &base + &Fr::from_repr(BigInteger256::from(*byte as u64))
+        })
+    }
+
+    fn hash(inputs: &[&[u8]]) -> Vec<u8> {
+        let mut h = Sha512::default();
+        for input in inputs.iter() {
+            h.input(input);
+        }
+        h.result().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::vrf::ecvrf::rfc9381::EcVrfEdwards25519Sha512Tai as EcVrf;
+    use rand::thread_rng;
+
+    struct TestVector {
+        sk:    &'static str,
+        pk:    &'static str,
+        alpha: &'static str,
+        pi:    &'static str,
+        beta:  &'static str,
+    }
+
+    // Examples 16 and 17 of RFC 9381, Appendix B.3
+    const TEST_VECTORS: [TestVector; 2] = [
+        TestVector {
+            sk:    "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            pk:    "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            alpha: "",
+            pi:    "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f\
+                    26f8a57ccaed74ee1b190bed1f479d97\
+                    27d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805",
+            beta:  "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff\
+                    66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae",
+        },
+        TestVector {
+            sk:    "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            pk:    "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            alpha: "72",
+            pi:    "f3141cd382dc42909d19ec5110469e4feae18300e94f304590abdced48aed593\
+                    3bf0864a62558b3ed7f2fea45c92a465\
+                    301b3bbf5e3e54ddf2d935be3b67926da3ef39226bbc355bdc9850112c8f4b02",
+            beta:  "eb4440665d3891d668e7e0fcaf587f1b4bd7fbfe99d0eb2211ccec90496310eb\
+                    5e33821bc613efb94db5e5b54c70a848a0bef4553a41befc57663b56373a5031",
+        },
+    ];
+
+    #[test]
+    fn rfc9381_test_vectors() {
+        for vector in TEST_VECTORS.iter() {
+            let mut sk = [0u8; 32];
+            sk.copy_from_slice(&hex::decode(vector.sk).unwrap());
+            let pk = hex::decode(vector.pk).unwrap();
+            let alpha = hex::decode(vector.alpha).unwrap();
+            let pi = hex::decode(vector.pi).unwrap();
+            let beta = hex::decode(vector.beta).unwrap();
+
+            assert_eq!(EcVrf::get_public_key(&sk).to_vec(), pk);
+            assert_eq!(EcVrf::prove(&sk, &alpha).unwrap(), pi);
+            assert_eq!(EcVrf::proof_to_hash(&pi).unwrap(), beta);
+            assert_eq!(EcVrf::verify(&pk, &alpha, &pi, true).unwrap(), beta);
+        }
+    }
+
+    #[test]
+    fn rfc9381_failed_verification() {
+        let rng = &mut thread_rng();
+        let (sk, pk) = EcVrf::keygen(rng);
+        let pi = EcVrf::prove(&sk, b"message").unwrap();
+        assert!(EcVrf::verify(&pk, b"message", &pi, true).is_ok());
+
+        //Different message
+        assert!(EcVrf::verify(&pk, b"other message", &pi, true).is_err());
+
+        //Different public key
+        let (_, other_pk) = EcVrf::keygen(rng);
+        assert!(EcVrf::verify(&other_pk, b"message", &pi, true).is_err());
+
+        //Tampered proof
+        let mut bad_pi = pi.clone();
+        bad_pi[EcVrf::PT_LEN] ^= 1;
+        assert!(EcVrf::verify(&pk, b"message", &bad_pi, true).is_err());
+
+        //Non canonical s
+        let mut bad_pi = pi.clone();
+        for byte in bad_pi[EcVrf::PT_LEN + EcVrf::C_LEN..].iter_mut() {
+            *byte = 0xff;
+        }
+        assert!(EcVrf::verify(&pk, b"message", &bad_pi, true).is_err());
+
+        //Wrong length
+        assert!(EcVrf::verify(&pk, b"message", &pi[1..], true).is_err());
+
+        //Small order public key (the identity)
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        assert!(EcVrf::verify(&identity, b"message", &pi, true).is_err());
+    }
+}
@@ -1,7 +1,7 @@
 use algebra::{Field, ToBytes, FromBytes};
 use rand::Rng;
 use std::{hash::Hash, fmt::Debug};
-use crate::Error;
+use crate::{CryptoError, Error, KeyProvider};
 
 pub mod ecvrf;
 
@@ -39,4 +39,57 @@ pub trait FieldBasedVrf {
     ) -> Result<Self::Data, Error>;
 
     fn keyverify(pk: &Self::PublicKey) -> bool;
+
+    /// Proves `message` with the secret key `key_id` of `provider`, whose
+    /// public key is `pk`.
+    fn prove_with_key_provider<R: Rng, K: KeyProvider<Self::SecretKey>>
+    (
+        rng:      &mut R,
+        pp:       &Self::GHParams,
+        pk:       &Self::PublicKey,
+        provider: &K,
+        key_id:   &str,
+        message:  &[Self::Data],
+    ) -> Result<Self::Proof, Error>
+    {
+        provider.with_secret_key(key_id, |sk| Self::prove(rng, pp, pk, sk, message))?
+    }
+
+    /// Proves a batch of messages under the same key pair, returning the proofs
+    /// in the same order as `messages`.
+    fn batch_prove<R: Rng>
+    (
+        rng:      &mut R,
+        pp:       &Self::GHParams,
+        pk:       &Self::PublicKey,
+        sk:       &Self::SecretKey,
+        messages: &[&[Self::Data]],
+    ) -> Result<Vec<Self::Proof>, Error>
+    {
+        messages
+            .iter()
+            .map(|message| Self::prove(rng, pp, pk, sk, message))
+            .collect()
+    }
+
+    /// Verifies a batch of VRF proofs, the i-th of which is a proof for
+    /// `messages[i]` under `pks[i]`, and returns their VRF outputs. Fails if
+    /// any of the proofs is invalid.
+    fn batch_verify
+    (
+        pp:       &Self::GHParams,
+        pks:      &[Self::PublicKey],
+        messages: &[&[Self::Data]],
+        proofs:   &[Self::Proof],
+    ) -> Result<Vec<Self::Data>, Error>
+    {
+        if messages.len() != pks.len() || proofs.len() != pks.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength("batch".to_owned(), pks.len())))
+        }
+        pks.iter()
+            .zip(messages)
+            .zip(proofs)
+            .map(|((pk, message), proof)| Self::proof_to_hash(pp, pk, message, proof))
+            .collect()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,203 @@
+//! Bundles of the proofs of the statements of a composite application, e.g. a
+//! transaction proven by several circuits, possibly over different curves.
+//!
+//! A `StatementBundle` holds, for each statement, its label, the verifying key,
+//! the proof and the public inputs, and verifies them all in one call. Its
+//! integrity hash binds all of them, in order, and can be signed or compared
+//! with the one computed by the producer of the bundle.
+use crate::versioning::curve_id;
+use algebra::{bytes::ToBytes, PairingEngine};
+use blake2::{Blake2s, Digest};
+use r1cs_core::SynthesisError;
+use rayon::prelude::*;
+use std::io::{self, Write};
+
+/// The personalization of the integrity hash.
+const BUNDLE_PERSONALIZATION: &[u8] = b"ginger-lib statement bundle";
+
+/// A proof of a statement, together with everything needed to verify it.
+pub trait Statement: Send + Sync {
+    /// The name of the proof system, e.g. `"groth16"`.
+    fn proof_system(&self) -> &'static str;
+
+    /// The identifier of the curve, as computed by `curve_id`.
+    fn curve_id(&self) -> u64;
+
+    /// Writes the verifying key, the public inputs and the proof.
+    fn write_statement(&self, writer: &mut dyn Write) -> io::Result<()>;
+
+    fn verify(&self) -> Result<bool, SynthesisError>;
+}
+
+/// A Groth16 proof with its verifying key and public inputs.
+#[cfg(feature = "groth16")]
+pub struct Groth16Statement<E: PairingEngine> {
+    pub vk:            crate::groth16::VerifyingKey<E>,
+    pvk:               crate::groth16::PreparedVerifyingKey<E>,
+    pub proof:         crate::groth16::Proof<E>,
+    pub public_inputs: Vec<E::Fr>,
+}
+
+#[cfg(feature = "groth16")]
+impl<E: PairingEngine> Groth16Statement<E> {
+    pub fn new(
+        vk: crate::groth16::VerifyingKey<E>,
+        proof: crate::groth16::Proof<E>,
+        public_inputs: Vec<E::Fr>,
+    ) -> Self {
+        let pvk = crate::groth16::prepare_verifying_key(&vk);
+        Self { vk, pvk, proof, public_inputs }
+    }
+}
+
+#[cfg(feature = "groth16")]
+impl<E: PairingEngine> Statement for Groth16Statement<E> {
+    fn proof_system(&self) -> &'static str {
+        "groth16"
+    }
+
+    fn curve_id(&self) -> u64 {
+        curve_id::<E>()
+    }
+
+    fn write_statement(&self, mut writer: &mut dyn Write) -> io::Result<()> {
+        self.vk.write(&mut writer)?;
+        (self.public_inputs.len() as u64).write(&mut writer)?;
+        self.public_inputs.write(&mut writer)?;
+        self.proof.write(&mut writer)
+    }
+
+    fn verify(&self) -> Result<bool, SynthesisError> {
+        crate::groth16::verify_proof(&self.pvk, &self.proof, &self.public_inputs)
+    }
+}
+
+/// A GM17 proof with its verifying key and public inputs.
+#[cfg(feature = "gm17")]
+pub struct Gm17Statement<E: PairingEngine> {
+    pub vk:            crate::gm17::VerifyingKey<E>,
+    pvk:               crate::gm17::PreparedVerifyingKey<E>,
+    pub proof:         crate::gm17::Proof<E>,
+    pub public_inputs: Vec<E::Fr>,
+}
+
+#[cfg(feature = "gm17")]
+impl<E: PairingEngine> Gm17Statement<E> {
+    pub fn new(
+        vk: crate::gm17::VerifyingKey<E>,
+        proof: crate::gm17::Proof<E>,
+        public_inputs: Vec<E::Fr>,
+    ) -> Self {
+        let pvk = crate::gm17::prepare_verifying_key(&vk);
+        Self { vk, pvk, proof, public_inputs }
+    }
+}
+
+#[cfg(feature = "gm17")]
+impl<E: PairingEngine> Statement for Gm17Statement<E> {
+    fn proof_system(&self) -> &'static str {
+        "gm17"
+    }
+
+    fn curve_id(&self) -> u64 {
+        curve_id::<E>()
+    }
+
+    fn write_statement(&self, mut writer: &mut dyn Write) -> io::Result<()> {
+        self.vk.write(&mut writer)?;
+        (self.public_inputs.len() as u64).write(&mut writer)?;
+        self.public_inputs.write(&mut writer)?;
+        self.proof.write(&mut writer)
+    }
+
+    fn verify(&self) -> Result<bool, SynthesisError> {
+        crate::gm17::verify_proof(&self.pvk, &self.proof, &self.public_inputs)
+    }
+}
+
+/// Labelled statements, verified and hashed together.
+#[derive(Default)]
+pub struct StatementBundle {
+    statements: Vec<(String, Box<dyn Statement>)>,
+}
+
+impl StatementBundle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `statement` under `label`. Labels are not required to be
+    /// unique, but they are bound by the integrity hash.
+    pub fn add<S: Statement + 'static>(mut self, label: &str, statement: S) -> Self {
+        self.statements.push((label.to_owned(), Box::new(statement)));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.statements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    pub fn labels(&self) -> Vec<&str> {
+        self.statements.iter().map(|(label, _)| label.as_str()).collect()
+    }
+
+    /// Returns the statement labelled `label`, if any.
+    pub fn get(&self, label: &str) -> Option<&dyn Statement> {
+        self.statements
+            .iter()
+            .find(|(l, _)| l == label)
+            .map(|(_, statement)| statement.as_ref())
+    }
+
+    /// Returns the Blake2s hash of the statements, in order: for each, the
+    /// label, the proof system and the curve, then the verifying key, the
+    /// public inputs and the proof. Variable length items are prefixed by
+    /// their lengths.
+    pub fn integrity_hash(&self) -> [u8; 32] {
+        let mut bytes = vec![];
+        Self::write_bytes(&mut bytes, BUNDLE_PERSONALIZATION);
+        (self.statements.len() as u64).write(&mut bytes).unwrap();
+        for (label, statement) in self.statements.iter() {
+            Self::write_bytes(&mut bytes, label.as_bytes());
+            Self::write_bytes(&mut bytes, statement.proof_system().as_bytes());
+            statement.curve_id().write(&mut bytes).unwrap();
+            statement.write_statement(&mut bytes).unwrap();
+        }
+
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&Blake2s::digest(&bytes));
+        hash
+    }
+
+    fn write_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) {
+        (bytes.len() as u64).write(&mut *buffer).unwrap();
+        buffer.extend_from_slice(bytes);
+    }
+
+    /// Verifies all the statements, in parallel. An empty bundle is valid.
+    pub fn verify_all(&self) -> Result<bool, SynthesisError> {
+        Ok(self.verify_each()?.into_iter().all(|(_, valid)| valid))
+    }
+
+    /// Same as `verify_all`, after checking the integrity hash against the
+    /// `expected` one, e.g. the one signed by the producer of the bundle.
+    pub fn verify_all_with_hash(&self, expected: &[u8; 32]) -> Result<bool, SynthesisError> {
+        if &self.integrity_hash() != expected {
+            return Ok(false);
+        }
+        self.verify_all()
+    }
+
+    /// Verifies all the statements, in parallel, and returns the labels of the
+    /// statements with whether they are valid.
+    pub fn verify_each(&self) -> Result<Vec<(&str, bool)>, SynthesisError> {
+        self.statements
+            .par_iter()
+            .map(|(label, statement)| Ok((label.as_str(), statement.verify()?)))
+            .collect()
+    }
+}
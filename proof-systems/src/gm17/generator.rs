@@ -1,4 +1,4 @@
-use algebra::fft::EvaluationDomain;
+use algebra::fft::GeneralEvaluationDomain;
 use algebra::{
     msm::FixedBaseMSM, UniformRand,
     AffineCurve, Field, PairingEngine, PrimeField, ProjectiveCurve,
@@ -178,7 +178,7 @@ where
     let domain_time = start_timer!(|| "Constructing evaluation domain");
 
     let domain_size = 2 * assembly.num_constraints + 2 * assembly.num_inputs - 1;
-    let domain = EvaluationDomain::<E::Fr>::new(domain_size)
+    let domain = GeneralEvaluationDomain::<E::Fr>::new(domain_size)
         .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
     let t = domain.sample_element_outside_domain(rng);
 
@@ -232,12 +232,12 @@ where
     let gamma_z = zt * &gamma;
     let alpha_beta = alpha + &beta;
     let ab_gamma_z = alpha_beta * &gamma * &zt;
-    let g_gamma = g.into_affine().mul(gamma.into_repr());
-    let g_gamma_z = g.into_affine().mul(gamma_z.into_repr());
-    let h_gamma = h.into_affine().mul(gamma.into_repr());
-    let h_gamma_z = h_gamma.into_affine().mul(zt.into_repr());
-    let g_ab_gamma_z = g.into_affine().mul(ab_gamma_z.into_repr());
-    let g_gamma2_z2 = g.into_affine().mul(gamma_z.square().into_repr());
+    let g_gamma = g.into_affine().mul(gamma);
+    let g_gamma_z = g.into_affine().mul(gamma_z);
+    let h_gamma = h.into_affine().mul(gamma);
+    let h_gamma_z = h_gamma.into_affine().mul(zt);
+    let g_ab_gamma_z = g.into_affine().mul(ab_gamma_z);
+    let g_gamma2_z2 = g.into_affine().mul(gamma_z.square());
 
     // Compute the vector G_gamma2_z_t := Z(t) * t^i * gamma^2 * G
     let gamma2_z_t = gamma_z * &gamma;
@@ -304,8 +304,8 @@ where
 
     // Generate R1CS verification key
     let verifying_key_time = start_timer!(|| "Generate the R1CS verification key");
-    let g_alpha = g.into_affine().mul(alpha.into_repr());
-    let h_beta = h.into_affine().mul(beta.into_repr());
+    let g_alpha = g.into_affine().mul(alpha);
+    let h_beta = h.into_affine().mul(beta);
     end_timer!(verifying_key_time);
 
     let vk = VerifyingKey::<E> {
@@ -5,21 +5,26 @@ use r1cs_core::SynthesisError;
 use std::io::{self, Read, Result as IoResult, Write};
 
 /// Reduce an R1CS instance to a *Square Arithmetic Program* instance.
+#[cfg(feature = "prover")]
 pub mod r1cs_to_sap;
 
 /// Generate public parameters for the GM17 zkSNARK construction.
+#[cfg(feature = "prover")]
 pub mod generator;
 
 /// Create proofs for the GM17 zkSNARK construction.
+#[cfg(feature = "prover")]
 pub mod prover;
 
 /// Verify proofs for the GM17 zkSNARK construction.
 pub mod verifier;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod test;
 
-pub use self::{generator::*, prover::*, verifier::*};
+#[cfg(feature = "prover")]
+pub use self::{generator::*, prover::*};
+pub use self::verifier::*;
 
 /// A proof in the GM17 SNARK.
 #[derive(Clone)]
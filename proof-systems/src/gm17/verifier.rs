@@ -1,4 +1,4 @@
-use algebra::{AffineCurve, Field, PairingCurve, PairingEngine, PrimeField, ProjectiveCurve};
+use algebra::{AffineCurve, Field, PairingCurve, PairingEngine, ProjectiveCurve};
 
 use super::{PreparedVerifyingKey, Proof, VerifyingKey};
 
@@ -35,7 +35,7 @@ pub fn verify_proof<E: PairingEngine>(
 
     let mut g_psi = pvk.query[0].into_projective();
     for (i, b) in public_inputs.iter().zip(pvk.query.iter().skip(1)) {
-        g_psi.add_assign(&b.mul(i.into_repr()));
+        g_psi.add_assign(&b.mul(i));
     }
 
     let mut test1_a_g_alpha = proof.a.into_projective();
@@ -0,0 +1,326 @@
+//! Multi-party computation ceremonies generating the parameters of Groth16, after
+//! [BGM17]. The ceremony has two phases:
+//! - the circuit-independent "powers of tau" of `powers_of_tau`, whose output
+//!   supports any circuit of up to a given number of constraints and inputs;
+//! - the circuit-specific `phase2`, specializing the powers of tau to a circuit.
+//!
+//! In each phase, participants contribute in turn by multiplying the secrets
+//! into the current state and publishing a proof of knowledge of their share.
+//! The final parameters are secure as long as a single participant of each phase
+//! is honest and forgets its share. A phase is usually closed by the contribution
+//! of a random beacon, whose share is derived by `beacon_rng` from a value public
+//! but unpredictable at the time of the last contribution.
+//!
+//! [BGM17]: https://eprint.iacr.org/2017/1050
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    msm::VariableBaseMSM,
+    to_bytes, AffineCurve, PairingEngine, PrimeField, ProjectiveCurve, UniformRand,
+};
+use blake2::{Blake2b, Digest};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+use rayon::prelude::*;
+
+use std::io::{self, Read, Write};
+
+pub mod phase2;
+pub mod powers_of_tau;
+
+pub use self::{phase2::*, powers_of_tau::*};
+
+/// The length of the hashes of the transcripts.
+pub const HASH_LEN: usize = 64;
+
+/// A hash of a transcript of a ceremony.
+pub type Hash = [u8; HASH_LEN];
+
+pub(crate) fn hash(bytes: &[u8]) -> Hash {
+    let mut hash = [0u8; HASH_LEN];
+    hash.copy_from_slice(&Blake2b::digest(bytes));
+    hash
+}
+
+pub(crate) fn read_hash<R: Read>(mut reader: R) -> io::Result<Hash> {
+    let mut hash = [0u8; HASH_LEN];
+    reader.read_exact(&mut hash)?;
+    Ok(hash)
+}
+
+/// Returns the RNG deriving the share of a random beacon from its `beacon` value,
+/// hashed `2^num_iterations_exp` times to delay the knowledge of the share.
+pub fn beacon_rng(beacon: &[u8], num_iterations_exp: u32) -> ChaChaRng {
+    assert!(num_iterations_exp < 64);
+
+    let mut current = hash(beacon);
+    for _ in 0..(1u64 << num_iterations_exp) {
+        current = hash(&current);
+    }
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&current[..32]);
+    ChaChaRng::from_seed(seed)
+}
+
+/// A proof of knowledge of the share `x` of a contribution, bound to the
+/// transcript of the ceremony: a random point `s` of G1, `s * x`, and `r * x`,
+/// where `r` is a point of G2 derived from the transcript, `s` and `s * x`.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
+pub struct ProofOfKnowledge<E: PairingEngine> {
+    pub s:   E::G1Affine,
+    pub s_x: E::G1Affine,
+    pub r_x: E::G2Affine,
+}
+
+impl<E: PairingEngine> ProofOfKnowledge<E> {
+    pub(crate) fn new<R: Rng>(x: E::Fr, transcript: &[u8], rng: &mut R) -> Self {
+        let s = E::G1Projective::rand(rng).into_affine();
+        let s_x = s.mul(x).into_affine();
+        let r_x = hash_to_g2::<E>(transcript, &s, &s_x).mul(x).into_affine();
+        Self { s, s_x, r_x }
+    }
+
+    /// Returns the point `r` of G2 the share is applied to.
+    pub fn r(&self, transcript: &[u8]) -> E::G2Affine {
+        hash_to_g2::<E>(transcript, &self.s, &self.s_x)
+    }
+
+    /// Checks the proof against `transcript`.
+    pub fn verify(&self, transcript: &[u8]) -> bool {
+        !self.s.is_zero()
+            && !self.s_x.is_zero()
+            && self.s.group_membership_test()
+            && self.s_x.group_membership_test()
+            && self.r_x.group_membership_test()
+            && same_ratio::<E>((self.s, self.s_x), (self.r(transcript), self.r_x))
+    }
+}
+
+impl<E: PairingEngine> ToBytes for ProofOfKnowledge<E> {
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.s.write(&mut writer)?;
+        self.s_x.write(&mut writer)?;
+        self.r_x.write(&mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for ProofOfKnowledge<E> {
+    fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let s = E::G1Affine::read(&mut reader)?;
+        let s_x = E::G1Affine::read(&mut reader)?;
+        let r_x = E::G2Affine::read(&mut reader)?;
+        Ok(Self { s, s_x, r_x })
+    }
+}
+
+/// Hashes the transcript and the first points of a proof of knowledge to a point
+/// `r` of G2 whose discrete logarithm is unknown to anyone, as needed for the
+/// proof to show knowledge of the share: random x-coordinates derived from the
+/// hash are tried until one is on the curve, and the point is then mapped to the
+/// prime order subgroup. A multiple of the generator by a scalar derived from
+/// the hash, as `G2Projective::rand` returns, would not do.
+fn hash_to_g2<E: PairingEngine>(
+    transcript: &[u8],
+    s: &E::G1Affine,
+    s_x: &E::G1Affine,
+) -> E::G2Affine {
+    let mut h = Blake2b::default();
+    h.input(transcript);
+    h.input(&to_bytes![s, s_x].unwrap());
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&h.result()[..32]);
+    let rng = &mut ChaChaRng::from_seed(seed);
+    loop {
+        let x = E::Fqe::rand(rng);
+        if let Some(p) = E::G2Affine::from_x_coordinate(x, rng.gen()) {
+            let r = p.scale_by_cofactor_fast();
+            if !r.is_zero() {
+                return r.into_affine();
+            }
+        }
+    }
+}
+
+/// Returns `true` iff `g1.1 = x * g1.0` and `g2.1 = x * g2.0` for the same `x`.
+pub(crate) fn same_ratio<E: PairingEngine>(
+    g1: (E::G1Affine, E::G1Affine),
+    g2: (E::G2Affine, E::G2Affine),
+) -> bool {
+    E::pairing(g1.0, g2.1) == E::pairing(g1.1, g2.0)
+}
+
+/// Returns a random linear combination of `v` and the same of `w`, which have the
+/// same ratio iff `w[i] = x * v[i]` for all `i` with overwhelming probability.
+pub(crate) fn merge_pairs<G: AffineCurve, R: Rng>(v: &[G], w: &[G], rng: &mut R) -> (G, G) {
+    assert_eq!(v.len(), w.len());
+
+    let rho = (0..v.len())
+        .map(|_| G::ScalarField::rand(rng).into_repr())
+        .collect::<Vec<_>>();
+    (
+        VariableBaseMSM::multi_scalar_mul(v, &rho).into_affine(),
+        VariableBaseMSM::multi_scalar_mul(w, &rho).into_affine(),
+    )
+}
+
+/// Returns a pair with the ratio of consecutive elements of `v`, if they all
+/// have the same.
+pub(crate) fn power_pairs<G: AffineCurve, R: Rng>(v: &[G], rng: &mut R) -> (G, G) {
+    merge_pairs(&v[..v.len() - 1], &v[1..], rng)
+}
+
+/// Returns `true` iff all of `points` are in the prime order subgroup.
+pub(crate) fn in_subgroup<G: AffineCurve>(points: &[G]) -> bool {
+    points.par_iter().all(|p| p.group_membership_test())
+}
+
+/// Multiplies `points[i]` by `scalars[i]`.
+pub(crate) fn scale<G: AffineCurve>(points: &mut [G], scalars: &[G::ScalarField]) {
+    let mut scaled = points
+        .par_iter()
+        .zip(scalars.par_iter())
+        .map(|(p, s)| p.mul(*s))
+        .collect::<Vec<_>>();
+    G::Projective::batch_normalization(&mut scaled);
+    points
+        .par_iter_mut()
+        .zip(scaled.par_iter())
+        .for_each(|(p, s)| *p = s.into_affine());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::groth16::{
+        create_random_proof, prepare_verifying_key, test::MySillyCircuit, verify_proof, Parameters,
+    };
+    use algebra::{
+        curves::bls12_381::{Bls12_381, G1Projective, G2Affine, G2Projective},
+        fields::bls12_381::Fr,
+    };
+    use rand::thread_rng;
+
+    #[test]
+    fn powers_of_tau() {
+        let rng = &mut thread_rng();
+
+        let mut accumulators = vec![Accumulator::<Bls12_381>::new(8)];
+        let mut keys = vec![];
+        for i in 0..3 {
+            let mut accumulator = accumulators.last().unwrap().clone();
+            let key = if i < 2 {
+                accumulator.contribute(rng)
+            } else {
+                accumulator.contribute(&mut beacon_rng(b"beacon", 4))
+            };
+            accumulators.push(accumulator);
+            keys.push(key);
+        }
+        assert!(verify_powers_of_tau(&accumulators, &keys, rng));
+
+        // A key of another contribution, or a skipped contribution, is rejected
+        assert!(!verify_contribution(&accumulators[0], &accumulators[1], &keys[1], rng));
+        assert!(!verify_contribution(&accumulators[0], &accumulators[2], &keys[1], rng));
+
+        // Tampering with the powers is detected
+        let mut tampered = accumulators[1].clone();
+        tampered.tau_powers_g1.swap(2, 3);
+        assert!(!verify_contribution(&accumulators[0], &tampered, &keys[0], rng));
+        let mut tampered = accumulators[1].clone();
+        tampered.beta_g2 = accumulators[0].beta_g2;
+        assert!(!verify_contribution(&accumulators[0], &tampered, &keys[0], rng));
+
+        let bytes = to_bytes![accumulators[3], keys[2]].unwrap();
+        let mut reader = bytes.as_slice();
+        assert_eq!(Accumulator::<Bls12_381>::read(&mut reader).unwrap(), accumulators[3]);
+        assert_eq!(PowersOfTauPublicKey::<Bls12_381>::read(&mut reader).unwrap(), keys[2]);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn hash_to_g2_unknown_dlog() {
+        let rng = &mut thread_rng();
+        let s = G1Projective::rand(rng).into_affine();
+        let s_x = s.mul(Fr::rand(rng)).into_affine();
+        let r = hash_to_g2::<Bls12_381>(b"transcript", &s, &s_x);
+        assert!(!r.is_zero());
+        assert!(r.group_membership_test());
+        assert_eq!(r, hash_to_g2::<Bls12_381>(b"transcript", &s, &s_x));
+        assert_ne!(r, hash_to_g2::<Bls12_381>(b"other transcript", &s, &s_x));
+
+        // r is not the multiple of the generator by the scalar sampled from the
+        // seed of the hash, which anyone could recompute
+        let mut h = Blake2b::default();
+        h.input(b"transcript");
+        h.input(&to_bytes![s, s_x].unwrap());
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&h.result()[..32]);
+        let dlog = Fr::rand(&mut ChaChaRng::from_seed(seed));
+        assert_ne!(r, G2Affine::prime_subgroup_generator().mul(dlog).into_affine());
+        assert_ne!(r, G2Projective::rand(&mut ChaChaRng::from_seed(seed)).into_affine());
+    }
+
+    #[test]
+    fn phase2() {
+        let rng = &mut thread_rng();
+        let circuit = || MySillyCircuit::<Fr> { a: None, b: None };
+
+        let mut powers = Accumulator::<Bls12_381>::new(8);
+        powers.contribute(rng);
+
+        // The circuit has an evaluation domain of size 8
+        assert!(MPCParameters::new(circuit(), &Accumulator::<Bls12_381>::new(4)).is_err());
+
+        let mut params = MPCParameters::new(circuit(), &powers).unwrap();
+        assert!(!params.verify(circuit(), &powers, rng).unwrap());
+
+        let hashes = vec![
+            params.contribute(rng),
+            params.contribute(rng),
+            params.contribute(&mut beacon_rng(b"beacon", 4)),
+        ];
+        assert!(params.verify(circuit(), &powers, rng).unwrap());
+        assert_eq!(params.contribution_hashes(), hashes);
+
+        let bytes = to_bytes![params].unwrap();
+        let read = MPCParameters::<Bls12_381>::read(bytes.as_slice()).unwrap();
+        assert_eq!(read.params(), params.params());
+        assert_eq!(read.contributions(), params.contributions());
+
+        let pvk = prepare_verifying_key(&params.params().vk);
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let c = a * &b;
+        let proof = create_random_proof(
+            MySillyCircuit { a: Some(a), b: Some(b) },
+            params.params(),
+            rng,
+        )
+        .unwrap();
+        assert!(verify_proof(&pvk, &proof, &[c]).unwrap());
+        assert!(!verify_proof(&pvk, &proof, &[a]).unwrap());
+
+        // Tampering with the parameters or the contributions is detected
+        let with_parts = |circuit_params: &Parameters<Bls12_381>, contributions: &[Phase2PublicKey<Bls12_381>]| {
+            let mut bytes = to_bytes![circuit_params].unwrap();
+            bytes.extend_from_slice(params.cs_hash());
+            bytes.extend_from_slice(&(contributions.len() as u32).to_be_bytes());
+            for key in contributions.iter() {
+                bytes.extend(to_bytes![key].unwrap());
+            }
+            MPCParameters::<Bls12_381>::read(bytes.as_slice()).unwrap()
+        };
+        assert!(with_parts(params.params(), params.contributions()).verify(circuit(), &powers, rng).unwrap());
+        let mut tampered_params = params.params().clone();
+        tampered_params.l_query[0] = tampered_params.l_query[1];
+        let tampered = with_parts(&tampered_params, params.contributions());
+        assert!(!tampered.verify(circuit(), &powers, rng).unwrap());
+        let mut tampered_contributions = params.contributions().to_vec();
+        tampered_contributions.swap(0, 1);
+        let tampered = with_parts(params.params(), &tampered_contributions);
+        assert!(!tampered.verify(circuit(), &powers, rng).unwrap());
+        let mut other_powers = powers.clone();
+        other_powers.contribute(rng);
+        assert!(!params.verify(circuit(), &other_powers, rng).unwrap());
+    }
+}
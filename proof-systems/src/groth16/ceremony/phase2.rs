@@ -0,0 +1,382 @@
+//! The circuit-specific phase of the ceremony. The parameters of a circuit are
+//! first derived deterministically from the powers of tau, with `delta = 1`,
+//! then each contribution multiplies `delta` by a fresh secret. As in [BGM17],
+//! `gamma` is left to one.
+//!
+//! [BGM17]: https://eprint.iacr.org/2017/1050
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    fft::{EvaluationDomain, GeneralEvaluationDomain},
+    to_bytes, AffineCurve, Field, PairingEngine, ProjectiveCurve, UniformRand,
+};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use r1cs_core::{ConstraintSynthesizer, ConstraintSystem, Index, SynthesisError};
+use rand::Rng;
+use rayon::prelude::*;
+
+use super::{
+    hash, in_subgroup, merge_pairs, read_hash, same_ratio, scale, Accumulator, Hash,
+    ProofOfKnowledge,
+};
+use crate::groth16::{KeypairAssembly, Parameters, VerifyingKey};
+
+use std::io::{self, Read, Write};
+
+/// The public key of a contribution to the parameters of a circuit: the value of
+/// `delta * g1` after the contribution, and a proof of knowledge of its share.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
+pub struct Phase2PublicKey<E: PairingEngine> {
+    pub delta_after: E::G1Affine,
+    pub pok:         ProofOfKnowledge<E>,
+}
+
+impl<E: PairingEngine> ToBytes for Phase2PublicKey<E> {
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.delta_after.write(&mut writer)?;
+        self.pok.write(&mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for Phase2PublicKey<E> {
+    fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let delta_after = E::G1Affine::read(&mut reader)?;
+        let pok = ProofOfKnowledge::read(&mut reader)?;
+        Ok(Self { delta_after, pok })
+    }
+}
+
+/// The parameters of a circuit during the circuit-specific phase, with the
+/// public keys of the contributions so far.
+#[derive(Clone, Debug)]
+pub struct MPCParameters<E: PairingEngine> {
+    params:        Parameters<E>,
+    cs_hash:       Hash,
+    contributions: Vec<Phase2PublicKey<E>>,
+}
+
+impl<E: PairingEngine> MPCParameters<E> {
+    /// Derives the initial parameters of `circuit` from the powers of tau. Fails
+    /// with `PolynomialDegreeTooLarge` if the evaluation domain of the circuit is
+    /// larger than the degree of `powers`, or is not of power-of-2 size.
+    pub fn new<C: ConstraintSynthesizer<E::Fr>>(
+        circuit: C,
+        powers: &Accumulator<E>,
+    ) -> Result<Self, SynthesisError> {
+        let mut assembly = KeypairAssembly::<E> {
+            num_inputs:      0,
+            num_aux:         0,
+            num_constraints: 0,
+            at:              vec![],
+            bt:              vec![],
+            ct:              vec![],
+        };
+
+        // Allocate the "one" input variable
+        assembly.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+        // Synthesize the circuit.
+        let synthesis_time = start_timer!(|| "Constraint synthesis");
+        circuit.generate_constraints(&mut assembly)?;
+        end_timer!(synthesis_time);
+
+        let domain_size = assembly.num_constraints + assembly.num_inputs;
+        let domain = match GeneralEvaluationDomain::<E::Fr>::new(domain_size) {
+            Some(GeneralEvaluationDomain::Radix2(domain)) if domain.size() <= powers.degree() => {
+                domain
+            },
+            _ => return Err(SynthesisError::PolynomialDegreeTooLarge),
+        };
+        let m = domain.size();
+
+        let lagrange_time = start_timer!(|| "Compute Lagrange coefficients in the groups");
+        let lagrange_g1 = lagrange_coefficients(&domain, &powers.tau_powers_g1);
+        let lagrange_g2 = lagrange_coefficients(&domain, &powers.tau_powers_g2);
+        let alpha_lagrange_g1 = lagrange_coefficients(&domain, &powers.alpha_tau_powers_g1);
+        let beta_lagrange_g1 = lagrange_coefficients(&domain, &powers.beta_tau_powers_g1);
+        end_timer!(lagrange_time);
+
+        // Evaluate the QAP polynomials of each variable at tau in the groups, with
+        // ext = beta * a + alpha * b + c
+        let queries_time = start_timer!(|| "Compute queries");
+        let num_inputs = assembly.num_inputs;
+        let num_variables = num_inputs + assembly.num_aux;
+        let mut a = vec![E::G1Projective::zero(); num_variables];
+        let mut b_g1 = vec![E::G1Projective::zero(); num_variables];
+        let mut b_g2 = vec![E::G2Projective::zero(); num_variables];
+        let mut ext = vec![E::G1Projective::zero(); num_variables];
+
+        for i in 0..num_inputs {
+            a[i] = lagrange_g1[assembly.num_constraints + i].into_projective();
+            ext[i] = beta_lagrange_g1[assembly.num_constraints + i].into_projective();
+        }
+
+        let index = |var: Index| match var {
+            Index::Input(i) => i,
+            Index::Aux(i) => num_inputs + i,
+        };
+        for i in 0..assembly.num_constraints {
+            for &(coeff, var) in assembly.at[i].iter() {
+                a[index(var)] += &lagrange_g1[i].mul(coeff);
+                ext[index(var)] += &beta_lagrange_g1[i].mul(coeff);
+            }
+            for &(coeff, var) in assembly.bt[i].iter() {
+                b_g1[index(var)] += &lagrange_g1[i].mul(coeff);
+                b_g2[index(var)] += &lagrange_g2[i].mul(coeff);
+                ext[index(var)] += &alpha_lagrange_g1[i].mul(coeff);
+            }
+            for &(coeff, var) in assembly.ct[i].iter() {
+                ext[index(var)] += &lagrange_g1[i].mul(coeff);
+            }
+        }
+
+        // tau^i * (tau^m - 1) * g1
+        let mut h = (0..m - 1)
+            .into_par_iter()
+            .map(|i| {
+                powers.tau_powers_g1[i + m].into_projective()
+                    - &powers.tau_powers_g1[i].into_projective()
+            })
+            .collect::<Vec<_>>();
+        end_timer!(queries_time);
+
+        E::G1Projective::batch_normalization(&mut a);
+        E::G1Projective::batch_normalization(&mut b_g1);
+        E::G2Projective::batch_normalization(&mut b_g2);
+        E::G1Projective::batch_normalization(&mut ext);
+        E::G1Projective::batch_normalization(&mut h);
+
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let g2 = E::G2Affine::prime_subgroup_generator();
+        let alpha_g1 = powers.alpha_tau_powers_g1[0];
+        let beta_g1 = powers.beta_tau_powers_g1[0];
+        let beta_g2 = powers.beta_g2;
+
+        let vk = VerifyingKey::<E> {
+            alpha_g1_beta_g2: E::pairing(alpha_g1, beta_g2),
+            gamma_g2:         g2,
+            delta_g2:         g2,
+            gamma_abc_g1:     ext[..num_inputs].iter().map(|p| p.into_affine()).collect(),
+        };
+
+        let params = Parameters {
+            vk,
+            alpha_g1,
+            beta_g1,
+            beta_g2,
+            delta_g1: g1,
+            delta_g2: g2,
+            a_query: a.into_iter().map(Into::into).collect(),
+            b_g1_query: b_g1.into_iter().map(Into::into).collect(),
+            b_g2_query: b_g2.into_iter().map(Into::into).collect(),
+            h_query: h.into_iter().map(Into::into).collect(),
+            l_query: ext[num_inputs..].iter().map(|p| p.into_affine()).collect(),
+        };
+        let cs_hash = hash(&to_bytes![params].unwrap());
+
+        Ok(Self { params, cs_hash, contributions: vec![] })
+    }
+
+    /// Returns the current parameters of the circuit.
+    pub fn params(&self) -> &Parameters<E> {
+        &self.params
+    }
+
+    /// Returns the hash of the initial parameters of the circuit, to which all
+    /// the contributions are bound.
+    pub fn cs_hash(&self) -> &Hash {
+        &self.cs_hash
+    }
+
+    pub fn contributions(&self) -> &[Phase2PublicKey<E>] {
+        &self.contributions
+    }
+
+    /// Returns the hashes of the contributions, so that participants can check
+    /// that theirs is included.
+    pub fn contribution_hashes(&self) -> Vec<Hash> {
+        self.contributions
+            .iter()
+            .map(|c| hash(&to_bytes![c].unwrap()))
+            .collect()
+    }
+
+    /// Multiplies `delta` by a fresh secret sampled from `rng`, and returns the
+    /// hash of the contribution.
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> Hash {
+        let transcript = transcript(&self.cs_hash, &self.contributions);
+
+        let (delta, delta_inverse) = loop {
+            let delta = E::Fr::rand(rng);
+            if let Some(delta_inverse) = delta.inverse() {
+                break (delta, delta_inverse);
+            }
+        };
+        let pok = ProofOfKnowledge::new(delta, &transcript, rng);
+
+        self.params.delta_g1 = self.params.delta_g1.mul(delta).into_affine();
+        self.params.delta_g2 = self.params.delta_g2.mul(delta).into_affine();
+        self.params.vk.delta_g2 = self.params.delta_g2;
+        let h_len = self.params.h_query.len();
+        scale(&mut self.params.h_query, &vec![delta_inverse; h_len]);
+        let l_len = self.params.l_query.len();
+        scale(&mut self.params.l_query, &vec![delta_inverse; l_len]);
+
+        let key = Phase2PublicKey { delta_after: self.params.delta_g1, pok };
+        let contribution_hash = hash(&to_bytes![key].unwrap());
+        self.contributions.push(key);
+        contribution_hash
+    }
+
+    /// Checks that the parameters result from a chain of contributions to the
+    /// initial parameters of `circuit` derived from `powers`. Parameters without
+    /// contributions are rejected, as their `delta` is known. The randomness of
+    /// `rng` is used to batch the checks of the queries.
+    pub fn verify<C: ConstraintSynthesizer<E::Fr>, R: Rng>(
+        &self,
+        circuit: C,
+        powers: &Accumulator<E>,
+        rng: &mut R,
+    ) -> Result<bool, SynthesisError> {
+        let initial = Self::new(circuit, powers)?;
+        let (before, after) = (&initial.params, &self.params);
+
+        // Only delta and the queries divided by delta change
+        if initial.cs_hash != self.cs_hash
+            || self.contributions.is_empty()
+            || before.vk.alpha_g1_beta_g2 != after.vk.alpha_g1_beta_g2
+            || before.vk.gamma_g2 != after.vk.gamma_g2
+            || before.vk.gamma_abc_g1 != after.vk.gamma_abc_g1
+            || before.alpha_g1 != after.alpha_g1
+            || before.beta_g1 != after.beta_g1
+            || before.beta_g2 != after.beta_g2
+            || before.a_query != after.a_query
+            || before.b_g1_query != after.b_g1_query
+            || before.b_g2_query != after.b_g2_query
+            || before.h_query.len() != after.h_query.len()
+            || before.l_query.len() != after.l_query.len()
+            || after.vk.delta_g2 != after.delta_g2
+        {
+            return Ok(false);
+        }
+
+        // The chain of contributions leads to the current delta
+        let mut delta_before = before.delta_g1;
+        for (i, key) in self.contributions.iter().enumerate() {
+            let transcript = transcript(&self.cs_hash, &self.contributions[..i]);
+            if !key.pok.verify(&transcript)
+                || !key.delta_after.group_membership_test()
+                || !same_ratio::<E>(
+                    (delta_before, key.delta_after),
+                    (key.pok.r(&transcript), key.pok.r_x),
+                )
+            {
+                return Ok(false);
+            }
+            delta_before = key.delta_after;
+        }
+        if after.delta_g1 != delta_before
+            || !after.delta_g2.group_membership_test()
+            || !in_subgroup(&after.h_query)
+            || !in_subgroup(&after.l_query)
+        {
+            return Ok(false);
+        }
+
+        // The same delta is used across groups, and divides the queries
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let delta_g2 = (E::G2Affine::prime_subgroup_generator(), after.delta_g2);
+        Ok(same_ratio::<E>((g1, after.delta_g1), delta_g2)
+            && same_ratio::<E>(merge_pairs(&after.h_query, &before.h_query, rng), delta_g2)
+            && same_ratio::<E>(merge_pairs(&after.l_query, &before.l_query, rng), delta_g2))
+    }
+}
+
+impl<E: PairingEngine> ToBytes for MPCParameters<E> {
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.params.write(&mut writer)?;
+        writer.write_all(&self.cs_hash)?;
+        writer.write_u32::<BigEndian>(self.contributions.len() as u32)?;
+        for key in self.contributions.iter() {
+            key.write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: PairingEngine> FromBytes for MPCParameters<E> {
+    fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let params = Parameters::read(&mut reader)?;
+        let cs_hash = read_hash(&mut reader)?;
+        let num_contributions = reader.read_u32::<BigEndian>()? as usize;
+        let contributions = (0..num_contributions)
+            .map(|_| Phase2PublicKey::read(&mut reader))
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self { params, cs_hash, contributions })
+    }
+}
+
+/// Returns the transcript the contribution following `contributions` is bound to.
+fn transcript<E: PairingEngine>(cs_hash: &Hash, contributions: &[Phase2PublicKey<E>]) -> Hash {
+    let mut bytes = cs_hash.to_vec();
+    for key in contributions.iter() {
+        key.write(&mut bytes).unwrap();
+    }
+    hash(&bytes)
+}
+
+/// Computes `L_j(tau) * g` for the Lagrange polynomials `L_j` of `domain`, from
+/// the powers `tau^i * g`, by an inverse FFT in the group.
+fn lagrange_coefficients<G: AffineCurve>(
+    domain: &EvaluationDomain<G::ScalarField>,
+    powers: &[G],
+) -> Vec<G> {
+    #[inline]
+    fn bitreverse(mut n: usize, l: u32) -> usize {
+        let mut r = 0;
+        for _ in 0..l {
+            r = (r << 1) | (n & 1);
+            n >>= 1;
+        }
+        r
+    }
+
+    let n = domain.size();
+    let log_n = domain.log_size_of_group;
+    let mut a = powers[..n].iter().map(|g| g.into_projective()).collect::<Vec<_>>();
+
+    for k in 0..n {
+        let rk = bitreverse(k, log_n);
+        if k < rk {
+            a.swap(rk, k);
+        }
+    }
+
+    let mut m = 1;
+    for _ in 0..log_n {
+        let w_m = domain.group_gen_inv.pow(&[(n / (2 * m)) as u64]);
+        let mut twiddles = Vec::with_capacity(m);
+        let mut w = G::ScalarField::one();
+        for _ in 0..m {
+            twiddles.push(w);
+            w *= &w_m;
+        }
+
+        a.par_chunks_mut(2 * m).for_each(|chunk| {
+            let (lo, hi) = chunk.split_at_mut(m);
+            for ((lo, hi), w) in lo.iter_mut().zip(hi.iter_mut()).zip(twiddles.iter()) {
+                let mut t = *hi;
+                ProjectiveCurve::mul_assign(&mut t, *w);
+                *hi = *lo - &t;
+                *lo += &t;
+            }
+        });
+
+        m *= 2;
+    }
+
+    a.par_iter_mut()
+        .for_each(|g| ProjectiveCurve::mul_assign(g, domain.size_inv));
+    G::Projective::batch_normalization(&mut a);
+    a.into_iter().map(|g| g.into_affine()).collect()
+}
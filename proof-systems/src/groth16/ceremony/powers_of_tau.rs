@@ -0,0 +1,232 @@
+//! The circuit-independent phase of the ceremony, computing the powers of a
+//! secret `tau` in both groups, and the same multiplied by secrets `alpha` and
+//! `beta`. An accumulator of degree `n` supports the circuits whose evaluation
+//! domain has at most `n` elements.
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes, AffineCurve, Field, PairingEngine, ProjectiveCurve, UniformRand,
+};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use rand::Rng;
+
+use super::{hash, in_subgroup, power_pairs, same_ratio, scale, Hash, ProofOfKnowledge};
+
+use std::io::{self, Read, Write};
+
+/// The state of the powers of tau after a number of contributions.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
+pub struct Accumulator<E: PairingEngine> {
+    /// `tau^i * g1`, for `i` in `0..2 * degree - 1`.
+    pub tau_powers_g1:       Vec<E::G1Affine>,
+    /// `tau^i * g2`, for `i` in `0..degree`.
+    pub tau_powers_g2:       Vec<E::G2Affine>,
+    /// `alpha * tau^i * g1`, for `i` in `0..degree`.
+    pub alpha_tau_powers_g1: Vec<E::G1Affine>,
+    /// `beta * tau^i * g1`, for `i` in `0..degree`.
+    pub beta_tau_powers_g1:  Vec<E::G1Affine>,
+    /// `beta * g2`.
+    pub beta_g2:             E::G2Affine,
+}
+
+impl<E: PairingEngine> Accumulator<E> {
+    /// Returns the accumulator of degree `degree` before any contribution, in
+    /// which all the secrets are one.
+    pub fn new(degree: usize) -> Self {
+        assert!(degree >= 2);
+
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let g2 = E::G2Affine::prime_subgroup_generator();
+        Self {
+            tau_powers_g1:       vec![g1; 2 * degree - 1],
+            tau_powers_g2:       vec![g2; degree],
+            alpha_tau_powers_g1: vec![g1; degree],
+            beta_tau_powers_g1:  vec![g1; degree],
+            beta_g2:             g2,
+        }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.tau_powers_g2.len()
+    }
+
+    /// Returns the hash of the accumulator, to which the next contribution is bound.
+    pub fn hash(&self) -> Hash {
+        hash(&to_bytes![self].unwrap())
+    }
+
+    fn is_well_formed(&self) -> bool {
+        let degree = self.degree();
+        degree >= 2
+            && self.tau_powers_g1.len() == 2 * degree - 1
+            && self.alpha_tau_powers_g1.len() == degree
+            && self.beta_tau_powers_g1.len() == degree
+            && self.tau_powers_g1[0] == E::G1Affine::prime_subgroup_generator()
+            && self.tau_powers_g2[0] == E::G2Affine::prime_subgroup_generator()
+    }
+
+    /// Multiplies fresh secrets sampled from `rng` into the accumulator, and
+    /// returns the public key proving the knowledge of these secrets.
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> PowersOfTauPublicKey<E> {
+        let degree = self.degree();
+        let transcript = self.hash();
+
+        let tau = E::Fr::rand(rng);
+        let alpha = E::Fr::rand(rng);
+        let beta = E::Fr::rand(rng);
+        let key = PowersOfTauPublicKey {
+            tau:   ProofOfKnowledge::new(tau, &transcript, rng),
+            alpha: ProofOfKnowledge::new(alpha, &transcript, rng),
+            beta:  ProofOfKnowledge::new(beta, &transcript, rng),
+        };
+
+        let mut tau_powers = Vec::with_capacity(2 * degree - 1);
+        let mut power = E::Fr::one();
+        for _ in 0..(2 * degree - 1) {
+            tau_powers.push(power);
+            power *= &tau;
+        }
+        let alpha_tau_powers = tau_powers[..degree].iter().map(|p| *p * &alpha).collect::<Vec<_>>();
+        let beta_tau_powers = tau_powers[..degree].iter().map(|p| *p * &beta).collect::<Vec<_>>();
+
+        scale(&mut self.tau_powers_g1, &tau_powers);
+        scale(&mut self.tau_powers_g2, &tau_powers[..degree]);
+        scale(&mut self.alpha_tau_powers_g1, &alpha_tau_powers);
+        scale(&mut self.beta_tau_powers_g1, &beta_tau_powers);
+        self.beta_g2 = self.beta_g2.mul(beta).into_affine();
+
+        key
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Accumulator<E> {
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_u32::<BigEndian>(self.degree() as u32)?;
+        for g in self.tau_powers_g1.iter() {
+            g.write(&mut writer)?;
+        }
+        for g in self.tau_powers_g2.iter() {
+            g.write(&mut writer)?;
+        }
+        for g in self.alpha_tau_powers_g1.iter() {
+            g.write(&mut writer)?;
+        }
+        for g in self.beta_tau_powers_g1.iter() {
+            g.write(&mut writer)?;
+        }
+        self.beta_g2.write(&mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for Accumulator<E> {
+    fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let degree = reader.read_u32::<BigEndian>()? as usize;
+        if degree < 2 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "degree smaller than two"));
+        }
+        let tau_powers_g1 = read_points(2 * degree - 1, &mut reader)?;
+        let tau_powers_g2 = read_points(degree, &mut reader)?;
+        let alpha_tau_powers_g1 = read_points(degree, &mut reader)?;
+        let beta_tau_powers_g1 = read_points(degree, &mut reader)?;
+        let beta_g2 = E::G2Affine::read(&mut reader)?;
+        Ok(Self { tau_powers_g1, tau_powers_g2, alpha_tau_powers_g1, beta_tau_powers_g1, beta_g2 })
+    }
+}
+
+fn read_points<G: AffineCurve, R: Read>(len: usize, mut reader: R) -> io::Result<Vec<G>> {
+    (0..len).map(|_| G::read(&mut reader)).collect()
+}
+
+/// The public key of a contribution to the powers of tau, proving the knowledge
+/// of its shares of `tau`, `alpha` and `beta`.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
+pub struct PowersOfTauPublicKey<E: PairingEngine> {
+    pub tau:   ProofOfKnowledge<E>,
+    pub alpha: ProofOfKnowledge<E>,
+    pub beta:  ProofOfKnowledge<E>,
+}
+
+impl<E: PairingEngine> ToBytes for PowersOfTauPublicKey<E> {
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.tau.write(&mut writer)?;
+        self.alpha.write(&mut writer)?;
+        self.beta.write(&mut writer)
+    }
+}
+
+impl<E: PairingEngine> FromBytes for PowersOfTauPublicKey<E> {
+    fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let tau = ProofOfKnowledge::read(&mut reader)?;
+        let alpha = ProofOfKnowledge::read(&mut reader)?;
+        let beta = ProofOfKnowledge::read(&mut reader)?;
+        Ok(Self { tau, alpha, beta })
+    }
+}
+
+/// Checks that `after` results from the contribution of `key` to `before`. The
+/// randomness of `rng` is used to batch the checks of the powers.
+pub fn verify_contribution<E: PairingEngine, R: Rng>(
+    before: &Accumulator<E>,
+    after: &Accumulator<E>,
+    key: &PowersOfTauPublicKey<E>,
+    rng: &mut R,
+) -> bool {
+    if !before.is_well_formed() || !after.is_well_formed() || before.degree() != after.degree() {
+        return false;
+    }
+
+    let transcript = before.hash();
+    if !key.tau.verify(&transcript) || !key.alpha.verify(&transcript) || !key.beta.verify(&transcript) {
+        return false;
+    }
+
+    if !in_subgroup(&after.tau_powers_g1)
+        || !in_subgroup(&after.tau_powers_g2)
+        || !in_subgroup(&after.alpha_tau_powers_g1)
+        || !in_subgroup(&after.beta_tau_powers_g1)
+        || !after.beta_g2.group_membership_test()
+    {
+        return false;
+    }
+
+    // The secrets are updated by the shares of the proofs of knowledge
+    let updated_by = |before: E::G1Affine, after: E::G1Affine, pok: &ProofOfKnowledge<E>| {
+        same_ratio::<E>((before, after), (pok.r(&transcript), pok.r_x))
+    };
+    if !updated_by(before.tau_powers_g1[1], after.tau_powers_g1[1], &key.tau)
+        || !updated_by(before.alpha_tau_powers_g1[0], after.alpha_tau_powers_g1[0], &key.alpha)
+        || !updated_by(before.beta_tau_powers_g1[0], after.beta_tau_powers_g1[0], &key.beta)
+    {
+        return false;
+    }
+
+    // The powers are consecutive, and the same secrets are used across groups
+    let g1 = (after.tau_powers_g1[0], after.tau_powers_g1[1]);
+    let g2 = (after.tau_powers_g2[0], after.tau_powers_g2[1]);
+    same_ratio::<E>(power_pairs(&after.tau_powers_g1, rng), g2)
+        && same_ratio::<E>(g1, power_pairs(&after.tau_powers_g2, rng))
+        && same_ratio::<E>(power_pairs(&after.alpha_tau_powers_g1, rng), g2)
+        && same_ratio::<E>(power_pairs(&after.beta_tau_powers_g1, rng), g2)
+        && same_ratio::<E>(
+            (after.tau_powers_g1[0], after.beta_tau_powers_g1[0]),
+            (after.tau_powers_g2[0], after.beta_g2),
+        )
+}
+
+/// Checks a whole transcript of the powers of tau: `accumulators[0]` is the
+/// initial accumulator, and `accumulators[i + 1]` results from the contribution
+/// of `keys[i]` to `accumulators[i]`.
+pub fn verify_powers_of_tau<E: PairingEngine, R: Rng>(
+    accumulators: &[Accumulator<E>],
+    keys: &[PowersOfTauPublicKey<E>],
+    rng: &mut R,
+) -> bool {
+    accumulators.len() == keys.len() + 1
+        && accumulators[0].degree() >= 2
+        && accumulators[0] == Accumulator::new(accumulators[0].degree())
+        && accumulators
+            .windows(2)
+            .zip(keys.iter())
+            .all(|(pair, key)| verify_contribution(&pair[0], &pair[1], key, rng))
+}
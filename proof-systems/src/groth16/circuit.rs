@@ -0,0 +1,172 @@
+//! Keys and proofs bound to the circuit they were generated for, so that mixing
+//! up the artifacts of different circuits is a type error, or an error on
+//! deserialization, rather than a proof that mysteriously fails to verify.
+//!
+//! A circuit is identified by a marker type implementing `CircuitMarker`, whose
+//! `CIRCUIT_ID` is written in front of the serialization of its artifacts.
+use algebra::{
+    bytes::{FromBytes, ToBytes},
+    PairingEngine,
+};
+use r1cs_core::SynthesisError;
+use std::{
+    fmt,
+    io::{self, Read, Result as IoResult, Write},
+    marker::PhantomData,
+};
+
+use super::{
+    prepare_verifying_key, verify_proof, Parameters, PreparedVerifyingKey, Proof, VerifyingKey,
+};
+
+#[cfg(feature = "prover")]
+use super::{create_random_proof, generate_random_parameters};
+#[cfg(feature = "prover")]
+use r1cs_core::ConstraintSynthesizer;
+#[cfg(feature = "prover")]
+use rand::Rng;
+
+/// A marker of a circuit, usually the type of the circuit itself.
+pub trait CircuitMarker {
+    /// An identifier unique among the circuits of an application, e.g.
+    /// `"transfer-v2"`.
+    const CIRCUIT_ID: &'static str;
+}
+
+/// An artifact of Groth16 for the circuit `C`.
+pub struct ForCircuit<T, C: CircuitMarker> {
+    inner:    T,
+    _circuit: PhantomData<fn() -> C>,
+}
+
+/// Parameters generated for the circuit `C`.
+pub type CircuitParameters<E, C> = ForCircuit<Parameters<E>, C>;
+
+/// A verifying key of the circuit `C`.
+pub type CircuitVerifyingKey<E, C> = ForCircuit<VerifyingKey<E>, C>;
+
+/// A prepared verifying key of the circuit `C`.
+pub type CircuitPreparedVerifyingKey<E, C> = ForCircuit<PreparedVerifyingKey<E>, C>;
+
+/// A proof for the circuit `C`.
+pub type CircuitProof<E, C> = ForCircuit<Proof<E>, C>;
+
+impl<T, C: CircuitMarker> ForCircuit<T, C> {
+    /// Binds `inner` to the circuit `C`. The caller is responsible for `inner`
+    /// being generated for `C`.
+    pub fn new_unchecked(inner: T) -> Self {
+        Self { inner, _circuit: PhantomData }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<E: PairingEngine, C: CircuitMarker> CircuitParameters<E, C> {
+    pub fn vk(&self) -> CircuitVerifyingKey<E, C> {
+        ForCircuit::new_unchecked(self.inner.vk.clone())
+    }
+}
+
+impl<T: Clone, C: CircuitMarker> Clone for ForCircuit<T, C> {
+    fn clone(&self) -> Self {
+        Self::new_unchecked(self.inner.clone())
+    }
+}
+
+impl<T: PartialEq, C: CircuitMarker> PartialEq for ForCircuit<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T: fmt::Debug, C: CircuitMarker> fmt::Debug for ForCircuit<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForCircuit")
+            .field("circuit", &C::CIRCUIT_ID)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T: ToBytes, C: CircuitMarker> ToBytes for ForCircuit<T, C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (C::CIRCUIT_ID.len() as u32).write(&mut writer)?;
+        writer.write_all(C::CIRCUIT_ID.as_bytes())?;
+        self.inner.write(&mut writer)
+    }
+}
+
+impl<T: FromBytes, C: CircuitMarker> FromBytes for ForCircuit<T, C> {
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let len = u32::read(&mut reader)? as usize;
+        if len != C::CIRCUIT_ID.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("artifact of another circuit, expected `{}`", C::CIRCUIT_ID),
+            ));
+        }
+        let mut id = vec![0u8; len];
+        reader.read_exact(&mut id)?;
+        if id != C::CIRCUIT_ID.as_bytes() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "artifact of circuit `{}`, expected `{}`",
+                    String::from_utf8_lossy(&id),
+                    C::CIRCUIT_ID
+                ),
+            ));
+        }
+        Ok(Self::new_unchecked(T::read(&mut reader)?))
+    }
+}
+
+/// Same as `generate_random_parameters`, binding the parameters to `C`.
+#[cfg(feature = "prover")]
+pub fn generate_circuit_parameters<E, C, R>(
+    circuit: C,
+    rng: &mut R,
+) -> Result<CircuitParameters<E, C>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr> + CircuitMarker,
+    R: Rng,
+{
+    generate_random_parameters(circuit, rng).map(ForCircuit::new_unchecked)
+}
+
+/// Same as `create_random_proof`, for parameters of the same circuit.
+#[cfg(feature = "prover")]
+pub fn create_circuit_proof<E, C, R>(
+    circuit: C,
+    params: &CircuitParameters<E, C>,
+    rng: &mut R,
+) -> Result<CircuitProof<E, C>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr> + CircuitMarker,
+    R: Rng,
+{
+    create_random_proof(circuit, &params.inner, rng).map(ForCircuit::new_unchecked)
+}
+
+pub fn prepare_circuit_verifying_key<E: PairingEngine, C: CircuitMarker>(
+    vk: &CircuitVerifyingKey<E, C>,
+) -> CircuitPreparedVerifyingKey<E, C> {
+    ForCircuit::new_unchecked(prepare_verifying_key(&vk.inner))
+}
+
+/// Same as `verify_proof`, for a proof and a key of the same circuit.
+pub fn verify_circuit_proof<E: PairingEngine, C: CircuitMarker>(
+    pvk: &CircuitPreparedVerifyingKey<E, C>,
+    proof: &CircuitProof<E, C>,
+    public_inputs: &[E::Fr],
+) -> Result<bool, SynthesisError> {
+    verify_proof(&pvk.inner, &proof.inner, public_inputs)
+}
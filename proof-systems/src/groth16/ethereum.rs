@@ -0,0 +1,208 @@
+//! Encoding of Groth16 proofs over BLS12-381 in the format of the BLS12-381
+//! precompiles of Ethereum (EIP-2537), so that they can be checked by a contract
+//! with a multi-scalar multiplication of the public inputs and a single call to
+//! the pairing check precompile.
+//!
+//! A base field element is encoded as 64 bytes, big endian, the top 16 of which
+//! are zero; an element c0 + c1 * u of the quadratic extension as the encoding of
+//! c0 followed by the one of c1; a point as the encoding of its x coordinate
+//! followed by the one of its y coordinate, with the point at infinity encoded as
+//! all zeros. Scalars are encoded as 32 bytes, big endian.
+
+use algebra::{
+    curves::bls12_381::{Bls12_381, G1Affine, G2Affine},
+    fields::bls12_381::{Fq, Fq2, Fr},
+    to_bytes, AffineCurve, PrimeField, ProjectiveCurve, ToBytes,
+};
+
+use crate::groth16::{Parameters, Proof, SynthesisError};
+
+use std::ops::{AddAssign, Neg};
+
+/// The address of the BLS12_PAIRING_CHECK precompile.
+pub const PAIRING_CHECK_PRECOMPILE: u8 = 0x0f;
+
+/// The length of the encoding of a base field element.
+pub const FP_LEN: usize = 64;
+
+/// The length of the encoding of a point of G1.
+pub const G1_LEN: usize = 2 * FP_LEN;
+
+/// The length of the encoding of a point of G2.
+pub const G2_LEN: usize = 4 * FP_LEN;
+
+/// The length of the encoding of a scalar.
+pub const SCALAR_LEN: usize = 32;
+
+fn encode_big_endian<T: ToBytes>(value: T, len: usize, out: &mut Vec<u8>) {
+    let mut bytes = to_bytes![value].unwrap();
+    bytes.reverse();
+    out.extend(std::iter::repeat(0u8).take(len - bytes.len()));
+    out.extend_from_slice(&bytes);
+}
+
+fn encode_fq(fq: &Fq, out: &mut Vec<u8>) {
+    encode_big_endian(fq.into_repr(), FP_LEN, out);
+}
+
+fn encode_fq2(fq2: &Fq2, out: &mut Vec<u8>) {
+    encode_fq(&fq2.c0, out);
+    encode_fq(&fq2.c1, out);
+}
+
+fn encode_g1(point: &G1Affine, out: &mut Vec<u8>) {
+    if point.is_zero() {
+        out.extend_from_slice(&[0u8; G1_LEN]);
+    } else {
+        encode_fq(&point.x, out);
+        encode_fq(&point.y, out);
+    }
+}
+
+fn encode_g2(point: &G2Affine, out: &mut Vec<u8>) {
+    if point.is_zero() {
+        out.extend_from_slice(&[0u8; G2_LEN]);
+    } else {
+        encode_fq2(&point.x, out);
+        encode_fq2(&point.y, out);
+    }
+}
+
+/// Encodes `proof` as A, B and C, in this order.
+pub fn encode_proof(proof: &Proof<Bls12_381>) -> Vec<u8> {
+    let mut out = Vec::with_capacity(2 * G1_LEN + G2_LEN);
+    encode_g1(&proof.a, &mut out);
+    encode_g2(&proof.b, &mut out);
+    encode_g1(&proof.c, &mut out);
+    out
+}
+
+/// Encodes the verifying key of `params` as alpha in G1, beta, gamma and delta in
+/// G2, followed by the points of G1 to be combined with the public inputs, the
+/// first of which corresponds to the "one" input. As opposed to `VerifyingKey`,
+/// alpha and beta are needed separately, hence the full parameters.
+pub fn encode_verifying_key(params: &Parameters<Bls12_381>) -> Vec<u8> {
+    let mut out = Vec::with_capacity(
+        G1_LEN + 3 * G2_LEN + params.vk.gamma_abc_g1.len() * G1_LEN,
+    );
+    encode_g1(&params.alpha_g1, &mut out);
+    encode_g2(&params.beta_g2, &mut out);
+    encode_g2(&params.vk.gamma_g2, &mut out);
+    encode_g2(&params.vk.delta_g2, &mut out);
+    for point in params.vk.gamma_abc_g1.iter() {
+        encode_g1(point, &mut out);
+    }
+    out
+}
+
+/// Encodes the public inputs of a proof, as scalars.
+pub fn encode_public_inputs(public_inputs: &[Fr]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(public_inputs.len() * SCALAR_LEN);
+    for input in public_inputs.iter() {
+        encode_big_endian(input.into_repr(), SCALAR_LEN, &mut out);
+    }
+    out
+}
+
+// Returns the pairs (A, B), (-alpha, beta), (-sum_i x_i * gamma_abc_i, gamma) and
+// (-C, delta), whose product of pairings is one iff `proof` is valid.
+fn pairing_check_pairs(
+    params: &Parameters<Bls12_381>,
+    proof: &Proof<Bls12_381>,
+    public_inputs: &[Fr],
+) -> Result<Vec<(G1Affine, G2Affine)>, SynthesisError> {
+    if (public_inputs.len() + 1) != params.vk.gamma_abc_g1.len() {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+
+    let mut g_ic = params.vk.gamma_abc_g1[0].into_projective();
+    for (i, b) in public_inputs.iter().zip(params.vk.gamma_abc_g1.iter().skip(1)) {
+        g_ic.add_assign(&b.mul(*i));
+    }
+
+    Ok(vec![
+        (proof.a, proof.b),
+        (params.alpha_g1.neg(), params.beta_g2),
+        (g_ic.into_affine().neg(), params.vk.gamma_g2),
+        (proof.c.neg(), params.vk.delta_g2),
+    ])
+}
+
+/// Returns the input of the pairing check precompile verifying `proof` against
+/// `public_inputs`. The call returns one iff the proof is valid; a contract builds
+/// the same input, computing the combination of the public inputs on chain.
+pub fn encode_pairing_check(
+    params: &Parameters<Bls12_381>,
+    proof: &Proof<Bls12_381>,
+    public_inputs: &[Fr],
+) -> Result<Vec<u8>, SynthesisError> {
+    let pairs = pairing_check_pairs(params, proof, public_inputs)?;
+    let mut out = Vec::with_capacity(pairs.len() * (G1_LEN + G2_LEN));
+    for (g1, g2) in pairs.iter() {
+        encode_g1(g1, &mut out);
+        encode_g2(g2, &mut out);
+    }
+    Ok(out)
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use super::*;
+    use crate::groth16::{create_random_proof, generate_random_parameters, test::MySillyCircuit};
+    use algebra::{Field, PairingCurve, PairingEngine, UniformRand};
+    use rand::thread_rng;
+
+    #[test]
+    fn encode_generators() {
+        let mut g1 = vec![];
+        encode_g1(&G1Affine::prime_subgroup_generator(), &mut g1);
+        assert_eq!(
+            hex::encode(&g1),
+            "00000000000000000000000000000000\
+             17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb\
+             00000000000000000000000000000000\
+             08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+        );
+
+        let mut zero = vec![];
+        encode_g2(&G2Affine::zero(), &mut zero);
+        assert_eq!(zero, vec![0u8; G2_LEN]);
+    }
+
+    #[test]
+    fn encode_and_check_proof() {
+        let rng = &mut thread_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_381, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let c = a * &b;
+        let proof =
+            create_random_proof(MySillyCircuit { a: Some(a), b: Some(b) }, &params, rng).unwrap();
+
+        assert_eq!(encode_proof(&proof).len(), 2 * G1_LEN + G2_LEN);
+        assert_eq!(encode_verifying_key(&params).len(), G1_LEN + 3 * G2_LEN + 2 * G1_LEN);
+        assert_eq!(encode_public_inputs(&[c]).len(), SCALAR_LEN);
+        assert_eq!(
+            encode_pairing_check(&params, &proof, &[c]).unwrap().len(),
+            4 * (G1_LEN + G2_LEN)
+        );
+
+        let product_of_pairings = |public_inputs: &[Fr]| {
+            let prepared = pairing_check_pairs(&params, &proof, public_inputs)
+                .unwrap()
+                .iter()
+                .map(|(g1, g2)| (g1.prepare(), g2.prepare()))
+                .collect::<Vec<_>>();
+            let pairs = prepared.iter().map(|(g1, g2)| (g1, g2)).collect::<Vec<_>>();
+            Bls12_381::product_of_pairings(pairs.iter())
+        };
+        assert!(product_of_pairings(&[c]).is_one());
+        assert!(!product_of_pairings(&[a]).is_one());
+
+        assert!(encode_pairing_check(&params, &proof, &[]).is_err());
+    }
+}
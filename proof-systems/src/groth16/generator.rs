@@ -2,7 +2,7 @@ use algebra::{
     groups::Group, msm::FixedBaseMSM, Field, PairingEngine, PrimeField, ProjectiveCurve,
     UniformRand,
 };
-use algebra::fft::EvaluationDomain;
+use algebra::fft::GeneralEvaluationDomain;
 
 use r1cs_core::{
     ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
@@ -177,7 +177,7 @@ pub fn generate_parameters<E, C, R>(
     let domain_time = start_timer!(|| "Constructing evaluation domain");
 
     let domain_size = assembly.num_constraints + (assembly.num_inputs - 1) + 1;
-    let domain = EvaluationDomain::<E::Fr>::new(domain_size)
+    let domain = GeneralEvaluationDomain::<E::Fr>::new(domain_size)
         .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
     let t = domain.sample_element_outside_domain(rng);
 
@@ -1,28 +1,52 @@
 //! An implementation of the [Groth][Groth16] zkSNARK.
 //! [Groth16]: https://eprint.iacr.org/2016/260.pdf
 use algebra::{bytes::{
-    ToBytes, FromBytes,
+    ToBytes, FromBytes, FromBytesWithProfile, SerializationProfile,
 }, PairingCurve, PairingEngine};
 use r1cs_core::SynthesisError;
+use crate::versioning::Versioned;
 use std::io::{self, Read, Result as IoResult, Write};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
 /// Reduce an R1CS instance to a *Quadratic Arithmetic Program* instance.
+#[cfg(feature = "prover")]
 pub mod r1cs_to_qap;
 
 /// Generate public parameters for the Groth16 zkSNARK construction.
+#[cfg(feature = "prover")]
 pub mod generator;
 
 /// Create proofs for the Groth16 zkSNARK construction.
+#[cfg(feature = "prover")]
 pub mod prover;
 
+/// Create proofs for streams of circuits by a pipeline of the prover phases.
+#[cfg(feature = "prover")]
+pub mod pipeline;
+
 /// Verify proofs for the Groth16 zkSNARK construction.
 pub mod verifier;
 
-#[cfg(test)]
+/// Bind keys and proofs to the circuit they are generated for.
+pub mod circuit;
+
+/// Encode proofs over BLS12-381 for verification by the precompiles of Ethereum.
+pub mod ethereum;
+
+/// A fixed suite of micro-benchmarks to gate updates on performance regressions.
+#[cfg(feature = "prover")]
+pub mod regression;
+
+/// Generate the parameters of the Groth16 zkSNARK construction by a multi-party computation.
+#[cfg(feature = "ceremony")]
+pub mod ceremony;
+
+#[cfg(all(test, feature = "prover"))]
 mod test;
 
-pub use self::{generator::*, prover::*, verifier::*};
+#[cfg(feature = "prover")]
+pub use self::{generator::*, pipeline::*, prover::*};
+pub use self::{circuit::*, verifier::*};
 
 /// A proof in the Groth16 SNARK.
 #[derive(Clone, Debug)]
@@ -54,6 +78,15 @@ impl<E: PairingEngine> FromBytes for Proof<E> {
     }
 }
 
+impl<E: PairingEngine> FromBytesWithProfile for Proof<E> {
+    fn read_with_profile<R: Read>(mut reader: R, profile: SerializationProfile) -> IoResult<Self> {
+        let a = profile.read_point(&mut reader)?;
+        let b = profile.read_point(&mut reader)?;
+        let c = profile.read_point(&mut reader)?;
+        Ok(Proof{a, b, c})
+    }
+}
+
 impl<E: PairingEngine> PartialEq for Proof<E> {
     fn eq(&self, other: &Self) -> bool {
         self.a == other.a && self.b == other.b && self.c == other.c
@@ -131,6 +164,18 @@ impl<E: PairingEngine> FromBytes for VerifyingKey<E> {
     }
 }
 
+impl<E: PairingEngine> FromBytesWithProfile for VerifyingKey<E> {
+    fn read_with_profile<R: Read>(reader: R, profile: SerializationProfile) -> IoResult<Self> {
+        let vk = Self::read(reader)?;
+        profile.check_point(&vk.gamma_g2)?;
+        profile.check_point(&vk.delta_g2)?;
+        for point in vk.gamma_abc_g1.iter() {
+            profile.check_point(point)?;
+        }
+        Ok(vk)
+    }
+}
+
 
 impl<E: PairingEngine> Default for VerifyingKey<E> {
     fn default() -> Self {
@@ -238,6 +283,24 @@ impl<E: PairingEngine> FromBytes for Parameters<E>{
     }
 }
 
+impl<E: PairingEngine> Versioned for Proof<E> {
+    type Engine = E;
+    const MAGIC: [u8; 4] = *b"G16P";
+    const NAME: &'static str = "Groth16 proof";
+}
+
+impl<E: PairingEngine> Versioned for VerifyingKey<E> {
+    type Engine = E;
+    const MAGIC: [u8; 4] = *b"G16V";
+    const NAME: &'static str = "Groth16 verifying key";
+}
+
+impl<E: PairingEngine> Versioned for Parameters<E> {
+    type Engine = E;
+    const MAGIC: [u8; 4] = *b"G16K";
+    const NAME: &'static str = "Groth16 proving key";
+}
+
 /// Preprocessed verification key parameters that enable faster verification
 /// at the expense of larger size in memory.
 #[derive(Clone, Debug)]
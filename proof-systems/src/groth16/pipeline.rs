@@ -0,0 +1,107 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use algebra::{Field, PairingEngine, UniformRand};
+
+use crate::groth16::{
+    compute_proof, compute_witness, synthesize, Parameters, Proof, ProverWitness,
+    ProvingAssignment,
+};
+
+use r1cs_core::{ConstraintSynthesizer, SynthesisError};
+
+use std::{
+    sync::{
+        mpsc::{sync_channel, Receiver},
+        Arc,
+    },
+    thread,
+};
+
+/// The number of jobs buffered between two consecutive phases of the
+/// pipeline. Bounds the memory held by the pipeline to a few witnesses.
+const PIPELINE_DEPTH: usize = 1;
+
+type Synthesized<E> = (ProvingAssignment<E>, <E as PairingEngine>::Fr, <E as PairingEngine>::Fr);
+type Mapped<E> = (ProverWitness<E>, <E as PairingEngine>::Fr, <E as PairingEngine>::Fr);
+
+/// The proofs created by `prove_stream`, in the order of their circuits.
+pub struct ProofStream<E: PairingEngine> {
+    receiver: Receiver<Result<Proof<E>, SynthesisError>>,
+}
+
+impl<E: PairingEngine> Iterator for ProofStream<E> {
+    type Item = Result<Proof<E>, SynthesisError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// Creates proofs for a stream of circuits by a pipeline of the three phases
+/// of the prover: the synthesis of the circuit, the witness map with its
+/// FFTs, and the MSMs. Each phase runs on its own thread and hands over its
+/// results to the next one, so that the synthesis of a circuit overlaps with
+/// the FFTs of the previous one and the MSMs of the one before, while the
+/// parallel parts of all phases share the work-stealing thread pool of rayon.
+///
+/// The proofs are returned as they are completed; a circuit which fails to
+/// be proven yields an error in its place, without stopping the stream.
+/// Dropping the stream stops the pipeline after the circuits in flight.
+pub fn prove_stream<E, C, I, R>(
+    circuits: I,
+    params: Arc<Parameters<E>>,
+    rng: &mut R,
+) -> ProofStream<E>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr> + Send + 'static,
+    I: IntoIterator<Item = C>,
+    I::IntoIter: Send + 'static,
+    R: Rng,
+{
+    let circuits = circuits.into_iter();
+    let mut stream_rng = StdRng::from_seed(rng.gen());
+
+    let (synthesized_sender, synthesized_receiver) =
+        sync_channel::<Result<Synthesized<E>, SynthesisError>>(PIPELINE_DEPTH);
+    let (mapped_sender, mapped_receiver) =
+        sync_channel::<Result<Mapped<E>, SynthesisError>>(PIPELINE_DEPTH);
+    let (proof_sender, proof_receiver) = sync_channel(PIPELINE_DEPTH);
+
+    thread::spawn(move || {
+        for circuit in circuits {
+            let r = E::Fr::rand(&mut stream_rng);
+            let s = E::Fr::rand(&mut stream_rng);
+            let synthesized = synthesize::<E, C>(circuit).map(|prover| (prover, r, s));
+            if synthesized_sender.send(synthesized).is_err() {
+                break;
+            }
+        }
+    });
+
+    thread::spawn(move || {
+        let zero = E::Fr::zero();
+        for synthesized in synthesized_receiver {
+            let mapped = synthesized.and_then(|(prover, r, s)| {
+                compute_witness(&prover, &zero, &zero, &zero).map(|witness| (witness, r, s))
+            });
+            if mapped_sender.send(mapped).is_err() {
+                break;
+            }
+        }
+    });
+
+    thread::spawn(move || {
+        for mapped in mapped_receiver {
+            let proof =
+                mapped.and_then(|(witness, r, s)| compute_proof(&params, &witness, r, s));
+            if proof_sender.send(proof).is_err() {
+                break;
+            }
+        }
+    });
+
+    ProofStream {
+        receiver: proof_receiver,
+    }
+}
@@ -1,4 +1,5 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
 use rayon::prelude::*;
 
 use algebra::{
@@ -6,15 +7,17 @@ use algebra::{
     ProjectiveCurve, UniformRand,
 };
 
-use crate::groth16::{r1cs_to_qap::R1CStoQAP, Parameters, Proof};
+use crate::groth16::{generator::KeypairAssembly, r1cs_to_qap::R1CStoQAP, Parameters, Proof};
 
 use r1cs_core::{
-    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SharedWitness,
+    SynthesisError, Variable, WitnessReader, WitnessReplay, WitnessSource,
 };
 
 use smallvec::SmallVec;
 
 use std::{
+    io::Read,
     ops::{AddAssign, MulAssign, SubAssign},
     sync::Arc,
 };
@@ -76,6 +79,22 @@ pub struct ProvingAssignment<E: PairingEngine> {
 }
 
 impl<E: PairingEngine> ProvingAssignment<E> {
+    fn new() -> Self {
+        ProvingAssignment {
+            at:               vec![],
+            bt:               vec![],
+            ct:               vec![],
+            a:                vec![],
+            b:                vec![],
+            c:                vec![],
+            input_assignment: vec![],
+            aux_assignment:   vec![],
+            num_inputs:       0,
+            num_aux:          0,
+            num_constraints:  0,
+        }
+    }
+
     pub fn which_is_unsatisfied(&self) -> Option<usize> {
         for (i, ((a_i, b_i), c_i)) in (self.a.iter().zip(self.b.iter()))
             .zip(self.c.iter())
@@ -198,6 +217,61 @@ where
     create_proof::<E, C>(circuit, params, d1, d2, d3, r, s)
 }
 
+/// How the prover samples the randomness `r`, `s` blinding a proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProverMode {
+    /// `r` and `s` are sampled from the RNG of the prover. The default, and the
+    /// only mode yielding zero-knowledge proofs in general.
+    Random,
+    /// `r` and `s` are derived from the seed, so that the same circuit, witness
+    /// and seed yield the same proof bit-for-bit, e.g. to reproduce a failure in
+    /// CI. The proofs are zero-knowledge only as long as the seed is secret and
+    /// never reused.
+    Deterministic([u8; 32]),
+    /// `r = s = 0`: the proofs are not blinded, and leak information about the
+    /// witness. For benchmarks and tests only.
+    NoBlinding,
+}
+
+impl Default for ProverMode {
+    fn default() -> Self {
+        ProverMode::Random
+    }
+}
+
+impl ProverMode {
+    /// Returns the blinding factors `(r, s)` of a proof in this mode. `rng` is
+    /// used in `Random` mode only.
+    pub fn blinding_factors<F: PrimeField, R: Rng>(&self, rng: &mut R) -> (F, F) {
+        match self {
+            ProverMode::Random => (F::rand(rng), F::rand(rng)),
+            ProverMode::Deterministic(seed) => {
+                let rng = &mut ChaChaRng::from_seed(*seed);
+                (F::rand(rng), F::rand(rng))
+            },
+            ProverMode::NoBlinding => (F::zero(), F::zero()),
+        }
+    }
+}
+
+/// Same as `create_random_proof`, with the blinding of the proof chosen by `mode`.
+pub fn create_proof_with_mode<E, C, R>(
+    circuit: C,
+    params: &Parameters<E>,
+    mode: ProverMode,
+    rng: &mut R,
+) -> Result<Proof<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+    R: Rng,
+{
+    let zero = E::Fr::zero();
+    let (r, s) = mode.blinding_factors(rng);
+
+    create_proof::<E, C>(circuit, params, zero, zero, zero, r, s)
+}
+
 pub fn create_proof<E, C>(
     circuit: C,
     params: &Parameters<E>,
@@ -212,19 +286,35 @@ where
     C: ConstraintSynthesizer<E::Fr>,
 {
     let prover_time = start_timer!(|| "Prover");
-    let mut prover = ProvingAssignment {
-        at:               vec![],
-        bt:               vec![],
-        ct:               vec![],
-        a:                vec![],
-        b:                vec![],
-        c:                vec![],
-        input_assignment: vec![],
-        aux_assignment:   vec![],
-        num_inputs:       0,
-        num_aux:          0,
-        num_constraints:  0,
-    };
+
+    let prover = synthesize::<E, C>(circuit)?;
+    let witness = compute_witness(&prover, &d1, &d2, &d3)?;
+    drop(prover);
+    let proof = compute_proof(params, &witness, r, s);
+
+    end_timer!(prover_time);
+    proof
+}
+
+/// The witness of a synthesized circuit and the coefficients of its h
+/// polynomial, as the scalars of the multi-scalar multiplications of the
+/// prover.
+pub struct ProverWitness<E: PairingEngine> {
+    num_inputs:         usize,
+    input_assignment:   Arc<Vec<<E::Fr as PrimeField>::BigInt>>,
+    aux_assignment:     Arc<Vec<<E::Fr as PrimeField>::BigInt>>,
+    h_input_assignment: Arc<Vec<<E::Fr as PrimeField>::BigInt>>,
+    h_aux_assignment:   Arc<Vec<<E::Fr as PrimeField>::BigInt>>,
+}
+
+/// First phase of the prover: synthesizes `circuit`, evaluating the
+/// linear combinations of its constraints on the assignment.
+pub fn synthesize<E, C>(circuit: C) -> Result<ProvingAssignment<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+{
+    let mut prover = ProvingAssignment::new();
 
     // Allocate the "one" input variable
     prover.alloc_input(|| "", || Ok(E::Fr::one()))?;
@@ -234,19 +324,324 @@ where
     circuit.generate_constraints(&mut prover)?;
     end_timer!(synthesis_time);
 
+    Ok(prover)
+}
+
+/// Same as `synthesize`, but assigns the variables of `circuit` from a witness
+/// previously written by a `WitnessWriter` or in the shared memory layout, taken
+/// from `witness` in allocation order, instead of computing them. The circuit may
+/// therefore be synthesized without values.
+pub fn synthesize_from_witness<E, C, S>(
+    circuit: C,
+    witness: &mut S,
+) -> Result<ProvingAssignment<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+    S: WitnessSource<E::Fr>,
+{
+    let mut prover = ProvingAssignment::new();
+
+    let synthesis_time = start_timer!(|| "Constraint synthesis from witness");
+    {
+        let mut replay = WitnessReplay::new(&mut prover, witness);
+
+        // Allocate the "one" input variable
+        replay.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+        // Synthesize the circuit.
+        circuit.generate_constraints(&mut replay)?;
+    }
+    end_timer!(synthesis_time);
+
+    Ok(prover)
+}
+
+/// Creates a proof for `circuit` whose assignment is read from `witness`, see
+/// `synthesize_from_witness`. The whole witness must be consumed by the circuit.
+/// The witness is read a chunk at a time, but the assignment is collected by the
+/// prover, whose memory use is therefore the same as for `create_proof`.
+pub fn create_proof_from_witness<E, C, R>(
+    circuit: C,
+    witness: R,
+    params: &Parameters<E>,
+    r: E::Fr,
+    s: E::Fr,
+) -> Result<Proof<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+    R: Read,
+{
+    let prover_time = start_timer!(|| "Prover from witness");
+
+    let mut witness = WitnessReader::new(witness)?;
+    let prover = synthesize_from_witness::<E, C, _>(circuit, &mut witness)?;
+    witness.finish()?;
+
+    let zero = E::Fr::zero();
+    let witness = compute_witness(&prover, &zero, &zero, &zero)?;
+    drop(prover);
+    let proof = compute_proof(params, &witness, r, s);
+
+    end_timer!(prover_time);
+    proof
+}
+
+/// Same as `create_proof_from_witness`, for a witness in the shared memory layout,
+/// e.g. the one of a `MappedWitness` handed over by a witness generator running in
+/// another process. The values are read in place, without copying the witness.
+pub fn create_proof_from_shared_witness<E, C>(
+    circuit: C,
+    mut witness: SharedWitness<E::Fr>,
+    params: &Parameters<E>,
+    r: E::Fr,
+    s: E::Fr,
+) -> Result<Proof<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+{
+    let prover_time = start_timer!(|| "Prover from shared witness");
+
+    let prover = synthesize_from_witness::<E, C, _>(circuit, &mut witness)?;
+    witness.finish()?;
+
+    let zero = E::Fr::zero();
+    let witness = compute_witness(&prover, &zero, &zero, &zero)?;
+    drop(prover);
+    let proof = compute_proof(params, &witness, r, s);
+
+    end_timer!(prover_time);
+    proof
+}
+
+/// The constraints of a circuit, synthesized once by `synthesize_constraints`,
+/// so that subsequent proofs only run the circuit to assign its variables, see
+/// `create_proof_with_matrices`.
+pub struct ConstraintMatrices<E: PairingEngine> {
+    assembly: KeypairAssembly<E>,
+}
+
+impl<E: PairingEngine> ConstraintMatrices<E> {
+    pub fn num_constraints(&self) -> usize {
+        self.assembly.num_constraints
+    }
+
+    /// Returns the number of input variables, including the constant one.
+    pub fn num_inputs(&self) -> usize {
+        self.assembly.num_inputs
+    }
+
+    pub fn num_aux(&self) -> usize {
+        self.assembly.num_aux
+    }
+}
+
+/// Synthesizes the constraints of `circuit`, which may have no assignment.
+pub fn synthesize_constraints<E, C>(circuit: C) -> Result<ConstraintMatrices<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+{
+    let mut assembly = KeypairAssembly {
+        num_inputs:      0,
+        num_aux:         0,
+        num_constraints: 0,
+        at:              vec![],
+        bt:              vec![],
+        ct:              vec![],
+    };
+
+    // Allocate the "one" input variable
+    assembly.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+    let synthesis_time = start_timer!(|| "Constraint synthesis");
+    circuit.generate_constraints(&mut assembly)?;
+    end_timer!(synthesis_time);
+
+    Ok(ConstraintMatrices { assembly })
+}
+
+/// A constraint system computing the assignment of a circuit only, ignoring its
+/// constraints.
+struct WitnessAssignment<E: PairingEngine> {
+    input_assignment: Vec<E::Fr>,
+    aux_assignment:   Vec<E::Fr>,
+    num_constraints:  usize,
+}
+
+impl<E: PairingEngine> ConstraintSystem<E::Fr> for WitnessAssignment<E> {
+    type Root = Self;
+
+    #[inline]
+    fn alloc<F, A, AR>(&mut self, _: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<E::Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.aux_assignment.len();
+        self.aux_assignment.push(f()?);
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
+
+    #[inline]
+    fn alloc_input<F, A, AR>(&mut self, _: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<E::Fr, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.input_assignment.len();
+        self.input_assignment.push(f()?);
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    #[inline]
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, _: LA, _: LB, _: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<E::Fr>) -> LinearCombination<E::Fr>,
+        LB: FnOnce(LinearCombination<E::Fr>) -> LinearCombination<E::Fr>,
+        LC: FnOnce(LinearCombination<E::Fr>) -> LinearCombination<E::Fr>,
+    {
+        // The constraints are taken from the matrices, so that the linear
+        // combinations are not even constructed.
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn pop_namespace(&mut self) {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+}
+
+/// Same as `create_random_proof`, for a circuit whose constraints are `matrices`.
+pub fn create_random_proof_with_matrices<E, C, R>(
+    circuit: C,
+    matrices: &ConstraintMatrices<E>,
+    params: &Parameters<E>,
+    rng: &mut R,
+) -> Result<Proof<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+    R: Rng,
+{
+    let r = E::Fr::rand(rng);
+    let s = E::Fr::rand(rng);
+
+    create_proof_with_matrices::<E, C>(circuit, matrices, params, r, s)
+}
+
+/// Same as `create_proof`, for a circuit whose constraints were synthesized by
+/// `synthesize_constraints`: the circuit is only run to assign its variables,
+/// without constructing the linear combinations of its constraints.
+///
+/// The constraints of the circuit must not depend on its assignment. Fails with
+/// `SynthesisError::Unsatisfiable` if the numbers of variables or constraints of
+/// the circuit differ from the ones of `matrices`.
+pub fn create_proof_with_matrices<E, C>(
+    circuit: C,
+    matrices: &ConstraintMatrices<E>,
+    params: &Parameters<E>,
+    r: E::Fr,
+    s: E::Fr,
+) -> Result<Proof<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+{
+    let prover_time = start_timer!(|| "Prover with constraint matrices");
+
+    let mut assignment = WitnessAssignment::<E> {
+        input_assignment: vec![],
+        aux_assignment:   vec![],
+        num_constraints:  0,
+    };
+
+    // Allocate the "one" input variable
+    assignment.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+    let synthesis_time = start_timer!(|| "Witness assignment");
+    circuit.generate_constraints(&mut assignment)?;
+    end_timer!(synthesis_time);
+
+    if assignment.input_assignment.len() != matrices.num_inputs()
+        || assignment.aux_assignment.len() != matrices.num_aux()
+        || assignment.num_constraints != matrices.num_constraints()
+    {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let witness_map_time = start_timer!(|| "R1CS to QAP witness map");
+    let zero = E::Fr::zero();
+    let (full_input_assignment, h, _) = R1CStoQAP::witness_map_from_matrices::<E, _>(
+        &matrices.assembly.at,
+        &matrices.assembly.bt,
+        &matrices.assembly.ct,
+        &assignment.input_assignment,
+        &assignment.aux_assignment,
+        &zero,
+        &zero,
+        &zero,
+    )?;
+    end_timer!(witness_map_time);
+    drop(assignment);
+
+    let witness = prover_witness(full_input_assignment, h, matrices.num_inputs());
+    let proof = compute_proof(params, &witness, r, s);
+
+    end_timer!(prover_time);
+    proof
+}
+
+/// Second phase of the prover: maps the assignment to the QAP, computing the
+/// h polynomial by FFTs.
+pub fn compute_witness<E: PairingEngine>(
+    prover: &ProvingAssignment<E>,
+    d1: &E::Fr,
+    d2: &E::Fr,
+    d3: &E::Fr,
+) -> Result<ProverWitness<E>, SynthesisError> {
     let witness_map_time = start_timer!(|| "R1CS to QAP witness map");
-    let (full_input_assignment, h, _) = R1CStoQAP::witness_map::<E>(&prover, &d1, &d2, &d3)?;
+    let (full_input_assignment, h, _) = R1CStoQAP::witness_map::<E>(prover, d1, d2, d3)?;
     end_timer!(witness_map_time);
 
+    Ok(prover_witness(full_input_assignment, h, prover.num_inputs))
+}
+
+fn prover_witness<E: PairingEngine>(
+    full_input_assignment: Vec<E::Fr>,
+    h: Vec<E::Fr>,
+    num_inputs: usize,
+) -> ProverWitness<E> {
     let input_assignment = Arc::new(
-        full_input_assignment[1..prover.num_inputs]
+        full_input_assignment[1..num_inputs]
             .into_iter()
             .map(|s| s.into_repr())
             .collect::<Vec<_>>(),
     );
 
     let aux_assignment = Arc::new(
-        full_input_assignment[prover.num_inputs..]
+        full_input_assignment[num_inputs..]
             .into_par_iter()
             .map(|s| s.into_repr())
             .collect::<Vec<_>>(),
@@ -254,76 +649,122 @@ where
     drop(full_input_assignment);
 
     let h_input_assignment = Arc::new(
-        h[0..prover.num_inputs]
+        h[0..num_inputs]
             .into_iter()
             .map(|s| s.into_repr())
             .collect::<Vec<_>>(),
     );
     let h_aux_assignment = Arc::new(
-        h[prover.num_inputs..]
+        h[num_inputs..]
             .into_par_iter()
             .map(|s| s.into_repr())
             .collect::<Vec<_>>(),
     );
     drop(h);
 
-    // Compute A
-    let a_acc_time = start_timer!(|| "Compute A");
-    let (a_inputs_source, a_aux_source) = params.get_a_query(prover.num_inputs)?;
-    let a_inputs_acc = VariableBaseMSM::multi_scalar_mul(a_inputs_source, &input_assignment);
-    let a_aux_acc = VariableBaseMSM::multi_scalar_mul(a_aux_source, &aux_assignment);
+    ProverWitness {
+        num_inputs,
+        input_assignment,
+        aux_assignment,
+        h_input_assignment,
+        h_aux_assignment,
+    }
+}
 
+/// Third phase of the prover: computes the proof elements by multi-scalar
+/// multiplications. The MSMs of A, B in G1, B in G2 and C are independent of
+/// each other and run concurrently.
+pub fn compute_proof<E: PairingEngine>(
+    params: &Parameters<E>,
+    witness: &ProverWitness<E>,
+    r: E::Fr,
+    s: E::Fr,
+) -> Result<Proof<E>, SynthesisError> {
+    let ProverWitness {
+        num_inputs,
+        input_assignment,
+        aux_assignment,
+        h_input_assignment,
+        h_aux_assignment,
+    } = witness;
+
+    let (a_inputs_source, a_aux_source) = params.get_a_query(*num_inputs)?;
+    let (b_g1_inputs_source, b_g1_aux_source) = params.get_b_g1_query(*num_inputs)?;
+    let (b_g2_inputs_source, b_g2_aux_source) = params.get_b_g2_query(*num_inputs)?;
+    let (h_inputs_source, h_aux_source) = params.get_h_query(*num_inputs)?;
+    let l_aux_source = params.get_l_query_full()?;
+    let a_query_zero = params.get_a_query_full()?[0];
+    let b_g1_query_zero = params.get_b_g1_query_full()?[0];
+    let b_g2_query_zero = params.get_b_g2_query_full()?[0];
+
+    let ((a_acc, b_g1_acc), (b_g2_acc, c_acc)) = rayon::join(
+        || rayon::join(
+            || {
+                let a_acc_time = start_timer!(|| "Compute A");
+                let a_inputs_acc =
+                    VariableBaseMSM::multi_scalar_mul(a_inputs_source, input_assignment);
+                let a_aux_acc = VariableBaseMSM::multi_scalar_mul(a_aux_source, aux_assignment);
+                end_timer!(a_acc_time);
+                a_inputs_acc + &a_aux_acc
+            },
+            || {
+                let b_g1_acc_time = start_timer!(|| "Compute B in G1");
+                let b_inputs_acc =
+                    VariableBaseMSM::multi_scalar_mul(b_g1_inputs_source, input_assignment);
+                let b_aux_acc =
+                    VariableBaseMSM::multi_scalar_mul(b_g1_aux_source, aux_assignment);
+                end_timer!(b_g1_acc_time);
+                b_inputs_acc + &b_aux_acc
+            },
+        ),
+        || rayon::join(
+            || {
+                let b_g2_acc_time = start_timer!(|| "Compute B in G2");
+                let b_inputs_acc =
+                    VariableBaseMSM::multi_scalar_mul(b_g2_inputs_source, input_assignment);
+                let b_aux_acc =
+                    VariableBaseMSM::multi_scalar_mul(b_g2_aux_source, aux_assignment);
+                end_timer!(b_g2_acc_time);
+                b_inputs_acc + &b_aux_acc
+            },
+            || {
+                let c_acc_time = start_timer!(|| "Compute C");
+                let h_inputs_acc =
+                    VariableBaseMSM::multi_scalar_mul(h_inputs_source, h_input_assignment);
+                let h_aux_acc =
+                    VariableBaseMSM::multi_scalar_mul(h_aux_source, h_aux_assignment);
+                let l_aux_acc = VariableBaseMSM::multi_scalar_mul(l_aux_source, aux_assignment);
+                end_timer!(c_acc_time);
+                h_inputs_acc + &h_aux_acc + &l_aux_acc
+            },
+        ),
+    );
+
+    // Compute A
     let r_g1 = params.delta_g1.mul(r);
 
     let mut g_a = r_g1;
-    g_a.add_assign(&params.get_a_query_full()?[0].into_projective());
-    g_a.add_assign(&a_inputs_acc);
-    g_a.add_assign(&a_aux_acc);
+    g_a.add_assign(&a_query_zero.into_projective());
+    g_a.add_assign(&a_acc);
     g_a.add_assign(&params.alpha_g1.into());
-    end_timer!(a_acc_time);
 
     // Compute B in G1
-    let b_g1_acc_time = start_timer!(|| "Compute B in G1");
-
-    let (b_inputs_source, b_aux_source) = params.get_b_g1_query(prover.num_inputs)?;
-    let b_inputs_acc = VariableBaseMSM::multi_scalar_mul(b_inputs_source, &input_assignment);
-    let b_aux_acc = VariableBaseMSM::multi_scalar_mul(b_aux_source, &aux_assignment);
-
     let s_g1 = params.delta_g1.mul(s.clone());
 
     let mut g1_b = s_g1;
-    g1_b.add_assign(&params.get_b_g1_query_full()?[0].into_projective());
-    g1_b.add_assign(&b_inputs_acc);
-    g1_b.add_assign(&b_aux_acc);
+    g1_b.add_assign(&b_g1_query_zero.into_projective());
+    g1_b.add_assign(&b_g1_acc);
     g1_b.add_assign(&params.beta_g1.into());
-    end_timer!(b_g1_acc_time);
 
     // Compute B in G2
-    let b_g2_acc_time = start_timer!(|| "Compute B in G2");
-
-    let (b_inputs_source, b_aux_source) = params.get_b_g2_query(prover.num_inputs)?;
-    let b_inputs_acc = VariableBaseMSM::multi_scalar_mul(b_inputs_source, &input_assignment);
-    let b_aux_acc = VariableBaseMSM::multi_scalar_mul(b_aux_source, &aux_assignment);
-
     let s_g2 = params.delta_g2.mul(s.clone());
 
     let mut g2_b = s_g2;
-    g2_b.add_assign(&params.get_b_g2_query_full()?[0].into_projective());
-    g2_b.add_assign(&b_inputs_acc);
-    g2_b.add_assign(&b_aux_acc);
+    g2_b.add_assign(&b_g2_query_zero.into_projective());
+    g2_b.add_assign(&b_g2_acc);
     g2_b.add_assign(&params.beta_g2.into());
-    end_timer!(b_g2_acc_time);
 
     // Compute C
-    let c_acc_time = start_timer!(|| "Compute C");
-
-    let (h_inputs_source, h_aux_source) = params.get_h_query(prover.num_inputs)?;
-    let h_inputs_acc = VariableBaseMSM::multi_scalar_mul(h_inputs_source, &h_input_assignment);
-    let h_aux_acc = VariableBaseMSM::multi_scalar_mul(h_aux_source, &h_aux_assignment);
-
-    let l_aux_source = params.get_l_query_full()?;
-    let l_aux_acc = VariableBaseMSM::multi_scalar_mul(l_aux_source, &aux_assignment);
-
     let s_g_a = g_a.clone().mul(&s);
     let r_g1_b = g1_b.clone().mul(&r);
     let r_s_delta_g1 = params.delta_g1.into_projective().mul(&r).mul(&s);
@@ -331,12 +772,7 @@ where
     let mut g_c = s_g_a;
     g_c.add_assign(&r_g1_b);
     g_c.sub_assign(&r_s_delta_g1);
-    g_c.add_assign(&l_aux_acc);
-    g_c.add_assign(&h_inputs_acc);
-    g_c.add_assign(&h_aux_acc);
-    end_timer!(c_acc_time);
-
-    end_timer!(prover_time);
+    g_c.add_assign(&c_acc);
 
     Ok(Proof {
         a: g_a.into_affine(),
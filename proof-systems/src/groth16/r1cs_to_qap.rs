@@ -1,5 +1,5 @@
 use algebra::{Field, PairingEngine};
-use algebra::fft::EvaluationDomain;
+use algebra::fft::GeneralEvaluationDomain;
 
 use crate::groth16::{generator::KeypairAssembly, prover::ProvingAssignment};
 use r1cs_core::{Index, SynthesisError};
@@ -16,7 +16,7 @@ impl R1CStoQAP {
         t: &E::Fr,
     ) -> Result<(Vec<E::Fr>, Vec<E::Fr>, Vec<E::Fr>, E::Fr, usize, usize), SynthesisError> {
         let domain_size = assembly.num_constraints + (assembly.num_inputs - 1) + 1;
-        let domain = EvaluationDomain::<E::Fr>::new(domain_size)
+        let domain = GeneralEvaluationDomain::<E::Fr>::new(domain_size)
             .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
         let domain_size = domain.size();
 
@@ -74,6 +74,34 @@ impl R1CStoQAP {
         d2: &E::Fr,
         d3: &E::Fr,
     ) -> Result<(Vec<E::Fr>, Vec<E::Fr>, usize), SynthesisError> {
+        Self::witness_map_from_matrices::<E, _>(
+            &prover.at,
+            &prover.bt,
+            &prover.ct,
+            &prover.input_assignment,
+            &prover.aux_assignment,
+            d1,
+            d2,
+            d3,
+        )
+    }
+
+    /// Same as `witness_map`, for the constraints `at`, `bt`, `ct` and the
+    /// assignment of their variables.
+    pub(crate) fn witness_map_from_matrices<E, T>(
+        at: &[T],
+        bt: &[T],
+        ct: &[T],
+        input_assignment: &[E::Fr],
+        aux_assignment: &[E::Fr],
+        d1: &E::Fr,
+        d2: &E::Fr,
+        d3: &E::Fr,
+    ) -> Result<(Vec<E::Fr>, Vec<E::Fr>, usize), SynthesisError>
+    where
+        E: PairingEngine,
+        T: AsRef<[(E::Fr, Index)]> + Sync,
+    {
         #[inline]
         fn evaluate_constraint<E: PairingEngine>(
             terms: &[(E::Fr, Index)],
@@ -94,28 +122,30 @@ impl R1CStoQAP {
         let zero = E::Fr::zero();
         let one = E::Fr::one();
 
-        let mut full_input_assignment = prover.input_assignment.clone();
-        full_input_assignment.extend(prover.aux_assignment.clone());
+        let num_inputs = input_assignment.len();
+        let num_constraints = at.len();
+
+        let mut full_input_assignment = input_assignment.to_vec();
+        full_input_assignment.extend_from_slice(aux_assignment);
 
-        let domain =
-            EvaluationDomain::<E::Fr>::new(prover.num_constraints + (prover.num_inputs - 1) + 1)
-                .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        let domain = GeneralEvaluationDomain::<E::Fr>::new(num_constraints + (num_inputs - 1) + 1)
+            .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
         let domain_size = domain.size();
 
         let mut a = vec![zero; domain_size];
         let mut b = vec![zero; domain_size];
-        a[..prover.num_constraints]
+        a[..num_constraints]
             .par_iter_mut()
-            .zip(b[..prover.num_constraints].par_iter_mut())
-            .zip((&prover.at).par_iter())
-            .zip((&prover.bt).par_iter())
+            .zip(b[..num_constraints].par_iter_mut())
+            .zip(at.par_iter())
+            .zip(bt.par_iter())
             .for_each(|(((a, b), at_i), bt_i)| {
-                *a = evaluate_constraint::<E>(&at_i, &full_input_assignment, prover.num_inputs);
-                *b = evaluate_constraint::<E>(&bt_i, &full_input_assignment, prover.num_inputs);
+                *a = evaluate_constraint::<E>(at_i.as_ref(), &full_input_assignment, num_inputs);
+                *b = evaluate_constraint::<E>(bt_i.as_ref(), &full_input_assignment, num_inputs);
             });
 
-        for i in 0..prover.num_inputs {
-            a[prover.num_constraints + i] = if i > 0 { full_input_assignment[i] } else { one };
+        for i in 0..num_inputs {
+            a[num_constraints + i] = if i > 0 { full_input_assignment[i] } else { one };
         }
 
         domain.ifft_in_place(&mut a);
@@ -139,15 +169,11 @@ impl R1CStoQAP {
         drop(b);
 
         let mut c = vec![zero; domain_size];
-        c[..prover.num_constraints]
+        c[..num_constraints]
             .par_iter_mut()
             .enumerate()
             .for_each(|(i, c)| {
-                *c = evaluate_constraint::<E>(
-                    &prover.ct[i],
-                    &full_input_assignment,
-                    prover.num_inputs,
-                );
+                *c = evaluate_constraint::<E>(ct[i].as_ref(), &full_input_assignment, num_inputs);
             });
 
         domain.ifft_in_place(&mut c);
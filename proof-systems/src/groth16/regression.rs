@@ -0,0 +1,148 @@
+//! A fixed suite of micro-benchmarks of the operations dominating the cost of
+//! Groth16 over BLS12-381, to be compared against a baseline by the harness of
+//! `bench_utils::regression`. Downstream projects record a baseline on their CI
+//! machines once, e.g. with `Baseline::from_measurements(&run_micro_benchmarks(..))`,
+//! and gate later updates on `check_regressions`.
+
+use algebra::{
+    curves::bls12_381::{Bls12_381, G1Projective, G2Projective},
+    fields::bls12_381::Fr,
+    fft::EvaluationDomain,
+    msm::VariableBaseMSM,
+    Field, PrimeField, ProjectiveCurve, UniformRand,
+};
+use bench_utils::regression::{
+    black_box, run_suite, Baseline, Benchmark, Measurement, RegressionReport, SuiteConfig,
+};
+use r1cs_core::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::groth16::{create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof};
+
+use std::ops::{AddAssign, MulAssign};
+
+const MSM_SIZE: usize = 1 << 12;
+const FFT_SIZE: usize = 1 << 14;
+const NUM_CONSTRAINTS: usize = 1 << 10;
+
+// Proves the knowledge of x such that x^(2^NUM_CONSTRAINTS) = y, for public y.
+struct RepeatedSquaring {
+    x: Option<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for RepeatedSquaring {
+    fn generate_constraints<CS: ConstraintSystem<Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let mut value = self.x;
+        let mut var = cs.alloc(|| "x", || value.ok_or(SynthesisError::AssignmentMissing))?;
+        for i in 0..NUM_CONSTRAINTS {
+            value = value.map(|v| v.square());
+            let next = if i == NUM_CONSTRAINTS - 1 {
+                cs.alloc_input(|| "y", || value.ok_or(SynthesisError::AssignmentMissing))?
+            } else {
+                cs.alloc(
+                    || format!("x^(2^{})", i + 1),
+                    || value.ok_or(SynthesisError::AssignmentMissing),
+                )?
+            };
+            cs.enforce(
+                || format!("square {}", i),
+                |lc| lc + var,
+                |lc| lc + var,
+                |lc| lc + next,
+            );
+            var = next;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the benchmarks of the suite. Their inputs are sampled from a fixed
+/// seed, so that the same computations are timed across runs.
+pub fn micro_benchmarks() -> Vec<Benchmark> {
+    let rng = &mut StdRng::seed_from_u64(0x6769_6e67_6572u64);
+
+    let (a, b) = (Fr::rand(rng), Fr::rand(rng));
+    let (p, q) = (G1Projective::rand(rng), G1Projective::rand(rng));
+    let p2 = G2Projective::rand(rng);
+    let s = Fr::rand(rng);
+
+    let bases = (0..MSM_SIZE)
+        .map(|_| G1Projective::rand(rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..MSM_SIZE)
+        .map(|_| Fr::rand(rng).into_repr())
+        .collect::<Vec<_>>();
+
+    let domain = EvaluationDomain::<Fr>::new(FFT_SIZE).unwrap();
+    let coeffs = (0..FFT_SIZE).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+    let x = Fr::rand(rng);
+    let mut y = x;
+    for _ in 0..NUM_CONSTRAINTS {
+        y.square_in_place();
+    }
+    let params = generate_random_parameters::<Bls12_381, _, _>(RepeatedSquaring { x: None }, rng)
+        .unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+    let proof = create_random_proof(RepeatedSquaring { x: Some(x) }, &params, rng).unwrap();
+    let mut prover_rng = StdRng::seed_from_u64(1u64);
+
+    vec![
+        Benchmark::new("Fr mul", move || {
+            let mut c = black_box(a);
+            c.mul_assign(&b);
+            black_box(c);
+        }),
+        Benchmark::new("Fr inverse", move || {
+            black_box(black_box(a).inverse());
+        }),
+        Benchmark::new("G1 add", move || {
+            let mut r = black_box(p);
+            r.add_assign(&q);
+            black_box(r);
+        }),
+        Benchmark::new("G1 mul", move || {
+            let mut r = black_box(p);
+            ProjectiveCurve::mul_assign(&mut r, s);
+            black_box(r);
+        }),
+        Benchmark::new("G2 mul", move || {
+            let mut r = black_box(p2);
+            ProjectiveCurve::mul_assign(&mut r, s);
+            black_box(r);
+        }),
+        Benchmark::new(format!("G1 MSM: {} bases", MSM_SIZE), move || {
+            black_box(VariableBaseMSM::multi_scalar_mul(&bases, &scalars));
+        }),
+        Benchmark::new(format!("FFT: 2^{} coefficients", domain.log_size_of_group), move || {
+            black_box(domain.fft(&coeffs));
+        }),
+        Benchmark::new(format!("Groth16 prove: {} constraints", NUM_CONSTRAINTS), move || {
+            black_box(
+                create_random_proof(RepeatedSquaring { x: Some(x) }, &params, &mut prover_rng)
+                    .unwrap(),
+            );
+        }),
+        Benchmark::new("Groth16 verify", move || {
+            assert!(verify_proof(&pvk, &proof, &[y]).unwrap());
+        }),
+    ]
+}
+
+/// Runs the suite with `config`.
+pub fn run_micro_benchmarks(config: &SuiteConfig) -> Vec<Measurement> {
+    run_suite(micro_benchmarks(), config)
+}
+
+/// Runs the suite with `config` and compares the timings against `baseline`,
+/// with the relative `tolerance` of `RegressionReport::compare`.
+pub fn check_regressions(
+    baseline: &Baseline,
+    tolerance: f64,
+    config: &SuiteConfig,
+) -> RegressionReport {
+    RegressionReport::compare(&run_micro_benchmarks(config), baseline, tolerance)
+}
@@ -1,8 +1,9 @@
+use crate::groth16::CircuitMarker;
 use algebra::Field;
 use r1cs_core::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
-struct MySillyCircuit<F: Field> {
-    a: Option<F>,
-    b: Option<F>,
+pub(crate) struct MySillyCircuit<F: Field> {
+    pub(crate) a: Option<F>,
+    pub(crate) b: Option<F>,
 }
 
 impl<ConstraintF: Field> ConstraintSynthesizer<ConstraintF> for MySillyCircuit<ConstraintF> {
@@ -34,18 +35,28 @@ impl<ConstraintF: Field> ConstraintSynthesizer<ConstraintF> for MySillyCircuit<C
     }
 }
 
+impl<F: Field> CircuitMarker for MySillyCircuit<F> {
+    const CIRCUIT_ID: &'static str = "silly";
+}
+
 mod bls12_377 {
     use super::*;
     use crate::groth16::{
         Parameters, Proof, VerifyingKey, PreparedVerifyingKey,
         create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
+        verify_batch_parallel, batch_verify, prove_stream, create_proof_from_witness,
+        create_proof_from_shared_witness, create_proof_with_mode, ProverMode,
+        generate_circuit_parameters, create_circuit_proof, prepare_circuit_verifying_key,
+        verify_circuit_proof, CircuitProof, CircuitVerifyingKey, create_proof,
+        create_proof_with_matrices, synthesize_constraints,
     };
+    use r1cs_core::{write_shared_witness, SharedWitness, WitnessWriter};
 
     use algebra::{curves::bls12_377::Bls12_377, fields::bls12_377::Fr, UniformRand,
             ToBytes, FromBytes, to_bytes,
     };
     use rand::thread_rng;
-    use std::ops::MulAssign;
+    use std::{ops::MulAssign, sync::Arc};
 
     #[test]
     fn prove_and_verify() {
@@ -78,6 +89,298 @@ mod bls12_377 {
         }
     }
 
+    #[test]
+    fn prove_with_modes() {
+        let rng = &mut thread_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let c = a * &b;
+        let prove = |mode: ProverMode, rng: &mut _| {
+            create_proof_with_mode(MySillyCircuit { a: Some(a), b: Some(b) }, &params, mode, rng)
+                .unwrap()
+        };
+
+        let proofs = vec![
+            prove(ProverMode::Random, rng),
+            prove(ProverMode::Random, rng),
+            prove(ProverMode::Deterministic([1u8; 32]), rng),
+            prove(ProverMode::Deterministic([1u8; 32]), rng),
+            prove(ProverMode::Deterministic([2u8; 32]), rng),
+            prove(ProverMode::NoBlinding, rng),
+            prove(ProverMode::NoBlinding, rng),
+        ];
+        for proof in proofs.iter() {
+            assert!(verify_proof(&pvk, proof, &[c]).unwrap());
+        }
+
+        // Only the deterministic modes reproduce the proofs
+        assert_ne!(proofs[0], proofs[1]);
+        assert_eq!(proofs[2], proofs[3]);
+        assert_ne!(proofs[2], proofs[4]);
+        assert_eq!(proofs[5], proofs[6]);
+        assert_ne!(proofs[2], proofs[5]);
+    }
+
+    #[test]
+    fn verify_batch() {
+        let rng = &mut thread_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+
+        let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+
+        let mut proofs = vec![];
+        let mut inputs = vec![];
+        for _ in 0..10 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+
+            proofs.push(create_random_proof(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b),
+                },
+                &params,
+                rng,
+            )
+            .unwrap());
+            inputs.push(vec![a * &b]);
+        }
+
+        let report = verify_batch_parallel(&pvk, &proofs, &inputs);
+        assert!(report.all_valid());
+        assert!(report.failed.is_empty());
+
+        // A wrong public input, and one of wrong length.
+        inputs[3][0] = Fr::rand(rng);
+        inputs[7].push(Fr::rand(rng));
+        let report = verify_batch_parallel(&pvk, &proofs, &inputs);
+        assert!(!report.all_valid());
+        assert!(!report.failed.is_empty());
+        assert!(report.failed.iter().all(|&i| i == 3 || i == 7));
+    }
+
+    #[test]
+    fn batch_verify_with_multiple_keys() {
+        let rng = &mut thread_rng();
+
+        let params = (0..2)
+            .map(|_| {
+                generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let pvks = params
+            .iter()
+            .map(|params| prepare_verifying_key::<Bls12_377>(&params.vk))
+            .collect::<Vec<_>>();
+
+        let mut proofs = vec![];
+        let mut keys = vec![];
+        let mut inputs = vec![];
+        for i in 0..10 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+
+            proofs.push(create_random_proof(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b),
+                },
+                &params[i % 2],
+                rng,
+            )
+            .unwrap());
+            keys.push(&pvks[i % 2]);
+            inputs.push(vec![a * &b]);
+        }
+
+        assert!(batch_verify(&proofs, &keys, &inputs, rng).unwrap());
+
+        // A proof checked against the wrong key.
+        keys.swap(0, 1);
+        assert!(!batch_verify(&proofs, &keys, &inputs, rng).unwrap());
+        keys.swap(0, 1);
+
+        // A wrong public input, and one of wrong length.
+        inputs[3][0] = Fr::rand(rng);
+        assert!(!batch_verify(&proofs, &keys, &inputs, rng).unwrap());
+        inputs[7].push(Fr::rand(rng));
+        assert!(batch_verify(&proofs, &keys, &inputs, rng).is_err());
+        inputs[7].pop();
+
+        // Different numbers of proofs, keys and public inputs.
+        assert!(batch_verify(&proofs[1..], &keys, &inputs, rng).is_err());
+        assert!(batch_verify(&proofs, &keys[1..], &inputs, rng).is_err());
+        assert!(batch_verify(&proofs, &keys, &inputs[1..], rng).is_err());
+    }
+
+    #[test]
+    fn prove_stream_and_verify() {
+        let rng = &mut thread_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+
+        let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+
+        let num_proofs = 10;
+        let witnesses = (0..num_proofs)
+            .map(|i| {
+                // One circuit without assignment, which cannot be proven.
+                if i == 4 {
+                    (None, None)
+                } else {
+                    (Some(Fr::rand(rng)), Some(Fr::rand(rng)))
+                }
+            })
+            .collect::<Vec<_>>();
+        let circuits = witnesses
+            .clone()
+            .into_iter()
+            .map(|(a, b)| MySillyCircuit { a, b })
+            .collect::<Vec<_>>();
+
+        let proofs = prove_stream(circuits, Arc::new(params), rng).collect::<Vec<_>>();
+        assert_eq!(proofs.len(), num_proofs);
+
+        for (proof, (a, b)) in proofs.iter().zip(witnesses.iter()) {
+            match (a, b) {
+                (Some(a), Some(b)) => {
+                    let proof = proof.as_ref().unwrap();
+                    assert!(verify_proof(&pvk, proof, &[*a * b]).unwrap());
+                    assert!(!verify_proof(&pvk, proof, &[*a]).unwrap());
+                }
+                _ => assert!(proof.is_err()),
+            }
+        }
+    }
+
+    #[test]
+    fn prove_from_witness_and_verify() {
+        let rng = &mut thread_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+
+        let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+
+        // Stream the witness, in chunks of a single element.
+        let mut writer = WitnessWriter::new(vec![], 1).unwrap();
+        MySillyCircuit { a: Some(a), b: Some(b) }.generate_constraints(&mut writer).unwrap();
+        let witness = writer.finish().unwrap();
+
+        // Prove from the witness, without assignment in the circuit.
+        let proof = create_proof_from_witness(
+            MySillyCircuit { a: None, b: None },
+            witness.as_slice(),
+            &params,
+            Fr::rand(rng),
+            Fr::rand(rng),
+        )
+        .unwrap();
+        assert!(verify_proof(&pvk, &proof, &[a * &b]).unwrap());
+
+        // A truncated witness.
+        assert!(create_proof_from_witness(
+            MySillyCircuit { a: None, b: None },
+            &witness[..witness.len() - 1],
+            &params,
+            Fr::rand(rng),
+            Fr::rand(rng),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn witness_read_in_allocation_order() {
+        use r1cs_core::{WitnessReader, WitnessSource, WITNESS_MAGIC, WITNESS_VERSION};
+
+        let rng = &mut thread_rng();
+        let (a, b) = (Fr::rand(rng), Fr::rand(rng));
+
+        // Interleaved allocations close the chunks in allocation order.
+        let mut writer = WitnessWriter::new(vec![], 4).unwrap();
+        writer.alloc(|| "a", || Ok(a)).unwrap();
+        writer.alloc_input(|| "b", || Ok(b)).unwrap();
+        let witness = writer.finish().unwrap();
+        let mut reader = WitnessReader::<Fr, _>::new(witness.as_slice()).unwrap();
+        assert_eq!(reader.next_input().unwrap(), Fr::one());
+        assert_eq!(reader.next_aux().unwrap(), a);
+        assert_eq!(reader.next_input().unwrap(), b);
+        assert_eq!(reader.finish().unwrap(), (2, 1));
+
+        // Reading an input after more than a chunk of auxiliary variables would
+        // need to buffer them, and fails instead.
+        let element_size = to_bytes![Fr::zero()].unwrap().len() as u32;
+        let witness = to_bytes![
+            WITNESS_MAGIC, WITNESS_VERSION, element_size, 1u32,
+            1u8, 1u32, a, 1u8, 1u32, a, 0u8, 1u32, Fr::one()
+        ].unwrap();
+        let mut reader = WitnessReader::<Fr, _>::new(witness.as_slice()).unwrap();
+        assert!(reader.next_input().is_err());
+    }
+
+    #[test]
+    fn prove_from_shared_witness_and_verify() {
+        let rng = &mut thread_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+
+        let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+
+        // Lay the witness out as a witness generator would in a shared memory segment,
+        // padded to a whole page.
+        let mut segment = vec![];
+        write_shared_witness(&mut segment, &[Fr::one(), a * &b], &[a, b]).unwrap();
+        segment.resize(4096, 0u8);
+
+        let proof = create_proof_from_shared_witness(
+            MySillyCircuit { a: None, b: None },
+            SharedWitness::new(&segment).unwrap(),
+            &params,
+            Fr::rand(rng),
+            Fr::rand(rng),
+        )
+        .unwrap();
+        assert!(verify_proof(&pvk, &proof, &[a * &b]).unwrap());
+
+        // A witness with a missing auxiliary variable.
+        let mut segment = vec![];
+        write_shared_witness(&mut segment, &[Fr::one(), a * &b], &[a]).unwrap();
+        assert!(create_proof_from_shared_witness(
+            MySillyCircuit { a: None, b: None },
+            SharedWitness::new(&segment).unwrap(),
+            &params,
+            Fr::rand(rng),
+            Fr::rand(rng),
+        )
+        .is_err());
+
+        // A truncated segment.
+        let mut segment = vec![];
+        write_shared_witness(&mut segment, &[Fr::one(), a * &b], &[a, b]).unwrap();
+        assert!(SharedWitness::<Fr>::new(&segment[..segment.len() - 1]).is_err());
+    }
+
     #[test]
     fn serialize_deserialize() {
 
@@ -123,6 +426,214 @@ mod bls12_377 {
 
         assert!(verify_proof(&pvk_deserialized, &proof_deserialized, &[c]).unwrap());
     }
+
+    #[test]
+    fn prove_with_constraint_matrices() {
+        let rng = &mut thread_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+        let matrices =
+            synthesize_constraints::<Bls12_377, _>(MySillyCircuit { a: None, b: None }).unwrap();
+        assert_eq!(matrices.num_constraints(), 6);
+        assert_eq!((matrices.num_inputs(), matrices.num_aux()), (2, 2));
+
+        let zero = Fr::zero();
+        for _ in 0..3 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let c = a * &b;
+            let r = Fr::rand(rng);
+            let s = Fr::rand(rng);
+
+            let circuit = || MySillyCircuit { a: Some(a), b: Some(b) };
+            let proof = create_proof_with_matrices(circuit(), &matrices, &params, r, s).unwrap();
+            assert!(verify_proof(&pvk, &proof, &[c]).unwrap());
+            assert_eq!(proof, create_proof(circuit(), &params, zero, zero, zero, r, s).unwrap());
+        }
+
+        // The assignment is still required
+        let circuit = MySillyCircuit { a: None, b: None };
+        assert!(create_proof_with_matrices(circuit, &matrices, &params, zero, zero).is_err());
+    }
+
+    #[test]
+    fn circuit_keys() {
+        struct OtherCircuit;
+        impl CircuitMarker for OtherCircuit {
+            const CIRCUIT_ID: &'static str = "other";
+        }
+
+        let rng = &mut thread_rng();
+        let params =
+            generate_circuit_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let pvk = prepare_circuit_verifying_key(&params.vk());
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let c = a * &b;
+        let proof = create_circuit_proof(MySillyCircuit { a: Some(a), b: Some(b) }, &params, rng)
+            .unwrap();
+        assert!(verify_circuit_proof(&pvk, &proof, &[c]).unwrap());
+        assert!(!verify_circuit_proof(&pvk, &proof, &[a]).unwrap());
+
+        // The circuit is checked on deserialization
+        let bytes = to_bytes![proof].unwrap();
+        let read = CircuitProof::<Bls12_377, MySillyCircuit<Fr>>::read(bytes.as_slice()).unwrap();
+        assert_eq!(read, proof);
+        assert!(CircuitProof::<Bls12_377, OtherCircuit>::read(bytes.as_slice()).is_err());
+        let bytes = to_bytes![params.vk()].unwrap();
+        assert!(CircuitVerifyingKey::<Bls12_377, OtherCircuit>::read(bytes.as_slice()).is_err());
+        assert_eq!(
+            CircuitVerifyingKey::<Bls12_377, MySillyCircuit<Fr>>::read(bytes.as_slice()).unwrap(),
+            params.vk()
+        );
+    }
+
+    #[test]
+    fn public_input_check() {
+        use r1cs_core::check_public_inputs;
+
+        let rng = &mut thread_rng();
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let c = a * &b;
+        let circuit = || MySillyCircuit { a: Some(a), b: Some(b) };
+
+        assert_eq!(check_public_inputs(circuit(), &[c]).unwrap(), None);
+
+        let mismatch = check_public_inputs(circuit(), &[a]).unwrap().unwrap();
+        assert_eq!((mismatch.index, mismatch.path.as_str()), (0, "c"));
+        assert_eq!((mismatch.expected, mismatch.found), (Some(c), Some(a)));
+
+        // Missing and extra inputs
+        let mismatch = check_public_inputs(circuit(), &[]).unwrap().unwrap();
+        assert_eq!((mismatch.index, mismatch.found), (0, None));
+        let mismatch = check_public_inputs(circuit(), &[c, a]).unwrap().unwrap();
+        assert_eq!((mismatch.index, mismatch.expected), (1, None));
+
+        // The values of the inputs are required
+        assert!(check_public_inputs(MySillyCircuit { a: None, b: None }, &[c]).is_err());
+    }
+
+    #[test]
+    fn versioned_serialization() {
+        use crate::versioning::{Header, Versioned, HEADER_LEN};
+        use algebra::curves::sw6::SW6;
+
+        let rng = &mut thread_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let proof = create_random_proof(MySillyCircuit { a: Some(a), b: Some(b) }, &params, rng)
+            .unwrap();
+
+        let mut params_serialized = vec![];
+        params.write_versioned(&mut params_serialized).unwrap();
+        assert_eq!(Parameters::<Bls12_377>::read_versioned(params_serialized.as_slice()).unwrap(), params);
+
+        let mut vk_serialized = vec![];
+        params.vk.write_versioned(&mut vk_serialized).unwrap();
+        assert_eq!(VerifyingKey::<Bls12_377>::read_versioned(vk_serialized.as_slice()).unwrap(), params.vk);
+
+        let mut proof_serialized = vec![];
+        proof.write_versioned(&mut proof_serialized).unwrap();
+        assert_eq!(proof_serialized[HEADER_LEN..], to_bytes!(proof).unwrap()[..]);
+        assert_eq!(Proof::<Bls12_377>::read_versioned(proof_serialized.as_slice()).unwrap(), proof);
+        assert_eq!(Header::read(proof_serialized.as_slice()).unwrap(), Header::of::<Proof<Bls12_377>>());
+
+        // Another kind of artifact, another curve, another version, unknown flags
+        assert!(VerifyingKey::<Bls12_377>::read_versioned(proof_serialized.as_slice()).is_err());
+        assert!(Proof::<SW6>::read_versioned(proof_serialized.as_slice()).is_err());
+        let mut other_version = proof_serialized.clone();
+        other_version[4] += 1;
+        assert!(Proof::<Bls12_377>::read_versioned(other_version.as_slice()).is_err());
+        let mut unknown_flags = proof_serialized.clone();
+        unknown_flags[HEADER_LEN - 1] = 1;
+        assert!(Proof::<Bls12_377>::read_versioned(unknown_flags.as_slice()).is_err());
+
+        // Unversioned artifacts are rejected
+        assert!(Proof::<Bls12_377>::read_versioned(to_bytes!(proof).unwrap().as_slice()).is_err());
+
+        // Reading with the checks of a profile
+        use algebra::{Field, FromBytesWithProfile, SerializationProfile};
+        assert_eq!(
+            Proof::<Bls12_377>::read_versioned_with_profile(proof_serialized.as_slice(), SerializationProfile::Strict).unwrap(),
+            proof
+        );
+        assert_eq!(
+            VerifyingKey::<Bls12_377>::read_with_profile(to_bytes!(params.vk).unwrap().as_slice(), SerializationProfile::Strict).unwrap(),
+            params.vk
+        );
+        let mut bad_proof = proof.clone();
+        bad_proof.a.y.double_in_place();
+        let bad_proof_serialized = to_bytes!(bad_proof).unwrap();
+        assert!(Proof::<Bls12_377>::read_with_profile(bad_proof_serialized.as_slice(), SerializationProfile::Strict).is_err());
+        assert_eq!(
+            Proof::<Bls12_377>::read_with_profile(bad_proof_serialized.as_slice(), SerializationProfile::Trusted).unwrap(),
+            bad_proof
+        );
+    }
+
+    #[cfg(feature = "bundle")]
+    #[test]
+    fn statement_bundle() {
+        use crate::bundle::{Groth16Statement, StatementBundle};
+        use algebra::{curves::mnt4753::MNT4, fields::mnt4753::Fr as MNT4Fr};
+
+        let rng = &mut thread_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let proof = create_random_proof(MySillyCircuit { a: Some(a), b: Some(b) }, &params, rng)
+            .unwrap();
+
+        let mnt4_params =
+            generate_random_parameters::<MNT4, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let mnt4_a = MNT4Fr::rand(rng);
+        let mnt4_b = MNT4Fr::rand(rng);
+        let mnt4_proof = create_random_proof(
+            MySillyCircuit { a: Some(mnt4_a), b: Some(mnt4_b) },
+            &mnt4_params,
+            rng,
+        )
+        .unwrap();
+
+        let bundle = |c: Fr| {
+            StatementBundle::new()
+                .add("bls12_377", Groth16Statement::new(params.vk.clone(), proof.clone(), vec![c]))
+                .add(
+                    "mnt4753",
+                    Groth16Statement::new(mnt4_params.vk.clone(), mnt4_proof.clone(), vec![mnt4_a * &mnt4_b]),
+                )
+        };
+
+        let valid = bundle(a * &b);
+        assert_eq!(valid.labels(), vec!["bls12_377", "mnt4753"]);
+        assert!(valid.verify_all().unwrap());
+        let hash = valid.integrity_hash();
+        assert_eq!(bundle(a * &b).integrity_hash(), hash);
+        assert!(valid.verify_all_with_hash(&hash).unwrap());
+
+        // A wrong public input changes the hash and fails verification
+        let invalid = bundle(a);
+        assert_ne!(invalid.integrity_hash(), hash);
+        assert!(!invalid.verify_all_with_hash(&hash).unwrap());
+        assert!(!invalid.verify_all().unwrap());
+        assert_eq!(invalid.verify_each().unwrap(), vec![("bls12_377", false), ("mnt4753", true)]);
+
+        assert!(StatementBundle::new().verify_all().unwrap());
+    }
 }
 
 mod sw6 {
@@ -1,10 +1,15 @@
-use algebra::{AffineCurve, PairingCurve, PairingEngine, PrimeField, ProjectiveCurve};
+use algebra::{AffineCurve, Field, PairingCurve, PairingEngine, PrimeField, ProjectiveCurve, UniformRand};
 
 use super::{PreparedVerifyingKey, Proof, VerifyingKey};
 
 use crate::groth16::SynthesisError;
 
-use std::ops::{AddAssign, Neg};
+use rand::Rng;
+use rayon::prelude::*;
+use std::{
+    ops::{AddAssign, MulAssign, Neg},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 pub fn prepare_verifying_key<E: PairingEngine>(vk: &VerifyingKey<E>) -> PreparedVerifyingKey<E> {
     PreparedVerifyingKey {
@@ -26,7 +31,7 @@ pub fn verify_proof<E: PairingEngine>(
 
     let mut g_ic = pvk.gamma_abc_g1[0].into_projective();
     for (i, b) in public_inputs.iter().zip(pvk.gamma_abc_g1.iter().skip(1)) {
-        g_ic.add_assign(&b.mul(i.into_repr()));
+        g_ic.add_assign(&b.mul(i));
     }
 
     let qap = E::miller_loop(
@@ -41,4 +46,140 @@ pub fn verify_proof<E: PairingEngine>(
     let test = E::final_exponentiation(&qap).ok_or(SynthesisError::UnexpectedIdentity)?;
 
     Ok(test == pvk.alpha_g1_beta_g2)
-}
\ No newline at end of file
+}
+
+/// Verifies `proofs[i]` against `pvks[i]` and `public_inputs[i]` for all `i`
+/// at once, by a random linear combination of their verification equations:
+/// with random scalars r_i, it checks that
+///
+///     prod_i e(r_i * A_i, B_i) * prod_vk e(sum_i r_i * acc_i, -gamma) * e(sum_i r_i * C_i, -delta)
+///         = prod_vk e(alpha, beta)^{sum_i r_i},
+///
+/// where the sums run over the proofs with the same verifying key. This costs
+/// a single final exponentiation and a multi-Miller loop of n + 2k pairs, for n
+/// proofs under k distinct keys, instead of n final exponentiations and 3n pairs.
+/// A false result does not tell which proofs are invalid: use
+/// `verify_batch_parallel` for that. Returns an error if the numbers of proofs,
+/// of verifying keys and of public inputs differ.
+pub fn batch_verify<E: PairingEngine, R: Rng>(
+    proofs: &[Proof<E>],
+    pvks: &[&PreparedVerifyingKey<E>],
+    public_inputs: &[Vec<E::Fr>],
+    rng: &mut R,
+) -> Result<bool, SynthesisError> {
+    if proofs.len() != pvks.len() || proofs.len() != public_inputs.len() {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+
+    // For each distinct key: sum_i r_i * acc_i, sum_i r_i * C_i and sum_i r_i
+    let mut keys: Vec<(&PreparedVerifyingKey<E>, E::G1Projective, E::G1Projective, E::Fr)> = vec![];
+    let mut a_b_pairs = Vec::with_capacity(proofs.len());
+
+    for ((proof, pvk), inputs) in proofs.iter().zip(pvks).zip(public_inputs) {
+        if (inputs.len() + 1) != pvk.gamma_abc_g1.len() {
+            return Err(SynthesisError::MalformedVerifyingKey);
+        }
+        let r = E::Fr::rand(rng);
+
+        let mut g_ic = pvk.gamma_abc_g1[0].into_projective();
+        for (i, b) in inputs.iter().zip(pvk.gamma_abc_g1.iter().skip(1)) {
+            g_ic.add_assign(&b.mul(*i));
+        }
+
+        let position = match keys
+            .iter()
+            .position(|(key, _, _, _)| std::ptr::eq(*key, *pvk) || *key == *pvk)
+        {
+            Some(position) => position,
+            None => {
+                keys.push((*pvk, E::G1Projective::zero(), E::G1Projective::zero(), E::Fr::zero()));
+                keys.len() - 1
+            },
+        };
+        g_ic.mul_assign(r);
+        let (_, acc, c, r_sum) = &mut keys[position];
+        acc.add_assign(&g_ic);
+        c.add_assign(&proof.c.mul(r));
+        r_sum.add_assign(&r);
+
+        a_b_pairs.push((proof.a.mul(r).into_affine().prepare(), proof.b.prepare()));
+    }
+
+    let acc_c_pairs = keys
+        .iter()
+        .map(|(_, acc, c, _)| (acc.into_affine().prepare(), c.into_affine().prepare()))
+        .collect::<Vec<_>>();
+    let mut pairs = a_b_pairs.iter().map(|(a, b)| (a, b)).collect::<Vec<_>>();
+    for ((acc, c), (pvk, _, _, _)) in acc_c_pairs.iter().zip(keys.iter()) {
+        pairs.push((acc, &pvk.gamma_g2_neg_pc));
+        pairs.push((c, &pvk.delta_g2_neg_pc));
+    }
+
+    let qap = E::miller_loop(pairs.iter());
+    let test = E::final_exponentiation(&qap).ok_or(SynthesisError::UnexpectedIdentity)?;
+
+    let mut expected = E::Fqk::one();
+    for (pvk, _, _, r_sum) in keys.iter() {
+        expected.mul_assign(&pvk.alpha_g1_beta_g2.pow(r_sum.into_repr()));
+    }
+
+    Ok(test == expected)
+}
+
+/// The outcome of `verify_batch_parallel`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BatchVerificationReport {
+    /// The indices of the proofs found invalid, in increasing order. Proofs
+    /// whose verification returned an error are counted as invalid.
+    pub failed: Vec<usize>,
+    /// Whether all proofs have been verified. As verification stops at the
+    /// first failure, `failed` may not list all the invalid proofs otherwise.
+    pub complete: bool,
+}
+
+impl BatchVerificationReport {
+    /// Returns true iff all proofs of the batch are valid.
+    pub fn all_valid(&self) -> bool {
+        self.complete && self.failed.is_empty()
+    }
+}
+
+/// Verifies `proofs[i]` against `public_inputs[i]` for all `i`, distributing
+/// the (independent) verifications over the threads of the rayon pool.
+///
+/// Once a proof is found invalid, no further verifications are started.
+///
+/// # Panics
+///
+/// If the numbers of proofs and of public inputs differ.
+pub fn verify_batch_parallel<E: PairingEngine>(
+    pvk: &PreparedVerifyingKey<E>,
+    proofs: &[Proof<E>],
+    public_inputs: &[Vec<E::Fr>],
+) -> BatchVerificationReport {
+    assert_eq!(proofs.len(), public_inputs.len());
+
+    let failure_found = AtomicBool::new(false);
+    let outcomes = proofs
+        .par_iter()
+        .zip(public_inputs.par_iter())
+        .enumerate()
+        .filter_map(|(i, (proof, inputs))| {
+            if failure_found.load(Ordering::Relaxed) {
+                return None;
+            }
+            let verified = verify_proof(pvk, proof, inputs.as_slice()).unwrap_or(false);
+            if verified {
+                Some((i, true))
+            } else {
+                failure_found.store(true, Ordering::Relaxed);
+                Some((i, false))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    BatchVerificationReport {
+        complete: outcomes.len() == proofs.len(),
+        failed: outcomes.into_iter().filter(|(_, verified)| !verified).map(|(i, _)| i).collect(),
+    }
+}
@@ -10,8 +10,39 @@
 #[macro_use]
 extern crate bench_utils;
 
+#[cfg(feature = "ceremony")]
+#[macro_use]
+extern crate derivative;
+
 #[cfg(feature = "groth16")]
 pub mod groth16;
 
 #[cfg(feature = "gm17")]
 pub mod gm17;
+
+#[cfg(feature = "bundle")]
+pub mod bundle;
+
+pub mod versioning;
+
+use algebra::capabilities::{Capabilities, SerializationFormat};
+
+/// Returns the capabilities of this crate and of the crates it depends on.
+pub fn capabilities() -> Capabilities {
+    r1cs_core::capabilities().extend(
+        ("proof-systems", env!("CARGO_PKG_VERSION")),
+        &[
+            ("proof-systems/groth16", cfg!(feature = "groth16")),
+            ("proof-systems/gm17", cfg!(feature = "gm17")),
+            ("proof-systems/prover", cfg!(feature = "prover")),
+            ("proof-systems/ceremony", cfg!(feature = "ceremony")),
+            ("proof-systems/bundle", cfg!(feature = "bundle")),
+            ("proof-systems/shared-memory", cfg!(feature = "shared-memory")),
+            ("proof-systems/print-trace", cfg!(feature = "print-trace")),
+        ],
+        &[SerializationFormat {
+            name:    "versioned proofs and keys",
+            version: u32::from(versioning::SERIALIZATION_VERSION),
+        }],
+    )
+}
@@ -0,0 +1,175 @@
+//! A header prepended to serialized proofs and keys, so that an artifact of the
+//! wrong kind, of another curve or of an unsupported version fails to
+//! deserialize with a clear error instead of yielding garbage. The header
+//! consists of:
+//! - the magic bytes of the kind of artifact (4 bytes);
+//! - the version `SERIALIZATION_VERSION` of the format (u8);
+//! - the identifier of the curve, as computed by `curve_id` (u64);
+//! - flags (u16), of which none is defined in the current version;
+//!
+//! followed by the `ToBytes` serialization of the artifact. All integers are
+//! little endian.
+use algebra::{
+    bytes::{FromBytes, FromBytesWithProfile, SerializationProfile, ToBytes},
+    AffineCurve, PairingEngine,
+};
+use std::io::{self, Read, Write};
+
+/// The version of the serialization format of the artifacts.
+pub const SERIALIZATION_VERSION: u8 = 1;
+
+/// The length in bytes of the header.
+pub const HEADER_LEN: usize = 15;
+
+/// Returns an identifier of the curve of `E`: the 64 bit FNV-1a hash of the
+/// serialization of the generators of G1 and G2.
+pub fn curve_id<E: PairingEngine>() -> u64 {
+    let mut bytes = vec![];
+    E::G1Affine::prime_subgroup_generator().write(&mut bytes).unwrap();
+    E::G2Affine::prime_subgroup_generator().write(&mut bytes).unwrap();
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// The header of a serialized artifact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub magic:    [u8; 4],
+    pub version:  u8,
+    pub curve_id: u64,
+    pub flags:    u16,
+}
+
+impl Header {
+    /// Returns the header of the artifacts of type `T` in the current version.
+    pub fn of<T: Versioned>() -> Self {
+        Self {
+            magic:    T::MAGIC,
+            version:  SERIALIZATION_VERSION,
+            curve_id: curve_id::<T::Engine>(),
+            flags:    0,
+        }
+    }
+
+    /// Checks that the header is the one of an artifact of type `T` readable by
+    /// this version of the library.
+    pub fn check<T: Versioned>(&self) -> io::Result<()> {
+        let expected = Self::of::<T>();
+        if self.magic != expected.magic {
+            return Err(invalid_data(format!(
+                "expected a {}, found magic bytes {:?}",
+                T::NAME,
+                self.magic
+            )));
+        }
+        if self.version != expected.version {
+            return Err(invalid_data(format!(
+                "unsupported {} serialization version {}, expected {}",
+                T::NAME,
+                self.version,
+                expected.version
+            )));
+        }
+        if self.curve_id != expected.curve_id {
+            return Err(invalid_data(format!(
+                "{} for another curve: found curve id {:#018x}, expected {:#018x}",
+                T::NAME,
+                self.curve_id,
+                expected.curve_id
+            )));
+        }
+        if self.flags != expected.flags {
+            return Err(invalid_data(format!("unknown {} flags {:#06x}", T::NAME, self.flags)));
+        }
+        Ok(())
+    }
+}
+
+impl ToBytes for Header {
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.magic.write(&mut writer)?;
+        self.version.write(&mut writer)?;
+        self.curve_id.write(&mut writer)?;
+        self.flags.write(&mut writer)
+    }
+}
+
+impl FromBytes for Header {
+    fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let magic = <[u8; 4]>::read(&mut reader)?;
+        let version = u8::read(&mut reader)?;
+        let curve_id = u64::read(&mut reader)?;
+        let flags = u16::read(&mut reader)?;
+        Ok(Self { magic, version, curve_id, flags })
+    }
+}
+
+/// An artifact serialized with a header.
+pub trait Versioned: ToBytes + FromBytes {
+    /// The pairing engine the artifact is defined over.
+    type Engine: PairingEngine;
+
+    /// The magic bytes identifying the kind of artifact.
+    const MAGIC: [u8; 4];
+
+    /// The name of the kind of artifact, for error messages.
+    const NAME: &'static str;
+
+    /// Writes the header, followed by the artifact.
+    fn write_versioned<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        Header::of::<Self>().write(&mut writer)?;
+        self.write(&mut writer)
+    }
+
+    /// Reads and checks the header, then reads the artifact.
+    fn read_versioned<R: Read>(mut reader: R) -> io::Result<Self> {
+        Header::read(&mut reader)?.check::<Self>()?;
+        Self::read(&mut reader)
+    }
+
+    /// Same as `read_versioned`, with the checks of `profile`.
+    fn read_versioned_with_profile<R: Read>(mut reader: R, profile: SerializationProfile) -> io::Result<Self>
+    where
+        Self: FromBytesWithProfile,
+    {
+        Header::read(&mut reader)?.check::<Self>()?;
+        Self::read_with_profile(&mut reader, profile)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::curves::{
+        bls12_377::Bls12_377, bls12_381::Bls12_381, mnt4753::MNT4, mnt6753::MNT6, sw6::SW6,
+    };
+
+    #[test]
+    fn curve_ids() {
+        let ids = vec![
+            curve_id::<Bls12_377>(),
+            curve_id::<Bls12_381>(),
+            curve_id::<MNT4>(),
+            curve_id::<MNT6>(),
+            curve_id::<SW6>(),
+        ];
+        for (i, id) in ids.iter().enumerate() {
+            assert!(ids[i + 1..].iter().all(|other| other != id));
+        }
+    }
+
+    #[test]
+    fn header_serialization() {
+        let header = Header { magic: *b"TEST", version: 3, curve_id: 42, flags: 1 };
+        let mut bytes = vec![];
+        header.write(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), HEADER_LEN);
+        assert_eq!(Header::read(bytes.as_slice()).unwrap(), header);
+        assert!(Header::read(&bytes[..HEADER_LEN - 1]).is_err());
+    }
+}
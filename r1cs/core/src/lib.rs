@@ -7,18 +7,32 @@
 #![deny(renamed_and_removed_lints, stable_features, unused_allocation)]
 #![deny(unused_comparisons, bare_trait_objects, unused_must_use, const_err)]
 
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "shared-memory"), forbid(unsafe_code))]
+#![cfg_attr(feature = "shared-memory", deny(unsafe_code))]
 
 mod constraint_system;
 mod error;
 mod impl_lc;
 mod impl_constraint_var;
+mod public_inputs;
+mod witness;
 
 pub use constraint_system::{ConstraintSystem, ConstraintSynthesizer, Namespace};
 pub use error::SynthesisError;
+pub use public_inputs::{check_public_inputs, PublicInputMismatch};
+pub use witness::{
+    write_shared_witness, SharedWitness, WitnessReader, WitnessReplay, WitnessSource,
+    WitnessWriter, SHARED_WITNESS_HEADER_LEN, SHARED_WITNESS_MAGIC, WITNESS_MAGIC,
+    WITNESS_VERSION,
+};
+#[cfg(feature = "shared-memory")]
+pub use witness::MappedWitness;
 pub use algebra::ToConstraintField;
 
-use algebra::Field;
+use algebra::{
+    capabilities::{self as algebra_capabilities, Capabilities, SerializationFormat},
+    Field,
+};
 use smallvec::SmallVec as StackVec;
 use std::cmp::Ordering;
 
@@ -85,3 +99,15 @@ pub enum ConstraintVar<F: Field> {
     /// A wrapper around a `Variable`.
     Var(Variable),
 }
+
+/// Returns the capabilities of this crate and of `algebra`.
+pub fn capabilities() -> Capabilities {
+    algebra_capabilities::capabilities().extend(
+        ("r1cs-core", env!("CARGO_PKG_VERSION")),
+        &[("r1cs-core/shared-memory", cfg!(feature = "shared-memory"))],
+        &[
+            SerializationFormat { name: "witness", version: u32::from(WITNESS_VERSION) },
+            SerializationFormat { name: "shared witness", version: u32::from(WITNESS_VERSION) },
+        ],
+    )
+}
@@ -0,0 +1,157 @@
+//! A sanity check of the public inputs of a circuit, to be run before proving:
+//! a public input vector whose values are shifted, or otherwise differ from the
+//! inputs the circuit allocates, only shows up as a proof failing to verify.
+
+use crate::{
+    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+};
+use algebra::Field;
+use std::fmt;
+
+/// The first public input whose value differs from the one allocated by the
+/// circuit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicInputMismatch<F: Field> {
+    /// The index of the input in the public input vector.
+    pub index:    usize,
+    /// The path of the input in the namespaces of the circuit, empty if the
+    /// circuit allocates fewer inputs than given.
+    pub path:     String,
+    /// The value allocated by the circuit, `None` if the circuit allocates
+    /// fewer inputs than given.
+    pub expected: Option<F>,
+    /// The value given, `None` if the circuit allocates more inputs than given.
+    pub found:    Option<F>,
+}
+
+impl<F: Field> fmt::Display for PublicInputMismatch<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.expected, &self.found) {
+            (Some(expected), Some(found)) => write!(
+                f,
+                "public input {} (`{}`) is {}, but the circuit allocates {}",
+                self.index, self.path, found, expected
+            ),
+            (Some(_), None) => write!(
+                f,
+                "missing public input {} (`{}`) allocated by the circuit",
+                self.index, self.path
+            ),
+            (None, _) => write!(
+                f,
+                "public input {} is not allocated by the circuit",
+                self.index
+            ),
+        }
+    }
+}
+
+impl<F: Field> std::error::Error for PublicInputMismatch<F> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+/// Synthesizes `circuit` and compares the values of the inputs it allocates,
+/// in allocation order, to `public_inputs`, i.e. the public input vector of the
+/// proof without the "one" input. Returns the first mismatch, if any.
+pub fn check_public_inputs<F, C>(
+    circuit: C,
+    public_inputs: &[F],
+) -> Result<Option<PublicInputMismatch<F>>, SynthesisError>
+where
+    F: Field,
+    C: ConstraintSynthesizer<F>,
+{
+    let mut cs = InputRecorder {
+        namespace:       vec![],
+        inputs:          vec![],
+        num_aux:         0,
+        num_constraints: 0,
+    };
+    circuit.generate_constraints(&mut cs)?;
+
+    let len = cs.inputs.len().max(public_inputs.len());
+    let mismatch = (0..len)
+        .find(|&i| cs.inputs.get(i).map(|(_, v)| v) != public_inputs.get(i))
+        .map(|index| PublicInputMismatch {
+            index,
+            path: cs.inputs.get(index).map_or(String::new(), |(path, _)| path.clone()),
+            expected: cs.inputs.get(index).map(|(_, v)| *v),
+            found: public_inputs.get(index).cloned(),
+        });
+    Ok(mismatch)
+}
+
+/// A constraint system recording the paths and the values of the inputs.
+struct InputRecorder<F: Field> {
+    namespace:       Vec<String>,
+    inputs:          Vec<(String, F)>,
+    num_aux:         usize,
+    num_constraints: usize,
+}
+
+impl<F: Field> ConstraintSystem<F> for InputRecorder<F> {
+    type Root = Self;
+
+    #[inline]
+    fn alloc<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        // The values of the auxiliary variables are not needed
+        let index = self.num_aux;
+        self.num_aux += 1;
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
+
+    #[inline]
+    fn alloc_input<FN, A, AR>(&mut self, annotation: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        // The "one" input variable is not allocated by the circuit
+        let index = self.inputs.len() + 1;
+        let mut path = self.namespace.clone();
+        path.push(annotation().into());
+        self.inputs.push((path.join("/"), f()?));
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    #[inline]
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, _: LA, _: LB, _: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.namespace.push(name_fn().into());
+    }
+
+    fn pop_namespace(&mut self) {
+        self.namespace.pop();
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+}
@@ -0,0 +1,580 @@
+//! A chunked on-disk format for the assignment of a constraint system, allowing
+//! witness generation to be streamed to disk and the assignment to be fed back
+//! to a prover without computing it again. The witness generator only holds a
+//! chunk of the assignment at a time, and so does the reader; note however that
+//! the Groth16 prover still collects the whole assignment, as the constraints
+//! refer to arbitrary earlier variables, so that its memory use stays linear in
+//! the size of the witness.
+//!
+//! The format consists of a header, followed by a sequence of records:
+//! - header: the magic bytes `WITNESS_MAGIC`, the version `WITNESS_VERSION` (u8),
+//!   the size in bytes of a serialized field element (u32) and the maximum number
+//!   of elements in a chunk (u32);
+//! - input chunk: the tag `0u8`, the number of elements (u32) and the values of
+//!   the next input variables, in allocation order;
+//! - aux chunk: the tag `1u8`, the number of elements (u32) and the values of the
+//!   next auxiliary variables, in allocation order;
+//! - end: the tag `2u8`, followed by the total number of input and auxiliary
+//!   variables (u64 each).
+//!
+//! Chunks are written in allocation order: a chunk is closed as soon as a
+//! variable of the other kind is allocated. All integers are little endian. The
+//! "one" input variable is always the first input of the witness.
+//!
+//! A witness may also be handed over in a flat layout, meant to be written in place
+//! to a shared memory segment (e.g. a memfd) by a witness generator running in
+//! another process, possibly written in another language, and read by the prover
+//! without further copies:
+//! - header (`SHARED_WITNESS_HEADER_LEN` bytes): the magic bytes
+//!   `SHARED_WITNESS_MAGIC`, the version `WITNESS_VERSION` (u8), the size in bytes
+//!   of a serialized field element (u32), the number of input and auxiliary
+//!   variables (u64 each) and 7 reserved zero bytes, so that the values start
+//!   at an offset multiple of 8;
+//! - the values of all the input variables, followed by the values of all the
+//!   auxiliary variables, in allocation order.
+//!
+//! Field elements are serialized as by `ToBytes`, i.e. as their canonical
+//! representation in little endian 64 bit limbs. Bytes after the last value are
+//! ignored, so that the segment can be rounded up to a whole number of pages.
+
+use crate::{ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+use algebra::{Field, FromBytes, ToBytes};
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+    marker::PhantomData,
+};
+
+/// The magic bytes at the beginning of a witness file.
+pub const WITNESS_MAGIC: [u8; 4] = *b"GWIT";
+
+/// The version of the witness format.
+pub const WITNESS_VERSION: u8 = 1;
+
+/// The magic bytes at the beginning of a witness in the shared memory layout.
+pub const SHARED_WITNESS_MAGIC: [u8; 4] = *b"GSWT";
+
+/// The length of the header of a witness in the shared memory layout.
+pub const SHARED_WITNESS_HEADER_LEN: usize = 32;
+
+const INPUT_CHUNK_TAG: u8 = 0;
+const AUX_CHUNK_TAG: u8 = 1;
+const END_TAG: u8 = 2;
+
+fn invalid_data(msg: &str) -> SynthesisError {
+    io::Error::new(io::ErrorKind::InvalidData, msg).into()
+}
+
+fn serialized_size<F: Field>() -> Result<u32, SynthesisError> {
+    let mut buf = vec![];
+    F::zero().write(&mut buf)?;
+    Ok(buf.len() as u32)
+}
+
+/// A constraint system which streams the assignment of the circuit synthesized
+/// on it to `writer`, in chunks of at most `chunk_size` elements, ignoring the
+/// constraints. Only the current chunk of inputs or of auxiliary variables is
+/// kept in memory.
+pub struct WitnessWriter<F: Field, W: Write> {
+    writer:          W,
+    chunk_size:      usize,
+    input_chunk:     Vec<F>,
+    aux_chunk:       Vec<F>,
+    num_inputs:      u64,
+    num_aux:         u64,
+    num_constraints: usize,
+}
+
+impl<F: Field, W: Write> WitnessWriter<F, W> {
+    /// Writes the header of the witness to `writer` and allocates the "one"
+    /// input variable.
+    pub fn new(mut writer: W, chunk_size: usize) -> Result<Self, SynthesisError> {
+        assert!(chunk_size > 0 && chunk_size <= u32::max_value() as usize);
+
+        WITNESS_MAGIC.write(&mut writer)?;
+        WITNESS_VERSION.write(&mut writer)?;
+        serialized_size::<F>()?.write(&mut writer)?;
+        (chunk_size as u32).write(&mut writer)?;
+
+        let mut witness = Self {
+            writer,
+            chunk_size,
+            input_chunk: Vec::with_capacity(chunk_size),
+            aux_chunk: Vec::with_capacity(chunk_size),
+            num_inputs: 0,
+            num_aux: 0,
+            num_constraints: 0,
+        };
+        witness.alloc_input(|| "", || Ok(F::one()))?;
+
+        Ok(witness)
+    }
+
+    fn write_chunk(writer: &mut W, tag: u8, chunk: &mut Vec<F>) -> Result<(), SynthesisError> {
+        if !chunk.is_empty() {
+            tag.write(&mut *writer)?;
+            (chunk.len() as u32).write(&mut *writer)?;
+            for value in chunk.iter() {
+                value.write(&mut *writer)?;
+            }
+            chunk.clear();
+        }
+        Ok(())
+    }
+
+    /// Flushes the pending chunks, writes the end record and returns the
+    /// underlying writer.
+    pub fn finish(mut self) -> Result<W, SynthesisError> {
+        Self::write_chunk(&mut self.writer, INPUT_CHUNK_TAG, &mut self.input_chunk)?;
+        Self::write_chunk(&mut self.writer, AUX_CHUNK_TAG, &mut self.aux_chunk)?;
+        END_TAG.write(&mut self.writer)?;
+        self.num_inputs.write(&mut self.writer)?;
+        self.num_aux.write(&mut self.writer)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<F: Field, W: Write> ConstraintSystem<F> for WitnessWriter<F, W> {
+    type Root = Self;
+
+    #[inline]
+    fn alloc<FN, A, AR>(&mut self, _: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_aux as usize;
+        self.num_aux += 1;
+
+        let value = f()?;
+        Self::write_chunk(&mut self.writer, INPUT_CHUNK_TAG, &mut self.input_chunk)?;
+        self.aux_chunk.push(value);
+        if self.aux_chunk.len() == self.chunk_size {
+            Self::write_chunk(&mut self.writer, AUX_CHUNK_TAG, &mut self.aux_chunk)?;
+        }
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
+
+    #[inline]
+    fn alloc_input<FN, A, AR>(&mut self, _: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_inputs as usize;
+        self.num_inputs += 1;
+
+        let value = f()?;
+        Self::write_chunk(&mut self.writer, AUX_CHUNK_TAG, &mut self.aux_chunk)?;
+        self.input_chunk.push(value);
+        if self.input_chunk.len() == self.chunk_size {
+            Self::write_chunk(&mut self.writer, INPUT_CHUNK_TAG, &mut self.input_chunk)?;
+        }
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    #[inline]
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, _: LA, _: LB, _: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn pop_namespace(&mut self) {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+}
+
+/// A source of the values of the variables of a witness, in allocation order.
+pub trait WitnessSource<F: Field> {
+    /// Returns the value of the next input variable.
+    fn next_input(&mut self) -> Result<F, SynthesisError>;
+
+    /// Returns the value of the next auxiliary variable.
+    fn next_aux(&mut self) -> Result<F, SynthesisError>;
+}
+
+/// Reads back a witness written by a `WitnessWriter`, one chunk at a time.
+/// Inputs and auxiliary variables are returned in allocation order. At most one
+/// chunk of each kind is held in memory: reading fails if the variables are not
+/// consumed in the order they were written, rather than buffering the chunks
+/// read ahead.
+pub struct WitnessReader<F: Field, R: Read> {
+    reader:     R,
+    chunk_size: usize,
+    inputs:     VecDeque<F>,
+    aux:        VecDeque<F>,
+    num_inputs: u64,
+    num_aux:    u64,
+    // The total number of inputs and auxiliary variables, once the end record is read
+    totals:     Option<(u64, u64)>,
+}
+
+impl<F: Field, R: Read> WitnessReader<F, R> {
+    /// Reads and checks the header of the witness.
+    pub fn new(mut reader: R) -> Result<Self, SynthesisError> {
+        if <[u8; 4]>::read(&mut reader)? != WITNESS_MAGIC {
+            return Err(invalid_data("invalid witness magic bytes"));
+        }
+        if u8::read(&mut reader)? != WITNESS_VERSION {
+            return Err(invalid_data("unsupported witness version"));
+        }
+        if u32::read(&mut reader)? != serialized_size::<F>()? {
+            return Err(invalid_data("witness field element size mismatch"));
+        }
+        let chunk_size = u32::read(&mut reader)? as usize;
+        if chunk_size == 0 {
+            return Err(invalid_data("invalid witness chunk size"));
+        }
+
+        Ok(Self {
+            reader,
+            chunk_size,
+            inputs: VecDeque::new(),
+            aux: VecDeque::new(),
+            num_inputs: 0,
+            num_aux: 0,
+            totals: None,
+        })
+    }
+
+    // Reads the next record, returning false if the end record was already read.
+    fn read_record(&mut self) -> Result<bool, SynthesisError> {
+        if self.totals.is_some() {
+            return Ok(false);
+        }
+        let tag = u8::read(&mut self.reader)?;
+        let chunk = match tag {
+            INPUT_CHUNK_TAG => &mut self.inputs,
+            AUX_CHUNK_TAG => &mut self.aux,
+            END_TAG => {
+                let num_inputs = u64::read(&mut self.reader)?;
+                let num_aux = u64::read(&mut self.reader)?;
+                self.totals = Some((num_inputs, num_aux));
+                return Ok(true);
+            },
+            _ => return Err(invalid_data("invalid witness record tag")),
+        };
+        let len = u32::read(&mut self.reader)? as usize;
+        if len == 0 || len > self.chunk_size {
+            return Err(invalid_data("invalid witness chunk length"));
+        }
+        if chunk.len() + len > self.chunk_size {
+            return Err(invalid_data("witness not consumed in allocation order"));
+        }
+        for _ in 0..len {
+            chunk.push_back(F::read(&mut self.reader)?);
+        }
+        Ok(true)
+    }
+
+    /// Checks that the whole witness has been consumed, returning the number of
+    /// input and auxiliary variables read.
+    pub fn finish(mut self) -> Result<(usize, usize), SynthesisError> {
+        if !self.inputs.is_empty() || !self.aux.is_empty() || !self.read_record()? {
+            return Err(invalid_data("witness not fully consumed"));
+        }
+        match self.totals {
+            Some((num_inputs, num_aux))
+                if num_inputs == self.num_inputs && num_aux == self.num_aux =>
+            {
+                Ok((num_inputs as usize, num_aux as usize))
+            },
+            _ => Err(invalid_data("witness not fully consumed")),
+        }
+    }
+}
+
+impl<F: Field, R: Read> WitnessSource<F> for WitnessReader<F, R> {
+    fn next_input(&mut self) -> Result<F, SynthesisError> {
+        loop {
+            if let Some(value) = self.inputs.pop_front() {
+                self.num_inputs += 1;
+                return Ok(value);
+            }
+            if !self.read_record()? {
+                return Err(SynthesisError::AssignmentMissing);
+            }
+        }
+    }
+
+    fn next_aux(&mut self) -> Result<F, SynthesisError> {
+        loop {
+            if let Some(value) = self.aux.pop_front() {
+                self.num_aux += 1;
+                return Ok(value);
+            }
+            if !self.read_record()? {
+                return Err(SynthesisError::AssignmentMissing);
+            }
+        }
+    }
+}
+
+/// Writes a witness in the shared memory layout, with the given values of the
+/// input variables, starting with the "one" input, and of the auxiliary ones.
+pub fn write_shared_witness<F: Field, W: Write>(
+    mut writer: W,
+    inputs: &[F],
+    aux: &[F],
+) -> Result<(), SynthesisError> {
+    SHARED_WITNESS_MAGIC.write(&mut writer)?;
+    WITNESS_VERSION.write(&mut writer)?;
+    serialized_size::<F>()?.write(&mut writer)?;
+    (inputs.len() as u64).write(&mut writer)?;
+    (aux.len() as u64).write(&mut writer)?;
+    [0u8; 7].write(&mut writer)?;
+    for value in inputs.iter().chain(aux.iter()) {
+        value.write(&mut writer)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// A witness in the shared memory layout, borrowed from the bytes it is stored in
+/// (e.g. the mapping of a shared memory segment). Values are deserialized from
+/// `bytes` one at a time, as they are consumed, without intermediate buffers.
+pub struct SharedWitness<'a, F: Field> {
+    inputs:       &'a [u8],
+    aux:          &'a [u8],
+    element_size: usize,
+    num_inputs:   usize,
+    num_aux:      usize,
+    next_input:   usize,
+    next_aux:     usize,
+    _field:       PhantomData<F>,
+}
+
+impl<'a, F: Field> SharedWitness<'a, F> {
+    /// Checks the header of the witness stored in `bytes`, and that `bytes` is long
+    /// enough to hold all its values.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, SynthesisError> {
+        if bytes.len() < SHARED_WITNESS_HEADER_LEN {
+            return Err(invalid_data("shared witness too short"));
+        }
+        let mut header = &bytes[..SHARED_WITNESS_HEADER_LEN];
+        if <[u8; 4]>::read(&mut header)? != SHARED_WITNESS_MAGIC {
+            return Err(invalid_data("invalid witness magic bytes"));
+        }
+        if u8::read(&mut header)? != WITNESS_VERSION {
+            return Err(invalid_data("unsupported witness version"));
+        }
+        let element_size = u32::read(&mut header)?;
+        if element_size != serialized_size::<F>()? {
+            return Err(invalid_data("witness field element size mismatch"));
+        }
+        let num_inputs = u64::read(&mut header)?;
+        let num_aux = u64::read(&mut header)?;
+        if <[u8; 7]>::read(&mut header)? != [0u8; 7] {
+            return Err(invalid_data("invalid shared witness header"));
+        }
+
+        // Check the length of the values, taking care of overflows
+        let element_size = element_size as usize;
+        let values_len = |num: u64| {
+            if num > usize::max_value() as u64 {
+                return None;
+            }
+            (num as usize).checked_mul(element_size)
+        };
+        let (inputs_len, aux_len) = match (values_len(num_inputs), values_len(num_aux)) {
+            (Some(inputs_len), Some(aux_len)) => (inputs_len, aux_len),
+            _ => return Err(invalid_data("shared witness too short")),
+        };
+        let values = &bytes[SHARED_WITNESS_HEADER_LEN..];
+        match inputs_len.checked_add(aux_len) {
+            Some(len) if len <= values.len() => (),
+            _ => return Err(invalid_data("shared witness too short")),
+        }
+        let (inputs, values) = values.split_at(inputs_len);
+
+        Ok(Self {
+            inputs,
+            aux: &values[..aux_len],
+            element_size,
+            num_inputs: num_inputs as usize,
+            num_aux: num_aux as usize,
+            next_input: 0,
+            next_aux: 0,
+            _field: PhantomData,
+        })
+    }
+
+    /// Returns the number of input variables of the witness, including the "one" input.
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    /// Returns the number of auxiliary variables of the witness.
+    pub fn num_aux(&self) -> usize {
+        self.num_aux
+    }
+
+    /// Checks that the whole witness has been consumed, returning the number of
+    /// input and auxiliary variables read.
+    pub fn finish(self) -> Result<(usize, usize), SynthesisError> {
+        if self.next_input != self.num_inputs || self.next_aux != self.num_aux {
+            return Err(invalid_data("witness not fully consumed"));
+        }
+        Ok((self.num_inputs, self.num_aux))
+    }
+
+    fn read_value(values: &[u8], element_size: usize, index: usize) -> Result<F, SynthesisError> {
+        let start = index * element_size;
+        Ok(F::read(&values[start..start + element_size])?)
+    }
+}
+
+impl<F: Field> WitnessSource<F> for SharedWitness<'_, F> {
+    fn next_input(&mut self) -> Result<F, SynthesisError> {
+        if self.next_input == self.num_inputs {
+            return Err(SynthesisError::AssignmentMissing);
+        }
+        let value = Self::read_value(self.inputs, self.element_size, self.next_input)?;
+        self.next_input += 1;
+        Ok(value)
+    }
+
+    fn next_aux(&mut self) -> Result<F, SynthesisError> {
+        if self.next_aux == self.num_aux {
+            return Err(SynthesisError::AssignmentMissing);
+        }
+        let value = Self::read_value(self.aux, self.element_size, self.next_aux)?;
+        self.next_aux += 1;
+        Ok(value)
+    }
+}
+
+/// A constraint system wrapping `CS` which assigns the variables allocated on it
+/// with the values of a witness, in allocation order, instead of calling the
+/// assignment closures. Synthesizing a circuit without values on it feeds the
+/// witness to `CS`.
+pub struct WitnessReplay<'a, F: Field, CS: ConstraintSystem<F>, S: WitnessSource<F>> {
+    cs:      &'a mut CS,
+    witness: &'a mut S,
+    _field:  PhantomData<F>,
+}
+
+impl<'a, F: Field, CS: ConstraintSystem<F>, S: WitnessSource<F>> WitnessReplay<'a, F, CS, S> {
+    /// Replays `witness` on `cs`.
+    pub fn new(cs: &'a mut CS, witness: &'a mut S) -> Self {
+        Self { cs, witness, _field: PhantomData }
+    }
+}
+
+impl<F: Field, CS: ConstraintSystem<F>, S: WitnessSource<F>> ConstraintSystem<F>
+    for WitnessReplay<'_, F, CS, S>
+{
+    type Root = Self;
+
+    #[inline]
+    fn one() -> Variable {
+        CS::one()
+    }
+
+    #[inline]
+    fn alloc<FN, A, AR>(&mut self, annotation: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let value = self.witness.next_aux()?;
+        self.cs.alloc(annotation, || Ok(value))
+    }
+
+    #[inline]
+    fn alloc_input<FN, A, AR>(&mut self, annotation: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let value = self.witness.next_input()?;
+        self.cs.alloc_input(annotation, || Ok(value))
+    }
+
+    #[inline]
+    fn enforce<A, AR, LA, LB, LC>(&mut self, annotation: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.cs.enforce(annotation, a, b, c)
+    }
+
+    #[inline]
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.cs.get_root().push_namespace(name_fn)
+    }
+
+    #[inline]
+    fn pop_namespace(&mut self) {
+        self.cs.get_root().pop_namespace()
+    }
+
+    #[inline]
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    #[inline]
+    fn num_constraints(&self) -> usize {
+        self.cs.num_constraints()
+    }
+}
+
+/// A witness in the shared memory layout, mapped read-only in memory from a file,
+/// such as a memfd received from the witness generator or a file in a tmpfs.
+#[cfg(feature = "shared-memory")]
+pub struct MappedWitness {
+    mmap: memmap::Mmap,
+}
+
+#[cfg(feature = "shared-memory")]
+impl MappedWitness {
+    /// Maps `file` in memory. The file must not be modified, nor truncated, as long
+    /// as it is mapped.
+    #[allow(unsafe_code)]
+    pub fn new(file: &std::fs::File) -> Result<Self, SynthesisError> {
+        // Safe as long as the file is not modified while mapped, as required above
+        let mmap = unsafe { memmap::Mmap::map(file)? };
+        Ok(Self { mmap })
+    }
+
+    /// Returns the witness stored in the mapping, checking its header.
+    pub fn witness<F: Field>(&self) -> Result<SharedWitness<'_, F>, SynthesisError> {
+        SharedWitness::new(&self.mmap)
+    }
+}
@@ -7,6 +7,7 @@ use std::fmt::Debug;
 pub mod blake2s;
 pub mod injective_map;
 pub mod pedersen;
+pub mod vector;
 
 pub trait CommitmentGadget<C: CommitmentScheme, ConstraintF: Field> {
     type OutputGadget: EqGadget<ConstraintF>
@@ -0,0 +1,167 @@
+use primitives::{
+    commitment::vector::FieldBasedMerkleVectorCommitment,
+    crh::FieldBasedHash,
+    merkle_tree::field_based_mht::FieldBasedMerkleTreeConfig,
+};
+use algebra::{Field, PrimeField};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+
+use crate::{
+    commitment::vector::VectorCommitmentGadget,
+    crh::FieldBasedHashGadget,
+    merkle_tree::field_based_mht::FieldBasedMerkleTreePathGadget,
+};
+use r1cs_std::prelude::*;
+use std::{borrow::Borrow, marker::PhantomData};
+
+#[derive(Clone)]
+pub struct FieldBasedMerkleVectorCommitmentParametersGadget;
+
+pub struct FieldBasedMerkleVectorCommitmentGadget<P, HGadget, ConstraintF>
+    where
+        P: FieldBasedMerkleTreeConfig,
+        P::H: FieldBasedHash<Data = ConstraintF>,
+        HGadget: FieldBasedHashGadget<P::H, ConstraintF>,
+        ConstraintF: PrimeField,
+{
+    _params:      PhantomData<P>,
+    _hash_gadget: PhantomData<HGadget>,
+    _field:       PhantomData<ConstraintF>,
+}
+
+impl<P, HGadget, ConstraintF> VectorCommitmentGadget<FieldBasedMerkleVectorCommitment<P>, ConstraintF>
+for FieldBasedMerkleVectorCommitmentGadget<P, HGadget, ConstraintF>
+    where
+        P: FieldBasedMerkleTreeConfig,
+        P::H: FieldBasedHash<Data = ConstraintF>,
+        HGadget: FieldBasedHashGadget<P::H, ConstraintF>,
+        ConstraintF: PrimeField,
+{
+    type ParametersGadget = FieldBasedMerkleVectorCommitmentParametersGadget;
+    type MessageGadget = HGadget::DataGadget;
+    type CommitmentGadget = HGadget::DataGadget;
+    type OpeningGadget = FieldBasedMerkleTreePathGadget<P, HGadget, ConstraintF>;
+
+    /// Enforces the membership of `message` with respect to the root `commitment`,
+    /// and that the directions of the path are the bits of `index`.
+    fn conditionally_check_opening_gadget<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        _parameters: &Self::ParametersGadget,
+        commitment: &Self::CommitmentGadget,
+        index: &[Boolean],
+        message: &Self::MessageGadget,
+        opening: &Self::OpeningGadget,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        opening.conditionally_check_leaf_index(cs.ns(|| "check index"), index, should_enforce)?;
+        opening.conditionally_check_membership(cs.ns(|| "check membership"), commitment, message, should_enforce)
+    }
+}
+
+impl<ConstraintF: Field> AllocGadget<(), ConstraintF> for FieldBasedMerkleVectorCommitmentParametersGadget {
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(_: CS, _: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<()>,
+    {
+        Ok(FieldBasedMerkleVectorCommitmentParametersGadget)
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        _: CS,
+        _: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<()>,
+    {
+        Ok(FieldBasedMerkleVectorCommitmentParametersGadget)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use primitives::{
+        commitment::vector::{FieldBasedMerkleVectorCommitment, VectorCommitment},
+        crh::MNT4PoseidonHash,
+        merkle_tree::field_based_mht::FieldBasedMerkleTreeConfig,
+    };
+    use crate::{
+        commitment::vector::{
+            FieldBasedMerkleVectorCommitmentGadget, FieldBasedMerkleVectorCommitmentParametersGadget,
+            VectorCommitmentGadget,
+        },
+        crh::MNT4PoseidonHashGadget,
+        merkle_tree::field_based_mht::FieldBasedMerkleTreePathGadget,
+    };
+    use algebra::{fields::mnt4753::Fr, UniformRand};
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{
+        fields::mnt6753::FqGadget, prelude::*, test_constraint_system::TestConstraintSystem,
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    struct MNT4753FieldBasedMerkleTreeParams;
+
+    impl FieldBasedMerkleTreeConfig for MNT4753FieldBasedMerkleTreeParams {
+        const HEIGHT: usize = 4;
+        type H = MNT4PoseidonHash;
+    }
+
+    type VC = FieldBasedMerkleVectorCommitment<MNT4753FieldBasedMerkleTreeParams>;
+    type HG = MNT4PoseidonHashGadget;
+    type VCGadget = FieldBasedMerkleVectorCommitmentGadget<MNT4753FieldBasedMerkleTreeParams, HG, Fr>;
+
+    fn check_opening(index: usize, opened_index: usize, message: Fr, use_bad_message: bool) -> bool {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let mut messages = (0..8).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        messages[index] = message;
+        let (commitment, committed) = VC::commit(&(), &messages).unwrap();
+        let opening = VC::open(&(), &committed, opened_index).unwrap();
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let parameters_g = FieldBasedMerkleVectorCommitmentParametersGadget::alloc(
+            cs.ns(|| "alloc parameters"),
+            || Ok(()),
+        ).unwrap();
+        let commitment_g = FqGadget::alloc(cs.ns(|| "alloc commitment"), || Ok(commitment)).unwrap();
+        let message_g = FqGadget::alloc(
+            cs.ns(|| "alloc message"),
+            || if use_bad_message { Ok(Fr::rand(rng)) } else { Ok(message) },
+        ).unwrap();
+        let opening_g = FieldBasedMerkleTreePathGadget::<_, HG, _>::alloc(
+            cs.ns(|| "alloc opening"),
+            || Ok(opening),
+        ).unwrap();
+        let index_g = (0..3)
+            .map(|i| Boolean::alloc(cs.ns(|| format!("alloc index bit {}", i)), || Ok((index >> i) & 1 == 1)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        <VCGadget as VectorCommitmentGadget<VC, Fr>>::check_opening_gadget(
+            cs.ns(|| "check opening"),
+            &parameters_g,
+            &commitment_g,
+            &index_g,
+            &message_g,
+            &opening_g,
+        ).unwrap();
+
+        cs.is_satisfied()
+    }
+
+    #[test]
+    fn field_based_merkle_vector_commitment_gadget_test() {
+        let rng = &mut XorShiftRng::seed_from_u64(9174123u64);
+        let message = Fr::rand(rng);
+
+        assert!(check_opening(5, 5, message, false));
+
+        // Wrong message
+        assert!(!check_opening(5, 5, message, true));
+
+        // Opening of another position
+        assert!(!check_opening(5, 4, message, false));
+    }
+}
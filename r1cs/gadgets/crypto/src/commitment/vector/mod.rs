@@ -0,0 +1,55 @@
+use primitives::commitment::vector::VectorCommitment;
+use algebra::Field;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+use std::fmt::Debug;
+
+pub mod pedersen;
+pub use self::pedersen::*;
+
+#[cfg(feature = "merkle_tree")]
+pub mod field_based_mht;
+#[cfg(feature = "merkle_tree")]
+pub use self::field_based_mht::*;
+
+pub trait VectorCommitmentGadget<VC: VectorCommitment, ConstraintF: Field> {
+    type ParametersGadget: AllocGadget<VC::Parameters, ConstraintF> + Clone;
+    type MessageGadget: AllocGadget<VC::Message, ConstraintF> + Clone;
+    type CommitmentGadget: EqGadget<ConstraintF>
+        + AllocGadget<VC::Commitment, ConstraintF>
+        + Clone
+        + Sized
+        + Debug;
+    type OpeningGadget: AllocGadget<VC::Opening, ConstraintF>;
+
+    /// Enforces that `opening` opens `commitment` to `message` at the position
+    /// given by the little endian bits `index`.
+    fn check_opening_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        commitment: &Self::CommitmentGadget,
+        index: &[Boolean],
+        message: &Self::MessageGadget,
+        opening: &Self::OpeningGadget,
+    ) -> Result<(), SynthesisError> {
+        Self::conditionally_check_opening_gadget(
+            cs,
+            parameters,
+            commitment,
+            index,
+            message,
+            opening,
+            &Boolean::Constant(true),
+        )
+    }
+
+    fn conditionally_check_opening_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        commitment: &Self::CommitmentGadget,
+        index: &[Boolean],
+        message: &Self::MessageGadget,
+        opening: &Self::OpeningGadget,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError>;
+}
@@ -0,0 +1,303 @@
+use primitives::commitment::vector::{PedersenVectorCommitment, PedersenVectorCommitmentParameters};
+use algebra::{BitIterator, FpParameters, Group, PrimeField};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+
+use crate::commitment::vector::VectorCommitmentGadget;
+use r1cs_std::{prelude::*, Assignment};
+use std::{borrow::Borrow, marker::PhantomData};
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group, ConstraintF: PrimeField"))]
+pub struct PedersenVectorCommitmentGadgetParameters<G: Group, ConstraintF: PrimeField> {
+    params: PedersenVectorCommitmentParameters<G>,
+    #[doc(hidden)]
+    _engine: PhantomData<ConstraintF>,
+}
+
+/// A scalar of the group, as its little endian bits.
+#[derive(Clone, Debug)]
+pub struct PedersenVectorMessageGadget(pub Vec<Boolean>);
+
+#[derive(Clone, Debug)]
+pub struct PedersenVectorOpeningGadget(pub Vec<PedersenVectorMessageGadget>);
+
+pub struct PedersenVectorCommitmentGadget<G: Group, ConstraintF: PrimeField, GG: GroupGadget<G, ConstraintF>>(
+    #[doc(hidden)] PhantomData<*const G>,
+    #[doc(hidden)] PhantomData<*const GG>,
+    PhantomData<ConstraintF>,
+);
+
+impl<ConstraintF, G, GG> VectorCommitmentGadget<PedersenVectorCommitment<G>, ConstraintF>
+    for PedersenVectorCommitmentGadget<G, ConstraintF, GG>
+where
+    ConstraintF: PrimeField,
+    G: Group,
+    GG: GroupGadget<G, ConstraintF>,
+{
+    type ParametersGadget = PedersenVectorCommitmentGadgetParameters<G, ConstraintF>;
+    type MessageGadget = PedersenVectorMessageGadget;
+    type CommitmentGadget = GG;
+    type OpeningGadget = PedersenVectorOpeningGadget;
+
+    /// Recomputes the commitment to the vector given by `opening`, and enforces that its
+    /// message at the position `index` is equal to `message`.
+    fn conditionally_check_opening_gadget<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        commitment: &Self::CommitmentGadget,
+        index: &[Boolean],
+        message: &Self::MessageGadget,
+        opening: &Self::OpeningGadget,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        let messages = &opening.0;
+        if messages.len() > parameters.params.generators.len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        // Compute m_0 * G_0 + ... + m_{n-1} * G_{n-1}
+        let result = GG::precomputed_base_multiscalar_mul(
+            cs.ns(|| "multiexp"),
+            &parameters.params.generators,
+            messages.iter().map(|message| message.0.as_slice()),
+        )?;
+        commitment.conditional_enforce_equal(
+            cs.ns(|| "check commitment"),
+            &result,
+            should_enforce,
+        )?;
+
+        // Enforce that the position `index` exists and holds `message`
+        let mut is_index_in_range = Boolean::constant(false);
+        for (i, opened_message) in messages.iter().enumerate() {
+            if index.len() < std::mem::size_of::<usize>() * 8 && i >> index.len() != 0 {
+                break;
+            }
+            let mut cs = cs.ns(|| format!("position {}", i));
+
+            let mut is_index = Boolean::constant(true);
+            for (j, bit) in index.iter().enumerate() {
+                let bit = if (i >> j) & 1 == 1 { *bit } else { bit.not() };
+                is_index = Boolean::and(cs.ns(|| format!("compare bit {}", j)), &is_index, &bit)?;
+            }
+            is_index_in_range = Boolean::or(cs.ns(|| "is index in range"), &is_index_in_range, &is_index)?;
+
+            let should_enforce_equal = Boolean::and(cs.ns(|| "should enforce equal"), &is_index, should_enforce)?;
+            opened_message.0.as_slice().conditional_enforce_equal(
+                cs.ns(|| "check message"),
+                message.0.as_slice(),
+                &should_enforce_equal,
+            )?;
+        }
+        is_index_in_range.conditional_enforce_equal(
+            cs.ns(|| "check index in range"),
+            &Boolean::constant(true),
+            should_enforce,
+        )
+    }
+}
+
+impl<G, ConstraintF> AllocGadget<PedersenVectorCommitmentParameters<G>, ConstraintF>
+    for PedersenVectorCommitmentGadgetParameters<G, ConstraintF>
+where
+    G: Group,
+    ConstraintF: PrimeField,
+{
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<PedersenVectorCommitmentParameters<G>>,
+    {
+        let params = value_gen()?.borrow().clone();
+        Ok(PedersenVectorCommitmentGadgetParameters { params, _engine: PhantomData })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<PedersenVectorCommitmentParameters<G>>,
+    {
+        Self::alloc(cs, value_gen)
+    }
+}
+
+// Returns the little endian bits of `value`, as many as the bits of the modulus.
+fn scalar_to_bits<SF: PrimeField>(value: Option<SF>) -> Vec<Option<bool>> {
+    let num_bits = SF::Params::MODULUS_BITS as usize;
+    match value {
+        Some(value) => {
+            let mut bits = BitIterator::new(value.into_repr()).map(Some).collect::<Vec<_>>();
+            bits.reverse();
+            bits.truncate(num_bits);
+            bits
+        },
+        None => vec![None; num_bits],
+    }
+}
+
+impl<SF, ConstraintF> AllocGadget<SF, ConstraintF> for PedersenVectorMessageGadget
+where
+    SF: PrimeField,
+    ConstraintF: PrimeField,
+{
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SF>,
+    {
+        let bits = scalar_to_bits(value_gen().map(|value| *value.borrow()).ok())
+            .into_iter()
+            .enumerate()
+            .map(|(i, bit)| Boolean::alloc(cs.ns(|| format!("bit {}", i)), || bit.get()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PedersenVectorMessageGadget(bits))
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SF>,
+    {
+        let bits = scalar_to_bits(value_gen().map(|value| *value.borrow()).ok())
+            .into_iter()
+            .enumerate()
+            .map(|(i, bit)| Boolean::alloc_input(cs.ns(|| format!("bit {}", i)), || bit.get()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PedersenVectorMessageGadget(bits))
+    }
+}
+
+impl<SF, ConstraintF> AllocGadget<Vec<SF>, ConstraintF> for PedersenVectorOpeningGadget
+where
+    SF: PrimeField,
+    ConstraintF: PrimeField,
+{
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<Vec<SF>>,
+    {
+        let messages = value_gen()?
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(i, message)| {
+                <PedersenVectorMessageGadget as AllocGadget<SF, ConstraintF>>::alloc(
+                    cs.ns(|| format!("message {}", i)),
+                    || Ok(message),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PedersenVectorOpeningGadget(messages))
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<Vec<SF>>,
+    {
+        let messages = value_gen()?
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(i, message)| {
+                <PedersenVectorMessageGadget as AllocGadget<SF, ConstraintF>>::alloc_input(
+                    cs.ns(|| format!("message {}", i)),
+                    || Ok(message),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PedersenVectorOpeningGadget(messages))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use algebra::{
+        curves::jubjub::JubJubProjective as JubJub,
+        fields::jubjub::{fq::Fq, fr::Fr},
+        UniformRand,
+    };
+    use primitives::commitment::vector::{PedersenVectorCommitment, VectorCommitment};
+    use crate::commitment::vector::{
+        PedersenVectorCommitmentGadget, PedersenVectorCommitmentGadgetParameters,
+        PedersenVectorMessageGadget, PedersenVectorOpeningGadget, VectorCommitmentGadget,
+    };
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{
+        groups::jubjub::JubJubGadget, prelude::*, test_constraint_system::TestConstraintSystem,
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type VC = PedersenVectorCommitment<JubJub>;
+    type VCGadget = PedersenVectorCommitmentGadget<JubJub, Fq, JubJubGadget>;
+
+    fn check_opening(index: usize, opened_index: usize, use_bad_message: bool) -> bool {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let parameters = VC::setup(5, rng).unwrap();
+        let messages = (0..5).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let (commitment, committed) = VC::commit(&parameters, &messages).unwrap();
+        let opening = VC::open(&parameters, &committed, opened_index).unwrap();
+        let message = if use_bad_message { Fr::rand(rng) } else { messages[index] };
+
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let parameters_g = PedersenVectorCommitmentGadgetParameters::alloc(
+            cs.ns(|| "alloc parameters"),
+            || Ok(&parameters),
+        ).unwrap();
+        let commitment_g = JubJubGadget::alloc(cs.ns(|| "alloc commitment"), || Ok(commitment)).unwrap();
+        let message_g = <PedersenVectorMessageGadget as AllocGadget<Fr, Fq>>::alloc(
+            cs.ns(|| "alloc message"),
+            || Ok(message),
+        ).unwrap();
+        let opening_g = <PedersenVectorOpeningGadget as AllocGadget<Vec<Fr>, Fq>>::alloc(
+            cs.ns(|| "alloc opening"),
+            || Ok(opening),
+        ).unwrap();
+        let index_g = (0..3)
+            .map(|i| Boolean::alloc(cs.ns(|| format!("alloc index bit {}", i)), || Ok((index >> i) & 1 == 1)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        <VCGadget as VectorCommitmentGadget<VC, Fq>>::check_opening_gadget(
+            cs.ns(|| "check opening"),
+            &parameters_g,
+            &commitment_g,
+            &index_g,
+            &message_g,
+            &opening_g,
+        ).unwrap();
+
+        cs.is_satisfied()
+    }
+
+    #[test]
+    fn pedersen_vector_commitment_gadget_test() {
+        for index in 0..5 {
+            assert!(check_opening(index, index, false));
+            assert!(!check_opening(index, index, true));
+        }
+
+        // The index is out of range
+        assert!(!check_opening(6, 0, true));
+    }
+}
@@ -0,0 +1,660 @@
+//! Consistency checks between the native primitives and their gadgets: on
+//! random inputs, the gadget must compute the same output as the primitive,
+//! with satisfied constraints, and must not be satisfiable with the output of
+//! another input. The checks are generic, so that the gadgets of new curves and
+//! parameters are covered by instantiating them in the tests of this module.
+//!
+//! The checks panic on failure, naming the unsatisfied constraint if any.
+use algebra::{curves::models::SWModelParameters, PrimeField, UniformRand};
+use digest::Digest;
+use primitives::{
+    crh::{FieldBasedHash, FixedLengthCRH},
+    merkle_tree::field_based_mht::{FieldBasedMerkleHashTree, FieldBasedMerkleTreeConfig},
+    signature::{
+        ecdsa::{EcdsaPublicKey, EcdsaSignature, EcdsaSignatureScheme},
+        FieldBasedSignatureScheme, SignatureScheme,
+    },
+    vrf::FieldBasedVrf,
+    CommitmentScheme,
+};
+use r1cs_core::ConstraintSystem;
+use r1cs_std::{prelude::*, test_constraint_system::TestConstraintSystem};
+use rand::Rng;
+
+use crate::{
+    commitment::CommitmentGadget,
+    crh::{FieldBasedHashGadget, FixedLengthCRHGadget},
+    merkle_tree::field_based_mht::FieldBasedMerkleTreePathGadget,
+    signature::{
+        ecdsa::{EcdsaPublicKeyGadget, EcdsaSigVerificationGadget, EcdsaSignatureGadget},
+        FieldBasedSigGadget, SigRandomizePkGadget,
+    },
+    vrf::FieldBasedVrfGadget,
+};
+
+fn assert_satisfied<ConstraintF: PrimeField>(cs: &TestConstraintSystem<ConstraintF>, what: &str) {
+    assert!(
+        cs.is_satisfied(),
+        "{}: unsatisfied constraint {}",
+        what,
+        cs.which_is_unsatisfied().unwrap()
+    );
+}
+
+fn random_bytes<R: Rng>(rng: &mut R, len: usize) -> Vec<u8> {
+    (0..len).map(|_| rng.gen()).collect()
+}
+
+// Returns a copy of `input` with one bit flipped.
+fn tamper<R: Rng>(rng: &mut R, input: &[u8]) -> Vec<u8> {
+    let mut tampered = input.to_vec();
+    let i = rng.gen_range(0, tampered.len());
+    tampered[i] ^= 1 << rng.gen_range(0, 8);
+    tampered
+}
+
+// Evaluates the gadget of `H` on `input` and enforces the result to be `output`,
+// returning the constraint system.
+fn synthesize_fixed_length_crh<ConstraintF, H, HG>(
+    parameters: &H::Parameters,
+    input: &[u8],
+    output: &H::Output,
+) -> TestConstraintSystem<ConstraintF>
+where
+    ConstraintF: PrimeField,
+    H: FixedLengthCRH,
+    HG: FixedLengthCRHGadget<H, ConstraintF>,
+{
+    let mut cs = TestConstraintSystem::<ConstraintF>::new();
+    let parameters_g = HG::ParametersGadget::alloc(cs.ns(|| "alloc parameters"), || Ok(parameters)).unwrap();
+    let input_g = UInt8::alloc_vec(cs.ns(|| "alloc input"), input).unwrap();
+    let output_g = HG::check_evaluation_gadget(cs.ns(|| "evaluate"), &parameters_g, &input_g).unwrap();
+    let expected_g = HG::OutputGadget::alloc(cs.ns(|| "alloc expected output"), || Ok(output)).unwrap();
+    output_g.enforce_equal(cs.ns(|| "output == expected output"), &expected_g).unwrap();
+    cs
+}
+
+/// Checks the gadget `HG` of the hash `H` on `num_samples` random inputs of
+/// `H::INPUT_SIZE_BITS / 8` bytes.
+pub fn check_fixed_length_crh<ConstraintF, H, HG, R>(rng: &mut R, num_samples: usize)
+where
+    ConstraintF: PrimeField,
+    H: FixedLengthCRH,
+    HG: FixedLengthCRHGadget<H, ConstraintF>,
+    R: Rng,
+{
+    for _ in 0..num_samples {
+        let parameters = H::setup(rng).unwrap();
+        let input = random_bytes(rng, H::INPUT_SIZE_BITS / 8);
+        let output = H::evaluate(&parameters, &input).unwrap();
+        let cs = synthesize_fixed_length_crh::<ConstraintF, H, HG>(&parameters, &input, &output);
+        assert_satisfied(&cs, "fixed length CRH");
+
+        let other_output = H::evaluate(&parameters, &tamper(rng, &input)).unwrap();
+        let cs = synthesize_fixed_length_crh::<ConstraintF, H, HG>(&parameters, &input, &other_output);
+        assert!(!cs.is_satisfied(), "fixed length CRH: output of another input accepted");
+    }
+}
+
+// Evaluates the gadget of `H` on `input` and enforces the result to be `output`,
+// returning the constraint system and the value computed by the gadget.
+fn synthesize_field_based_hash<ConstraintF, H, HG>(
+    input: &[ConstraintF],
+    output: &ConstraintF,
+) -> (TestConstraintSystem<ConstraintF>, Option<ConstraintF>)
+where
+    ConstraintF: PrimeField,
+    H: FieldBasedHash<Data = ConstraintF>,
+    HG: FieldBasedHashGadget<H, ConstraintF>,
+{
+    let mut cs = TestConstraintSystem::<ConstraintF>::new();
+    let input_g = input
+        .iter()
+        .enumerate()
+        .map(|(i, x)| HG::DataGadget::alloc(cs.ns(|| format!("alloc input {}", i)), || Ok(x)).unwrap())
+        .collect::<Vec<_>>();
+    let output_g = HG::check_evaluation_gadget(cs.ns(|| "evaluate"), &input_g).unwrap();
+    let expected_g = HG::DataGadget::alloc(cs.ns(|| "alloc expected output"), || Ok(output)).unwrap();
+    output_g.enforce_equal(cs.ns(|| "output == expected output"), &expected_g).unwrap();
+    let value = output_g.get_value();
+    (cs, value)
+}
+
+/// Checks the gadget `HG` of the hash `H` on `num_samples` random inputs of
+/// `input_len` field elements.
+pub fn check_field_based_hash<ConstraintF, H, HG, R>(rng: &mut R, input_len: usize, num_samples: usize)
+where
+    ConstraintF: PrimeField,
+    H: FieldBasedHash<Data = ConstraintF>,
+    HG: FieldBasedHashGadget<H, ConstraintF>,
+    R: Rng,
+{
+    for _ in 0..num_samples {
+        let input = (0..input_len).map(|_| ConstraintF::rand(rng)).collect::<Vec<_>>();
+        let output = H::evaluate(&input).unwrap();
+        let (cs, value) = synthesize_field_based_hash::<ConstraintF, H, HG>(&input, &output);
+        assert_satisfied(&cs, "field based hash");
+        assert_eq!(value, Some(output));
+
+        let mut other_input = input.clone();
+        other_input[rng.gen_range(0, input_len)] += &ConstraintF::one();
+        let other_output = H::evaluate(&other_input).unwrap();
+        let (cs, _) = synthesize_field_based_hash::<ConstraintF, H, HG>(&input, &other_output);
+        assert!(!cs.is_satisfied(), "field based hash: output of another input accepted");
+    }
+}
+
+// Computes the commitment to `input` with the gadget of `C` and enforces it to
+// be `output`, returning the constraint system.
+fn synthesize_commitment<ConstraintF, C, CG>(
+    parameters: &C::Parameters,
+    input: &[u8],
+    randomness: &C::Randomness,
+    output: &C::Output,
+) -> TestConstraintSystem<ConstraintF>
+where
+    ConstraintF: PrimeField,
+    C: CommitmentScheme,
+    CG: CommitmentGadget<C, ConstraintF>,
+{
+    let mut cs = TestConstraintSystem::<ConstraintF>::new();
+    let parameters_g = CG::ParametersGadget::alloc(cs.ns(|| "alloc parameters"), || Ok(parameters)).unwrap();
+    let input_g = UInt8::alloc_vec(cs.ns(|| "alloc input"), input).unwrap();
+    let randomness_g = CG::RandomnessGadget::alloc(cs.ns(|| "alloc randomness"), || Ok(randomness)).unwrap();
+    let output_g = CG::check_commitment_gadget(cs.ns(|| "commit"), &parameters_g, &input_g, &randomness_g).unwrap();
+    let expected_g = CG::OutputGadget::alloc(cs.ns(|| "alloc expected output"), || Ok(output)).unwrap();
+    output_g.enforce_equal(cs.ns(|| "output == expected output"), &expected_g).unwrap();
+    cs
+}
+
+/// Checks the gadget `CG` of the commitment scheme `C` on `num_samples` random
+/// inputs of `input_len` bytes and random randomnesses.
+pub fn check_commitment<ConstraintF, C, CG, R>(rng: &mut R, input_len: usize, num_samples: usize)
+where
+    ConstraintF: PrimeField,
+    C: CommitmentScheme,
+    CG: CommitmentGadget<C, ConstraintF>,
+    R: Rng,
+{
+    for _ in 0..num_samples {
+        let parameters = C::setup(rng).unwrap();
+        let input = random_bytes(rng, input_len);
+        let randomness = C::Randomness::rand(rng);
+        let output = C::commit(&parameters, &input, &randomness).unwrap();
+        let cs = synthesize_commitment::<ConstraintF, C, CG>(&parameters, &input, &randomness, &output);
+        assert_satisfied(&cs, "commitment");
+
+        let other_output = C::commit(&parameters, &tamper(rng, &input), &randomness).unwrap();
+        let cs = synthesize_commitment::<ConstraintF, C, CG>(&parameters, &input, &randomness, &other_output);
+        assert!(!cs.is_satisfied(), "commitment: output of another input accepted");
+    }
+}
+
+// Verifies `signature` with the gadget of `S`, returning the constraint system
+// and the verdict of the gadget.
+fn synthesize_signature_verification<ConstraintF, S, SG>(
+    public_key: &S::PublicKey,
+    signature: &S::Signature,
+    message: &[ConstraintF],
+) -> (TestConstraintSystem<ConstraintF>, Option<bool>)
+where
+    ConstraintF: PrimeField,
+    S: FieldBasedSignatureScheme<Data = ConstraintF>,
+    SG: FieldBasedSigGadget<S, ConstraintF>,
+{
+    let mut cs = TestConstraintSystem::<ConstraintF>::new();
+    let public_key_g = SG::PublicKeyGadget::alloc(cs.ns(|| "alloc public key"), || Ok(public_key)).unwrap();
+    let signature_g = SG::SignatureGadget::alloc(cs.ns(|| "alloc signature"), || Ok(signature)).unwrap();
+    let message_g = message
+        .iter()
+        .enumerate()
+        .map(|(i, x)| SG::DataGadget::alloc(cs.ns(|| format!("alloc message {}", i)), || Ok(x)).unwrap())
+        .collect::<Vec<_>>();
+    let verdict = SG::enforce_signature_verdict(cs.ns(|| "verdict"), &public_key_g, &signature_g, &message_g)
+        .unwrap()
+        .get_value();
+    SG::enforce_signature_verification(cs.ns(|| "verify"), &public_key_g, &signature_g, &message_g).unwrap();
+    (cs, verdict)
+}
+
+/// Checks the verification gadget `SG` of the signature scheme `S` on
+/// `num_samples` signatures of random messages of `message_len` field elements,
+/// and on the same signatures with another message.
+pub fn check_field_based_signature<ConstraintF, S, SG, R>(rng: &mut R, message_len: usize, num_samples: usize)
+where
+    ConstraintF: PrimeField,
+    S: FieldBasedSignatureScheme<Data = ConstraintF>,
+    SG: FieldBasedSigGadget<S, ConstraintF>,
+    R: Rng,
+{
+    for _ in 0..num_samples {
+        let (public_key, secret_key) = S::keygen(rng);
+        let message = (0..message_len).map(|_| ConstraintF::rand(rng)).collect::<Vec<_>>();
+        let signature = S::sign(rng, &public_key, &secret_key, &message).unwrap();
+        assert!(S::verify(&public_key, &message, &signature).unwrap());
+        let (cs, verdict) =
+            synthesize_signature_verification::<ConstraintF, S, SG>(&public_key, &signature, &message);
+        assert_satisfied(&cs, "signature verification");
+        assert_eq!(verdict, Some(true));
+
+        let mut other_message = message.clone();
+        other_message[rng.gen_range(0, message_len)] += &ConstraintF::one();
+        assert!(!S::verify(&public_key, &other_message, &signature).unwrap());
+        let (cs, verdict) =
+            synthesize_signature_verification::<ConstraintF, S, SG>(&public_key, &signature, &other_message);
+        assert!(!cs.is_satisfied(), "signature verification: signature of another message accepted");
+        assert_eq!(verdict, Some(false));
+    }
+}
+
+// Randomizes `public_key` with the gadget of `S` and enforces the result to be
+// `output`, returning the constraint system.
+fn synthesize_public_key_randomization<ConstraintF, S, SG>(
+    parameters: &S::Parameters,
+    public_key: &S::PublicKey,
+    randomness: &[u8],
+    output: &S::PublicKey,
+) -> TestConstraintSystem<ConstraintF>
+where
+    ConstraintF: PrimeField,
+    S: SignatureScheme,
+    SG: SigRandomizePkGadget<S, ConstraintF>,
+{
+    let mut cs = TestConstraintSystem::<ConstraintF>::new();
+    let parameters_g = SG::ParametersGadget::alloc(cs.ns(|| "alloc parameters"), || Ok(parameters)).unwrap();
+    let public_key_g = SG::PublicKeyGadget::alloc(cs.ns(|| "alloc public key"), || Ok(public_key)).unwrap();
+    let randomness_g = UInt8::alloc_vec(cs.ns(|| "alloc randomness"), randomness).unwrap();
+    let output_g =
+        SG::check_randomization_gadget(cs.ns(|| "randomize"), &parameters_g, &public_key_g, &randomness_g).unwrap();
+    let expected_g = SG::PublicKeyGadget::alloc(cs.ns(|| "alloc expected output"), || Ok(output)).unwrap();
+    output_g.enforce_equal(cs.ns(|| "output == expected output"), &expected_g).unwrap();
+    cs
+}
+
+/// Checks the public key randomization gadget `SG` of the signature scheme `S`
+/// on `num_samples` random keys and randomnesses of `randomness_len` bytes.
+pub fn check_public_key_randomization<ConstraintF, S, SG, R>(rng: &mut R, randomness_len: usize, num_samples: usize)
+where
+    ConstraintF: PrimeField,
+    S: SignatureScheme,
+    SG: SigRandomizePkGadget<S, ConstraintF>,
+    R: Rng,
+{
+    for _ in 0..num_samples {
+        let parameters = S::setup(rng).unwrap();
+        let (public_key, _) = S::keygen(&parameters, rng).unwrap();
+        let randomness = random_bytes(rng, randomness_len);
+        let output = S::randomize_public_key(&parameters, &public_key, &randomness).unwrap();
+        let cs = synthesize_public_key_randomization::<ConstraintF, S, SG>(&parameters, &public_key, &randomness, &output);
+        assert_satisfied(&cs, "public key randomization");
+
+        let other_output = S::randomize_public_key(&parameters, &public_key, &tamper(rng, &randomness)).unwrap();
+        let cs =
+            synthesize_public_key_randomization::<ConstraintF, S, SG>(&parameters, &public_key, &randomness, &other_output);
+        assert!(!cs.is_satisfied(), "public key randomization: output of another randomness accepted");
+    }
+}
+
+// Verifies the ECDSA `signature` of the message with hash `message_hash` with
+// the gadget, returning the constraint system.
+fn synthesize_ecdsa_verification<ConstraintF, P>(
+    public_key: &EcdsaPublicKey<P>,
+    signature: &EcdsaSignature<P>,
+    message_hash: &[u8],
+) -> TestConstraintSystem<ConstraintF>
+where
+    ConstraintF: PrimeField,
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+{
+    let mut cs = TestConstraintSystem::<ConstraintF>::new();
+    let public_key_g =
+        EcdsaPublicKeyGadget::<P, ConstraintF>::alloc(cs.ns(|| "alloc public key"), || Ok(public_key)).unwrap();
+    let signature_g =
+        EcdsaSignatureGadget::<P, ConstraintF>::alloc(cs.ns(|| "alloc signature"), || Ok(signature)).unwrap();
+    let message_hash_g = message_hash
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+        .enumerate()
+        .map(|(i, bit)| Boolean::alloc(cs.ns(|| format!("alloc message hash bit {}", i)), || Ok(bit)).unwrap())
+        .collect::<Vec<_>>();
+    EcdsaSigVerificationGadget::<P, ConstraintF>::enforce_signature_verification(
+        cs.ns(|| "verify"),
+        &public_key_g,
+        &signature_g,
+        &message_hash_g,
+    )
+    .unwrap();
+    cs
+}
+
+/// Checks the ECDSA verification gadget over the curve `P`, with the hash `D`,
+/// on `num_samples` signatures of random messages of `message_len` bytes, and on
+/// the same signatures with another message.
+pub fn check_ecdsa_signature<ConstraintF, P, D, R>(rng: &mut R, message_len: usize, num_samples: usize)
+where
+    ConstraintF: PrimeField,
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    D: Digest,
+    R: Rng,
+{
+    for _ in 0..num_samples {
+        let (public_key, secret_key) = EcdsaSignatureScheme::<P, D>::keygen(rng);
+        let message = random_bytes(rng, message_len);
+        let signature = EcdsaSignatureScheme::<P, D>::sign(rng, &secret_key, &message).unwrap();
+        assert!(EcdsaSignatureScheme::<P, D>::verify(&public_key, &message, &signature).unwrap());
+        let cs = synthesize_ecdsa_verification::<ConstraintF, P>(&public_key, &signature, &D::digest(&message));
+        assert_satisfied(&cs, "ECDSA verification");
+
+        let other_message = tamper(rng, &message);
+        assert!(!EcdsaSignatureScheme::<P, D>::verify(&public_key, &other_message, &signature).unwrap());
+        let cs = synthesize_ecdsa_verification::<ConstraintF, P>(&public_key, &signature, &D::digest(&other_message));
+        assert!(!cs.is_satisfied(), "ECDSA verification: signature of another message accepted");
+    }
+}
+
+// Verifies the VRF `proof` with the gadget of `S` and enforces the VRF output to
+// be `output`, returning the constraint system and the output computed by the
+// gadget.
+fn synthesize_vrf_verification<ConstraintF, S, SG>(
+    parameters: &S::GHParams,
+    public_key: &S::PublicKey,
+    proof: &S::Proof,
+    message: &[ConstraintF],
+    output: &ConstraintF,
+) -> (TestConstraintSystem<ConstraintF>, Option<ConstraintF>)
+where
+    ConstraintF: PrimeField,
+    S: FieldBasedVrf<Data = ConstraintF>,
+    SG: FieldBasedVrfGadget<S, ConstraintF>,
+{
+    let mut cs = TestConstraintSystem::<ConstraintF>::new();
+    let parameters_g = SG::GHParametersGadget::alloc(cs.ns(|| "alloc parameters"), || Ok(parameters)).unwrap();
+    let public_key_g = SG::PublicKeyGadget::alloc(cs.ns(|| "alloc public key"), || Ok(public_key)).unwrap();
+    let proof_g = SG::ProofGadget::alloc(cs.ns(|| "alloc proof"), || Ok(proof)).unwrap();
+    let message_g = message
+        .iter()
+        .enumerate()
+        .map(|(i, x)| SG::DataGadget::alloc(cs.ns(|| format!("alloc message {}", i)), || Ok(x)).unwrap())
+        .collect::<Vec<_>>();
+    let output_g = SG::enforce_proof_to_hash_verification(
+        cs.ns(|| "verify"),
+        &parameters_g,
+        &public_key_g,
+        &proof_g,
+        &message_g,
+    )
+    .unwrap();
+    let expected_g = SG::DataGadget::alloc(cs.ns(|| "alloc expected output"), || Ok(output)).unwrap();
+    output_g.enforce_equal(cs.ns(|| "output == expected output"), &expected_g).unwrap();
+    let value = output_g.get_value();
+    (cs, value)
+}
+
+/// Checks the verification gadget `SG` of the VRF `S`, with the group hash
+/// parameters `parameters`, on `num_samples` proofs of random messages of
+/// `message_len` field elements, and on the same proofs with another message.
+pub fn check_field_based_vrf<ConstraintF, S, SG, R>(
+    rng: &mut R,
+    parameters: &S::GHParams,
+    message_len: usize,
+    num_samples: usize,
+) where
+    ConstraintF: PrimeField,
+    S: FieldBasedVrf<Data = ConstraintF>,
+    SG: FieldBasedVrfGadget<S, ConstraintF>,
+    R: Rng,
+{
+    for _ in 0..num_samples {
+        let (public_key, secret_key) = S::keygen(rng);
+        let message = (0..message_len).map(|_| ConstraintF::rand(rng)).collect::<Vec<_>>();
+        let proof = S::prove(rng, parameters, &public_key, &secret_key, &message).unwrap();
+        let output = S::proof_to_hash(parameters, &public_key, &message, &proof).unwrap();
+        let (cs, value) =
+            synthesize_vrf_verification::<ConstraintF, S, SG>(parameters, &public_key, &proof, &message, &output);
+        assert_satisfied(&cs, "VRF verification");
+        assert_eq!(value, Some(output));
+
+        let mut other_message = message.clone();
+        other_message[rng.gen_range(0, message_len)] += &ConstraintF::one();
+        assert!(S::proof_to_hash(parameters, &public_key, &other_message, &proof).is_err());
+        let (cs, _) =
+            synthesize_vrf_verification::<ConstraintF, S, SG>(parameters, &public_key, &proof, &other_message, &output);
+        assert!(!cs.is_satisfied(), "VRF verification: proof of another message accepted");
+    }
+}
+
+// Checks the membership of `leaf` in the tree with root `root` with the path
+// gadget, returning the constraint system.
+fn synthesize_merkle_path<ConstraintF, P, HG>(
+    tree: &FieldBasedMerkleHashTree<P>,
+    index: usize,
+    leaf: &ConstraintF,
+    root: &ConstraintF,
+) -> TestConstraintSystem<ConstraintF>
+where
+    ConstraintF: PrimeField,
+    P: FieldBasedMerkleTreeConfig,
+    P::H: FieldBasedHash<Data = ConstraintF>,
+    HG: FieldBasedHashGadget<P::H, ConstraintF>,
+{
+    let mut cs = TestConstraintSystem::<ConstraintF>::new();
+    let path = tree.generate_proof(index, leaf).unwrap();
+    let path_g =
+        FieldBasedMerkleTreePathGadget::<P, HG, ConstraintF>::alloc(cs.ns(|| "alloc path"), || Ok(path)).unwrap();
+    let leaf_g = HG::DataGadget::alloc(cs.ns(|| "alloc leaf"), || Ok(leaf)).unwrap();
+    let root_g = HG::DataGadget::alloc(cs.ns(|| "alloc root"), || Ok(root)).unwrap();
+    path_g.check_membership(cs.ns(|| "check membership"), &root_g, &leaf_g).unwrap();
+    cs
+}
+
+/// Checks the path gadget of the field based Merkle tree `P`, with the hash
+/// gadget `HG`, on `num_samples` leaves of a tree of `num_leaves` random leaves,
+/// against the root of the tree and against the root of another tree.
+pub fn check_field_based_merkle_tree<ConstraintF, P, HG, R>(rng: &mut R, num_leaves: usize, num_samples: usize)
+where
+    ConstraintF: PrimeField,
+    P: FieldBasedMerkleTreeConfig,
+    P::H: FieldBasedHash<Data = ConstraintF>,
+    HG: FieldBasedHashGadget<P::H, ConstraintF>,
+    R: Rng,
+{
+    let leaves = (0..num_leaves).map(|_| ConstraintF::rand(rng)).collect::<Vec<_>>();
+    let tree = FieldBasedMerkleHashTree::<P>::new(&leaves).unwrap();
+    let root = tree.root();
+
+    let mut other_leaves = leaves.clone();
+    other_leaves[rng.gen_range(0, num_leaves)] += &ConstraintF::one();
+    let other_root = FieldBasedMerkleHashTree::<P>::new(&other_leaves).unwrap().root();
+
+    for _ in 0..num_samples {
+        let index = rng.gen_range(0, num_leaves);
+        let cs = synthesize_merkle_path::<ConstraintF, P, HG>(&tree, index, &leaves[index], &root);
+        assert_satisfied(&cs, "Merkle path");
+
+        let cs = synthesize_merkle_path::<ConstraintF, P, HG>(&tree, index, &leaves[index], &other_root);
+        assert!(!cs.is_satisfied(), "Merkle path: root of another tree accepted");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{
+        curves::{
+            jubjub::JubJubProjective as JubJub, mnt4753::G1Projective as MNT4G1Projective,
+            mnt6753::G1Projective as MNT6G1Projective, secp256k1::Secp256k1Parameters,
+        },
+        fields::{bls12_381::Fr as Bls12_381Fr, mnt4753::Fr as MNT4Fr, mnt6753::Fr as MNT6Fr},
+    };
+    use blake2::Blake2s;
+    use primitives::{
+        commitment::{blake2s::Blake2sCommitment, pedersen::PedersenCommitment},
+        crh::{
+            bowe_hopwood::BoweHopwoodPedersenCRH,
+            pedersen::{PedersenCRH, PedersenWindow},
+            MNT4PoseidonHash, MNT6PoseidonHash,
+        },
+        signature::schnorr::{field_based_schnorr::FieldBasedSchnorrSignatureScheme, SchnorrSignature},
+        vrf::ecvrf::FieldBasedEcVrf,
+    };
+    use r1cs_std::groups::curves::{
+        short_weierstrass::mnt::{mnt4::mnt4753::MNT4G1Gadget, mnt6::mnt6753::MNT6G1Gadget},
+        twisted_edwards::jubjub::JubJubGadget,
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use sha2::Sha256;
+
+    use crate::{
+        commitment::{blake2s::Blake2sCommitmentGadget, pedersen::PedersenCommitmentGadget},
+        crh::{
+            bowe_hopwood::BoweHopwoodPedersenCRHGadget, pedersen::PedersenCRHGadget, MNT4PoseidonHashGadget,
+            MNT6PoseidonHashGadget,
+        },
+        signature::schnorr::{field_based_schnorr::FieldBasedSchnorrSigVerificationGadget, SchnorrRandomizePkGadget},
+        vrf::ecvrf::FieldBasedEcVrfProofVerificationGadget,
+    };
+
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    struct Window;
+
+    impl PedersenWindow for Window {
+        const WINDOW_SIZE: usize = 128;
+        const NUM_WINDOWS: usize = 8;
+    }
+
+    #[derive(Clone)]
+    struct VrfWindow;
+
+    impl PedersenWindow for VrfWindow {
+        const WINDOW_SIZE: usize = 128;
+        const NUM_WINDOWS: usize = 2;
+    }
+
+    type MNT4GroupHash = BoweHopwoodPedersenCRH<MNT6G1Projective, VrfWindow>;
+    type MNT6GroupHash = BoweHopwoodPedersenCRH<MNT4G1Projective, VrfWindow>;
+
+    struct MNT4MerkleTreeParams;
+
+    impl FieldBasedMerkleTreeConfig for MNT4MerkleTreeParams {
+        const HEIGHT: usize = 6;
+        type H = MNT4PoseidonHash;
+    }
+
+    struct MNT6MerkleTreeParams;
+
+    impl FieldBasedMerkleTreeConfig for MNT6MerkleTreeParams {
+        const HEIGHT: usize = 6;
+        type H = MNT6PoseidonHash;
+    }
+
+    #[test]
+    fn pedersen_crh_consistency() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        check_fixed_length_crh::<
+            Bls12_381Fr,
+            PedersenCRH<JubJub, Window>,
+            PedersenCRHGadget<JubJub, Bls12_381Fr, JubJubGadget>,
+            _,
+        >(rng, 2);
+    }
+
+    #[test]
+    fn poseidon_consistency() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        for &input_len in &[1, 2, 5] {
+            check_field_based_hash::<MNT4Fr, MNT4PoseidonHash, MNT4PoseidonHashGadget, _>(rng, input_len, 2);
+            check_field_based_hash::<MNT6Fr, MNT6PoseidonHash, MNT6PoseidonHashGadget, _>(rng, input_len, 2);
+        }
+    }
+
+    #[test]
+    fn commitment_consistency() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        check_commitment::<Bls12_381Fr, Blake2sCommitment, Blake2sCommitmentGadget, _>(rng, 32, 2);
+        check_commitment::<
+            Bls12_381Fr,
+            PedersenCommitment<JubJub, Window>,
+            PedersenCommitmentGadget<JubJub, Bls12_381Fr, JubJubGadget>,
+            _,
+        >(rng, 32, 2);
+    }
+
+    #[test]
+    fn field_based_schnorr_consistency() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        check_field_based_signature::<
+            MNT4Fr,
+            FieldBasedSchnorrSignatureScheme<MNT4Fr, MNT6G1Projective, MNT4PoseidonHash>,
+            FieldBasedSchnorrSigVerificationGadget<
+                MNT4Fr, MNT6G1Projective, MNT6G1Gadget, MNT4PoseidonHash, MNT4PoseidonHashGadget,
+            >,
+            _,
+        >(rng, 1, 2);
+        check_field_based_signature::<
+            MNT6Fr,
+            FieldBasedSchnorrSignatureScheme<MNT6Fr, MNT4G1Projective, MNT6PoseidonHash>,
+            FieldBasedSchnorrSigVerificationGadget<
+                MNT6Fr, MNT4G1Projective, MNT4G1Gadget, MNT6PoseidonHash, MNT6PoseidonHashGadget,
+            >,
+            _,
+        >(rng, 1, 2);
+    }
+
+    #[test]
+    fn schnorr_randomize_pk_consistency() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        check_public_key_randomization::<
+            Bls12_381Fr,
+            SchnorrSignature<JubJub, Blake2s>,
+            SchnorrRandomizePkGadget<JubJub, Bls12_381Fr, JubJubGadget>,
+            _,
+        >(rng, 32, 2);
+    }
+
+    #[test]
+    fn ecdsa_consistency() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        check_ecdsa_signature::<MNT4Fr, Secp256k1Parameters, Sha256, _>(rng, 32, 1);
+    }
+
+    #[test]
+    fn field_based_ecvrf_consistency() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let parameters = MNT4GroupHash::setup(rng).unwrap();
+        check_field_based_vrf::<
+            MNT4Fr,
+            FieldBasedEcVrf<MNT4Fr, MNT6G1Projective, MNT4PoseidonHash, MNT4GroupHash>,
+            FieldBasedEcVrfProofVerificationGadget<
+                MNT4Fr, MNT6G1Projective, MNT6G1Gadget, MNT4PoseidonHash, MNT4PoseidonHashGadget,
+                MNT4GroupHash, BoweHopwoodPedersenCRHGadget<MNT6G1Projective, MNT4Fr, MNT6G1Gadget>,
+            >,
+            _,
+        >(rng, &parameters, 1, 1);
+        let parameters = MNT6GroupHash::setup(rng).unwrap();
+        check_field_based_vrf::<
+            MNT6Fr,
+            FieldBasedEcVrf<MNT6Fr, MNT4G1Projective, MNT6PoseidonHash, MNT6GroupHash>,
+            FieldBasedEcVrfProofVerificationGadget<
+                MNT6Fr, MNT4G1Projective, MNT4G1Gadget, MNT6PoseidonHash, MNT6PoseidonHashGadget,
+                MNT6GroupHash, BoweHopwoodPedersenCRHGadget<MNT4G1Projective, MNT6Fr, MNT4G1Gadget>,
+            >,
+            _,
+        >(rng, &parameters, 1, 1);
+    }
+
+    #[test]
+    fn field_based_merkle_tree_consistency() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        for &num_leaves in &[4, 13, 32] {
+            check_field_based_merkle_tree::<MNT4Fr, MNT4MerkleTreeParams, MNT4PoseidonHashGadget, _>(
+                rng, num_leaves, 3,
+            );
+            check_field_based_merkle_tree::<MNT6Fr, MNT6MerkleTreeParams, MNT6PoseidonHashGadget, _>(
+                rng, num_leaves, 3,
+            );
+        }
+    }
+}
@@ -15,6 +15,10 @@ pub mod pedersen;
 pub mod poseidon;
 pub use self::poseidon::*;
 
+pub mod pow;
+
+pub mod sha256;
+
 pub trait FixedLengthCRHGadget<H: FixedLengthCRH, ConstraintF: Field>: Sized {
     type OutputGadget: ConditionalEqGadget<ConstraintF>
         + EqGadget<ConstraintF>
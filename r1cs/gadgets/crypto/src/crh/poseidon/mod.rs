@@ -7,7 +7,8 @@ use algebra::{
 use primitives::crh::poseidon::PoseidonParameters;
 use crate::crh::FieldBasedHashGadget;
 use primitives::crh::{
-    poseidon::PoseidonHash,
+    FieldBasedHashPersonalization, personalization_tag,
+    poseidon::{PoseidonHash, PersonalizedPoseidonHash},
     parameters::{
         MNT4753PoseidonParameters, MNT6753PoseidonParameters
     },
@@ -70,7 +71,7 @@ impl<ConstraintF: PrimeField + MulShort, P: PoseidonParameters<Fr = ConstraintF>
         Ok(())
     }
 
-    fn poseidon_perm<CS: ConstraintSystem<ConstraintF>>(
+    pub(crate) fn poseidon_perm<CS: ConstraintSystem<ConstraintF>>(
         mut cs: CS,
         state: &mut [FpGadget<ConstraintF>],
     ) -> Result<(), SynthesisError>
@@ -221,25 +222,26 @@ impl<ConstraintF: PrimeField + MulShort, P: PoseidonParameters<Fr = ConstraintF>
     }
 }
 
-impl<ConstraintF, P> FieldBasedHashGadget<PoseidonHash<ConstraintF, P>, ConstraintF> for PoseidonHashGadget<ConstraintF, P>
+impl<ConstraintF, P> PoseidonHashGadget<ConstraintF, P>
     where
         ConstraintF: PrimeField + MulShort,
         P:           PoseidonParameters<Fr = ConstraintF>
 {
-    type DataGadget = FpGadget<ConstraintF>;
-
-    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+    // Hashes `input` starting from the constant `initial_state`, as returned
+    // by `PoseidonHash::initial_state`.
+    fn evaluate_from_state<CS: ConstraintSystem<ConstraintF>>(
         mut cs: CS,
-        input: &[Self::DataGadget],
-    ) -> Result<Self::DataGadget, SynthesisError>
+        initial_state: &[ConstraintF],
+        input: &[FpGadget<ConstraintF>],
+    ) -> Result<FpGadget<ConstraintF>, SynthesisError>
     // Assumption:
     //     rate r = 2
     //     capacity c = 1
     //     t = 3
     {
-        let state_0 = FpGadget::<ConstraintF>::from_value(cs.ns(|| "hardcode_state_0"), &P::AFTER_ZERO_PERM[0]);
-        let state_1 = FpGadget::<ConstraintF>::from_value(cs.ns(|| "hardcode_state_1"), &P::AFTER_ZERO_PERM[1]);
-        let state_2 = FpGadget::<ConstraintF>::from_value(cs.ns(|| "hardcode_state_2"), &P::AFTER_ZERO_PERM[2]);
+        let state_0 = FpGadget::<ConstraintF>::from_value(cs.ns(|| "hardcode_state_0"), &initial_state[0]);
+        let state_1 = FpGadget::<ConstraintF>::from_value(cs.ns(|| "hardcode_state_1"), &initial_state[1]);
+        let state_2 = FpGadget::<ConstraintF>::from_value(cs.ns(|| "hardcode_state_2"), &initial_state[2]);
 
         let mut state = [state_0, state_1, state_2];
 
@@ -283,6 +285,59 @@ impl<ConstraintF, P> FieldBasedHashGadget<PoseidonHash<ConstraintF, P>, Constrai
     }
 }
 
+impl<ConstraintF, P> FieldBasedHashGadget<PoseidonHash<ConstraintF, P>, ConstraintF> for PoseidonHashGadget<ConstraintF, P>
+    where
+        ConstraintF: PrimeField + MulShort,
+        P:           PoseidonParameters<Fr = ConstraintF>
+{
+    type DataGadget = FpGadget<ConstraintF>;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        input: &[Self::DataGadget],
+    ) -> Result<Self::DataGadget, SynthesisError>
+    {
+        Self::evaluate_from_state(cs, &PoseidonHash::<ConstraintF, P>::initial_state(None), input)
+    }
+}
+
+pub struct PersonalizedPoseidonHashGadget
+<
+    ConstraintF: PrimeField,
+    P:           PoseidonParameters<Fr = ConstraintF>,
+    T:           FieldBasedHashPersonalization,
+>
+{
+    _field:           PhantomData<ConstraintF>,
+    _parameters:      PhantomData<P>,
+    _personalization: PhantomData<T>,
+}
+
+impl<ConstraintF, P, T> FieldBasedHashGadget<PersonalizedPoseidonHash<ConstraintF, P, T>, ConstraintF>
+for PersonalizedPoseidonHashGadget<ConstraintF, P, T>
+    where
+        ConstraintF: PrimeField + MulShort,
+        P:           PoseidonParameters<Fr = ConstraintF>,
+        T:           FieldBasedHashPersonalization,
+{
+    type DataGadget = FpGadget<ConstraintF>;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        input: &[Self::DataGadget],
+    ) -> Result<Self::DataGadget, SynthesisError>
+    {
+        // The personalization is a constant of the circuit, mixed into the hardcoded initial state
+        let tag = personalization_tag::<ConstraintF>(T::PERSONALIZATION)
+            .map_err(|_| SynthesisError::Unsatisfiable)?;
+        PoseidonHashGadget::<ConstraintF, P>::evaluate_from_state(
+            cs,
+            &PoseidonHash::<ConstraintF, P>::initial_state(Some(tag)),
+            input,
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use rand::thread_rng;
@@ -367,4 +422,32 @@ mod test {
         assert_eq!(primitive_result, gadget_result.value.unwrap());
         assert!(cs.is_satisfied());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn crh_mnt4_753_personalized_primitive_gadget_test() {
+        struct NullifierTag;
+        impl FieldBasedHashPersonalization for NullifierTag {
+            const PERSONALIZATION: &'static [u8] = b"nullifier";
+        }
+
+        type H = PersonalizedPoseidonHash<MNT4753Fr, MNT4753PoseidonParameters, NullifierTag>;
+        type HG = PersonalizedPoseidonHashGadget<MNT4753Fr, MNT4753PoseidonParameters, NullifierTag>;
+
+        let mut rng = &mut thread_rng();
+        let mut cs = TestConstraintSystem::<MNT4753Fr>::new();
+
+        let input = vec![MNT4753Fr::rand(&mut rng), MNT4753Fr::rand(&mut rng), MNT4753Fr::rand(&mut rng)];
+        let primitive_result = H::evaluate(&input).unwrap();
+
+        let input_gadget = input
+            .iter()
+            .enumerate()
+            .map(|(i, v)| Mnt4FieldGadget::alloc(cs.ns(|| format!("alloc_v{}", i)), || Ok(v)).unwrap())
+            .collect::<Vec<_>>();
+        let gadget_result = HG::check_evaluation_gadget(cs.ns(|| "check_poseidon_gadget"), &input_gadget).unwrap();
+
+        assert_eq!(primitive_result, gadget_result.value.unwrap());
+        assert_ne!(MNT4PoseidonHash::evaluate(&input).unwrap(), primitive_result);
+        assert!(cs.is_satisfied());
+    }
+}
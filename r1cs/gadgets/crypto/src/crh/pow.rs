@@ -0,0 +1,102 @@
+//! Gadgets for the proof-of-work style difficulty checks of
+//! `primitives::crh::pow`.
+use algebra::{BigInteger, PrimeField};
+use primitives::crh::FieldBasedHash;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::{fields::fp::FpGadget, prelude::*};
+
+use crate::crh::FieldBasedHashGadget;
+
+/// Enforces that `hash`, as an integer, is strictly smaller than `target`.
+///
+/// The canonical bit decomposition of `hash` is enforced, so that no
+/// representative of `hash` other than the canonical one can be used to meet
+/// the target. Returns `SynthesisError::Unsatisfiable` if the target is zero.
+pub fn enforce_meets_target<ConstraintF, CS>(
+    mut cs: CS,
+    hash: &FpGadget<ConstraintF>,
+    target: &ConstraintF::BigInt,
+) -> Result<(), SynthesisError>
+    where
+        ConstraintF: PrimeField,
+        CS: ConstraintSystem<ConstraintF>,
+{
+    if target.is_zero() {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    let mut max = *target;
+    max.sub_noborrow(&ConstraintF::BigInt::from(1));
+
+    let bits = hash.to_bits_strict(cs.ns(|| "hash to bits strict"))?;
+    Boolean::enforce_smaller_or_equal_than(cs.ns(|| "enforce hash < target"), &bits, max)
+}
+
+/// Enforces the evaluation of `H` on `input` and that the output meets
+/// `target`. Returns the output.
+pub fn check_pow_gadget<ConstraintF, H, HG, CS>(
+    mut cs: CS,
+    input: &[FpGadget<ConstraintF>],
+    target: &ConstraintF::BigInt,
+) -> Result<FpGadget<ConstraintF>, SynthesisError>
+    where
+        ConstraintF: PrimeField,
+        H: FieldBasedHash<Data = ConstraintF>,
+        HG: FieldBasedHashGadget<H, ConstraintF, DataGadget = FpGadget<ConstraintF>>,
+        CS: ConstraintSystem<ConstraintF>,
+{
+    let hash = HG::check_evaluation_gadget(cs.ns(|| "evaluate hash"), input)?;
+    enforce_meets_target(cs.ns(|| "check target"), &hash, target)?;
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::crh::MNT4PoseidonHashGadget;
+    use algebra::{fields::mnt4753::Fr as MNT4753Fr, Field, FpParameters, UniformRand};
+    use primitives::crh::{pow::{meets_target, target_from_leading_zeros}, MNT4PoseidonHash};
+    use r1cs_std::test_constraint_system::TestConstraintSystem;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_pow_gadget() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+        let modulus_bits = <MNT4753Fr as PrimeField>::Params::MODULUS_BITS;
+
+        for _ in 0..5 {
+            let input = [MNT4753Fr::rand(&mut rng), MNT4753Fr::rand(&mut rng)];
+            let hash = MNT4PoseidonHash::evaluate(&input).unwrap();
+            let leading_zeros = modulus_bits - hash.into_repr().num_bits();
+
+            // The gadget is satisfied exactly for the targets met natively:
+            // that of the number of leading zeros of the hash, and not the
+            // next one. Also the target given by the hash itself is not met.
+            let targets = vec![
+                target_from_leading_zeros::<MNT4753Fr>(0),
+                target_from_leading_zeros::<MNT4753Fr>(leading_zeros),
+                target_from_leading_zeros::<MNT4753Fr>(leading_zeros + 1),
+                hash.into_repr(),
+            ];
+            for target in targets.iter() {
+                let mut cs = TestConstraintSystem::<MNT4753Fr>::new();
+                let input_g = input
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| FpGadget::alloc(cs.ns(|| format!("alloc input {}", i)), || Ok(*x)).unwrap())
+                    .collect::<Vec<_>>();
+                check_pow_gadget::<_, MNT4PoseidonHash, MNT4PoseidonHashGadget, _>(
+                    cs.ns(|| "check pow"),
+                    &input_g,
+                    target,
+                ).unwrap();
+                assert_eq!(cs.is_satisfied(), meets_target(&hash, target));
+            }
+        }
+
+        // The zero target can't be met.
+        let mut cs = TestConstraintSystem::<MNT4753Fr>::new();
+        let hash = FpGadget::alloc(cs.ns(|| "alloc hash"), || Ok(MNT4753Fr::zero())).unwrap();
+        assert!(enforce_meets_target(cs.ns(|| "zero target"), &hash, &<MNT4753Fr as PrimeField>::BigInt::from(0)).is_err());
+    }
+}
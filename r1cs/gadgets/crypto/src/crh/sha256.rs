@@ -0,0 +1,258 @@
+//! The SHA-256 hash function (FIPS 180-4, https://doi.org/10.6028/NIST.FIPS.180-4) on
+//! bit strings, matching the native implementation of the `sha2` crate.
+//! Following the standard, bit strings and words are big endian: the bits of a byte
+//! string are given most significant bit of the first byte first.
+use algebra::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+
+use r1cs_std::prelude::*;
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// The initial hash value of SHA-256.
+pub const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Converts 32 big endian bits to a `UInt32`.
+fn uint32_from_bits_be(bits: &[Boolean]) -> UInt32 {
+    let mut bits = bits.to_vec();
+    bits.reverse();
+    UInt32::from_bits_le(&bits)
+}
+
+fn uint32_to_bits_be(word: &UInt32) -> Vec<Boolean> {
+    let mut bits = word.to_bits_le();
+    bits.reverse();
+    bits
+}
+
+/// Shifts `word` to the right by `by` bits.
+fn shr(word: &UInt32, by: usize) -> UInt32 {
+    let bits = word
+        .to_bits_le()
+        .into_iter()
+        .skip(by)
+        .chain(std::iter::repeat(Boolean::constant(false)))
+        .take(32)
+        .collect::<Vec<_>>();
+    UInt32::from_bits_le(&bits)
+}
+
+/// Bitwise application of a three-input boolean function.
+fn triop<ConstraintF, CS, F>(
+    mut cs: CS,
+    a: &UInt32,
+    b: &UInt32,
+    c: &UInt32,
+    f: F,
+) -> Result<UInt32, SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+    F: Fn(&mut CS, usize, &Boolean, &Boolean, &Boolean) -> Result<Boolean, SynthesisError>,
+{
+    let bits = a
+        .to_bits_le()
+        .iter()
+        .zip(b.to_bits_le().iter())
+        .zip(c.to_bits_le().iter())
+        .enumerate()
+        .map(|(i, ((a, b), c))| f(&mut cs, i, a, b, c))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(UInt32::from_bits_le(&bits))
+}
+
+/// Ch(e, f, g) = (e and f) xor (not e and g) = g xor (e and (f xor g))
+fn ch<ConstraintF: PrimeField, CS: ConstraintSystem<ConstraintF>>(
+    cs: CS,
+    e: &UInt32,
+    f: &UInt32,
+    g: &UInt32,
+) -> Result<UInt32, SynthesisError> {
+    triop(cs, e, f, g, |cs, i, e, f, g| {
+        let f_xor_g = Boolean::xor(cs.ns(|| format!("f xor g {}", i)), f, g)?;
+        let e_and = Boolean::and(cs.ns(|| format!("e and (f xor g) {}", i)), e, &f_xor_g)?;
+        Boolean::xor(cs.ns(|| format!("ch {}", i)), g, &e_and)
+    })
+}
+
+/// Maj(a, b, c) = (a and b) xor (a and c) xor (b and c) = (a and b) xor (c and (a xor b))
+fn maj<ConstraintF: PrimeField, CS: ConstraintSystem<ConstraintF>>(
+    cs: CS,
+    a: &UInt32,
+    b: &UInt32,
+    c: &UInt32,
+) -> Result<UInt32, SynthesisError> {
+    triop(cs, a, b, c, |cs, i, a, b, c| {
+        let a_and_b = Boolean::and(cs.ns(|| format!("a and b {}", i)), a, b)?;
+        let a_xor_b = Boolean::xor(cs.ns(|| format!("a xor b {}", i)), a, b)?;
+        let c_and = Boolean::and(cs.ns(|| format!("c and (a xor b) {}", i)), c, &a_xor_b)?;
+        Boolean::xor(cs.ns(|| format!("maj {}", i)), &a_and_b, &c_and)
+    })
+}
+
+/// Returns x.rotr(r1) xor x.rotr(r2) xor x.rotr(r3), where the last rotation is a shift
+/// if `shift_last` is set.
+fn sigma<ConstraintF: PrimeField, CS: ConstraintSystem<ConstraintF>>(
+    mut cs: CS,
+    x: &UInt32,
+    (r1, r2, r3): (usize, usize, usize),
+    shift_last: bool,
+) -> Result<UInt32, SynthesisError> {
+    let last = if shift_last { shr(x, r3) } else { x.rotr(r3) };
+    x.rotr(r1)
+        .xor(cs.ns(|| "first xor"), &x.rotr(r2))?
+        .xor(cs.ns(|| "second xor"), &last)
+}
+
+/// Enforces the SHA-256 compression function on the state `h` and the 512 bits block
+/// `block`, updating `h`.
+pub fn sha256_compression_gadget<ConstraintF, CS>(
+    mut cs: CS,
+    h: &mut [UInt32],
+    block: &[Boolean],
+) -> Result<(), SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    assert_eq!(h.len(), 8);
+    assert_eq!(block.len(), 512);
+
+    // Message schedule
+    let mut w = block.chunks(32).map(uint32_from_bits_be).collect::<Vec<_>>();
+    for i in 16..64 {
+        let mut cs = cs.ns(|| format!("message schedule {}", i));
+        let s0 = sigma(cs.ns(|| "s0"), &w[i - 15], (7, 18, 3), true)?;
+        let s1 = sigma(cs.ns(|| "s1"), &w[i - 2], (17, 19, 10), true)?;
+        let new_w = UInt32::addmany(cs.ns(|| "w"), &[w[i - 16].clone(), s0, w[i - 7].clone(), s1])?;
+        w.push(new_w);
+    }
+
+    let mut v = h.to_vec();
+    for i in 0..64 {
+        let mut cs = cs.ns(|| format!("round {}", i));
+        let s1 = sigma(cs.ns(|| "S1"), &v[4], (6, 11, 25), false)?;
+        let ch = ch(cs.ns(|| "ch"), &v[4], &v[5], &v[6])?;
+        let temp1 = UInt32::addmany(
+            cs.ns(|| "temp1"),
+            &[v[7].clone(), s1, ch, UInt32::constant(ROUND_CONSTANTS[i]), w[i].clone()],
+        )?;
+        let s0 = sigma(cs.ns(|| "S0"), &v[0], (2, 13, 22), false)?;
+        let maj = maj(cs.ns(|| "maj"), &v[0], &v[1], &v[2])?;
+        let temp2 = UInt32::addmany(cs.ns(|| "temp2"), &[s0, maj])?;
+
+        v[7] = v[6].clone();
+        v[6] = v[5].clone();
+        v[5] = v[4].clone();
+        v[4] = UInt32::addmany(cs.ns(|| "e"), &[v[3].clone(), temp1.clone()])?;
+        v[3] = v[2].clone();
+        v[2] = v[1].clone();
+        v[1] = v[0].clone();
+        v[0] = UInt32::addmany(cs.ns(|| "a"), &[temp1, temp2])?;
+    }
+
+    for i in 0..8 {
+        h[i] = UInt32::addmany(cs.ns(|| format!("h[{}] + v[{}]", i, i)), &[h[i].clone(), v[i].clone()])?;
+    }
+
+    Ok(())
+}
+
+/// Enforces the SHA-256 hash of the big endian bit string `input`, returning the
+/// 256 bits of the digest in big endian order.
+pub fn sha256_gadget<ConstraintF, CS>(
+    mut cs: CS,
+    input: &[Boolean],
+) -> Result<Vec<Boolean>, SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    // Padding: a one, zeros up to 448 bits modulo 512, and the 64 bits length
+    let mut padded = input.to_vec();
+    padded.push(Boolean::constant(true));
+    while padded.len() % 512 != 448 {
+        padded.push(Boolean::constant(false));
+    }
+    let len = input.len() as u64;
+    padded.extend((0..64).rev().map(|i| Boolean::constant((len >> i) & 1 == 1)));
+
+    let mut h = SHA256_IV.iter().map(|&iv| UInt32::constant(iv)).collect::<Vec<_>>();
+    for (i, block) in padded.chunks(512).enumerate() {
+        sha256_compression_gadget(cs.ns(|| format!("block {}", i)), &mut h, block)?;
+    }
+
+    Ok(h.iter().flat_map(uint32_to_bits_be).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use algebra::fields::bls12_377::fr::Fr;
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+    use r1cs_core::ConstraintSystem;
+    use sha2::{Digest, Sha256};
+
+    use super::sha256_gadget;
+    use r1cs_std::{
+        boolean::AllocatedBit, prelude::*, test_constraint_system::TestConstraintSystem,
+    };
+
+    #[test]
+    fn test_sha256() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        for input_len in (0..32).chain((32..256).filter(|a| a % 8 == 0)) {
+            let data: Vec<u8> = (0..input_len).map(|_| rng.gen()).collect();
+
+            let hash_result = Sha256::digest(&data);
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let mut input_bits = vec![];
+            for (byte_i, input_byte) in data.iter().enumerate() {
+                for bit_i in (0..8).rev() {
+                    let cs = cs.ns(|| format!("input bit_gadget {} {}", byte_i, bit_i));
+
+                    input_bits.push(
+                        AllocatedBit::alloc(cs, || Ok((input_byte >> bit_i) & 1u8 == 1u8))
+                            .unwrap()
+                            .into(),
+                    );
+                }
+            }
+
+            let r = sha256_gadget(&mut cs, &input_bits).unwrap();
+
+            assert!(cs.is_satisfied());
+
+            let expected = hash_result
+                .as_ref()
+                .iter()
+                .flat_map(|&byte| (0..8).rev().map(move |i| (byte >> i) & 1u8 == 1u8))
+                .collect::<Vec<_>>();
+            let actual = r.iter().map(|b| b.get_value().unwrap()).collect::<Vec<_>>();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_sha256_constant_constraints() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+        let input_bits: Vec<_> = (0..512).map(|_| Boolean::constant(rng.gen())).collect();
+        sha256_gadget(&mut cs, &input_bits).unwrap();
+        assert_eq!(cs.num_constraints(), 0);
+    }
+}
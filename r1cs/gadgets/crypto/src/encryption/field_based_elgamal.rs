@@ -0,0 +1,311 @@
+use algebra::{PrimeField, ProjectiveCurve, Group, ToConstraintField};
+use crate::{
+    encryption::FieldBasedEncryptionGadget,
+    crh::FieldBasedHashGadget,
+};
+use primitives::{
+    encryption::field_based_elgamal::{FieldBasedElGamalCiphertext, FieldBasedElGamalEncryptionScheme},
+    crh::FieldBasedHash,
+};
+use r1cs_std::{
+    fields::{fp::FpGadget, FieldGadget},
+    to_field_gadget_vec::ToConstraintFieldGadget,
+    alloc::{AllocGadget, ConstantGadget},
+    eq::EqGadget,
+    groups::GroupGadget,
+    bits::boolean::Boolean,
+};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use std::{
+    borrow::Borrow,
+    marker::PhantomData,
+};
+
+#[derive(Derivative)]
+#[derivative(
+Debug(bound = "ConstraintF: PrimeField, G: Group, GG: GroupGadget<G, ConstraintF>"),
+Clone(bound = "ConstraintF: PrimeField, G: Group, GG: GroupGadget<G, ConstraintF>"),
+)]
+pub struct FieldBasedElGamalCiphertextGadget<
+    ConstraintF: PrimeField,
+    G:  Group,
+    GG: GroupGadget<G, ConstraintF>,
+>
+{
+    pub r:       GG,
+    pub c:       Vec<FpGadget<ConstraintF>>,
+    _group:      PhantomData<G>,
+}
+
+impl<ConstraintF, G, GG> AllocGadget<FieldBasedElGamalCiphertext<ConstraintF, G>, ConstraintF>
+for FieldBasedElGamalCiphertextGadget<ConstraintF, G, GG>
+    where
+        ConstraintF: PrimeField,
+        G:           ProjectiveCurve,
+        GG:          GroupGadget<G, ConstraintF, Value = G>,
+{
+    fn alloc<FN, T, CS: ConstraintSystem<ConstraintF>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+        where
+            FN: FnOnce() -> Result<T, SynthesisError>,
+            T: Borrow<FieldBasedElGamalCiphertext<ConstraintF, G>>,
+    {
+        let ciphertext = f()?.borrow().clone();
+
+        let r = GG::alloc(cs.ns(|| "alloc r"), || Ok(ciphertext.r))?;
+        let c = ciphertext.c.iter().enumerate().map(|(i, c_i)|
+            FpGadget::<ConstraintF>::alloc(cs.ns(|| format!("alloc c_{}", i)), || Ok(c_i))
+        ).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self{r, c, _group: PhantomData})
+    }
+
+    fn alloc_input<FN, T, CS: ConstraintSystem<ConstraintF>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+        where
+            FN: FnOnce() -> Result<T, SynthesisError>,
+            T: Borrow<FieldBasedElGamalCiphertext<ConstraintF, G>>,
+    {
+        let ciphertext = f()?.borrow().clone();
+
+        let r = GG::alloc_input(cs.ns(|| "alloc r"), || Ok(ciphertext.r))?;
+        let c = ciphertext.c.iter().enumerate().map(|(i, c_i)|
+            FpGadget::<ConstraintF>::alloc_input(cs.ns(|| format!("alloc c_{}", i)), || Ok(c_i))
+        ).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self{r, c, _group: PhantomData})
+    }
+}
+
+/// The encryption randomness, allocated as its big-endian bit decomposition.
+#[derive(Derivative)]
+#[derivative(
+Debug(bound = "ScalarF: PrimeField"),
+Clone(bound = "ScalarF: PrimeField"),
+)]
+pub struct FieldBasedElGamalRandomnessGadget<ScalarF: PrimeField> {
+    pub bits:    Vec<Boolean>,
+    _field:      PhantomData<ScalarF>,
+}
+
+impl<ScalarF, ConstraintF> AllocGadget<ScalarF, ConstraintF>
+for FieldBasedElGamalRandomnessGadget<ScalarF>
+    where
+        ScalarF:     PrimeField,
+        ConstraintF: PrimeField,
+{
+    fn alloc<FN, T, CS: ConstraintSystem<ConstraintF>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+        where
+            FN: FnOnce() -> Result<T, SynthesisError>,
+            T: Borrow<ScalarF>,
+    {
+        let bits = match f() {
+            Ok(r) => r.borrow().write_bits().into_iter().map(Some).collect::<Vec<_>>(),
+            _ => vec![None; ScalarF::size_in_bits()],
+        };
+
+        let bits = bits.into_iter().enumerate().map(|(i, b)|
+            Boolean::alloc(cs.ns(|| format!("alloc bit_{}", i)), || b.ok_or(SynthesisError::AssignmentMissing))
+        ).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self{bits, _field: PhantomData})
+    }
+
+    fn alloc_input<FN, T, CS: ConstraintSystem<ConstraintF>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+        where
+            FN: FnOnce() -> Result<T, SynthesisError>,
+            T: Borrow<ScalarF>,
+    {
+        let bits = match f() {
+            Ok(r) => r.borrow().write_bits().into_iter().map(Some).collect::<Vec<_>>(),
+            _ => vec![None; ScalarF::size_in_bits()],
+        };
+
+        let bits = bits.into_iter().enumerate().map(|(i, b)|
+            Boolean::alloc_input(cs.ns(|| format!("alloc bit_{}", i)), || b.ok_or(SynthesisError::AssignmentMissing))
+        ).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self{bits, _field: PhantomData})
+    }
+}
+
+pub struct FieldBasedElGamalEncryptionGadget<
+    ConstraintF: PrimeField,
+    G:  Group,
+    GG: GroupGadget<G, ConstraintF>,
+    H:  FieldBasedHash<Data = ConstraintF>,
+    HG: FieldBasedHashGadget<H, ConstraintF>,
+>
+{
+    _field:         PhantomData<ConstraintF>,
+    _group:         PhantomData<G>,
+    _group_gadget:  PhantomData<GG>,
+    _hash:          PhantomData<H>,
+    _hash_gadget:   PhantomData<HG>,
+}
+
+impl<ConstraintF, G, GG, H, HG> FieldBasedEncryptionGadget<FieldBasedElGamalEncryptionScheme<ConstraintF, G, H>, ConstraintF>
+for FieldBasedElGamalEncryptionGadget<ConstraintF, G, GG, H, HG>
+    where
+        ConstraintF: PrimeField,
+        G:           ProjectiveCurve + ToConstraintField<ConstraintF>,
+        GG:          GroupGadget<G, ConstraintF, Value = G> + ToConstraintFieldGadget<ConstraintF, FieldGadget = HG::DataGadget>,
+        H:           FieldBasedHash<Data = ConstraintF>,
+        HG:          FieldBasedHashGadget<H, ConstraintF, DataGadget = FpGadget<ConstraintF>>,
+{
+    type DataGadget = FpGadget<ConstraintF>;
+    type PublicKeyGadget = GG;
+    type RandomnessGadget = FieldBasedElGamalRandomnessGadget<G::ScalarField>;
+    type CiphertextGadget = FieldBasedElGamalCiphertextGadget<ConstraintF, G, GG>;
+
+    fn enforce_encryption<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        public_key: &Self::PublicKeyGadget,
+        randomness: &Self::RandomnessGadget,
+        message:    &[Self::DataGadget],
+        ciphertext: &Self::CiphertextGadget,
+    ) -> Result<(), SynthesisError> {
+
+        if message.len() != ciphertext.c.len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let r_bits_le = randomness.bits.iter().rev().cloned().collect::<Vec<_>>();
+
+        //Let's hardcode generator and use it as `result` param here to avoid edge cases in addition
+        let g = GG::from_value(cs.ns(|| "hardcode generator"), &G::prime_subgroup_generator());
+
+        //Enforce S = r * pk
+        let shared = public_key
+            .mul_bits(cs.ns(|| "r * pk + g"), &g, r_bits_le.iter())?
+            .sub(cs.ns(|| "subtract g from r * pk"), &g)?;
+
+        //Enforce R = r * G, using S as `result` param to avoid edge cases in addition
+        let r_times_g = GG::mul_bits_fixed_base(
+            &g.get_constant(),
+            cs.ns(|| "r * G + S"),
+            &shared,
+            r_bits_le.as_slice()
+        )?.sub(cs.ns(|| "subtract S from r * G + S"), &shared)?;
+        r_times_g.enforce_equal(cs.ns(|| "R == r * G"), &ciphertext.r)?;
+
+        let shared_coords = shared.to_field_gadget_elements()?;
+
+        //Enforce c_i = m_i + H(S.x, S.y, i)
+        for (i, (m_i, c_i)) in message.iter().zip(ciphertext.c.iter()).enumerate() {
+            let index = FpGadget::<ConstraintF>::from_value(
+                cs.ns(|| format!("hardcode index {}", i)),
+                &ConstraintF::from(i as u64)
+            );
+            let mut hash_input = shared_coords.clone();
+            hash_input.push(index);
+
+            let k_i = HG::check_evaluation_gadget(
+                cs.ns(|| format!("compute k_{}", i)),
+                hash_input.as_slice()
+            )?;
+            m_i
+                .add(cs.ns(|| format!("m_{} + k_{}", i, i)), &k_i)?
+                .enforce_equal(cs.ns(|| format!("c_{} == m_{} + k_{}", i, i, i)), c_i)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use algebra::curves::mnt6753::G1Projective as MNT6G1Projective;
+    use algebra::fields::mnt4753::Fr as MNT4Fr;
+
+    use primitives::{
+        encryption::{
+            FieldBasedEncryptionScheme, field_based_elgamal::*,
+        },
+        crh::MNT4PoseidonHash,
+    };
+
+    use crate::{
+        encryption::{
+            FieldBasedEncryptionGadget, field_based_elgamal::*,
+        },
+        crh::MNT4PoseidonHashGadget,
+    };
+
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::alloc::AllocGadget;
+    use r1cs_std::groups::curves::short_weierstrass::mnt::mnt6::mnt6753::MNT6G1Gadget;
+
+    use rand::{Rng, thread_rng};
+    use r1cs_std::test_constraint_system::TestConstraintSystem;
+
+    type ElGamalMNT4 = FieldBasedElGamalEncryptionScheme<MNT4Fr, MNT6G1Projective, MNT4PoseidonHash>;
+
+    type ElGamalMNT4Gadget = FieldBasedElGamalEncryptionGadget<
+        MNT4Fr, MNT6G1Projective, MNT6G1Gadget, MNT4PoseidonHash, MNT4PoseidonHashGadget
+    >;
+
+    fn mnt4_elgamal_gadget_generate_constraints(
+        message: &[MNT4Fr],
+        pk: MNT6G1Projective,
+        randomness: <ElGamalMNT4 as FieldBasedEncryptionScheme>::Randomness,
+        ciphertext: FieldBasedElGamalCiphertext<MNT4Fr, MNT6G1Projective>,
+    ) -> bool {
+        let mut cs = TestConstraintSystem::<MNT4Fr>::new();
+
+        //Alloc pk, randomness, message and ciphertext
+        let pk_g = <ElGamalMNT4Gadget as FieldBasedEncryptionGadget<ElGamalMNT4, MNT4Fr>>::PublicKeyGadget::alloc(
+            cs.ns(|| "alloc pk"),
+            || Ok(pk)
+        ).unwrap();
+        let randomness_g = <ElGamalMNT4Gadget as FieldBasedEncryptionGadget<ElGamalMNT4, MNT4Fr>>::RandomnessGadget::alloc(
+            cs.ns(|| "alloc randomness"),
+            || Ok(randomness)
+        ).unwrap();
+        let message_g = message.iter().enumerate().map(|(i, m)|
+            <ElGamalMNT4Gadget as FieldBasedEncryptionGadget<ElGamalMNT4, MNT4Fr>>::DataGadget::alloc(
+                cs.ns(|| format!("alloc message_{}", i)),
+                || Ok(m)
+            ).unwrap()
+        ).collect::<Vec<_>>();
+        let ciphertext_g = <ElGamalMNT4Gadget as FieldBasedEncryptionGadget<ElGamalMNT4, MNT4Fr>>::CiphertextGadget::alloc(
+            cs.ns(|| "alloc ciphertext"),
+            || Ok(ciphertext)
+        ).unwrap();
+
+        //Enforce encryption
+        ElGamalMNT4Gadget::enforce_encryption(
+            cs.ns(|| "enforce encryption"),
+            &pk_g,
+            &randomness_g,
+            message_g.as_slice(),
+            &ciphertext_g,
+        ).unwrap();
+
+        let is_cs_satisfied = cs.is_satisfied();
+
+        if !is_cs_satisfied {
+            println!("**********Unsatisfied constraints***********");
+            println!("{:?}", cs.which_is_unsatisfied());
+        }
+
+        is_cs_satisfied
+    }
+
+    #[test]
+    fn mnt4_elgamal_gadget_test() {
+        //Encrypt random field elements and get the ciphertext and the randomness
+        let rng = &mut thread_rng();
+        let message: Vec<MNT4Fr> = (0..2).map(|_| rng.gen()).collect();
+        let (pk, _) = ElGamalMNT4::keygen(rng);
+        let (ciphertext, randomness) = ElGamalMNT4::encrypt(rng, &pk, message.as_slice()).unwrap();
+
+        //Positive case
+        assert!(mnt4_elgamal_gadget_generate_constraints(&message, pk, randomness, ciphertext.clone()));
+
+        //Change message
+        let wrong_message: Vec<MNT4Fr> = (0..2).map(|_| rng.gen()).collect();
+        assert!(!mnt4_elgamal_gadget_generate_constraints(&wrong_message, pk, randomness, ciphertext.clone()));
+
+        //Change pk
+        let (wrong_pk, _) = ElGamalMNT4::keygen(rng);
+        assert!(!mnt4_elgamal_gadget_generate_constraints(&message, wrong_pk, randomness, ciphertext.clone()));
+
+        //Change randomness
+        let (wrong_ciphertext, _) = ElGamalMNT4::encrypt(rng, &pk, message.as_slice()).unwrap();
+        assert!(!mnt4_elgamal_gadget_generate_constraints(&message, pk, randomness, wrong_ciphertext));
+    }
+}
@@ -0,0 +1,24 @@
+use algebra::Field;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+use primitives::encryption::FieldBasedEncryptionScheme;
+
+pub mod field_based_elgamal;
+
+pub trait FieldBasedEncryptionGadget<S: FieldBasedEncryptionScheme, ConstraintF: Field> {
+
+    type DataGadget:       FieldGadget<ConstraintF, ConstraintF>;
+    type PublicKeyGadget:  AllocGadget<S::PublicKey, ConstraintF>;
+    type RandomnessGadget: AllocGadget<S::Randomness, ConstraintF>;
+    type CiphertextGadget: AllocGadget<S::Ciphertext, ConstraintF>;
+
+    /// Enforce `ciphertext` to be the encryption of `message` under `public_key`,
+    /// using `randomness`.
+    fn enforce_encryption<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        public_key: &Self::PublicKeyGadget,
+        randomness: &Self::RandomnessGadget,
+        message:    &[Self::DataGadget],
+        ciphertext: &Self::CiphertextGadget,
+    ) -> Result<(), SynthesisError>;
+}
@@ -0,0 +1,25 @@
+use algebra::PrimeField;
+use primitives::fiat_shamir::FiatShamirRng;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::fields::fp::FpGadget;
+
+pub mod poseidon;
+pub use self::poseidon::*;
+
+/// The in-circuit counterpart of the random oracle `FS`, squeezing the same
+/// challenges from the same absorbed field elements.
+pub trait FiatShamirRngGadget<FS: FiatShamirRng<Field = ConstraintF>, ConstraintF: PrimeField>: Sized {
+    fn new<CS: ConstraintSystem<ConstraintF>>(cs: CS, domain: &[u8]) -> Result<Self, SynthesisError>;
+
+    fn absorb_field_elements<CS: ConstraintSystem<ConstraintF>>(
+        &mut self,
+        cs: CS,
+        elems: &[FpGadget<ConstraintF>],
+    ) -> Result<(), SynthesisError>;
+
+    fn squeeze_field_elements<CS: ConstraintSystem<ConstraintF>>(
+        &mut self,
+        cs: CS,
+        num: usize,
+    ) -> Result<Vec<FpGadget<ConstraintF>>, SynthesisError>;
+}
@@ -0,0 +1,133 @@
+use algebra::{MulShort, PrimeField};
+use primitives::{
+    crh::{personalization_tag, poseidon::{PoseidonHash, PoseidonParameters}},
+    fiat_shamir::PoseidonFSRng,
+};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::{alloc::ConstantGadget, fields::{fp::FpGadget, FieldGadget}};
+use crate::{crh::PoseidonHashGadget, fiat_shamir::FiatShamirRngGadget};
+use std::marker::PhantomData;
+
+pub struct PoseidonFSRngGadget<ConstraintF: PrimeField + MulShort, P: PoseidonParameters<Fr = ConstraintF>> {
+    state:       Vec<FpGadget<ConstraintF>>,
+    pending:     Vec<FpGadget<ConstraintF>>,
+    _parameters: PhantomData<P>,
+}
+
+impl<ConstraintF, P> PoseidonFSRngGadget<ConstraintF, P>
+    where
+        ConstraintF: PrimeField + MulShort,
+        P:           PoseidonParameters<Fr = ConstraintF>,
+{
+    fn squeeze_field_element<CS: ConstraintSystem<ConstraintF>>(
+        &mut self,
+        mut cs: CS,
+    ) -> Result<FpGadget<ConstraintF>, SynthesisError>
+    {
+        if self.pending.is_empty() {
+            PoseidonHashGadget::<ConstraintF, P>::poseidon_perm(cs.ns(|| "poseidon_perm"), &mut self.state)?;
+        } else {
+            for (i, chunk) in self.pending.chunks(P::R).enumerate() {
+                for (j, elem) in chunk.iter().enumerate() {
+                    self.state[j].add_in_place(cs.ns(|| format!("absorb_{}_{}", i, j)), elem)?;
+                }
+                self.state[P::R].add_constant_in_place(
+                    cs.ns(|| format!("absorb_length_{}", i)),
+                    &ConstraintF::from(chunk.len() as u64),
+                )?;
+                PoseidonHashGadget::<ConstraintF, P>::poseidon_perm(
+                    cs.ns(|| format!("poseidon_perm_{}", i)),
+                    &mut self.state,
+                )?;
+            }
+            self.pending.clear();
+        }
+        Ok(self.state[0].clone())
+    }
+}
+
+impl<ConstraintF, P> FiatShamirRngGadget<PoseidonFSRng<ConstraintF, P>, ConstraintF> for PoseidonFSRngGadget<ConstraintF, P>
+    where
+        ConstraintF: PrimeField + MulShort,
+        P:           PoseidonParameters<Fr = ConstraintF>,
+{
+    fn new<CS: ConstraintSystem<ConstraintF>>(mut cs: CS, domain: &[u8]) -> Result<Self, SynthesisError> {
+        // The domain is a constant of the circuit, mixed into the hardcoded initial state
+        let tag = personalization_tag::<ConstraintF>(domain)
+            .map_err(|_| SynthesisError::Unsatisfiable)?;
+        let state = PoseidonHash::<ConstraintF, P>::initial_state(Some(tag))
+            .iter()
+            .enumerate()
+            .map(|(i, value)| FpGadget::<ConstraintF>::from_value(cs.ns(|| format!("hardcode_state_{}", i)), value))
+            .collect();
+        Ok(Self {
+            state,
+            pending:     vec![],
+            _parameters: PhantomData,
+        })
+    }
+
+    fn absorb_field_elements<CS: ConstraintSystem<ConstraintF>>(
+        &mut self,
+        _cs: CS,
+        elems: &[FpGadget<ConstraintF>],
+    ) -> Result<(), SynthesisError>
+    {
+        self.pending.extend_from_slice(elems);
+        Ok(())
+    }
+
+    fn squeeze_field_elements<CS: ConstraintSystem<ConstraintF>>(
+        &mut self,
+        mut cs: CS,
+        num: usize,
+    ) -> Result<Vec<FpGadget<ConstraintF>>, SynthesisError>
+    {
+        (0..num)
+            .map(|i| self.squeeze_field_element(cs.ns(|| format!("squeeze_{}", i))))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{fields::mnt4753::Fr as MNT4753Fr, UniformRand};
+    use primitives::{crh::parameters::MNT4753PoseidonParameters, fiat_shamir::FiatShamirRng};
+    use r1cs_std::{alloc::AllocGadget, test_constraint_system::TestConstraintSystem};
+    use rand::thread_rng;
+
+    type FS = PoseidonFSRng<MNT4753Fr, MNT4753PoseidonParameters>;
+    type FSGadget = PoseidonFSRngGadget<MNT4753Fr, MNT4753PoseidonParameters>;
+
+    #[test]
+    fn poseidon_fs_rng_gadget_test() {
+        let mut rng = thread_rng();
+        let mut cs = TestConstraintSystem::<MNT4753Fr>::new();
+
+        let elems = (0..3).map(|_| MNT4753Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let elem_gadgets = elems
+            .iter()
+            .enumerate()
+            .map(|(i, elem)| FpGadget::<MNT4753Fr>::alloc(cs.ns(|| format!("alloc_{}", i)), || Ok(*elem)).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut fs = FS::new(b"protocol").unwrap();
+        fs.absorb_field_elements(&elems);
+        let mut challenges = fs.squeeze_field_elements(2);
+        fs.absorb_field_elements(&elems[..1]);
+        challenges.extend(fs.squeeze_field_elements(2));
+
+        let mut fs_gadget = FSGadget::new(cs.ns(|| "new"), b"protocol").unwrap();
+        fs_gadget.absorb_field_elements(cs.ns(|| "absorb_1"), &elem_gadgets).unwrap();
+        let mut challenge_gadgets = fs_gadget.squeeze_field_elements(cs.ns(|| "squeeze_1"), 2).unwrap();
+        fs_gadget.absorb_field_elements(cs.ns(|| "absorb_2"), &elem_gadgets[..1]).unwrap();
+        challenge_gadgets.extend(fs_gadget.squeeze_field_elements(cs.ns(|| "squeeze_2"), 2).unwrap());
+
+        assert_eq!(
+            challenges,
+            challenge_gadgets.iter().map(|c| c.get_value().unwrap()).collect::<Vec<_>>()
+        );
+        assert!(cs.is_satisfied());
+    }
+}
@@ -9,6 +9,16 @@ pub mod commitment;
 #[cfg(feature = "commitment")]
 pub use self::commitment::*;
 
+#[cfg(feature = "encryption")]
+pub mod encryption;
+#[cfg(feature = "encryption")]
+pub use self::encryption::*;
+
+#[cfg(feature = "fiat_shamir")]
+pub mod fiat_shamir;
+#[cfg(feature = "fiat_shamir")]
+pub use self::fiat_shamir::*;
+
 #[cfg(feature = "merkle_tree")]
 pub mod merkle_tree;
 #[cfg(feature = "merkle_tree")]
@@ -32,4 +42,12 @@ pub use self::vrf::*;
 #[cfg(feature = "nizk")]
 pub mod nizk;
 #[cfg(feature = "nizk")]
-pub use self::nizk::*;
\ No newline at end of file
+pub use self::nizk::*;
+
+#[cfg(feature = "pcd")]
+pub mod pcd;
+#[cfg(feature = "pcd")]
+pub use self::pcd::*;
+
+#[cfg(feature = "consistency-tests")]
+pub mod consistency;
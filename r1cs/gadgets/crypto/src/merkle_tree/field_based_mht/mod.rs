@@ -84,6 +84,259 @@ impl<P, HGadget, ConstraintF> FieldBasedMerkleTreePathGadget<P, HGadget, Constra
             should_enforce,
         )
     }
+
+    pub fn check_update<
+        CS: ConstraintSystem<ConstraintF>,
+    >(
+        &self,
+        cs: CS,
+        old_root: &HGadget::DataGadget,
+        new_root: &HGadget::DataGadget,
+        old_leaf: &HGadget::DataGadget,
+        new_leaf: &HGadget::DataGadget,
+    ) -> Result<(), SynthesisError> {
+        self.conditionally_check_update(cs, old_root, new_root, old_leaf, new_leaf, &Boolean::Constant(true))
+    }
+
+    /// Enforces, if `should_enforce` is true, the transition from `old_root` to `new_root`
+    /// replacing `old_leaf` by `new_leaf`, i.e. the membership of both leaves at the same
+    /// position, with respect to the corresponding root. See `FieldBasedMerkleTreeUpdateProof`.
+    pub fn conditionally_check_update<
+        CS: ConstraintSystem<ConstraintF>,
+    >(
+        &self,
+        mut cs: CS,
+        old_root: &HGadget::DataGadget,
+        new_root: &HGadget::DataGadget,
+        old_leaf: &HGadget::DataGadget,
+        new_leaf: &HGadget::DataGadget,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        self.conditionally_check_membership(cs.ns(|| "check old leaf"), old_root, old_leaf, should_enforce)?;
+        self.conditionally_check_membership(cs.ns(|| "check new leaf"), new_root, new_leaf, should_enforce)
+    }
+
+    /// Enforces, if `should_enforce` is true, that this is the path of the leaf whose
+    /// index has little endian bits `index`, i.e. that the directions of the path are
+    /// the bits of the index, padded with zeros.
+    pub fn conditionally_check_leaf_index<
+        CS: ConstraintSystem<ConstraintF>,
+    >(
+        &self,
+        mut cs: CS,
+        index: &[Boolean],
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        if index.len() > self.path.len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        for (i, &(_, ref direction)) in self.path.iter().enumerate() {
+            let bit = index.get(i).cloned().unwrap_or(Boolean::Constant(false));
+            direction.conditional_enforce_equal(
+                &mut cs.ns(|| format!("direction_is_index_bit_{}", i)),
+                &bit,
+                should_enforce,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Gadget for `FieldBasedMerkleTreeMultiPath`. The indices of the opened leaves
+/// determine which nodes are hashed together, so they are constants of the circuit,
+/// given at setup time to `alloc_with_indices` without any multiproof. Leaf indices
+/// which are variables of the circuit are bound to them by `conditionally_check_leaf_indices`.
+pub struct FieldBasedMerkleTreeMultiPathGadget<P, HGadget, ConstraintF>
+    where
+        P: FieldBasedMerkleTreeConfig,
+        P::H: FieldBasedHash<Data = ConstraintF>,
+        HGadget: FieldBasedHashGadget<P::H, ConstraintF>,
+        ConstraintF: Field,
+{
+    indices:   Vec<usize>,
+    auxiliary: Vec<HGadget::DataGadget>,
+}
+
+impl<P, HGadget, ConstraintF> FieldBasedMerkleTreeMultiPathGadget<P, HGadget, ConstraintF>
+    where
+        P: FieldBasedMerkleTreeConfig,
+        P::H: FieldBasedHash<Data = ConstraintF>,
+        HGadget: FieldBasedHashGadget<P::H, ConstraintF>,
+        ConstraintF: PrimeField,
+{
+    /// Allocates the multiproof of the leaves at `indices`, which must be strictly
+    /// increasing. Only the auxiliary nodes are taken from `value_gen`, so that the
+    /// gadget can be allocated at setup time from `indices` alone. Fails if the
+    /// multiproof is for other indices.
+    pub fn alloc_with_indices<F, T, CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        indices: &[usize],
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+        where
+            F: FnOnce() -> Result<T, SynthesisError>,
+            T: Borrow<FieldBasedMerkleTreeMultiPath<P>>,
+    {
+        Self::alloc_auxiliary(cs, indices, value_gen, false)
+    }
+
+    /// Same as `alloc_with_indices`, allocating the auxiliary nodes as public inputs.
+    pub fn alloc_input_with_indices<F, T, CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        indices: &[usize],
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+        where
+            F: FnOnce() -> Result<T, SynthesisError>,
+            T: Borrow<FieldBasedMerkleTreeMultiPath<P>>,
+    {
+        Self::alloc_auxiliary(cs, indices, value_gen, true)
+    }
+
+    fn alloc_auxiliary<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        indices: &[usize],
+        value_gen: F,
+        input: bool,
+    ) -> Result<Self, SynthesisError>
+        where
+            F: FnOnce() -> Result<T, SynthesisError>,
+            T: Borrow<FieldBasedMerkleTreeMultiPath<P>>,
+    {
+        if indices.is_empty()
+            || indices.windows(2).any(|pair| pair[0] >= pair[1])
+            || indices[indices.len() - 1] >= 1 << (P::HEIGHT - 1)
+        {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let num_auxiliary = num_auxiliary_nodes(indices, P::HEIGHT);
+
+        let auxiliary_values = value_gen().ok().map(|value| value.borrow().clone());
+        if let Some(multi_path) = &auxiliary_values {
+            if multi_path.indices != indices || multi_path.auxiliary.len() != num_auxiliary {
+                return Err(SynthesisError::Unsatisfiable);
+            }
+        }
+
+        let mut auxiliary = Vec::with_capacity(num_auxiliary);
+        for i in 0..num_auxiliary {
+            let value_gen = || {
+                auxiliary_values
+                    .as_ref()
+                    .map(|multi_path| multi_path.auxiliary[i])
+                    .ok_or(SynthesisError::AssignmentMissing)
+            };
+            let cs = cs.ns(|| format!("auxiliary_hash_{}", i));
+            let node_hash = if input {
+                HGadget::DataGadget::alloc_input(cs, value_gen)?
+            } else {
+                HGadget::DataGadget::alloc(cs, value_gen)?
+            };
+            auxiliary.push(node_hash);
+        }
+        Ok(FieldBasedMerkleTreeMultiPathGadget { indices: indices.to_vec(), auxiliary })
+    }
+
+    /// Enforces, if `should_enforce` is true, that the leaf indices with little endian
+    /// bits `indices` are the ones of this multiproof.
+    pub fn conditionally_check_leaf_indices<
+        CS: ConstraintSystem<ConstraintF>,
+    >(
+        &self,
+        mut cs: CS,
+        indices: &[Vec<Boolean>],
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        if indices.len() != self.indices.len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        for (i, (&index, bits)) in self.indices.iter().zip(indices.iter()).enumerate() {
+            // The bits must be able to represent the index.
+            if bits.len() > P::HEIGHT - 1 || index.checked_shr(bits.len() as u32).unwrap_or(0) != 0 {
+                return Err(SynthesisError::Unsatisfiable);
+            }
+            for (j, bit) in bits.iter().enumerate() {
+                bit.conditional_enforce_equal(
+                    &mut cs.ns(|| format!("index_{}_bit_{}", i, j)),
+                    &Boolean::Constant((index >> j) & 1 == 1),
+                    should_enforce,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check_membership<
+        CS: ConstraintSystem<ConstraintF>,
+    >(
+        &self,
+        cs: CS,
+        root: &HGadget::DataGadget,
+        leaves: &[HGadget::DataGadget],
+    ) -> Result<(), SynthesisError> {
+        self.conditionally_check_membership(cs, root, leaves, &Boolean::Constant(true))
+    }
+
+    /// Enforces the membership of `leaves`, at the indices of the multiproof, if
+    /// `should_enforce` is true. As the indices are constants, no selection of the
+    /// hash inputs is needed, and the shared nodes of the paths are hashed once.
+    pub fn conditionally_check_membership<
+        CS: ConstraintSystem<ConstraintF>,
+    >(
+        &self,
+        mut cs: CS,
+        root: &HGadget::DataGadget,
+        leaves: &[HGadget::DataGadget],
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError> {
+
+        assert_eq!(leaves.len(), self.indices.len());
+        assert!(!leaves.is_empty());
+
+        let mut auxiliary = self.auxiliary.iter();
+        let mut nodes = self.indices.iter().cloned().zip(leaves.iter().cloned()).collect::<Vec<_>>();
+
+        for level in 0..P::HEIGHT - 1 {
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut i = 0;
+            while i < nodes.len() {
+                let index = nodes[i].0;
+                let (lhs, rhs) = if index % 2 == 0 && i + 1 < nodes.len() && nodes[i + 1].0 == index + 1 {
+                    i += 1;
+                    (nodes[i - 1].1.clone(), nodes[i].1.clone())
+                } else {
+                    let sibling = auxiliary.next().ok_or(SynthesisError::Unsatisfiable)?.clone();
+                    if index % 2 == 0 {
+                        (nodes[i].1.clone(), sibling)
+                    } else {
+                        (sibling, nodes[i].1.clone())
+                    }
+                };
+                let parent = hash_inner_node_gadget::<P::H, HGadget, ConstraintF, _>(
+                    &mut cs.ns(|| format!("hash_inner_node_{}_of_level_{}", parents.len(), level)),
+                    lhs,
+                    rhs,
+                )?;
+                parents.push((index / 2, parent));
+                i += 1;
+            }
+            nodes = parents;
+        }
+
+        if auxiliary.next().is_some() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        root.conditional_enforce_equal(
+            &mut cs.ns(|| "root_is_last"),
+            &nodes[0].1,
+            should_enforce,
+        )
+    }
 }
 
 pub struct FieldBasedMerkleTreeGadget<P, HGadget, ConstraintF>
@@ -162,6 +415,29 @@ impl<P, HGadget, ConstraintF> FieldBasedMerkleTreeGadget<P, HGadget, ConstraintF
     }
 }
 
+/// Returns the number of auxiliary nodes of the multiproof of the leaves at `indices`,
+/// i.e. of the siblings which are not computable from the opened leaves.
+fn num_auxiliary_nodes(indices: &[usize], height: usize) -> usize {
+    let mut num_auxiliary = 0;
+    let mut nodes = indices.to_vec();
+    for _ in 0..height - 1 {
+        let mut parents = Vec::with_capacity(nodes.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            let index = nodes[i];
+            if index % 2 == 0 && i + 1 < nodes.len() && nodes[i + 1] == index + 1 {
+                i += 1;
+            } else {
+                num_auxiliary += 1;
+            }
+            parents.push(index / 2);
+            i += 1;
+        }
+        nodes = parents;
+    }
+    num_auxiliary
+}
+
 pub(crate) fn hash_inner_node_gadget<H, HG, ConstraintF, CS>(
     cs: CS,
     left_child: HG::DataGadget,
@@ -231,6 +507,43 @@ for FieldBasedMerkleTreePathGadget<P, HGadget, ConstraintF>
     }
 }
 
+impl<P, HGadget, ConstraintF> AllocGadget<FieldBasedMerkleTreeMultiPath<P>, ConstraintF>
+for FieldBasedMerkleTreeMultiPathGadget<P, HGadget, ConstraintF>
+    where
+        P: FieldBasedMerkleTreeConfig,
+        P::H: FieldBasedHash<Data = ConstraintF>,
+        HGadget: FieldBasedHashGadget<P::H, ConstraintF>,
+        ConstraintF: PrimeField,
+{
+    /// Takes the indices from the multiproof. Use `alloc_with_indices` when there is
+    /// none at setup time.
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+        where
+            F: FnOnce() -> Result<T, SynthesisError>,
+            T: Borrow<FieldBasedMerkleTreeMultiPath<P>>,
+    {
+        let value = value_gen()?;
+        let indices = value.borrow().indices.clone();
+        Self::alloc_with_indices(cs, &indices, || Ok(value))
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+        where
+            F: FnOnce() -> Result<T, SynthesisError>,
+            T: Borrow<FieldBasedMerkleTreeMultiPath<P>>,
+    {
+        let value = value_gen()?;
+        let indices = value.borrow().indices.clone();
+        Self::alloc_input_with_indices(cs, &indices, || Ok(value))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use primitives::{
@@ -239,7 +552,7 @@ mod test {
     };
     use crate::crh::MNT4PoseidonHashGadget;
     use algebra::fields::mnt4753::Fr;
-    use r1cs_core::ConstraintSystem;
+    use r1cs_core::{ConstraintSystem, Index, LinearCombination, Variable};
     use rand::{Rng, SeedableRng};
     use rand_xorshift::XorShiftRng;
     use super::*;
@@ -259,6 +572,62 @@ mod test {
 
     type HG = MNT4PoseidonHashGadget;
 
+    /// Counts variables and constraints without evaluating any assignment, as the
+    /// constraint systems used to generate the parameters of a circuit do.
+    #[derive(Default)]
+    struct SetupConstraintSystem {
+        num_inputs:      usize,
+        num_aux:         usize,
+        num_constraints: usize,
+    }
+
+    impl ConstraintSystem<Fr> for SetupConstraintSystem {
+        type Root = Self;
+
+        fn alloc<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+            where
+                FN: FnOnce() -> Result<Fr, SynthesisError>,
+                A: FnOnce() -> AR,
+                AR: Into<String>,
+        {
+            self.num_aux += 1;
+            Ok(Variable::new_unchecked(Index::Aux(self.num_aux - 1)))
+        }
+
+        fn alloc_input<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+            where
+                FN: FnOnce() -> Result<Fr, SynthesisError>,
+                A: FnOnce() -> AR,
+                AR: Into<String>,
+        {
+            self.num_inputs += 1;
+            Ok(Variable::new_unchecked(Index::Input(self.num_inputs)))
+        }
+
+        fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, _: LA, _: LB, _: LC)
+            where
+                A: FnOnce() -> AR,
+                AR: Into<String>,
+                LA: FnOnce(LinearCombination<Fr>) -> LinearCombination<Fr>,
+                LB: FnOnce(LinearCombination<Fr>) -> LinearCombination<Fr>,
+                LC: FnOnce(LinearCombination<Fr>) -> LinearCombination<Fr>,
+        {
+            self.num_constraints += 1;
+        }
+
+        fn push_namespace<NR: Into<String>, N: FnOnce() -> NR>(&mut self, _: N) {}
+
+        fn pop_namespace(&mut self) {}
+
+        fn get_root(&mut self) -> &mut Self::Root {
+            self
+        }
+
+        fn num_constraints(&self) -> usize {
+            self.num_constraints
+        }
+    }
+
     fn check_merkle_paths(leaves: &[Fr], use_bad_root: bool) -> bool {
 
         let tree = MNT4753FieldBasedMerkleTree::new(leaves).unwrap();
@@ -416,4 +785,188 @@ mod test {
         assert!(!check_merkle_paths(&leaves, true));
         assert!(!check_leaves(&leaves, true));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn multi_path_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+
+        let leaves = (0..32).map(|_| rng.gen()).collect::<Vec<Fr>>();
+        let tree = MNT4753FieldBasedMerkleTree::new(&leaves).unwrap();
+        let root = tree.root();
+
+        let indices = vec![0, 1, 2, 9, 17, 31];
+        let proof = tree.generate_multi_proof(&indices).unwrap();
+
+        for &use_bad_root in &[false, true] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let root_g = FqGadget::alloc(
+                cs.ns(|| "alloc root"),
+                || if use_bad_root { Ok(Fr::zero()) } else { Ok(root) },
+            ).unwrap();
+
+            let leaves_g = indices
+                .iter()
+                .map(|&i| FqGadget::alloc(cs.ns(|| format!("alloc leaf_{}", i)), || Ok(leaves[i])).unwrap())
+                .collect::<Vec<_>>();
+
+            let multi_path = FieldBasedMerkleTreeMultiPathGadget::<_, HG, _>::alloc(
+                cs.ns(|| "alloc multi path"),
+                || Ok(proof.clone()),
+            ).unwrap();
+
+            multi_path.check_membership(cs.ns(|| "check multi path"), &root_g, &leaves_g).unwrap();
+            assert_eq!(cs.is_satisfied(), !use_bad_root);
+        }
+
+        // Fewer constraints than checking the paths one by one
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let root_g = FqGadget::alloc(cs.ns(|| "alloc root"), || Ok(root)).unwrap();
+        let leaves_g = indices
+            .iter()
+            .map(|&i| FqGadget::alloc(cs.ns(|| format!("alloc leaf_{}", i)), || Ok(leaves[i])).unwrap())
+            .collect::<Vec<_>>();
+        let multi_path = FieldBasedMerkleTreeMultiPathGadget::<_, HG, _>::alloc(
+            cs.ns(|| "alloc multi path"),
+            || Ok(proof.clone()),
+        ).unwrap();
+        let before = cs.num_constraints();
+        multi_path.check_membership(cs.ns(|| "check multi path"), &root_g, &leaves_g).unwrap();
+        let multi_path_constraints = cs.num_constraints() - before;
+
+        let path = FieldBasedMerkleTreePathGadget::<_, HG, _>::alloc(
+            cs.ns(|| "alloc path"),
+            || Ok(tree.generate_proof(0, &leaves[0]).unwrap()),
+        ).unwrap();
+        let before = cs.num_constraints();
+        path.check_membership(cs.ns(|| "check path"), &root_g, &leaves_g[0]).unwrap();
+        let path_constraints = cs.num_constraints() - before;
+
+        assert!(cs.is_satisfied());
+        assert!(multi_path_constraints < indices.len() * path_constraints);
+    }
+
+    #[test]
+    fn multi_path_indices_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+
+        let leaves = (0..32).map(|_| rng.gen()).collect::<Vec<Fr>>();
+        let tree = MNT4753FieldBasedMerkleTree::new(&leaves).unwrap();
+        let root = tree.root();
+
+        let indices = vec![0, 1, 2, 9, 17, 31];
+        let proof = tree.generate_multi_proof(&indices).unwrap();
+
+        // The shape of the gadget only depends on the indices
+        let mut setup_cs = SetupConstraintSystem::default();
+        let root_g = FqGadget::alloc(setup_cs.ns(|| "alloc root"), || Err(SynthesisError::AssignmentMissing)).unwrap();
+        let leaves_g = indices
+            .iter()
+            .map(|&i| FqGadget::alloc(
+                setup_cs.ns(|| format!("alloc leaf_{}", i)),
+                || Err(SynthesisError::AssignmentMissing),
+            ).unwrap())
+            .collect::<Vec<_>>();
+        let multi_path = FieldBasedMerkleTreeMultiPathGadget::<_, HG, _>::alloc_with_indices(
+            setup_cs.ns(|| "alloc multi path"),
+            &indices,
+            || Err::<FieldBasedMerkleTreeMultiPath<MNT4753FieldBasedMerkleTreeParams>, _>(SynthesisError::AssignmentMissing),
+        ).unwrap();
+        assert_eq!(multi_path.auxiliary.len(), proof.auxiliary.len());
+        multi_path.check_membership(setup_cs.ns(|| "check multi path"), &root_g, &leaves_g).unwrap();
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let root_g = FqGadget::alloc(cs.ns(|| "alloc root"), || Ok(root)).unwrap();
+        let leaves_g = indices
+            .iter()
+            .map(|&i| FqGadget::alloc(cs.ns(|| format!("alloc leaf_{}", i)), || Ok(leaves[i])).unwrap())
+            .collect::<Vec<_>>();
+        let multi_path = FieldBasedMerkleTreeMultiPathGadget::<_, HG, _>::alloc(
+            cs.ns(|| "alloc multi path"),
+            || Ok(proof.clone()),
+        ).unwrap();
+        multi_path.check_membership(cs.ns(|| "check multi path"), &root_g, &leaves_g).unwrap();
+        assert!(cs.is_satisfied());
+        assert_eq!(setup_cs.num_constraints(), cs.num_constraints());
+
+        // A multiproof for other indices is rejected
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let result = FieldBasedMerkleTreeMultiPathGadget::<_, HG, _>::alloc_with_indices(
+            cs.ns(|| "alloc multi path with other indices"),
+            &[0, 1, 2, 9, 17, 30],
+            || Ok(proof.clone()),
+        );
+        assert!(match result { Err(SynthesisError::Unsatisfiable) => true, _ => false });
+
+        for &use_bad_index in &[false, true] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let root_g = FqGadget::alloc(cs.ns(|| "alloc root"), || Ok(root)).unwrap();
+            let leaves_g = indices
+                .iter()
+                .map(|&i| FqGadget::alloc(cs.ns(|| format!("alloc leaf_{}", i)), || Ok(leaves[i])).unwrap())
+                .collect::<Vec<_>>();
+            let indices_g = indices
+                .iter()
+                .enumerate()
+                .map(|(i, &index)| {
+                    let index = if use_bad_index && i == 3 { index + 1 } else { index };
+                    (0..5)
+                        .map(|j| Boolean::alloc(
+                            cs.ns(|| format!("alloc index_{}_bit_{}", i, j)),
+                            || Ok((index >> j) & 1 == 1),
+                        ).unwrap())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            let multi_path = FieldBasedMerkleTreeMultiPathGadget::<_, HG, _>::alloc_with_indices(
+                cs.ns(|| "alloc multi path"),
+                &indices,
+                || Ok(proof.clone()),
+            ).unwrap();
+            multi_path.conditionally_check_leaf_indices(
+                cs.ns(|| "check leaf indices"),
+                &indices_g,
+                &Boolean::Constant(true),
+            ).unwrap();
+            multi_path.check_membership(cs.ns(|| "check multi path"), &root_g, &leaves_g).unwrap();
+            assert_eq!(cs.is_satisfied(), !use_bad_index);
+        }
+    }
+
+    #[test]
+    fn update_test() {
+        let mut rng = XorShiftRng::seed_from_u64(9174123u64);
+
+        let leaves = (0..16).map(|_| rng.gen()).collect::<Vec<Fr>>();
+        let mut tree = MNT4753FieldBasedMerkleTree::new(&leaves).unwrap();
+        let old_root = tree.root();
+        let proof = tree.update_leaf(7, rng.gen()).unwrap();
+        let new_root = tree.root();
+
+        for &use_bad_root in &[false, true] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let old_root_g = FqGadget::alloc(cs.ns(|| "alloc old root"), || Ok(old_root)).unwrap();
+            let new_root_g = FqGadget::alloc(
+                cs.ns(|| "alloc new root"),
+                || if use_bad_root { Ok(old_root) } else { Ok(new_root) },
+            ).unwrap();
+            let old_leaf_g = FqGadget::alloc(cs.ns(|| "alloc old leaf"), || Ok(proof.old_leaf)).unwrap();
+            let new_leaf_g = FqGadget::alloc(cs.ns(|| "alloc new leaf"), || Ok(proof.new_leaf)).unwrap();
+            let path = FieldBasedMerkleTreePathGadget::<_, HG, _>::alloc(
+                cs.ns(|| "alloc path"),
+                || Ok(proof.path.clone()),
+            ).unwrap();
+
+            path.check_update(
+                cs.ns(|| "check update"),
+                &old_root_g,
+                &new_root_g,
+                &old_leaf_g,
+                &new_leaf_g,
+            ).unwrap();
+            assert_eq!(cs.is_satisfied(), !use_bad_root);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use algebra::{AffineCurve, Field, PairingEngine, ToConstraintField};
+use algebra::{AffineCurve, Field, PairingEngine, ProjectiveCurve, ToConstraintField};
 use proof_systems::groth16::{
     Parameters, PreparedVerifyingKey, Proof, VerifyingKey,
 };
@@ -127,15 +127,40 @@ for Groth16VerifierGadget<PairingE, ConstraintF, P>
     type ProofGadget = ProofGadget<PairingE, ConstraintF, P>;
 
     fn check_verify<'a, CS, I, T>(
-        mut cs: CS,
+        cs: CS,
         vk: &Self::VerificationKeyGadget,
-        mut public_inputs: I,
+        public_inputs: I,
         proof: &Self::ProofGadget,
     ) -> Result<(), SynthesisError>
         where
             CS: ConstraintSystem<ConstraintF>,
             I: Iterator<Item = &'a T>,
             T: 'a + ToBitsGadget<ConstraintF> + ?Sized,
+    {
+        Self::conditional_check_verify(cs, vk, public_inputs, proof, &Boolean::constant(true))
+    }
+}
+
+impl<PairingE, ConstraintF, P> Groth16VerifierGadget<PairingE, ConstraintF, P>
+    where
+        PairingE: PairingEngine,
+        ConstraintF: Field,
+        P: PairingGadget<PairingE, ConstraintF>,
+{
+    /// Enforces the verification of `proof` only if `should_enforce` is true. The
+    /// proof is processed anyway, hence it must be made of valid points even when
+    /// the verification is not enforced (e.g. the generators).
+    pub fn conditional_check_verify<'a, CS, I, T>(
+        mut cs: CS,
+        vk: &VerifyingKeyGadget<PairingE, ConstraintF, P>,
+        mut public_inputs: I,
+        proof: &ProofGadget<PairingE, ConstraintF, P>,
+        should_enforce: &Boolean,
+    ) -> Result<(), SynthesisError>
+        where
+            CS: ConstraintSystem<ConstraintF>,
+            I: Iterator<Item = &'a T>,
+            T: 'a + ToBitsGadget<ConstraintF> + ?Sized,
     {
         let pvk = vk.prepare(&mut cs.ns(|| "Prepare vk"))?;
 
@@ -178,7 +203,7 @@ for Groth16VerifierGadget<PairingE, ConstraintF, P>
 
         let test = P::final_exponentiation(cs.ns(|| "Final Exp"), &test_exp).unwrap();
 
-        test.enforce_equal(cs.ns(|| "Test 1"), &pvk.alpha_g1_beta_g2)?;
+        test.conditional_enforce_equal(cs.ns(|| "Test 1"), &pvk.alpha_g1_beta_g2, should_enforce)?;
         Ok(())
     }
 }
@@ -278,6 +303,56 @@ for VerifyingKeyGadget<PairingE, ConstraintF, P>
     }
 }
 
+impl<PairingE, ConstraintF, P> ConstantGadget<VerifyingKey<PairingE>, ConstraintF>
+for VerifyingKeyGadget<PairingE, ConstraintF, P>
+    where
+        PairingE: PairingEngine,
+        ConstraintF: Field,
+        P: PairingGadget<PairingE, ConstraintF>,
+{
+    fn from_value<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        value: &VerifyingKey<PairingE>,
+    ) -> Self
+    {
+        let alpha_g1_beta_g2 =
+            P::GTGadget::from_value(cs.ns(|| "hardcode alpha_g1_beta_g2"), &value.alpha_g1_beta_g2);
+        let gamma_g2 =
+            P::G2Gadget::from_value(cs.ns(|| "hardcode gamma_g2"), &value.gamma_g2.into_projective());
+        let delta_g2 =
+            P::G2Gadget::from_value(cs.ns(|| "hardcode delta_g2"), &value.delta_g2.into_projective());
+        let gamma_abc_g1 = value.gamma_abc_g1
+            .iter()
+            .enumerate()
+            .map(|(i, gamma_abc_i)| {
+                P::G1Gadget::from_value(
+                    cs.ns(|| format!("hardcode gamma_abc_{}", i)),
+                    &gamma_abc_i.into_projective(),
+                )
+            })
+            .collect();
+
+        Self {
+            alpha_g1_beta_g2,
+            gamma_g2,
+            delta_g2,
+            gamma_abc_g1,
+        }
+    }
+
+    fn get_constant(&self) -> VerifyingKey<PairingE> {
+        VerifyingKey {
+            alpha_g1_beta_g2: self.alpha_g1_beta_g2.get_constant(),
+            gamma_g2:         self.gamma_g2.get_constant().into_affine(),
+            delta_g2:         self.delta_g2.get_constant().into_affine(),
+            gamma_abc_g1:     self.gamma_abc_g1
+                .iter()
+                .map(|gamma_abc_i| gamma_abc_i.get_constant().into_affine())
+                .collect(),
+        }
+    }
+}
+
 impl<PairingE, ConstraintF, P> AllocGadget<Proof<PairingE>, ConstraintF>
 for ProofGadget<PairingE, ConstraintF, P>
     where
@@ -0,0 +1,493 @@
+use algebra::{
+    curves::{mnt4753::MNT4, mnt6753::MNT6},
+    fields::{mnt4753::Fr as MNT4Fr, mnt6753::Fr as MNT6Fr},
+    to_bytes, AffineCurve, BigInteger, BitIterator, Field, FpParameters, PairingEngine, PrimeField,
+    ToBytes,
+};
+use primitives::crh::{
+    parameters::MNT6753PoseidonParameters, FieldBasedHash, FieldBasedHashPersonalization,
+    PersonalizedPoseidonHash,
+};
+use proof_systems::groth16::{
+    create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
+    Parameters, Proof, VerifyingKey,
+};
+use r1cs_core::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use r1cs_std::{
+    fields::fp::FpGadget,
+    pairing::{mnt4753::MNT4753PairingGadget, mnt6753::MNT6753PairingGadget},
+    prelude::*,
+    Assignment,
+};
+use rand::Rng;
+use std::marker::PhantomData;
+
+use crate::{
+    crh::{FieldBasedHashGadget, PersonalizedPoseidonHashGadget},
+    nizk::groth16::{Groth16VerifierGadget, ProofGadget, VerifyingKeyGadget},
+};
+
+/// A compliance predicate of a proof-carrying data (PCD) computation. Each node of the
+/// computation outputs a message made of `MESSAGE_LEN` field elements, and it is compliant
+/// if the predicate holds on its message and on the messages of its `ARITY` predecessors.
+/// The predicate may carry the local data of the node as witness; the instance passed to
+/// `PCD::setup` only determines the shape of the constraints.
+pub trait PCDPredicate {
+    const MESSAGE_LEN: usize;
+    const ARITY: usize;
+
+    /// Enforces the compliance of `message` with `prev_messages`. If `is_base` is set
+    /// the node has no predecessors, `prev_messages` are dummy and the predicate must
+    /// enforce `message` to be a valid starting point of the computation.
+    fn enforce_compliance<CS: ConstraintSystem<MNT4Fr>>(
+        &self,
+        cs: CS,
+        message: &[FpGadget<MNT4Fr>],
+        prev_messages: &[Vec<FpGadget<MNT4Fr>>],
+        is_base: &Boolean,
+    ) -> Result<(), SynthesisError>;
+}
+
+/// The proof of a node, i.e. a Groth16 proof over MNT6 of the wrap circuit.
+pub type PCDProof = Proof<MNT6>;
+
+#[derive(Clone)]
+pub struct PCDParameters {
+    /// Parameters of the circuit enforcing the predicate, proven over MNT4.
+    pub main_params:  Parameters<MNT4>,
+    /// Parameters of the circuit verifying the proofs of the main circuit, proven over MNT6.
+    pub wrap_params:  Parameters<MNT6>,
+    /// Hash of the verifying key of the main circuit, which is a public input of both
+    /// circuits.
+    pub main_vk_hash: MNT6Fr,
+}
+
+/// Proof-carrying data over the MNT4-753/MNT6-753 cycle, using Groth16 for both curves.
+///
+/// Each node is proven in two steps: a main proof over MNT4 enforces the predicate and
+/// verifies the (MNT6) proofs of the predecessors, and a wrap proof over MNT6 verifies the
+/// main proof. Since the two circuits would need each other's verifying key, the wrap
+/// verifying key is hardcoded in the main circuit, while the main verifying key is a
+/// witness of the wrap circuit which is bound to `PCDParameters::main_vk_hash`.
+/// The public input of both circuits is the message of the node followed by
+/// `main_vk_hash`, packed as little endian bits into field elements.
+pub struct PCD<P: PCDPredicate> {
+    _predicate: PhantomData<P>,
+}
+
+impl<P: PCDPredicate> PCD<P> {
+    pub fn setup<R: Rng>(predicate: P, rng: &mut R) -> Result<PCDParameters, SynthesisError> {
+        let message = vec![MNT4Fr::zero(); P::MESSAGE_LEN];
+
+        // The main verifying key is a witness of the wrap circuit, hence a dummy one
+        // with the right number of inputs is enough to generate its parameters.
+        let wrap_circuit = WrapCircuit {
+            main_vk:    dummy_verifying_key::<MNT4>(num_statement_inputs::<MNT4Fr>(P::MESSAGE_LEN)),
+            message:    message.clone(),
+            main_proof: dummy_proof::<MNT4>(),
+        };
+        let wrap_params = generate_random_parameters::<MNT6, _, _>(wrap_circuit, rng)?;
+
+        let main_circuit = MainCircuit {
+            predicate,
+            wrap_vk:       wrap_params.vk.clone(),
+            main_vk_hash:  MNT6Fr::zero(),
+            message:       message.clone(),
+            prev_messages: vec![message; P::ARITY],
+            prev_proofs:   vec![dummy_proof::<MNT6>(); P::ARITY],
+            is_base:       true,
+        };
+        let main_params = generate_random_parameters::<MNT4, _, _>(main_circuit, rng)?;
+        let main_vk_hash = hash_main_vk(&main_params.vk)?;
+
+        Ok(PCDParameters { main_params, wrap_params, main_vk_hash })
+    }
+
+    /// Proves a node outputting `message` from the messages and proofs of its
+    /// predecessors, which must be `P::ARITY`, or none for a base case.
+    pub fn prove<R: Rng>(
+        parameters: &PCDParameters,
+        predicate: P,
+        message: &[MNT4Fr],
+        prev: &[(Vec<MNT4Fr>, PCDProof)],
+        rng: &mut R,
+    ) -> Result<PCDProof, SynthesisError> {
+        if message.len() != P::MESSAGE_LEN
+            || (!prev.is_empty() && prev.len() != P::ARITY)
+            || prev.iter().any(|(prev_message, _)| prev_message.len() != P::MESSAGE_LEN)
+        {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let is_base = prev.is_empty();
+        let (prev_messages, prev_proofs) = if is_base {
+            // The verification of the predecessors is not enforced, but the proofs
+            // are still processed by the circuit: use valid points.
+            (
+                vec![vec![MNT4Fr::zero(); P::MESSAGE_LEN]; P::ARITY],
+                vec![dummy_proof::<MNT6>(); P::ARITY],
+            )
+        } else {
+            for (prev_message, prev_proof) in prev.iter() {
+                if !Self::verify(parameters, prev_message, prev_proof)? {
+                    return Err(SynthesisError::Unsatisfiable);
+                }
+            }
+            prev.iter().cloned().unzip()
+        };
+
+        let main_circuit = MainCircuit {
+            predicate,
+            wrap_vk: parameters.wrap_params.vk.clone(),
+            main_vk_hash: parameters.main_vk_hash,
+            message: message.to_vec(),
+            prev_messages,
+            prev_proofs,
+            is_base,
+        };
+        let main_proof = create_random_proof(main_circuit, &parameters.main_params, rng)?;
+
+        let wrap_circuit = WrapCircuit {
+            main_vk: parameters.main_params.vk.clone(),
+            message: message.to_vec(),
+            main_proof,
+        };
+        create_random_proof(wrap_circuit, &parameters.wrap_params, rng)
+    }
+
+    /// Verifies that `proof` attests a compliant computation outputting `message`.
+    pub fn verify(
+        parameters: &PCDParameters,
+        message: &[MNT4Fr],
+        proof: &PCDProof,
+    ) -> Result<bool, SynthesisError> {
+        if message.len() != P::MESSAGE_LEN {
+            return Ok(false);
+        }
+        let pvk = prepare_verifying_key(&parameters.wrap_params.vk);
+        let inputs = pack_bits::<MNT6Fr>(&statement_bits(message, &parameters.main_vk_hash));
+        verify_proof(&pvk, proof, &inputs)
+    }
+}
+
+struct MainVkPersonalization;
+
+impl FieldBasedHashPersonalization for MainVkPersonalization {
+    const PERSONALIZATION: &'static [u8] = b"pcd main vk";
+}
+
+type MainVkHash = PersonalizedPoseidonHash<MNT6Fr, MNT6753PoseidonParameters, MainVkPersonalization>;
+type MainVkHashGadget =
+    PersonalizedPoseidonHashGadget<MNT6Fr, MNT6753PoseidonParameters, MainVkPersonalization>;
+
+/// Circuit over MNT4Fr enforcing the predicate and the verification of the wrap proofs of
+/// the predecessors, unless the node is a base case.
+struct MainCircuit<P: PCDPredicate> {
+    predicate:     P,
+    wrap_vk:       VerifyingKey<MNT6>,
+    main_vk_hash:  MNT6Fr,
+    message:       Vec<MNT4Fr>,
+    prev_messages: Vec<Vec<MNT4Fr>>,
+    prev_proofs:   Vec<Proof<MNT6>>,
+    is_base:       bool,
+}
+
+impl<P: PCDPredicate> ConstraintSynthesizer<MNT4Fr> for MainCircuit<P> {
+    fn generate_constraints<CS: ConstraintSystem<MNT4Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let message = alloc_message(cs.ns(|| "alloc message"), &self.message)?;
+        let main_vk_hash_bits = field_to_bits(&self.main_vk_hash)
+            .into_iter()
+            .enumerate()
+            .map(|(i, bit)| Boolean::alloc(cs.ns(|| format!("alloc main vk hash bit {}", i)), || Ok(bit)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut statement = message_to_bits(cs.ns(|| "message to bits"), &message)?;
+        statement.extend_from_slice(&main_vk_hash_bits);
+        enforce_statement_inputs(cs.ns(|| "statement inputs"), &statement)?;
+
+        let is_base = Boolean::alloc(cs.ns(|| "alloc is base"), || Ok(self.is_base))?;
+        let should_verify = is_base.not();
+
+        let wrap_vk = VerifyingKeyGadget::<MNT6, MNT4Fr, MNT6753PairingGadget>::from_value(
+            cs.ns(|| "hardcode wrap vk"),
+            &self.wrap_vk,
+        );
+
+        let mut prev_messages = Vec::with_capacity(P::ARITY);
+        for (i, (prev_message, prev_proof)) in self.prev_messages
+            .iter()
+            .zip(self.prev_proofs.iter())
+            .enumerate()
+        {
+            let mut cs = cs.ns(|| format!("predecessor {}", i));
+
+            let prev_message = alloc_message(cs.ns(|| "alloc message"), prev_message)?;
+            let mut prev_statement = message_to_bits(cs.ns(|| "message to bits"), &prev_message)?;
+            prev_statement.extend_from_slice(&main_vk_hash_bits);
+
+            let prev_proof = ProofGadget::<MNT6, MNT4Fr, MNT6753PairingGadget>::alloc(
+                cs.ns(|| "alloc proof"),
+                || Ok(prev_proof),
+            )?;
+            Groth16VerifierGadget::<MNT6, MNT4Fr, MNT6753PairingGadget>::conditional_check_verify(
+                cs.ns(|| "verify proof"),
+                &wrap_vk,
+                prev_statement.chunks(<MNT6Fr as PrimeField>::Params::CAPACITY as usize),
+                &prev_proof,
+                &should_verify,
+            )?;
+            prev_messages.push(prev_message);
+        }
+
+        self.predicate.enforce_compliance(
+            cs.ns(|| "enforce compliance"),
+            &message,
+            &prev_messages,
+            &is_base,
+        )
+    }
+}
+
+/// Circuit over MNT6Fr verifying a main proof, with respect to the main verifying key
+/// whose hash is part of the public input.
+struct WrapCircuit {
+    main_vk:    VerifyingKey<MNT4>,
+    message:    Vec<MNT4Fr>,
+    main_proof: Proof<MNT4>,
+}
+
+impl ConstraintSynthesizer<MNT6Fr> for WrapCircuit {
+    fn generate_constraints<CS: ConstraintSystem<MNT6Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let main_vk = VerifyingKeyGadget::<MNT4, MNT6Fr, MNT4753PairingGadget>::alloc(
+            cs.ns(|| "alloc main vk"),
+            || Ok(&self.main_vk),
+        )?;
+
+        // Hash the main verifying key, packing its bytes as in `hash_main_vk`
+        let main_vk_bits = main_vk
+            .to_bytes(cs.ns(|| "main vk to bytes"))?
+            .iter()
+            .flat_map(|byte| byte.into_bits_le())
+            .collect::<Vec<_>>();
+        let main_vk_packed = main_vk_bits
+            .chunks(<MNT6Fr as PrimeField>::Params::CAPACITY as usize)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut chunk = chunk.to_vec();
+                chunk.reverse();
+                FpGadget::<MNT6Fr>::from_bits(cs.ns(|| format!("pack main vk chunk {}", i)), &chunk)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let main_vk_hash = MainVkHashGadget::check_evaluation_gadget(
+            cs.ns(|| "hash main vk"),
+            &main_vk_packed,
+        )?;
+        let mut main_vk_hash_bits = main_vk_hash.to_bits_strict(cs.ns(|| "main vk hash to bits"))?;
+        main_vk_hash_bits.reverse();
+
+        // The message bits are bound to the canonical ones of the main circuit by the
+        // verification of the main proof.
+        let mut statement = Vec::new();
+        for (i, message) in self.message.iter().enumerate() {
+            for (j, bit) in field_to_bits(message).into_iter().enumerate() {
+                statement.push(Boolean::alloc(cs.ns(|| format!("alloc message {} bit {}", i, j)), || Ok(bit))?);
+            }
+        }
+        statement.extend_from_slice(&main_vk_hash_bits);
+        enforce_statement_inputs(cs.ns(|| "statement inputs"), &statement)?;
+
+        let main_proof = ProofGadget::<MNT4, MNT6Fr, MNT4753PairingGadget>::alloc(
+            cs.ns(|| "alloc main proof"),
+            || Ok(&self.main_proof),
+        )?;
+        Groth16VerifierGadget::<MNT4, MNT6Fr, MNT4753PairingGadget>::conditional_check_verify(
+            cs.ns(|| "verify main proof"),
+            &main_vk,
+            statement.chunks(<MNT4Fr as PrimeField>::Params::CAPACITY as usize),
+            &main_proof,
+            &Boolean::constant(true),
+        )
+    }
+}
+
+fn alloc_message<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    message: &[F],
+) -> Result<Vec<FpGadget<F>>, SynthesisError> {
+    message
+        .iter()
+        .enumerate()
+        .map(|(i, element)| FpGadget::alloc(cs.ns(|| format!("alloc element {}", i)), || Ok(element)))
+        .collect()
+}
+
+// Returns the little endian bits of the elements of `message`.
+fn message_to_bits<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    message: &[FpGadget<F>],
+) -> Result<Vec<Boolean>, SynthesisError> {
+    let mut bits = Vec::new();
+    for (i, element) in message.iter().enumerate() {
+        let mut element_bits = element.to_bits_strict(cs.ns(|| format!("element {} to bits", i)))?;
+        element_bits.reverse();
+        bits.extend_from_slice(&element_bits);
+    }
+    Ok(bits)
+}
+
+// Allocates the public inputs packing `statement`, as in `pack_bits`, and enforces
+// them to be equal to it.
+fn enforce_statement_inputs<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    statement: &[Boolean],
+) -> Result<(), SynthesisError> {
+    for (i, chunk) in statement.chunks(F::Params::CAPACITY as usize).enumerate() {
+        let mut chunk = chunk.to_vec();
+        chunk.reverse();
+        let packed = FpGadget::<F>::from_bits(cs.ns(|| format!("pack chunk {}", i)), &chunk)?;
+        let input = FpGadget::<F>::alloc_input(
+            cs.ns(|| format!("alloc input {}", i)),
+            || packed.get_value().get(),
+        )?;
+        packed.enforce_equal(cs.ns(|| format!("check input {}", i)), &input)?;
+    }
+    Ok(())
+}
+
+// Returns the little endian bits of `value`, as many as the bits of the modulus.
+fn field_to_bits<F: PrimeField>(value: &F) -> Vec<bool> {
+    let mut bits = BitIterator::new(value.into_repr()).collect::<Vec<_>>();
+    bits.reverse();
+    bits.truncate(F::Params::MODULUS_BITS as usize);
+    bits
+}
+
+// Packs the little endian `bits` into field elements, `F::Params::CAPACITY` bits each.
+fn pack_bits<F: PrimeField>(bits: &[bool]) -> Vec<F> {
+    bits.chunks(F::Params::CAPACITY as usize)
+        .map(|chunk| {
+            let mut chunk = chunk.to_vec();
+            chunk.reverse();
+            F::from_repr(F::BigInt::from_bits(&chunk))
+        })
+        .collect()
+}
+
+fn statement_bits(message: &[MNT4Fr], main_vk_hash: &MNT6Fr) -> Vec<bool> {
+    let mut bits = message.iter().flat_map(field_to_bits).collect::<Vec<_>>();
+    bits.extend(field_to_bits(main_vk_hash));
+    bits
+}
+
+// Returns the number of elements of `F` packing the statement of a message of length
+// `message_len`.
+fn num_statement_inputs<F: PrimeField>(message_len: usize) -> usize {
+    let statement_len = message_len * <MNT4Fr as PrimeField>::Params::MODULUS_BITS as usize
+        + <MNT6Fr as PrimeField>::Params::MODULUS_BITS as usize;
+    let capacity = F::Params::CAPACITY as usize;
+    (statement_len + capacity - 1) / capacity
+}
+
+fn hash_main_vk(vk: &VerifyingKey<MNT4>) -> Result<MNT6Fr, SynthesisError> {
+    // Same serialization as the one of `VerifyingKeyGadget::to_bytes`
+    let bytes = to_bytes![vk.alpha_g1_beta_g2, vk.gamma_g2, vk.delta_g2, vk.gamma_abc_g1]?;
+    let bits = bytes
+        .iter()
+        .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+        .collect::<Vec<_>>();
+    MainVkHash::evaluate(&pack_bits::<MNT6Fr>(&bits)).map_err(|_| SynthesisError::Unsatisfiable)
+}
+
+fn dummy_proof<E: PairingEngine>() -> Proof<E> {
+    Proof {
+        a: E::G1Affine::prime_subgroup_generator(),
+        b: E::G2Affine::prime_subgroup_generator(),
+        c: E::G1Affine::prime_subgroup_generator(),
+    }
+}
+
+fn dummy_verifying_key<E: PairingEngine>(num_inputs: usize) -> VerifyingKey<E> {
+    let g1 = E::G1Affine::prime_subgroup_generator();
+    let g2 = E::G2Affine::prime_subgroup_generator();
+    VerifyingKey {
+        alpha_g1_beta_g2: E::pairing(g1, g2),
+        gamma_g2:         g2,
+        delta_g2:         g2,
+        gamma_abc_g1:     vec![g1; num_inputs + 1],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use algebra::{fields::mnt4753::Fr, Field};
+    use crate::pcd::{PCDPredicate, PCD};
+    use r1cs_core::{ConstraintSystem, SynthesisError};
+    use r1cs_std::{fields::fp::FpGadget, prelude::*};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    // A counter starting from zero, incremented by each node.
+    struct CounterPredicate;
+
+    impl PCDPredicate for CounterPredicate {
+        const MESSAGE_LEN: usize = 1;
+        const ARITY: usize = 1;
+
+        fn enforce_compliance<CS: ConstraintSystem<Fr>>(
+            &self,
+            mut cs: CS,
+            message: &[FpGadget<Fr>],
+            prev_messages: &[Vec<FpGadget<Fr>>],
+            is_base: &Boolean,
+        ) -> Result<(), SynthesisError> {
+            let zero = FpGadget::<Fr>::zero(cs.ns(|| "zero"))?;
+            let incremented = prev_messages[0][0].add_constant(cs.ns(|| "increment"), &Fr::one())?;
+            let expected = FpGadget::<Fr>::conditionally_select(
+                cs.ns(|| "select expected"),
+                is_base,
+                &zero,
+                &incremented,
+            )?;
+            message[0].enforce_equal(cs.ns(|| "check counter"), &expected)
+        }
+    }
+
+    type CounterPCD = PCD<CounterPredicate>;
+
+    // A base case and a single step, the smallest computation with a predecessor.
+    #[test]
+    fn counter_pcd_test() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let parameters = CounterPCD::setup(CounterPredicate, rng).unwrap();
+
+        let base = vec![Fr::zero()];
+        let base_proof = CounterPCD::prove(&parameters, CounterPredicate, &base, &[], rng).unwrap();
+        assert!(CounterPCD::verify(&parameters, &base, &base_proof).unwrap());
+
+        // The predecessor must have a valid proof
+        assert!(CounterPCD::prove(
+            &parameters,
+            CounterPredicate,
+            &[Fr::one() + &Fr::one()],
+            &[(vec![Fr::one()], base_proof.clone())],
+            rng,
+        ).is_err());
+
+        let next = vec![Fr::one()];
+        let next_proof = CounterPCD::prove(
+            &parameters,
+            CounterPredicate,
+            &next,
+            &[(base.clone(), base_proof)],
+            rng,
+        ).unwrap();
+        assert!(CounterPCD::verify(&parameters, &next, &next_proof).unwrap());
+        assert!(!CounterPCD::verify(&parameters, &base, &next_proof).unwrap());
+    }
+}
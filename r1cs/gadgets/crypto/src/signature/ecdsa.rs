@@ -0,0 +1,466 @@
+//! A gadget verifying ECDSA signatures over a short Weierstrass curve of prime
+//! order, e.g. secp256k1, whose base and scalar fields are emulated in the
+//! constraint field by `NonNativeFieldGadget`s.
+use algebra::{
+    curves::{
+        models::SWModelParameters,
+        short_weierstrass_projective::GroupAffine,
+        AffineCurve, ProjectiveCurve,
+    },
+    fields::{Field, PrimeField, SquareRootField},
+};
+use primitives::signature::ecdsa::{EcdsaPublicKey, EcdsaSignature};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::{
+    alloc::{AllocGadget, ConstantGadget},
+    bits::boolean::Boolean,
+    fields::nonnative::NonNativeFieldGadget,
+    select::CondSelectGadget,
+};
+use std::{borrow::Borrow, marker::PhantomData};
+
+/// A point of the curve other than the point at infinity, in affine
+/// coordinates over the emulated base field.
+#[derive(Derivative)]
+#[derivative(
+    Debug(bound = "P: SWModelParameters, P::BaseField: PrimeField, ConstraintF: PrimeField"),
+    Clone(bound = "P: SWModelParameters, P::BaseField: PrimeField, ConstraintF: PrimeField")
+)]
+pub struct NonNativeAffineGadget<P: SWModelParameters, ConstraintF: PrimeField>
+where
+    P::BaseField: PrimeField,
+{
+    pub x: NonNativeFieldGadget<P::BaseField, ConstraintF>,
+    pub y: NonNativeFieldGadget<P::BaseField, ConstraintF>,
+}
+
+pub type EcdsaPublicKeyGadget<P, ConstraintF> = NonNativeAffineGadget<P, ConstraintF>;
+
+impl<P, ConstraintF> NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    pub fn from_value<CS: ConstraintSystem<ConstraintF>>(mut cs: CS, point: &GroupAffine<P>) -> Self {
+        Self {
+            x: NonNativeFieldGadget::from_value(cs.ns(|| "x"), &point.x),
+            y: NonNativeFieldGadget::from_value(cs.ns(|| "y"), &point.y),
+        }
+    }
+
+    pub fn get_value(&self) -> Option<GroupAffine<P>> {
+        match (self.x.get_value(), self.y.get_value()) {
+            (Some(x), Some(y)) => Some(GroupAffine::new(x, y, false)),
+            _ => None,
+        }
+    }
+
+    /// Enforces y^2 = x^3 + a * x + b.
+    fn enforce_on_curve<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<(), SynthesisError> {
+        let mut x_sq_plus_a = self.x.mul(cs.ns(|| "x^2"), &self.x)?;
+        if !P::COEFF_A.is_zero() {
+            let a = NonNativeFieldGadget::from_value(cs.ns(|| "a"), &P::COEFF_A);
+            x_sq_plus_a = x_sq_plus_a.add(cs.ns(|| "x^2 + a"), &a)?;
+        }
+        let b = NonNativeFieldGadget::from_value(cs.ns(|| "b"), &P::COEFF_B);
+        let rhs = x_sq_plus_a
+            .mul(cs.ns(|| "x^3 + a * x"), &self.x)?
+            .add(cs.ns(|| "x^3 + a * x + b"), &b)?;
+        self.y.mul_equals(cs.ns(|| "y^2 = x^3 + a * x + b"), &self.y, &rhs)
+    }
+
+    /// Returns `self + other`. The addition law is incomplete: the constraints
+    /// are unsatisfiable if `self` and `other` have the same x-coordinate.
+    pub fn add<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let dx = other.x.sub(cs.ns(|| "x2 - x1"), &self.x)?;
+        let dy = other.y.sub(cs.ns(|| "y2 - y1"), &self.y)?;
+        let dx_inv = dx.inverse(cs.ns(|| "(x2 - x1)^-1"))?;
+        let lambda = dy.mul(cs.ns(|| "lambda"), &dx_inv)?;
+        self.add_with_slope(cs.ns(|| "add"), &other.x, &lambda)
+    }
+
+    pub fn double<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Self, SynthesisError> {
+        let x_sq = self.x.mul(cs.ns(|| "x^2"), &self.x)?;
+        let mut numerator = x_sq.mul_by_constant(cs.ns(|| "3 * x^2"), &P::BaseField::from(3u64))?;
+        if !P::COEFF_A.is_zero() {
+            let a = NonNativeFieldGadget::from_value(cs.ns(|| "a"), &P::COEFF_A);
+            numerator = numerator.add(cs.ns(|| "3 * x^2 + a"), &a)?;
+        }
+        let denominator = self.y.add(cs.ns(|| "2 * y"), &self.y)?;
+        let denominator_inv = denominator.inverse(cs.ns(|| "(2 * y)^-1"))?;
+        let lambda = numerator.mul(cs.ns(|| "lambda"), &denominator_inv)?;
+        self.add_with_slope(cs.ns(|| "double"), &self.x, &lambda)
+    }
+
+    /// Returns the sum of `self` and the point with x-coordinate `other_x` on
+    /// the line through `self` with slope `lambda`.
+    fn add_with_slope<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other_x: &NonNativeFieldGadget<P::BaseField, ConstraintF>,
+        lambda: &NonNativeFieldGadget<P::BaseField, ConstraintF>,
+    ) -> Result<Self, SynthesisError> {
+        // x3 = lambda^2 - x1 - x2
+        let x = lambda
+            .mul(cs.ns(|| "lambda^2"), lambda)?
+            .sub(cs.ns(|| "lambda^2 - x1"), &self.x)?
+            .sub(cs.ns(|| "lambda^2 - x1 - x2"), other_x)?;
+        // y3 = lambda * (x1 - x3) - y1
+        let x1_minus_x3 = self.x.sub(cs.ns(|| "x1 - x3"), &x)?;
+        let y = lambda
+            .mul(cs.ns(|| "lambda * (x1 - x3)"), &x1_minus_x3)?
+            .sub(cs.ns(|| "lambda * (x1 - x3) - y1"), &self.y)?;
+        Ok(Self { x, y })
+    }
+
+    /// A point of the prime order subgroup with unknown discrete logarithm,
+    /// found by try-and-increment on the x-coordinate.
+    fn offset_point() -> GroupAffine<P> {
+        let mut x = P::BaseField::one();
+        loop {
+            let rhs = x.square() * &x + &P::mul_by_a(&x) + &P::COEFF_B;
+            if let Some(y) = rhs.sqrt() {
+                let point = GroupAffine::<P>::new(x, y, false).scale_by_cofactor();
+                if !point.is_zero() {
+                    return point.into_affine();
+                }
+            }
+            x += &P::BaseField::one();
+        }
+    }
+
+    /// Returns `g_bits * g + self_bits * self` for the constant `g` and the
+    /// big-endian `g_bits` and `self_bits` of the same length, by a joint
+    /// double-and-add. The accumulator starts from an offset point, which is
+    /// subtracted at the end, so that the incomplete addition law meets an
+    /// exceptional case only with negligible probability, or if the result
+    /// is zero.
+    pub fn double_scalar_mul<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        g: &GroupAffine<P>,
+        g_bits: &[Boolean],
+        self_bits: &[Boolean],
+    ) -> Result<Self, SynthesisError> {
+        assert_eq!(g_bits.len(), self_bits.len());
+
+        let offset = Self::offset_point();
+        let mut correction = offset.into_projective();
+        for _ in 0..g_bits.len() {
+            correction.double_in_place();
+        }
+        let correction = (-correction).into_affine();
+
+        let g = Self::from_value(cs.ns(|| "g"), g);
+        let mut acc = Self::from_value(cs.ns(|| "offset"), &offset);
+        for (i, (g_bit, self_bit)) in g_bits.iter().zip(self_bits.iter()).enumerate() {
+            acc = acc.double(cs.ns(|| format!("double {}", i)))?;
+            let acc_plus_g = acc.add(cs.ns(|| format!("add g {}", i)), &g)?;
+            acc = Self::conditionally_select(
+                cs.ns(|| format!("select g {}", i)),
+                g_bit,
+                &acc_plus_g,
+                &acc,
+            )?;
+            let acc_plus_self = acc.add(cs.ns(|| format!("add self {}", i)), self)?;
+            acc = Self::conditionally_select(
+                cs.ns(|| format!("select self {}", i)),
+                self_bit,
+                &acc_plus_self,
+                &acc,
+            )?;
+        }
+
+        let correction = Self::from_value(cs.ns(|| "correction"), &correction);
+        acc.add(cs.ns(|| "subtract offset"), &correction)
+    }
+}
+
+impl<P, ConstraintF> CondSelectGadget<ConstraintF> for NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    fn conditionally_select<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        cond: &Boolean,
+        first: &Self,
+        second: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let x = NonNativeFieldGadget::conditionally_select(cs.ns(|| "select x"), cond, &first.x, &second.x)?;
+        let y = NonNativeFieldGadget::conditionally_select(cs.ns(|| "select y"), cond, &first.y, &second.y)?;
+        Ok(Self { x, y })
+    }
+
+    fn cost() -> usize {
+        2 * <NonNativeFieldGadget<P::BaseField, ConstraintF> as CondSelectGadget<ConstraintF>>::cost()
+    }
+}
+
+impl<P, ConstraintF> AllocGadget<EcdsaPublicKey<P>, ConstraintF> for NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    fn alloc<FN, T, CS: ConstraintSystem<ConstraintF>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EcdsaPublicKey<P>>,
+    {
+        let (x, y) = match f() {
+            Ok(pk) => {
+                let pk = pk.borrow().0.into_affine();
+                (Ok(pk.x), Ok(pk.y))
+            }
+            _ => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let x = NonNativeFieldGadget::alloc(cs.ns(|| "alloc x"), || x)?;
+        let y = NonNativeFieldGadget::alloc(cs.ns(|| "alloc y"), || y)?;
+        let pk = Self { x, y };
+        pk.enforce_on_curve(cs.ns(|| "check pk on curve"))?;
+        Ok(pk)
+    }
+
+    fn alloc_input<FN, T, CS: ConstraintSystem<ConstraintF>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EcdsaPublicKey<P>>,
+    {
+        let (x, y) = match f() {
+            Ok(pk) => {
+                let pk = pk.borrow().0.into_affine();
+                (Ok(pk.x), Ok(pk.y))
+            }
+            _ => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let x = NonNativeFieldGadget::alloc_input(cs.ns(|| "alloc x"), || x)?;
+        let y = NonNativeFieldGadget::alloc_input(cs.ns(|| "alloc y"), || y)?;
+        let pk = Self { x, y };
+        pk.enforce_on_curve(cs.ns(|| "check pk on curve"))?;
+        Ok(pk)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Debug(bound = "P: SWModelParameters, ConstraintF: PrimeField"),
+    Clone(bound = "P: SWModelParameters, ConstraintF: PrimeField")
+)]
+pub struct EcdsaSignatureGadget<P: SWModelParameters, ConstraintF: PrimeField> {
+    pub r: NonNativeFieldGadget<P::ScalarField, ConstraintF>,
+    pub s: NonNativeFieldGadget<P::ScalarField, ConstraintF>,
+}
+
+impl<P, ConstraintF> AllocGadget<EcdsaSignature<P>, ConstraintF> for EcdsaSignatureGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    ConstraintF: PrimeField,
+{
+    fn alloc<FN, T, CS: ConstraintSystem<ConstraintF>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EcdsaSignature<P>>,
+    {
+        let (r, s) = match f() {
+            Ok(sig) => {
+                let sig = *sig.borrow();
+                (Ok(sig.r), Ok(sig.s))
+            }
+            _ => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let r = NonNativeFieldGadget::alloc(cs.ns(|| "alloc r"), || r)?;
+        let s = NonNativeFieldGadget::alloc(cs.ns(|| "alloc s"), || s)?;
+        Ok(Self { r, s })
+    }
+
+    fn alloc_input<FN, T, CS: ConstraintSystem<ConstraintF>>(mut cs: CS, f: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EcdsaSignature<P>>,
+    {
+        let (r, s) = match f() {
+            Ok(sig) => {
+                let sig = *sig.borrow();
+                (Ok(sig.r), Ok(sig.s))
+            }
+            _ => (
+                Err(SynthesisError::AssignmentMissing),
+                Err(SynthesisError::AssignmentMissing),
+            ),
+        };
+
+        let r = NonNativeFieldGadget::alloc_input(cs.ns(|| "alloc r"), || r)?;
+        let s = NonNativeFieldGadget::alloc_input(cs.ns(|| "alloc s"), || s)?;
+        Ok(Self { r, s })
+    }
+}
+
+pub struct EcdsaSigVerificationGadget<P: SWModelParameters, ConstraintF: PrimeField> {
+    _parameters: PhantomData<P>,
+    _field:      PhantomData<ConstraintF>,
+}
+
+impl<P, ConstraintF> EcdsaSigVerificationGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    /// Enforces that `signature` is a valid signature under `public_key` of
+    /// the message with hash `message_hash`, given by its big-endian bits.
+    /// As by the native scheme, only the leftmost bits of the hash are used,
+    /// up to the bit length of the group order. Both the low-s and the
+    /// high-s form of a signature are accepted.
+    pub fn enforce_signature_verification<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        public_key: &EcdsaPublicKeyGadget<P, ConstraintF>,
+        signature: &EcdsaSignatureGadget<P, ConstraintF>,
+        message_hash: &[Boolean],
+    ) -> Result<(), SynthesisError> {
+        // The x-coordinate of R' is reduced modulo the group order by packing
+        // its bits into the scalar field.
+        let scalar_bits = P::ScalarField::size_in_bits();
+        assert!(P::BaseField::size_in_bits() <= scalar_bits);
+
+        let hash_bits = &message_hash[..std::cmp::min(message_hash.len(), scalar_bits)];
+        let z = NonNativeFieldGadget::from_bits(cs.ns(|| "hash to scalar"), hash_bits)?;
+
+        // u1 = z / s, u2 = r / s, for non-zero r and s
+        let _r_inv = signature.r.inverse(cs.ns(|| "check r is non-zero"))?;
+        let s_inv = signature.s.inverse(cs.ns(|| "s^-1"))?;
+        let u1 = z.mul(cs.ns(|| "u1"), &s_inv)?;
+        let u2 = signature.r.mul(cs.ns(|| "u2"), &s_inv)?;
+        let u1_bits = u1.to_bits_strict(cs.ns(|| "u1 to bits"))?;
+        let u2_bits = u2.to_bits_strict(cs.ns(|| "u2 to bits"))?;
+
+        // R' = u1 * G + u2 * pk, and x(R') = r mod n
+        let r_prime = public_key.double_scalar_mul(
+            cs.ns(|| "u1 * G + u2 * pk"),
+            &GroupAffine::<P>::prime_subgroup_generator(),
+            &u1_bits,
+            &u2_bits,
+        )?;
+        let x_bits = r_prime.x.to_bits_strict(cs.ns(|| "x(R') to bits"))?;
+        let x_mod_n = NonNativeFieldGadget::<P::ScalarField, ConstraintF>::from_bits(
+            cs.ns(|| "x(R') mod n"),
+            &x_bits,
+        )?;
+        x_mod_n.enforce_equal(cs.ns(|| "check x(R') mod n = r"), &signature.r)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{curves::secp256k1::Secp256k1Parameters, fields::mnt4753::Fr as MNT4Fr};
+    use digest::Digest;
+    use primitives::signature::ecdsa::EcdsaSignatureScheme;
+    use r1cs_std::test_constraint_system::TestConstraintSystem;
+    use rand::thread_rng;
+    use sha2::Sha256;
+
+    type Ecdsa = EcdsaSignatureScheme<Secp256k1Parameters, Sha256>;
+    type EcdsaGadget = EcdsaSigVerificationGadget<Secp256k1Parameters, MNT4Fr>;
+
+    fn ecdsa_gadget_constraint_system(
+        pk: EcdsaPublicKey<Secp256k1Parameters>,
+        sig: EcdsaSignature<Secp256k1Parameters>,
+        message: &[u8],
+    ) -> TestConstraintSystem<MNT4Fr> {
+        let mut cs = TestConstraintSystem::<MNT4Fr>::new();
+
+        let pk_g = EcdsaPublicKeyGadget::<Secp256k1Parameters, MNT4Fr>::alloc(
+            cs.ns(|| "alloc pk"),
+            || Ok(pk),
+        ).unwrap();
+        let sig_g = EcdsaSignatureGadget::<Secp256k1Parameters, MNT4Fr>::alloc(
+            cs.ns(|| "alloc sig"),
+            || Ok(sig),
+        ).unwrap();
+        let hash_bits = Sha256::digest(message)
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+            .enumerate()
+            .map(|(i, bit)| Boolean::alloc(cs.ns(|| format!("alloc hash bit {}", i)), || Ok(bit)).unwrap())
+            .collect::<Vec<_>>();
+
+        EcdsaGadget::enforce_signature_verification(
+            cs.ns(|| "verify sig"),
+            &pk_g,
+            &sig_g,
+            &hash_bits,
+        ).unwrap();
+        cs
+    }
+
+    fn ecdsa_gadget_generate_constraints(
+        pk: EcdsaPublicKey<Secp256k1Parameters>,
+        sig: EcdsaSignature<Secp256k1Parameters>,
+        message: &[u8],
+    ) -> bool {
+        let cs = ecdsa_gadget_constraint_system(pk, sig, message);
+        if !cs.is_satisfied() {
+            println!("**********Unsatisfied constraints***********");
+            println!("{:?}", cs.which_is_unsatisfied());
+        }
+        cs.is_satisfied()
+    }
+
+    // Each constraint system takes long to generate, hence the tampering of the
+    // assignment of the valid signature instead of more signatures.
+    #[test]
+    fn secp256k1_ecdsa_gadget_test() {
+        let rng = &mut thread_rng();
+        let message = b"Hi, I am an ECDSA signature!";
+        let (pk, sk) = Ecdsa::keygen(rng);
+        let sig = Ecdsa::sign(rng, &sk, message).unwrap();
+        let mut cs = ecdsa_gadget_constraint_system(pk, sig, message);
+        assert!(cs.is_satisfied());
+
+        // Tampering with r, s or the message hash in the assignment
+        let tampered: [(&str, fn(MNT4Fr) -> MNT4Fr); 3] = [
+            ("alloc sig/alloc r/alloc limb 0/alloc", |v| v + &MNT4Fr::one()),
+            ("alloc sig/alloc s/alloc limb 0/alloc", |v| v + &MNT4Fr::one()),
+            ("alloc hash bit 0/boolean", |v| MNT4Fr::one() - &v),
+        ];
+        for (path, tamper) in tampered.iter() {
+            let value = cs.get(path);
+            cs.set(path, tamper(value));
+            assert!(!cs.is_satisfied(), "tampering with {} went undetected", path);
+            cs.set(path, value);
+        }
+        assert!(cs.is_satisfied());
+
+        // The high-s form is accepted as well.
+        let high_sig = EcdsaSignature { r: sig.r, s: -sig.s };
+        assert!(ecdsa_gadget_generate_constraints(pk, high_sig, message));
+
+        // Wrong message
+        assert!(!ecdsa_gadget_generate_constraints(pk, sig, b"Bad message"));
+    }
+}
@@ -6,6 +6,7 @@ use primitives::signature::{
 };
 
 pub mod schnorr;
+pub mod ecdsa;
 
 pub trait SigRandomizePkGadget<S: SignatureScheme, ConstraintF: Field> {
     type ParametersGadget: AllocGadget<S::Parameters, ConstraintF> + Clone;
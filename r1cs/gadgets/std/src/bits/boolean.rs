@@ -1,6 +1,6 @@
 use algebra::{BitIterator, Field, FpParameters, PrimeField, ToConstraintField};
 
-use crate::{prelude::*, Assignment};
+use crate::{bits::BitOrder, prelude::*, Assignment};
 use r1cs_core::{ConstraintSystem, LinearCombination, SynthesisError, Variable, ConstraintVar};
 use std::borrow::Borrow;
 use crate::fields::fp::FpGadget;
@@ -458,6 +458,133 @@ impl Boolean {
         Ok(allocated_bits[0..values_len].to_vec())
     }
 
+    /// Packs `bits` into field elements, each of them holding a chunk of
+    /// `ConstraintF::Params::CAPACITY` consecutive bits interpreted in `order`.
+    pub fn pack<ConstraintF, CS>(
+        mut cs: CS,
+        bits: &[Self],
+        order: BitOrder,
+    ) -> Result<Vec<FpGadget<ConstraintF>>, SynthesisError>
+        where
+            ConstraintF: PrimeField,
+            CS: ConstraintSystem<ConstraintF>,
+    {
+        bits.chunks(ConstraintF::Params::CAPACITY as usize)
+            .enumerate()
+            .map(|(i, chunk)| {
+                // `from_bits` expects big endian bits
+                let mut chunk = chunk.to_vec();
+                if order == BitOrder::LittleEndian {
+                    chunk.reverse();
+                }
+                FpGadget::from_bits(cs.ns(|| format!("pack chunk {}", i)), &chunk)
+            })
+            .collect()
+    }
+
+    /// Uses two bits to perform a lookup into a table of 4 constants,
+    /// returning `table[b[0] + 2 * b[1]]`.
+    pub fn two_bit_lookup<ConstraintF, CS>(
+        mut cs: CS,
+        b: &[Self],
+        table: &[bool],
+    ) -> Result<Self, SynthesisError>
+        where
+            ConstraintF: Field,
+            CS: ConstraintSystem<ConstraintF>,
+    {
+        assert_eq!(b.len(), 2);
+        assert_eq!(table.len(), 4);
+
+        let value = match (b[0].get_value(), b[1].get_value()) {
+            (Some(b0), Some(b1)) => Some(table[b0 as usize + 2 * b1 as usize]),
+            _ => None,
+        };
+        if let (Boolean::Constant(_), Boolean::Constant(_)) = (b[0], b[1]) {
+            return Ok(Boolean::constant(value.unwrap()));
+        }
+
+        let c = Self::table_constants::<ConstraintF>(table);
+        let variable = cs.alloc(
+            || "lookup result",
+            || if value.get()? { Ok(ConstraintF::one()) } else { Ok(ConstraintF::zero()) },
+        )?;
+
+        // result = c0 + b0 * (c1 - c0) + b1 * (c2 - c0) + b0 * b1 * (c3 - c2 - c1 + c0)
+        let one = CS::one();
+        cs.enforce(
+            || "enforce lookup",
+            |lc| lc + b[1].lc(one, c[3] - &c[2] - &c[1] + &c[0]) + (c[1] - &c[0], one),
+            |lc| lc + b[0].lc(one, ConstraintF::one()),
+            |lc| lc + variable + (-c[0], one) + b[1].lc(one, c[0] - &c[2]),
+        );
+
+        // The result is one of the constants of the table, hence a bit.
+        Ok(Boolean::Is(AllocatedBit { variable, value }))
+    }
+
+    /// Uses three bits to perform a lookup into a table of 8 constants,
+    /// returning `table[b[0] + 2 * b[1] + 4 * b[2]]`.
+    pub fn three_bit_lookup<ConstraintF, CS>(
+        mut cs: CS,
+        b: &[Self],
+        table: &[bool],
+    ) -> Result<Self, SynthesisError>
+        where
+            ConstraintF: Field,
+            CS: ConstraintSystem<ConstraintF>,
+    {
+        assert_eq!(b.len(), 3);
+        assert_eq!(table.len(), 8);
+
+        if let Boolean::Constant(b2) = b[2] {
+            let table = if b2 { &table[4..] } else { &table[..4] };
+            return Self::two_bit_lookup(cs, &b[..2], table);
+        }
+
+        let value = match (b[0].get_value(), b[1].get_value(), b[2].get_value()) {
+            (Some(b0), Some(b1), Some(b2)) => {
+                Some(table[b0 as usize + 2 * b1 as usize + 4 * b2 as usize])
+            },
+            _ => None,
+        };
+
+        let c = Self::table_constants::<ConstraintF>(table);
+        let b0b1 = Self::and(cs.ns(|| "b0 and b1"), &b[0], &b[1])?;
+        let variable = cs.alloc(
+            || "lookup result",
+            || if value.get()? { Ok(ConstraintF::one()) } else { Ok(ConstraintF::zero()) },
+        )?;
+
+        // The two-bit lookups into both halves of the table, selected by b2:
+        // b2 * (hi - lo) = result - lo
+        let one = CS::one();
+        let two_bit_lc = |c: &[ConstraintF]| {
+            b0b1.lc(one, c[3] - &c[2] - &c[1] + &c[0])
+                + b[0].lc(one, c[1] - &c[0])
+                + b[1].lc(one, c[2] - &c[0])
+                + (c[0], one)
+        };
+        let lo = two_bit_lc(&c[..4]);
+        let hi = two_bit_lc(&c[4..]);
+        cs.enforce(
+            || "enforce lookup",
+            |lc| lc + b[2].lc(one, ConstraintF::one()),
+            |lc| lc + &hi - &lo,
+            |lc| lc + variable - &lo,
+        );
+
+        // The result is one of the constants of the table, hence a bit.
+        Ok(Boolean::Is(AllocatedBit { variable, value }))
+    }
+
+    fn table_constants<ConstraintF: Field>(table: &[bool]) -> Vec<ConstraintF> {
+        table
+            .iter()
+            .map(|t| if *t { ConstraintF::one() } else { ConstraintF::zero() })
+            .collect()
+    }
+
     /// Construct a boolean from a known constant
     pub fn constant(b: bool) -> Self {
         Boolean::Constant(b)
@@ -689,6 +816,72 @@ impl Boolean {
 
         Ok(())
     }
+
+    /// Asserts that this bit_gadget representation is smaller than or equal
+    /// to the constant `element`, when interpreted in big endian.
+    pub fn enforce_smaller_or_equal_than<ConstraintF, CS, E>(
+        mut cs: CS,
+        bits: &[Self],
+        element: E,
+    ) -> Result<(), SynthesisError>
+        where
+            ConstraintF: Field,
+            CS: ConstraintSystem<ConstraintF>,
+            E: AsRef<[u64]>,
+    {
+        let b = element.as_ref();
+        let b_num_bits = BitIterator::new(b).skip_while(|b| !*b).count();
+
+        // Fewer bits than `element` can't represent a bigger number.
+        if bits.len() < b_num_bits {
+            return Ok(());
+        }
+
+        // Any extra leading bits must be zero.
+        let (extra_bits, bits) = bits.split_at(bits.len() - b_num_bits);
+        for (i, should_be_zero) in extra_bits.iter().enumerate() {
+            should_be_zero.enforce_equal(
+                &mut cs.ns(|| format!("Check {}-th extra bit is zero", i)),
+                &Boolean::constant(false),
+            )?;
+        }
+        let mut bits_iter = bits.iter();
+
+        // Runs of ones in b
+        let mut last_run = Boolean::constant(true);
+        let mut current_run = vec![];
+
+        let mut run_i = 0;
+        let mut nand_i = 0;
+
+        for b in BitIterator::new(b).skip_while(|b| !*b) {
+            let a = bits_iter.next().unwrap();
+
+            if b {
+                // This is part of a run of ones.
+                current_run.push(*a);
+            } else {
+                if !current_run.is_empty() {
+                    // This is the start of a run of zeros, but we need
+                    // to k-ary AND against `last_run` first.
+
+                    current_run.push(last_run);
+                    last_run = Self::kary_and(cs.ns(|| format!("run {}", run_i)), &current_run)?;
+                    run_i += 1;
+                    current_run.truncate(0);
+                }
+
+                // If `last_run` is true, i.e. all bits so far agree with
+                // those of `element`, `a` must be false.
+                Self::enforce_nand(cs.ns(|| format!("nand {}", nand_i)), &[last_run, *a])?;
+                nand_i += 1;
+            }
+        }
+        assert!(bits_iter.next().is_none());
+
+        // A final run of ones doesn't restrict the corresponding bits.
+        Ok(())
+    }
 }
 
 impl PartialEq for Boolean {
@@ -873,10 +1066,10 @@ impl<ConstraintF: Field> CondSelectGadget<ConstraintF> for Boolean {
 #[cfg(test)]
 mod test {
     use super::{AllocatedBit, Boolean};
-    use crate::{prelude::*, test_constraint_system::TestConstraintSystem};
-    use algebra::{fields::bls12_381::Fr, BitIterator, Field, PrimeField, UniformRand, ToBits};
+    use crate::{bits::BitOrder, prelude::*, test_constraint_system::TestConstraintSystem};
+    use algebra::{fields::bls12_381::Fr, BitIterator, Field, FpParameters, PrimeField, UniformRand, ToBits};
     use r1cs_core::ConstraintSystem;
-    use rand::SeedableRng;
+    use rand::{Rng, SeedableRng};
     use rand_xorshift::XorShiftRng;
     use std::str::FromStr;
 
@@ -2021,6 +2214,52 @@ mod test {
         // }
     }
 
+    #[test]
+    fn test_enforce_smaller_or_equal_than() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        for _ in 0..100 {
+            let r = Fr::rand(&mut rng).into_repr();
+            let s = Fr::rand(&mut rng).into_repr();
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let mut bits = vec![];
+            for (i, b) in BitIterator::new(r).enumerate() {
+                bits.push(Boolean::from(
+                    AllocatedBit::alloc(cs.ns(|| format!("bit_gadget {}", i)), || Ok(b)).unwrap(),
+                ));
+            }
+
+            Boolean::enforce_smaller_or_equal_than(cs.ns(|| "r <= s"), &bits, s).unwrap();
+            Boolean::enforce_smaller_or_equal_than(cs.ns(|| "r <= r"), &bits, r).unwrap();
+            assert_eq!(cs.is_satisfied(), r <= s);
+        }
+
+        // Leading bits beyond those of the element must be zero.
+        {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let bits = vec![
+                Boolean::from(AllocatedBit::alloc(cs.ns(|| "bit 0"), || Ok(false)).unwrap()),
+                Boolean::from(AllocatedBit::alloc(cs.ns(|| "bit 1"), || Ok(true)).unwrap()),
+                Boolean::from(AllocatedBit::alloc(cs.ns(|| "bit 2"), || Ok(true)).unwrap()),
+            ];
+            Boolean::enforce_smaller_or_equal_than(cs.ns(|| "3 <= 3"), &bits, [3u64]).unwrap();
+            assert!(cs.is_satisfied());
+            Boolean::enforce_smaller_or_equal_than(cs.ns(|| "3 <= 2"), &bits, [2u64]).unwrap();
+            assert!(!cs.is_satisfied());
+        }
+        {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let bits = vec![
+                Boolean::from(AllocatedBit::alloc(cs.ns(|| "bit 0"), || Ok(true)).unwrap()),
+                Boolean::from(AllocatedBit::alloc(cs.ns(|| "bit 1"), || Ok(false)).unwrap()),
+            ];
+            Boolean::enforce_smaller_or_equal_than(cs.ns(|| "2 <= 1"), &bits, [1u64]).unwrap();
+            assert!(!cs.is_satisfied());
+        }
+    }
+
     #[test]
     fn test_enforce_nand() {
         {
@@ -2117,4 +2356,111 @@ mod test {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_pack() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+        let capacity = <Fr as PrimeField>::Params::CAPACITY as usize;
+
+        for &len in [1, 64, capacity, capacity + 1, 3 * capacity - 5].iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let values = (0..len).map(|_| rng.gen()).collect::<Vec<bool>>();
+            let bits = values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    Boolean::from(AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), || Ok(*v)).unwrap())
+                })
+                .collect::<Vec<_>>();
+
+            for &order in [BitOrder::LittleEndian, BitOrder::BigEndian].iter() {
+                let packed = Boolean::pack(cs.ns(|| format!("pack {:?}", order)), &bits, order).unwrap();
+                assert_eq!(packed.len(), (len + capacity - 1) / capacity);
+                for (fe, chunk) in packed.iter().zip(values.chunks(capacity)) {
+                    let mut chunk = chunk.to_vec();
+                    if order == BitOrder::LittleEndian {
+                        chunk.reverse();
+                    }
+                    let expected = chunk
+                        .iter()
+                        .fold(Fr::zero(), |acc, b| acc.double() + &if *b { Fr::one() } else { Fr::zero() });
+                    assert_eq!(fe.get_value().unwrap(), expected);
+                }
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_lookups() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        for _ in 0..10 {
+            let table = (0..8).map(|_| rng.gen()).collect::<Vec<bool>>();
+            for index in 0..8usize {
+                for &constant in [false, true].iter() {
+                    let mut cs = TestConstraintSystem::<Fr>::new();
+                    let bits = (0..3)
+                        .map(|i| {
+                            let v = (index >> i) & 1 == 1;
+                            if constant {
+                                Boolean::constant(v)
+                            } else {
+                                AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), || Ok(v))
+                                    .unwrap()
+                                    .into()
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    let r = Boolean::two_bit_lookup(cs.ns(|| "two bit lookup"), &bits[..2], &table[..4]).unwrap();
+                    assert_eq!(r.get_value().unwrap(), table[index % 4]);
+                    let r = Boolean::three_bit_lookup(cs.ns(|| "three bit lookup"), &bits, &table).unwrap();
+                    assert_eq!(r.get_value().unwrap(), table[index]);
+                    assert!(cs.is_satisfied());
+
+                    // A wrong result is rejected
+                    if let Boolean::Is(_) = r {
+                        let v = if table[index] { Fr::zero() } else { Fr::one() };
+                        cs.set("three bit lookup/lookup result", v);
+                        assert!(!cs.is_satisfied());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_less_than() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        for _ in 0..50 {
+            let a: u64 = rng.gen();
+            let b: u64 = if rng.gen() { rng.gen() } else { a };
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let alloc = |cs: &mut TestConstraintSystem<Fr>, name: &str, v: u64| {
+                let bits = (0..64)
+                    .map(|i| {
+                        AllocatedBit::alloc(cs.ns(|| format!("{} bit {}", name, i)), || Ok((v >> i) & 1 == 1))
+                            .unwrap()
+                            .into()
+                    })
+                    .collect::<Vec<Boolean>>();
+                Boolean::pack(cs.ns(|| format!("pack {}", name)), &bits, BitOrder::LittleEndian)
+                    .unwrap()
+                    .pop()
+                    .unwrap()
+            };
+            let a_fe = alloc(&mut cs, "a", a);
+            let b_fe = alloc(&mut cs, "b", b);
+
+            let lt = a_fe.is_less_than(cs.ns(|| "a < b"), &b_fe, 64).unwrap();
+            assert_eq!(lt.get_value().unwrap(), a < b);
+            let gt = b_fe.is_less_than(cs.ns(|| "b < a"), &a_fe, 64).unwrap();
+            assert_eq!(gt.get_value().unwrap(), b < a);
+            assert!(cs.is_satisfied());
+        }
+    }
+}
+
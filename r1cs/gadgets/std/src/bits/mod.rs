@@ -4,8 +4,18 @@ use r1cs_core::{ConstraintSystem, SynthesisError};
 
 pub mod boolean;
 pub mod uint32;
+pub mod uint64;
 pub mod uint8;
 
+/// The order of the bits of a vector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The least significant bit first.
+    LittleEndian,
+    /// The most significant bit first.
+    BigEndian,
+}
+
 pub trait ToBitsGadget<ConstraintF: Field> {
     fn to_bits<CS: ConstraintSystem<ConstraintF>>(
         &self,
@@ -124,6 +134,19 @@ pub trait ToCompressedBitsGadget<ConstraintF: Field> {
     ) -> Result<Vec<Boolean>, SynthesisError>;
 }
 
+pub trait FromCompressedBitsGadget<ConstraintF: Field>
+where
+    Self: Sized,
+{
+    /// Enforce decompression of an element from the bits produced by `to_compressed`,
+    /// rejecting (by unsatisfiable constraints) exactly the bit strings that are not
+    /// a valid compression.
+    fn from_compressed<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        compressed: &[Boolean],
+    ) -> Result<Self, SynthesisError>;
+}
+
 impl<ConstraintF: Field> ToBytesGadget<ConstraintF> for [UInt8] {
     fn to_bytes<CS: ConstraintSystem<ConstraintF>>(
         &self,
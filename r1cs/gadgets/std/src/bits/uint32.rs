@@ -115,6 +115,10 @@ impl UInt32 {
         Self { value, bits }
     }
 
+    pub fn get_value(&self) -> Option<u32> {
+        self.value
+    }
+
     pub fn rotr(&self, by: usize) -> Self {
         let by = by % 32;
 
@@ -133,6 +137,53 @@ impl UInt32 {
         }
     }
 
+    pub fn rotl(&self, by: usize) -> Self {
+        self.rotr(32 - by % 32)
+    }
+
+    /// Shift this `UInt32` right by `by` bits, shifting in zeros.
+    pub fn shr(&self, by: usize) -> Self {
+        let by = std::cmp::min(by, 32);
+
+        let new_bits = self
+            .bits
+            .iter()
+            .skip(by)
+            .cloned()
+            .chain(std::iter::repeat(Boolean::constant(false)))
+            .take(32)
+            .collect();
+
+        UInt32 {
+            bits:  new_bits,
+            value: self.value.map(|v| v.checked_shr(by as u32).unwrap_or(0)),
+        }
+    }
+
+    /// Shift this `UInt32` left by `by` bits, shifting in zeros.
+    pub fn shl(&self, by: usize) -> Self {
+        let by = std::cmp::min(by, 32);
+
+        let new_bits = std::iter::repeat(Boolean::constant(false))
+            .take(by)
+            .chain(self.bits.iter().cloned())
+            .take(32)
+            .collect();
+
+        UInt32 {
+            bits:  new_bits,
+            value: self.value.map(|v| v.checked_shl(by as u32).unwrap_or(0)),
+        }
+    }
+
+    /// Bitwise NOT of this `UInt32`
+    pub fn not(&self) -> Self {
+        UInt32 {
+            bits:  self.bits.iter().map(|b| b.not()).collect(),
+            value: self.value.map(|v| !v),
+        }
+    }
+
     /// XOR this `UInt32` with another `UInt32`
     pub fn xor<ConstraintF, CS>(&self, mut cs: CS, other: &Self) -> Result<Self, SynthesisError>
     where
@@ -158,8 +209,71 @@ impl UInt32 {
         })
     }
 
+    /// AND this `UInt32` with another `UInt32`
+    pub fn and<ConstraintF, CS>(&self, mut cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        ConstraintF: Field,
+        CS: ConstraintSystem<ConstraintF>,
+    {
+        let new_value = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a & b),
+            _ => None,
+        };
+
+        let bits = self
+            .bits
+            .iter()
+            .zip(other.bits.iter())
+            .enumerate()
+            .map(|(i, (a, b))| Boolean::and(cs.ns(|| format!("and of bit_gadget {}", i)), a, b))
+            .collect::<Result<_, _>>()?;
+
+        Ok(UInt32 {
+            bits,
+            value: new_value,
+        })
+    }
+
+    /// OR this `UInt32` with another `UInt32`
+    pub fn or<ConstraintF, CS>(&self, mut cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        ConstraintF: Field,
+        CS: ConstraintSystem<ConstraintF>,
+    {
+        let new_value = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a | b),
+            _ => None,
+        };
+
+        let bits = self
+            .bits
+            .iter()
+            .zip(other.bits.iter())
+            .enumerate()
+            .map(|(i, (a, b))| Boolean::or(cs.ns(|| format!("or of bit_gadget {}", i)), a, b))
+            .collect::<Result<_, _>>()?;
+
+        Ok(UInt32 {
+            bits,
+            value: new_value,
+        })
+    }
+
     /// Perform modular addition of several `UInt32` objects.
-    pub fn addmany<ConstraintF, CS>(mut cs: CS, operands: &[Self]) -> Result<Self, SynthesisError>
+    pub fn addmany<ConstraintF, CS>(cs: CS, operands: &[Self]) -> Result<Self, SynthesisError>
+    where
+        ConstraintF: PrimeField,
+        CS: ConstraintSystem<ConstraintF>,
+    {
+        Self::addmany_with_carry(cs, operands).map(|(sum, _)| sum)
+    }
+
+    /// Perform addition of several `UInt32` objects, returning the sum modulo
+    /// 2^32 together with the bits of the carry, least significant first.
+    pub fn addmany_with_carry<ConstraintF, CS>(
+        mut cs: CS,
+        operands: &[Self],
+    ) -> Result<(Self, Vec<Boolean>), SynthesisError>
     where
         ConstraintF: PrimeField,
         CS: ConstraintSystem<ConstraintF>,
@@ -230,8 +344,14 @@ impl UInt32 {
         if all_constants && modular_value.is_some() {
             // We can just return a constant, rather than
             // unpacking the result into allocated bits.
+            let result_value = result_value.unwrap();
+            let mut carry_bits = vec![];
+            while max_value >> 32 != 0 {
+                carry_bits.push(Boolean::constant((result_value >> (32 + carry_bits.len())) & 1 == 1));
+                max_value >>= 1;
+            }
 
-            return Ok(UInt32::constant(modular_value.unwrap()));
+            return Ok((UInt32::constant(modular_value.unwrap()), carry_bits));
         }
 
         // Storage area for the resulting bits
@@ -260,13 +380,16 @@ impl UInt32 {
         // Enforce that the linear combination equals zero
         cs.enforce(|| "modular addition", |lc| lc, |lc| lc, |_| lc);
 
-        // Discard carry bits that we don't care about
-        result_bits.truncate(32);
+        // Split off the carry bits
+        let carry_bits = result_bits.split_off(32);
 
-        Ok(UInt32 {
-            bits:  result_bits,
-            value: modular_value,
-        })
+        Ok((
+            UInt32 {
+                bits:  result_bits,
+                value: modular_value,
+            },
+            carry_bits,
+        ))
     }
 }
 
@@ -537,4 +660,88 @@ mod test {
             num = num.rotate_right(1);
         }
     }
+
+    #[test]
+    fn test_uint32_shifts() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        for _ in 0..100 {
+            let num: u32 = rng.gen();
+            let a = UInt32::constant(num);
+
+            for i in 0..40 {
+                let expected = [
+                    num.rotate_left(i as u32 % 32),
+                    num.checked_shr(i as u32).unwrap_or(0),
+                    num.checked_shl(i as u32).unwrap_or(0),
+                ];
+                for (b, expected) in [a.rotl(i), a.shr(i), a.shl(i)].iter().zip(expected.iter()) {
+                    assert_eq!(b.get_value().unwrap(), *expected);
+                    assert_eq!(UInt32::from_bits_le(&b.to_bits_le()).get_value(), Some(*expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_uint32_and_or_not() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        for _ in 0..100 {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let a: u32 = rng.gen();
+            let b: u32 = rng.gen();
+            let c: u32 = rng.gen();
+
+            let a_bit = UInt32::alloc(cs.ns(|| "a_bit"), Some(a)).unwrap();
+            let b_bit = UInt32::constant(b);
+            let c_bit = UInt32::alloc(cs.ns(|| "c_bit"), Some(c)).unwrap();
+
+            let r = a_bit.and(cs.ns(|| "and"), &b_bit).unwrap();
+            let r = r.or(cs.ns(|| "or"), &c_bit.not()).unwrap();
+
+            assert!(cs.is_satisfied());
+
+            let expected = (a & b) | !c;
+            assert_eq!(r.get_value(), Some(expected));
+            assert_eq!(UInt32::from_bits_le(&r.to_bits_le()).get_value(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_uint32_addmany_with_carry() {
+        let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+        for _ in 0..100 {
+            let values = (0..5).map(|_| rng.gen()).collect::<Vec<u32>>();
+            let sum = values.iter().map(|v| u64::from(*v)).sum::<u64>();
+
+            for &constant in [false, true].iter() {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let operands = values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        if constant {
+                            UInt32::constant(*v)
+                        } else {
+                            UInt32::alloc(cs.ns(|| format!("operand {}", i)), Some(*v)).unwrap()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let (r, carry) = UInt32::addmany_with_carry(cs.ns(|| "addition"), &operands).unwrap();
+
+                assert!(cs.is_satisfied());
+                assert_eq!(r.get_value(), Some(sum as u32));
+                assert_eq!(carry.len(), 3);
+                let carry_value = carry
+                    .iter()
+                    .rev()
+                    .fold(0u64, |acc, b| (acc << 1) | u64::from(b.get_value().unwrap()));
+                assert_eq!(carry_value, sum >> 32);
+            }
+        }
+    }
 }
@@ -0,0 +1,425 @@
+//! A builder layering over `ConstraintSystem`, for writing circuits over a prime
+//! field as arithmetic expressions instead of sequences of gadget calls.
+//!
+//! Every value of the circuit is a `Wire`, combined with the usual arithmetic
+//! operators. Each operation and each assertion gets a namespace of its own,
+//! numbered in the order of creation, so that no annotation is to be provided by
+//! hand. Since operators cannot return errors, the first error raised by the
+//! underlying constraint system is kept by the builder, which skips all the
+//! subsequent operations, and is returned by `CircuitBuilder::finish`.
+//!
+//! ```ignore
+//! impl<F: PrimeField> ConstraintSynthesizer<F> for Cube<F> {
+//!     fn generate_constraints<CS: ConstraintSystem<F>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+//!         let b = CircuitBuilder::new(cs);
+//!         let x = b.witness(|| self.x.get());
+//!         let y = b.input(|| self.y.get());
+//!         b.assert_equal(&(&x * &x * &x + &x + F::from(5u64)), &y);
+//!         b.finish()
+//!     }
+//! }
+//! ```
+use algebra::PrimeField;
+use r1cs_core::{ConstraintSystem, Namespace, SynthesisError};
+
+use crate::{fields::fp::FpGadget, prelude::*};
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    marker::PhantomData,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+pub struct CircuitBuilder<F: PrimeField, CS: ConstraintSystem<F>> {
+    cs:          RefCell<CS>,
+    num_objects: Cell<usize>,
+    error:       RefCell<Option<SynthesisError>>,
+    _field:      PhantomData<F>,
+}
+
+impl<F: PrimeField, CS: ConstraintSystem<F>> CircuitBuilder<F, CS> {
+    pub fn new(cs: CS) -> Self {
+        Self {
+            cs:          RefCell::new(cs),
+            num_objects: Cell::new(0),
+            error:       RefCell::new(None),
+            _field:      PhantomData,
+        }
+    }
+
+    /// Returns the first error raised while building the circuit, if any.
+    pub fn finish(self) -> Result<(), SynthesisError> {
+        match self.error.into_inner() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `true` if an error has been raised while building the circuit.
+    pub fn has_failed(&self) -> bool {
+        self.error.borrow().is_some()
+    }
+
+    /// Returns the number of constraints enforced so far.
+    pub fn num_constraints(&self) -> usize {
+        self.cs.borrow().num_constraints()
+    }
+
+    fn next_name(&self, prefix: &str) -> String {
+        let i = self.num_objects.get();
+        self.num_objects.set(i + 1);
+        format!("{} {}", prefix, i)
+    }
+
+    fn record<T>(&self, result: Result<T, SynthesisError>) -> Option<T> {
+        match result {
+            Ok(t) => Some(t),
+            Err(e) => {
+                let mut error = self.error.borrow_mut();
+                if error.is_none() {
+                    *error = Some(e);
+                }
+                None
+            },
+        }
+    }
+
+    /// Calls `f` in a fresh namespace of the underlying constraint system, e.g.
+    /// to use gadgets for which the builder has no helper. Returns `None`, and
+    /// does not call `f`, if an error has been raised.
+    pub fn with_cs<R, FN>(&self, f: FN) -> Option<R>
+    where
+        FN: FnOnce(&mut Namespace<'_, F, CS::Root>) -> Result<R, SynthesisError>,
+    {
+        if self.has_failed() {
+            return None;
+        }
+        let name = self.next_name("gadget");
+        let result = f(&mut self.cs.borrow_mut().ns(|| name));
+        self.record(result)
+    }
+
+    /// Enters a namespace named `name` for all the operations run by `f`.
+    pub fn scope<R, FN: FnOnce() -> R>(&self, name: &str, f: FN) -> R {
+        let name = self.next_name(name);
+        self.cs.borrow_mut().get_root().push_namespace(|| name);
+        let result = f();
+        self.cs.borrow_mut().get_root().pop_namespace();
+        result
+    }
+
+    fn wire<FN>(&self, op: &str, f: FN) -> Wire<'_, F, CS>
+    where
+        FN: FnOnce(&mut Namespace<'_, F, CS::Root>) -> Result<FpGadget<F>, SynthesisError>,
+    {
+        if self.has_failed() {
+            return self.zero();
+        }
+        let name = self.next_name(op);
+        let result = f(&mut self.cs.borrow_mut().ns(|| name));
+        match self.record(result) {
+            Some(gadget) => Wire { builder: self, gadget },
+            None => self.zero(),
+        }
+    }
+
+    fn zero(&self) -> Wire<'_, F, CS> {
+        self.constant(F::zero())
+    }
+
+    /// Allocates a public input of the circuit.
+    pub fn input<FN>(&self, value_gen: FN) -> Wire<'_, F, CS>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+    {
+        self.wire("input", |cs| FpGadget::alloc_input(cs, value_gen))
+    }
+
+    /// Allocates a private witness of the circuit.
+    pub fn witness<FN>(&self, value_gen: FN) -> Wire<'_, F, CS>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+    {
+        self.wire("witness", |cs| FpGadget::alloc(cs, value_gen))
+    }
+
+    /// Returns the constant `value`, which costs no variable.
+    pub fn constant(&self, value: F) -> Wire<'_, F, CS> {
+        Wire { builder: self, gadget: FpGadget::from_value(&mut *self.cs.borrow_mut(), &value) }
+    }
+
+    /// Wraps a gadget allocated by other means.
+    pub fn from_gadget(&self, gadget: FpGadget<F>) -> Wire<'_, F, CS> {
+        Wire { builder: self, gadget }
+    }
+
+    fn assert<FN>(&self, op: &str, f: FN)
+    where
+        FN: FnOnce(&mut Namespace<'_, F, CS::Root>) -> Result<(), SynthesisError>,
+    {
+        if self.has_failed() {
+            return;
+        }
+        let name = self.next_name(op);
+        let result = f(&mut self.cs.borrow_mut().ns(|| name));
+        self.record(result);
+    }
+
+    /// Enforces `a == b`.
+    pub fn assert_equal(&self, a: &Wire<'_, F, CS>, b: &Wire<'_, F, CS>) {
+        self.assert("assert equal", |cs| a.gadget.enforce_equal(cs, &b.gadget))
+    }
+
+    /// Enforces `a != b`.
+    pub fn assert_not_equal(&self, a: &Wire<'_, F, CS>, b: &Wire<'_, F, CS>) {
+        self.assert("assert not equal", |cs| a.gadget.enforce_not_equal(cs, &b.gadget))
+    }
+
+    /// Enforces `a == 0`.
+    pub fn assert_zero(&self, a: &Wire<'_, F, CS>) {
+        self.assert_equal(a, &self.zero())
+    }
+
+    /// Enforces `a * b == c`, with a single constraint.
+    pub fn assert_mul(&self, a: &Wire<'_, F, CS>, b: &Wire<'_, F, CS>, c: &Wire<'_, F, CS>) {
+        self.assert("assert mul", |cs| a.gadget.mul_equals(cs, &b.gadget, &c.gadget))
+    }
+
+    /// Enforces `a` to be either zero or one, with a single constraint.
+    pub fn assert_boolean(&self, a: &Wire<'_, F, CS>) {
+        self.assert("assert boolean", |cs| {
+            let one_minus_a = a
+                .gadget
+                .negate(cs.ns(|| "negate"))?
+                .add_constant(cs.ns(|| "add one"), &F::one())?;
+            let zero = FpGadget::zero(cs.ns(|| "zero"))?;
+            a.gadget.mul_equals(cs.ns(|| "a * (1 - a) = 0"), &one_minus_a, &zero)
+        })
+    }
+}
+
+/// A value of a circuit under construction by a `CircuitBuilder`.
+pub struct Wire<'a, F: PrimeField, CS: ConstraintSystem<F>> {
+    builder: &'a CircuitBuilder<F, CS>,
+    gadget:  FpGadget<F>,
+}
+
+impl<'a, F: PrimeField, CS: ConstraintSystem<F>> Wire<'a, F, CS> {
+    /// Returns the value of the wire, if known.
+    pub fn value(&self) -> Option<F> {
+        self.gadget.get_value()
+    }
+
+    pub fn gadget(&self) -> &FpGadget<F> {
+        &self.gadget
+    }
+
+    pub fn into_gadget(self) -> FpGadget<F> {
+        self.gadget
+    }
+
+    pub fn square(&self) -> Self {
+        self.builder.wire("square", |cs| self.gadget.square(cs))
+    }
+
+    /// Returns the inverse of the wire, enforcing it to be non-zero.
+    pub fn inverse(&self) -> Self {
+        self.builder.wire("inverse", |cs| self.gadget.inverse(cs))
+    }
+
+    fn add_wire(&self, other: &Self) -> Self {
+        self.builder.wire("add", |cs| self.gadget.add(cs, &other.gadget))
+    }
+
+    fn sub_wire(&self, other: &Self) -> Self {
+        self.builder.wire("sub", |cs| self.gadget.sub(cs, &other.gadget))
+    }
+
+    fn mul_wire(&self, other: &Self) -> Self {
+        self.builder.wire("mul", |cs| self.gadget.mul(cs, &other.gadget))
+    }
+
+    fn add_constant(&self, other: &F) -> Self {
+        self.builder.wire("add constant", |cs| self.gadget.add_constant(cs, other))
+    }
+
+    fn sub_constant(&self, other: &F) -> Self {
+        self.builder.wire("sub constant", |cs| self.gadget.sub_constant(cs, other))
+    }
+
+    fn mul_constant(&self, other: &F) -> Self {
+        self.builder.wire("mul constant", |cs| self.gadget.mul_by_constant(cs, other))
+    }
+}
+
+impl<F: PrimeField, CS: ConstraintSystem<F>> Clone for Wire<'_, F, CS> {
+    fn clone(&self) -> Self {
+        Self { builder: self.builder, gadget: self.gadget.clone() }
+    }
+}
+
+impl<F: PrimeField, CS: ConstraintSystem<F>> fmt::Debug for Wire<'_, F, CS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Wire({:?})", self.gadget)
+    }
+}
+
+macro_rules! impl_wire_ops {
+    ($trait: ident, $fn: ident, $wire_fn: ident, $constant_fn: ident) => {
+        impl<'a, 'r, 's, F: PrimeField, CS: ConstraintSystem<F>> $trait<&'s Wire<'a, F, CS>>
+            for &'r Wire<'a, F, CS>
+        {
+            type Output = Wire<'a, F, CS>;
+
+            fn $fn(self, other: &'s Wire<'a, F, CS>) -> Wire<'a, F, CS> {
+                self.$wire_fn(other)
+            }
+        }
+
+        impl<'a, 's, F: PrimeField, CS: ConstraintSystem<F>> $trait<&'s Wire<'a, F, CS>>
+            for Wire<'a, F, CS>
+        {
+            type Output = Wire<'a, F, CS>;
+
+            fn $fn(self, other: &'s Wire<'a, F, CS>) -> Wire<'a, F, CS> {
+                self.$wire_fn(other)
+            }
+        }
+
+        impl<'a, 'r, F: PrimeField, CS: ConstraintSystem<F>> $trait<Wire<'a, F, CS>>
+            for &'r Wire<'a, F, CS>
+        {
+            type Output = Wire<'a, F, CS>;
+
+            fn $fn(self, other: Wire<'a, F, CS>) -> Wire<'a, F, CS> {
+                self.$wire_fn(&other)
+            }
+        }
+
+        impl<'a, F: PrimeField, CS: ConstraintSystem<F>> $trait<Wire<'a, F, CS>>
+            for Wire<'a, F, CS>
+        {
+            type Output = Wire<'a, F, CS>;
+
+            fn $fn(self, other: Wire<'a, F, CS>) -> Wire<'a, F, CS> {
+                self.$wire_fn(&other)
+            }
+        }
+
+        impl<'a, 'r, F: PrimeField, CS: ConstraintSystem<F>> $trait<F> for &'r Wire<'a, F, CS> {
+            type Output = Wire<'a, F, CS>;
+
+            fn $fn(self, other: F) -> Wire<'a, F, CS> {
+                self.$constant_fn(&other)
+            }
+        }
+
+        impl<'a, F: PrimeField, CS: ConstraintSystem<F>> $trait<F> for Wire<'a, F, CS> {
+            type Output = Wire<'a, F, CS>;
+
+            fn $fn(self, other: F) -> Wire<'a, F, CS> {
+                self.$constant_fn(&other)
+            }
+        }
+    };
+}
+
+impl_wire_ops!(Add, add, add_wire, add_constant);
+impl_wire_ops!(Sub, sub, sub_wire, sub_constant);
+impl_wire_ops!(Mul, mul, mul_wire, mul_constant);
+
+impl<'a, 'r, F: PrimeField, CS: ConstraintSystem<F>> Neg for &'r Wire<'a, F, CS> {
+    type Output = Wire<'a, F, CS>;
+
+    fn neg(self) -> Wire<'a, F, CS> {
+        self.builder.wire("negate", |cs| self.gadget.negate(cs))
+    }
+}
+
+impl<'a, F: PrimeField, CS: ConstraintSystem<F>> Neg for Wire<'a, F, CS> {
+    type Output = Wire<'a, F, CS>;
+
+    fn neg(self) -> Wire<'a, F, CS> {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{test_constraint_system::TestConstraintSystem, Assignment};
+    use algebra::{fields::bls12_381::Fr, Field};
+    use r1cs_core::ConstraintSynthesizer;
+
+    // Proves the knowledge of x such that x^3 + x + 5 = y, for public y.
+    struct Cube {
+        x: Option<Fr>,
+        y: Option<Fr>,
+    }
+
+    impl ConstraintSynthesizer<Fr> for Cube {
+        fn generate_constraints<CS: ConstraintSystem<Fr>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), SynthesisError> {
+            let b = CircuitBuilder::new(cs);
+            let x = b.witness(|| self.x.get());
+            let y = b.input(|| self.y.get());
+            let result = b.scope("cube", || {
+                let x_cube = &x.square() * &x;
+                b.assert_mul(&x, &x.square(), &x_cube);
+                x_cube + &x + Fr::from(5u64)
+            });
+            b.assert_equal(&result, &y);
+            b.assert_not_equal(&x, &b.constant(Fr::zero()));
+            b.finish()
+        }
+    }
+
+    #[test]
+    fn cube() {
+        let x = Fr::from(3u64);
+        let y = Fr::from(35u64);
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        Cube { x: Some(x), y: Some(y) }.generate_constraints(&mut cs).unwrap();
+        assert!(cs.is_satisfied());
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        Cube { x: Some(x), y: Some(y + &Fr::one()) }.generate_constraints(&mut cs).unwrap();
+        assert!(!cs.is_satisfied());
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        assert!(Cube { x: None, y: Some(y) }.generate_constraints(&mut cs).is_err());
+    }
+
+    #[test]
+    fn operators() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let b = CircuitBuilder::new(&mut cs);
+        let (u, v) = (Fr::from(7u64), Fr::from(11u64));
+        let a = b.witness(|| Ok(u));
+        let c = b.witness(|| Ok(v));
+
+        assert_eq!((&a + &c).value(), Some(u + &v));
+        assert_eq!((&a - &c).value(), Some(u - &v));
+        assert_eq!((&a * &c).value(), Some(u * &v));
+        assert_eq!((-&a).value(), Some(-u));
+        assert_eq!((&a * v).value(), Some(u * &v));
+        assert_eq!(a.inverse().value(), u.inverse());
+
+        let bit = b.witness(|| Ok(Fr::one()));
+        b.assert_boolean(&bit);
+        b.assert_zero(&(&a - &a));
+        b.finish().unwrap();
+        assert!(cs.is_satisfied());
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let b = CircuitBuilder::new(&mut cs);
+        let not_bit = b.witness(|| Ok(Fr::from(2u64)));
+        b.assert_boolean(&not_bit);
+        b.finish().unwrap();
+        assert!(!cs.is_satisfied());
+    }
+}
@@ -68,6 +68,29 @@ impl<F: PrimeField> FpGadget<F> {
         Ok(bits.into_iter().map(Boolean::from).collect())
     }
 
+    /// Returns whether `self < other`, for `self` and `other` known to be
+    /// integers of at most `num_bits` bits, e.g. packed from `num_bits` bits.
+    pub fn is_less_than<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        num_bits: usize,
+    ) -> Result<Boolean, SynthesisError> {
+        assert!(num_bits < F::Params::CAPACITY as usize);
+
+        // d = 2^num_bits + self - other is a positive integer of num_bits + 1
+        // bits, whose most significant bit is set iff self >= other
+        let two_to_num_bits = F::from(2u64).pow(&[num_bits as u64]);
+        let d = self
+            .sub(cs.ns(|| "self - other"), other)?
+            .add_constant(cs.ns(|| "add 2^num_bits"), &two_to_num_bits)?;
+        let d_bits = d.to_bits_with_length_restriction(
+            cs.ns(|| "d to bits"),
+            F::Params::MODULUS_BITS as usize - num_bits - 1,
+        )?;
+        Ok(d_bits[0].not())
+    }
+
     #[inline]
     pub fn to_bytes_with_length_restriction<CS: ConstraintSystem<F>>(
         &self,
@@ -26,6 +26,26 @@ impl<P: Fp2Parameters<Fp = ConstraintF>, ConstraintF: PrimeField + SquareRootFie
         }
     }
 
+    /// Returns `self` as a gadget of the base field, enforcing that its
+    /// higher coefficient is zero.
+    pub fn as_base_field<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<FpGadget<ConstraintF>, SynthesisError> {
+        let zero = FpGadget::zero(cs.ns(|| "zero"))?;
+        self.c1.enforce_equal(cs.ns(|| "c1 is zero"), &zero)?;
+        Ok(self.c0.clone())
+    }
+
+    /// Embeds a gadget of the base field into Fp2.
+    pub fn from_base_field<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        fe: &FpGadget<ConstraintF>,
+    ) -> Result<Self, SynthesisError> {
+        let zero = FpGadget::zero(cs.ns(|| "zero"))?;
+        Ok(Self::new(fe.clone(), zero))
+    }
+
     /// Multiply a FpGadget by quadratic nonresidue P::NONRESIDUE.
     #[inline]
     pub fn mul_fp_gadget_by_nonresidue<CS: ConstraintSystem<ConstraintF>>(
@@ -470,7 +490,7 @@ impl<P: Fp2Parameters<Fp = ConstraintF>, ConstraintF: PrimeField + SquareRootFie
         power: usize,
     ) -> Result<&mut Self, SynthesisError> {
         self.c1
-            .mul_by_constant_in_place(cs, &P::FROBENIUS_COEFF_FP2_C1[power % 2])?;
+            .mul_by_constant_in_place(cs, &P::frobenius_coeff_c1(power))?;
         Ok(self)
     }
 
@@ -32,6 +32,27 @@ impl<P: Fp3Parameters<Fp = ConstraintF>, ConstraintF: PrimeField + SquareRootFie
         }
     }
 
+    /// Returns `self` as a gadget of the base field, enforcing that its
+    /// higher coefficients are zero.
+    pub fn as_base_field<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<FpGadget<ConstraintF>, SynthesisError> {
+        let zero = FpGadget::zero(cs.ns(|| "zero"))?;
+        self.c1.enforce_equal(cs.ns(|| "c1 is zero"), &zero)?;
+        self.c2.enforce_equal(cs.ns(|| "c2 is zero"), &zero)?;
+        Ok(self.c0.clone())
+    }
+
+    /// Embeds a gadget of the base field into Fp3.
+    pub fn from_base_field<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        fe: &FpGadget<ConstraintF>,
+    ) -> Result<Self, SynthesisError> {
+        let zero = FpGadget::zero(cs.ns(|| "zero"))?;
+        Ok(Self::new(fe.clone(), zero.clone(), zero))
+    }
+
     /// Multiply a FpGadget by quadratic nonresidue P::NONRESIDUE.
     #[inline]
     pub fn mul_fp_gadget_by_nonresidue<CS: ConstraintSystem<ConstraintF>>(
@@ -643,11 +664,11 @@ impl<P: Fp3Parameters<Fp = ConstraintF>, ConstraintF: PrimeField + SquareRootFie
 
         self.c1.mul_by_constant_in_place(
             cs.ns(|| "c1_power"),
-            &P::FROBENIUS_COEFF_FP3_C1[power % 3],
+            &P::frobenius_coeff_c1(power),
         )?;
         self.c2.mul_by_constant_in_place(
             cs.ns(|| "c2_power"),
-            &P::FROBENIUS_COEFF_FP3_C2[power % 3],
+            &P::frobenius_coeff_c2(power),
         )?;
 
         Ok(self)
@@ -5,7 +5,7 @@ use algebra::{fields::{
 use r1cs_core::{ConstraintSystem, ConstraintVar, SynthesisError};
 use std::{borrow::Borrow, marker::PhantomData};
 
-use crate::{prelude::*, Assignment};
+use crate::{fields::fp::FpGadget, prelude::*, Assignment};
 
 type Fp2Gadget<P, ConstraintF> = super::fp2::Fp2Gadget<<P as Fp4Parameters>::Fp2Params, ConstraintF>;
 
@@ -37,6 +37,27 @@ impl<P, ConstraintF: PrimeField + SquareRootField> Fp4Gadget<P, ConstraintF>
         }
     }
 
+    /// Returns `self` as a gadget of the base prime field, enforcing that all
+    /// of its coefficients but the constant one are zero.
+    pub fn as_base_field<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<FpGadget<ConstraintF>, SynthesisError> {
+        let zero = Fp2Gadget::<P, ConstraintF>::zero(cs.ns(|| "zero"))?;
+        self.c1.enforce_equal(cs.ns(|| "c1 is zero"), &zero)?;
+        self.c0.as_base_field(cs.ns(|| "c0 as base field"))
+    }
+
+    /// Embeds a gadget of the base prime field into Fp4.
+    pub fn from_base_field<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        fe: &FpGadget<ConstraintF>,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = Fp2Gadget::<P, ConstraintF>::from_base_field(cs.ns(|| "c0"), fe)?;
+        let c1 = Fp2Gadget::<P, ConstraintF>::zero(cs.ns(|| "c1"))?;
+        Ok(Self::new(c0, c1))
+    }
+
     /// Multiply a Fp2Gadget by quadratic nonresidue P::NONRESIDUE.
     #[inline]
     pub fn mul_fp2_gadget_by_nonresidue<CS: ConstraintSystem<ConstraintF>>(
@@ -490,11 +511,11 @@ impl<P, ConstraintF: PrimeField + SquareRootField> FieldGadget<Fp4<P>, Constrain
 
         self.c1.c0.mul_by_constant_in_place(
             cs.ns(|| "c1_c0_power"),
-            &P::FROBENIUS_COEFF_FP4_C1[power % 4],
+            &P::frobenius_coeff_c1(power),
         )?;
         self.c1.c1.mul_by_constant_in_place(
             cs.ns(|| "c1_c1_power"),
-            &P::FROBENIUS_COEFF_FP4_C1[power % 4],
+            &P::frobenius_coeff_c1(power),
         )?;
 
         Ok(self)
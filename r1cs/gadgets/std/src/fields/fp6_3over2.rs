@@ -8,7 +8,7 @@ use algebra::{
 use r1cs_core::{ConstraintSystem, ConstraintVar, SynthesisError};
 use std::{borrow::Borrow, marker::PhantomData};
 
-use crate::{prelude::*, Assignment};
+use crate::{fields::fp::FpGadget, prelude::*, Assignment};
 
 type Fp2Gadget<P, ConstraintF> =
     super::fp2::Fp2Gadget<<P as Fp6Parameters>::Fp2Params, ConstraintF>;
@@ -46,6 +46,28 @@ where
             _params: PhantomData,
         }
     }
+
+    /// Returns `self` as a gadget of the base prime field, enforcing that all
+    /// of its coefficients but the constant one are zero.
+    pub fn as_base_field<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<FpGadget<ConstraintF>, SynthesisError> {
+        let zero = Fp2Gadget::<P, ConstraintF>::zero(cs.ns(|| "zero"))?;
+        self.c1.enforce_equal(cs.ns(|| "c1 is zero"), &zero)?;
+        self.c2.enforce_equal(cs.ns(|| "c2 is zero"), &zero)?;
+        self.c0.as_base_field(cs.ns(|| "c0 as base field"))
+    }
+
+    /// Embeds a gadget of the base prime field into Fp6.
+    pub fn from_base_field<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        fe: &FpGadget<ConstraintF>,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = Fp2Gadget::<P, ConstraintF>::from_base_field(cs.ns(|| "c0"), fe)?;
+        let zero = Fp2Gadget::<P, ConstraintF>::zero(cs.ns(|| "zero"))?;
+        Ok(Self::new(c0, zero.clone(), zero))
+    }
     /// Multiply a Fp2Gadget by cubic nonresidue P::NONRESIDUE.
     #[inline]
     pub fn mul_fp2_gadget_by_nonresidue<CS: ConstraintSystem<ConstraintF>>(
@@ -749,11 +771,11 @@ where
 
         self.c1.mul_by_constant_in_place(
             cs.ns(|| "c1_power"),
-            &P::FROBENIUS_COEFF_FP6_C1[power % 6],
+            &P::frobenius_coeff_c1(power),
         )?;
         self.c2.mul_by_constant_in_place(
             cs.ns(|| "c2_power"),
-            &P::FROBENIUS_COEFF_FP6_C2[power % 6],
+            &P::frobenius_coeff_c2(power),
         )?;
 
         Ok(self)
@@ -12,6 +12,7 @@ pub mod fp3;
 pub mod fp4;
 pub mod fp6_3over2;
 pub mod fp6_2over3;
+pub mod nonnative;
 
 pub mod bls12_377;
 pub mod edwards_bls12;
@@ -0,0 +1,900 @@
+//! Emulation of the arithmetic of a prime field `SimulationF` in a constraint
+//! system over a different prime field `ConstraintF`.
+//!
+//! An element is represented by an integer of at most `n` bits, `n` being the
+//! bit length of the modulus `p` of `SimulationF`, given by its limbs in base
+//! `2^bits_per_limb`. The limbs are small enough that the products of two of
+//! them, summed up over all limbs, do not wrap around the modulus of
+//! `ConstraintF`. The result `r` of an operation is witnessed together with
+//! the quotient `q` of the reduction modulo `p`, and the integer identity
+//! `x + 2p - q * p - r = 0` is checked limb by limb, carrying the excess of
+//! each limb over to the next one, where `x` is the unreduced result of the
+//! operation computed on the limbs.
+use algebra::{FpParameters, PrimeField, ToBytes};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, Zero};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use std::{borrow::Borrow, marker::PhantomData};
+
+use crate::{fields::fp::FpGadget, prelude::*, Assignment};
+
+#[derive(Derivative)]
+#[derivative(
+    Debug(bound = "SimulationF: PrimeField, ConstraintF: PrimeField"),
+    Clone(bound = "SimulationF: PrimeField, ConstraintF: PrimeField")
+)]
+#[must_use]
+pub struct NonNativeFieldGadget<SimulationF: PrimeField, ConstraintF: PrimeField> {
+    /// The limbs of the representing integer, least significant first.
+    pub limbs: Vec<FpGadget<ConstraintF>>,
+    #[derivative(Debug = "ignore")]
+    _simulation: PhantomData<SimulationF>,
+}
+
+fn field_to_biguint<F: PrimeField>(fe: &F) -> BigUint {
+    BigUint::from_bytes_le(&to_bytes!(fe.into_repr()).unwrap())
+}
+
+fn biguint_to_field<F: PrimeField>(n: &BigUint) -> F {
+    let base = F::from(256u64);
+    n.to_bytes_be()
+        .iter()
+        .fold(F::zero(), |acc, byte| acc * &base + &F::from(*byte))
+}
+
+fn bigint_to_field<F: PrimeField>(n: &BigInt) -> F {
+    let (sign, bytes) = n.to_bytes_be();
+    let magnitude = biguint_to_field::<F>(&BigUint::from_bytes_be(&bytes));
+    if sign == Sign::Minus {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+impl<SimulationF: PrimeField, ConstraintF: PrimeField>
+    NonNativeFieldGadget<SimulationF, ConstraintF>
+{
+    /// The bit length of the limbs. Leaves a headroom of 10 bits for the sums
+    /// of the products of two limbs and for the carries, which suffices for up
+    /// to 62 limbs.
+    pub fn bits_per_limb() -> usize {
+        (ConstraintF::size_in_bits() - 10) / 2
+    }
+
+    /// The number of limbs of an element.
+    pub fn num_limbs() -> usize {
+        Self::num_limbs_for(SimulationF::size_in_bits())
+    }
+
+    fn num_limbs_for(num_bits: usize) -> usize {
+        let bits_per_limb = Self::bits_per_limb();
+        (num_bits + bits_per_limb - 1) / bits_per_limb
+    }
+
+    fn modulus() -> BigUint {
+        BigUint::from_bytes_le(
+            &to_bytes!(<SimulationF as PrimeField>::Params::MODULUS).unwrap(),
+        )
+    }
+
+    /// Splits `value` into `num_limbs` limbs.
+    fn limb_values(value: &BigUint, num_limbs: usize) -> Vec<BigUint> {
+        let bits_per_limb = Self::bits_per_limb();
+        let mask = (BigUint::one() << bits_per_limb) - BigUint::one();
+        (0..num_limbs)
+            .map(|i| (value >> (i * bits_per_limb)) & &mask)
+            .collect()
+    }
+
+    fn limb_integer_values(limbs: &[FpGadget<ConstraintF>]) -> Option<Vec<BigInt>> {
+        limbs
+            .iter()
+            .map(|limb| limb.get_value().map(|v| BigInt::from(field_to_biguint(&v))))
+            .collect()
+    }
+
+    /// Returns the integer represented by the limbs.
+    fn integer_value(&self) -> Option<BigUint> {
+        let bits_per_limb = Self::bits_per_limb();
+        let mut value = BigUint::zero();
+        for limb in self.limbs.iter().rev() {
+            value = (value << bits_per_limb) + field_to_biguint(&limb.get_value()?);
+        }
+        Some(value)
+    }
+
+    pub fn get_value(&self) -> Option<SimulationF> {
+        self.integer_value().map(|v| biguint_to_field(&v))
+    }
+
+    /// Enforces that `fe` is an integer of at most `num_bits` bits.
+    fn enforce_bit_length<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        fe: &FpGadget<ConstraintF>,
+        num_bits: usize,
+    ) -> Result<(), SynthesisError> {
+        fe.to_bits_with_length_restriction(cs, ConstraintF::size_in_bits() - num_bits)?;
+        Ok(())
+    }
+
+    /// Allocates the limbs of an integer of at most `num_bits` bits, checking
+    /// their lengths.
+    fn alloc_limbs<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        value: Option<&BigUint>,
+        num_bits: usize,
+        as_input: bool,
+    ) -> Result<Vec<FpGadget<ConstraintF>>, SynthesisError> {
+        let bits_per_limb = Self::bits_per_limb();
+        let num_limbs = Self::num_limbs_for(num_bits);
+        let limb_values = value.map(|v| Self::limb_values(v, num_limbs));
+
+        let mut limbs = Vec::with_capacity(num_limbs);
+        for i in 0..num_limbs {
+            let limb_value = || {
+                limb_values
+                    .as_ref()
+                    .map(|values| biguint_to_field::<ConstraintF>(&values[i]))
+                    .get()
+            };
+            let limb = if as_input {
+                FpGadget::alloc_input(cs.ns(|| format!("alloc limb {}", i)), limb_value)?
+            } else {
+                FpGadget::alloc(cs.ns(|| format!("alloc limb {}", i)), limb_value)?
+            };
+            Self::enforce_bit_length(
+                cs.ns(|| format!("check length of limb {}", i)),
+                &limb,
+                std::cmp::min(bits_per_limb, num_bits - i * bits_per_limb),
+            )?;
+            limbs.push(limb);
+        }
+        Ok(limbs)
+    }
+
+    /// Enforces that the polynomial with coefficients `coeffs` vanishes at
+    /// `2^bits_per_limb`, i.e. that the integer it represents is zero, where
+    /// `values` are the integer values of the coefficients. The check is sound
+    /// as long as the absolute values of the coefficients are smaller than
+    /// `2^(2 * bits_per_limb + 7)`.
+    fn enforce_zero_integer<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        coeffs: &[FpGadget<ConstraintF>],
+        values: Option<Vec<BigInt>>,
+    ) -> Result<(), SynthesisError> {
+        let bits_per_limb = Self::bits_per_limb();
+        let base = biguint_to_field::<ConstraintF>(&(BigUint::one() << bits_per_limb));
+        // The carries are shifted by an offset to make them non-negative, so
+        // that their lengths can be checked.
+        let carry_offset = BigInt::one() << (bits_per_limb + 8);
+        let carry_offset_fe = bigint_to_field::<ConstraintF>(&carry_offset);
+        let zero = FpGadget::<ConstraintF>::zero(cs.ns(|| "zero"))?;
+
+        let mut carry = zero.clone();
+        let mut carry_value = Some(BigInt::zero());
+        for (i, coeff) in coeffs.iter().enumerate() {
+            let sum = coeff.add(cs.ns(|| format!("add carry to coeff {}", i)), &carry)?;
+            if i == coeffs.len() - 1 {
+                sum.enforce_equal(cs.ns(|| "check last coeff"), &zero)?;
+                break;
+            }
+
+            // sum = 2^bits_per_limb * next_carry
+            let next_carry_value = match (&values, &carry_value) {
+                (Some(values), Some(carry_value)) => Some((&values[i] + carry_value) >> bits_per_limb),
+                _ => None,
+            };
+            let shifted_carry = FpGadget::alloc(cs.ns(|| format!("alloc carry {}", i)), || {
+                next_carry_value
+                    .as_ref()
+                    .map(|c| bigint_to_field::<ConstraintF>(&(c + &carry_offset)))
+                    .get()
+            })?;
+            Self::enforce_bit_length(
+                cs.ns(|| format!("check length of carry {}", i)),
+                &shifted_carry,
+                bits_per_limb + 9,
+            )?;
+            let next_carry =
+                shifted_carry.sub_constant(cs.ns(|| format!("unshift carry {}", i)), &carry_offset_fe)?;
+            let carried = next_carry.mul_by_constant(cs.ns(|| format!("shift carry {}", i)), &base)?;
+            sum.enforce_equal(cs.ns(|| format!("check coeff {}", i)), &carried)?;
+
+            carry = next_carry;
+            carry_value = next_carry_value;
+        }
+        Ok(())
+    }
+
+    /// Enforces that `r` is congruent modulo `p` to the non-negative integer `x`
+    /// of at most `num_bits` bits, given by the polynomial with coefficients
+    /// `coeffs` evaluated at `2^bits_per_limb`. The absolute values of the
+    /// coefficients must be smaller than `2^(2 * bits_per_limb + 6)`.
+    fn enforce_reduction<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        coeffs: Vec<FpGadget<ConstraintF>>,
+        values: Option<Vec<BigInt>>,
+        num_bits: usize,
+        r: &Self,
+    ) -> Result<(), SynthesisError> {
+        let bits_per_limb = Self::bits_per_limb();
+        let n = SimulationF::size_in_bits();
+        let p = Self::modulus();
+        let p_limbs = Self::limb_values(&p, Self::num_limbs());
+        let two_p_limbs = Self::limb_values(&(&p << 1), Self::num_limbs_for(n + 1));
+
+        // As r < 2^n < 2p, the quotient of x + 2p - r is non-negative and of
+        // at most num_bits - n + 3 bits.
+        let q_bits = num_bits + 3 - std::cmp::min(num_bits, n);
+        let x_value = values.as_ref().map(|values| {
+            values
+                .iter()
+                .enumerate()
+                .fold(BigInt::zero(), |acc, (i, v)| acc + (v.clone() << (i * bits_per_limb)))
+        });
+        let q_value = match (x_value, r.integer_value()) {
+            (Some(x), Some(r)) => ((x + BigInt::from(&p << 1) - BigInt::from(r)) / BigInt::from(p.clone()))
+                .to_biguint(),
+            _ => None,
+        };
+        let q = Self::alloc_limbs(cs.ns(|| "alloc quotient"), q_value.as_ref(), q_bits, false)?;
+
+        // d = x + 2p - q * p - r
+        let len = [
+            coeffs.len(),
+            q.len() + p_limbs.len() - 1,
+            r.limbs.len(),
+            two_p_limbs.len(),
+        ]
+        .iter()
+        .cloned()
+        .max()
+        .unwrap();
+        let zero = FpGadget::<ConstraintF>::zero(cs.ns(|| "zero"))?;
+        let mut d = coeffs;
+        d.resize(len, zero);
+        let mut d_values = values.map(|mut values| {
+            values.resize(len, BigInt::zero());
+            values
+        });
+        let q_values = Self::limb_integer_values(&q);
+        let r_values = Self::limb_integer_values(&r.limbs);
+
+        for (i, two_p_i) in two_p_limbs.iter().enumerate() {
+            d[i] = d[i].add_constant(
+                cs.ns(|| format!("add 2p limb {}", i)),
+                &biguint_to_field(two_p_i),
+            )?;
+            if let Some(values) = d_values.as_mut() {
+                values[i] += BigInt::from(two_p_i.clone());
+            }
+        }
+        for (i, q_i) in q.iter().enumerate() {
+            for (j, p_j) in p_limbs.iter().enumerate() {
+                let q_i_p_j =
+                    q_i.mul_by_constant(cs.ns(|| format!("q_{} * p_{}", i, j)), &biguint_to_field(p_j))?;
+                d[i + j] = d[i + j].sub(cs.ns(|| format!("sub q_{} * p_{}", i, j)), &q_i_p_j)?;
+            }
+        }
+        for (i, r_i) in r.limbs.iter().enumerate() {
+            d[i] = d[i].sub(cs.ns(|| format!("sub r_{}", i)), r_i)?;
+        }
+        let d_values = match (d_values, q_values, r_values) {
+            (Some(mut d_values), Some(q_values), Some(r_values)) => {
+                for (i, q_i) in q_values.iter().enumerate() {
+                    for (j, p_j) in p_limbs.iter().enumerate() {
+                        d_values[i + j] -= q_i * BigInt::from(p_j.clone());
+                    }
+                }
+                for (i, r_i) in r_values.iter().enumerate() {
+                    d_values[i] -= r_i;
+                }
+                Some(d_values)
+            }
+            _ => None,
+        };
+
+        Self::enforce_zero_integer(cs.ns(|| "check x + 2p - q * p - r = 0"), &d, d_values)
+    }
+
+    /// Returns the coefficients of the product of the polynomials with
+    /// coefficients `a` and `b`, together with their integer values.
+    fn poly_mul<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        a: &[FpGadget<ConstraintF>],
+        b: &[FpGadget<ConstraintF>],
+    ) -> Result<(Vec<FpGadget<ConstraintF>>, Option<Vec<BigInt>>), SynthesisError> {
+        let len = a.len() + b.len() - 1;
+        let zero = FpGadget::<ConstraintF>::zero(cs.ns(|| "zero"))?;
+        let mut coeffs = vec![zero; len];
+        for (i, a_i) in a.iter().enumerate() {
+            for (j, b_j) in b.iter().enumerate() {
+                let a_i_b_j = a_i.mul(cs.ns(|| format!("a_{} * b_{}", i, j)), b_j)?;
+                coeffs[i + j] = coeffs[i + j].add(cs.ns(|| format!("add a_{} * b_{}", i, j)), &a_i_b_j)?;
+            }
+        }
+
+        let values = match (Self::limb_integer_values(a), Self::limb_integer_values(b)) {
+            (Some(a_values), Some(b_values)) => {
+                let mut values = vec![BigInt::zero(); len];
+                for (i, a_i) in a_values.iter().enumerate() {
+                    for (j, b_j) in b_values.iter().enumerate() {
+                        values[i + j] += a_i * b_j;
+                    }
+                }
+                Some(values)
+            }
+            _ => None,
+        };
+        Ok((coeffs, values))
+    }
+
+    fn alloc_reduced<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        value: Option<SimulationF>,
+    ) -> Result<Self, SynthesisError> {
+        Self::alloc(cs, || value.get())
+    }
+
+    pub fn add<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let mut coeffs = Vec::with_capacity(self.limbs.len());
+        for (i, (a_i, b_i)) in self.limbs.iter().zip(other.limbs.iter()).enumerate() {
+            coeffs.push(a_i.add(cs.ns(|| format!("a_{} + b_{}", i, i)), b_i)?);
+        }
+        // The limbs of the sum do not wrap around, so their values are integers.
+        let values = Self::limb_integer_values(&coeffs);
+
+        let result = Self::alloc_reduced(
+            cs.ns(|| "alloc sum"),
+            self.get_value().and_then(|a| other.get_value().map(|b| a + &b)),
+        )?;
+        Self::enforce_reduction(
+            cs.ns(|| "reduce sum"),
+            coeffs,
+            values,
+            SimulationF::size_in_bits() + 1,
+            &result,
+        )?;
+        Ok(result)
+    }
+
+    pub fn sub<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let (coeffs, values) = self.sub_limbs(cs.ns(|| "a - b"), other)?;
+        let result = Self::alloc_reduced(
+            cs.ns(|| "alloc difference"),
+            self.get_value().and_then(|a| other.get_value().map(|b| a - &b)),
+        )?;
+        Self::enforce_reduction(
+            cs.ns(|| "reduce difference"),
+            coeffs,
+            values,
+            SimulationF::size_in_bits() + 2,
+            &result,
+        )?;
+        Ok(result)
+    }
+
+    /// Returns the limbs of `self - other + 2p`, a non-negative integer of at
+    /// most `n + 2` bits, together with their integer values.
+    fn sub_limbs<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(Vec<FpGadget<ConstraintF>>, Option<Vec<BigInt>>), SynthesisError> {
+        let n = SimulationF::size_in_bits();
+        let two_p_limbs = Self::limb_values(&(Self::modulus() << 1), Self::num_limbs_for(n + 1));
+        let zero = FpGadget::<ConstraintF>::zero(cs.ns(|| "zero"))?;
+
+        let mut coeffs = Vec::with_capacity(two_p_limbs.len());
+        for (i, two_p_i) in two_p_limbs.iter().enumerate() {
+            let a_i = self.limbs.get(i).unwrap_or(&zero);
+            let b_i = other.limbs.get(i).unwrap_or(&zero);
+            let coeff = a_i
+                .sub(cs.ns(|| format!("a_{} - b_{}", i, i)), b_i)?
+                .add_constant(cs.ns(|| format!("add 2p limb {}", i)), &biguint_to_field(two_p_i))?;
+            coeffs.push(coeff);
+        }
+
+        let values = match (Self::limb_integer_values(&self.limbs), Self::limb_integer_values(&other.limbs)) {
+            (Some(a), Some(b)) => Some(
+                two_p_limbs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, two_p_i)| {
+                        let a_i = a.get(i).cloned().unwrap_or_else(BigInt::zero);
+                        let b_i = b.get(i).cloned().unwrap_or_else(BigInt::zero);
+                        a_i - b_i + BigInt::from(two_p_i.clone())
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        };
+        Ok((coeffs, values))
+    }
+
+    pub fn negate<CS: ConstraintSystem<ConstraintF>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        let zero = Self::zero(cs.ns(|| "zero"))?;
+        zero.sub(cs.ns(|| "0 - a"), self)
+    }
+
+    pub fn mul<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let (coeffs, values) = Self::poly_mul(cs.ns(|| "a * b"), &self.limbs, &other.limbs)?;
+        let result = Self::alloc_reduced(
+            cs.ns(|| "alloc product"),
+            self.get_value().and_then(|a| other.get_value().map(|b| a * &b)),
+        )?;
+        Self::enforce_reduction(
+            cs.ns(|| "reduce product"),
+            coeffs,
+            values,
+            2 * SimulationF::size_in_bits(),
+            &result,
+        )?;
+        Ok(result)
+    }
+
+    pub fn mul_by_constant<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        fe: &SimulationF,
+    ) -> Result<Self, SynthesisError> {
+        let constant = Self::from_value(cs.ns(|| "constant"), fe);
+        self.mul(cs.ns(|| "a * constant"), &constant)
+    }
+
+    /// Returns the unreduced product of `self` and `other`, to be summed up with
+    /// other products before a single reduction.
+    pub fn mul_without_reduce<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<NonNativeFieldMulResultGadget<SimulationF, ConstraintF>, SynthesisError> {
+        let (limbs, _) = Self::poly_mul(cs.ns(|| "a * b"), &self.limbs, &other.limbs)?;
+        Ok(NonNativeFieldMulResultGadget {
+            limbs,
+            surfeit: 1,
+            _simulation: PhantomData,
+        })
+    }
+
+    /// Returns the sum of the products `a[i] * b[i]`, reduced lazily.
+    pub fn sum_of_products<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        a: &[Self],
+        b: &[Self],
+    ) -> Result<Self, SynthesisError> {
+        assert_eq!(a.len(), b.len());
+        let zero = Self::zero(cs.ns(|| "zero"))?;
+        let mut sum = NonNativeFieldMulResultGadget::from_reduced(&zero);
+        for (i, (a_i, b_i)) in a.iter().zip(b.iter()).enumerate() {
+            let product = a_i.mul_without_reduce(cs.ns(|| format!("a_{} * b_{}", i, i)), b_i)?;
+            sum = sum.add(cs.ns(|| format!("add a_{} * b_{}", i, i)), &product)?;
+        }
+        sum.reduce(cs.ns(|| "reduce sum"))
+    }
+
+    /// Enforces that `self * other = result`.
+    pub fn mul_equals<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        result: &Self,
+    ) -> Result<(), SynthesisError> {
+        let (coeffs, values) = Self::poly_mul(cs.ns(|| "a * b"), &self.limbs, &other.limbs)?;
+        Self::enforce_reduction(
+            cs.ns(|| "reduce product"),
+            coeffs,
+            values,
+            2 * SimulationF::size_in_bits(),
+            result,
+        )
+    }
+
+    /// Returns the inverse of `self`, enforcing that it exists.
+    pub fn inverse<CS: ConstraintSystem<ConstraintF>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        let inverse = Self::alloc(cs.ns(|| "alloc inverse"), || {
+            self.get_value()
+                .get()?
+                .inverse()
+                .ok_or(SynthesisError::DivisionByZero)
+        })?;
+        let one = Self::one(cs.ns(|| "one"))?;
+        self.mul_equals(cs.ns(|| "check inverse"), &inverse, &one)?;
+        Ok(inverse)
+    }
+
+    /// Enforces that `self` and `other` represent the same field element.
+    pub fn enforce_equal<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        let (coeffs, values) = self.sub_limbs(cs.ns(|| "a - b"), other)?;
+        let zero = Self::zero(cs.ns(|| "zero"))?;
+        Self::enforce_reduction(
+            cs.ns(|| "check a - b = 0"),
+            coeffs,
+            values,
+            SimulationF::size_in_bits() + 2,
+            &zero,
+        )
+    }
+
+    pub fn zero<CS: ConstraintSystem<ConstraintF>>(cs: CS) -> Result<Self, SynthesisError> {
+        Ok(Self::from_value(cs, &SimulationF::zero()))
+    }
+
+    pub fn one<CS: ConstraintSystem<ConstraintF>>(cs: CS) -> Result<Self, SynthesisError> {
+        Ok(Self::from_value(cs, &SimulationF::one()))
+    }
+
+    /// Packs the big-endian `bits`, of at most the bit length of the modulus,
+    /// into an element. Costs no constraints.
+    pub fn from_bits<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        bits: &[Boolean],
+    ) -> Result<Self, SynthesisError> {
+        assert!(bits.len() <= SimulationF::size_in_bits());
+        let bits_per_limb = Self::bits_per_limb();
+
+        let mut limbs = Vec::with_capacity(Self::num_limbs());
+        for i in 0..Self::num_limbs() {
+            let mut limb = FpGadget::<ConstraintF>::zero(cs.ns(|| format!("zero limb {}", i)))?;
+            let mut coeff = ConstraintF::one();
+            for (j, bit) in bits.iter().rev().skip(i * bits_per_limb).take(bits_per_limb).enumerate() {
+                limb = limb.conditionally_add_constant(
+                    cs.ns(|| format!("add bit {} to limb {}", j, i)),
+                    bit,
+                    coeff,
+                )?;
+                coeff.double_in_place();
+            }
+            limbs.push(limb);
+        }
+        Ok(Self {
+            limbs,
+            _simulation: PhantomData,
+        })
+    }
+
+    /// Returns the big-endian bits of the canonical representative of `self`,
+    /// of the bit length of the modulus.
+    pub fn to_bits_strict<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        let bit_values = self.get_value().map(|v| v.write_bits());
+        let mut bits = Vec::with_capacity(SimulationF::size_in_bits());
+        for i in 0..SimulationF::size_in_bits() {
+            bits.push(Boolean::alloc(cs.ns(|| format!("alloc bit {}", i)), || {
+                bit_values.as_ref().map(|values| values[i]).get()
+            })?);
+        }
+        Boolean::enforce_in_field::<_, _, SimulationF>(cs.ns(|| "check bits in field"), &bits)?;
+
+        let packed = Self::from_bits(cs.ns(|| "pack bits"), &bits)?;
+        self.enforce_equal(cs.ns(|| "check bits"), &packed)?;
+        Ok(bits)
+    }
+}
+
+/// An unreduced sum of products of emulated elements: the coefficients of the
+/// product polynomials in `2^bits_per_limb`, added up without reduction modulo
+/// `p`, so that a single reduction is paid for many products. The surfeit, i.e.
+/// the number of products summed up, bounds the size of the coefficients.
+#[derive(Derivative)]
+#[derivative(
+    Debug(bound = "SimulationF: PrimeField, ConstraintF: PrimeField"),
+    Clone(bound = "SimulationF: PrimeField, ConstraintF: PrimeField")
+)]
+#[must_use]
+pub struct NonNativeFieldMulResultGadget<SimulationF: PrimeField, ConstraintF: PrimeField> {
+    /// The coefficients, least significant first.
+    pub limbs:   Vec<FpGadget<ConstraintF>>,
+    /// The number of products summed up.
+    pub surfeit: usize,
+    #[derivative(Debug = "ignore")]
+    _simulation: PhantomData<SimulationF>,
+}
+
+impl<SimulationF: PrimeField, ConstraintF: PrimeField>
+    NonNativeFieldMulResultGadget<SimulationF, ConstraintF>
+{
+    /// The largest surfeit supported by the reduction. A coefficient of a
+    /// product is the sum of at most `num_limbs` products of two limbs, and the
+    /// coefficients must stay below `2^(2 * bits_per_limb + 6)`.
+    pub fn max_surfeit() -> usize {
+        63 / NonNativeFieldGadget::<SimulationF, ConstraintF>::num_limbs()
+    }
+
+    /// Returns `elem` as an unreduced sum, of surfeit one.
+    pub fn from_reduced(elem: &NonNativeFieldGadget<SimulationF, ConstraintF>) -> Self {
+        Self {
+            limbs:       elem.limbs.clone(),
+            surfeit:     1,
+            _simulation: PhantomData,
+        }
+    }
+
+    /// Returns `self + other`. The operands are reduced first if the surfeit of
+    /// the sum would exceed `max_surfeit()`.
+    pub fn add<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let max_surfeit = Self::max_surfeit();
+        assert!(max_surfeit >= 2);
+
+        let mut a = self.clone();
+        let mut b = other.clone();
+        if a.surfeit + b.surfeit > max_surfeit {
+            a = Self::from_reduced(&a.reduce(cs.ns(|| "reduce a"))?);
+        }
+        if a.surfeit + b.surfeit > max_surfeit {
+            b = Self::from_reduced(&b.reduce(cs.ns(|| "reduce b"))?);
+        }
+
+        let len = std::cmp::max(a.limbs.len(), b.limbs.len());
+        let mut limbs = Vec::with_capacity(len);
+        for i in 0..len {
+            let limb = match (a.limbs.get(i), b.limbs.get(i)) {
+                (Some(a_i), Some(b_i)) => a_i.add(cs.ns(|| format!("a_{} + b_{}", i, i)), b_i)?,
+                (Some(limb), None) | (None, Some(limb)) => limb.clone(),
+                (None, None) => unreachable!(),
+            };
+            limbs.push(limb);
+        }
+        Ok(Self {
+            limbs,
+            surfeit: a.surfeit + b.surfeit,
+            _simulation: PhantomData,
+        })
+    }
+
+    /// Reduces the sum modulo `p`.
+    pub fn reduce<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<NonNativeFieldGadget<SimulationF, ConstraintF>, SynthesisError> {
+        let bits_per_limb = NonNativeFieldGadget::<SimulationF, ConstraintF>::bits_per_limb();
+        // The coefficients do not wrap around, so their values are integers.
+        let values = NonNativeFieldGadget::<SimulationF, ConstraintF>::limb_integer_values(&self.limbs);
+        let value = values.as_ref().and_then(|values| {
+            values
+                .iter()
+                .enumerate()
+                .fold(BigInt::zero(), |acc, (i, v)| acc + (v.clone() << (i * bits_per_limb)))
+                .to_biguint()
+                .map(|x| biguint_to_field::<SimulationF>(&x))
+        });
+
+        // The sum is smaller than surfeit * 2^(2n)
+        let surfeit_bits = (0usize.leading_zeros() - (self.surfeit - 1).leading_zeros()) as usize;
+        let result = NonNativeFieldGadget::alloc_reduced(cs.ns(|| "alloc reduced"), value)?;
+        NonNativeFieldGadget::enforce_reduction(
+            cs.ns(|| "reduce"),
+            self.limbs.clone(),
+            values,
+            2 * SimulationF::size_in_bits() + surfeit_bits,
+            &result,
+        )?;
+        Ok(result)
+    }
+}
+
+impl<SimulationF: PrimeField, ConstraintF: PrimeField> AllocGadget<SimulationF, ConstraintF>
+    for NonNativeFieldGadget<SimulationF, ConstraintF>
+{
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(cs: CS, value_gen: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SimulationF>,
+    {
+        let value = value_gen().ok().map(|v| field_to_biguint(v.borrow()));
+        let limbs = Self::alloc_limbs(cs, value.as_ref(), SimulationF::size_in_bits(), false)?;
+        Ok(Self {
+            limbs,
+            _simulation: PhantomData,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SimulationF>,
+    {
+        let value = value_gen().ok().map(|v| field_to_biguint(v.borrow()));
+        let limbs = Self::alloc_limbs(cs, value.as_ref(), SimulationF::size_in_bits(), true)?;
+        Ok(Self {
+            limbs,
+            _simulation: PhantomData,
+        })
+    }
+}
+
+impl<SimulationF: PrimeField, ConstraintF: PrimeField> ConstantGadget<SimulationF, ConstraintF>
+    for NonNativeFieldGadget<SimulationF, ConstraintF>
+{
+    fn from_value<CS: ConstraintSystem<ConstraintF>>(mut cs: CS, value: &SimulationF) -> Self {
+        let limbs = Self::limb_values(&field_to_biguint(value), Self::num_limbs())
+            .iter()
+            .enumerate()
+            .map(|(i, limb)| FpGadget::from_value(cs.ns(|| format!("limb {}", i)), &biguint_to_field(limb)))
+            .collect();
+        Self {
+            limbs,
+            _simulation: PhantomData,
+        }
+    }
+
+    fn get_constant(&self) -> SimulationF {
+        self.get_value().unwrap()
+    }
+}
+
+impl<SimulationF: PrimeField, ConstraintF: PrimeField> CondSelectGadget<ConstraintF>
+    for NonNativeFieldGadget<SimulationF, ConstraintF>
+{
+    fn conditionally_select<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        cond: &Boolean,
+        first: &Self,
+        second: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let mut limbs = Vec::with_capacity(first.limbs.len());
+        for (i, (first_i, second_i)) in first.limbs.iter().zip(second.limbs.iter()).enumerate() {
+            limbs.push(FpGadget::conditionally_select(
+                cs.ns(|| format!("select limb {}", i)),
+                cond,
+                first_i,
+                second_i,
+            )?);
+        }
+        Ok(Self {
+            limbs,
+            _simulation: PhantomData,
+        })
+    }
+
+    fn cost() -> usize {
+        Self::num_limbs() * <FpGadget<ConstraintF> as CondSelectGadget<ConstraintF>>::cost()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_constraint_system::TestConstraintSystem;
+    use algebra::fields::{bls12_381::Fr as BLSFr, mnt4753::Fr as MNT4Fr, secp256k1::Fq as SecpFq};
+    use rand::thread_rng;
+
+    fn nonnative_field_test<SimulationF: PrimeField, ConstraintF: PrimeField>() {
+        let rng = &mut thread_rng();
+        let mut cs = TestConstraintSystem::<ConstraintF>::new();
+
+        let a_native = SimulationF::rand(rng);
+        let b_native = SimulationF::rand(rng);
+        let a = NonNativeFieldGadget::<SimulationF, ConstraintF>::alloc(cs.ns(|| "alloc a"), || Ok(a_native)).unwrap();
+        let b = NonNativeFieldGadget::<SimulationF, ConstraintF>::alloc(cs.ns(|| "alloc b"), || Ok(b_native)).unwrap();
+
+        let sum = a.add(cs.ns(|| "a + b"), &b).unwrap();
+        assert_eq!(sum.get_value().unwrap(), a_native + &b_native);
+        let difference = a.sub(cs.ns(|| "a - b"), &b).unwrap();
+        assert_eq!(difference.get_value().unwrap(), a_native - &b_native);
+        let negation = a.negate(cs.ns(|| "-a")).unwrap();
+        assert_eq!(negation.get_value().unwrap(), -a_native);
+        let product = a.mul(cs.ns(|| "a * b"), &b).unwrap();
+        assert_eq!(product.get_value().unwrap(), a_native * &b_native);
+        let inverse = a.inverse(cs.ns(|| "a^-1")).unwrap();
+        assert_eq!(inverse.get_value().unwrap(), a_native.inverse().unwrap());
+        a.mul_equals(cs.ns(|| "a * b = product"), &b, &product).unwrap();
+
+        // (a + b) - b = a
+        let a_again = sum.sub(cs.ns(|| "(a + b) - b"), &b).unwrap();
+        a_again.enforce_equal(cs.ns(|| "(a + b) - b = a"), &a).unwrap();
+
+        // The bits of a represent a
+        let bits = a.to_bits_strict(cs.ns(|| "a to bits")).unwrap();
+        assert_eq!(bits.iter().map(|b| b.get_value().unwrap()).collect::<Vec<_>>(), a_native.write_bits());
+        let a_from_bits = NonNativeFieldGadget::<SimulationF, ConstraintF>::from_bits(cs.ns(|| "a from bits"), &bits).unwrap();
+        assert_eq!(a_from_bits.get_value().unwrap(), a_native);
+
+        let cond = Boolean::constant(false);
+        let selected = NonNativeFieldGadget::conditionally_select(cs.ns(|| "select b"), &cond, &a, &b).unwrap();
+        assert_eq!(selected.get_value().unwrap(), b_native);
+        assert!(cs.is_satisfied());
+
+        // a + b = a is unsatisfiable for b != 0
+        sum.enforce_equal(cs.ns(|| "a + b = a"), &a).unwrap();
+        assert!(!cs.is_satisfied());
+    }
+
+    fn lazy_reduction_test<SimulationF: PrimeField, ConstraintF: PrimeField>() {
+        let rng = &mut thread_rng();
+        let max_surfeit = NonNativeFieldMulResultGadget::<SimulationF, ConstraintF>::max_surfeit();
+
+        for &len in [1, 5, max_surfeit + 2].iter() {
+            let mut cs = TestConstraintSystem::<ConstraintF>::new();
+            let a_native = (0..len).map(|_| SimulationF::rand(rng)).collect::<Vec<_>>();
+            let b_native = (0..len).map(|_| SimulationF::rand(rng)).collect::<Vec<_>>();
+            let alloc = |cs: &mut TestConstraintSystem<ConstraintF>, name: &str, v: &[SimulationF]| {
+                v.iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        NonNativeFieldGadget::<SimulationF, ConstraintF>::alloc(
+                            cs.ns(|| format!("alloc {}_{}", name, i)),
+                            || Ok(*v),
+                        )
+                        .unwrap()
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let a = alloc(&mut cs, "a", &a_native);
+            let b = alloc(&mut cs, "b", &b_native);
+
+            let expected = a_native
+                .iter()
+                .zip(b_native.iter())
+                .fold(SimulationF::zero(), |acc, (a_i, b_i)| acc + &(*a_i * b_i));
+            let num_constraints = cs.num_constraints();
+            let sum = NonNativeFieldGadget::sum_of_products(cs.ns(|| "sum of products"), &a, &b)
+                .unwrap();
+            assert_eq!(sum.get_value().unwrap(), expected);
+            let lazy_cost = cs.num_constraints() - num_constraints;
+            assert!(cs.is_satisfied());
+
+            // Reducing every product is more expensive
+            if len > 1 {
+                let num_constraints = cs.num_constraints();
+                let mut eager = a[0].mul(cs.ns(|| "eager a_0 * b_0"), &b[0]).unwrap();
+                for i in 1..len {
+                    let product = a[i].mul(cs.ns(|| format!("eager a_{} * b_{}", i, i)), &b[i]).unwrap();
+                    eager = eager.add(cs.ns(|| format!("eager add {}", i)), &product).unwrap();
+                }
+                assert_eq!(eager.get_value().unwrap(), expected);
+                assert!(lazy_cost < cs.num_constraints() - num_constraints);
+            }
+
+            // A wrong result is detected
+            let wrong = NonNativeFieldGadget::<SimulationF, ConstraintF>::alloc(
+                cs.ns(|| "alloc wrong"),
+                || Ok(expected + &SimulationF::one()),
+            )
+            .unwrap();
+            sum.enforce_equal(cs.ns(|| "sum = wrong"), &wrong).unwrap();
+            assert!(!cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn lazy_reduction_secp256k1_over_bls12_381_test() {
+        lazy_reduction_test::<SecpFq, BLSFr>();
+    }
+
+    #[test]
+    fn secp256k1_over_bls12_381_test() {
+        nonnative_field_test::<SecpFq, BLSFr>();
+    }
+
+    #[test]
+    fn secp256k1_over_mnt4753_test() {
+        nonnative_field_test::<SecpFq, MNT4Fr>();
+    }
+}
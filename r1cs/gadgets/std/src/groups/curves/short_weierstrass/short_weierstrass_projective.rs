@@ -1,7 +1,7 @@
 use algebra::{
     curves::short_weierstrass_projective::{GroupAffine as SWAffine, GroupProjective as SWProjective},
     SWModelParameters,
-    AffineCurve, BitIterator, Field, PrimeField, ProjectiveCurve};
+    AffineCurve, BitIterator, Field, PrimeField, ProjectiveCurve, SquareRootField};
 use r1cs_core::{ConstraintSystem, SynthesisError};
 use std::{borrow::Borrow, marker::PhantomData, ops::Neg};
 
@@ -980,9 +980,42 @@ impl<ConstraintF> CompressAffinePointGadget<ConstraintF>
     }
 }
 
-use crate::ToCompressedBitsGadget;
+use crate::{ToCompressedBitsGadget, FromCompressedBitsGadget};
 use crate::fields::fp::FpGadget;
 
+/// Enforce the compression of the point (x, y, infinity) as done natively by
+/// `ToCompressedBits`: the bits of the x coordinate, which is taken as zero for
+/// the point at infinity, followed by the infinity flag and the parity of the
+/// y coordinate, which is unset for the point at infinity.
+fn compress_point<ConstraintF, CS>(
+    mut cs: CS,
+    x: &FpGadget<ConstraintF>,
+    y: &FpGadget<ConstraintF>,
+    infinity: &Boolean,
+) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        ConstraintF: PrimeField,
+        CS: ConstraintSystem<ConstraintF>,
+{
+    let zero = FpGadget::<ConstraintF>::zero(cs.ns(|| "zero"))?;
+    let x = FpGadget::<ConstraintF>::conditionally_select(
+        cs.ns(|| "x or zero at infinity"),
+        infinity,
+        &zero,
+        x,
+    )?;
+
+    //Enforce x_coordinate to bits
+    let mut compressed_bits = x.to_bits_strict(cs.ns(|| "x_to_bits_strict"))?;
+    compressed_bits.push(*infinity);
+
+    let is_odd = y.is_odd(cs.ns(|| "y parity"))?;
+    let parity = Boolean::and(cs.ns(|| "y parity unless infinity"), &infinity.not(), &is_odd)?;
+    compressed_bits.push(parity);
+
+    Ok(compressed_bits)
+}
+
 impl<ConstraintF> ToCompressedBitsGadget<ConstraintF> for CompressAffinePointGadget<ConstraintF>
     where
         ConstraintF: PrimeField,
@@ -990,15 +1023,103 @@ impl<ConstraintF> ToCompressedBitsGadget<ConstraintF> for CompressAffinePointGad
 
     /// Enforce compression of a point through serialization of the x coordinate and storing
     /// a sign bit for the y coordinate.
-    fn to_compressed<CS: ConstraintSystem<ConstraintF>>(&self, mut cs: CS)
+    fn to_compressed<CS: ConstraintSystem<ConstraintF>>(&self, cs: CS)
                                                         -> Result<Vec<Boolean>, SynthesisError> {
-        //Enforce x_coordinate to bytes
-        let mut compressed_bits = self.x.to_bits_strict(cs.ns(|| "x_to_bits_strict"))?;
-        compressed_bits.push(self.infinity);
+        compress_point(cs, &self.x, &self.y, &self.infinity)
+    }
+}
+
+impl<P, ConstraintF> ToCompressedBitsGadget<ConstraintF> for AffineGadget<P, ConstraintF, FpGadget<ConstraintF>>
+    where
+        P: SWModelParameters<BaseField = ConstraintF>,
+        ConstraintF: PrimeField + SquareRootField,
+{
+    /// Enforce compression of a point exactly as done natively by `ToCompressedBits`.
+    fn to_compressed<CS: ConstraintSystem<ConstraintF>>(&self, cs: CS)
+                                                        -> Result<Vec<Boolean>, SynthesisError> {
+        compress_point(cs, &self.x, &self.y, &self.infinity)
+    }
+}
 
-        let is_odd = self.y.is_odd(cs.ns(|| "y parity"))?;
-        compressed_bits.push(is_odd);
+impl<P, ConstraintF> FromCompressedBitsGadget<ConstraintF> for AffineGadget<P, ConstraintF, FpGadget<ConstraintF>>
+    where
+        P: SWModelParameters<BaseField = ConstraintF>,
+        ConstraintF: PrimeField + SquareRootField,
+{
+    /// Enforce decompression of a point as done natively by `FromCompressedBits`:
+    /// the x coordinate must be smaller than the modulus, the point at infinity
+    /// must come with zero x coordinate and unset parity, and any other point
+    /// must be on the curve, with y coordinate of the given parity. Membership
+    /// to the prime order subgroup is implied by being on the curve, as only
+    /// curves of prime order are supported: panics if `P::COFACTOR` is not one,
+    /// as a prover could otherwise decompress a point of small order.
+    fn from_compressed<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        compressed: &[Boolean],
+    ) -> Result<Self, SynthesisError> {
+        assert!(
+            P::COFACTOR == [1],
+            "in-circuit decompression is only supported for curves of prime order"
+        );
+        let num_bits = ConstraintF::size_in_bits();
+        assert_eq!(compressed.len(), num_bits + 2);
+        let x_bits = &compressed[..num_bits];
+        let infinity = compressed[num_bits];
+        let parity = compressed[num_bits + 1];
+
+        // Pack the big-endian bits of x, enforcing them to be in the field.
+        Boolean::enforce_in_field::<_, _, ConstraintF>(cs.ns(|| "x in field"), x_bits)?;
+        let mut x = FpGadget::<ConstraintF>::zero(cs.ns(|| "zero"))?;
+        let mut coeff = ConstraintF::one();
+        for (i, bit) in x_bits.iter().rev().enumerate() {
+            x = x.conditionally_add_constant(cs.ns(|| format!("pack bit {}", i)), bit, coeff)?;
+            coeff.double_in_place();
+        }
 
-        Ok(compressed_bits)
+        // Witness the y coordinate of the given parity. If x^3 + ax + b is not a
+        // square, y is set to zero, failing the on curve check below.
+        let y = FpGadget::<ConstraintF>::alloc(cs.ns(|| "alloc y"), || {
+            if infinity.get_value().get()? {
+                return Ok(ConstraintF::one());
+            }
+            let x = x.get_value().get()?;
+            let parity = parity.get_value().get()?;
+            let x3_plus_ax_plus_b = P::add_b(&((x.square() * &x) + &P::mul_by_a(&x)));
+            Ok(x3_plus_ax_plus_b.sqrt().map_or(ConstraintF::zero(), |y| {
+                if y.is_odd() ^ parity { -y } else { y }
+            }))
+        })?;
+
+        // Unless the point is at infinity, check that y^2 - b = x * (x^2 + a),
+        // and the parity of y.
+        let not_infinity = infinity.not();
+        let x2 = x.square(cs.ns(|| "x^2"))?;
+        let y2 = y.square(cs.ns(|| "y^2"))?;
+        let x2_plus_a = x2.add_constant(cs.ns(|| "x^2 + a"), &P::COEFF_A)?;
+        let y2_minus_b = y2.add_constant(cs.ns(|| "y^2 - b"), &P::COEFF_B.neg())?;
+        let x3_plus_ax = x2_plus_a.mul(cs.ns(|| "x^3 + ax"), &x)?;
+        x3_plus_ax.conditional_enforce_equal(
+            cs.ns(|| "on curve check"),
+            &y2_minus_b,
+            &not_infinity,
+        )?;
+        let is_odd = y.is_odd(cs.ns(|| "y parity"))?;
+        is_odd.conditional_enforce_equal(cs.ns(|| "parity check"), &parity, &not_infinity)?;
+
+        // The point at infinity is compressed with zero x coordinate and parity,
+        // and is represented with y = 1.
+        let zero = FpGadget::<ConstraintF>::zero(cs.ns(|| "zero x"))?;
+        x.conditional_enforce_equal(cs.ns(|| "x zero at infinity"), &zero, &infinity)?;
+        Boolean::and(cs.ns(|| "parity and infinity"), &infinity, &parity)?
+            .enforce_equal(cs.ns(|| "parity unset at infinity"), &Boolean::constant(false))?;
+        let one = FpGadget::<ConstraintF>::one(cs.ns(|| "one"))?;
+        let y = FpGadget::<ConstraintF>::conditionally_select(
+            cs.ns(|| "y or one at infinity"),
+            &infinity,
+            &one,
+            &y,
+        )?;
+
+        Ok(Self::new(x, y, infinity))
     }
-}
\ No newline at end of file
+}
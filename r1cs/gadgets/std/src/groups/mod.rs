@@ -182,11 +182,42 @@ mod test {
     use algebra::{Field, ProjectiveCurve, ToCompressedBits};
     use r1cs_core::ConstraintSystem;
 
-    use crate::{prelude::*, test_constraint_system::TestConstraintSystem, ToCompressedBitsGadget};
+    use crate::{
+        prelude::*, test_constraint_system::TestConstraintSystem,
+        ToCompressedBitsGadget, FromCompressedBitsGadget,
+    };
     use algebra::groups::Group;
     use rand;
     use crate::groups::curves::short_weierstrass::short_weierstrass_projective::CompressAffinePointGadget;
 
+    fn compression_test<
+        ConstraintF: Field,
+        G: ProjectiveCurve,
+        GG: GroupGadget<G, ConstraintF, Value = G> + ToCompressedBitsGadget<ConstraintF> + FromCompressedBitsGadget<ConstraintF>,
+        CS: ConstraintSystem<ConstraintF>,
+    >(
+        cs: &mut CS,
+        p: G,
+    )
+        where G::Affine: ToCompressedBits,
+    {
+        let p_compressed = p.into_affine().compress();
+        let p_compressed_gadget = p_compressed.iter().enumerate().map(|(i, bit)|
+            Boolean::alloc(cs.ns(|| format!("alloc bit {}", i)), || Ok(*bit)).unwrap()
+        ).collect::<Vec<_>>();
+
+        // Decompression agrees with the native one
+        let p_gadget = GG::from_compressed(cs.ns(|| "decompress p"), &p_compressed_gadget).unwrap();
+        assert_eq!(p_gadget.get_value().unwrap(), p);
+
+        // Compression agrees with the native one
+        let p_recompressed = p_gadget.to_compressed(cs.ns(|| "compress p")).unwrap();
+        assert_eq!(
+            p_recompressed.iter().map(|b| b.get_value().unwrap()).collect::<Vec<_>>(),
+            p_compressed
+        );
+    }
+
     pub(crate) fn group_test<
         ConstraintF: Field,
         G: Group,
@@ -336,6 +367,14 @@ mod test {
         }
         assert_eq!(p1_compressed_by_gadget_conv, p1_compressed);
 
+        //Test decompression, also of the point at infinity
+        let p2: MNT4G1Projective = rand::random();
+        compression_test::<_, _, MNT4G1Gadget, _>(&mut cs.ns(|| "compression test p2"), p2);
+        compression_test::<_, _, MNT4G1Gadget, _>(
+            &mut cs.ns(|| "compression test zero"),
+            <MNT4G1Projective as ProjectiveCurve>::zero()
+        );
+        assert!(cs.is_satisfied());
     }
 
     #[test]
@@ -367,5 +406,13 @@ mod test {
         }
         assert_eq!(p1_compressed_by_gadget_conv, p1_compressed);
 
+        //Test decompression, also of the point at infinity
+        let p2: MNT6G1Projective = rand::random();
+        compression_test::<_, _, MNT6G1Gadget, _>(&mut cs.ns(|| "compression test p2"), p2);
+        compression_test::<_, _, MNT6G1Gadget, _>(
+            &mut cs.ns(|| "compression test zero"),
+            <MNT6G1Projective as ProjectiveCurve>::zero()
+        );
+        assert!(cs.is_satisfied());
     }
 }
\ No newline at end of file
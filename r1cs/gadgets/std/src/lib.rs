@@ -50,14 +50,16 @@ pub mod groups;
 pub mod pairing;
 
 pub mod alloc;
+pub mod builder;
 pub mod eq;
+pub mod plugin;
 pub mod select;
 pub mod to_field_gadget_vec;
 
 pub mod prelude {
     pub use crate::{
         alloc::*,
-        bits::{boolean::Boolean, uint32::UInt32, uint8::UInt8, ToBitsGadget, FromBitsGadget, ToBytesGadget},
+        bits::{boolean::Boolean, uint32::UInt32, uint64::UInt64, uint8::UInt8, ToBitsGadget, FromBitsGadget, ToBytesGadget},
         eq::*,
         fields::FieldGadget,
         groups::GroupGadget,
@@ -0,0 +1,452 @@
+//! A plugin interface for gadget libraries and curve parameter sets provided by
+//! third-party crates, so that a prover can be extended without forking the
+//! library.
+//!
+//! A plugin implements `GadgetLibrary`, whose gadgets act on a constraint system
+//! through the object safe `DynConstraintSystem`, and is registered at startup in
+//! a `PluginRegistry`, where the prover looks gadgets and curves up by name. The
+//! interface is versioned by `PLUGIN_INTERFACE_VERSION`: libraries built against
+//! another version of the interface are refused at registration. Since the
+//! interface is made of Rust trait objects, plugins must be built with the same
+//! compiler as the prover.
+use algebra::{
+    bytes::ToBytes,
+    capabilities,
+    curves::models::{SWModelParameters, TEModelParameters},
+    Field, PrimeField,
+};
+use r1cs_core::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
+
+use crate::fields::fp::FpGadget;
+
+use std::{error::Error, fmt};
+
+/// The version of the plugin interface, bumped on any change of the traits of
+/// this module or of the types they use.
+pub const PLUGIN_INTERFACE_VERSION: u32 = 1;
+
+/// An object safe version of `ConstraintSystem`, implemented by all constraint
+/// systems. The closures are boxed instead of generic, and the namespaces are
+/// pushed and popped explicitly.
+pub trait DynConstraintSystem<F: Field> {
+    fn alloc(
+        &mut self,
+        annotation: Box<dyn FnOnce() -> String + '_>,
+        f: Box<dyn FnOnce() -> Result<F, SynthesisError> + '_>,
+    ) -> Result<Variable, SynthesisError>;
+
+    fn alloc_input(
+        &mut self,
+        annotation: Box<dyn FnOnce() -> String + '_>,
+        f: Box<dyn FnOnce() -> Result<F, SynthesisError> + '_>,
+    ) -> Result<Variable, SynthesisError>;
+
+    fn enforce(
+        &mut self,
+        annotation: Box<dyn FnOnce() -> String + '_>,
+        a: LinearCombination<F>,
+        b: LinearCombination<F>,
+        c: LinearCombination<F>,
+    );
+
+    fn push_namespace(&mut self, name: Box<dyn FnOnce() -> String + '_>);
+
+    fn pop_namespace(&mut self);
+
+    fn num_constraints(&self) -> usize;
+}
+
+impl<F: Field, CS: ConstraintSystem<F>> DynConstraintSystem<F> for CS {
+    fn alloc(
+        &mut self,
+        annotation: Box<dyn FnOnce() -> String + '_>,
+        f: Box<dyn FnOnce() -> Result<F, SynthesisError> + '_>,
+    ) -> Result<Variable, SynthesisError> {
+        ConstraintSystem::alloc(self, annotation, f)
+    }
+
+    fn alloc_input(
+        &mut self,
+        annotation: Box<dyn FnOnce() -> String + '_>,
+        f: Box<dyn FnOnce() -> Result<F, SynthesisError> + '_>,
+    ) -> Result<Variable, SynthesisError> {
+        ConstraintSystem::alloc_input(self, annotation, f)
+    }
+
+    fn enforce(
+        &mut self,
+        annotation: Box<dyn FnOnce() -> String + '_>,
+        a: LinearCombination<F>,
+        b: LinearCombination<F>,
+        c: LinearCombination<F>,
+    ) {
+        ConstraintSystem::enforce(self, annotation, |_| a, |_| b, |_| c)
+    }
+
+    fn push_namespace(&mut self, name: Box<dyn FnOnce() -> String + '_>) {
+        ConstraintSystem::push_namespace(self.get_root(), name)
+    }
+
+    fn pop_namespace(&mut self) {
+        ConstraintSystem::pop_namespace(self.get_root())
+    }
+
+    fn num_constraints(&self) -> usize {
+        ConstraintSystem::num_constraints(self)
+    }
+}
+
+/// A `ConstraintSystem` forwarding to a `DynConstraintSystem`, on which plugins
+/// use the gadgets of this crate.
+pub struct PluginConstraintSystem<'a, F: Field> {
+    cs: &'a mut dyn DynConstraintSystem<F>,
+}
+
+impl<'a, F: Field> PluginConstraintSystem<'a, F> {
+    pub fn new(cs: &'a mut dyn DynConstraintSystem<F>) -> Self {
+        Self { cs }
+    }
+}
+
+impl<F: Field> ConstraintSystem<F> for PluginConstraintSystem<'_, F> {
+    type Root = Self;
+
+    fn alloc<FN, A, AR>(&mut self, annotation: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.cs.alloc(Box::new(|| annotation().into()), Box::new(f))
+    }
+
+    fn alloc_input<FN, A, AR>(&mut self, annotation: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.cs.alloc_input(Box::new(|| annotation().into()), Box::new(f))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, annotation: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.cs.enforce(
+            Box::new(|| annotation().into()),
+            a(LinearCombination::zero()),
+            b(LinearCombination::zero()),
+            c(LinearCombination::zero()),
+        )
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.cs.push_namespace(Box::new(|| name_fn().into()))
+    }
+
+    fn pop_namespace(&mut self) {
+        self.cs.pop_namespace()
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.cs.num_constraints()
+    }
+}
+
+/// A gadget of a plugin, taking and returning field elements.
+pub trait Gadget<F: PrimeField>: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// The number of field elements expected by `synthesize`.
+    fn num_inputs(&self) -> usize;
+
+    /// Enforces the gadget on `inputs`, returning its outputs.
+    fn synthesize(
+        &self,
+        cs: &mut dyn DynConstraintSystem<F>,
+        inputs: &[FpGadget<F>],
+    ) -> Result<Vec<FpGadget<F>>, SynthesisError>;
+}
+
+/// The model of the equation of a curve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveModel {
+    ShortWeierstrass,
+    TwistedEdwards,
+}
+
+/// The description of a curve provided by a plugin. The coefficients and the
+/// generator are serialized with `ToBytes`, integers as little endian `u64` limbs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CurveParameterSet {
+    pub name:                 String,
+    pub model:                CurveModel,
+    pub base_field_modulus:   Vec<u64>,
+    pub scalar_field_modulus: Vec<u64>,
+    pub cofactor:             Vec<u64>,
+    /// `a` and `b` for short Weierstrass curves, `a` and `d` for twisted Edwards ones.
+    pub coefficients:         Vec<u8>,
+    pub generator:            Vec<u8>,
+}
+
+impl CurveParameterSet {
+    pub fn short_weierstrass<P: SWModelParameters>(name: &str) -> Self {
+        Self {
+            name:                 name.to_owned(),
+            model:                CurveModel::ShortWeierstrass,
+            base_field_modulus:   P::BaseField::characteristic().to_vec(),
+            scalar_field_modulus: P::ScalarField::characteristic().to_vec(),
+            cofactor:             P::COFACTOR.to_vec(),
+            coefficients:         to_bytes![P::COEFF_A, P::COEFF_B].unwrap(),
+            generator:            to_bytes![P::AFFINE_GENERATOR_COEFFS.0, P::AFFINE_GENERATOR_COEFFS.1].unwrap(),
+        }
+    }
+
+    pub fn twisted_edwards<P: TEModelParameters>(name: &str) -> Self {
+        Self {
+            name:                 name.to_owned(),
+            model:                CurveModel::TwistedEdwards,
+            base_field_modulus:   P::BaseField::characteristic().to_vec(),
+            scalar_field_modulus: P::ScalarField::characteristic().to_vec(),
+            cofactor:             P::COFACTOR.to_vec(),
+            coefficients:         to_bytes![P::COEFF_A, P::COEFF_D].unwrap(),
+            generator:            to_bytes![P::AFFINE_GENERATOR_COEFFS.0, P::AFFINE_GENERATOR_COEFFS.1].unwrap(),
+        }
+    }
+}
+
+/// A library of gadgets, and of the curves they work with.
+pub trait GadgetLibrary<F: PrimeField>: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn version(&self) -> &str;
+
+    /// The version of the plugin interface the library is built against.
+    fn interface_version(&self) -> u32 {
+        PLUGIN_INTERFACE_VERSION
+    }
+
+    fn gadgets(&self) -> Vec<&dyn Gadget<F>>;
+
+    fn curves(&self) -> Vec<CurveParameterSet> {
+        vec![]
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PluginError {
+    /// The library is built against another version of the interface.
+    IncompatibleInterface(String, u32),
+    /// A library with the same name is already registered.
+    DuplicateLibrary(String),
+    /// A curve with the same name is built in, or already registered.
+    DuplicateCurve(String),
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::IncompatibleInterface(library, version) => write!(
+                f,
+                "library {} is built against version {} of the plugin interface, expected {}",
+                library, version, PLUGIN_INTERFACE_VERSION
+            ),
+            PluginError::DuplicateLibrary(library) => write!(f, "library {} already registered", library),
+            PluginError::DuplicateCurve(curve) => write!(f, "curve {} already defined", curve),
+        }
+    }
+}
+
+impl Error for PluginError {}
+
+/// The gadget libraries registered by the plugins, and their curves.
+pub struct PluginRegistry<F: PrimeField> {
+    libraries: Vec<Box<dyn GadgetLibrary<F>>>,
+    curves:    Vec<CurveParameterSet>,
+}
+
+impl<F: PrimeField> PluginRegistry<F> {
+    pub fn new() -> Self {
+        Self { libraries: vec![], curves: vec![] }
+    }
+
+    /// Registers `library`, after checking its interface version and that neither
+    /// it nor its curves clash with the ones already known.
+    pub fn register<L: GadgetLibrary<F> + 'static>(&mut self, library: L) -> Result<(), PluginError> {
+        if library.interface_version() != PLUGIN_INTERFACE_VERSION {
+            return Err(PluginError::IncompatibleInterface(
+                library.name().to_owned(),
+                library.interface_version(),
+            ));
+        }
+        if self.library(library.name()).is_some() {
+            return Err(PluginError::DuplicateLibrary(library.name().to_owned()));
+        }
+        let curves = library.curves();
+        let built_in = capabilities::capabilities();
+        for (i, curve) in curves.iter().enumerate() {
+            if built_in.supports_curve(&curve.name)
+                || self.curve(&curve.name).is_some()
+                || curves[..i].iter().any(|c| c.name == curve.name)
+            {
+                return Err(PluginError::DuplicateCurve(curve.name.clone()));
+            }
+        }
+
+        self.curves.extend(curves);
+        self.libraries.push(Box::new(library));
+        Ok(())
+    }
+
+    /// Returns the names and the versions of the registered libraries.
+    pub fn libraries(&self) -> Vec<(&str, &str)> {
+        self.libraries.iter().map(|l| (l.name(), l.version())).collect()
+    }
+
+    pub fn library(&self, name: &str) -> Option<&dyn GadgetLibrary<F>> {
+        self.libraries.iter().find(|l| l.name() == name).map(|l| l.as_ref())
+    }
+
+    /// Returns the gadget `name` of the library `library`.
+    pub fn gadget(&self, library: &str, name: &str) -> Option<&dyn Gadget<F>> {
+        self.library(library)?.gadgets().into_iter().find(|g| g.name() == name)
+    }
+
+    pub fn curves(&self) -> &[CurveParameterSet] {
+        &self.curves
+    }
+
+    pub fn curve(&self, name: &str) -> Option<&CurveParameterSet> {
+        self.curves.iter().find(|c| c.name == name)
+    }
+}
+
+impl<F: PrimeField> Default for PluginRegistry<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{prelude::*, test_constraint_system::TestConstraintSystem};
+    use algebra::{
+        curves::{bls12_381::g1::Bls12_381G1Parameters, jubjub::JubJubParameters},
+        fields::bls12_381::Fr,
+    };
+
+    struct CubeGadget;
+
+    impl Gadget<Fr> for CubeGadget {
+        fn name(&self) -> &str {
+            "cube"
+        }
+
+        fn num_inputs(&self) -> usize {
+            1
+        }
+
+        fn synthesize(
+            &self,
+            cs: &mut dyn DynConstraintSystem<Fr>,
+            inputs: &[FpGadget<Fr>],
+        ) -> Result<Vec<FpGadget<Fr>>, SynthesisError> {
+            let mut cs = PluginConstraintSystem::new(cs);
+            let square = inputs[0].square(cs.ns(|| "square"))?;
+            Ok(vec![square.mul(cs.ns(|| "cube"), &inputs[0])?])
+        }
+    }
+
+    struct TestLibrary {
+        name:              &'static str,
+        interface_version: u32,
+        curves:            Vec<CurveParameterSet>,
+    }
+
+    impl GadgetLibrary<Fr> for TestLibrary {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn version(&self) -> &str {
+            "0.1.0"
+        }
+
+        fn interface_version(&self) -> u32 {
+            self.interface_version
+        }
+
+        fn gadgets(&self) -> Vec<&dyn Gadget<Fr>> {
+            vec![&CubeGadget]
+        }
+
+        fn curves(&self) -> Vec<CurveParameterSet> {
+            self.curves.clone()
+        }
+    }
+
+    fn library(name: &'static str, curves: Vec<CurveParameterSet>) -> TestLibrary {
+        TestLibrary { name, interface_version: PLUGIN_INTERFACE_VERSION, curves }
+    }
+
+    #[test]
+    fn plugin_gadget() {
+        let mut registry = PluginRegistry::new();
+        registry.register(library("powers", vec![])).unwrap();
+        assert_eq!(registry.libraries(), vec![("powers", "0.1.0")]);
+        assert!(registry.gadget("powers", "square").is_none());
+        assert!(registry.gadget("roots", "cube").is_none());
+
+        let gadget = registry.gadget("powers", "cube").unwrap();
+        assert_eq!(gadget.num_inputs(), 1);
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let x = FpGadget::alloc(cs.ns(|| "x"), || Ok(Fr::from(3u64))).unwrap();
+        let outputs = {
+            let mut cs = cs.ns(|| "plugin");
+            gadget.synthesize(&mut cs, &[x]).unwrap()
+        };
+        assert_eq!(outputs[0].get_value(), Some(Fr::from(27u64)));
+        assert_eq!(cs.num_constraints(), 2);
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn plugin_registration() {
+        let mut registry = PluginRegistry::<Fr>::new();
+        let curve = CurveParameterSet::twisted_edwards::<JubJubParameters>("jubjub_copy");
+        registry.register(library("curves", vec![curve.clone()])).unwrap();
+        assert_eq!(registry.curve("jubjub_copy"), Some(&curve));
+        assert_eq!(curve.model, CurveModel::TwistedEdwards);
+
+        // Names must be unique, also with respect to the built-in curves
+        assert_eq!(
+            registry.register(library("curves", vec![])),
+            Err(PluginError::DuplicateLibrary("curves".to_owned()))
+        );
+        assert!(registry.register(library("other", vec![curve])).is_err());
+        let built_in = CurveParameterSet::short_weierstrass::<Bls12_381G1Parameters>("bls12_381");
+        assert!(registry.register(library("other", vec![built_in])).is_err());
+
+        let mut outdated = library("outdated", vec![]);
+        outdated.interface_version = PLUGIN_INTERFACE_VERSION + 1;
+        assert!(registry.register(outdated).is_err());
+
+        assert_eq!(registry.libraries().len(), 1);
+        assert_eq!(registry.curves().len(), 1);
+    }
+}
@@ -2,6 +2,10 @@ use algebra::Field;
 use r1cs_core::{ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
 
 use radix_trie::Trie;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
 
 #[derive(Debug)]
 enum NamedObject {
@@ -70,15 +74,30 @@ impl<ConstraintF: Field> TestConstraintSystem<ConstraintF> {
     }
 
     pub fn which_is_unsatisfied(&self) -> Option<&str> {
-        for &(ref a, ref b, ref c, ref path) in &self.constraints {
-            let mut a = Self::eval_lc(a.as_ref(), &self.inputs, &self.aux);
+        self.first_unsatisfied()
+            .map(|unsatisfied| &*self.constraints[unsatisfied.index].3)
+    }
+
+    /// Returns the first constraint violated by the assignment, with the values
+    /// of its linear combinations.
+    pub fn first_unsatisfied(&self) -> Option<UnsatisfiedConstraint<ConstraintF>> {
+        for (index, &(ref a, ref b, ref c, ref path)) in self.constraints.iter().enumerate() {
+            let a = Self::eval_lc(a.as_ref(), &self.inputs, &self.aux);
             let b = Self::eval_lc(b.as_ref(), &self.inputs, &self.aux);
             let c = Self::eval_lc(c.as_ref(), &self.inputs, &self.aux);
 
-            a.mul_assign(&b);
-
-            if a != c {
-                return Some(&*path);
+            let mut lhs = a;
+            lhs.mul_assign(&b);
+
+            if lhs != c {
+                return Some(UnsatisfiedConstraint {
+                    index,
+                    path: path.clone(),
+                    a,
+                    b,
+                    c,
+                    lhs,
+                });
             }
         }
 
@@ -89,10 +108,44 @@ impl<ConstraintF: Field> TestConstraintSystem<ConstraintF> {
         self.which_is_unsatisfied().is_none()
     }
 
+    /// Same as `is_satisfied`, but returns the first violated constraint on
+    /// failure, so that `cs.check_satisfied().unwrap()` reports it in tests.
+    pub fn check_satisfied(&self) -> Result<(), UnsatisfiedConstraint<ConstraintF>> {
+        match self.first_unsatisfied() {
+            Some(unsatisfied) => Err(unsatisfied),
+            None => Ok(()),
+        }
+    }
+
     pub fn num_constraints(&self) -> usize {
         self.constraints.len()
     }
 
+    /// Returns the number of constraints, variables and non-zero entries of the
+    /// system, in total and per namespace.
+    pub fn report(&self) -> ConstraintSystemReport {
+        let mut report = ConstraintSystemReport::default();
+        report.num_variables = self.inputs.len() + self.aux.len();
+
+        for (_, path) in self.inputs.iter() {
+            report.update(path, |ns| ns.num_inputs += 1);
+        }
+        for (_, path) in self.aux.iter() {
+            report.update(path, |ns| ns.num_aux += 1);
+        }
+        for (a, b, c, path) in self.constraints.iter() {
+            let non_zeros = [a.as_ref().len(), b.as_ref().len(), c.as_ref().len()];
+            report.update(path, |ns| {
+                ns.num_constraints += 1;
+                for (n, m) in ns.non_zeros.iter_mut().zip(non_zeros.iter()) {
+                    *n += m;
+                }
+            });
+        }
+
+        report
+    }
+
     pub fn set(&mut self, path: &str, to: ConstraintF) {
         match self.named_objects.get(path) {
             Some(&NamedObject::Var(ref v)) => match v.get_unchecked() {
@@ -130,6 +183,142 @@ impl<ConstraintF: Field> TestConstraintSystem<ConstraintF> {
     }
 }
 
+/// A constraint `a * b = c` violated by the assignment of a constraint system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsatisfiedConstraint<ConstraintF: Field> {
+    /// The index of the constraint, in the order of synthesis.
+    pub index: usize,
+    /// The namespace path of the constraint.
+    pub path:  String,
+    /// The values of the linear combinations of the constraint.
+    pub a:     ConstraintF,
+    pub b:     ConstraintF,
+    pub c:     ConstraintF,
+    /// The left hand side `a * b`, which differs from `c`.
+    pub lhs:   ConstraintF,
+}
+
+impl<ConstraintF: Field> fmt::Display for UnsatisfiedConstraint<ConstraintF> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "constraint {} `{}` is not satisfied: a * b = {} * {} = {}, but c = {}",
+            self.index, self.path, self.a, self.b, self.lhs, self.c
+        )
+    }
+}
+
+/// The size of the part of a constraint system synthesized in a namespace,
+/// including its sub-namespaces.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NamespaceReport {
+    pub num_constraints: usize,
+    pub num_inputs:      usize,
+    pub num_aux:         usize,
+    /// The number of non-zero entries in the rows of the A, B and C matrices
+    /// of the constraints.
+    pub non_zeros:       [usize; 3],
+}
+
+impl NamespaceReport {
+    fn write_json(&self, json: &mut String) {
+        write!(
+            json,
+            "{{\"num_constraints\":{},\"num_inputs\":{},\"num_aux\":{},\"non_zeros\":[{},{},{}]}}",
+            self.num_constraints,
+            self.num_inputs,
+            self.num_aux,
+            self.non_zeros[0],
+            self.non_zeros[1],
+            self.non_zeros[2]
+        )
+        .unwrap();
+    }
+}
+
+/// A report on the size of a constraint system, see
+/// `TestConstraintSystem::report()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConstraintSystemReport {
+    /// The number of variables, including the constant one.
+    pub num_variables: usize,
+    /// The size of the whole system.
+    pub total:         NamespaceReport,
+    /// The size of each namespace, by path.
+    pub namespaces:    BTreeMap<String, NamespaceReport>,
+}
+
+impl ConstraintSystemReport {
+    /// Applies `f` to the reports of the system and of the namespaces
+    /// enclosing the object at `path`.
+    fn update<F: Fn(&mut NamespaceReport)>(&mut self, path: &str, f: F) {
+        f(&mut self.total);
+        for (i, _) in path.match_indices('/') {
+            f(self.namespaces.entry(path[..i].to_owned()).or_default());
+        }
+    }
+
+    /// Returns the fraction of non-zero entries of the A, B and C matrices.
+    pub fn densities(&self) -> [f64; 3] {
+        let size = (self.total.num_constraints * self.num_variables) as f64;
+        let mut densities = [0f64; 3];
+        if size > 0.0 {
+            for (d, n) in densities.iter_mut().zip(self.total.non_zeros.iter()) {
+                *d = *n as f64 / size;
+            }
+        }
+        densities
+    }
+
+    /// Returns the namespaces sorted by decreasing number of constraints.
+    pub fn heaviest_namespaces(&self) -> Vec<(&str, &NamespaceReport)> {
+        let mut namespaces = self
+            .namespaces
+            .iter()
+            .map(|(ns, report)| (ns.as_str(), report))
+            .collect::<Vec<_>>();
+        namespaces.sort_by(|a, b| b.1.num_constraints.cmp(&a.1.num_constraints));
+        namespaces
+    }
+
+    /// Dumps the report as a JSON object.
+    pub fn to_json(&self) -> String {
+        let densities = self.densities();
+        let mut json = String::new();
+        write!(
+            json,
+            "{{\"num_variables\":{},\"densities\":[{},{},{}],\"total\":",
+            self.num_variables, densities[0], densities[1], densities[2]
+        )
+        .unwrap();
+        self.total.write_json(&mut json);
+        json.push_str(",\"namespaces\":{");
+        for (i, (ns, report)) in self.namespaces.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write_json_string(ns, &mut json);
+            json.push(':');
+            report.write_json(&mut json);
+        }
+        json.push_str("}}");
+        json
+    }
+}
+
+fn write_json_string(s: &str, json: &mut String) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
 fn compute_path(ns: &[String], this: String) -> String {
     if this.chars().any(|a| a == '/') {
         panic!("'/' is not allowed in names");
@@ -228,3 +417,58 @@ impl<ConstraintF: Field> ConstraintSystem<ConstraintF> for TestConstraintSystem<
         self.constraints.len()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::fields::bls12_381::Fr;
+
+    #[test]
+    fn report() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let one = TestConstraintSystem::<Fr>::one();
+        let x = cs.alloc_input(|| "x", || Ok(Fr::one())).unwrap();
+        {
+            let mut cs = cs.ns(|| "gadget");
+            let y = cs.alloc(|| "y", || Ok(Fr::one())).unwrap();
+            cs.enforce(|| "y = x", |lc| lc + y, |lc| lc + one, |lc| lc + x);
+            {
+                let mut cs = cs.ns(|| "inner \"quoted\"");
+                let z = cs.alloc(|| "z", || Ok(Fr::one())).unwrap();
+                cs.enforce(|| "z * z = x", |lc| lc + z, |lc| lc + z, |lc| lc + x);
+            }
+        }
+        cs.enforce(|| "x * x = x", |lc| lc + x, |lc| lc + x, |lc| lc + x);
+        assert!(cs.is_satisfied());
+
+        let report = cs.report();
+        assert_eq!(report.num_variables, 4);
+        let total = NamespaceReport {
+            num_constraints: 3,
+            num_inputs:      2,
+            num_aux:         2,
+            non_zeros:       [3, 3, 3],
+        };
+        assert_eq!(report.total, total);
+        assert_eq!(report.namespaces.len(), 2);
+        assert_eq!(report.namespaces["gadget"].num_constraints, 2);
+        assert_eq!(report.namespaces["gadget"].num_aux, 2);
+        assert_eq!(report.namespaces["gadget"].non_zeros, [2, 2, 2]);
+        assert_eq!(report.namespaces["gadget/inner \"quoted\""].num_constraints, 1);
+        assert_eq!(report.heaviest_namespaces()[0].0, "gadget");
+        assert_eq!(report.densities()[2], 3.0 / 12.0);
+
+        // Break the constraint of the inner namespace
+        cs.set("gadget/inner \"quoted\"/z", Fr::zero());
+        let unsatisfied = cs.check_satisfied().unwrap_err();
+        assert_eq!(unsatisfied.index, 1);
+        assert_eq!(unsatisfied.path, "gadget/inner \"quoted\"/z * z = x");
+        assert_eq!((unsatisfied.lhs, unsatisfied.c), (Fr::zero(), Fr::one()));
+        assert_eq!(cs.which_is_unsatisfied(), Some(unsatisfied.path.as_str()));
+        assert!(format!("{}", unsatisfied).starts_with("constraint 1 `gadget/inner"));
+
+        let json = report.to_json();
+        assert!(json.starts_with("{\"num_variables\":4,"));
+        assert!(json.contains("\"gadget/inner \\\"quoted\\\"\":{\"num_constraints\":1,"));
+    }
+}